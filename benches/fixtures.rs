@@ -1,8 +1,8 @@
-use rins::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, SimulationConfig};
+use rins::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, PricingMode, SimulationConfig};
 use rins::events::{Event, Peril, Risk};
 use rins::market::Market;
 use rins::simulation::Simulation;
-use rins::types::{Day, InsuredId, InsurerId, SubmissionId, Year};
+use rins::types::{Day, InsuredId, InsurerId, SubmissionId, Territory, Year};
 
 pub struct Scenario {
     pub n_insureds: usize,
@@ -18,8 +18,10 @@ pub const LARGE: Scenario = Scenario { n_insureds: 1000, insurer_count: 10 };
 fn default_risk() -> Risk {
     Risk {
         sum_insured: 5_000_000_000,
-        territory: "US-SE".to_string(),
+        territory: Territory(0),
         perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+        attachment: 0,
+        limit: 5_000_000_000,
     }
 }
 
@@ -73,10 +75,11 @@ pub fn build_simulation(scenario: &Scenario, seed: u64, years: u32) -> Simulatio
                 payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
             })
             .collect(),
         n_insureds: scenario.n_insureds,
-        attritional: AttritionalConfig { annual_rate: 2.0, mu: -3.0, sigma: 1.0 },
+        attritional: AttritionalConfig { annual_rate: 2.0, mu: -3.0, sigma: 1.0, contagion: 0.0, overrides: vec![] },
         catastrophe: CatConfig {
             event_classes: vec![CatEventClass {
                 label: "test".to_string(),
@@ -84,15 +87,65 @@ pub fn build_simulation(scenario: &Scenario, seed: u64, years: u32) -> Simulatio
                 pareto_scale: 0.05,
                 pareto_shape: 1.5,
                 max_damage_fraction: 1.0,
+                footprint: vec![],
             }],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         },
         quotes_per_submission: None,
         max_rol_mu: 0.0,
         max_rol_sigma: 0.0,
         disable_cats: false,
+        brokers: vec![],
+        quote_latency: None,
+        claims_development: None,
+        premium_finance: None,
+        retention: None,
+        investment: None,
+        demand: None,
+        market_intelligence: None,
+        rng: None,
+        runoff: None,
+        portfolio_transfer: None,
+        recapitalisation: None,
+        health: None,
+        exposure_snapshots: None,
+        territory_limits: None,
+        policy_terms: None,
+        inflation: None,
+        ils: None,
+        partial_lines: None,
+        portfolio: None,
+        aggregate_deductible: None,
+        solvency: None,
+        regulator: None,
+        loyalty: None,
+        expenses: None,
+        payment_terms: None,
+        pricing_mode: PricingMode::Adaptive,
+        scenario: None,
+        entrant_profiles: None,
+        entry_capacity: None,
+        brokerage: None,
+        mitigation: None,
+        rate_index: None,
+        pml_reporting: None,
+        large_loss: None,
+        reinsurance: None,
+        exposure_growth: None,
+        rationing: None,
+        rollover: None,
+        interest_rates: None,
+        casualty: None,
+        growth_targets: None,
+        quote_comparison: false,
+        risk_views: None,
+        capital_ledger: false,
+        quarterly_reporting: false,
     };
-    let mut sim = Simulation::from_config(config);
+    let mut sim = Simulation::from_config(config).unwrap();
     sim.start();
     sim
 }
@@ -4,9 +4,9 @@ use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
 use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
-use rins::events::{Event, Peril, SimEvent};
+use rins::events::{Event, Peril, QueuedEvent};
 use rins::market::Market;
-use rins::types::{Day, InsurerId, Year};
+use rins::types::{Day, InsurerId, Territory, Year};
 
 use fixtures::{LARGE, MEDIUM, SMALL, build_simulation, prepopulate_policies};
 
@@ -30,7 +30,7 @@ fn bench_loss_distribution(c: &mut Criterion) {
                         market.on_loss_event(
                             Day(180),
                             Peril::WindstormAtlantic,
-                            "US-SE",
+                            Territory(0),
                             0.10,
                         )
                     },
@@ -100,10 +100,7 @@ fn bench_event_queue(c: &mut Criterion) {
                         (0..n)
                             .map(|i| {
                                 let day = if i % 2 == 0 { i as u64 } else { (n - i) as u64 };
-                                Reverse(SimEvent {
-                                    day: Day(day),
-                                    event: Event::YearEnd { year: Year(1) },
-                                })
+                                Reverse(QueuedEvent::new(Day(day), Event::YearEnd { year: Year(1) }, i as u64))
                             })
                             .collect::<Vec<_>>()
                     },
@@ -146,6 +143,35 @@ fn bench_insurer_lookup(c: &mut Criterion) {
     group.finish();
 }
 
+// ── Group 6: occurrence_routing — parallel per-policy claims ────────────────
+
+/// `Market::route_occurrence` for one cat occurrence striking every insured, on a one-thread
+/// pool (the serial path's cost) and on the global pool.
+fn bench_occurrence_routing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("occurrence_routing");
+    group.sample_size(20);
+    let serial = rayon::ThreadPoolBuilder::new().num_threads(1).build().expect("one-thread pool");
+    for &policy_count in &[10_000usize, 100_000] {
+        let mut market = Market::new();
+        prepopulate_policies(&mut market, policy_count);
+        let damages = market.on_loss_event(Day(180), Peril::WindstormAtlantic, Territory(0), 0.10);
+        group.throughput(Throughput::Elements(policy_count as u64));
+        for (name, pool) in [("serial", Some(&serial)), ("parallel", None)] {
+            group.bench_with_input(BenchmarkId::new(name, policy_count), &damages, |b, damages| {
+                b.iter_batched(
+                    || market.clone(),
+                    |mut market| match pool {
+                        Some(pool) => pool.install(|| market.route_occurrence(1, damages)),
+                        None => market.route_occurrence(1, damages),
+                    },
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_loss_distribution,
@@ -153,5 +179,6 @@ criterion_group!(
     bench_multi_year,
     bench_event_queue,
     bench_insurer_lookup,
+    bench_occurrence_routing,
 );
 criterion_main!(benches);
@@ -0,0 +1,18 @@
+//! Embeds the git commit the crate was built from as `RINS_GIT_HASH`, for the `RunMetadata`
+//! header of event logs. Builds outside a git checkout leave it unset.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok());
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=RINS_GIT_HASH={}", hash.trim());
+    }
+}
@@ -1,46 +1,118 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::Serialize;
 
 use crate::{
-    events::{Event, Peril, SimEvent},
-    types::{InsuredId, InsurerId, PolicyId, SubmissionId},
+    config::InflationConfig,
+    events::{DeclineReason, Event, Line, Peril, RegulatoryAction, RejectionReason, SimEvent},
+    market::FULL_LINE_BPS,
+    types::{BrokerId, Day, InsuredId, InsurerId, PolicyId, SubmissionId, Territory, Year},
 };
 
 /// Per-year aggregate statistics derived from the event stream.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct YearStats {
     pub year: u32,
     /// Sum of PolicyBound.premium in the year (cents).
     pub bound_premium: u64,
     /// Sum of PolicyBound.sum_insured in the year (cents).
     pub sum_insured: u64,
-    /// Sum of ClaimSettled.amount in the year (cents).
+    /// Sum of ClaimSettled.amount in the year (cents). Under claims development this is
+    /// paid basis: ClaimPaid instalments are counted in the year they are paid.
     pub claims: u64,
+    /// Sum of ReserveEstablished.reserve in the year (cents). Zero without claims development.
+    pub reserves_established: u64,
+    /// Count of PolicyCancelled events in the year (premium default, or a multi-year anniversary
+    /// cancellation). Zero without premium finance or multi-year terms.
+    pub cancelled_count: u32,
+    /// Sum of PolicyCancelled.premium_reversed in the year (cents): written premium never earned.
+    pub premium_reversed: u64,
+    /// Sum of PolicyCancelled.bad_debt in the year (cents): earned premium never collected.
+    pub bad_debt: u64,
+    /// Market-wide investment return drawn for the year (0.0 without an investment process).
+    pub investment_return: f64,
+    /// Sum of InvestmentIncome.amount across insurers in the year (cents; negative in a crash).
+    pub investment_income: i64,
+    /// Short rate published for the year (0.0 without interest rates).
+    pub interest_rate: f64,
+    /// Insurers whose `UnderwritingPlanSet` for the year carries an undercut: growth seekers
+    /// that missed last year's premium target and are pricing below their own view.
+    pub undercutting_insurers: u32,
     /// Sum of InsuredLoss.ground_up_loss where peril = Attritional (cents).
     pub attr_gul: u64,
     /// Sum of InsuredLoss.ground_up_loss where peril = WindstormAtlantic (cents).
     pub cat_gul: u64,
+    /// Sum of InsuredLoss.ground_up_loss where peril = LargeFire (cents).
+    pub large_gul: u64,
+    /// Sum of InsuredLoss.ground_up_loss where peril = Casualty, by accident year (cents).
+    pub casualty_gul: u64,
+    /// Casualty claims reported in the year, from any accident year (cents): the ultimates in
+    /// `ClaimReported`. Lags `casualty_gul` by the reporting pattern.
+    pub casualty_reported: u64,
+    /// Subset of `bound_premium` (and anniversary instalments) written on the casualty line
+    /// (cents).
+    pub casualty_premium: u64,
     /// Sum of last-known remaining_capital per insurer at year-end (cents).
     pub total_capital: u64,
     /// Count of InsurerInsolvent events in the year.
     pub insolvent_count: u32,
+    /// The insurers behind `insolvent_count`, in event order.
+    pub insolvent_insurers: Vec<InsurerId>,
     /// Count of SubmissionDropped events in the year (supply-side: all insurers declined).
     pub dropped_count: u32,
     /// Count of QuoteRejected events in the year (demand-side: insured's reservation price breached).
     pub rejected_count: u32,
-    /// Sum of unique-insured sum_insured from CoverageRequested in the year (cents).
+    /// Subset of `rejected_count` declined by elastic demand (`RejectionReason::PriceTooHigh`).
+    pub price_declined_count: u32,
+    /// Quote responses reported in brokers' MarketIntelligence for the year (zero unless published).
+    pub quote_responses: u32,
+    /// Of which declines (MarketIntelligence.decline_rate × quote_responses, summed over brokers).
+    pub quote_declines: u32,
+    /// Sum of unique-insured sum_insured from property CoverageRequested in the year (cents);
+    /// casualty limits are not assets.
     pub total_assets: u64,
     /// Count of WindstormAtlantic LossEvent firings in the year.
     pub cat_event_count: u32,
     /// Count of InsurerEntered events in the year.
     pub entrant_count: u32,
-    /// Active insurer count at year-end (after entries and insolvencies).
+    /// Count of InsurerExited events in the year (voluntary run-off).
+    pub exit_count: u32,
+    /// Count of InsurerReEntered events in the year.
+    pub reentry_count: u32,
+    /// Sum of CapitalRaised.amount in the year (gross, cents).
+    pub capital_raised: u64,
+    /// Sum of CatBondIssued.premium in the year (cents).
+    pub cat_bond_premium: u64,
+    /// Sum of CatBondTriggered.payout in the year (cents).
+    pub cat_bond_recoveries: u64,
+    /// Sum of ReinsurancePurchased.premium in the year (cents).
+    pub reinsurance_premium: u64,
+    /// Sum of ReinsuranceRecovered.amount in the year (cents).
+    pub reinsurance_recoveries: u64,
+    /// Cat XoL rate on line the year's treaties were bought at (0.0 if none were bought).
+    pub reinsurance_rate_on_line: f64,
+    /// Count of PortfolioTransferred events in the year.
+    pub transfer_count: u32,
+    /// Sum of PortfolioTransferred.price in the year (cents; negative when sellers paid buyers).
+    pub transfer_price: i64,
+    /// Active insurer count at year-end (after entries, exits, re-entries and insolvencies).
     pub insurer_count: u32,
+    /// Mix-adjusted rate index from RateIndexPublished (first published year = 100; 0.0 unless
+    /// published).
+    pub rate_index: f64,
     /// AP/TP ratio in effect at the start of this year (computed from prior-year trailing CRs).
     /// 1.0 = neutral; < 1.0 = soft market; > 1.0 = hard market.
     pub ap_tp_factor: f64,
     /// Gini coefficient of bound-policy count across active insurers in this year.
     /// 0.0 = perfectly equal share; 1.0 = one insurer writes everything.
     pub gini_market_share: f64,
+    /// Herfindahl–Hirschman index of bound line share across this year's writers: Σ share².
+    /// 1/n for n equal writers; 1.0 = one insurer writes everything.
+    pub herfindahl: f64,
+    /// Fraction of this year's bound line share written by the three largest writers.
+    pub top3_share: f64,
+    /// Count of insurers on at least one PolicyBound panel this year.
+    pub active_writers: u32,
     /// Mean CR sensitivity of active (non-insolvent) insurers at year-end.
     /// Converges as selection operates over 200+ years.
     pub cr_sensitivity_mean: f64,
@@ -64,27 +136,63 @@ pub struct YearStats {
     /// Includes new binds (same as bound_premium) plus carry-overs from the prior year.
     /// Use loss_ratio_full_exposure() to compute FeLR%.
     pub full_exposure_premium: u64,
+    /// Premium earned in the year (cents): each binding or collected instalment earns pro rata
+    /// over the 360 days from its day, so a policy written mid-year earns the rest next year.
+    /// A cancelled policy stops earning on its cancellation day.
+    pub earned_premium: u64,
+    /// Written premium not yet earned at year-end (cents): the market's unearned premium reserve.
+    pub unearned_premium: u64,
 }
 
 impl YearStats {
-    fn zero(year: u32) -> Self {
+    pub(crate) fn zero(year: u32) -> Self {
         Self {
             year,
             bound_premium: 0,
             sum_insured: 0,
             claims: 0,
+            reserves_established: 0,
+            cancelled_count: 0,
+            premium_reversed: 0,
+            bad_debt: 0,
+            investment_return: 0.0,
+            investment_income: 0,
+            interest_rate: 0.0,
+            undercutting_insurers: 0,
+            rate_index: 0.0,
             attr_gul: 0,
             cat_gul: 0,
+            large_gul: 0,
+            casualty_gul: 0,
+            casualty_reported: 0,
+            casualty_premium: 0,
             total_capital: 0,
             insolvent_count: 0,
+            insolvent_insurers: Vec::new(),
             dropped_count: 0,
             rejected_count: 0,
+            price_declined_count: 0,
+            quote_responses: 0,
+            quote_declines: 0,
             total_assets: 0,
             cat_event_count: 0,
             entrant_count: 0,
+            exit_count: 0,
+            reentry_count: 0,
+            capital_raised: 0,
+            cat_bond_premium: 0,
+            cat_bond_recoveries: 0,
+            reinsurance_premium: 0,
+            reinsurance_recoveries: 0,
+            reinsurance_rate_on_line: 0.0,
+            transfer_count: 0,
+            transfer_price: 0,
             insurer_count: 0,
             ap_tp_factor: 0.0,
             gini_market_share: 0.0,
+            herfindahl: 0.0,
+            top3_share: 0.0,
+            active_writers: 0,
             cr_sensitivity_mean: 0.0,
             cr_sensitivity_std: 0.0,
             capacity_sensitivity_mean: 0.0,
@@ -94,6 +202,8 @@ impl YearStats {
             policies_in_force: 0,
             avg_line_pct: 0.0,
             full_exposure_premium: 0,
+            earned_premium: 0,
+            unearned_premium: 0,
         }
     }
 
@@ -106,6 +216,35 @@ impl YearStats {
         }
     }
 
+    /// Casualty loss ratio on a reported basis: casualty claims reported in the year / casualty
+    /// premium written in the year. Zero if no casualty premium. Reserving swings show here
+    /// without touching the property book.
+    pub fn casualty_loss_ratio(&self) -> f64 {
+        if self.casualty_premium == 0 { 0.0 } else { self.casualty_reported as f64 / self.casualty_premium as f64 }
+    }
+
+    /// Earned loss ratio: claims / earned premium. Unlike `loss_ratio`, unaffected by the
+    /// timing of writings, so it does not flatter growing books or penalise shrinking ones.
+    /// Zero if nothing was earned.
+    pub fn loss_ratio_earned(&self) -> f64 {
+        if self.earned_premium == 0 {
+            0.0
+        } else {
+            self.claims as f64 / self.earned_premium as f64
+        }
+    }
+
+    /// Net loss ratio: claims less cat XoL recoveries over bound premium less the XoL premium
+    /// ceded. Equals `loss_ratio` without reinsurance. Zero if nothing is retained.
+    pub fn loss_ratio_net(&self) -> f64 {
+        let net_premium = self.bound_premium.saturating_sub(self.reinsurance_premium);
+        if net_premium == 0 {
+            0.0
+        } else {
+            self.claims.saturating_sub(self.reinsurance_recoveries) as f64 / net_premium as f64
+        }
+    }
+
     /// Market-wide rate on line: bound premium / sum insured. Zero if no exposure.
     pub fn rate_on_line(&self) -> f64 {
         if self.sum_insured == 0 {
@@ -133,7 +272,7 @@ impl YearStats {
 }
 
 /// Distribution statistics for a continuous metric across N simulation runs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DistStats {
     pub n: usize,
     pub min: f64,
@@ -148,7 +287,7 @@ pub struct DistStats {
 }
 
 /// Distribution statistics for a sparse integer count metric (p50 + max are sufficient).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CountDist {
     pub n: usize,
     pub p50: u32,
@@ -157,7 +296,7 @@ pub struct CountDist {
 }
 
 /// Per-year cross-run distribution of all key YearStats metrics.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct YearDist {
     pub year: u32,
     pub loss_ratio: DistStats,
@@ -168,9 +307,16 @@ pub struct YearDist {
     pub insolvents: CountDist,
     pub dropped: CountDist,
     pub entrants: CountDist,
+    /// Fraction of runs with at least one insolvency in the year. `insolvents.mean` is the
+    /// expected number of insolvencies.
+    pub p_insolvency: f64,
+    /// 99% VaR of year-end total capital (B USD): the 1st percentile across runs.
+    pub cap_var99_b: f64,
+    /// 99% TVaR of year-end total capital (B USD): mean over the worst 1% of runs (at least one).
+    pub cap_tvar99_b: f64,
 }
 
-fn percentile_stats(values: &mut Vec<f64>) -> Option<DistStats> {
+pub(crate) fn percentile_stats(values: &mut Vec<f64>) -> Option<DistStats> {
     if values.is_empty() {
         return None;
     }
@@ -223,6 +369,19 @@ fn count_dist(values: &mut Vec<u32>) -> Option<CountDist> {
     Some(CountDist { n, p50, max: values[n - 1], mean })
 }
 
+/// Lower-tail VaR and TVaR at `level` (e.g. 0.99) of a sorted ascending sample: the
+/// (1 − level) percentile, and the mean of the worst ⌈n × (1 − level)⌉ values.
+fn lower_tail(sorted: &[f64], level: f64) -> (f64, f64) {
+    let n = sorted.len();
+    let h = (1.0 - level) * (n - 1) as f64;
+    let lo = h.floor() as usize;
+    let hi = (lo + 1).min(n - 1);
+    let var = sorted[lo] * (1.0 - (h - lo as f64)) + sorted[hi] * (h - lo as f64);
+    let k = ((n as f64 * (1.0 - level)).ceil() as usize).max(1);
+    let tvar = sorted[..k].iter().sum::<f64>() / k as f64;
+    (var, tvar)
+}
+
 /// Compute per-year cross-run distributions for key YearStats metrics.
 ///
 /// Years present in fewer than 2 runs are excluded (insufficient data for a distribution).
@@ -255,24 +414,46 @@ pub fn analyse_distributions(all_runs: &[Vec<YearStats>], expense_ratio: f64) ->
         let mut insol_vals: Vec<u32> = year_stats.iter().map(|s| s.insolvent_count).collect();
         let mut drop_vals: Vec<u32> = year_stats.iter().map(|s| s.dropped_count).collect();
         let mut entr_vals: Vec<u32> = year_stats.iter().map(|s| s.entrant_count).collect();
+        let runs_with_insolvency = year_stats.iter().filter(|s| s.insolvent_count > 0).count();
 
         // All vecs have the same length (>= 2), so unwrap is safe.
+        let total_cap_b = percentile_stats(&mut cap_vals).unwrap();
+        // percentile_stats leaves cap_vals sorted ascending.
+        let (cap_var99_b, cap_tvar99_b) = lower_tail(&cap_vals, 0.99);
         result.push(YearDist {
             year,
             loss_ratio: percentile_stats(&mut lr_vals).unwrap(),
             rate_on_line: percentile_stats(&mut rol_vals).unwrap(),
             combined_ratio: percentile_stats(&mut cr_vals).unwrap(),
-            total_cap_b: percentile_stats(&mut cap_vals).unwrap(),
+            total_cap_b,
             cat_events: count_dist(&mut cat_vals).unwrap(),
             insolvents: count_dist(&mut insol_vals).unwrap(),
             dropped: count_dist(&mut drop_vals).unwrap(),
             entrants: count_dist(&mut entr_vals).unwrap(),
+            p_insolvency: runs_with_insolvency as f64 / year_stats.len() as f64,
+            cap_var99_b,
+            cap_tvar99_b,
         });
     }
 
     result
 }
 
+/// Per-insurer ruin probability across runs: the fraction of runs in which the insurer became
+/// insolvent in any analysis year. Insurers that never failed are absent (probability zero).
+pub fn ruin_probabilities(all_runs: &[Vec<YearStats>]) -> BTreeMap<InsurerId, f64> {
+    let mut ruined: BTreeMap<InsurerId, u32> = BTreeMap::new();
+    for run in all_runs {
+        let failed: BTreeSet<InsurerId> =
+            run.iter().flat_map(|s| s.insolvent_insurers.iter().copied()).collect();
+        for insurer_id in failed {
+            *ruined.entry(insurer_id).or_insert(0) += 1;
+        }
+    }
+    let n = all_runs.len().max(1) as f64;
+    ruined.into_iter().map(|(id, k)| (id, k as f64 / n)).collect()
+}
+
 /// A mechanics invariant violation detected in the event stream.
 #[derive(Debug)]
 pub enum MechanicsViolation {
@@ -282,7 +463,8 @@ pub enum MechanicsViolation {
     LossBeforeBound { insured_id: u64, loss_day: u64, bound_day: u64 },
     /// Attritional AssetDamage arrived on or before the insured's CoverageRequested day.
     AttrNotStrictlyPostBound { insured_id: u64, loss_day: u64, bound_day: u64 },
-    /// PolicyExpired did not fire at QuoteAccepted_day + 361.
+    /// PolicyExpired did not fire at QuoteAccepted_day + 1 + 360 × term (term = 1 + collected
+    /// multi-year anniversaries).
     PolicyExpiredTiming { policy_id: u64, expected: u64, actual: u64 },
     /// ClaimSettled arrived after the policy had expired.
     ClaimAfterExpiry { policy_id: u64, claim_day: u64, expiry_day: u64 },
@@ -339,9 +521,105 @@ fn gini_from_counts(counts: &HashMap<InsurerId, f64>) -> f64 {
     2.0 * weighted / (n * total) - (n + 1.0) / n
 }
 
+/// Herfindahl index and top-3 share of per-insurer bound line share.
+/// Returns (0.0, 0.0) for empty or all-zero inputs.
+fn concentration_from_counts(counts: &HashMap<InsurerId, f64>) -> (f64, f64) {
+    let total: f64 = counts.values().sum();
+    if total == 0.0 {
+        return (0.0, 0.0);
+    }
+    let mut shares: Vec<f64> = counts.values().map(|&x| x / total).collect();
+    shares.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let herfindahl = shares.iter().map(|s| s * s).sum();
+    let top3 = shares.iter().take(3).sum();
+    (herfindahl, top3)
+}
+
+/// Restate nominal per-year statistics in real terms: every money field is divided by the
+/// year's asset value index, giving year-1 values. Counts, ratios and rates are unchanged —
+/// they are already scale-free.
+pub fn deflate(stats: &[YearStats], inflation: &InflationConfig) -> Vec<YearStats> {
+    stats
+        .iter()
+        .map(|s| {
+            let index = inflation.asset_index(Year(s.year));
+            let real = |v: u64| (v as f64 / index).round() as u64;
+            let real_signed = |v: i64| (v as f64 / index).round() as i64;
+            YearStats {
+                bound_premium: real(s.bound_premium),
+                sum_insured: real(s.sum_insured),
+                claims: real(s.claims),
+                reserves_established: real(s.reserves_established),
+                premium_reversed: real(s.premium_reversed),
+                bad_debt: real(s.bad_debt),
+                investment_income: real_signed(s.investment_income),
+                attr_gul: real(s.attr_gul),
+                cat_gul: real(s.cat_gul),
+                large_gul: real(s.large_gul),
+                casualty_gul: real(s.casualty_gul),
+                casualty_reported: real(s.casualty_reported),
+                casualty_premium: real(s.casualty_premium),
+                total_capital: real(s.total_capital),
+                total_assets: real(s.total_assets),
+                capital_raised: real(s.capital_raised),
+                cat_bond_premium: real(s.cat_bond_premium),
+                cat_bond_recoveries: real(s.cat_bond_recoveries),
+                reinsurance_premium: real(s.reinsurance_premium),
+                reinsurance_recoveries: real(s.reinsurance_recoveries),
+                transfer_price: real_signed(s.transfer_price),
+                total_distributed: real(s.total_distributed),
+                full_exposure_premium: real(s.full_exposure_premium),
+                earned_premium: real(s.earned_premium),
+                unearned_premium: real(s.unearned_premium),
+                ..s.clone()
+            }
+        })
+        .collect()
+}
+
+/// Initial capital of each insurer present at simulation start, read from the Day(0)
+/// `InsurerCreated` events that `start()` logs, or from the Day(0) `InsurerEntered` events of
+/// logs written before those existed. Lets an offline consumer call [`analyse`] on a saved log
+/// without the config that produced it.
+pub fn initial_capitals(events: &[SimEvent]) -> HashMap<InsurerId, u64> {
+    let created = created_capitals(events);
+    if !created.is_empty() {
+        return created;
+    }
+    events
+        .iter()
+        .take_while(|e| e.day.0 == 0)
+        .filter_map(|e| match e.event {
+            Event::InsurerEntered { insurer_id, initial_capital, .. } => Some((insurer_id, initial_capital)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Expense ratio of the first starting insurer, from its `InsurerCreated` event — the value
+/// `batch::expense_ratio` reads from the config. `None` for logs without initial conditions.
+pub fn expense_ratio(events: &[SimEvent]) -> Option<f64> {
+    events.iter().take_while(|e| e.day.0 == 0).find_map(|e| match e.event {
+        Event::InsurerCreated { expense_ratio, .. } => Some(expense_ratio),
+        _ => None,
+    })
+}
+
+fn created_capitals(events: &[SimEvent]) -> HashMap<InsurerId, u64> {
+    events
+        .iter()
+        .take_while(|e| e.day.0 == 0)
+        .filter_map(|e| match e.event {
+            Event::InsurerCreated { insurer_id, initial_capital, .. } => Some((insurer_id, initial_capital)),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Compute per-year statistics from a typed event slice.
 ///
-/// `initial_capitals` seeds each insurer's capital before any ClaimSettled is seen.
+/// `initial_capitals` seeds each insurer's capital before any ClaimSettled is seen. Logs that
+/// carry `InsurerCreated` events describe their own starting capitals; those take precedence.
 /// Warmup years are read from the SimulationStart event; years ≤ warmup_years are excluded
 /// from the returned Vec.
 ///
@@ -352,48 +630,146 @@ pub fn analyse(
     initial_capitals: &HashMap<InsurerId, u64>,
     _expense_ratio: f64,
 ) -> (u32, Vec<YearStats>) {
-    let warmup_years = events
-        .iter()
-        .find_map(|e| {
-            if let Event::SimulationStart { warmup_years, .. } = &e.event {
-                Some(*warmup_years)
-            } else {
-                None
-            }
-        })
-        .unwrap_or(0);
+    let mut acc = Accumulator::new(initial_capitals);
+    for sim_event in events {
+        acc.push(sim_event);
+    }
+    acc.year_stats()
+}
 
-    let mut stats: HashMap<u32, YearStats> = HashMap::new();
-    let mut last_capital: HashMap<InsurerId, u64> = initial_capitals.clone();
-    let mut assets_seen: HashMap<u32, HashSet<InsuredId>> = HashMap::new();
-    let mut active_insurer_count = initial_capitals.len() as u32;
+/// [`analyse`] one event at a time: folds each event into per-year statistics as it is
+/// dispatched, so a run can be analysed without keeping its log (`Simulation::aggregate_only`).
+/// Its state grows with the policies in force and the years simulated, not with the events.
+#[derive(Debug, Clone, Default)]
+pub struct Accumulator {
+    warmup_years: u32,
+    events: u64,
+    /// True once a Day(0) `InsurerCreated` has replaced the caller's starting capitals.
+    created: bool,
+    stats: HashMap<u32, YearStats>,
+    last_capital: HashMap<InsurerId, u64>,
+    assets_seen: HashMap<u32, HashSet<InsuredId>>,
+    active_insurer_count: u32,
+    // Insurers currently in voluntary run-off; already excluded from active_insurer_count.
+    in_runoff: HashSet<InsurerId>,
     // Bound-policy line share per (year, insurer_id) — used to compute the Gini coefficient.
-    let mut bound_by_insurer: HashMap<u32, HashMap<InsurerId, f64>> = HashMap::new();
+    bound_by_insurer: HashMap<u32, HashMap<InsurerId, f64>>,
     // Sensitivity parameters per active insurer: (cr_sensitivity, capacity_sensitivity, market_weight_floor).
     // Populated from InsurerEntered (including day-0 initial insurers); pruned on InsurerInsolvent.
-    let mut insurer_sensitivity: HashMap<InsurerId, (f64, f64, f64)> = HashMap::new();
+    insurer_sensitivity: HashMap<InsurerId, (f64, f64, f64)>,
     // Active policy set for policies_in_force snapshot at year-end.
-    let mut active_policies: HashSet<PolicyId> = HashSet::new();
+    active_policies: HashSet<PolicyId>,
     // Line size accumulator per year (LeadQuoteIssued + FollowerQuoteIssued): (sum, count).
-    let mut line_size_by_year: HashMap<u32, (f64, u64)> = HashMap::new();
+    line_size_by_year: HashMap<u32, (f64, u64)>,
     // Full-exposure premium tracking: premium and bound-year per policy.
     // At PolicyExpired, if expiry year != bound year, the premium counts as carry-over
     // in the expiry year's full_exposure_premium.
-    let mut policy_premiums: HashMap<PolicyId, u64> = HashMap::new();
-    let mut policy_bound_year: HashMap<PolicyId, u32> = HashMap::new();
+    policy_premiums: HashMap<PolicyId, u64>,
+    policy_bound_year: HashMap<PolicyId, u32>,
+    // Premium still being earned, per policy in force; earned up to each YearEnd.
+    unearned: HashMap<PolicyId, Unearned>,
+    // Insureds placing casualty cover (from CoverageRequested), and the casualty policies in
+    // force, so their premium and anniversary instalments count as casualty premium.
+    casualty_insureds: HashSet<InsuredId>,
+    casualty_policies: HashSet<PolicyId>,
+}
 
-    for sim_event in events {
+/// One binding's or instalment's premium, earned pro rata over the 360 days from `start`.
+#[derive(Debug, Clone, Copy)]
+struct Unearned {
+    premium: u64,
+    start: u64,
+    earned: u64,
+}
+
+impl Unearned {
+    /// Premium earned through the end of `day`.
+    fn earned_through(&self, day: u64) -> u64 {
+        let days = (day + 1).saturating_sub(self.start).min(Day::DAYS_PER_YEAR);
+        (self.premium as u128 * days as u128 / Day::DAYS_PER_YEAR as u128) as u64
+    }
+
+    /// Earn through the end of `day`; returns the premium newly earned.
+    fn earn_through(&mut self, day: u64) -> u64 {
+        let earned = self.earned_through(day).max(self.earned);
+        let new = earned - self.earned;
+        self.earned = earned;
+        new
+    }
+}
+
+impl Accumulator {
+    /// `initial_capitals` as in [`analyse`].
+    pub fn new(initial_capitals: &HashMap<InsurerId, u64>) -> Self {
+        Accumulator {
+            last_capital: initial_capitals.clone(),
+            active_insurer_count: initial_capitals.len() as u32,
+            ..Default::default()
+        }
+    }
+
+    /// Events folded in so far.
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+
+    /// Post-warmup statistics for every year seen so far, and the warmup length.
+    pub fn year_stats(&self) -> (u32, Vec<YearStats>) {
+        let mut result: Vec<YearStats> =
+            self.stats.values().filter(|s| s.year > self.warmup_years).cloned().collect();
+        result.sort_by_key(|s| s.year);
+        (self.warmup_years, result)
+    }
+
+    /// Fold in the next event in log order.
+    pub fn push(&mut self, sim_event: &SimEvent) {
+        self.events += 1;
         let year = sim_event.day.year().0;
+        match sim_event.event {
+            Event::SimulationStart { warmup_years, .. } => self.warmup_years = warmup_years,
+            Event::InsurerCreated { insurer_id, initial_capital, .. } if sim_event.day.0 == 0 => {
+                if !self.created {
+                    self.created = true;
+                    self.last_capital.clear();
+                    self.active_insurer_count = 0;
+                }
+                self.last_capital.insert(insurer_id, initial_capital);
+                self.active_insurer_count += 1;
+            }
+            _ => {}
+        }
 
+        let Accumulator {
+            stats,
+            last_capital,
+            assets_seen,
+            active_insurer_count,
+            in_runoff,
+            bound_by_insurer,
+            insurer_sensitivity,
+            active_policies,
+            line_size_by_year,
+            policy_premiums,
+            policy_bound_year,
+            unearned,
+            casualty_insureds,
+            casualty_policies,
+            ..
+        } = self;
         match &sim_event.event {
-            Event::PolicyBound { policy_id, panel, premium, sum_insured, .. } => {
+            Event::PolicyBound { policy_id, insured_id, panel, premium, sum_insured, .. } => {
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 s.bound_premium += premium;
+                if casualty_insureds.contains(insured_id) {
+                    s.casualty_premium += premium;
+                    casualty_policies.insert(*policy_id);
+                }
                 s.sum_insured += sum_insured;
                 s.full_exposure_premium += premium;
                 active_policies.insert(*policy_id);
                 policy_premiums.insert(*policy_id, *premium);
                 policy_bound_year.insert(*policy_id, year);
+                unearned.insert(*policy_id, Unearned { premium: *premium, start: sim_event.day.0, earned: 0 });
                 // Track per-insurer line share for Gini computation.
                 let year_map = bound_by_insurer.entry(year).or_default();
                 for (insurer_id, line_share) in panel {
@@ -411,32 +787,172 @@ pub fn analyse(
                     s.full_exposure_premium += prem;
                 }
                 active_policies.remove(policy_id);
+                casualty_policies.remove(policy_id);
+                if let Some(u) = unearned.remove(policy_id) {
+                    let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                    s.earned_premium += u.premium - u.earned;
+                }
+            }
+            Event::PolicyAnniversary { policy_id, premium, sum_insured, .. } if *premium > 0 => {
+                // A collected instalment is written like a fresh binding; the previous cover
+                // year's premium carries over into this year as at expiry.
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                if policy_bound_year.get(policy_id).is_some_and(|&y| y < year)
+                    && let Some(&prem) = policy_premiums.get(policy_id)
+                {
+                    s.full_exposure_premium += prem;
+                }
+                s.bound_premium += premium;
+                if casualty_policies.contains(policy_id) {
+                    s.casualty_premium += premium;
+                }
+                s.sum_insured += sum_insured;
+                s.full_exposure_premium += premium;
+                policy_premiums.insert(*policy_id, *premium);
+                policy_bound_year.insert(*policy_id, year);
+                // The previous cover year is fully earned; the instalment starts earning today.
+                let next = Unearned { premium: *premium, start: sim_event.day.0, earned: 0 };
+                if let Some(u) = unearned.insert(*policy_id, next) {
+                    s.earned_premium += u.premium - u.earned;
+                }
             }
             Event::ClaimSettled { insurer_id, amount, remaining_capital, .. } => {
                 last_capital.insert(*insurer_id, *remaining_capital);
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 s.claims += amount;
             }
+            Event::ClaimPaid { amount, .. } => {
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.claims += amount;
+            }
+            Event::ClaimReported { peril: Peril::Casualty, amount, .. } => {
+                stats.entry(year).or_insert_with(|| YearStats::zero(year)).casualty_reported += amount;
+            }
+            Event::ReserveEstablished { reserve, .. } => {
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.reserves_established += reserve;
+            }
+            Event::PolicyCancelled { policy_id, premium_reversed, bad_debt, .. } => {
+                active_policies.remove(policy_id);
+                casualty_policies.remove(policy_id);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                if let Some(mut u) = unearned.remove(policy_id) {
+                    s.earned_premium += u.earn_through(sim_event.day.0);
+                }
+                s.cancelled_count += 1;
+                s.premium_reversed += premium_reversed;
+                s.bad_debt += bad_debt;
+            }
+            Event::MarketIntelligence { quote_responses, decline_rate, .. } => {
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.quote_responses += quote_responses;
+                s.quote_declines += (decline_rate * *quote_responses as f64).round() as u32;
+            }
+            Event::RateIndexPublished { index, .. } => {
+                stats.entry(year).or_insert_with(|| YearStats::zero(year)).rate_index = *index;
+            }
+            Event::InvestmentReturnDrawn { rate, .. } => {
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.investment_return = *rate;
+            }
+            Event::InvestmentIncome { amount, .. } => {
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.investment_income += amount;
+            }
+            Event::InterestRatePublished { rate, .. } => {
+                stats.entry(year).or_insert_with(|| YearStats::zero(year)).interest_rate = *rate;
+            }
+            Event::UnderwritingPlanSet { undercut, .. } if *undercut > 0.0 => {
+                stats.entry(year).or_insert_with(|| YearStats::zero(year)).undercutting_insurers += 1;
+            }
             Event::AssetDamage { peril, ground_up_loss, .. } => {
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 match peril {
                     Peril::Attritional => s.attr_gul += ground_up_loss,
                     Peril::WindstormAtlantic => s.cat_gul += ground_up_loss,
+                    Peril::LargeFire => s.large_gul += ground_up_loss,
+                    Peril::Casualty => s.casualty_gul += ground_up_loss,
                 }
             }
             Event::InsurerInsolvent { insurer_id, .. } => {
-                active_insurer_count = active_insurer_count.saturating_sub(1);
+                if !in_runoff.remove(insurer_id) {
+                    *active_insurer_count = active_insurer_count.saturating_sub(1);
+                }
                 insurer_sensitivity.remove(insurer_id);
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 s.insolvent_count += 1;
+                s.insolvent_insurers.push(*insurer_id);
+            }
+            Event::InsurerExited { insurer_id, .. } => {
+                in_runoff.insert(*insurer_id);
+                *active_insurer_count = active_insurer_count.saturating_sub(1);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.exit_count += 1;
+            }
+            Event::InsurerReEntered { insurer_id, .. } => {
+                in_runoff.remove(insurer_id);
+                *active_insurer_count += 1;
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.reentry_count += 1;
+            }
+            // A forced run-off counts as an exit; stepping back up the ladder as a re-entry.
+            Event::RegulatoryIntervention { insurer_id, action } => match action {
+                RegulatoryAction::ForcedRunoff => {
+                    in_runoff.insert(*insurer_id);
+                    *active_insurer_count = active_insurer_count.saturating_sub(1);
+                    let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                    s.exit_count += 1;
+                }
+                RegulatoryAction::RestrictedToRenewals | RegulatoryAction::Reinstated => {
+                    if in_runoff.remove(insurer_id) {
+                        *active_insurer_count += 1;
+                        let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                        s.reentry_count += 1;
+                    }
+                }
+                RegulatoryAction::LicenceRevoked => {}
+            },
+            Event::CapitalRaised { insurer_id, amount, remaining_capital, .. } => {
+                last_capital.insert(*insurer_id, *remaining_capital);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.capital_raised += amount;
+            }
+            Event::CatBondIssued(bond) => {
+                last_capital.insert(bond.insurer_id, bond.remaining_capital);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.cat_bond_premium += bond.premium;
+            }
+            Event::CatBondTriggered { insurer_id, payout, remaining_capital, .. } => {
+                last_capital.insert(*insurer_id, *remaining_capital);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.cat_bond_recoveries += payout;
+            }
+            Event::ReinsurancePurchased { insurer_id, premium, rate_on_line, remaining_capital, .. } => {
+                last_capital.insert(*insurer_id, *remaining_capital);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.reinsurance_premium += premium;
+                s.reinsurance_rate_on_line = *rate_on_line;
+            }
+            Event::ReinsuranceRecovered { insurer_id, amount, remaining_capital, .. } => {
+                last_capital.insert(*insurer_id, *remaining_capital);
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.reinsurance_recoveries += amount;
+            }
+            Event::PortfolioTransferred(transfer) => {
+                let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
+                s.transfer_count += 1;
+                s.transfer_price += transfer.price;
             }
             Event::SubmissionDropped { .. } => {
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 s.dropped_count += 1;
             }
-            Event::QuoteRejected { .. } => {
+            Event::QuoteRejected { reason, .. } => {
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 s.rejected_count += 1;
+                if *reason == RejectionReason::PriceTooHigh {
+                    s.price_declined_count += 1;
+                }
             }
             Event::LossEvent { peril: Peril::WindstormAtlantic, .. } => {
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
@@ -448,12 +964,13 @@ pub fn analyse(
                 cr_sensitivity,
                 capacity_sensitivity,
                 market_weight_floor,
+                ..
             } => {
                 last_capital.insert(*insurer_id, *initial_capital);
                 insurer_sensitivity.insert(*insurer_id, (*cr_sensitivity, *capacity_sensitivity, *market_weight_floor));
                 // Day(0) events are the initial insurers logged by `start()` — not market entrants.
                 if sim_event.day.0 > 0 {
-                    active_insurer_count += 1;
+                    *active_insurer_count += 1;
                     let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                     s.entrant_count += 1;
                 }
@@ -463,6 +980,9 @@ pub fn analyse(
                 let s = stats.entry(year).or_insert_with(|| YearStats::zero(year));
                 s.total_distributed += amount;
             }
+            Event::CoverageRequested { insured_id, risk } if risk.line() == Line::Casualty => {
+                casualty_insureds.insert(*insured_id);
+            }
             Event::CoverageRequested { insured_id, risk } => {
                 let seen = assets_seen.entry(year).or_default();
                 if seen.insert(*insured_id) {
@@ -474,7 +994,9 @@ pub fn analyse(
                 // Keep last_capital current so YearEnd total is accurate even without ClaimSettled.
                 last_capital.insert(*insurer_id, *capital);
             }
-            Event::LeadQuoteIssued { line_size, .. } | Event::FollowerQuoteIssued { line_size, .. } => {
+            Event::LeadQuoteIssued { line_size, .. }
+            | Event::LeadQuotePartial { line_size, .. }
+            | Event::FollowerQuoteIssued { line_size, .. } => {
                 let entry = line_size_by_year.entry(year).or_insert((0.0, 0));
                 entry.0 += line_size;
                 entry.1 += 1;
@@ -484,17 +1006,21 @@ pub fn analyse(
                 let total_cap: u64 = last_capital.values().sum();
                 let s = stats.entry(y.0).or_insert_with(|| YearStats::zero(y.0));
                 s.total_capital = total_cap;
-                s.insurer_count = active_insurer_count;
+                s.insurer_count = *active_insurer_count;
                 s.policies_in_force = active_policies.len() as u32;
+                s.earned_premium += unearned.values_mut().map(|u| u.earn_through(sim_event.day.0)).sum::<u64>();
+                s.unearned_premium = unearned.values().map(|u| u.premium - u.earned).sum();
                 // Average line size: mean of LeadQuoteIssued.line_size for this year.
                 if let Some((sum, count)) = line_size_by_year.get(&y.0) {
                     if *count > 0 {
                         s.avg_line_pct = sum / *count as f64 * 100.0;
                     }
                 }
-                // Gini coefficient of bound-policy count across active writers this year.
+                // Concentration of bound line share across active writers this year.
                 if let Some(counts) = bound_by_insurer.get(&y.0) {
                     s.gini_market_share = gini_from_counts(counts);
+                    (s.herfindahl, s.top3_share) = concentration_from_counts(counts);
+                    s.active_writers = counts.len() as u32;
                 }
                 // Sensitivity distribution snapshot across active insurers.
                 let n = insurer_sensitivity.len();
@@ -521,11 +1047,698 @@ pub fn analyse(
             _ => {}
         }
     }
+}
 
-    let mut result: Vec<YearStats> =
-        stats.into_values().filter(|s| s.year > warmup_years).collect();
-    result.sort_by_key(|s| s.year);
-    (warmup_years, result)
+/// One insured's position in one analysis year — a row of the insured-level panel.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InsuredYear {
+    pub year: u32,
+    pub insured_id: InsuredId,
+    /// Gross premium on policies bound (or multi-year instalments collected) this year, net of
+    /// premium reversed on cancellation (cents).
+    pub premium_paid: u64,
+    /// Ground-up asset damage suffered this year, insured or not (cents).
+    pub ground_up_losses: u64,
+    /// Subset of `ground_up_losses` from attritional damage (cents).
+    pub attr_gul: u64,
+    /// Subset of `ground_up_losses` from WindstormAtlantic damage (cents).
+    pub cat_gul: u64,
+    /// Subset of `ground_up_losses` from LargeFire damage (cents).
+    pub large_gul: u64,
+    /// Subset of `ground_up_losses` from Casualty occurrences (cents).
+    pub casualty_gul: u64,
+    /// Claim payments received this year from any insurer on any of the insured's policies (cents).
+    pub recoveries: u64,
+    /// Ground-up losses this year not matched by this year's recoveries (cents): retention,
+    /// limit shortfall, or damage with no cover in force. Floored at zero when developing claims
+    /// pay out an earlier year's loss.
+    pub uninsured_loss: u64,
+    /// True if the insured held a policy in force at any point in the year.
+    pub insured: bool,
+}
+
+/// Per-insured panel data: one row per (year, insured) for every insured active in the market.
+///
+/// An insured appears in a year once it requests coverage, suffers damage, or holds a policy
+/// in force. Recoveries are credited in the year the claim is paid, so a developing claim
+/// contributes to each year it pays into. Warmup years are excluded as in [`analyse`].
+/// Rows are sorted by (year, insured_id).
+pub fn insured_panel(events: &[SimEvent]) -> Vec<InsuredYear> {
+    let mut warmup_years = 0;
+    let mut rows: HashMap<(u32, InsuredId), InsuredYear> = HashMap::new();
+    let mut policy_insured: HashMap<PolicyId, (InsuredId, u32)> = HashMap::new();
+    // Policies in force per insured — an insured carrying a policy into a new year is insured in it.
+    let mut in_force: HashMap<InsuredId, u32> = HashMap::new();
+
+    fn row(
+        rows: &mut HashMap<(u32, InsuredId), InsuredYear>,
+        year: u32,
+        insured_id: InsuredId,
+    ) -> &mut InsuredYear {
+        rows.entry((year, insured_id)).or_insert(InsuredYear {
+            year,
+            insured_id,
+            premium_paid: 0,
+            ground_up_losses: 0,
+            attr_gul: 0,
+            cat_gul: 0,
+            large_gul: 0,
+            casualty_gul: 0,
+            recoveries: 0,
+            uninsured_loss: 0,
+            insured: false,
+        })
+    }
+
+    for sim_event in events {
+        let year = sim_event.day.year().0;
+        match &sim_event.event {
+            Event::SimulationStart { warmup_years: w, .. } => warmup_years = *w,
+            Event::YearStart { year: y } => {
+                for (&insured_id, &n) in &in_force {
+                    if n > 0 {
+                        row(&mut rows, y.0, insured_id).insured = true;
+                    }
+                }
+            }
+            Event::CoverageRequested { insured_id, .. } => {
+                row(&mut rows, year, *insured_id);
+            }
+            Event::PolicyBound { policy_id, insured_id, premium, .. } => {
+                let r = row(&mut rows, year, *insured_id);
+                r.premium_paid += premium;
+                r.insured = true;
+                policy_insured.insert(*policy_id, (*insured_id, year));
+                *in_force.entry(*insured_id).or_insert(0) += 1;
+            }
+            Event::PolicyAnniversary { policy_id, insured_id, premium, .. } if *premium > 0 => {
+                row(&mut rows, year, *insured_id).premium_paid += premium;
+                policy_insured.insert(*policy_id, (*insured_id, year));
+            }
+            Event::PolicyCancelled { policy_id, premium_reversed, .. } => {
+                if let Some(&(insured_id, bound_year)) = policy_insured.get(policy_id) {
+                    let r = row(&mut rows, bound_year, insured_id);
+                    r.premium_paid = r.premium_paid.saturating_sub(*premium_reversed);
+                    if let Some(n) = in_force.get_mut(&insured_id) {
+                        *n = n.saturating_sub(1);
+                    }
+                }
+            }
+            Event::PolicyExpired { policy_id } => {
+                if let Some((insured_id, _)) = policy_insured.get(policy_id)
+                    && let Some(n) = in_force.get_mut(insured_id)
+                {
+                    *n = n.saturating_sub(1);
+                }
+            }
+            Event::AssetDamage { insured_id, peril, ground_up_loss } => {
+                let r = row(&mut rows, year, *insured_id);
+                r.ground_up_losses += ground_up_loss;
+                match peril {
+                    Peril::Attritional => r.attr_gul += ground_up_loss,
+                    Peril::WindstormAtlantic => r.cat_gul += ground_up_loss,
+                    Peril::LargeFire => r.large_gul += ground_up_loss,
+                    Peril::Casualty => r.casualty_gul += ground_up_loss,
+                }
+            }
+            Event::ClaimSettled { policy_id, amount, .. } | Event::ClaimPaid { policy_id, amount, .. } => {
+                if let Some(&(insured_id, _)) = policy_insured.get(policy_id) {
+                    row(&mut rows, year, insured_id).recoveries += amount;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut result: Vec<InsuredYear> =
+        rows.into_values().filter(|r| r.year > warmup_years).collect();
+    for r in &mut result {
+        r.uninsured_loss = r.ground_up_losses.saturating_sub(r.recoveries);
+    }
+    result.sort_by_key(|r| (r.year, r.insured_id));
+    result
+}
+
+/// The insured panel keyed by insured: each insured's loss experience as a time series, sorted
+/// by year. Rows are those of [`insured_panel`].
+pub fn analyse_by_insured(events: &[SimEvent]) -> HashMap<InsuredId, Vec<InsuredYear>> {
+    let mut by_insured: HashMap<InsuredId, Vec<InsuredYear>> = HashMap::new();
+    // The panel is sorted by (year, insured_id), so each series comes out in year order.
+    for r in insured_panel(events) {
+        by_insured.entry(r.insured_id).or_default().push(r);
+    }
+    by_insured
+}
+
+/// One insurer's book in one analysis year — a row of the per-insurer time series.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InsurerYearStats {
+    pub year: u32,
+    /// The insurer's line share of premium on policies bound (or multi-year instalments
+    /// collected) this year (cents). Gross of cancellations, as `YearStats::bound_premium`.
+    pub premium: u64,
+    /// ClaimSettled / ClaimPaid amounts paid by this insurer this year (cents).
+    pub claims: u64,
+    /// Subset of `claims` from WindstormAtlantic (cents).
+    pub cat_claims: u64,
+    /// Capital at year-end from YearEndCapital; zero in the year the insurer failed.
+    pub capital: u64,
+    /// Count of PolicyBound panels this insurer sat on this year, lead or follow.
+    pub policies_written: u32,
+}
+
+impl InsurerYearStats {
+    /// This insurer's loss ratio: claims / premium. Zero if no premium.
+    pub fn loss_ratio(&self) -> f64 {
+        if self.premium == 0 { 0.0 } else { self.claims as f64 / self.premium as f64 }
+    }
+
+    /// Fraction of this insurer's claims that were cat claims. Zero if no claims.
+    pub fn cat_share(&self) -> f64 {
+        if self.claims == 0 { 0.0 } else { self.cat_claims as f64 / self.claims as f64 }
+    }
+}
+
+/// Per-insurer time series: for each insurer, one row per analysis year in which it wrote,
+/// paid claims or reported capital, sorted by year.
+///
+/// [`analyse`] aggregates across the market; this splits the same premium and claims by
+/// insurer so divergence between books can be studied directly. Premium is allocated by panel
+/// line share, so the insurers' premiums sum to the market's bound premium up to rounding.
+/// Warmup years are excluded as in [`analyse`].
+pub fn analyse_by_insurer(events: &[SimEvent]) -> HashMap<InsurerId, Vec<InsurerYearStats>> {
+    let mut warmup_years = 0;
+    let mut rows: HashMap<(u32, InsurerId), InsurerYearStats> = HashMap::new();
+    // Panel per policy, so multi-year instalments are split like the original binding.
+    let mut policy_panel: HashMap<PolicyId, Vec<(InsurerId, f64)>> = HashMap::new();
+
+    fn row(
+        rows: &mut HashMap<(u32, InsurerId), InsurerYearStats>,
+        year: u32,
+        insurer_id: InsurerId,
+    ) -> &mut InsurerYearStats {
+        rows.entry((year, insurer_id)).or_insert(InsurerYearStats {
+            year,
+            premium: 0,
+            claims: 0,
+            cat_claims: 0,
+            capital: 0,
+            policies_written: 0,
+        })
+    }
+
+    for sim_event in events {
+        let year = sim_event.day.year().0;
+        match &sim_event.event {
+            Event::SimulationStart { warmup_years: w, .. } => warmup_years = *w,
+            Event::PolicyBound { policy_id, panel, premium, .. } => {
+                for &(insurer_id, line_share) in panel {
+                    let r = row(&mut rows, year, insurer_id);
+                    r.premium += (*premium as f64 * line_share).round() as u64;
+                    r.policies_written += 1;
+                }
+                policy_panel.insert(*policy_id, panel.clone());
+            }
+            Event::PolicyAnniversary { policy_id, premium, .. } if *premium > 0 => {
+                for &(insurer_id, line_share) in policy_panel.get(policy_id).into_iter().flatten() {
+                    row(&mut rows, year, insurer_id).premium += (*premium as f64 * line_share).round() as u64;
+                }
+            }
+            Event::ClaimSettled { insurer_id, amount, peril, .. }
+            | Event::ClaimPaid { insurer_id, amount, peril, .. } => {
+                let r = row(&mut rows, year, *insurer_id);
+                r.claims += amount;
+                if *peril == Peril::WindstormAtlantic {
+                    r.cat_claims += amount;
+                }
+            }
+            Event::YearEndCapital { insurer_id, capital, .. } => {
+                row(&mut rows, year, *insurer_id).capital = *capital;
+            }
+            Event::InsurerInsolvent { insurer_id } => {
+                row(&mut rows, year, *insurer_id).capital = 0;
+            }
+            _ => {}
+        }
+    }
+
+    let mut by_insurer: HashMap<InsurerId, Vec<InsurerYearStats>> = HashMap::new();
+    for ((year, insurer_id), r) in rows {
+        if year > warmup_years {
+            by_insurer.entry(insurer_id).or_default().push(r);
+        }
+    }
+    for series in by_insurer.values_mut() {
+        series.sort_by_key(|r| r.year);
+    }
+    by_insurer
+}
+
+/// One broker's placements in one analysis year (brokerage only).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BrokerYearStats {
+    pub year: u32,
+    /// Bindings and multi-year anniversaries that earned this broker commission.
+    pub placements: u32,
+    /// Premium on those placements (cents).
+    pub premium: u64,
+    /// Commission earned (cents).
+    pub brokerage: u64,
+}
+
+/// Per-broker annual totals from `BrokerageEarned`, one row per analysis year with a
+/// placement, sorted by year. Empty for a log without brokerage. Warmup years are excluded
+/// as in [`analyse`].
+pub fn analyse_by_broker(events: &[SimEvent]) -> HashMap<BrokerId, Vec<BrokerYearStats>> {
+    let mut warmup_years = 0;
+    let mut rows: HashMap<(u32, BrokerId), BrokerYearStats> = HashMap::new();
+    for sim_event in events {
+        let year = sim_event.day.year().0;
+        match &sim_event.event {
+            Event::SimulationStart { warmup_years: w, .. } => warmup_years = *w,
+            Event::BrokerageEarned { broker_id, premium, amount, .. } => {
+                let r = rows.entry((year, *broker_id)).or_insert(BrokerYearStats {
+                    year,
+                    placements: 0,
+                    premium: 0,
+                    brokerage: 0,
+                });
+                r.placements += 1;
+                r.premium += premium;
+                r.brokerage += amount;
+            }
+            _ => {}
+        }
+    }
+
+    let mut by_broker: HashMap<BrokerId, Vec<BrokerYearStats>> = HashMap::new();
+    for ((year, broker_id), r) in rows {
+        if year > warmup_years {
+            by_broker.entry(broker_id).or_default().push(r);
+        }
+    }
+    for series in by_broker.values_mut() {
+        series.sort_by_key(|r| r.year);
+    }
+    by_broker
+}
+
+/// One analysis year of the placement funnel: see [`submission_funnel`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunnelYear {
+    pub year: u32,
+    /// Submissions routed by a broker (`SubmissionRouted`).
+    pub submissions: u32,
+    /// Submissions with at least one lead quote (`LeadQuoteIssued` or `LeadQuotePartial`).
+    pub quoted: u32,
+    /// Lead quote requests declined, by reason (`LeadQuoteDeclined`).
+    pub lead_declines: BTreeMap<DeclineReason, u32>,
+    /// The same declines split by the declining insurer.
+    pub lead_declines_by_insurer: BTreeMap<InsurerId, BTreeMap<DeclineReason, u32>>,
+    /// Panels put to the insured (`QuotePresented`).
+    pub presented: u32,
+    /// Quotes the insured accepted (`QuoteAccepted`).
+    pub accepted: u32,
+    /// Quotes the insured turned down (`QuoteRejected`).
+    pub rejected: u32,
+    /// Policies bound (`PolicyBound`).
+    pub bound: u32,
+    /// Submissions every candidate lead declined (`SubmissionDropped`).
+    pub dropped: u32,
+}
+
+impl FunnelYear {
+    fn new(year: u32) -> Self {
+        FunnelYear {
+            year,
+            submissions: 0,
+            quoted: 0,
+            lead_declines: BTreeMap::new(),
+            lead_declines_by_insurer: BTreeMap::new(),
+            presented: 0,
+            accepted: 0,
+            rejected: 0,
+            bound: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Lead declines of every reason.
+    pub fn declined(&self) -> u32 {
+        self.lead_declines.values().sum()
+    }
+
+    /// Quote-to-bind conversion: bound / quoted. Zero if nothing was quoted.
+    pub fn quote_to_bind(&self) -> f64 {
+        if self.quoted == 0 { 0.0 } else { self.bound as f64 / self.quoted as f64 }
+    }
+
+    /// Submissions that ended in a binding: bound / submissions. Zero without submissions.
+    pub fn bind_rate(&self) -> f64 {
+        if self.submissions == 0 { 0.0 } else { self.bound as f64 / self.submissions as f64 }
+    }
+}
+
+/// The placement funnel per analysis year: submissions routed, quoted, declined (by reason
+/// and by insurer), presented, accepted or rejected, bound and dropped. Each stage counts in
+/// the year its event falls, so a submission routed at year-end may bind in the next. A
+/// submission quoted by two leads in turn (a loyal incumbent passed over, a partial lead
+/// without followers) counts as quoted once. Warmup years are excluded as in [`analyse`].
+pub fn submission_funnel(events: &[SimEvent]) -> Vec<FunnelYear> {
+    let mut warmup_years = 0;
+    let mut years: BTreeMap<u32, FunnelYear> = BTreeMap::new();
+    let mut quoted: HashSet<SubmissionId> = HashSet::new();
+    for sim_event in events {
+        let year = sim_event.day.year().0;
+        let r = years.entry(year).or_insert_with(|| FunnelYear::new(year));
+        match &sim_event.event {
+            Event::SimulationStart { warmup_years: w, .. } => warmup_years = *w,
+            Event::SubmissionRouted { .. } => r.submissions += 1,
+            Event::LeadQuoteIssued { submission_id, .. } | Event::LeadQuotePartial { submission_id, .. }
+                if quoted.insert(*submission_id) =>
+            {
+                r.quoted += 1;
+            }
+            Event::LeadQuoteDeclined { insurer_id, reason, .. } => {
+                *r.lead_declines.entry(*reason).or_insert(0) += 1;
+                *r.lead_declines_by_insurer.entry(*insurer_id).or_default().entry(*reason).or_insert(0) += 1;
+            }
+            Event::QuotePresented { .. } => r.presented += 1,
+            Event::QuoteAccepted { .. } => r.accepted += 1,
+            Event::QuoteRejected { .. } => r.rejected += 1,
+            Event::PolicyBound { .. } => r.bound += 1,
+            Event::SubmissionDropped { .. } => r.dropped += 1,
+            _ => {}
+        }
+    }
+    years.into_values().filter(|r| r.year > warmup_years).collect()
+}
+
+/// One quote-spread decile of the compared policies: see [`winners_curse`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpreadDecile {
+    /// 1 = the policies bound furthest below the median quote, 10 = furthest above.
+    pub decile: u32,
+    pub policies: u32,
+    /// Spreads (`premium / median_quote − 1`) in the decile: lowest, highest and mean.
+    pub min_spread: f64,
+    pub max_spread: f64,
+    pub mean_spread: f64,
+    /// Premium written over the policies' terms (binding plus any anniversaries).
+    pub premium: u64,
+    /// Claims incurred on the policies: `ClaimSettled` amounts and `ClaimReported` ultimates.
+    pub claims: u64,
+}
+
+impl SpreadDecile {
+    /// Claims / premium. Zero without premium.
+    pub fn loss_ratio(&self) -> f64 {
+        if self.premium == 0 { 0.0 } else { self.claims as f64 / self.premium as f64 }
+    }
+}
+
+/// Winner's-curse diagnostic: the policies carrying a `QuoteComparisonRecorded` (run with
+/// `quote_comparison`), ranked by quote spread and cut into ten equal-count deciles, each with
+/// the loss ratio it went on to run. Adverse selection shows as loss ratios falling from the
+/// cheapest decile to the dearest: the business won furthest below the market's view is the
+/// business the market would have priced up. Policies bound in warmup years are excluded as in
+/// [`analyse`]; claims count whenever they fall, so the last years' policies are under-developed.
+/// Fewer than ten policies leave some deciles empty, and those are omitted.
+pub fn winners_curse(events: &[SimEvent]) -> Vec<SpreadDecile> {
+    let mut warmup_years = 0;
+    let mut spreads: Vec<(PolicyId, f64)> = Vec::new();
+    let mut premium: HashMap<PolicyId, u64> = HashMap::new();
+    let mut claims: HashMap<PolicyId, u64> = HashMap::new();
+    for sim_event in events {
+        match &sim_event.event {
+            Event::SimulationStart { warmup_years: w, .. } => warmup_years = *w,
+            Event::QuoteComparisonRecorded { policy_id, premium: p, median_quote, .. }
+                if *median_quote > 0 && sim_event.day.year().0 > warmup_years =>
+            {
+                spreads.push((*policy_id, *p as f64 / *median_quote as f64 - 1.0));
+                premium.insert(*policy_id, *p);
+            }
+            Event::PolicyAnniversary { policy_id, premium: p, .. } => {
+                if let Some(written) = premium.get_mut(policy_id) {
+                    *written += p;
+                }
+            }
+            Event::ClaimSettled { policy_id, amount, .. } | Event::ClaimReported { policy_id, amount, .. } => {
+                *claims.entry(*policy_id).or_insert(0) += amount;
+            }
+            _ => {}
+        }
+    }
+    spreads.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    let n = spreads.len();
+    let mut deciles: Vec<SpreadDecile> = Vec::new();
+    for (rank, &(policy_id, spread)) in spreads.iter().enumerate() {
+        let decile = (rank * 10 / n) as u32 + 1;
+        if deciles.last().is_none_or(|d| d.decile != decile) {
+            deciles.push(SpreadDecile {
+                decile,
+                policies: 0,
+                min_spread: spread,
+                max_spread: spread,
+                mean_spread: 0.0,
+                premium: 0,
+                claims: 0,
+            });
+        }
+        let d = deciles.last_mut().expect("pushed above");
+        d.policies += 1;
+        d.max_spread = spread;
+        d.mean_spread += spread;
+        d.premium += premium[&policy_id];
+        d.claims += claims.get(&policy_id).copied().unwrap_or(0);
+    }
+    for d in &mut deciles {
+        d.mean_spread /= d.policies as f64;
+    }
+    deciles
+}
+
+/// One quarter of market activity: see [`quarterly_stats`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuarterStats {
+    pub year: u32,
+    /// 1–4.
+    pub quarter: u8,
+    /// Premium written in the quarter (bindings and anniversaries).
+    pub written_premium: u64,
+    /// Claims incurred in the quarter: `ClaimSettled` amounts and `ClaimReported` ultimates.
+    pub claims_incurred: u64,
+    /// Market capital at the quarter end.
+    pub capital: u64,
+    /// Change in market capital since the previous quarter end (the initial capital for the
+    /// first), including entry, raises and distributions at the year end in between.
+    pub capital_change: i64,
+}
+
+impl QuarterStats {
+    /// Claims / premium. Zero without premium.
+    pub fn loss_ratio(&self) -> f64 {
+        if self.written_premium == 0 { 0.0 } else { self.claims_incurred as f64 / self.written_premium as f64 }
+    }
+}
+
+/// Quarterly resolution: one row per `QuarterEnd` (run with `quarterly_reporting`), so a
+/// mid-year cat shows as the quarter it hit rather than as one year's total. Warmup quarters
+/// are excluded as in [`analyse`]. Empty for a log without quarterly reporting.
+pub fn quarterly_stats(events: &[SimEvent]) -> Vec<QuarterStats> {
+    let mut warmup_years = 0;
+    let mut last_capital: i64 = initial_capitals(events).values().sum::<u64>() as i64;
+    let mut quarters = Vec::new();
+    for sim_event in events {
+        match sim_event.event {
+            Event::SimulationStart { warmup_years: w, .. } => warmup_years = w,
+            Event::QuarterEnd { year, quarter, written_premium, claims_incurred, capital } => {
+                let capital_change = capital as i64 - last_capital;
+                last_capital = capital as i64;
+                if year.0 > warmup_years {
+                    quarters.push(QuarterStats { year: year.0, quarter, written_premium, claims_incurred, capital, capital_change });
+                }
+            }
+            _ => {}
+        }
+    }
+    quarters
+}
+
+/// What one catastrophe occurrence did to the market: see [`event_postmortem`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventPostmortem {
+    pub event_id: u64,
+    pub day: Day,
+    pub peril: Peril,
+    /// The territory's name from the log's `RunMetadata` header; its id without one.
+    pub territory: String,
+    pub damage_fraction: f64,
+    /// Insureds with an AssetDamage from this occurrence.
+    pub insureds_struck: u32,
+    /// Ground-up loss summed over the struck insureds, insured or not (cents).
+    pub total_gul: u64,
+    /// Claims from this occurrence summed over all panels (cents).
+    pub insured_loss: u64,
+    /// One entry per insurer that paid or reserved a claim, sorted by insurer.
+    pub insurers: Vec<InsurerEventImpact>,
+    /// Market rate on line for the event year and every later year in the log: the
+    /// `RateIndexPublished` rate when the log has one, else bound premium / sum insured.
+    pub rates: Vec<(u32, f64)>,
+}
+
+impl EventPostmortem {
+    /// Relative change in market rate on line `years` after the event year, e.g. 0.25 for a
+    /// 25% rise. `None` if the log does not reach that year or the event year has no rate.
+    pub fn rate_change(&self, years: usize) -> Option<f64> {
+        let &(_, before) = self.rates.first()?;
+        let &(_, after) = self.rates.get(years)?;
+        (before > 0.0).then(|| after / before - 1.0)
+    }
+}
+
+/// One insurer's share of an occurrence.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InsurerEventImpact {
+    pub insurer_id: InsurerId,
+    /// ClaimSettled (or, under claims development, ClaimReported) amounts (cents).
+    pub claims: u64,
+    pub claim_count: u32,
+    /// Capital before the first and after the last of these claims, from `ClaimSettled`
+    /// (`None` under claims development, where claims carry no capital).
+    pub capital_before: Option<u64>,
+    pub capital_after: Option<u64>,
+    /// The insurer failed on the event day, after paying its claims.
+    pub insolvent: bool,
+}
+
+/// Post-mortem of the catastrophe `LossEvent` with `event_id`: the AssetDamage it caused, the
+/// claims each insurer settled on it, the capital they lost, any insolvency it triggered and
+/// the market rate path from the event year onwards. `None` if the log has no such event.
+///
+/// Damage is attributed to the occurrence whose `IndustryLossEstimate` closes it, and claims
+/// to the damage of the same insured on the same day, so an insured struck twice on one day
+/// has its claims credited to the first occurrence.
+pub fn event_postmortem(events: &[SimEvent], event_id: u64) -> Option<EventPostmortem> {
+    let mut report: Option<EventPostmortem> = None;
+    let mut policy_insured: HashMap<PolicyId, InsuredId> = HashMap::new();
+    // Occurrences fired on the current day whose damage is still being routed, in order.
+    let mut open: Vec<u64> = Vec::new();
+    let mut open_day = Day(0);
+    let mut struck: HashSet<InsuredId> = HashSet::new();
+    let mut impacts: BTreeMap<InsurerId, InsurerEventImpact> = BTreeMap::new();
+    // (premium, sum insured) bound per year, and published index rates.
+    let mut bound: BTreeMap<u32, (u64, u64)> = BTreeMap::new();
+    let mut index_rates: BTreeMap<u32, f64> = BTreeMap::new();
+    let mut territory_names: &[String] = &[];
+
+    for sim_event in events {
+        let day = sim_event.day;
+        if day != open_day {
+            open.clear();
+            open_day = day;
+        }
+        let on_event_day = report.as_ref().is_some_and(|r| r.day == day);
+        match &sim_event.event {
+            Event::RunMetadata(metadata) => territory_names = &metadata.territories,
+            Event::PolicyBound { policy_id, insured_id, premium, sum_insured, .. } => {
+                policy_insured.insert(*policy_id, *insured_id);
+                let b = bound.entry(day.year().0).or_default();
+                b.0 += premium;
+                b.1 += sum_insured;
+            }
+            Event::RateIndexPublished { year, rate_on_line, .. } => {
+                index_rates.insert(year.0, *rate_on_line);
+            }
+            Event::LossEvent { event_id: id, peril, territory, damage_fraction, .. } => {
+                open.push(*id);
+                if *id == event_id && report.is_none() {
+                    report = Some(EventPostmortem {
+                        event_id,
+                        day,
+                        peril: *peril,
+                        territory: territory_names
+                            .get(territory.0 as usize)
+                            .map_or_else(|| territory.to_string(), String::clone),
+                        damage_fraction: *damage_fraction,
+                        insureds_struck: 0,
+                        total_gul: 0,
+                        insured_loss: 0,
+                        insurers: Vec::new(),
+                        rates: Vec::new(),
+                    });
+                }
+            }
+            Event::IndustryLossEstimate { event_id: id, .. } => open.retain(|o| o != id),
+            Event::AssetDamage { insured_id, peril, ground_up_loss }
+                if peril.is_cat() && open.first() == Some(&event_id) && on_event_day =>
+            {
+                let r = report.as_mut().expect("open event has a report");
+                if struck.insert(*insured_id) {
+                    r.insureds_struck += 1;
+                }
+                r.total_gul += ground_up_loss;
+            }
+            Event::ClaimSettled { policy_id, insurer_id, amount, peril, remaining_capital }
+                if on_event_day
+                    && peril.is_cat()
+                    && policy_insured.get(policy_id).is_some_and(|i| struck.contains(i)) =>
+            {
+                let impact = impacts.entry(*insurer_id).or_insert(InsurerEventImpact {
+                    insurer_id: *insurer_id,
+                    claims: 0,
+                    claim_count: 0,
+                    capital_before: Some(remaining_capital + amount),
+                    capital_after: None,
+                    insolvent: false,
+                });
+                impact.claims += amount;
+                impact.claim_count += 1;
+                impact.capital_after = Some(*remaining_capital);
+            }
+            Event::ClaimReported { policy_id, insurer_id, amount, peril, .. }
+                if on_event_day
+                    && peril.is_cat()
+                    && policy_insured.get(policy_id).is_some_and(|i| struck.contains(i)) =>
+            {
+                let impact = impacts.entry(*insurer_id).or_insert(InsurerEventImpact {
+                    insurer_id: *insurer_id,
+                    claims: 0,
+                    claim_count: 0,
+                    capital_before: None,
+                    capital_after: None,
+                    insolvent: false,
+                });
+                impact.claims += amount;
+                impact.claim_count += 1;
+            }
+            Event::InsurerInsolvent { insurer_id } if on_event_day => {
+                if let Some(impact) = impacts.get_mut(insurer_id) {
+                    impact.insolvent = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut report = report?;
+    let event_year = report.day.year().0;
+    let years: BTreeSet<u32> = bound.keys().chain(index_rates.keys()).copied().filter(|&y| y >= event_year).collect();
+    report.rates = years
+        .into_iter()
+        .filter_map(|year| {
+            let rate = if index_rates.is_empty() {
+                let &(premium, sum_insured) = bound.get(&year)?;
+                (sum_insured > 0).then(|| premium as f64 / sum_insured as f64)?
+            } else {
+                *index_rates.get(&year)?
+            };
+            Some((year, rate))
+        })
+        .collect();
+    report.insurers = impacts.into_values().collect();
+    report.insured_loss = report.insurers.iter().map(|i| i.claims).sum();
+    Some(report)
 }
 
 /// Check all 6 mechanics invariants. Returns one item per violation found.
@@ -535,12 +1748,17 @@ pub fn verify_mechanics(events: &[SimEvent]) -> Vec<MechanicsViolation> {
     // Per-submission tracking for the quoting chain and expiry timing.
     let mut lqr_day: HashMap<SubmissionId, u64> = HashMap::new();
     let mut qa_day: HashMap<SubmissionId, u64> = HashMap::new();
+    // Day of the latest lead answer: later than the request when rationing batches it.
+    let mut lead_answer_day: HashMap<SubmissionId, u64> = HashMap::new();
 
     // Per-policy tracking.
     let mut policy_from_sub: HashMap<SubmissionId, PolicyId> = HashMap::new();
     let mut expiry_day: HashMap<PolicyId, u64> = HashMap::new();
+    // Multi-year terms: instalments collected per policy, and policies cancelled before expiry.
+    let mut anniversaries: HashMap<PolicyId, u64> = HashMap::new();
+    let mut cancelled: HashSet<PolicyId> = HashSet::new();
 
-    // Per-insured tracking: first CoverageRequested day + sum_insured.
+    // Per-insured tracking: first CoverageRequested day + largest sum_insured.
     let mut insured_cr_day: HashMap<InsuredId, u64> = HashMap::new();
     let mut insured_sum_insured: HashMap<InsuredId, u64> = HashMap::new();
 
@@ -551,20 +1769,30 @@ pub fn verify_mechanics(events: &[SimEvent]) -> Vec<MechanicsViolation> {
         match &ev.event {
             Event::CoverageRequested { insured_id, risk } => {
                 insured_cr_day.entry(*insured_id).or_insert(day);
-                insured_sum_insured.entry(*insured_id).or_insert(risk.sum_insured);
+                // Largest value requested: under asset growth, losses follow the revalued asset.
+                insured_sum_insured
+                    .entry(*insured_id)
+                    .and_modify(|si| *si = (*si).max(risk.sum_insured))
+                    .or_insert(risk.sum_insured);
             }
             Event::LeadQuoteRequested { submission_id, .. } => {
                 lqr_day.entry(*submission_id).or_insert(day);
             }
+            Event::LeadQuoteIssued { submission_id, .. }
+            | Event::LeadQuotePartial { submission_id, .. }
+            | Event::LeadQuoteDeclined { submission_id, .. } => {
+                lead_answer_day.insert(*submission_id, day);
+            }
             Event::QuoteAccepted { submission_id, .. } => {
                 qa_day.insert(*submission_id, day);
             }
             Event::PolicyBound { policy_id, submission_id, .. } => {
                 policy_from_sub.insert(*submission_id, *policy_id);
 
-                // Invariant 1 — DayOffsetChain: PolicyBound must be lqr_day + 2.
+                // Invariant 1 — DayOffsetChain: PolicyBound must be lqr_day + 2, counted from the
+                // last lead answer when a rationing batch held the request.
                 if let Some(&lqr) = lqr_day.get(submission_id) {
-                    let expected = lqr + 2;
+                    let expected = lead_answer_day.get(submission_id).copied().unwrap_or(lqr).max(lqr) + 2;
                     if day != expected {
                         violations.push(MechanicsViolation::DayOffsetChain {
                             submission_id: submission_id.0,
@@ -578,15 +1806,24 @@ pub fn verify_mechanics(events: &[SimEvent]) -> Vec<MechanicsViolation> {
             Event::PolicyExpired { policy_id } => {
                 expiry_day.insert(*policy_id, day);
             }
+            Event::PolicyAnniversary { policy_id, premium, .. } if *premium > 0 => {
+                *anniversaries.entry(*policy_id).or_insert(0) += 1;
+            }
+            Event::PolicyCancelled { policy_id, .. } => {
+                cancelled.insert(*policy_id);
+            }
             _ => {}
         }
     }
 
-    // Check PolicyExpiredTiming: expected = qa_day + 361.
+    // Check PolicyExpiredTiming: expected = qa_day + 361, plus 360 per collected anniversary.
+    // A policy cancelled at an anniversary stops collecting, so its expiry may fall any whole
+    // number of years later.
     for (sub_id, pid) in &policy_from_sub {
         if let (Some(&qa), Some(&actual)) = (qa_day.get(sub_id), expiry_day.get(pid)) {
-            let expected = qa + 361;
-            if actual != expected {
+            let expected = qa + 361 + 360 * anniversaries.get(pid).copied().unwrap_or(0);
+            let whole_years_later = actual > expected && (actual - expected) % 360 == 0;
+            if actual != expected && !(cancelled.contains(pid) && whole_years_later) {
                 violations.push(MechanicsViolation::PolicyExpiredTiming {
                     policy_id: pid.0,
                     expected,
@@ -649,13 +1886,13 @@ pub fn verify_mechanics(events: &[SimEvent]) -> Vec<MechanicsViolation> {
                     }
                 }
             }
-            // Invariant 7 — InvalidDamageFraction: LossEvent.damage_fraction must be in (0, 1].
-            Event::LossEvent { event_id, damage_fraction, .. } => {
-                if *damage_fraction <= 0.0 || *damage_fraction > 1.0 {
-                    violations.push(MechanicsViolation::InvalidDamageFraction {
-                        event_id: *event_id,
-                        damage_fraction: *damage_fraction,
-                    });
+            // Invariant 7 — InvalidDamageFraction: LossEvent.damage_fraction, and each footprint
+            // territory's, must be in (0, 1].
+            Event::LossEvent { event_id, damage_fraction, footprint, .. } => {
+                for &d in std::iter::once(damage_fraction).chain(footprint.iter().map(|(_, d)| d)) {
+                    if d <= 0.0 || d > 1.0 {
+                        violations.push(MechanicsViolation::InvalidDamageFraction { event_id: *event_id, damage_fraction: d });
+                    }
                 }
             }
             _ => {}
@@ -700,6 +1937,20 @@ pub enum IntegrityViolation {
     /// Inv 24 — Same insurer appears in both LeadQuoteRequested and FollowerQuoteRequested
     /// for the same submission.
     InsurerBothLeadAndFollower { submission_id: u64, insurer_id: u64 },
+    /// Inv 25 — PolicyBound panel shares must sum to 100% (within half a basis point).
+    PanelSharesNotWhole { policy_id: u64, total_share: f64 },
+    /// Inv 26 — each panel member's claim on a loss must equal the policy's insured loss × its
+    /// signed line, within the cents the integer split can move to the leader.
+    ClaimShareMismatch { policy_id: u64, insurer_id: u64, day: u64, amount: u64, expected: u64 },
+    /// Inv 27 — followers write at the lead's rate, so the bound premium must equal
+    /// Σ lead premium × share over the panel.
+    PremiumSplitMismatch { policy_id: u64, premium: u64, expected: u64 },
+    /// Inv 28 — an `ExposureSnapshot` must equal Σ sum_insured × signed line over the insurer's
+    /// in-force policies in that territory covering that peril; a missing snapshot counts as 0.
+    ExposureSnapshotMismatch { day: u64, insurer_id: u64, territory: String, peril: String, snapshot: u64, live: u64 },
+    /// Inv 29 — with the capital ledger on, a capital reported for an insurer (`kind` names the
+    /// event) must equal its initial capital plus its `CapitalFlow`s, floored at zero.
+    CapitalLedgerMismatch { insurer_id: u64, day: u64, kind: String, reported: u64, ledger: i64 },
 }
 
 impl std::fmt::Display for IntegrityViolation {
@@ -756,6 +2007,21 @@ impl std::fmt::Display for IntegrityViolation {
             Self::InsurerBothLeadAndFollower { submission_id, insurer_id } => {
                 write!(f, "InsurerBothLeadAndFollower sub={submission_id} insurer={insurer_id}")
             }
+            Self::PanelSharesNotWhole { policy_id, total_share } => {
+                write!(f, "PanelSharesNotWhole policy={policy_id} total_share={total_share}")
+            }
+            Self::ClaimShareMismatch { policy_id, insurer_id, day, amount, expected } => {
+                write!(f, "ClaimShareMismatch policy={policy_id} insurer={insurer_id} day={day} amount={amount} expected={expected}")
+            }
+            Self::PremiumSplitMismatch { policy_id, premium, expected } => {
+                write!(f, "PremiumSplitMismatch policy={policy_id} premium={premium} expected={expected}")
+            }
+            Self::ExposureSnapshotMismatch { day, insurer_id, territory, peril, snapshot, live } => {
+                write!(f, "ExposureSnapshotMismatch day={day} insurer={insurer_id} territory={territory} peril={peril} snapshot={snapshot} live={live}")
+            }
+            Self::CapitalLedgerMismatch { insurer_id, day, kind, reported, ledger } => {
+                write!(f, "CapitalLedgerMismatch insurer={insurer_id} day={day} kind={kind} reported={reported} ledger={ledger}")
+            }
         }
     }
 }
@@ -787,15 +2053,75 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
     let mut follower_responses: HashMap<(SubmissionId, InsurerId), u32> = HashMap::new();
     let mut follower_orphan_responses: Vec<(SubmissionId, InsurerId, u64, String)> = Vec::new();
     let mut sub_lead_insurer: HashMap<SubmissionId, InsurerId> = HashMap::new();
+    // Panel tracking for Inv 25–27: each in-force policy's current lines (in bps, following
+    // portfolio transfers) and the claim runs split across them.
+    let mut lead_premium: HashMap<(SubmissionId, InsurerId), u64> = HashMap::new();
+    let mut live_panels: HashMap<PolicyId, Vec<(InsurerId, u32)>> = HashMap::new();
+    let mut claim_runs: Vec<ClaimRun> = Vec::new();
+    let mut in_claim_run = false;
+    let mut panel_violations: Vec<IntegrityViolation> = Vec::new();
+    // Exposure tracking for Inv 28: each insured's territory and perils, carried onto its
+    // policies at binding, and the expected snapshot set for the day being reported.
+    let mut insured_cover: HashMap<InsuredId, (Territory, Vec<Peril>)> = HashMap::new();
+    let mut policy_cover: HashMap<PolicyId, (u64, Territory, Vec<Peril>)> = HashMap::new();
+    let mut expected_snapshot: Option<(u64, BTreeMap<ExposureKey, u64>)> = None;
 
     for ev in events {
         let day = ev.day.0;
         if day > max_day {
             max_day = day;
         }
+        // A panel's claims on one loss are dispatched back to back (with claims development,
+        // interleaved with their own payments and reserves); any other event ends the run.
+        match &ev.event {
+            Event::ClaimSettled { policy_id, insurer_id, amount, .. }
+            | Event::ClaimReported { policy_id, insurer_id, amount, .. } => {
+                let extends = in_claim_run
+                    && claim_runs.last().is_some_and(|run| {
+                        run.policy_id == *policy_id && run.day == day && run.claims.iter().all(|c| c.0 != *insurer_id)
+                    });
+                if !extends {
+                    let panel = live_panels.get(policy_id).cloned().unwrap_or_default();
+                    claim_runs.push(ClaimRun { policy_id: *policy_id, day, panel, claims: vec![] });
+                }
+                if let Some(run) = claim_runs.last_mut() {
+                    run.claims.push((*insurer_id, *amount));
+                }
+                in_claim_run = true;
+            }
+            Event::ClaimPaid { .. } | Event::ReserveEstablished { .. } | Event::CapitalFlow { .. } => {}
+            _ => in_claim_run = false,
+        }
+        // A day's snapshots are logged back to back; compare them with the live book as a set.
+        if let Event::ExposureSnapshot { insurer_id, territory, peril, aggregate_sum_insured } = &ev.event {
+            if expected_snapshot.as_ref().is_none_or(|(d, _)| *d != day) {
+                flush_exposure_snapshot(expected_snapshot.take(), &mut panel_violations);
+                expected_snapshot = Some((day, live_exposure(&live_panels, &policy_cover)));
+            }
+            if let Some((_, expected)) = expected_snapshot.as_mut() {
+                let live = expected.remove(&(*insurer_id, *territory, *peril)).unwrap_or(0);
+                if live != *aggregate_sum_insured {
+                    panel_violations.push(IntegrityViolation::ExposureSnapshotMismatch {
+                        day,
+                        insurer_id: insurer_id.0,
+                        territory: territory.to_string(),
+                        peril: format!("{peril:?}"),
+                        snapshot: *aggregate_sum_insured,
+                        live,
+                    });
+                }
+            }
+        } else {
+            flush_exposure_snapshot(expected_snapshot.take(), &mut panel_violations);
+        }
         match &ev.event {
             Event::CoverageRequested { insured_id, risk } => {
-                insured_sum_insured.entry(*insured_id).or_insert(risk.sum_insured);
+                insured_cover.insert(*insured_id, (risk.territory, risk.perils_covered.clone()));
+                // Largest value requested: under asset growth, losses follow the revalued asset.
+                insured_sum_insured
+                    .entry(*insured_id)
+                    .and_modify(|si| *si = (*si).max(risk.sum_insured))
+                    .or_insert(risk.sum_insured);
             }
             Event::QuoteAccepted { submission_id, leader_id, .. } => {
                 sub_accepted_day.insert(*submission_id, day);
@@ -804,7 +2130,7 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
                 // panel member (highest relationship score).
                 sub_insurer_quoted.insert(*submission_id, *leader_id);
             }
-            Event::PolicyBound { policy_id, submission_id, panel, insured_id, sum_insured, .. } => {
+            Event::PolicyBound { policy_id, submission_id, panel, insured_id, sum_insured, premium } => {
                 policy_sum_insured.insert(*policy_id, *sum_insured);
                 // leader is the first panel member; used for PolicyBoundInsurerMismatch check.
                 if let Some((leader_id, _)) = panel.first() {
@@ -816,6 +2142,44 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
                 sub_policy.insert(*submission_id, *policy_id);
                 *policy_bind_count.entry(*policy_id).or_insert(0) += 1;
                 bound_policies.insert(*policy_id);
+
+                let total_share: f64 = panel.iter().map(|(_, share)| share).sum();
+                if (total_share - 1.0).abs() * FULL_LINE_BPS as f64 > 0.5 {
+                    panel_violations.push(IntegrityViolation::PanelSharesNotWhole { policy_id: policy_id.0, total_share });
+                }
+                if let Some(&lead) = panel.first().and_then(|(leader_id, _)| lead_premium.get(&(*submission_id, *leader_id))) {
+                    let expected = panel.iter().map(|(_, share)| (lead as f64 * share).round() as u64).sum::<u64>();
+                    if premium.abs_diff(expected) > panel.len() as u64 {
+                        panel_violations.push(IntegrityViolation::PremiumSplitMismatch {
+                            policy_id: policy_id.0,
+                            premium: *premium,
+                            expected,
+                        });
+                    }
+                }
+                let lines = panel.iter().map(|&(id, share)| (id, (share * FULL_LINE_BPS as f64).round() as u32)).collect();
+                live_panels.insert(*policy_id, lines);
+                if let Some((territory, perils)) = insured_cover.get(insured_id) {
+                    policy_cover.insert(*policy_id, (*sum_insured, *territory, perils.clone()));
+                }
+            }
+            Event::PolicyExpired { policy_id } | Event::PolicyCancelled { policy_id, .. } => {
+                live_panels.remove(policy_id);
+            }
+            Event::PortfolioTransferred(transfer) => {
+                let (seller_id, buyer_id) = (&transfer.seller_id, &transfer.buyer_id);
+                // Mirrors `Market::transfer_lines`: the seller's line merges into the buyer's.
+                for lines in live_panels.values_mut() {
+                    let Some(i) = lines.iter().position(|(id, _)| id == seller_id) else { continue };
+                    match lines.iter().position(|(id, _)| id == buyer_id) {
+                        Some(j) => {
+                            let (_, bps) = lines.remove(i);
+                            let j = if j > i { j - 1 } else { j };
+                            lines[j].1 += bps;
+                        }
+                        None => lines[i].0 = *buyer_id,
+                    }
+                }
             }
             Event::AssetDamage { insured_id, .. } => {
                 loss_keys.insert((day, *insured_id));
@@ -829,9 +2193,12 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
                 lead_requested.entry((*submission_id, *insurer_id)).or_insert(day);
                 sub_lead_insurer.entry(*submission_id).or_insert(*insurer_id);
             }
-            Event::LeadQuoteIssued { submission_id, insurer_id, .. } => {
+            Event::LeadQuoteIssued { submission_id, insurer_id, premium, .. }
+            | Event::LeadQuotePartial { submission_id, insurer_id, premium, .. } => {
+                lead_premium.insert((*submission_id, *insurer_id), *premium);
                 if !lead_requested.contains_key(&(*submission_id, *insurer_id)) {
-                    orphan_responses.push((*submission_id, *insurer_id, day, "LeadQuoteIssued".to_string()));
+                    let kind = if matches!(ev.event, Event::LeadQuotePartial { .. }) { "LeadQuotePartial" } else { "LeadQuoteIssued" };
+                    orphan_responses.push((*submission_id, *insurer_id, day, kind.to_string()));
                 }
                 *lead_responses.entry((*submission_id, *insurer_id)).or_insert(0) += 1;
                 sub_lead_issued.insert(*submission_id);
@@ -842,7 +2209,8 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
                 }
                 *lead_responses.entry((*submission_id, *insurer_id)).or_insert(0) += 1;
             }
-            Event::FollowerQuoteRequested { submission_id, insurer_id, .. } => {
+            Event::FollowerQuoteRequested { submission_id, insurer_id, .. }
+            | Event::RenewalOffered { submission_id, insurer_id, .. } => {
                 follower_requested.entry((*submission_id, *insurer_id)).or_insert(day);
             }
             Event::FollowerQuoteIssued { submission_id, insurer_id, .. } => {
@@ -851,9 +2219,11 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
                 }
                 *follower_responses.entry((*submission_id, *insurer_id)).or_insert(0) += 1;
             }
-            Event::FollowerQuoteDeclined { submission_id, insurer_id, .. } => {
+            Event::FollowerQuoteDeclined { submission_id, insurer_id, .. }
+            | Event::RenewalDeclined { submission_id, insurer_id, .. } => {
                 if !follower_requested.contains_key(&(*submission_id, *insurer_id)) {
-                    follower_orphan_responses.push((*submission_id, *insurer_id, day, "FollowerQuoteDeclined".to_string()));
+                    let kind = if matches!(ev.event, Event::RenewalDeclined { .. }) { "RenewalDeclined" } else { "FollowerQuoteDeclined" };
+                    follower_orphan_responses.push((*submission_id, *insurer_id, day, kind.to_string()));
                 }
                 *follower_responses.entry((*submission_id, *insurer_id)).or_insert(0) += 1;
             }
@@ -861,6 +2231,8 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
         }
     }
 
+    flush_exposure_snapshot(expected_snapshot, &mut panel_violations);
+
     let mut violations: Vec<IntegrityViolation> = Vec::new();
 
     // ── Claims (3) ────────────────────────────────────────────────────────────
@@ -1027,7 +2399,8 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
 
     // ── Follower Flow (4) ─────────────────────────────────────────────────────
 
-    // Inv 21: every FollowerQuoteRequested must have a prior LeadQuoteIssued for same sub.
+    // Inv 21: every FollowerQuoteRequested (or RenewalOffered) must have a prior LeadQuoteIssued
+    // for same sub.
     for (&(sub_id, ins_id), &req_day) in &follower_requested {
         if !sub_lead_issued.contains(&sub_id) {
             violations.push(IntegrityViolation::FollowerRequestWithoutLeadIssued {
@@ -1038,62 +2411,256 @@ pub fn verify_integrity(events: &[SimEvent]) -> Vec<IntegrityViolation> {
         }
     }
 
-    // Inv 22: at most one follower response per (submission, insurer).
-    for (&(sub_id, ins_id), &count) in &follower_responses {
-        if count > 1 {
-            violations.push(IntegrityViolation::FollowerDuplicateResponse {
-                submission_id: sub_id.0,
-                insurer_id: ins_id.0,
-                count,
-            });
-        }
+    // Inv 22: at most one follower response per (submission, insurer).
+    for (&(sub_id, ins_id), &count) in &follower_responses {
+        if count > 1 {
+            violations.push(IntegrityViolation::FollowerDuplicateResponse {
+                submission_id: sub_id.0,
+                insurer_id: ins_id.0,
+                count,
+            });
+        }
+    }
+
+    // Inv 23: every follower response needs a prior FollowerQuoteRequested.
+    for (sub_id, ins_id, orphan_day, kind) in follower_orphan_responses {
+        violations.push(IntegrityViolation::FollowerOrphanResponse {
+            submission_id: sub_id.0,
+            insurer_id: ins_id.0,
+            day: orphan_day,
+            kind,
+        });
+    }
+
+    // Inv 24: an insurer cannot be both the lead and a follower for the same submission.
+    for (&(sub_id, ins_id), _) in &follower_requested {
+        if sub_lead_insurer.get(&sub_id) == Some(&ins_id) {
+            violations.push(IntegrityViolation::InsurerBothLeadAndFollower {
+                submission_id: sub_id.0,
+                insurer_id: ins_id.0,
+            });
+        }
+    }
+
+    // ── Panel Split (3) ───────────────────────────────────────────────────────
+
+    // Inv 25 and 27 are checked at PolicyBound above, Inv 28 at each snapshot day.
+    violations.append(&mut panel_violations);
+
+    // Inv 26: each member's claim on a loss is the loss × its signed line. Members pay the
+    // floor of their share and the leader takes the residual, so no claim is off by more
+    // than the panel size in cents. Claims by insurers off the panel are Inv 5's concern.
+    for run in &claim_runs {
+        let loss: u64 = run.claims.iter().map(|c| c.1).sum();
+        let tolerance = run.panel.len() as u64;
+        for &(insurer_id, bps) in &run.panel {
+            let amount = run.claims.iter().find(|c| c.0 == insurer_id).map_or(0, |c| c.1);
+            let expected = (loss as u128 * bps as u128 / FULL_LINE_BPS as u128) as u64;
+            if amount.abs_diff(expected) > tolerance {
+                violations.push(IntegrityViolation::ClaimShareMismatch {
+                    policy_id: run.policy_id.0,
+                    insurer_id: insurer_id.0,
+                    day: run.day,
+                    amount,
+                    expected,
+                });
+            }
+        }
+    }
+
+    // ── Capital Ledger (1) ────────────────────────────────────────────────────
+
+    verify_capital_ledger(events, &mut violations);
+
+    violations
+}
+
+/// Inv 29: every capital the log reports for an insurer is its initial capital plus the
+/// `CapitalFlow`s logged for it, floored at zero. Skipped when the run kept no ledger.
+///
+/// Reports stamped by their own dispatch (`ClaimSettled`, `CatBondTriggered`, ...) must equal
+/// the balance once that dispatch's flows are logged. Snapshots queued mid-dispatch
+/// (`Event::capital_snapshot`: year-end reports, raises, exits) must equal the balance after
+/// exactly the flows made before they were queued: those with `queue_seq <= seq`.
+fn verify_capital_ledger(events: &[SimEvent], violations: &mut Vec<IntegrityViolation>) {
+    if !events.iter().any(|ev| matches!(ev.event, Event::CapitalFlow { .. })) {
+        return;
+    }
+    let mut ledgers: HashMap<InsurerId, CapitalLedger> = HashMap::new();
+    let mut pending: Vec<(InsurerId, u64, &'static str, u64)> = Vec::new();
+    for ev in events {
+        // A queued event opens a dispatch: the one before it has logged all its flows.
+        if ev.seq.is_some() {
+            for (insurer_id, day, kind, reported) in pending.drain(..) {
+                if let Some(ledger) = ledgers.get_mut(&insurer_id) {
+                    let balance = ledger.balance(day);
+                    check_capital_report(violations, insurer_id, day, kind, reported, balance);
+                }
+            }
+        }
+        let report = match &ev.event {
+            Event::InsurerCreated { insurer_id, initial_capital, .. }
+            | Event::InsurerEntered { insurer_id, initial_capital, .. } => {
+                // The initial insurers are announced twice on day 0.
+                ledgers.entry(*insurer_id).or_insert_with(|| CapitalLedger { day: ev.day.0, balances: vec![(0, *initial_capital as i64)] });
+                None
+            }
+            Event::CapitalFlow { insurer_id, amount, queue_seq, .. } => {
+                if let Some(ledger) = ledgers.get_mut(insurer_id) {
+                    let balance = ledger.balance(ev.day.0) + amount;
+                    ledger.balances.push((*queue_seq, balance));
+                }
+                None
+            }
+            Event::ClaimSettled { insurer_id, remaining_capital, .. } => Some((*insurer_id, "ClaimSettled", *remaining_capital)),
+            Event::CapitalRaised { insurer_id, remaining_capital, .. } => Some((*insurer_id, "CapitalRaised", *remaining_capital)),
+            Event::CatBondIssued(issue) => Some((issue.insurer_id, "CatBondIssued", issue.remaining_capital)),
+            Event::CatBondTriggered { insurer_id, remaining_capital, .. } => Some((*insurer_id, "CatBondTriggered", *remaining_capital)),
+            Event::ReinsurancePurchased { insurer_id, remaining_capital, .. } => {
+                Some((*insurer_id, "ReinsurancePurchased", *remaining_capital))
+            }
+            Event::ReinsuranceRecovered { insurer_id, remaining_capital, .. } => {
+                Some((*insurer_id, "ReinsuranceRecovered", *remaining_capital))
+            }
+            Event::InsurerExited { insurer_id, capital, .. } => Some((*insurer_id, "InsurerExited", *capital)),
+            Event::InsurerReEntered { insurer_id, capital } => Some((*insurer_id, "InsurerReEntered", *capital)),
+            Event::CapitalDistributed { insurer_id, remaining_capital, .. } => {
+                Some((*insurer_id, "CapitalDistributed", *remaining_capital))
+            }
+            Event::YearEndCapital { insurer_id, capital, .. } => Some((*insurer_id, "YearEndCapital", *capital)),
+            Event::SolvencyRatioReported { insurer_id, capital, .. } => Some((*insurer_id, "SolvencyRatioReported", *capital)),
+            _ => None,
+        };
+        let Some((insurer_id, kind, reported)) = report else { continue };
+        match (ev.event.capital_snapshot(), ev.seq) {
+            // Every flow made before the snapshot was queued is already logged.
+            (Some(_), Some(seq)) => {
+                if let Some(ledger) = ledgers.get_mut(&insurer_id) {
+                    let balance = ledger.balance_at(ev.day.0, seq);
+                    check_capital_report(violations, insurer_id, ev.day.0, kind, reported, balance);
+                }
+            }
+            _ => pending.push((insurer_id, ev.day.0, kind, reported)),
+        }
+    }
+    for (insurer_id, day, kind, reported) in pending {
+        if let Some(ledger) = ledgers.get_mut(&insurer_id) {
+            let balance = ledger.balance(day);
+            check_capital_report(violations, insurer_id, day, kind, reported, balance);
+        }
+    }
+}
+
+/// One insurer's capital rebuilt from its flows on `day`: each balance with the `queue_seq` of
+/// the flow that produced it, the first carried in from the day before.
+struct CapitalLedger {
+    day: u64,
+    balances: Vec<(u64, i64)>,
+}
+
+impl CapitalLedger {
+    /// The running balance on `day`, starting afresh from the closing balance on a new day.
+    fn balance(&mut self, day: u64) -> i64 {
+        if day != self.day {
+            let (_, closing) = self.balances[self.balances.len() - 1];
+            self.day = day;
+            self.balances = vec![(0, closing)];
+        }
+        self.balances[self.balances.len() - 1].1
+    }
+
+    /// The balance on `day` after the flows made before an event was queued with `seq`.
+    fn balance_at(&mut self, day: u64, seq: u64) -> i64 {
+        self.balance(day);
+        self.balances.iter().rev().find(|&&(queue_seq, _)| queue_seq <= seq).map_or(self.balances[0].1, |&(_, b)| b)
+    }
+}
+
+/// Inv 29: a reported capital must equal the ledger balance at its point in the log.
+fn check_capital_report(
+    violations: &mut Vec<IntegrityViolation>,
+    insurer_id: InsurerId,
+    day: u64,
+    kind: &'static str,
+    reported: u64,
+    ledger: i64,
+) {
+    if ledger.max(0) as u64 != reported {
+        violations.push(IntegrityViolation::CapitalLedgerMismatch { insurer_id: insurer_id.0, day, kind: kind.to_string(), reported, ledger });
     }
+}
 
-    // Inv 23: every follower response needs a prior FollowerQuoteRequested.
-    for (sub_id, ins_id, orphan_day, kind) in follower_orphan_responses {
-        violations.push(IntegrityViolation::FollowerOrphanResponse {
-            submission_id: sub_id.0,
-            insurer_id: ins_id.0,
-            day: orphan_day,
-            kind,
-        });
+/// (insurer, territory, peril) — the key of an `ExposureSnapshot`.
+type ExposureKey = (InsurerId, Territory, Peril);
+
+/// Live exposure rebuilt from the log, as `Market::exposure_by_insurer` computes it.
+fn live_exposure(
+    live_panels: &HashMap<PolicyId, Vec<(InsurerId, u32)>>,
+    policy_cover: &HashMap<PolicyId, (u64, Territory, Vec<Peril>)>,
+) -> BTreeMap<ExposureKey, u64> {
+    let mut exposure = BTreeMap::new();
+    for (policy_id, lines) in live_panels {
+        let Some((sum_insured, territory, perils)) = policy_cover.get(policy_id) else { continue };
+        for &(insurer_id, bps) in lines {
+            let line = sum_insured * bps as u64 / FULL_LINE_BPS as u64;
+            for &peril in perils {
+                *exposure.entry((insurer_id, *territory, peril)).or_default() += line;
+            }
+        }
     }
+    exposure
+}
 
-    // Inv 24: an insurer cannot be both the lead and a follower for the same submission.
-    for (&(sub_id, ins_id), _) in &follower_requested {
-        if sub_lead_insurer.get(&sub_id) == Some(&ins_id) {
-            violations.push(IntegrityViolation::InsurerBothLeadAndFollower {
-                submission_id: sub_id.0,
-                insurer_id: ins_id.0,
+/// Report the live exposures a finished snapshot day left out (Inv 28).
+fn flush_exposure_snapshot(expected: Option<(u64, BTreeMap<ExposureKey, u64>)>, violations: &mut Vec<IntegrityViolation>) {
+    let Some((day, expected)) = expected else { return };
+    for ((insurer_id, territory, peril), live) in expected {
+        if live > 0 {
+            violations.push(IntegrityViolation::ExposureSnapshotMismatch {
+                day,
+                insurer_id: insurer_id.0,
+                territory: territory.to_string(),
+                peril: format!("{peril:?}"),
+                snapshot: 0,
+                live,
             });
         }
     }
+}
 
-    violations
+/// Consecutive claims by a policy's panel on one day: the split of a single loss.
+struct ClaimRun {
+    policy_id: PolicyId,
+    day: u64,
+    /// The policy's signed lines when the loss was split.
+    panel: Vec<(InsurerId, u32)>,
+    claims: Vec<(InsurerId, u64)>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        events::{Event, Peril, Risk, SimEvent},
+        events::{Event, ExitReason, Peril, PortfolioTransfer, Risk, SCHEMA_VERSION, SimEvent},
         types::{Day, InsuredId, InsurerId, PolicyId, SubmissionId, Year},
     };
 
     fn sim_ev(day: u64, event: Event) -> SimEvent {
-        SimEvent { day: Day(day), event }
+        SimEvent { day: Day(day), seq: None, event }
     }
 
     fn dummy_risk() -> Risk {
         Risk {
             sum_insured: 1_000,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+            attachment: 0,
+            limit: 1_000,
         }
     }
 
     fn sim_start() -> SimEvent {
-        sim_ev(0, Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1 })
+        sim_ev(0, Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1, schema_version: SCHEMA_VERSION })
     }
 
     fn empty_capitals() -> HashMap<InsurerId, u64> {
@@ -1185,8 +2752,8 @@ mod tests {
         // Attritional AssetDamage must not increment cat_event_count.
         let events = vec![
             sim_start(),
-            sim_ev(50, Event::LossEvent { event_id: 1, peril: Peril::WindstormAtlantic, territory: "US-SE".to_string(), damage_fraction: 0.10 }),
-            sim_ev(80, Event::LossEvent { event_id: 2, peril: Peril::WindstormAtlantic, territory: "US-SE".to_string(), damage_fraction: 0.05 }),
+            sim_ev(50, Event::LossEvent { event_id: 1, peril: Peril::WindstormAtlantic, territory: Territory(0), damage_fraction: 0.10, footprint: vec![] }),
+            sim_ev(80, Event::LossEvent { event_id: 2, peril: Peril::WindstormAtlantic, territory: Territory(0), damage_fraction: 0.05, footprint: vec![] }),
             sim_ev(
                 80,
                 Event::AssetDamage {
@@ -1201,6 +2768,56 @@ mod tests {
         assert_eq!(stats[0].cat_event_count, 2);
     }
 
+    #[test]
+    fn premium_is_earned_pro_rata_over_the_cover_year() {
+        let bound = |day, policy, premium| {
+            sim_ev(day, Event::PolicyBound {
+                policy_id: PolicyId(policy),
+                submission_id: SubmissionId(policy),
+                insured_id: InsuredId(policy),
+                panel: vec![(InsurerId(1), 1.0)],
+                premium,
+                sum_insured: 100_000,
+            })
+        };
+        let events = vec![
+            sim_start(),
+            sim_ev(
+                90,
+                Event::PolicyBound {
+                    policy_id: PolicyId(2),
+                    submission_id: SubmissionId(2),
+                    insured_id: InsuredId(2),
+                    panel: vec![(InsurerId(1), 1.0)],
+                    premium: 360,
+                    sum_insured: 1_000,
+                },
+            ),
+            bound(180, 1, 3_600),
+            // Cancelled after 180 days of cover: half its premium is earned, the rest never is.
+            sim_ev(269, Event::PolicyCancelled {
+                policy_id: PolicyId(2),
+                insured_id: InsuredId(2),
+                reason: crate::events::CancellationReason::PremiumDefault,
+                basis: crate::events::CancellationBasis::ProRata,
+                premium_reversed: 180,
+                bad_debt: 0,
+            }),
+            sim_ev(359, Event::YearEnd { year: Year(1) }),
+            sim_ev(540, Event::PolicyExpired { policy_id: PolicyId(1) }),
+            sim_ev(719, Event::YearEnd { year: Year(2) }),
+        ];
+        let (_, stats) = analyse(&events, &empty_capitals(), 0.3);
+        // Policy 1, bound mid-year, earns half its premium in each year.
+        assert_eq!((stats[0].bound_premium, stats[0].earned_premium, stats[0].unearned_premium), (3_960, 1_980, 1_800));
+        assert_eq!((stats[1].bound_premium, stats[1].earned_premium, stats[1].unearned_premium), (0, 1_800, 0));
+
+        let mut growing = YearStats::zero(1);
+        (growing.bound_premium, growing.earned_premium, growing.claims) = (200, 100, 80);
+        assert!((growing.loss_ratio() - 0.4).abs() < 1e-12, "written basis flatters a growing book");
+        assert!((growing.loss_ratio_earned() - 0.8).abs() < 1e-12);
+    }
+
     #[test]
     fn test_capital_carry_forward() {
         // ClaimSettled in year 1 reduces capital to 800.
@@ -1251,6 +2868,28 @@ mod tests {
         assert_eq!(y4.insolvent_count, 0);
     }
 
+    #[test]
+    fn test_runoff_exits_and_reentries_adjust_active_count() {
+        let initials: HashMap<InsurerId, u64> = [(InsurerId(1), 1_000), (InsurerId(2), 1_000)].into();
+        let exited = |day| {
+            sim_ev(day, Event::InsurerExited { insurer_id: InsurerId(1), reason: ExitReason::CapitalFloor, capital: 400 })
+        };
+        let events = vec![
+            sim_start(),
+            exited(359),
+            sim_ev(359, Event::YearEnd { year: Year(1) }),
+            sim_ev(719, Event::InsurerReEntered { insurer_id: InsurerId(1), capital: 600 }),
+            sim_ev(719, Event::YearEnd { year: Year(2) }),
+            exited(900),
+            // An insurer that fails while in run-off has already left the active count.
+            sim_ev(1000, Event::InsurerInsolvent { insurer_id: InsurerId(1) }),
+            sim_ev(1079, Event::YearEnd { year: Year(3) }),
+        ];
+        let (_, stats) = analyse(&events, &initials, 0.344);
+        let counts: Vec<_> = stats.iter().map(|s| (s.exit_count, s.reentry_count, s.insurer_count)).collect();
+        assert_eq!(counts, vec![(1, 0, 1), (0, 1, 2), (1, 0, 1)]);
+    }
+
     #[test]
     fn test_warmup_years_excluded() {
         // SimulationStart with warmup_years=2 → years 1 and 2 must be absent.
@@ -1261,6 +2900,7 @@ mod tests {
                     year_start: Year(1),
                     warmup_years: 2,
                     analysis_years: 2,
+                    schema_version: SCHEMA_VERSION,
                 },
             ),
             sim_ev(359, Event::YearEnd { year: Year(1) }),
@@ -1284,6 +2924,428 @@ mod tests {
         assert!(stats.iter().any(|s| s.year == 3), "year 3 must be present");
     }
 
+    #[test]
+    fn concentration_metrics_follow_bound_line_share() {
+        let bound = |policy, panel| {
+            sim_ev(10, Event::PolicyBound {
+                policy_id: PolicyId(policy),
+                submission_id: SubmissionId(policy),
+                insured_id: InsuredId(policy),
+                panel,
+                premium: 100,
+                sum_insured: 1_000,
+            })
+        };
+        let events = vec![
+            sim_start(),
+            bound(1, vec![(InsurerId(1), 1.0)]),
+            bound(2, vec![(InsurerId(1), 1.0)]),
+            bound(3, vec![(InsurerId(2), 1.0)]),
+            bound(4, vec![(InsurerId(3), 0.5), (InsurerId(4), 0.5)]),
+            sim_ev(359, Event::YearEnd { year: Year(1) }),
+        ];
+        let (_, stats) = analyse(&events, &empty_capitals(), 0.344);
+        // Shares 1/2, 1/4, 1/8, 1/8.
+        assert_eq!(stats[0].active_writers, 4);
+        assert!((stats[0].herfindahl - 0.34375).abs() < 1e-12, "hhi = {}", stats[0].herfindahl);
+        assert!((stats[0].top3_share - 0.875).abs() < 1e-12, "top3 = {}", stats[0].top3_share);
+        assert!(stats[0].gini_market_share > 0.0);
+    }
+
+    #[test]
+    fn deflate_restates_money_in_year_one_values() {
+        let inflation = InflationConfig { asset_growth: 0.10, claims_inflation: 0.0 };
+        let mut s = YearStats::zero(3);
+        s.bound_premium = 1_210;
+        s.claims = 605;
+        s.transfer_price = -121;
+        s.cat_event_count = 2;
+        s.ap_tp_factor = 1.2;
+        let real = deflate(&[YearStats::zero(1), s], &inflation);
+        assert_eq!(real[1].bound_premium, 1_000, "1.1² index in year 3");
+        assert_eq!(real[1].claims, 500);
+        assert_eq!(real[1].transfer_price, -100);
+        assert_eq!(real[1].cat_event_count, 2, "counts are not deflated");
+        assert_eq!(real[1].ap_tp_factor, 1.2, "ratios are not deflated");
+        assert_eq!(real[1].loss_ratio(), 0.5);
+    }
+
+    #[test]
+    fn insured_panel_tracks_premium_losses_recoveries_and_cover_per_insured() {
+        let bound = |day, policy, insured| {
+            sim_ev(day, Event::PolicyBound {
+                policy_id: PolicyId(policy),
+                submission_id: SubmissionId(policy),
+                insured_id: InsuredId(insured),
+                panel: vec![(InsurerId(1), 1.0)],
+                premium: 100,
+                sum_insured: 1_000,
+            })
+        };
+        let damage = |day, insured, loss| {
+            sim_ev(day, Event::AssetDamage { insured_id: InsuredId(insured), peril: Peril::Attritional, ground_up_loss: loss })
+        };
+        let events = vec![
+            sim_start(),
+            sim_ev(0, Event::YearStart { year: Year(1) }),
+            sim_ev(0, Event::CoverageRequested { insured_id: InsuredId(1), risk: Box::new(dummy_risk()) }),
+            sim_ev(0, Event::CoverageRequested { insured_id: InsuredId(2), risk: Box::new(dummy_risk()) }),
+            bound(3, 1, 1),
+            damage(100, 1, 400),
+            damage(100, 2, 250),
+            sim_ev(100, Event::ClaimSettled {
+                policy_id: PolicyId(1),
+                insurer_id: InsurerId(1),
+                amount: 400,
+                peril: Peril::Attritional,
+                remaining_capital: 0,
+            }),
+            // Insured 1's policy runs into year 2 and expires there without renewal.
+            sim_ev(360, Event::YearStart { year: Year(2) }),
+            sim_ev(363, Event::PolicyExpired { policy_id: PolicyId(1) }),
+        ];
+
+        let panel = insured_panel(&events);
+        let row = |year, insured| panel.iter().find(|r| r.year == year && r.insured_id == InsuredId(insured));
+
+        let r = row(1, 1).expect("insured 1, year 1");
+        assert_eq!((r.premium_paid, r.ground_up_losses, r.recoveries, r.insured), (100, 400, 400, true));
+        let r = row(1, 2).expect("insured 2 requested cover and stayed uninsured");
+        assert_eq!((r.premium_paid, r.ground_up_losses, r.recoveries, r.insured), (0, 250, 0, false));
+        assert!(row(2, 1).expect("carried-over policy").insured);
+        assert!(row(2, 2).is_none(), "insured 2 did nothing in year 2");
+        assert!(panel.windows(2).all(|w| (w[0].year, w[0].insured_id) < (w[1].year, w[1].insured_id)));
+    }
+
+    #[test]
+    fn analyse_by_insured_splits_losses_by_peril_and_nets_recoveries() {
+        let damage = |day, peril, loss| {
+            sim_ev(day, Event::AssetDamage { insured_id: InsuredId(1), peril, ground_up_loss: loss })
+        };
+        let events = vec![
+            sim_start(),
+            sim_ev(3, Event::PolicyBound {
+                policy_id: PolicyId(1),
+                submission_id: SubmissionId(1),
+                insured_id: InsuredId(1),
+                panel: vec![(InsurerId(1), 1.0)],
+                premium: 100,
+                sum_insured: 1_000,
+            }),
+            damage(100, Peril::Attritional, 300),
+            damage(200, Peril::WindstormAtlantic, 500),
+            // Only the cat loss above the retention is recovered.
+            sim_ev(200, Event::ClaimSettled {
+                policy_id: PolicyId(1),
+                insurer_id: InsurerId(1),
+                amount: 350,
+                peril: Peril::WindstormAtlantic,
+                remaining_capital: 0,
+            }),
+            // Year 2: uninsured damage after the policy expired.
+            sim_ev(363, Event::PolicyExpired { policy_id: PolicyId(1) }),
+            damage(400, Peril::Attritional, 80),
+        ];
+
+        let by_insured = analyse_by_insured(&events);
+        let series = &by_insured[&InsuredId(1)];
+        assert_eq!(series.iter().map(|r| r.year).collect::<Vec<_>>(), vec![1, 2]);
+        let y1 = &series[0];
+        assert_eq!((y1.attr_gul, y1.cat_gul, y1.ground_up_losses), (300, 500, 800));
+        assert_eq!((y1.premium_paid, y1.recoveries, y1.uninsured_loss), (100, 350, 450));
+        let y2 = &series[1];
+        assert_eq!((y2.attr_gul, y2.recoveries, y2.uninsured_loss, y2.insured), (80, 0, 80, false));
+    }
+
+    #[test]
+    fn analyse_by_insurer_splits_premium_claims_and_capital_per_insurer() {
+        let claim = |day, insurer, amount, peril| {
+            sim_ev(day, Event::ClaimSettled {
+                policy_id: PolicyId(1),
+                insurer_id: InsurerId(insurer),
+                amount,
+                peril,
+                remaining_capital: 0,
+            })
+        };
+        let year_end_capital = |day, insurer, capital| {
+            sim_ev(day, Event::YearEndCapital {
+                insurer_id: InsurerId(insurer),
+                capital,
+                initial_capital: 10_000,
+                ytd_premium: 0,
+                ytd_claims: 0,
+                unearned_premium: 0,
+            })
+        };
+        let events = vec![
+            sim_start(),
+            sim_ev(3, Event::PolicyBound {
+                policy_id: PolicyId(1),
+                submission_id: SubmissionId(1),
+                insured_id: InsuredId(1),
+                panel: vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)],
+                premium: 1_000,
+                sum_insured: 10_000,
+            }),
+            claim(100, 1, 300, Peril::Attritional),
+            claim(200, 1, 900, Peril::WindstormAtlantic),
+            claim(200, 2, 600, Peril::WindstormAtlantic),
+            year_end_capital(359, 1, 9_400),
+            year_end_capital(359, 2, 9_800),
+            // Year 2: insurer 2 fails; insurer 1 writes nothing but reports capital.
+            sim_ev(400, Event::InsurerInsolvent { insurer_id: InsurerId(2) }),
+            year_end_capital(719, 1, 9_500),
+        ];
+
+        let by_insurer = analyse_by_insurer(&events);
+        let one = &by_insurer[&InsurerId(1)];
+        let two = &by_insurer[&InsurerId(2)];
+
+        assert_eq!((one[0].year, one[0].premium, one[0].policies_written), (1, 600, 1));
+        assert_eq!((one[0].claims, one[0].capital), (1_200, 9_400));
+        assert_eq!(one[0].loss_ratio(), 2.0);
+        assert_eq!(one[0].cat_share(), 0.75);
+        assert_eq!((two[0].premium, two[0].claims, two[0].cat_share()), (400, 600, 1.0));
+        assert_eq!((one[1].year, one[1].premium, one[1].capital), (2, 0, 9_500));
+        assert_eq!((two[1].year, two[1].capital), (2, 0), "capital path ends at zero on failure");
+        assert_eq!((one.len(), two.len()), (2, 2));
+    }
+
+    #[test]
+    fn analyse_by_broker_totals_commission_per_broker_and_year() {
+        let earned = |day, broker, premium| {
+            sim_ev(day, Event::BrokerageEarned {
+                broker_id: BrokerId(broker),
+                policy_id: PolicyId(day),
+                premium,
+                amount: premium / 5,
+            })
+        };
+        let events = vec![sim_start(), earned(3, 1, 1_000), earned(40, 1, 500), earned(41, 2, 2_000), earned(400, 1, 800)];
+
+        let by_broker = analyse_by_broker(&events);
+        let one = &by_broker[&BrokerId(1)];
+        assert_eq!(one[0], BrokerYearStats { year: 1, placements: 2, premium: 1_500, brokerage: 300 });
+        assert_eq!(one[1], BrokerYearStats { year: 2, placements: 1, premium: 800, brokerage: 160 });
+        assert_eq!(by_broker[&BrokerId(2)].len(), 1);
+    }
+
+    #[test]
+    fn submission_funnel_counts_each_stage_and_splits_declines_by_insurer() {
+        let routed = |sub| Event::SubmissionRouted { submission_id: SubmissionId(sub), insured_id: InsuredId(sub), broker_id: BrokerId(0) };
+        let declined = |sub, insurer, reason| Event::LeadQuoteDeclined {
+            submission_id: SubmissionId(sub),
+            insured_id: InsuredId(sub),
+            insurer_id: InsurerId(insurer),
+            reason,
+        };
+        let issued = |sub| Event::LeadQuoteIssued {
+            submission_id: SubmissionId(sub),
+            insured_id: InsuredId(sub),
+            insurer_id: InsurerId(2),
+            atp: 80,
+            premium: 100,
+            cat_exposure_at_quote: 0,
+            line_size: 1.0,
+        };
+        let presented = |sub| Event::QuotePresented {
+            submission_id: SubmissionId(sub),
+            insured_id: InsuredId(sub),
+            leader_id: InsurerId(2),
+            panel: vec![(InsurerId(2), 1.0)],
+            premium: 100,
+        };
+        let events = vec![
+            sim_start(),
+            // Submission 0: ins1 is full, ins2 quotes and the insured binds.
+            sim_ev(1, routed(0)),
+            sim_ev(2, declined(0, 1, DeclineReason::MaxCatAggregateBreached)),
+            sim_ev(2, issued(0)),
+            sim_ev(3, presented(0)),
+            sim_ev(3, Event::QuoteAccepted {
+                submission_id: SubmissionId(0),
+                insured_id: InsuredId(0),
+                leader_id: InsurerId(2),
+                panel: vec![(InsurerId(2), 1.0)],
+                premium: 100,
+            }),
+            sim_ev(4, Event::PolicyBound {
+                policy_id: PolicyId(0),
+                submission_id: SubmissionId(0),
+                insured_id: InsuredId(0),
+                panel: vec![(InsurerId(2), 1.0)],
+                premium: 100,
+                sum_insured: 1_000,
+            }),
+            // Submission 1: every lead declines.
+            sim_ev(10, routed(1)),
+            sim_ev(11, declined(1, 1, DeclineReason::MaxCatAggregateBreached)),
+            sim_ev(11, declined(1, 2, DeclineReason::RateBelowTP)),
+            sim_ev(11, Event::SubmissionDropped { submission_id: SubmissionId(1), insured_id: InsuredId(1) }),
+            // Submission 2: quoted but too dear.
+            sim_ev(20, routed(2)),
+            sim_ev(21, issued(2)),
+            sim_ev(22, presented(2)),
+            sim_ev(22, Event::QuoteRejected {
+                submission_id: SubmissionId(2),
+                insured_id: InsuredId(2),
+                reason: RejectionReason::ReservationPriceExceeded,
+            }),
+        ];
+
+        let funnel = submission_funnel(&events);
+        assert_eq!(funnel.len(), 1);
+        let y = &funnel[0];
+        assert_eq!((y.submissions, y.quoted, y.presented, y.accepted, y.rejected, y.bound, y.dropped), (3, 2, 2, 1, 1, 1, 1));
+        assert_eq!(y.declined(), 3);
+        assert_eq!(y.lead_declines[&DeclineReason::MaxCatAggregateBreached], 2);
+        assert_eq!(y.lead_declines_by_insurer[&InsurerId(1)][&DeclineReason::MaxCatAggregateBreached], 2);
+        assert_eq!(y.lead_declines_by_insurer[&InsurerId(2)][&DeclineReason::RateBelowTP], 1);
+        assert!((y.quote_to_bind() - 0.5).abs() < 1e-12);
+        assert!((y.bind_rate() - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn winners_curse_ranks_policies_into_spread_deciles_with_their_loss_ratios() {
+        // 20 policies against a median quote of 100, bound at 50, 55, …, 145: two per decile.
+        let mut events = vec![sim_start()];
+        for i in 0..20u64 {
+            events.push(sim_ev(i, Event::QuoteComparisonRecorded {
+                policy_id: PolicyId(i),
+                premium: 50 + 5 * i,
+                median_quote: 100,
+                quotes: vec![(InsurerId(1), 50 + 5 * i), (InsurerId(2), 100), (InsurerId(3), 150)],
+            }));
+        }
+        // The cheaper half is the business that claims.
+        for i in 0..10u64 {
+            let (policy_id, insurer_id, peril) = (PolicyId(i), InsurerId(1), Peril::Attritional);
+            events.push(sim_ev(100 + i, match i {
+                1 => Event::ClaimReported { claim_id: crate::types::ClaimId(i), policy_id, insurer_id, peril, amount: 100 },
+                _ => Event::ClaimSettled { policy_id, insurer_id, amount: 100, peril, remaining_capital: 0 },
+            }));
+        }
+        events.push(sim_ev(200, Event::PolicyAnniversary {
+            policy_id: PolicyId(19),
+            insured_id: InsuredId(19),
+            premium: 145,
+            sum_insured: 1_000,
+        }));
+
+        let deciles = winners_curse(&events);
+        assert_eq!(deciles.len(), 10);
+        assert!(deciles.iter().all(|d| d.policies == 2));
+        let first = &deciles[0];
+        assert_eq!((first.decile, first.premium, first.claims), (1, 105, 200));
+        assert!((first.min_spread + 0.5).abs() < 1e-12 && (first.max_spread + 0.45).abs() < 1e-12);
+        assert!((first.mean_spread + 0.475).abs() < 1e-12);
+        assert!(first.loss_ratio() > 1.0);
+        let last = &deciles[9];
+        assert_eq!((last.decile, last.premium, last.claims), (10, 140 + 145 + 145, 0));
+        assert_eq!(last.loss_ratio(), 0.0);
+
+        assert!(winners_curse(&[sim_start()]).is_empty());
+    }
+
+    #[test]
+    fn quarterly_stats_track_capital_between_quarter_ends_outside_warmup() {
+        let quarter = |day: u64, year: u32, quarter: u8, capital: u64| {
+            sim_ev(day, Event::QuarterEnd { year: Year(year), quarter, written_premium: 100, claims_incurred: 40, capital })
+        };
+        let events = vec![
+            sim_ev(0, Event::SimulationStart { year_start: Year(1), warmup_years: 1, analysis_years: 1, schema_version: SCHEMA_VERSION }),
+            sim_ev(0, Event::InsurerEntered {
+                insurer_id: InsurerId(1),
+                initial_capital: 1_000,
+                cr_sensitivity: 0.0,
+                capacity_sensitivity: 0.0,
+                market_weight_floor: 0.0,
+                cat_elf: 0.0,
+                profile: None,
+            }),
+            quarter(89, 1, 1, 1_050),
+            quarter(179, 1, 2, 1_100),
+            quarter(269, 1, 3, 900),
+            quarter(359, 1, 4, 950),
+            quarter(449, 2, 1, 700),
+        ];
+        let quarters = quarterly_stats(&events);
+        assert_eq!(quarters.len(), 1, "warmup quarters are excluded");
+        let q = &quarters[0];
+        assert_eq!((q.year, q.quarter, q.capital), (2, 1, 700));
+        assert_eq!(q.capital_change, -250, "measured from the last warmup quarter end");
+        assert!((q.loss_ratio() - 0.4).abs() < 1e-12);
+        assert!(quarterly_stats(&events[..2]).is_empty());
+    }
+
+    #[test]
+    fn event_postmortem_follows_one_occurrence_through_claims_and_rates() {
+        let bind = |day, policy, insured, premium| {
+            sim_ev(day, Event::PolicyBound {
+                policy_id: PolicyId(policy),
+                submission_id: SubmissionId(policy),
+                insured_id: InsuredId(insured),
+                panel: vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)],
+                premium,
+                sum_insured: 10_000,
+            })
+        };
+        let loss = |id| Event::LossEvent {
+            event_id: id,
+            peril: Peril::WindstormAtlantic,
+            territory: Territory(0),
+            damage_fraction: 0.1,
+            footprint: vec![],
+        };
+        let damage = |insured, peril, gul| Event::AssetDamage { insured_id: InsuredId(insured), peril, ground_up_loss: gul };
+        let claim = |policy, insurer, amount, remaining_capital| Event::ClaimSettled {
+            policy_id: PolicyId(policy),
+            insurer_id: InsurerId(insurer),
+            amount,
+            peril: Peril::WindstormAtlantic,
+            remaining_capital,
+        };
+        let events = vec![
+            sim_start(),
+            bind(3, 1, 1, 100),
+            bind(3, 2, 2, 100),
+            // Two same-day occurrences: 7 strikes insured 1, 8 strikes insured 2.
+            sim_ev(100, loss(7)),
+            sim_ev(100, loss(8)),
+            sim_ev(100, damage(1, Peril::WindstormAtlantic, 1_000)),
+            sim_ev(100, damage(2, Peril::Attritional, 50)),
+            sim_ev(100, Event::IndustryLossEstimate { event_id: 7, total_gul: 1_000, insured_loss: 0 }),
+            sim_ev(100, damage(2, Peril::WindstormAtlantic, 2_000)),
+            sim_ev(100, Event::IndustryLossEstimate { event_id: 8, total_gul: 2_000, insured_loss: 0 }),
+            sim_ev(100, claim(1, 1, 600, 9_400)),
+            sim_ev(100, claim(1, 2, 400, 0)),
+            sim_ev(100, claim(2, 1, 1_200, 8_200)),
+            sim_ev(100, Event::InsurerInsolvent { insurer_id: InsurerId(2) }),
+            bind(363, 3, 1, 150),
+        ];
+
+        let report = event_postmortem(&events, 7).expect("event 7 is in the log");
+        assert_eq!((report.insureds_struck, report.total_gul, report.insured_loss), (1, 1_000, 1_000));
+        assert_eq!(report.insurers.len(), 2);
+        let first = &report.insurers[0];
+        assert_eq!((first.claims, first.capital_before, first.capital_after), (600, Some(10_000), Some(9_400)));
+        assert!(!first.insolvent && report.insurers[1].insolvent);
+        assert_eq!(report.rates, vec![(1, 0.01), (2, 0.015)]);
+        assert!((report.rate_change(1).unwrap() - 0.5).abs() < 1e-12);
+        assert_eq!(report.rate_change(2), None);
+
+        assert_eq!(event_postmortem(&events, 8).unwrap().insured_loss, 1_200);
+        assert!(event_postmortem(&events, 9).is_none());
+
+        // Territory ids read as names once the log carries its RunMetadata header.
+        assert_eq!(report.territory, "0");
+        let header = crate::events::RunMetadata::capture(&crate::config::SimulationConfig::canonical());
+        let named: Vec<SimEvent> =
+            std::iter::once(sim_ev(0, Event::RunMetadata(Box::new(header)))).chain(events).collect();
+        assert_eq!(event_postmortem(&named, 7).unwrap().territory, "US-NE");
+    }
+
     // ── Mechanics invariant tests ─────────────────────────────────────────────
 
     /// Build a valid quoting chain (CoverageRequested → PolicyBound = 3 days).
@@ -1295,7 +3357,7 @@ mod tests {
         vec![
             sim_ev(
                 base_day,
-                Event::CoverageRequested { insured_id: InsuredId(1), risk: dummy_risk() },
+                Event::CoverageRequested { insured_id: InsuredId(1), risk: Box::new(dummy_risk()) },
             ),
             sim_ev(
                 base_day + 1,
@@ -1303,7 +3365,7 @@ mod tests {
                     submission_id,
                     insured_id: InsuredId(1),
                     insurer_id: InsurerId(1),
-                    risk: dummy_risk(),
+                    risk: Box::new(dummy_risk()),
                 },
             ),
             sim_ev(
@@ -1428,8 +3490,9 @@ mod tests {
             sim_ev(100, Event::LossEvent {
                 event_id: 99,
                 peril: Peril::WindstormAtlantic,
-                territory: "US-SE".to_string(),
+                territory: Territory(0),
                 damage_fraction: 0.0,
+                footprint: vec![],
             }),
         ];
         let violations = verify_mechanics(&events);
@@ -1446,8 +3509,9 @@ mod tests {
             sim_ev(100, Event::LossEvent {
                 event_id: 1,
                 peril: Peril::WindstormAtlantic,
-                territory: "US-SE".to_string(),
+                territory: Territory(0),
                 damage_fraction: 0.5,
+                footprint: vec![],
             }),
         ];
         let violations = verify_mechanics(&events);
@@ -1460,7 +3524,7 @@ mod tests {
     // ── Integration tests ─────────────────────────────────────────────────────
 
     fn small_test_config(seed: u64) -> crate::config::SimulationConfig {
-        use crate::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, SimulationConfig};
+        use crate::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, PricingMode, SimulationConfig};
         SimulationConfig {
             seed,
             years: 5,
@@ -1486,10 +3550,11 @@ mod tests {
                     payout_ratio: 0.0,
                     distribution_floor_multiple: 1.0,
                     leader_participation_cap: 1.0,
+                    appetite: vec![],
                 })
                 .collect(),
             n_insureds: 20,
-            attritional: AttritionalConfig { annual_rate: 2.0, mu: -4.7, sigma: 1.0 },
+            attritional: AttritionalConfig { annual_rate: 2.0, mu: -4.7, sigma: 1.0, contagion: 0.0, overrides: vec![] },
             catastrophe: CatConfig {
                 event_classes: vec![CatEventClass {
                     label: "test".to_string(),
@@ -1497,13 +3562,63 @@ mod tests {
                     pareto_scale: 0.04,
                     pareto_shape: 2.5,
                     max_damage_fraction: 1.0, // no truncation in tests
+                    footprint: vec![],
                 }],
                 territories: vec!["US-SE".to_string()],
+                season: None,
+                clustering: None,
+                catalogue: None,
             },
             quotes_per_submission: None,
             max_rol_mu: 0.0,
             max_rol_sigma: 0.0,
             disable_cats: false,
+            brokers: vec![],
+            quote_latency: None,
+            claims_development: None,
+            premium_finance: None,
+            retention: None,
+            investment: None,
+            demand: None,
+            market_intelligence: None,
+            rng: None,
+            runoff: None,
+            portfolio_transfer: None,
+            recapitalisation: None,
+            health: None,
+            exposure_snapshots: None,
+            territory_limits: None,
+            policy_terms: None,
+            inflation: None,
+            ils: None,
+            partial_lines: None,
+            portfolio: None,
+            aggregate_deductible: None,
+            solvency: None,
+            regulator: None,
+            loyalty: None,
+            expenses: None,
+            payment_terms: None,
+            pricing_mode: PricingMode::Adaptive,
+            scenario: None,
+            entrant_profiles: None,
+            entry_capacity: None,
+            brokerage: None,
+            mitigation: None,
+            rate_index: None,
+            pml_reporting: None,
+            large_loss: None,
+            reinsurance: None,
+            exposure_growth: None,
+            rationing: None,
+            rollover: None,
+            interest_rates: None,
+            casualty: None,
+            growth_targets: None,
+            quote_comparison: false,
+            risk_views: None,
+            capital_ledger: false,
+            quarterly_reporting: false,
         }
     }
 
@@ -1512,7 +3627,7 @@ mod tests {
         use crate::simulation::Simulation;
         for seed in [1u64, 2, 3] {
             let config = small_test_config(seed);
-            let mut sim = Simulation::from_config(config);
+            let mut sim = Simulation::from_config(config).unwrap();
             sim.start();
             sim.run();
             let mech = verify_mechanics(&sim.log);
@@ -1533,7 +3648,7 @@ mod tests {
                 submission_id: SubmissionId(1),
                 insured_id: InsuredId(1),
                 insurer_id: InsurerId(1),
-                risk: dummy_risk(),
+                risk: Box::new(dummy_risk()),
             },
         )];
         let violations = verify_integrity(&events);
@@ -1553,7 +3668,7 @@ mod tests {
                     submission_id: SubmissionId(1),
                     insured_id: InsuredId(1),
                     insurer_id: InsurerId(1),
-                    risk: dummy_risk(),
+                    risk: Box::new(dummy_risk()),
                 },
             ),
             sim_ev(
@@ -1610,6 +3725,255 @@ mod tests {
         );
     }
 
+    fn bound(panel: Vec<(InsurerId, f64)>, premium: u64) -> SimEvent {
+        sim_ev(
+            3,
+            Event::PolicyBound {
+                policy_id: PolicyId(1),
+                submission_id: SubmissionId(1),
+                insured_id: InsuredId(1),
+                panel,
+                premium,
+                sum_insured: 1_000,
+            },
+        )
+    }
+
+    fn lead_issued(premium: u64) -> SimEvent {
+        sim_ev(
+            1,
+            Event::LeadQuoteIssued {
+                submission_id: SubmissionId(1),
+                insured_id: InsuredId(1),
+                insurer_id: InsurerId(1),
+                atp: 90,
+                premium,
+                cat_exposure_at_quote: 0,
+                line_size: 0.6,
+            },
+        )
+    }
+
+    fn claim(insurer: u64, amount: u64) -> SimEvent {
+        sim_ev(
+            10,
+            Event::ClaimSettled {
+                policy_id: PolicyId(1),
+                insurer_id: InsurerId(insurer),
+                amount,
+                peril: Peril::Attritional,
+                remaining_capital: 0,
+            },
+        )
+    }
+
+    fn damage() -> SimEvent {
+        sim_ev(10, Event::AssetDamage { insured_id: InsuredId(1), peril: Peril::Attritional, ground_up_loss: 1_000 })
+    }
+
+    fn panel_violations(events: &[SimEvent]) -> Vec<IntegrityViolation> {
+        verify_integrity(events)
+            .into_iter()
+            .filter(|v| {
+                matches!(
+                    v,
+                    IntegrityViolation::PanelSharesNotWhole { .. }
+                        | IntegrityViolation::ClaimShareMismatch { .. }
+                        | IntegrityViolation::PremiumSplitMismatch { .. }
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_integrity_panel_split_holds_for_a_consistent_panel() {
+        let panel = vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)];
+        let events = vec![lead_issued(1_000), bound(panel, 1_000), damage(), claim(1, 601), claim(2, 399)];
+        let violations = panel_violations(&events);
+        assert!(violations.is_empty(), "leader may absorb the rounding residual, got: {violations:?}");
+    }
+
+    #[test]
+    fn test_integrity_panel_shares_must_sum_to_whole_line() {
+        let events = vec![bound(vec![(InsurerId(1), 0.6), (InsurerId(2), 0.3)], 900)];
+        let violations = panel_violations(&events);
+        assert!(
+            matches!(violations.as_slice(), [IntegrityViolation::PanelSharesNotWhole { policy_id: 1, .. }]),
+            "expected PanelSharesNotWhole, got: {violations:?}"
+        );
+    }
+
+    #[test]
+    fn test_integrity_claim_split_must_follow_lines() {
+        let panel = vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)];
+        let events = vec![bound(panel, 1_000), damage(), claim(1, 500), claim(2, 500)];
+        let violations = panel_violations(&events);
+        assert!(
+            violations
+                .iter()
+                .any(|v| matches!(v, IntegrityViolation::ClaimShareMismatch { insurer_id: 1, amount: 500, expected: 600, .. })),
+            "expected ClaimShareMismatch for the leader, got: {violations:?}"
+        );
+        assert_eq!(violations.len(), 2, "both members are off their line");
+    }
+
+    #[test]
+    fn test_integrity_bound_premium_must_be_lead_premium_across_panel() {
+        let panel = vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)];
+        let events = vec![lead_issued(1_000), bound(panel, 800)];
+        let violations = panel_violations(&events);
+        assert!(
+            matches!(violations.as_slice(), [IntegrityViolation::PremiumSplitMismatch { premium: 800, expected: 1_000, .. }]),
+            "expected PremiumSplitMismatch, got: {violations:?}"
+        );
+    }
+
+    #[test]
+    fn test_integrity_claim_split_follows_portfolio_transfer() {
+        // Insurer 2's line passes to insurer 1, which then pays the whole loss.
+        let panel = vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)];
+        let transfer = sim_ev(
+            5,
+            Event::PortfolioTransferred(Box::new(PortfolioTransfer {
+                seller_id: InsurerId(2),
+                buyer_id: InsurerId(1),
+                policies: 1,
+                unearned_premium: 0,
+                expected_losses: 0,
+                renewal_premium: 0,
+                franchise_value: 0,
+                retained_reserves: 0,
+                price: 0,
+            })),
+        );
+        let events = vec![bound(panel, 1_000), transfer, damage(), claim(1, 1_000)];
+        let violations = panel_violations(&events);
+        assert!(violations.is_empty(), "got: {violations:?}");
+    }
+
+    #[test]
+    fn test_integrity_exposure_snapshot_must_match_live_lines() {
+        let request = sim_ev(
+            0,
+            Event::CoverageRequested {
+                insured_id: InsuredId(1),
+                risk: Box::new(Risk {
+                    sum_insured: 1_000,
+                    territory: Territory(0),
+                    perils_covered: vec![Peril::Attritional],
+                    attachment: 0,
+                    limit: 1_000,
+                }),
+            },
+        );
+        let snapshot = |insurer: u64, aggregate_sum_insured: u64| {
+            sim_ev(
+                90,
+                Event::ExposureSnapshot {
+                    insurer_id: InsurerId(insurer),
+                    territory: Territory(0),
+                    peril: Peril::Attritional,
+                    aggregate_sum_insured,
+                },
+            )
+        };
+        let panel = vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)];
+        let mismatches = |events: &[SimEvent]| -> Vec<IntegrityViolation> {
+            verify_integrity(events)
+                .into_iter()
+                .filter(|v| matches!(v, IntegrityViolation::ExposureSnapshotMismatch { .. }))
+                .collect()
+        };
+
+        let events = vec![request.clone(), bound(panel.clone(), 1_000), snapshot(1, 600), snapshot(2, 400)];
+        assert!(mismatches(&events).is_empty());
+
+        let events = vec![request, bound(panel, 1_000), snapshot(1, 700)];
+        let violations = mismatches(&events);
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [
+                    IntegrityViolation::ExposureSnapshotMismatch { insurer_id: 1, snapshot: 700, live: 600, .. },
+                    IntegrityViolation::ExposureSnapshotMismatch { insurer_id: 2, snapshot: 0, live: 400, .. },
+                ]
+            ),
+            "expected a wrong and a missing snapshot, got: {violations:?}"
+        );
+    }
+
+    #[test]
+    fn test_integrity_reported_capital_must_match_the_capital_ledger() {
+        use crate::events::CapitalFlowReason;
+        let queued = |day: u64, seq: u64, event: Event| SimEvent { day: Day(day), seq: Some(seq), event };
+        let flow = |day: u64, reason: CapitalFlowReason, amount: i64, queue_seq: u64| {
+            sim_ev(day, Event::CapitalFlow { insurer_id: InsurerId(1), reason, amount, queue_seq })
+        };
+        let log = |claim_remaining: u64, year_end_capital: u64| {
+            vec![
+                sim_ev(0, Event::InsurerEntered {
+                    insurer_id: InsurerId(1),
+                    initial_capital: 1_000,
+                    cr_sensitivity: 0.0,
+                    capacity_sensitivity: 0.0,
+                    market_weight_floor: 0.0,
+                    cat_elf: 0.0,
+                    profile: None,
+                }),
+                queued(10, 1, Event::ClaimSettled {
+                    policy_id: PolicyId(1),
+                    insurer_id: InsurerId(1),
+                    amount: 300,
+                    peril: Peril::Attritional,
+                    remaining_capital: claim_remaining,
+                }),
+                flow(10, CapitalFlowReason::Claim, -300, 2),
+                // YearEnd queues the snapshot as seq 3, then raises capital and reports it as seq 4.
+                queued(359, 2, Event::YearEnd { year: Year(1) }),
+                flow(359, CapitalFlowReason::CapitalRaise, 200, 4),
+                queued(359, 3, Event::YearEndCapital {
+                    insurer_id: InsurerId(1),
+                    capital: year_end_capital,
+                    initial_capital: 1_000,
+                    ytd_premium: 0,
+                    ytd_claims: 300,
+                    unearned_premium: 0,
+                }),
+                queued(359, 4, Event::CapitalRaised { insurer_id: InsurerId(1), amount: 200, cost: 0, remaining_capital: 900 }),
+            ]
+        };
+        let mismatches = |events: &[SimEvent]| -> Vec<IntegrityViolation> {
+            verify_integrity(events)
+                .into_iter()
+                .filter(|v| matches!(v, IntegrityViolation::CapitalLedgerMismatch { .. }))
+                .collect()
+        };
+
+        assert!(mismatches(&log(700, 700)).is_empty(), "{:?}", mismatches(&log(700, 700)));
+
+        let violations = mismatches(&log(750, 700));
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [IntegrityViolation::CapitalLedgerMismatch { insurer_id: 1, day: 10, reported: 750, ledger: 700, .. }]
+            ),
+            "expected the tampered claim to be caught, got: {violations:?}"
+        );
+
+        // 900 is a balance the insurer held that day, but not when the snapshot was queued.
+        let violations = mismatches(&log(700, 900));
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [IntegrityViolation::CapitalLedgerMismatch { day: 359, reported: 900, ledger: 700, .. }]
+            ),
+            "expected the out-of-position snapshot to be caught, got: {violations:?}"
+        );
+
+        let unledgered: Vec<SimEvent> = log(750, 900).into_iter().filter(|e| !matches!(e.event, Event::CapitalFlow { .. })).collect();
+        assert!(mismatches(&unledgered).is_empty(), "a log without a ledger is not checked");
+    }
+
     // ── Distribution analysis tests ───────────────────────────────────────────
 
     #[test]
@@ -1675,6 +4039,31 @@ mod tests {
         assert_eq!(dists[0].year, 1);
     }
 
+    #[test]
+    fn analyse_distributions_tail_risk_and_ruin() {
+        // Total capital 1B..4B across four runs; runs 1 and 2 see insolvencies.
+        let run = |cap_b: u64, failed: &[u64]| {
+            let mut s = YearStats::zero(1);
+            s.total_capital = cap_b * 100_000_000_000;
+            s.insolvent_insurers = failed.iter().map(|&i| InsurerId(i)).collect();
+            s.insolvent_count = failed.len() as u32;
+            vec![s]
+        };
+        let all_runs = vec![run(1, &[1]), run(2, &[1, 2]), run(3, &[]), run(4, &[])];
+        let d = &analyse_distributions(&all_runs, 0.344)[0];
+
+        assert_eq!(d.p_insolvency, 0.5);
+        assert_eq!(d.insolvents.mean, 0.75, "expected insolvencies per run");
+        // VaR: h = 0.01 × 3 = 0.03 between 1B and 2B; TVaR: the single worst run.
+        assert!((d.cap_var99_b - 1.03).abs() < 1e-10, "VaR99 = {}", d.cap_var99_b);
+        assert_eq!(d.cap_tvar99_b, 1.0);
+
+        let ruin = ruin_probabilities(&all_runs);
+        assert_eq!(ruin.get(&InsurerId(1)), Some(&0.5));
+        assert_eq!(ruin.get(&InsurerId(2)), Some(&0.25));
+        assert_eq!(ruin.len(), 2, "insurers that never failed are absent");
+    }
+
     #[test]
     fn analyse_distributions_integration_small_config() {
         use crate::simulation::Simulation;
@@ -1688,7 +4077,7 @@ mod tests {
                 .map(|ic| (ic.id, ic.initial_capital as u64))
                 .collect();
             let expense = config.insurers.first().map(|ic| ic.expense_ratio).unwrap_or(0.344);
-            let mut sim = Simulation::from_config(config);
+            let mut sim = Simulation::from_config(config).unwrap();
             sim.start();
             sim.run();
             let (_, stats) = analyse(&sim.log, &initials, expense);
@@ -1712,7 +4101,7 @@ mod tests {
         for seed in [42u64, 43, 44, 45, 46] {
             let mut config = crate::config::SimulationConfig::canonical();
             config.seed = seed;
-            let mut sim = Simulation::from_config(config);
+            let mut sim = Simulation::from_config(config).unwrap();
             sim.start();
             sim.run();
             let mech = verify_mechanics(&sim.log);
@@ -1722,6 +4111,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn initial_conditions_are_read_from_the_log() {
+        use crate::simulation::Simulation;
+        let config = small_test_config(7);
+        let expected: HashMap<InsurerId, u64> =
+            config.insurers.iter().map(|ic| (ic.id, ic.initial_capital as u64)).collect();
+        let n_insureds = config.n_insureds;
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        assert_eq!(initial_capitals(&sim.log), expected);
+        assert_eq!(expense_ratio(&sim.log), Some(sim.config().insurers[0].expense_ratio));
+        let insureds = sim.log.iter().filter(|e| matches!(e.event, Event::InsuredCreated { .. })).count();
+        assert_eq!(insureds, n_insureds);
+
+        // Logged capitals take precedence over the caller's map.
+        let capitals = |caps: &HashMap<InsurerId, u64>| {
+            analyse(&sim.log, caps, 0.344).1.iter().map(|s| s.total_capital).collect::<Vec<_>>()
+        };
+        assert_eq!(capitals(&empty_capitals()), capitals(&expected));
+
+        // Older logs without InsurerCreated fall back to the Day(0) InsurerEntered events;
+        // later entrants are not initial insurers.
+        let mut legacy: Vec<SimEvent> =
+            sim.log.iter().filter(|e| !matches!(e.event, Event::InsurerCreated { .. })).cloned().collect();
+        legacy.push(sim_ev(
+            400,
+            Event::InsurerEntered {
+                insurer_id: InsurerId(999),
+                initial_capital: 1,
+                cr_sensitivity: 1.0,
+                capacity_sensitivity: 0.1,
+                market_weight_floor: 0.25,
+                cat_elf: 0.0,
+                profile: None,
+            },
+        ));
+        assert_eq!(initial_capitals(&legacy), expected);
+        assert_eq!(expense_ratio(&legacy), None);
+    }
+
     #[test]
     fn analyse_populates_sensitivity_means_from_event_stream() {
         // InsurerEntered at day 0 carries known sensitivity params.
@@ -1730,7 +4160,7 @@ mod tests {
         let events = vec![
             sim_ev(
                 0,
-                Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1 },
+                Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1, schema_version: SCHEMA_VERSION },
             ),
             sim_ev(
                 0,
@@ -1740,6 +4170,8 @@ mod tests {
                     cr_sensitivity: 1.5,
                     capacity_sensitivity: 0.12,
                     market_weight_floor: 0.25,
+                    cat_elf: 0.03,
+                    profile: None,
                 },
             ),
             sim_ev(359, Event::YearEnd { year: Year(1) }),
@@ -0,0 +1,471 @@
+//! Multi-run batches: run one config under consecutive seeds in parallel and collect the
+//! analysed output of every run.
+//!
+//! This is the engine behind `rins --runs N`, exposed so other binaries and notebook bindings
+//! can drive batches without going through the CLI. Runs execute on the rayon pool; results
+//! come back in seed order regardless of completion order.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::analysis::{self, YearDist, YearStats};
+use crate::config::{ConfigError, SimulationConfig};
+use crate::error::Error;
+use crate::events::{Event, RunMetadata, SimEvent};
+use crate::report::{self, InvariantCheck};
+use crate::simulation::Simulation;
+use crate::types::{Day, InsurerId};
+
+/// Expense ratio assumed when the config has no insurers.
+const DEFAULT_EXPENSE_RATIO: f64 = 0.344;
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// When set, writes `config.json` plus one `events_seed_{seed}.ndjson` per run here —
+    /// the layout `rins bundle` reads.
+    pub output_dir: Option<PathBuf>,
+    /// Run `report::invariant_checks` on every log; otherwise `BatchRun::checks` is empty.
+    pub invariant_checks: bool,
+    /// Analyse each run as it goes (`Simulation::aggregate_only`) and keep no log, so memory
+    /// stays flat however long the runs. No log means no `output_dir` (an error) and no
+    /// invariant checks (skipped).
+    pub aggregate_only: bool,
+}
+
+/// Reported once per run as it completes; runs finish in any order.
+#[derive(Debug, Clone)]
+pub struct BatchProgress {
+    pub seed: u64,
+    /// Runs finished so far, including this one.
+    pub completed: u64,
+    pub total: u64,
+    pub events: usize,
+    /// Where the run's event log was written, if `output_dir` was set.
+    pub log_path: Option<PathBuf>,
+    /// Events over all runs finished so far, including this one.
+    pub completed_events: u64,
+    /// Wall-clock time since the batch started.
+    pub elapsed: Duration,
+}
+
+impl BatchProgress {
+    /// Event throughput of the batch so far.
+    pub fn events_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 { self.completed_events as f64 / secs } else { 0.0 }
+    }
+
+    /// Time left if the remaining runs take as long on average as the finished ones.
+    pub fn eta(&self) -> Duration {
+        if self.completed == 0 {
+            return Duration::ZERO;
+        }
+        self.elapsed.mul_f64(self.total.saturating_sub(self.completed) as f64 / self.completed as f64)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchRun {
+    pub seed: u64,
+    pub events: usize,
+    pub stats: Vec<YearStats>,
+    pub checks: Vec<InvariantCheck>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchResults {
+    /// Seeds in run order; `runs[i]` used `seeds[i]`.
+    pub seeds: Vec<u64>,
+    pub expense_ratio: f64,
+    /// One entry per run, in seed order.
+    pub runs: Vec<BatchRun>,
+    /// Cross-run distributions per year; empty for fewer than 2 runs.
+    pub distributions: Vec<YearDist>,
+}
+
+impl BatchResults {
+    /// Per-run year statistics, in seed order.
+    pub fn stats(&self) -> Vec<Vec<YearStats>> {
+        self.runs.iter().map(|r| r.stats.clone()).collect()
+    }
+
+    /// Invariant results summed over every run.
+    pub fn merged_checks(&self) -> Vec<InvariantCheck> {
+        let checks: Vec<Vec<InvariantCheck>> = self.runs.iter().map(|r| r.checks.clone()).collect();
+        report::merge_checks(&checks)
+    }
+
+    /// Headline figures per run plus batch aggregates.
+    pub fn summary(&self) -> BatchSummary {
+        BatchSummary::new(self.runs.iter().map(|r| RunSummary::new(r.seed, &r.stats, self.expense_ratio)).collect())
+    }
+}
+
+/// One run's headline figures, for triaging outlier seeds (`rins --summary-json`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub seed: u64,
+    pub years: usize,
+    /// `InsurerInsolvent` events over the run.
+    pub insolvencies: u32,
+    pub cat_events: u32,
+    /// Year with the lowest year-end total capital, and that capital (cents). None without years.
+    pub min_capital_year: Option<u32>,
+    pub min_capital: u64,
+    /// Year with the highest combined ratio, and that ratio.
+    pub worst_cr_year: Option<u32>,
+    pub worst_combined_ratio: f64,
+}
+
+impl RunSummary {
+    pub fn new(seed: u64, stats: &[YearStats], expense_ratio: f64) -> Self {
+        let min_capital = stats.iter().min_by_key(|s| s.total_capital);
+        let worst_cr =
+            stats.iter().max_by(|a, b| a.combined_ratio(expense_ratio).total_cmp(&b.combined_ratio(expense_ratio)));
+        RunSummary {
+            seed,
+            years: stats.len(),
+            insolvencies: stats.iter().map(|s| s.insolvent_count).sum(),
+            cat_events: stats.iter().map(|s| s.cat_event_count).sum(),
+            min_capital_year: min_capital.map(|s| s.year),
+            min_capital: min_capital.map_or(0, |s| s.total_capital),
+            worst_cr_year: worst_cr.map(|s| s.year),
+            worst_combined_ratio: worst_cr.map_or(0.0, |s| s.combined_ratio(expense_ratio)),
+        }
+    }
+}
+
+/// Per-run summaries plus aggregates over the batch. The `*_seed` fields name the run that set
+/// each extreme, so outliers can be re-run with `--seeds`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchSummary {
+    pub n_runs: usize,
+    pub runs_with_insolvency: usize,
+    pub mean_insolvencies: f64,
+    pub mean_cat_events: f64,
+    pub min_capital: u64,
+    pub min_capital_seed: Option<u64>,
+    pub worst_combined_ratio: f64,
+    pub worst_cr_seed: Option<u64>,
+    pub runs: Vec<RunSummary>,
+}
+
+impl BatchSummary {
+    pub fn new(runs: Vec<RunSummary>) -> Self {
+        let n = runs.len().max(1) as f64;
+        let min_capital = runs.iter().filter(|r| r.min_capital_year.is_some()).min_by_key(|r| r.min_capital);
+        let worst_cr = runs
+            .iter()
+            .filter(|r| r.worst_cr_year.is_some())
+            .max_by(|a, b| a.worst_combined_ratio.total_cmp(&b.worst_combined_ratio));
+        BatchSummary {
+            n_runs: runs.len(),
+            runs_with_insolvency: runs.iter().filter(|r| r.insolvencies > 0).count(),
+            mean_insolvencies: runs.iter().map(|r| r.insolvencies as f64).sum::<f64>() / n,
+            mean_cat_events: runs.iter().map(|r| r.cat_events as f64).sum::<f64>() / n,
+            min_capital: min_capital.map_or(0, |r| r.min_capital),
+            min_capital_seed: min_capital.map(|r| r.seed),
+            worst_combined_ratio: worst_cr.map_or(0.0, |r| r.worst_combined_ratio),
+            worst_cr_seed: worst_cr.map(|r| r.seed),
+            runs,
+        }
+    }
+}
+
+/// Starting capital per insurer, as `analysis::analyse` expects it.
+pub fn initial_capitals(config: &SimulationConfig) -> HashMap<InsurerId, u64> {
+    config.insurers.iter().map(|ic| (ic.id, ic.initial_capital.max(0) as u64)).collect()
+}
+
+/// The expense ratio used for combined ratios: the first insurer's.
+pub fn expense_ratio(config: &SimulationConfig) -> f64 {
+    config.insurers.first().map(|ic| ic.expense_ratio).unwrap_or(DEFAULT_EXPENSE_RATIO)
+}
+
+/// Run `config` under seeds `config.seed .. config.seed + n_runs` in parallel.
+///
+/// `on_progress` is called from worker threads as each run finishes. Fails at once on an
+/// invalid config or if `output_dir` is set with `aggregate_only`, and otherwise on the first
+/// I/O error writing to `opts.output_dir`.
+pub fn run_batch(
+    config: &SimulationConfig,
+    n_runs: u64,
+    opts: &BatchOptions,
+    on_progress: impl Fn(BatchProgress) + Sync,
+) -> crate::Result<BatchResults> {
+    let seeds: Vec<u64> = (config.seed..config.seed + n_runs).collect();
+    run_seeds(config, &seeds, opts, on_progress)
+}
+
+/// Run `config` under exactly `seeds`, in parallel, e.g. to re-run the seeds that produced
+/// insolvencies in an earlier batch. Results come back in the order of `seeds`, which must be
+/// non-empty and distinct (each run's log is named by its seed). Fails as `run_batch` does.
+pub fn run_seeds(
+    config: &SimulationConfig,
+    seeds: &[u64],
+    opts: &BatchOptions,
+    on_progress: impl Fn(BatchProgress) + Sync,
+) -> crate::Result<BatchResults> {
+    let initial_capitals = initial_capitals(config);
+    let expense_ratio = expense_ratio(config);
+    let n_runs = seeds.len() as u64;
+
+    let mut distinct = seeds.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if seeds.is_empty() || distinct.len() < seeds.len() {
+        return Err(Error::Config(vec![ConfigError {
+            field: "seeds".to_string(),
+            message: "a batch needs at least one seed and no seed twice".to_string(),
+        }]));
+    }
+    if opts.aggregate_only && opts.output_dir.is_some() {
+        return Err(Error::Config(vec![ConfigError {
+            field: "BatchOptions.output_dir".to_string(),
+            message: "aggregate-only runs keep no event log to write".to_string(),
+        }]));
+    }
+    config.validate()?;
+    if let Some(ref dir) = opts.output_dir {
+        std::fs::create_dir_all(dir).map_err(Error::io(dir))?;
+        // Base config (seed = first seed) so `rins bundle` can hash and re-analyse the runs.
+        let json = serde_json::to_string_pretty(&SimulationConfig { seed: seeds[0], ..config.clone() })?;
+        let path = dir.join("config.json");
+        std::fs::write(&path, json).map_err(Error::io(path))?;
+    }
+
+    let started = Instant::now();
+    // (runs, events) finished so far, updated together so each report is consistent.
+    let completed = Mutex::new((0u64, 0u64));
+    let runs = seeds
+        .par_iter()
+        .map(|&seed| {
+            let mut run_config = config.clone();
+            run_config.seed = seed;
+            let mut sim = Simulation::from_config(run_config)?;
+            if opts.aggregate_only {
+                sim = sim.aggregate_only();
+            }
+            sim.start();
+            sim.run();
+            if let Some(e) = sim.schedule_error() {
+                return Err(e.clone().into());
+            }
+            let events = sim.events_logged() as usize;
+
+            let log_path = match opts.output_dir {
+                Some(ref dir) => {
+                    let path = dir.join(format!("events_seed_{seed}.ndjson"));
+                    write_log(&sim, &path)?;
+                    Some(path)
+                }
+                None => None,
+            };
+            let (runs_done, events_done) = {
+                let mut done = completed.lock().unwrap_or_else(|e| e.into_inner());
+                *done = (done.0 + 1, done.1 + events as u64);
+                *done
+            };
+            on_progress(BatchProgress {
+                seed,
+                completed: runs_done,
+                total: n_runs,
+                events,
+                log_path,
+                completed_events: events_done,
+                elapsed: started.elapsed(),
+            });
+
+            let checks =
+                if opts.invariant_checks && !opts.aggregate_only { report::invariant_checks(&sim.log) } else { vec![] };
+            let stats = match sim.year_stats() {
+                Some((_, stats)) => stats,
+                None => analysis::analyse(&sim.log, &initial_capitals, expense_ratio).1,
+            };
+            Ok(BatchRun { seed, events, stats, checks })
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let distributions = if runs.len() >= 2 {
+        let stats: Vec<Vec<YearStats>> = runs.iter().map(|r| r.stats.clone()).collect();
+        analysis::analyse_distributions(&stats, expense_ratio)
+    } else {
+        vec![]
+    };
+    Ok(BatchResults { seeds: seeds.to_vec(), expense_ratio, runs, distributions })
+}
+
+/// Parse a seed list: `u64`s separated by commas and/or whitespace, as given to `--seeds` or
+/// read from a `--seed-file` (one seed per line works, and `#` starts a comment).
+pub fn parse_seed_list(text: &str) -> Result<Vec<u64>, String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .flat_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().map_err(|_| format!("bad seed {s:?}")))
+        .collect()
+}
+
+/// Write `sim`'s log to `path` as NDJSON, headed by a `RunMetadata` line.
+pub fn write_log(sim: &Simulation, path: &Path) -> crate::Result<()> {
+    let mut writer = BufWriter::new(File::create(path).map_err(Error::io(path))?);
+    let header = SimEvent { day: Day(0), seq: None, event: Event::RunMetadata(Box::new(RunMetadata::capture(sim.config()))) };
+    for ev in std::iter::once(&header).chain(&sim.log) {
+        serde_json::to_writer(&mut writer, ev)?;
+        writeln!(writer).map_err(Error::io(path))?;
+    }
+    writer.flush().map_err(Error::io(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn small_config() -> SimulationConfig {
+        let mut config = SimulationConfig::canonical();
+        config.years = 2;
+        config.warmup_years = 0;
+        config.n_insureds = 20;
+        config.seed = 7;
+        config
+    }
+
+    #[test]
+    fn batch_returns_runs_in_seed_order_and_reports_each_one() {
+        let config = small_config();
+        let seen = Mutex::new(Vec::new());
+        let results = run_batch(&config, 3, &BatchOptions::default(), |p| {
+            seen.lock().unwrap().push((p.seed, p.completed, p.total, p.completed_events));
+        })
+        .unwrap();
+
+        assert_eq!(results.runs.iter().map(|r| r.seed).collect::<Vec<_>>(), vec![7, 8, 9]);
+        assert_eq!(results.distributions.len(), 2, "one YearDist per analysed year");
+        assert!(results.runs.iter().all(|r| r.checks.is_empty()), "checks are opt-in");
+
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen.iter().map(|s| s.0).collect::<Vec<_>>(), vec![7, 8, 9]);
+        let mut counts: Vec<u64> = seen.iter().map(|s| s.1).collect();
+        counts.sort();
+        assert_eq!(counts, vec![1, 2, 3]);
+        assert!(seen.iter().all(|s| s.2 == 3));
+        let all_events: usize = results.runs.iter().map(|r| r.events).sum();
+        assert_eq!(seen.iter().map(|s| s.3).max(), Some(all_events as u64), "events accumulate over runs");
+
+        // Each run matches a standalone simulation under its seed.
+        let mut solo = config.clone();
+        solo.seed = 8;
+        let mut sim = Simulation::from_config(solo).unwrap();
+        sim.start();
+        sim.run();
+        assert_eq!(results.runs[1].events, sim.log.len());
+        let stats = analysis::analyse(&sim.log, &initial_capitals(&config), expense_ratio(&config)).1;
+        assert_eq!(results.runs[1].stats.len(), stats.len());
+        assert_eq!(results.runs[1].stats[0].bound_premium, stats[0].bound_premium);
+    }
+
+    #[test]
+    fn aggregate_only_batches_match_and_refuse_to_write_logs() {
+        let config = small_config();
+        let full = run_batch(&config, 2, &BatchOptions::default(), |_| {}).unwrap();
+        let opts = BatchOptions { aggregate_only: true, invariant_checks: true, ..BatchOptions::default() };
+        let lean = run_batch(&config, 2, &opts, |_| {}).unwrap();
+        for (a, b) in full.runs.iter().zip(&lean.runs) {
+            assert_eq!(a.events, b.events);
+            crate::testing::assert_year_stats_match(&a.stats, &b.stats);
+        }
+        assert!(lean.runs.iter().all(|r| r.checks.is_empty()), "no log to check");
+
+        let opts = BatchOptions { output_dir: Some(std::env::temp_dir()), ..opts };
+        let err = run_batch(&config, 2, &opts, |_| {}).unwrap_err();
+        assert!(matches!(err, Error::Config(_)), "{err}");
+    }
+
+    #[test]
+    fn explicit_seed_lists_run_in_the_order_given() {
+        let config = small_config();
+        let results = run_seeds(&config, &[19, 5], &BatchOptions::default(), |_| {}).unwrap();
+        assert_eq!(results.seeds, vec![19, 5]);
+        assert_eq!(results.runs.iter().map(|r| r.seed).collect::<Vec<_>>(), vec![19, 5]);
+        let solo = run_batch(&SimulationConfig { seed: 5, ..config.clone() }, 1, &BatchOptions::default(), |_| {});
+        crate::testing::assert_year_stats_match(&results.runs[1].stats, &solo.unwrap().runs[0].stats);
+
+        assert!(matches!(run_seeds(&config, &[3, 3], &BatchOptions::default(), |_| {}), Err(Error::Config(_))));
+        assert!(matches!(run_seeds(&config, &[], &BatchOptions::default(), |_| {}), Err(Error::Config(_))));
+        assert_eq!(parse_seed_list("5,19, 1023\n# re-runs\n7 # insolvent\n"), Ok(vec![5, 19, 1023, 7]));
+        assert_eq!(parse_seed_list("5,x"), Err("bad seed \"x\"".to_string()));
+    }
+
+    #[test]
+    fn summary_names_each_runs_extremes_and_the_batch_outliers() {
+        let year = |year, total_capital, claims, insolvent_count, cat_event_count| YearStats {
+            total_capital,
+            claims,
+            bound_premium: 100,
+            insolvent_count,
+            cat_event_count,
+            ..YearStats::zero(year)
+        };
+        let a = RunSummary::new(3, &[year(1, 500, 50, 0, 1), year(2, 300, 120, 1, 2), year(3, 400, 60, 0, 0)], 0.3);
+        assert_eq!((a.insolvencies, a.cat_events, a.years), (1, 3, 3));
+        assert_eq!((a.min_capital_year, a.min_capital), (Some(2), 300));
+        assert_eq!(a.worst_cr_year, Some(2));
+        assert!((a.worst_combined_ratio - 1.5).abs() < 1e-12);
+
+        let b = RunSummary::new(9, &[year(1, 200, 10, 0, 0)], 0.3);
+        let batch = BatchSummary::new(vec![a, b]);
+        assert_eq!((batch.n_runs, batch.runs_with_insolvency), (2, 1));
+        assert_eq!((batch.min_capital, batch.min_capital_seed), (200, Some(9)));
+        assert_eq!(batch.worst_cr_seed, Some(3));
+        assert_eq!(batch.mean_cat_events, 1.5);
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!(json["runs"][1]["seed"], 9);
+    }
+
+    #[test]
+    fn progress_rate_and_eta_extrapolate_from_finished_runs() {
+        let p = BatchProgress {
+            seed: 1,
+            completed: 4,
+            total: 10,
+            events: 100,
+            log_path: None,
+            completed_events: 500,
+            elapsed: Duration::from_secs(2),
+        };
+        assert_eq!(p.events_per_sec(), 250.0);
+        assert_eq!(p.eta(), Duration::from_secs(3), "six runs left at half a second each");
+        assert_eq!(BatchProgress { completed: 10, ..p.clone() }.eta(), Duration::ZERO);
+        assert_eq!(BatchProgress { elapsed: Duration::ZERO, ..p }.events_per_sec(), 0.0);
+    }
+
+    #[test]
+    fn written_logs_lead_with_run_metadata() {
+        let config = small_config();
+        let mut sim = Simulation::from_config(config.clone()).unwrap();
+        sim.start();
+        sim.run();
+        let path = std::env::temp_dir().join(format!("rins_batch_header_{}.ndjson", std::process::id()));
+        write_log(&sim, &path).unwrap();
+        let log = crate::bundle::load_events(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let Event::RunMetadata(ref header) = log[0].event else {
+            panic!("first line is {:?}", log[0].event);
+        };
+        assert_eq!((header.seed, header.crate_version.as_str()), (7, env!("CARGO_PKG_VERSION")));
+        assert_eq!(header.config_hash, config.config_hash());
+        assert_eq!(header.config, serde_json::to_value(&config).unwrap(), "the full resolved config");
+        assert_eq!(log.len(), sim.log.len() + 1, "the header is not part of the simulation log");
+        assert_eq!(log[1], sim.log[0]);
+    }
+}
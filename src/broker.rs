@@ -1,14 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::events::{Event, Risk};
+use serde::{Deserialize, Serialize};
+
+use crate::events::{Event, Line, Risk, TerritoryIntelligence};
 use crate::insured::Insured;
-use crate::types::{Day, InsuredId, InsurerId, SubmissionId};
+use crate::types::{BrokerId, Day, InsuredId, InsurerId, SubmissionId, Territory, Year};
 
 /// Multiplicative decay applied to all relationship scores at each YearEnd.
 /// A score of 1.0 halves in ~3.1 years (0.80^3.1 ≈ 0.50).
 const SCORE_DECAY: f64 = 0.80;
 
 /// Transient state while a submission is in flight.
+#[derive(Clone, Serialize, Deserialize)]
 struct PendingQuote {
     insured_id: InsuredId,
     /// The risk submitted, needed to emit FollowerQuoteRequested.
@@ -29,25 +32,54 @@ struct PendingQuote {
     panel_lines: Vec<(InsurerId, u64, f64)>,
     /// Sum of offered line sizes received so far.
     accumulated_line: f64,
+    /// The lead wrote a partial line at its cat limit: its share is firm and only the
+    /// other lines are scaled to fill the residual.
+    firm_lead: bool,
+    /// Followers have been solicited on this submission; a later lead is not given them again.
+    followers_solicited: bool,
+    /// A loyal renewal: the incumbent lead and the highest premium the insured takes from it.
+    incumbent: Option<(InsurerId, u64)>,
+    /// Panel rollover: the expiring followers and their lines, offered again once the
+    /// incumbent lead re-prices. Emptied when the offers go out or the placement is re-marketed.
+    renewal_offers: Vec<(InsurerId, f64)>,
+    /// Candidates outside the expiring panel, solicited only for a shortfall left once the
+    /// renewal offers are answered.
+    shortfall_markets: Vec<InsurerId>,
 }
 
-/// Single broker that services all insureds.
+/// A broker servicing its own segment of insureds (all insureds when there is only one).
 /// Routes coverage requests to score-ranked insurers (incumbents get first look);
 /// assembles a panel of fractional lines, normalised to sum to 1.0.
 ///
 /// Quoting chain (lead-follow model):
-/// 1. `on_coverage_requested` → emits exactly one `LeadQuoteRequested` to the top scorer.
+/// 1. `on_coverage_requested` → emits `SubmissionRouted` and exactly one `LeadQuoteRequested`
+///    to the top scorer.
 /// 2. Lead issues → `on_lead_quote_issued` accumulates the lead's line, then emits
 ///    `FollowerQuoteRequested` for each remaining candidate.
 /// 3. Lead declines → `on_lead_quote_declined` advances `lead_candidate_idx` and retries
 ///    the next candidate at the **same day** (preserving Inv 1).
 /// 4. Followers respond via `on_follower_quote_issued` / `on_follower_quote_declined`.
 /// 5. Panel finalises when accumulated_line ≥ 1.0 or all followers have responded.
+///
+/// With partial lines, a lead at its cat limit issues via `on_lead_quote_partial`: its line is
+/// firm and the followers fill the residual. If none writes a line the next candidate leads.
+///
+/// A loyal renewal (`on_renewal_requested`) asks the incumbent to lead first; a quote above
+/// the insured's premium cap is set aside and the next candidate is asked to lead instead.
+///
+/// A rollover renewal (`on_rollover_requested`) goes further: once the incumbent lead re-prices
+/// within the cap, each expiring follower is sent `RenewalOffered` for its old line, and only
+/// the shortfall left by `RenewalDeclined` answers is marketed to new candidates as followers.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Broker {
+    pub id: BrokerId,
     pub insureds: Vec<Insured>,
     insurer_ids: Vec<InsurerId>,
     next_insurer_idx: usize,
     next_submission_id: u64,
+    /// Increment between successive submission ids. Brokers interleave their id
+    /// sequences (broker k of n issues k, k+n, k+2n, …) so ids stay market-unique.
+    submission_id_stride: u64,
     pending: HashMap<SubmissionId, PendingQuote>,
     /// Number of insurers solicited per submission (≥ 1, ≤ insurer_ids.len()).
     quotes_per_submission: usize,
@@ -55,6 +87,15 @@ pub struct Broker {
     pub relationship_scores: HashMap<InsurerId, f64>,
     /// Count of declines received from each insurer since the last YearEnd.
     decline_counts: HashMap<InsurerId, f64>,
+    /// Lead quoted property rates on line by territory since the last YearEnd (market intelligence).
+    quoted_rates: BTreeMap<Territory, Vec<f64>>,
+    /// Quote responses (issued + declined, lead and follower) since the last YearEnd.
+    quote_responses: u32,
+    /// Of which declines.
+    quote_declines: u32,
+    /// Commission earned on placed premium since the start of the run (cents; brokerage only).
+    #[serde(default)]
+    pub brokerage_earned: u64,
 }
 
 impl Broker {
@@ -66,17 +107,32 @@ impl Broker {
             decline_counts.insert(id, 0.0);
         }
         Broker {
+            id: BrokerId(0),
             insureds,
             insurer_ids,
             next_insurer_idx: 0,
             next_submission_id: 0,
+            submission_id_stride: 1,
             pending: HashMap::new(),
             quotes_per_submission,
             relationship_scores,
             decline_counts,
+            quoted_rates: BTreeMap::new(),
+            quote_responses: 0,
+            quote_declines: 0,
+            brokerage_earned: 0,
         }
     }
 
+    /// Identify this broker as `index` of `n_brokers`. Submission ids are interleaved
+    /// across brokers so that every id in the event stream is unique.
+    pub fn with_id(mut self, id: BrokerId, index: u64, n_brokers: u64) -> Self {
+        self.id = id;
+        self.next_submission_id = index;
+        self.submission_id_stride = n_brokers.max(1);
+        self
+    }
+
     /// Add a new insurer to the routing pool.
     pub fn add_insurer(&mut self, id: InsurerId) {
         self.insurer_ids.push(id);
@@ -89,6 +145,18 @@ impl Broker {
         *self.relationship_scores.entry(insurer_id).or_insert(0.0) += 1.0;
     }
 
+    /// Commission earned on a placement (`BrokerageEarned`).
+    pub fn on_brokerage_earned(&mut self, amount: u64) {
+        self.brokerage_earned += amount;
+    }
+
+    /// Renewal rights passed from `from` to `to` in a portfolio transfer: `to` inherits the
+    /// relationship score and `from` starts again from zero.
+    pub fn transfer_relationship(&mut self, from: InsurerId, to: InsurerId) {
+        let score = self.relationship_scores.insert(from, 0.0).unwrap_or(0.0);
+        *self.relationship_scores.entry(to).or_insert(0.0) += score;
+    }
+
     /// Year ended. Decay all relationship scores by SCORE_DECAY and reset decline counts.
     pub fn on_year_end(&mut self) {
         for score in self.relationship_scores.values_mut() {
@@ -97,6 +165,61 @@ impl Broker {
         for count in self.decline_counts.values_mut() {
             *count = 0.0;
         }
+        self.quoted_rates.clear();
+        self.quote_responses = 0;
+        self.quote_declines = 0;
+    }
+
+    /// Digest this year's quoting activity into a `MarketIntelligence` event: average lead
+    /// quoted rate, decline rate across all quote responses, and per-territory rate mean and
+    /// dispersion. Call before `on_year_end`, which resets the accumulators.
+    pub fn market_intelligence(&self, day: Day, year: Year) -> (Day, Event) {
+        let territories: Vec<TerritoryIntelligence> = self
+            .quoted_rates
+            .iter()
+            .map(|(territory, rates)| {
+                let n = rates.len() as f64;
+                let mean = rates.iter().sum::<f64>() / n;
+                let dispersion = if rates.len() > 1 {
+                    (rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+                } else {
+                    0.0
+                };
+                TerritoryIntelligence {
+                    territory: *territory,
+                    quotes: rates.len() as u32,
+                    avg_rate: mean,
+                    rate_dispersion: dispersion,
+                }
+            })
+            .collect();
+        let quotes: u32 = territories.iter().map(|t| t.quotes).sum();
+        let avg_quoted_rate = if quotes > 0 {
+            territories.iter().map(|t| t.avg_rate * t.quotes as f64).sum::<f64>() / quotes as f64
+        } else {
+            0.0
+        };
+        let decline_rate = if self.quote_responses > 0 {
+            self.quote_declines as f64 / self.quote_responses as f64
+        } else {
+            0.0
+        };
+        (
+            day,
+            Event::MarketIntelligence {
+                broker_id: self.id,
+                year,
+                avg_quoted_rate,
+                quote_responses: self.quote_responses,
+                decline_rate,
+                territories,
+            },
+        )
+    }
+
+    /// Submissions this broker is still placing (quotes outstanding).
+    pub fn pending_submissions(&self) -> usize {
+        self.pending.len()
     }
 
     /// Return the relationship score for an insurer (None if never seen).
@@ -107,13 +230,57 @@ impl Broker {
     /// An insured has requested coverage. Solicits k insurers ordered by relationship score
     /// (descending); cyclic distance from `next_insurer_idx` breaks ties (round-robin fallback).
     ///
-    /// Emits exactly **one** `LeadQuoteRequested` to the top scorer. The full k-length candidate
-    /// list is stored so `on_lead_quote_declined` can retry the next candidate in order.
+    /// Emits `SubmissionRouted` (same day) followed by exactly **one** `LeadQuoteRequested` to the
+    /// top scorer. The full k-length candidate list is stored so `on_lead_quote_declined` can
+    /// retry the next candidate in order.
     pub fn on_coverage_requested(
         &mut self,
         day: Day,
         insured_id: InsuredId,
         risk: Risk,
+    ) -> Vec<(Day, Event)> {
+        self.route(day, insured_id, risk, None, vec![])
+    }
+
+    /// A loyal insured is renewing. Routes as `on_coverage_requested`, except that the
+    /// incumbent lead heads the candidate list; if it quotes above `max_premium` the insured
+    /// shops the market and the next candidate is asked to lead.
+    pub fn on_renewal_requested(
+        &mut self,
+        day: Day,
+        insured_id: InsuredId,
+        risk: Risk,
+        incumbent: InsurerId,
+        max_premium: u64,
+    ) -> Vec<(Day, Event)> {
+        self.route(day, insured_id, risk, Some((incumbent, max_premium)), vec![])
+    }
+
+    /// An insured with panel rollover is renewing. The expiring lead (first in `panel`) is asked
+    /// to re-price; within `max_premium` the other panel members are offered their lines again
+    /// and any shortfall goes to the top-scored candidates outside the panel. A lead that
+    /// declines or prices past the cap re-markets the placement as a loyal renewal would.
+    pub fn on_rollover_requested(
+        &mut self,
+        day: Day,
+        insured_id: InsuredId,
+        risk: Risk,
+        panel: Vec<(InsurerId, f64)>,
+        max_premium: u64,
+    ) -> Vec<(Day, Event)> {
+        let Some(&(lead, _)) = panel.first() else {
+            return self.on_coverage_requested(day, insured_id, risk);
+        };
+        self.route(day, insured_id, risk, Some((lead, max_premium)), panel[1..].to_vec())
+    }
+
+    fn route(
+        &mut self,
+        day: Day,
+        insured_id: InsuredId,
+        risk: Risk,
+        incumbent: Option<(InsurerId, u64)>,
+        renewal_offers: Vec<(InsurerId, f64)>,
     ) -> Vec<(Day, Event)> {
         let n = self.insurer_ids.len();
         if n == 0 {
@@ -142,10 +309,31 @@ impl Broker {
         });
 
         let submission_id = SubmissionId(self.next_submission_id);
-        self.next_submission_id += 1;
+        self.next_submission_id += self.submission_id_stride;
 
         // Build the ordered candidate list (top k, score-sorted).
-        let candidates: Vec<InsurerId> = indices[..k].iter().map(|&j| self.insurer_ids[j]).collect();
+        let mut candidates: Vec<InsurerId> = indices[..k].iter().map(|&j| self.insurer_ids[j]).collect();
+        // A loyal renewal goes to the incumbent first, displacing the lowest-ranked candidate.
+        let incumbent = incumbent.filter(|(id, _)| self.insurer_ids.contains(id));
+        if let Some((id, _)) = incumbent {
+            candidates.retain(|&c| c != id);
+            candidates.insert(0, id);
+            candidates.truncate(k);
+        }
+        // A rollover keeps the expiring followers still in the pool; the remaining candidates
+        // only see the shortfall. Without the incumbent lead there is nothing to roll over.
+        let renewal_offers: Vec<(InsurerId, f64)> = match incumbent {
+            Some((lead, _)) => renewal_offers
+                .into_iter()
+                .filter(|(id, _)| *id != lead && self.insurer_ids.contains(id))
+                .collect(),
+            None => vec![],
+        };
+        let shortfall_markets: Vec<InsurerId> = if renewal_offers.is_empty() {
+            vec![]
+        } else {
+            candidates.iter().skip(1).filter(|c| !renewal_offers.iter().any(|(id, _)| id == *c)).copied().collect()
+        };
         let leader_id = candidates[0];
 
         self.pending.insert(
@@ -161,19 +349,27 @@ impl Broker {
                 follower_outstanding: 0,
                 panel_lines: vec![],
                 accumulated_line: 0.0,
+                firm_lead: false,
+                followers_solicited: false,
+                incumbent,
+                renewal_offers,
+                shortfall_markets,
             },
         );
 
-        // Emit exactly one LeadQuoteRequested for the top scorer.
-        vec![(
-            day.offset(1),
-            Event::LeadQuoteRequested {
-                submission_id,
-                insured_id,
-                insurer_id: leader_id,
-                risk,
-            },
-        )]
+        // Record the routing decision, then emit exactly one LeadQuoteRequested for the top scorer.
+        vec![
+            (day, Event::SubmissionRouted { submission_id, insured_id, broker_id: self.id }),
+            (
+                day.offset(1),
+                Event::LeadQuoteRequested {
+                    submission_id,
+                    insured_id,
+                    insurer_id: leader_id,
+                    risk: Box::new(risk),
+                },
+            ),
+        ]
     }
 
     /// Lead insurer has priced and issued a quote.
@@ -183,6 +379,9 @@ impl Broker {
     /// 3. Collect remaining candidates as followers; if none → finalise.
     /// 4. Otherwise set `follower_outstanding` and emit `FollowerQuoteRequested` for each,
     ///    at the **same day** as `LeadQuoteIssued` (D+1).
+    ///
+    /// On a loyal renewal, an incumbent quoting above the insured's premium cap is passed over
+    /// and the next candidate is asked to lead (same day).
     pub fn on_lead_quote_issued(
         &mut self,
         day: Day,
@@ -198,23 +397,64 @@ impl Broker {
             None => return vec![],
         };
 
+        self.quote_responses += 1;
+        // Territory rates are property rates; casualty quotes would blend in another line.
+        if pq.risk.line() == Line::Property {
+            self.quoted_rates
+                .entry(pq.risk.territory)
+                .or_default()
+                .push(premium as f64 / pq.risk.sum_insured as f64);
+        }
+
+        // The incumbent's renewal price rose past the insured's cap: shop the market.
+        if pq.incumbent.is_some_and(|(id, max_premium)| id == insurer_id && premium > max_premium) {
+            pq.incumbent = None;
+            pq.firm_lead = false;
+            return self.next_lead(day, submission_id);
+        }
+
         pq.panel_lines.push((insurer_id, premium, line_size));
         pq.accumulated_line += line_size;
         pq.lead_premium = Some(premium);
         pq.lead_atp = Some(atp);
 
         if pq.accumulated_line >= 1.0 {
-            let pq = self.pending.remove(&submission_id).unwrap();
-            return self.finalise_panel(day, submission_id, pq);
+            return self.complete_panel(day, submission_id);
         }
 
-        // Collect followers: remaining candidates after the current lead.
+        // Rollover: the incumbent re-priced within the cap, so the expiring panel is offered
+        // its lines again at the new premium before any new capacity is approached.
+        if !pq.renewal_offers.is_empty() && pq.incumbent.is_some_and(|(id, _)| id == insurer_id) {
+            let offers = std::mem::take(&mut pq.renewal_offers);
+            pq.follower_outstanding = offers.len();
+            pq.followers_solicited = true;
+            let insured_id = pq.insured_id;
+            return offers
+                .into_iter()
+                .map(|(follower_id, expiring_line)| {
+                    (
+                        day,
+                        Event::RenewalOffered {
+                            submission_id,
+                            insured_id,
+                            insurer_id: follower_id,
+                            risk: Box::new(pq.risk.clone()),
+                            lead_premium: premium,
+                            expiring_line,
+                        },
+                    )
+                })
+                .collect();
+        }
+
+        // Collect followers: remaining candidates after the current lead, unless a partial
+        // lead before this one already solicited them.
         let follower_start = pq.lead_candidate_idx + 1;
-        let follower_ids: Vec<InsurerId> = pq.candidates[follower_start..].to_vec();
+        let follower_ids: Vec<InsurerId> =
+            if pq.followers_solicited { vec![] } else { pq.candidates[follower_start..].to_vec() };
 
         if follower_ids.is_empty() {
-            let pq = self.pending.remove(&submission_id).unwrap();
-            return self.finalise_panel(day, submission_id, pq);
+            return self.complete_panel(day, submission_id);
         }
 
         let insured_id = pq.insured_id;
@@ -222,6 +462,7 @@ impl Broker {
         let lead_premium = premium;
         let lead_atp = atp;
         pq.follower_outstanding = follower_ids.len();
+        pq.followers_solicited = true;
 
         follower_ids
             .into_iter()
@@ -232,7 +473,7 @@ impl Broker {
                         submission_id,
                         insured_id,
                         insurer_id: follower_id,
-                        risk: risk.clone(),
+                        risk: Box::new(risk.clone()),
                         lead_premium,
                         lead_atp,
                     },
@@ -241,6 +482,28 @@ impl Broker {
             .collect()
     }
 
+    /// Lead insurer at its cat limit offered a partial line. Proceeds as `on_lead_quote_issued`
+    /// — the lead sets terms and the remaining candidates are solicited as followers — but the
+    /// lead's share is firm when the panel is finalised.
+    pub fn on_lead_quote_partial(
+        &mut self,
+        day: Day,
+        submission_id: SubmissionId,
+        insurer_id: InsurerId,
+        atp: u64,
+        premium: u64,
+        line_size: f64,
+    ) -> Vec<(Day, Event)> {
+        let insured_id = match self.pending.get_mut(&submission_id) {
+            Some(pq) => {
+                pq.firm_lead = true;
+                pq.insured_id
+            }
+            None => return vec![],
+        };
+        self.on_lead_quote_issued(day, submission_id, insured_id, insurer_id, atp, premium, line_size)
+    }
+
     /// Lead insurer declined. Retry with the next scored candidate as lead (same day),
     /// or emit `SubmissionDropped` if all candidates are exhausted.
     pub fn on_lead_quote_declined(
@@ -250,12 +513,23 @@ impl Broker {
         insurer_id: InsurerId,
     ) -> Vec<(Day, Event)> {
         *self.decline_counts.entry(insurer_id).or_insert(0.0) += 1.0;
+        self.quote_responses += 1;
+        self.quote_declines += 1;
+        self.next_lead(day, submission_id)
+    }
+
+    /// Advance to the next scored candidate and ask it to lead (same day), or emit
+    /// `SubmissionDropped` if all candidates are exhausted.
+    fn next_lead(&mut self, day: Day, submission_id: SubmissionId) -> Vec<(Day, Event)> {
         let pq = match self.pending.get_mut(&submission_id) {
             Some(pq) => pq,
             None => return vec![],
         };
 
         pq.lead_candidate_idx += 1;
+        // The placement is re-marketed: no panel to roll over, no separate shortfall.
+        pq.renewal_offers.clear();
+        pq.shortfall_markets.clear();
 
         if pq.lead_candidate_idx >= pq.candidates.len() {
             // All candidates exhausted.
@@ -278,7 +552,7 @@ impl Broker {
                 submission_id,
                 insured_id,
                 insurer_id: next_lead,
-                risk,
+                risk: Box::new(risk),
             },
         )]
     }
@@ -297,14 +571,16 @@ impl Broker {
             None => return vec![],
         };
 
+        self.quote_responses += 1;
         let lead_premium = pq.lead_premium.unwrap_or(0);
         pq.panel_lines.push((insurer_id, lead_premium, line_size));
         pq.accumulated_line += line_size;
         pq.follower_outstanding = pq.follower_outstanding.saturating_sub(1);
 
-        if pq.accumulated_line >= 1.0 || pq.follower_outstanding == 0 {
-            let pq = self.pending.remove(&submission_id).unwrap();
-            self.finalise_panel(day, submission_id, pq)
+        if pq.accumulated_line >= 1.0 {
+            self.complete_panel(day, submission_id)
+        } else if pq.follower_outstanding == 0 {
+            self.followers_answered(day, submission_id)
         } else {
             vec![]
         }
@@ -319,6 +595,8 @@ impl Broker {
         insurer_id: InsurerId,
     ) -> Vec<(Day, Event)> {
         *self.decline_counts.entry(insurer_id).or_insert(0.0) += 1.0;
+        self.quote_responses += 1;
+        self.quote_declines += 1;
         let pq = match self.pending.get_mut(&submission_id) {
             Some(pq) => pq,
             None => return vec![],
@@ -327,15 +605,60 @@ impl Broker {
         pq.follower_outstanding = pq.follower_outstanding.saturating_sub(1);
 
         if pq.follower_outstanding == 0 {
-            let pq = self.pending.remove(&submission_id).unwrap();
-            self.finalise_panel(day, submission_id, pq)
+            self.followers_answered(day, submission_id)
         } else {
             vec![]
         }
     }
 
+    /// Every solicited follower has answered. A rollover still short of a full line markets the
+    /// shortfall to the candidates outside the expiring panel (same day); otherwise complete.
+    fn followers_answered(&mut self, day: Day, submission_id: SubmissionId) -> Vec<(Day, Event)> {
+        let Some(pq) = self.pending.get_mut(&submission_id) else { return vec![] };
+        let markets = std::mem::take(&mut pq.shortfall_markets);
+        if markets.is_empty() || pq.accumulated_line >= 1.0 {
+            return self.complete_panel(day, submission_id);
+        }
+        pq.follower_outstanding = markets.len();
+        let (insured_id, lead_premium, lead_atp) = (pq.insured_id, pq.lead_premium.unwrap_or(0), pq.lead_atp.unwrap_or(0));
+        markets
+            .into_iter()
+            .map(|follower_id| {
+                (
+                    day,
+                    Event::FollowerQuoteRequested {
+                        submission_id,
+                        insured_id,
+                        insurer_id: follower_id,
+                        risk: Box::new(pq.risk.clone()),
+                        lead_premium,
+                        lead_atp,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Every line is in. A partial lead that drew no other line to fill its residual is
+    /// treated as a decline: the next candidate is asked to lead. Otherwise finalise.
+    fn complete_panel(&mut self, day: Day, submission_id: SubmissionId) -> Vec<(Day, Event)> {
+        let Some(pq) = self.pending.get_mut(&submission_id) else { return vec![] };
+        if pq.firm_lead && pq.accumulated_line <= pq.panel_lines[0].2 {
+            pq.panel_lines.clear();
+            pq.accumulated_line = 0.0;
+            pq.firm_lead = false;
+            pq.lead_premium = None;
+            pq.lead_atp = None;
+            return self.next_lead(day, submission_id);
+        }
+        let pq = self.pending.remove(&submission_id).unwrap();
+        self.finalise_panel(day, submission_id, pq)
+    }
+
     /// Trim panel lines to fill exactly 1.0, scale to normalise, then emit
     /// `QuotePresented` with blended premium — or `SubmissionDropped` if no lines.
+    /// A firm (partial) lead line keeps its share; the other lines are scaled to fill the
+    /// residual, and a panel with no other lines is dropped.
     ///
     /// Because all follower lines carry `lead_premium`, the blended premium equals
     /// `lead_premium` regardless of panel composition.
@@ -370,14 +693,22 @@ impl Broker {
 
         // Normalise so shares sum to exactly 1.0.
         let actual_total: f64 = included.iter().map(|&(_, _, l)| l).sum();
-        let panel: Vec<(InsurerId, f64)> = included.iter()
-            .map(|&(id, _, l)| (id, l / actual_total))
+        let firm = if pq.firm_lead { included[0].2 } else { 0.0 };
+        let scalable = actual_total - firm;
+        if scalable <= 0.0 {
+            return vec![(day.offset(1), Event::SubmissionDropped { submission_id, insured_id: pq.insured_id })];
+        }
+        let shares: Vec<f64> = included.iter().enumerate()
+            .map(|(i, &(_, _, l))| if pq.firm_lead && i == 0 { l } else { l * (1.0 - firm) / scalable })
+            .collect();
+        let panel: Vec<(InsurerId, f64)> = included.iter().zip(&shares)
+            .map(|(&(id, _, _), &share)| (id, share))
             .collect();
 
         // Blended premium = Σ share_i × premium_i.
         // Since all entries carry lead_premium, this equals lead_premium.
-        let blended_premium = included.iter()
-            .map(|&(_, prem, l)| prem as f64 * l / actual_total)
+        let blended_premium = included.iter().zip(&shares)
+            .map(|(&(_, prem, _), &share)| prem as f64 * share)
             .sum::<f64>()
             .round() as u64;
 
@@ -405,7 +736,7 @@ mod tests {
     fn make_insured(id: u64) -> Insured {
         Insured::new(
             InsuredId(id),
-            "US-SE".to_string(),
+            Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional],
             1.0, // accepts all quotes
         )
@@ -414,8 +745,10 @@ mod tests {
     fn small_risk() -> Risk {
         Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         }
     }
 
@@ -439,8 +772,8 @@ mod tests {
         // 2 insurers, qps=2 → exactly 1 LeadQuoteRequested (to top scorer only).
         let mut broker = broker_with_insurers(1, vec![1, 2]);
         let events = broker.on_coverage_requested(Day(0), InsuredId(1), small_risk());
-        assert_eq!(events.len(), 1);
-        assert!(matches!(events[0].1, Event::LeadQuoteRequested { .. }));
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1].1, Event::LeadQuoteRequested { .. }));
     }
 
     #[test]
@@ -452,8 +785,8 @@ mod tests {
         }
         for id in 1..=3u64 {
             let events = broker.on_coverage_requested(Day(0), InsuredId(id), small_risk());
-            assert_eq!(events.len(), 1);
-            if let Event::LeadQuoteRequested { insurer_id, .. } = events[0].1 {
+            assert_eq!(events.len(), 2);
+            if let Event::LeadQuoteRequested { insurer_id, .. } = events[1].1 {
                 assert_eq!(insurer_id, InsurerId(1), "high-score insurer must be the lead");
             } else {
                 panic!("expected LeadQuoteRequested");
@@ -465,8 +798,8 @@ mod tests {
     fn on_coverage_requested_single_insurer_still_works() {
         let mut broker = broker_with_insurers(1, vec![7]);
         let events = broker.on_coverage_requested(Day(0), InsuredId(1), small_risk());
-        assert_eq!(events.len(), 1);
-        if let Event::LeadQuoteRequested { insurer_id, .. } = events[0].1 {
+        assert_eq!(events.len(), 2);
+        if let Event::LeadQuoteRequested { insurer_id, .. } = events[1].1 {
             assert_eq!(insurer_id, InsurerId(7));
         } else {
             panic!("expected LeadQuoteRequested");
@@ -479,18 +812,46 @@ mod tests {
         let mut ids = vec![];
         for id in 1..=3u64 {
             let events = broker.on_coverage_requested(Day(0), InsuredId(id), small_risk());
-            if let Event::LeadQuoteRequested { submission_id, .. } = events[0].1 {
+            if let Event::LeadQuoteRequested { submission_id, .. } = events[1].1 {
                 ids.push(submission_id.0);
             }
         }
         assert_eq!(ids, vec![0, 1, 2], "submission_id must increment per request");
     }
 
+    #[test]
+    fn on_coverage_requested_emits_submission_routed_same_day() {
+        let mut broker = broker_with_insurers(1, vec![1]).with_id(BrokerId(3), 3, 4);
+        let events = broker.on_coverage_requested(Day(5), InsuredId(1), small_risk());
+        assert_eq!(events[0].0, Day(5), "SubmissionRouted must fire on the CoverageRequested day");
+        assert_eq!(
+            events[0].1,
+            Event::SubmissionRouted {
+                submission_id: SubmissionId(3),
+                insured_id: InsuredId(1),
+                broker_id: BrokerId(3),
+            }
+        );
+    }
+
+    #[test]
+    fn submission_ids_interleave_across_brokers() {
+        // Broker 1 of 2 issues odd ids only, so ids never collide with broker 0.
+        let mut broker = broker_with_insurers(2, vec![1]).with_id(BrokerId(1), 1, 2);
+        let ids: Vec<u64> = (1..=3u64)
+            .map(|id| match broker.on_coverage_requested(Day(0), InsuredId(id), small_risk())[0].1 {
+                Event::SubmissionRouted { submission_id, .. } => submission_id.0,
+                ref e => panic!("expected SubmissionRouted, got {e:?}"),
+            })
+            .collect();
+        assert_eq!(ids, vec![1, 3, 5]);
+    }
+
     #[test]
     fn on_coverage_requested_scheduled_day_plus_one() {
         let mut broker = broker_with_insurers(1, vec![1]);
         let events = broker.on_coverage_requested(Day(5), InsuredId(1), small_risk());
-        assert_eq!(events[0].0, Day(6), "LeadQuoteRequested must fire at day+1");
+        assert_eq!(events[1].0, Day(6), "LeadQuoteRequested must fire at day+1");
     }
 
     #[test]
@@ -499,12 +860,12 @@ mod tests {
         let risk = small_risk();
         let events = broker.on_coverage_requested(Day(0), InsuredId(42), risk.clone());
         if let Event::LeadQuoteRequested { submission_id, insured_id, insurer_id, risk: ev_risk } =
-            &events[0].1
+            &events[1].1
         {
             assert_eq!(*insured_id, InsuredId(42));
             assert_eq!(*insurer_id, InsurerId(7));
             assert_eq!(*submission_id, SubmissionId(0));
-            assert_eq!(*ev_risk, risk);
+            assert_eq!(**ev_risk, risk);
         } else {
             panic!("expected LeadQuoteRequested");
         }
@@ -517,8 +878,8 @@ mod tests {
         let mut assigned: Vec<u64> = vec![];
         for id in 1..=6u64 {
             let events = broker.on_coverage_requested(Day(0), InsuredId(id), small_risk());
-            assert_eq!(events.len(), 1);
-            if let Event::LeadQuoteRequested { insurer_id, .. } = events[0].1 {
+            assert_eq!(events.len(), 2);
+            if let Event::LeadQuoteRequested { insurer_id, .. } = events[1].1 {
                 assigned.push(insurer_id.0);
             }
         }
@@ -826,6 +1187,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn partial_lead_line_is_firm_and_followers_fill_the_residual() {
+        // Partial lead 0.2; followers offer 0.3 + 0.2 → scaled up to fill the other 0.8.
+        let mut broker = broker_with_insurers(1, vec![1, 2, 3]);
+        broker.on_coverage_requested(Day(0), InsuredId(1), small_risk());
+        let requests = broker.on_lead_quote_partial(
+            Day(1), SubmissionId(0), InsurerId(1), 100, 100, 0.2,
+        );
+        assert_eq!(requests.len(), 2, "remaining candidates are solicited as followers");
+        broker.on_follower_quote_issued(Day(1), SubmissionId(0), InsurerId(2), 0.3);
+        let events = broker.on_follower_quote_issued(Day(1), SubmissionId(0), InsurerId(3), 0.2);
+        if let [(_, Event::QuotePresented { leader_id, panel, premium, .. })] = events.as_slice() {
+            assert_eq!(*leader_id, InsurerId(1));
+            assert_eq!(*premium, 100);
+            assert!((panel[0].1 - 0.2).abs() < 1e-12, "lead share is not scaled: {panel:?}");
+            assert!((panel[1].1 - 0.48).abs() < 1e-12 && (panel[2].1 - 0.32).abs() < 1e-12, "{panel:?}");
+        } else {
+            panic!("expected QuotePresented, got {events:?}");
+        }
+    }
+
+    #[test]
+    fn partial_lead_without_residual_falls_back_to_next_lead() {
+        // The only follower declines: the partial lead is treated as a decline and insurer 2,
+        // already solicited as a follower, leads alone.
+        let mut broker = broker_with_insurers(1, vec![1, 2]);
+        broker.on_coverage_requested(Day(0), InsuredId(1), small_risk());
+        broker.on_lead_quote_partial(Day(1), SubmissionId(0), InsurerId(1), 100, 100, 0.2);
+        let retry = broker.on_follower_quote_declined(Day(1), SubmissionId(0), InsurerId(2));
+        assert!(
+            matches!(retry.as_slice(), [(Day(1), Event::LeadQuoteRequested { insurer_id: InsurerId(2), .. })]),
+            "same-day retry: {retry:?}"
+        );
+        let events = broker.on_lead_quote_issued(Day(1), SubmissionId(0), InsuredId(1), InsurerId(2), 90, 120, 0.5);
+        if let [(_, Event::QuotePresented { panel, premium, .. })] = events.as_slice() {
+            assert_eq!(panel, &vec![(InsurerId(2), 1.0)], "no follower is asked twice");
+            assert_eq!(*premium, 120);
+        } else {
+            panic!("expected QuotePresented, got {events:?}");
+        }
+
+        // With no candidate left the submission is dropped.
+        let mut broker = broker_with_insurers(1, vec![1]);
+        broker.on_coverage_requested(Day(0), InsuredId(1), small_risk());
+        let events = broker.on_lead_quote_partial(Day(1), SubmissionId(0), InsurerId(1), 100, 100, 0.2);
+        assert!(matches!(events.as_slice(), [(Day(1), Event::SubmissionDropped { .. })]), "{events:?}");
+    }
+
     #[test]
     fn on_follower_quote_declined_unknown_submission_returns_empty() {
         let mut broker = broker_with_insurers(1, vec![1]);
@@ -902,6 +1311,81 @@ mod tests {
         }
     }
 
+    // ── loyal renewals ────────────────────────────────────────────────────────
+
+    #[test]
+    fn renewal_asks_the_incumbent_to_lead_even_when_outscored() {
+        // qps=2, ins1 and ins2 outscore ins3: the incumbent ins3 leads and ins1 follows.
+        let mut broker = broker_with_qps(1, vec![1, 2, 3], 2);
+        broker.on_policy_bound(InsurerId(1));
+        broker.on_policy_bound(InsurerId(1));
+        broker.on_policy_bound(InsurerId(2));
+        let events = broker.on_renewal_requested(Day(0), InsuredId(1), small_risk(), InsurerId(3), 55_000);
+        assert!(matches!(events[1].1, Event::LeadQuoteRequested { insurer_id: InsurerId(3), .. }));
+        let events = broker.on_lead_quote_issued(
+            Day(1), SubmissionId(0), InsuredId(1), InsurerId(3), 50_000, 55_000, 0.5,
+        );
+        let followers: Vec<InsurerId> = events
+            .iter()
+            .filter_map(|(_, e)| match e {
+                Event::FollowerQuoteRequested { insurer_id, .. } => Some(*insurer_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(followers, vec![InsurerId(1)], "premium at the cap keeps the incumbent");
+    }
+
+    #[test]
+    fn renewal_shops_the_market_when_the_incumbent_quotes_above_the_cap() {
+        let mut broker = broker_with_qps(1, vec![1, 2], 2);
+        broker.on_renewal_requested(Day(0), InsuredId(1), small_risk(), InsurerId(2), 55_000);
+        let events = broker.on_lead_quote_issued(
+            Day(1), SubmissionId(0), InsuredId(1), InsurerId(2), 50_000, 55_001, 1.0,
+        );
+        assert_eq!(events.len(), 1);
+        assert!(
+            matches!(events[0], (Day(1), Event::LeadQuoteRequested { insurer_id: InsurerId(1), .. })),
+            "an incumbent above the cap is passed over the same day",
+        );
+        // The next lead's quote stands at any price: the cap applies to the incumbent only.
+        let events = broker.on_lead_quote_issued(
+            Day(1), SubmissionId(0), InsuredId(1), InsurerId(1), 50_000, 70_000, 1.0,
+        );
+        assert!(matches!(events[0].1, Event::QuotePresented { leader_id: InsurerId(1), premium: 70_000, .. }));
+    }
+
+    #[test]
+    fn rollover_reoffers_the_expiring_lines_and_markets_only_the_shortfall() {
+        let mut broker = broker_with_qps(1, vec![1, 2, 3, 4], 3);
+        broker.on_policy_bound(InsurerId(4));
+        broker.on_policy_bound(InsurerId(4));
+        let panel = vec![(InsurerId(1), 0.5), (InsurerId(2), 0.3), (InsurerId(3), 0.2)];
+        let events = broker.on_rollover_requested(Day(0), InsuredId(1), small_risk(), panel, 55_000);
+        assert!(matches!(events[1].1, Event::LeadQuoteRequested { insurer_id: InsurerId(1), .. }));
+
+        let events = broker.on_lead_quote_issued(Day(1), SubmissionId(0), InsuredId(1), InsurerId(1), 50_000, 55_000, 0.5);
+        let offers: Vec<(InsurerId, f64)> = events
+            .iter()
+            .filter_map(|(_, e)| match e {
+                Event::RenewalOffered { insurer_id, expiring_line, lead_premium: 55_000, .. } => Some((*insurer_id, *expiring_line)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(offers, vec![(InsurerId(2), 0.3), (InsurerId(3), 0.2)], "the outscoring ins4 is not approached yet");
+
+        assert!(broker.on_follower_quote_issued(Day(1), SubmissionId(0), InsurerId(2), 0.3).is_empty());
+        let events = broker.on_follower_quote_declined(Day(1), SubmissionId(0), InsurerId(3));
+        assert!(
+            matches!(events.as_slice(), [(Day(1), Event::FollowerQuoteRequested { insurer_id: InsurerId(4), .. })]),
+            "the declined line is marketed the same day",
+        );
+        let events = broker.on_follower_quote_issued(Day(1), SubmissionId(0), InsurerId(4), 1.0);
+        let Event::QuotePresented { ref panel, .. } = events[0].1 else { panic!("expected QuotePresented") };
+        let ids: Vec<InsurerId> = panel.iter().map(|&(id, _)| id).collect();
+        assert_eq!(ids, vec![InsurerId(1), InsurerId(2), InsurerId(4)]);
+        assert!((panel[2].1 - 0.2).abs() < 1e-9, "new capacity fills only the shortfall");
+    }
+
     // ── insured population ────────────────────────────────────────────────────
 
     #[test]
@@ -950,8 +1434,8 @@ mod tests {
         }
         for id in 1..=3u64 {
             let events = broker.on_coverage_requested(Day(0), InsuredId(id), small_risk());
-            assert_eq!(events.len(), 1);
-            if let Event::LeadQuoteRequested { insurer_id, .. } = events[0].1 {
+            assert_eq!(events.len(), 2);
+            if let Event::LeadQuoteRequested { insurer_id, .. } = events[1].1 {
                 assert_eq!(insurer_id, InsurerId(1), "high-score insurer must always be selected");
             } else {
                 panic!("expected LeadQuoteRequested");
@@ -967,8 +1451,8 @@ mod tests {
             assert!(result.is_empty(), "unknown submission → no events");
         }
         let events = broker.on_coverage_requested(Day(10), InsuredId(1), small_risk());
-        assert_eq!(events.len(), 1);
-        if let Event::LeadQuoteRequested { insurer_id, .. } = events[0].1 {
+        assert_eq!(events.len(), 2);
+        if let Event::LeadQuoteRequested { insurer_id, .. } = events[1].1 {
             assert_eq!(insurer_id, InsurerId(2), "low-decline insurer must be preferred");
         } else {
             panic!("expected LeadQuoteRequested");
@@ -984,8 +1468,43 @@ mod tests {
         broker.on_year_end();
         let ev1 = broker.on_coverage_requested(Day(360), InsuredId(1), small_risk());
         let ev2 = broker.on_coverage_requested(Day(360), InsuredId(1), small_risk());
-        let id1 = if let Event::LeadQuoteRequested { insurer_id, .. } = ev1[0].1 { insurer_id } else { panic!() };
-        let id2 = if let Event::LeadQuoteRequested { insurer_id, .. } = ev2[0].1 { insurer_id } else { panic!() };
+        let id1 = if let Event::LeadQuoteRequested { insurer_id, .. } = ev1[1].1 { insurer_id } else { panic!() };
+        let id2 = if let Event::LeadQuoteRequested { insurer_id, .. } = ev2[1].1 { insurer_id } else { panic!() };
         assert_ne!(id1, id2, "after year-end reset, round-robin must cycle both insurers");
     }
+
+    // ── market intelligence ───────────────────────────────────────────────────
+
+    #[test]
+    fn market_intelligence_digests_quotes_and_declines_then_resets() {
+        let mut broker = broker_with_qps(1, vec![1, 2], 2);
+        let si = small_risk().sum_insured;
+        for (rate, sub) in [(0.06, 0), (0.10, 1)] {
+            let sid = SubmissionId(sub);
+            broker.on_coverage_requested(Day(0), InsuredId(1), small_risk());
+            broker.on_lead_quote_issued(Day(1), sid, InsuredId(1), InsurerId(1), 0, (si as f64 * rate) as u64, 0.5);
+            broker.on_follower_quote_declined(Day(1), sid, InsurerId(2));
+        }
+
+        let (day, event) = broker.market_intelligence(Day(359), Year(1));
+        assert_eq!(day, Day(359));
+        let Event::MarketIntelligence { avg_quoted_rate, quote_responses, decline_rate, territories, .. } = event else {
+            panic!("expected MarketIntelligence");
+        };
+        assert!((avg_quoted_rate - 0.08).abs() < 1e-6);
+        assert_eq!(quote_responses, 4);
+        assert!((decline_rate - 0.5).abs() < 1e-12);
+        assert_eq!(territories.len(), 1);
+        assert_eq!(territories[0].quotes, 2);
+        assert!((territories[0].rate_dispersion - 0.0008f64.sqrt()).abs() < 1e-6);
+
+        broker.on_year_end();
+        let Event::MarketIntelligence { quote_responses, territories, .. } =
+            broker.market_intelligence(Day(719), Year(2)).1
+        else {
+            unreachable!()
+        };
+        assert_eq!(quote_responses, 0);
+        assert!(territories.is_empty());
+    }
 }
@@ -0,0 +1,330 @@
+//! Experiment bundles: package the analysed output of several run directories into one
+//! versioned JSON artifact for side-by-side comparison in the UI.
+//!
+//! A run directory is what `rins --runs N --output-dir DIR` writes: one
+//! `events_seed_{seed}.ndjson` per run plus `config.json`. A directory holding a single
+//! `events.ndjson` is also accepted. Each directory becomes one [`Experiment`] carrying its
+//! config hash, `SimulationStart` metadata, per-run `YearStats` and (for ≥ 2 runs) the
+//! cross-run `YearDist` table.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::analysis::{self, YearDist, YearStats};
+use crate::config::SimulationConfig;
+use crate::events::{Event, SimEvent};
+use crate::types::TerritoryTable;
+
+/// Bumped whenever the bundle schema changes shape.
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct Bundle {
+    pub version: u32,
+    pub experiments: Vec<Experiment>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Experiment {
+    /// Directory name, used as the experiment label.
+    pub name: String,
+    pub path: String,
+    /// `SimulationConfig::config_hash` of the directory's `config.json`; None without one.
+    pub config_hash: Option<String>,
+    pub metadata: ExperimentMetadata,
+    pub runs: Vec<RunSummary>,
+    /// Cross-run distributions per year; empty for a single run.
+    pub distributions: Vec<YearDist>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExperimentMetadata {
+    pub year_start: u32,
+    pub warmup_years: u32,
+    pub analysis_years: u32,
+    /// Event-log schema version of the runs (`events::SCHEMA_VERSION` of the writer); 0 if no
+    /// log has a `SimulationStart`.
+    pub schema_version: u32,
+    pub run_count: usize,
+    pub event_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunSummary {
+    /// Seed parsed from `events_seed_{seed}.ndjson`, else the config seed, else None.
+    pub seed: Option<u64>,
+    pub file: String,
+    /// From the log's `RunMetadata` header; `None` for logs written without one.
+    pub git_hash: Option<String>,
+    pub started_at: Option<u64>,
+    pub years: Vec<YearStats>,
+}
+
+/// Why a run directory could not be bundled.
+#[derive(Debug)]
+pub enum BundleError {
+    Io { path: PathBuf, message: String },
+    /// A line of an events file is not a valid `SimEvent`. `line` is 1-based.
+    BadEvent { path: PathBuf, line: usize, message: String },
+    BadConfig { path: PathBuf, message: String },
+    /// The directory holds no events files.
+    NoRuns(PathBuf),
+}
+
+impl std::fmt::Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "{}: {message}", path.display()),
+            Self::BadEvent { path, line, message } => {
+                write!(f, "{} line {line}: {message}", path.display())
+            }
+            Self::BadConfig { path, message } => write!(f, "{}: bad config: {message}", path.display()),
+            Self::NoRuns(dir) => write!(f, "{}: no events*.ndjson files", dir.display()),
+        }
+    }
+}
+
+/// Bundle each directory in `dirs` as one experiment, in the given order.
+pub fn build_bundle(dirs: &[PathBuf]) -> Result<Bundle, BundleError> {
+    let experiments = dirs.iter().map(|d| load_experiment(d)).collect::<Result<_, _>>()?;
+    Ok(Bundle { version: BUNDLE_VERSION, experiments })
+}
+
+/// Analyse every events file in `dir`. Initial capitals and the expense ratio come from the
+/// directory's `config.json` when present, else from the canonical config.
+pub fn load_experiment(dir: &Path) -> Result<Experiment, BundleError> {
+    let config_path = dir.join("config.json");
+    let config: Option<SimulationConfig> = if config_path.exists() {
+        let text = read(&config_path)?;
+        let config = serde_json::from_str(&text)
+            .map_err(|e| BundleError::BadConfig { path: config_path.clone(), message: e.to_string() })?;
+        Some(config)
+    } else {
+        None
+    };
+    let basis = config.clone().unwrap_or_else(SimulationConfig::canonical);
+    let initial_capitals: HashMap<_, _> =
+        basis.insurers.iter().map(|ic| (ic.id, ic.initial_capital.max(0) as u64)).collect();
+    let expense_ratio = basis.insurers.first().map(|ic| ic.expense_ratio).unwrap_or(0.344);
+
+    let mut files = events_files(dir)?;
+    if files.is_empty() {
+        return Err(BundleError::NoRuns(dir.to_path_buf()));
+    }
+    files.sort_by_key(|(seed, path)| (*seed, path.clone()));
+
+    let mut metadata = ExperimentMetadata {
+        year_start: 1,
+        warmup_years: 0,
+        analysis_years: 0,
+        schema_version: 0,
+        run_count: files.len(),
+        event_count: 0,
+    };
+    let mut runs = Vec::with_capacity(files.len());
+    for (seed, path) in files {
+        let log = load_events(&path)?;
+        metadata.event_count += log.len();
+        if let Some(Event::SimulationStart { year_start, warmup_years, analysis_years, schema_version }) =
+            log.iter().map(|e| &e.event).find(|e| matches!(e, Event::SimulationStart { .. }))
+        {
+            metadata.year_start = year_start.0;
+            metadata.warmup_years = *warmup_years;
+            metadata.analysis_years = *analysis_years;
+            metadata.schema_version = *schema_version;
+        }
+        let header = log.first().and_then(|e| match &e.event {
+            Event::RunMetadata(m) => Some(m),
+            _ => None,
+        });
+        let (_, years) = analysis::analyse(&log, &initial_capitals, expense_ratio);
+        runs.push(RunSummary {
+            seed: seed.or(config.as_ref().map(|c| c.seed)),
+            file: file_name(&path),
+            git_hash: header.and_then(|m| m.git_hash.clone()),
+            started_at: header.map(|m| m.started_at),
+            years,
+        });
+    }
+
+    let distributions = if runs.len() >= 2 {
+        let all: Vec<Vec<YearStats>> = runs.iter().map(|r| r.years.clone()).collect();
+        analysis::analyse_distributions(&all, expense_ratio)
+    } else {
+        vec![]
+    };
+
+    Ok(Experiment {
+        name: file_name(dir),
+        path: dir.display().to_string(),
+        config_hash: config.as_ref().map(SimulationConfig::config_hash),
+        metadata,
+        runs,
+        distributions,
+    })
+}
+
+/// Read an NDJSON event log, skipping blank lines. Logs written before schema version 4 name
+/// territories in every event; those names are interned into ids on the way in, in the order
+/// of the log's `RunMetadata.territories` and then first sight, so old logs load unchanged.
+pub fn load_events(path: &Path) -> Result<Vec<SimEvent>, BundleError> {
+    let mut legacy = TerritoryTable::default();
+    read(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            let event = serde_json::from_str::<SimEvent>(l)
+                .or_else(|e| intern_territory_names(l, &mut legacy).ok_or(e))
+                .map_err(|e| BundleError::BadEvent { path: path.to_path_buf(), line: i + 1, message: e.to_string() })?;
+            if let Event::RunMetadata(metadata) = &event.event {
+                legacy = TerritoryTable::new(&metadata.territories);
+            }
+            Ok(event)
+        })
+        .collect()
+}
+
+/// Parse a pre-interning `line`, replacing each territory name with its id in `table`.
+fn intern_territory_names(line: &str, table: &mut TerritoryTable) -> Option<SimEvent> {
+    fn intern(value: &mut Value, table: &mut TerritoryTable) {
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    match (key.as_str(), &mut *field) {
+                        ("territory", Value::String(name)) => {
+                            *field = table.intern(name).map_or(Value::Null, |t| t.0.into());
+                        }
+                        // `LossEvent.footprint`: `[territory, damage_fraction]` pairs.
+                        ("footprint", Value::Array(pairs)) => {
+                            for pair in pairs {
+                                if let Some(first) = pair.get_mut(0)
+                                    && let Value::String(name) = first
+                                {
+                                    *first = table.intern(name).map_or(Value::Null, |t| t.0.into());
+                                }
+                            }
+                        }
+                        _ => intern(field, table),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| intern(item, table)),
+            _ => {}
+        }
+    }
+    let mut value: Value = serde_json::from_str(line).ok()?;
+    intern(&mut value, table);
+    serde_json::from_value(value).ok()
+}
+
+/// `events_seed_{seed}.ndjson` files (seed parsed) and a plain `events.ndjson` (no seed).
+fn events_files(dir: &Path) -> Result<Vec<(Option<u64>, PathBuf)>, BundleError> {
+    let io_err = |e: std::io::Error| BundleError::Io { path: dir.to_path_buf(), message: e.to_string() };
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_err)? {
+        let path = entry.map_err(io_err)?.path();
+        let name = file_name(&path);
+        if name == "events.ndjson" {
+            files.push((None, path));
+        } else if let Some(seed) = name
+            .strip_prefix("events_seed_")
+            .and_then(|rest| rest.strip_suffix(".ndjson"))
+            .and_then(|s| s.parse().ok())
+        {
+            files.push((Some(seed), path));
+        }
+    }
+    Ok(files)
+}
+
+fn read(path: &Path) -> Result<String, BundleError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| BundleError::Io { path: path.to_path_buf(), message: e.to_string() })
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+
+    fn write_run(dir: &Path, config: &SimulationConfig) {
+        let mut sim = Simulation::from_config(config.clone()).unwrap();
+        sim.start();
+        sim.run();
+        let ndjson: String = sim
+            .log
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect();
+        std::fs::write(dir.join(format!("events_seed_{}.ndjson", config.seed)), ndjson).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rins_bundle_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn config_hash_ignores_seed_but_not_parameters() {
+        let a = SimulationConfig::canonical();
+        let b = SimulationConfig { seed: 7, ..a.clone() };
+        let c = SimulationConfig { years: a.years + 1, ..a.clone() };
+        assert_eq!(a.config_hash(), b.config_hash());
+        assert_ne!(a.config_hash(), c.config_hash());
+        assert_eq!(a.config_hash().len(), 16);
+    }
+
+    #[test]
+    fn bundles_runs_with_metadata_and_distributions() {
+        let dir = temp_dir("two_runs");
+        let config = SimulationConfig { years: 3, warmup_years: 1, ..SimulationConfig::canonical() };
+        std::fs::write(dir.join("config.json"), serde_json::to_string(&config).unwrap()).unwrap();
+        for seed in [2, 1] {
+            write_run(&dir, &SimulationConfig { seed, ..config.clone() });
+        }
+
+        let bundle = build_bundle(std::slice::from_ref(&dir)).unwrap();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        let exp = &bundle.experiments[0];
+        assert_eq!(exp.config_hash.as_deref(), Some(config.config_hash().as_str()));
+        assert_eq!(exp.runs.iter().map(|r| r.seed).collect::<Vec<_>>(), vec![Some(1), Some(2)]);
+        assert_eq!(exp.metadata.warmup_years, 1);
+        assert_eq!(exp.metadata.schema_version, crate::events::SCHEMA_VERSION);
+        assert_eq!(exp.metadata.run_count, 2);
+        assert!(!exp.distributions.is_empty());
+        let json = serde_json::to_value(&bundle).unwrap();
+        assert_eq!(json["experiments"][0]["runs"][0]["years"][0]["year"], 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn logs_from_earlier_releases_load_verify_and_analyse() {
+        // Captured from earlier builds; see tests/fixtures/README.md.
+        for name in ["schema_v1_initial.ndjson", "schema_v1_latest.ndjson"] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+            let log = load_events(&path).unwrap_or_else(|e| panic!("{e}"));
+            let start = log.iter().find(|e| matches!(e.event, Event::SimulationStart { .. }));
+            assert!(matches!(start.map(|e| &e.event), Some(Event::SimulationStart { schema_version: 1, .. })), "{name}");
+            assert!(crate::report::verify(&log).passed, "{name}");
+            let (_, years) = analysis::analyse(&log, &HashMap::new(), 0.3);
+            assert_eq!(years.len(), 2, "{name}");
+        }
+    }
+
+    #[test]
+    fn directory_without_events_is_an_error() {
+        let dir = temp_dir("empty");
+        assert!(matches!(load_experiment(&dir), Err(BundleError::NoRuns(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,367 @@
+//! Historical calibration: ingest a CSV of observed market metrics and compare simulated
+//! year distributions against it.
+//!
+//! The input CSV has a header row naming (in any order) the columns
+//! `year, industry_cr, rol_index, cat_losses`:
+//! - `industry_cr` — industry combined ratio as a fraction (0.95 = 95%);
+//! - `rol_index`   — any rate-on-line index (e.g. base 100);
+//! - `cat_losses`  — insured catastrophe losses in billions USD.
+//!
+//! Extra columns are ignored; blank lines and lines starting with `#` are skipped.
+//!
+//! The simulated market is far smaller than the industry, so only the combined ratio is
+//! compared in levels. The rate and cat-loss series are compared as indices — each series
+//! divided by its own mean — so the fit tests volatility and shape rather than scale.
+
+use crate::analysis::{DistStats, YearStats, percentile_stats};
+use crate::cycle::autocorrelation;
+
+/// One year of observed market history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoricalYear {
+    pub year: u32,
+    /// Industry combined ratio (fraction, not percent).
+    pub industry_cr: f64,
+    /// Rate-on-line index, any base.
+    pub rol_index: f64,
+    /// Insured catastrophe losses (billions USD).
+    pub cat_losses_b: f64,
+}
+
+/// Why a history CSV could not be parsed.
+#[derive(Debug, PartialEq)]
+pub enum HistoryParseError {
+    /// No header row (input empty or all comments).
+    Empty,
+    /// The header lacks a required column.
+    MissingColumn(&'static str),
+    /// A data row has too few fields or an unparseable value. `line` is 1-based.
+    BadValue { line: usize, column: &'static str, value: String },
+    /// A header row was present but no data rows followed.
+    NoData,
+}
+
+impl std::fmt::Display for HistoryParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "history CSV is empty"),
+            Self::MissingColumn(c) => write!(f, "history CSV header has no `{c}` column"),
+            Self::BadValue { line, column, value } => {
+                write!(f, "history CSV line {line}: bad `{column}` value {value:?}")
+            }
+            Self::NoData => write!(f, "history CSV has a header but no data rows"),
+        }
+    }
+}
+
+const COLUMNS: [&str; 4] = ["year", "industry_cr", "rol_index", "cat_losses"];
+
+/// Parse a history CSV (see module docs for the format). Rows are returned sorted by year.
+pub fn parse_history(text: &str) -> Result<Vec<HistoricalYear>, HistoryParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'));
+
+    let (_, header) = lines.next().ok_or(HistoryParseError::Empty)?;
+    let names: Vec<String> = header.split(',').map(|h| h.trim().to_ascii_lowercase()).collect();
+    let mut idx = [0usize; 4];
+    for (slot, col) in idx.iter_mut().zip(COLUMNS) {
+        *slot = names.iter().position(|n| n == col).ok_or(HistoryParseError::MissingColumn(col))?;
+    }
+
+    let mut rows = Vec::new();
+    for (line, row) in lines {
+        let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+        let field = |k: usize| -> Result<&str, HistoryParseError> {
+            fields.get(idx[k]).copied().ok_or_else(|| HistoryParseError::BadValue {
+                line,
+                column: COLUMNS[k],
+                value: String::new(),
+            })
+        };
+        let num = |k: usize| -> Result<f64, HistoryParseError> {
+            let raw = field(k)?;
+            raw.parse::<f64>().ok().filter(|v| v.is_finite()).ok_or_else(|| {
+                HistoryParseError::BadValue { line, column: COLUMNS[k], value: raw.to_string() }
+            })
+        };
+        let raw_year = field(0)?;
+        let year = raw_year.parse::<u32>().map_err(|_| HistoryParseError::BadValue {
+            line,
+            column: COLUMNS[0],
+            value: raw_year.to_string(),
+        })?;
+        rows.push(HistoricalYear {
+            year,
+            industry_cr: num(1)?,
+            rol_index: num(2)?,
+            cat_losses_b: num(3)?,
+        });
+    }
+    if rows.is_empty() {
+        return Err(HistoryParseError::NoData);
+    }
+    rows.sort_by_key(|r| r.year);
+    Ok(rows)
+}
+
+/// Calibration targets derived from history: the distributions and persistence the
+/// simulation should reproduce.
+#[derive(Debug, Clone)]
+pub struct CalibrationTargets {
+    pub years: usize,
+    pub combined_ratio: DistStats,
+    /// Rate-on-line index divided by its mean.
+    pub rate_index: DistStats,
+    /// Raw insured cat losses (billions USD).
+    pub cat_losses_b: DistStats,
+    /// Cat losses divided by their mean.
+    pub cat_loss_index: DistStats,
+    /// Lag-1 autocorrelation of the combined ratio (cycle persistence).
+    pub cr_lag1_autocorr: f64,
+    /// Lag-1 autocorrelation of the rate index.
+    pub rate_lag1_autocorr: f64,
+}
+
+/// Compute calibration targets from parsed history. Panics on an empty slice.
+pub fn calibration_targets(history: &[HistoricalYear]) -> CalibrationTargets {
+    assert!(!history.is_empty(), "calibration_targets requires at least one historical year");
+    let cr: Vec<f64> = history.iter().map(|h| h.industry_cr).collect();
+    let rate = to_index(history.iter().map(|h| h.rol_index).collect());
+    let cat: Vec<f64> = history.iter().map(|h| h.cat_losses_b).collect();
+    CalibrationTargets {
+        years: history.len(),
+        combined_ratio: percentile_stats(&mut cr.clone()).unwrap(),
+        rate_index: percentile_stats(&mut rate.clone()).unwrap(),
+        cat_losses_b: percentile_stats(&mut cat.clone()).unwrap(),
+        cat_loss_index: percentile_stats(&mut to_index(cat)).unwrap(),
+        cr_lag1_autocorr: lag1_autocorr(&cr),
+        rate_lag1_autocorr: lag1_autocorr(&rate),
+    }
+}
+
+/// Goodness of fit for one metric: historical vs pooled simulated distribution.
+#[derive(Debug, Clone)]
+pub struct MetricFit {
+    pub metric: &'static str,
+    pub historical: DistStats,
+    pub simulated: DistStats,
+    /// Two-sample Kolmogorov–Smirnov statistic: max |F_hist − F_sim|.
+    pub ks_statistic: f64,
+    /// Asymptotic KS p-value. Small values mean history is unlikely under the simulation.
+    pub ks_p_value: f64,
+    pub historical_lag1_autocorr: f64,
+    /// Mean over runs of each run's lag-1 autocorrelation.
+    pub simulated_lag1_autocorr: f64,
+}
+
+/// Compare simulated runs (post-warmup `YearStats` per run) against history.
+///
+/// Returns one `MetricFit` each for the combined ratio, rate index and cat-loss index, or an
+/// empty Vec if either side has no years. Simulated rate is `YearStats::rate_on_line`; simulated
+/// cat loss is `cat_gul`. Index series are normalised per run.
+pub fn goodness_of_fit(
+    history: &[HistoricalYear],
+    runs: &[Vec<YearStats>],
+    expense_ratio: f64,
+) -> Vec<MetricFit> {
+    if history.is_empty() || runs.iter().all(|r| r.is_empty()) {
+        return Vec::new();
+    }
+    let hist_series: [Vec<f64>; 3] = [
+        history.iter().map(|h| h.industry_cr).collect(),
+        to_index(history.iter().map(|h| h.rol_index).collect()),
+        to_index(history.iter().map(|h| h.cat_losses_b).collect()),
+    ];
+    let sim_series: Vec<[Vec<f64>; 3]> = runs
+        .iter()
+        .filter(|r| !r.is_empty())
+        .map(|run| {
+            [
+                run.iter().map(|s| s.combined_ratio(expense_ratio)).collect(),
+                to_index(run.iter().map(|s| s.rate_on_line()).collect()),
+                to_index(run.iter().map(|s| s.cat_gul as f64).collect()),
+            ]
+        })
+        .collect();
+
+    ["combined_ratio", "rate_index", "cat_loss_index"]
+        .into_iter()
+        .enumerate()
+        .map(|(k, metric)| {
+            let hist = &hist_series[k];
+            let pooled: Vec<f64> = sim_series.iter().flat_map(|s| s[k].iter().copied()).collect();
+            let ks = ks_statistic(hist, &pooled);
+            let sim_ac: f64 = sim_series.iter().map(|s| lag1_autocorr(&s[k])).sum::<f64>()
+                / sim_series.len() as f64;
+            MetricFit {
+                metric,
+                historical: percentile_stats(&mut hist.clone()).unwrap(),
+                simulated: percentile_stats(&mut pooled.clone()).unwrap(),
+                ks_statistic: ks,
+                ks_p_value: ks_p_value(ks, hist.len(), pooled.len()),
+                historical_lag1_autocorr: lag1_autocorr(hist),
+                simulated_lag1_autocorr: sim_ac,
+            }
+        })
+        .collect()
+}
+
+/// Divide a series by its mean. An all-zero series is returned unchanged.
+fn to_index(mut xs: Vec<f64>) -> Vec<f64> {
+    let mean = xs.iter().sum::<f64>() / xs.len().max(1) as f64;
+    if mean != 0.0 {
+        xs.iter_mut().for_each(|x| *x /= mean);
+    }
+    xs
+}
+
+/// Lag-1 sample autocorrelation. Zero for series shorter than 3 or with no variance.
+fn lag1_autocorr(xs: &[f64]) -> f64 {
+    autocorrelation(xs, 1)
+}
+
+/// Two-sample KS statistic: the largest gap between the two empirical CDFs.
+fn ks_statistic(a: &[f64], b: &[f64]) -> f64 {
+    let sorted = |xs: &[f64]| {
+        let mut v = xs.to_vec();
+        v.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        v
+    };
+    let (a, b) = (sorted(a), sorted(b));
+    let (mut i, mut j, mut d) = (0usize, 0usize, 0.0f64);
+    while i < a.len() && j < b.len() {
+        let x = a[i].min(b[j]);
+        while i < a.len() && a[i] <= x {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= x {
+            j += 1;
+        }
+        d = d.max((i as f64 / a.len() as f64 - j as f64 / b.len() as f64).abs());
+    }
+    d
+}
+
+/// Asymptotic two-sample KS p-value (Kolmogorov distribution with the Stephens correction).
+fn ks_p_value(d: f64, n: usize, m: usize) -> f64 {
+    if n == 0 || m == 0 {
+        return 1.0;
+    }
+    let ne = (n * m) as f64 / (n + m) as f64;
+    let lambda = (ne.sqrt() + 0.12 + 0.11 / ne.sqrt()) * d;
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let q: f64 = (1..=100)
+        .map(|k| {
+            let k = k as f64;
+            let sign = if k as u64 % 2 == 1 { 1.0 } else { -1.0 };
+            sign * (-2.0 * k * k * lambda * lambda).exp()
+        })
+        .sum();
+    (2.0 * q).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+# illustrative history
+year,rol_index,industry_cr,cat_losses,source
+2003,100,0.90,10,x
+
+2001,120,1.10,30,x
+2002,110,1.00,20,x
+";
+
+    fn stats(year: u32, premium: u64, claims: u64, cat_gul: u64) -> YearStats {
+        YearStats {
+            bound_premium: premium,
+            sum_insured: premium * 20,
+            claims,
+            cat_gul,
+            ..YearStats::zero(year)
+        }
+    }
+
+    #[test]
+    fn parse_history_reads_columns_by_name_and_sorts_by_year() {
+        let rows = parse_history(CSV).unwrap();
+        assert_eq!(rows.iter().map(|r| r.year).collect::<Vec<_>>(), vec![2001, 2002, 2003]);
+        assert_eq!(
+            rows[0],
+            HistoricalYear { year: 2001, industry_cr: 1.10, rol_index: 120.0, cat_losses_b: 30.0 }
+        );
+    }
+
+    #[test]
+    fn parse_history_reports_missing_column_and_bad_value_line() {
+        assert_eq!(
+            parse_history("year,industry_cr,cat_losses\n2001,1.0,5\n"),
+            Err(HistoryParseError::MissingColumn("rol_index"))
+        );
+        assert_eq!(
+            parse_history("year,industry_cr,rol_index,cat_losses\n2001,1.0,100,5\n2002,n/a,100,5\n"),
+            Err(HistoryParseError::BadValue { line: 3, column: "industry_cr", value: "n/a".into() })
+        );
+        assert_eq!(parse_history("# nothing\n"), Err(HistoryParseError::Empty));
+        assert_eq!(
+            parse_history("year,industry_cr,rol_index,cat_losses\n"),
+            Err(HistoryParseError::NoData)
+        );
+    }
+
+    #[test]
+    fn targets_normalise_rate_index_and_measure_persistence() {
+        let t = calibration_targets(&parse_history(CSV).unwrap());
+        assert_eq!(t.years, 3);
+        assert!((t.rate_index.mean - 1.0).abs() < 1e-12);
+        assert!((t.cat_losses_b.mean - 20.0).abs() < 1e-12);
+        assert!((t.combined_ratio.p50 - 1.0).abs() < 1e-12);
+        // Persistent (trending) series autocorrelate positively; alternating ones negatively.
+        assert!(lag1_autocorr(&[0.9, 0.95, 1.0, 1.05, 1.1]) > 0.0);
+        assert!(lag1_autocorr(&[0.9, 1.1, 0.9, 1.1, 0.9]) < 0.0);
+    }
+
+    #[test]
+    fn goodness_of_fit_is_perfect_when_simulation_replays_history() {
+        let run = vec![
+            stats(1, 1_000_000, 800_000, 3_000),
+            YearStats { sum_insured: 25_000_000, ..stats(2, 1_000_000, 700_000, 2_000) },
+            stats(3, 1_000_000, 600_000, 1_000),
+        ];
+        let history: Vec<HistoricalYear> = run
+            .iter()
+            .map(|s| HistoricalYear {
+                year: 2000 + s.year,
+                industry_cr: s.combined_ratio(0.3),
+                rol_index: s.rate_on_line(),
+                cat_losses_b: s.cat_gul as f64,
+            })
+            .collect();
+        let fits = goodness_of_fit(&history, &[run], 0.3);
+        assert_eq!(fits.len(), 3);
+        for f in &fits {
+            assert!(f.ks_statistic < 1e-12, "{}: D = {}", f.metric, f.ks_statistic);
+            assert!(f.ks_p_value > 0.99, "{}: p = {}", f.metric, f.ks_p_value);
+            assert!((f.historical_lag1_autocorr - f.simulated_lag1_autocorr).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn goodness_of_fit_flags_disjoint_distributions() {
+        let history: Vec<HistoricalYear> = (0..30)
+            .map(|y| HistoricalYear { year: y, industry_cr: 1.0, rol_index: 100.0, cat_losses_b: 1.0 })
+            .collect();
+        let run: Vec<YearStats> = (1..=30).map(|y| stats(y, 1_000_000, 1_000_000, 1)).collect();
+        let fits = goodness_of_fit(&history, &[run], 0.3); // simulated CR = 1.3 every year
+        let cr = &fits[0];
+        assert_eq!(cr.metric, "combined_ratio");
+        assert!((cr.ks_statistic - 1.0).abs() < 1e-12);
+        assert!(cr.ks_p_value < 1e-6);
+    }
+}
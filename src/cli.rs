@@ -0,0 +1,280 @@
+//! Command-line parsing for the `rins` subcommands.
+//!
+//! Each subcommand is a static [`Command`] listing the flags it accepts. [`Command::parse`]
+//! rejects unknown flags (suggesting the nearest known one), flags missing their value and
+//! surplus or missing positional arguments, and turns `-h` / `--help` into [`CliError::Help`]
+//! so the caller can print [`Command::usage`]. Values are kept as strings until the caller asks
+//! for them with [`Matches::parse`], which reports what the flag expected on failure.
+
+use std::str::FromStr;
+
+/// One flag a subcommand accepts.
+#[derive(Debug, Clone, Copy)]
+pub struct Flag {
+    /// Long form, including the dashes (`--seed`).
+    pub name: &'static str,
+    /// Optional short alias (`-o`).
+    pub short: Option<&'static str>,
+    /// Placeholder for the flag's value in the usage text; `None` for a switch.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+impl Flag {
+    /// A flag that takes no value.
+    pub const fn switch(name: &'static str, help: &'static str) -> Self {
+        Flag { name, short: None, value: None, help }
+    }
+
+    /// A flag followed by a value, as `--name value` or `--name=value`.
+    pub const fn value(name: &'static str, value: &'static str, help: &'static str) -> Self {
+        Flag { name, short: None, value: Some(value), help }
+    }
+
+    pub const fn short(mut self, short: &'static str) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    fn matches(&self, arg: &str) -> bool {
+        self.name == arg || self.short == Some(arg)
+    }
+}
+
+/// A subcommand: its positional arguments and the flags it accepts.
+#[derive(Debug)]
+pub struct Command {
+    /// Full invocation shown in the usage line (`rins verify`).
+    pub name: &'static str,
+    /// Positional synopsis (`<events.ndjson>`); empty for none.
+    pub args: &'static str,
+    pub about: &'static str,
+    /// Bounds on the number of positional arguments.
+    pub min_args: usize,
+    pub max_args: usize,
+    pub flags: &'static [Flag],
+}
+
+/// Why a command line was rejected.
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    /// `-h` / `--help` was given: print the usage and exit successfully.
+    Help,
+    UnknownFlag { flag: String, suggestion: Option<&'static str> },
+    MissingValue(&'static str),
+    BadValue { flag: &'static str, value: String, expected: &'static str },
+    /// Too few or too many positional arguments.
+    Arguments { expected: &'static str, got: usize },
+    /// Two flags that cannot be given together.
+    Conflict(&'static str, &'static str),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Help => write!(f, "help requested"),
+            Self::UnknownFlag { flag, suggestion: Some(s) } => write!(f, "unknown option {flag} (did you mean {s}?)"),
+            Self::UnknownFlag { flag, suggestion: None } => write!(f, "unknown option {flag}"),
+            Self::MissingValue(flag) => write!(f, "{flag} requires a value"),
+            Self::BadValue { flag, value, expected } => write!(f, "{flag} requires {expected}, got {value:?}"),
+            Self::Arguments { expected, got } => write!(f, "expected {expected}, got {got} argument(s)"),
+            Self::Conflict(a, b) => write!(f, "{a} cannot be used with {b}"),
+        }
+    }
+}
+
+/// A parsed command line: the flags given, in order, and the positional arguments.
+#[derive(Debug, Default)]
+pub struct Matches {
+    flags: Vec<(&'static str, Option<String>)>,
+    pub positionals: Vec<String>,
+}
+
+impl Matches {
+    /// Whether the flag (long name) was given.
+    pub fn has(&self, name: &str) -> bool {
+        self.flags.iter().any(|(f, _)| *f == name)
+    }
+
+    /// The value of the last occurrence of the flag, if given.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.flags.iter().rev().find(|(f, _)| *f == name).and_then(|(_, v)| v.as_deref())
+    }
+
+    /// The flag's value parsed as `T`; `expected` describes a valid value for the error.
+    pub fn parse<T: FromStr>(&self, name: &'static str, expected: &'static str) -> Result<Option<T>, CliError> {
+        self.value(name)
+            .map(|v| v.parse().map_err(|_| CliError::BadValue { flag: name, value: v.to_string(), expected }))
+            .transpose()
+    }
+}
+
+impl Command {
+    pub fn parse(&self, args: &[String]) -> Result<Matches, CliError> {
+        let mut matches = Matches::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "-h" || arg == "--help" {
+                return Err(CliError::Help);
+            }
+            if !arg.starts_with('-') || arg.len() == 1 || arg.parse::<f64>().is_ok() {
+                matches.positionals.push(arg.clone());
+                continue;
+            }
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let Some(flag) = self.flags.iter().find(|f| f.matches(name)) else {
+                return Err(CliError::UnknownFlag { flag: name.to_string(), suggestion: self.suggest(name) });
+            };
+            let value = match (flag.value, inline) {
+                (None, None) => None,
+                (None, Some(value)) => {
+                    return Err(CliError::BadValue { flag: flag.name, value, expected: "no value" });
+                }
+                (Some(_), Some(value)) => Some(value),
+                // A following flag is not a value, but a negative number is.
+                (Some(_), None) => match args.next() {
+                    Some(v) if !v.starts_with("--") => Some(v.clone()),
+                    _ => return Err(CliError::MissingValue(flag.name)),
+                },
+            };
+            matches.flags.push((flag.name, value));
+        }
+        let got = matches.positionals.len();
+        if got < self.min_args || got > self.max_args {
+            let expected = if self.args.is_empty() { "no arguments" } else { self.args };
+            return Err(CliError::Arguments { expected, got });
+        }
+        Ok(matches)
+    }
+
+    /// Usage text: the invocation line, the description and one line per flag.
+    pub fn usage(&self) -> String {
+        let mut out = format!("usage: {}", self.name);
+        if !self.args.is_empty() {
+            out.push(' ');
+            out.push_str(self.args);
+        }
+        if !self.flags.is_empty() {
+            out.push_str(" [options]");
+        }
+        out.push_str(&format!("\n\n{}\n", self.about));
+        if self.flags.is_empty() {
+            return out;
+        }
+        out.push_str("\noptions:\n");
+        let spec = |f: &Flag| {
+            let name = match f.short {
+                Some(s) => format!("{s}, {}", f.name),
+                None => f.name.to_string(),
+            };
+            match f.value {
+                Some(v) => format!("{name} <{v}>"),
+                None => name,
+            }
+        };
+        let width = self.flags.iter().map(|f| spec(f).len()).max().unwrap_or(0).max("-h, --help".len());
+        for f in self.flags {
+            out.push_str(&format!("  {:<width$}  {}\n", spec(f), f.help));
+        }
+        out.push_str(&format!("  {:<width$}  print this help\n", "-h, --help"));
+        out
+    }
+
+    /// The known flag nearest to a mistyped one, within two edits.
+    fn suggest(&self, name: &str) -> Option<&'static str> {
+        self.flags
+            .iter()
+            .map(|f| (edit_distance(name, f.name), f.name))
+            .filter(|&(d, _)| d <= 2)
+            .min_by_key(|&(d, _)| d)
+            .map(|(_, n)| n)
+    }
+}
+
+/// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAGS: &[Flag] = &[
+        Flag::value("--seed", "n", "master seed"),
+        Flag::switch("--quiet", "no progress output"),
+        Flag::value("--output", "path", "output file").short("-o"),
+    ];
+
+    const CMD: Command = Command {
+        name: "rins test",
+        args: "<path>",
+        about: "A test command.",
+        min_args: 1,
+        max_args: 1,
+        flags: FLAGS,
+    };
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_values_switches_aliases_and_positionals() {
+        let m = CMD.parse(&args("log.ndjson --seed 7 --quiet -o out.json --seed=9")).unwrap();
+        assert_eq!(m.positionals, vec!["log.ndjson"]);
+        assert!(m.has("--quiet") && !m.has("--help"));
+        assert_eq!(m.value("--output"), Some("out.json"));
+        assert_eq!(m.parse::<u64>("--seed", "a u64"), Ok(Some(9)), "the last occurrence wins");
+        assert_eq!(m.parse::<u64>("--missing", "a u64"), Ok(None));
+    }
+
+    #[test]
+    fn rejects_typos_missing_values_and_bad_arity() {
+        assert_eq!(
+            CMD.parse(&args("log --seeed 7")).unwrap_err(),
+            CliError::UnknownFlag { flag: "--seeed".to_string(), suggestion: Some("--seed") },
+        );
+        assert_eq!(
+            CMD.parse(&args("log --verbose")).unwrap_err(),
+            CliError::UnknownFlag { flag: "--verbose".to_string(), suggestion: None },
+        );
+        assert_eq!(CMD.parse(&args("log --seed")).unwrap_err(), CliError::MissingValue("--seed"));
+        assert_eq!(CMD.parse(&args("log --seed --quiet")).unwrap_err(), CliError::MissingValue("--seed"));
+        assert_eq!(CMD.parse(&args("")).unwrap_err(), CliError::Arguments { expected: "<path>", got: 0 });
+        assert_eq!(CMD.parse(&args("a b")).unwrap_err(), CliError::Arguments { expected: "<path>", got: 2 });
+        assert_eq!(CMD.parse(&args("log --quiet=yes")).unwrap_err(), CliError::BadValue {
+            flag: "--quiet",
+            value: "yes".to_string(),
+            expected: "no value",
+        });
+        assert_eq!(CMD.parse(&args("log --help --seeed")).unwrap_err(), CliError::Help);
+
+        let m = CMD.parse(&args("log --seed x")).unwrap();
+        assert_eq!(m.parse::<u64>("--seed", "a u64").unwrap_err().to_string(), "--seed requires a u64, got \"x\"");
+    }
+
+    #[test]
+    fn usage_lists_every_flag() {
+        let usage = CMD.usage();
+        assert!(usage.starts_with("usage: rins test <path> [options]"));
+        for flag in FLAGS {
+            assert!(usage.contains(flag.name) && usage.contains(flag.help));
+        }
+        assert!(usage.contains("-o, --output <path>"));
+    }
+}
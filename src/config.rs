@@ -1,6 +1,13 @@
-use crate::types::InsurerId;
+use std::collections::BTreeMap;
 
-#[derive(Clone)]
+use serde::{Deserialize, Serialize};
+
+use crate::events::{CancellationBasis, CatBondTrigger, Line};
+use crate::perils::CatCatalogEntry;
+use crate::rng::{RngBackend, Stream};
+use crate::types::{BrokerId, Day, InsuredId, InsurerId, TerritoryTable, Year};
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct InsurerConfig {
     pub id: InsurerId,
     pub initial_capital: i64, // signed to allow negative (no insolvency in MVP)
@@ -74,10 +81,34 @@ pub struct InsurerConfig {
     /// The lead's capacity_line is capped at this value before the pricing_line is applied.
     /// Canonical: 0.25. Use 1.0 in tests to preserve full-line (solo-writer) behaviour.
     pub leader_participation_cap: f64,
+    /// Underwriting appetite per line of business. A line without an entry is written at the
+    /// standard rate and line. Empty = every line (canonical; entrants always write every line).
+    #[serde(default)]
+    pub appetite: Vec<LineAppetite>,
+}
+
+/// An insurer's appetite for one line of business, applied to the risks placed on that line
+/// (`Risk::line`) and to no other: each line is its own risk, so the settings never reach
+/// another line's exposure or premium.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LineAppetite {
+    pub line: Line,
+    /// false = decline every risk on the line, lead or follower, with
+    /// `DeclineReason::OutsideAppetite`.
+    pub write: bool,
+    /// Largest share of a risk on the line the insurer signs, lead or follower, on top of its
+    /// capacity and pricing lines. None = no cap.
+    #[serde(default)]
+    pub target_share: Option<f64>,
+    /// Loading on the underwriter premium and the follower TP check for risks on the line:
+    /// × (1 + loading). Negative values price the line keenly. 0.0 = standard rate.
+    #[serde(default)]
+    pub loading: f64,
 }
 
-/// Attritional peril parameters — LogNormal damage fraction, Poisson frequency.
-#[derive(Clone)]
+/// Attritional peril parameters — LogNormal damage fraction, Poisson frequency, or negative
+/// binomial frequency with `contagion` > 0.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct AttritionalConfig {
     /// Expected number of attritional claims per insured per year.
     pub annual_rate: f64,
@@ -85,12 +116,154 @@ pub struct AttritionalConfig {
     pub mu: f64,
     /// LogNormal ln-space std-dev of the damage fraction.
     pub sigma: f64,
+    /// Negative binomial contagion c: each insured-year's claim count is Poisson with a
+    /// Gamma-distributed mean, so Var[N] = E[N] + c·E[N]². 0 = Poisson.
+    #[serde(default)]
+    pub contagion: f64,
+    /// Per-insured or per-territory parameter overrides. An insured's own override wins over a
+    /// territory override; fields left None fall back to the values above.
+    #[serde(default)]
+    pub overrides: Vec<AttritionalOverride>,
+}
+
+/// Attritional parameters for one insured (`insured_id`) or one line (`territory`).
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AttritionalOverride {
+    #[serde(default)]
+    pub insured_id: Option<InsuredId>,
+    #[serde(default)]
+    pub territory: Option<String>,
+    #[serde(default)]
+    pub annual_rate: Option<f64>,
+    #[serde(default)]
+    pub mu: Option<f64>,
+    #[serde(default)]
+    pub sigma: Option<f64>,
+    #[serde(default)]
+    pub contagion: Option<f64>,
+}
+
+/// The attritional frequency and severity parameters in force for one insured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttritionalParams {
+    pub annual_rate: f64,
+    pub mu: f64,
+    pub sigma: f64,
+    pub contagion: f64,
+}
+
+impl AttritionalConfig {
+    /// Parameters for `insured_id` writing in `territory`, after overrides.
+    pub fn params_for(&self, insured_id: InsuredId, territory: &str) -> AttritionalParams {
+        let base = AttritionalParams {
+            annual_rate: self.annual_rate,
+            mu: self.mu,
+            sigma: self.sigma,
+            contagion: self.contagion,
+        };
+        let o = self
+            .overrides
+            .iter()
+            .find(|o| o.insured_id == Some(insured_id))
+            .or_else(|| self.overrides.iter().find(|o| o.insured_id.is_none() && o.territory.as_deref() == Some(territory)));
+        let Some(o) = o else { return base };
+        AttritionalParams {
+            annual_rate: o.annual_rate.unwrap_or(base.annual_rate),
+            mu: o.mu.unwrap_or(base.mu),
+            sigma: o.sigma.unwrap_or(base.sigma),
+            contagion: o.contagion.unwrap_or(base.contagion),
+        }
+    }
+}
+
+/// Single-risk large losses between attritional and cat: per insured a Poisson count of
+/// `LargeFire` losses, each with a Pareto damage fraction truncated at total loss.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LargeLossConfig {
+    /// Expected number of large losses per insured per year (e.g. 0.01 = one in a hundred).
+    pub annual_rate: f64,
+    /// Pareto minimum damage fraction, in (0, 1].
+    pub scale: f64,
+    /// Pareto tail index α; smaller = heavier tail, more total losses.
+    pub shape: f64,
+}
+
+/// Growth-oriented underwriting. At each YearStart every targeted insurer with a year of
+/// written premium behind it sets next year's target at `(1 + annual_growth)` × last year's
+/// premium. If last year fell short of its own target by a fraction `s`, it undercuts its
+/// underwriter premium by `undercut_per_shortfall × s` this year, but never below
+/// `price_floor` × its actuarial price.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GrowthTargetConfig {
+    /// Insurers pursuing the objective. Empty = every insurer, entrants included.
+    pub insurer_ids: Vec<InsurerId>,
+    /// Target annual growth in written premium (0.10 = 10%).
+    pub annual_growth: f64,
+    /// Price cut per unit of relative premium shortfall (1.0: 20% short → 20% cheaper).
+    pub undercut_per_shortfall: f64,
+    /// Lowest premium the undercut may reach, as a multiple of the actuarial price.
+    pub price_floor: f64,
+}
+
+impl GrowthTargetConfig {
+    pub fn applies_to(&self, id: InsurerId) -> bool {
+        self.insurer_ids.is_empty() || self.insurer_ids.contains(&id)
+    }
+}
+
+/// Insurer-specific views of risk. Each insurer prices cat with its own view of the cat ELF:
+/// the configured `cat_elf` times a model error drawn once at creation from
+/// `LogNormal(−σ²/2, σ)` (mean 1), so insurers systematically disagree about technical price.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RiskViewConfig {
+    /// σ of the multiplicative cat ELF error. 0.0 = every insurer holds the true view.
+    pub cat_elf_sigma: f64,
+}
+
+/// Long-tail casualty: third-party liability losses that occur in the cover year but are
+/// reported over the following years. Casualty is its own line: each insured also places a
+/// liability risk (covering only `Peril::Casualty`, limit = the value of its first asset) as a
+/// separate submission, policy and premium. Per liability risk a Poisson count of `Casualty`
+/// occurrences, each with a Pareto severity (as a fraction of the limit, truncated at the
+/// full limit), charged to the policy in force on the accident day and reported `k` years
+/// later with probability `reporting_pattern[k - 1]`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CasualtyConfig {
+    /// Expected number of casualty occurrences per insured per year.
+    pub annual_rate: f64,
+    /// Pareto minimum severity as a fraction of sum insured, in (0, 1].
+    pub scale: f64,
+    /// Pareto tail index α; smaller = heavier tail.
+    pub shape: f64,
+    /// Share of claims first reported in each year after the accident year (Y+1, Y+2, …);
+    /// sums to 1.0. Canonical example: [0.35, 0.30, 0.20, 0.10, 0.05].
+    pub reporting_pattern: Vec<f64>,
+    /// σ of the market-wide reserving factor drawn at each YearStart: casualty claims reported
+    /// that year are reserved at `initial_reserve_ratio × LogNormal(−σ²/2, σ)`, so whole
+    /// report years are under- or over-reserved together and develop as the claims pay.
+    /// 0.0 = casualty reserved like any other claim.
+    pub reserve_sigma: f64,
+}
+
+impl CasualtyConfig {
+    /// Mean reporting delay in years.
+    pub fn mean_report_lag(&self) -> f64 {
+        self.reporting_pattern.iter().enumerate().map(|(k, share)| share * (k + 1) as f64).sum()
+    }
+
+    /// Expected annual casualty loss as a fraction of the limit: `annual_rate × E[min(X, 1)]`
+    /// for X ~ Pareto(`scale`, `shape`). Insurers price the casualty line at this ELF.
+    pub fn expected_loss_fraction(&self) -> f64 {
+        let (s, a) = (self.scale.min(1.0), self.shape);
+        let severity = if (a - 1.0).abs() < 1e-9 { s * (1.0 - s.ln()) } else { s + (s.powf(a) - s) / (1.0 - a) };
+        self.annual_rate * severity
+    }
 }
 
 /// One severity class in the compound catastrophe model (e.g. "minor" or "major").
 /// `schedule_loss_events` runs one independent Poisson draw per class and samples
 /// a damage fraction from that class's Pareto distribution.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CatEventClass {
     /// Short label for debugging and catalog output ("minor", "major", …).
     pub label: String,
@@ -103,13 +276,19 @@ pub struct CatEventClass {
     /// Upper truncation for Pareto draws ∈ (0, 1].
     /// Proxy for maximum net per-occurrence retained severity absent explicit RI.
     pub max_damage_fraction: f64,
+    /// Spatial footprint: relative damage in the territories 1, 2, … places either side of the
+    /// struck one in `CatConfig.territories` order (no wrap-around). One occurrence strikes them
+    /// at `footprint[k − 1] × damage_fraction`, so neighbouring zones lose together.
+    /// Empty = the struck territory only (canonical).
+    #[serde(default)]
+    pub footprint: Vec<f64>,
 }
 
 /// Compound catastrophe peril parameters.
 /// Each event class has its own Poisson frequency and Pareto severity distribution,
 /// allowing the model to separate high-frequency/low-severity (minor) from
 /// low-frequency/high-severity (major) events.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CatConfig {
     /// One or more severity classes. `schedule_loss_events` draws independently per class.
     pub event_classes: Vec<CatEventClass>,
@@ -119,9 +298,724 @@ pub struct CatConfig {
     /// Canonical: 3 territories → ~33% of insureds hit per event.
     /// Use a single-element list (`["US-SE"]`) in tests to preserve full-portfolio exposure.
     pub territories: Vec<String>,
+    /// Intra-year arrival profile. None = arrivals uniform over the year (canonical).
+    pub season: Option<SeasonProfile>,
+    /// Poisson cluster process: each drawn event becomes a parent storm that spawns
+    /// correlated secondaries. None = independent arrivals (canonical).
+    pub clustering: Option<ClusterConfig>,
+    /// Replay these events exactly (see `perils::parse_catalogue`) instead of drawing
+    /// arrivals; years the catalogue does not cover have no catastrophes. The event classes
+    /// still set the PML that sizes cat limits. None = stochastic arrivals (canonical).
+    pub catalogue: Option<Vec<CatCatalogEntry>>,
+}
+
+/// Seasonal arrival profile for a peril. A `window_share` fraction of arrivals lands uniformly
+/// in days `window_start..=window_end` of the year; the rest land uniformly on the other days.
+/// Atlantic wind: days 210–330 (roughly August–November) with ~0.9 of arrivals.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SeasonProfile {
+    /// First day of the season, in 1..360.
+    pub window_start: u64,
+    /// Last day of the season, in `window_start..360`.
+    pub window_end: u64,
+    /// Fraction of arrivals inside the window ∈ [0, 1].
+    pub window_share: f64,
+}
+
+/// Secondary events spawned by each parent storm. Secondaries strike the parent's territory
+/// within `max_lag_days` of it, with damage sampled from the parent's class and scaled by
+/// `damage_scale`. Secondaries that would fall past the year end are dropped. The class
+/// `annual_frequency` remains the parent rate, so clustering adds to the expected event count.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    /// Poisson mean of secondaries per parent.
+    pub mean_secondaries: f64,
+    /// Secondaries land 1..=max_lag_days after the parent.
+    pub max_lag_days: u64,
+    /// Multiplier on secondary damage fractions ∈ (0, 1].
+    pub damage_scale: f64,
+}
+
+/// Deterministic stress events injected on top of the stochastic catastrophe draw, e.g. a
+/// forced 1-in-250 windstorm in year 12 or two storms back to back. Background cats and
+/// attritional losses are drawn exactly as without the scenario.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScenarioConfig {
+    pub events: Vec<ScenarioEvent>,
+}
+
+/// One injected WindstormAtlantic occurrence.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScenarioEvent {
+    /// Simulation year, counting warm-up years (1 = the first simulated year).
+    pub year: u32,
+    /// Day within the year, in 1..360.
+    pub day_of_year: u64,
+    /// Territory struck; must be one of `CatConfig.territories`.
+    pub territory: String,
+    pub severity: ScenarioSeverity,
+}
+
+/// Size of an injected occurrence.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScenarioSeverity {
+    /// Damage fraction applied to every insured in the territory, in (0, 1].
+    DamageFraction(f64),
+    /// The compound cat model's 1-in-N damage fraction (the same quantile that sizes
+    /// `pml_200`), capped at 1.0.
+    ReturnPeriod(f64),
+}
+
+impl ScenarioConfig {
+    /// Reject events outside the run or the cat model: days outside 1..360, territories the
+    /// model does not know, and severities that are not positive.
+    pub fn check(&self, cat: &CatConfig) -> Result<(), String> {
+        for (i, e) in self.events.iter().enumerate() {
+            if e.year == 0 || !(1..Day::DAYS_PER_YEAR).contains(&e.day_of_year) {
+                return Err(format!("event {i}: year must be ≥ 1 and day_of_year in 1..360"));
+            }
+            if !cat.territories.contains(&e.territory) {
+                return Err(format!("event {i}: unknown territory {:?}", e.territory));
+            }
+            let valid = match e.severity {
+                ScenarioSeverity::DamageFraction(f) => f > 0.0 && f <= 1.0,
+                ScenarioSeverity::ReturnPeriod(rp) => rp >= 1.0,
+            };
+            if !valid {
+                return Err(format!("event {i}: severity {:?} out of range", e.severity));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One broker in the distribution channel. Each broker owns a segment of insureds
+/// (assigned cyclically at construction time) and keeps its own relationship scores.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BrokerConfig {
+    pub id: BrokerId,
+    /// Number of insurers this broker solicits per submission.
+    /// None = fall back to `SimulationConfig::quotes_per_submission`.
+    pub quotes_per_submission: Option<usize>,
+}
+
+/// Operational capacity of insurer underwriting teams. Each quote request draws a
+/// turnaround time ~ Exp(mean = base_turnaround_days × (1 + workload / capacity_per_window)),
+/// where workload is the insurer's request count over the trailing `window_days`. Quotes slower
+/// than the broker's `deadline_days` never arrive and are logged as `TurnaroundExceeded` declines.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct QuoteLatencyConfig {
+    /// Mean turnaround at zero workload (days).
+    pub base_turnaround_days: f64,
+    /// Requests per window an insurer can absorb before turnaround doubles.
+    pub capacity_per_window: f64,
+    /// Trailing window over which workload is counted (days).
+    pub window_days: u64,
+    /// Broker's response deadline (days); slower quotes are treated as declines.
+    pub deadline_days: f64,
+}
+
+/// Claims development: panel claims are reported on the loss day, reserved by each insurer,
+/// and paid in instalments over several years instead of settling instantly.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ClaimsDevelopmentConfig {
+    /// Fraction of each ultimate claim paid per development period; sums to 1.0.
+    /// Canonical example: [0.40, 0.30, 0.20, 0.10].
+    pub payment_pattern: Vec<f64>,
+    /// Days between instalments; the first is paid on the loss day. 360 = annual development.
+    pub payment_interval_days: u64,
+    /// Initial case reserve as a fraction of the ultimate claim.
+    /// < 1.0 = under-reserved: the shortfall (IBNR) emerges as reserve strengthening with each
+    /// payment; > 1.0 = prudent: the excess is released. 1.0 = reserves exact at report.
+    pub initial_reserve_ratio: f64,
 }
 
-#[derive(Clone)]
+/// Annual aggregate deductible (AAD): on top of its per-occurrence deductible, each policy
+/// retains the first `fraction × sum_insured` of covered loss in each year. Claims flow only
+/// once the aggregate is eroded; the aggregate resets each year.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AggregateDeductibleConfig {
+    /// Aggregate deductible as a fraction of sum insured.
+    pub fraction: f64,
+}
+
+/// Premium finance: insureds pay premium in equal instalments and may default on them.
+/// Insurers book the full premium at binding; a default cancels the policy and writes the
+/// uncollected premium back off capital.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PremiumFinanceConfig {
+    /// Equal instalments per annual premium, spaced `360 / instalments` days apart.
+    /// Instalment 0 is the deposit paid at binding.
+    pub instalments: u32,
+    /// Probability that the insured defaults on each instalment after the deposit.
+    pub default_probability: f64,
+    /// Days from the missed instalment to cancellation under `ProRata` (cover continues,
+    /// unpaid, through the grace period). Ignored for `AbInitio`.
+    pub grace_days: u64,
+    pub basis: CancellationBasis,
+}
+
+/// Premium payment terms: insurers account for premium on a cash basis. Written premium is
+/// booked at binding, but capital is credited only as each instalment (one per year, or
+/// `PremiumFinanceConfig::instalments`) is received, `settlement_delay_days` after it falls due.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PaymentTermsConfig {
+    /// Days from an instalment's due day to the cash reaching the insurer. Canonical example: 60.
+    pub settlement_delay_days: u64,
+}
+
+/// Insured retention choice: cover is bought excess of a deductible picked from a ladder,
+/// and insureds climb the ladder when premium strains their budget.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Deductibles as fractions of sum insured, ascending. Insureds start on the first rung.
+    pub deductible_ladder: Vec<f64>,
+    /// Per-occurrence limit as a fraction of sum insured (1.0 = full value).
+    pub limit_fraction: f64,
+    /// Step up one rung on rejection, or when the accepted rate exceeds this share of the
+    /// insured's effective max rate on line.
+    pub step_up_at: f64,
+    /// Step down one rung when the accepted rate is below this share (hysteresis band).
+    pub step_down_at: f64,
+}
+
+/// Price-elastic demand: above `reference_rate` an insured accepts a quote only with
+/// probability `(reference_rate / rate)^elasticity`. An insured that fails the draw buys a
+/// reduced limit of that same fraction instead, unless it is below `min_cover_fraction`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DemandConfig {
+    /// Rate on line at or below which demand is fully inelastic.
+    pub reference_rate: f64,
+    /// Demand elasticity above the reference rate (0.0 = inelastic).
+    pub elasticity: f64,
+    /// Smallest fraction of the requested limit worth buying; 1.0 disables partial cover.
+    pub min_cover_fraction: f64,
+}
+
+/// Broker market intelligence: each broker publishes an annual `MarketIntelligence` digest
+/// of its quoting activity, which insureds and prospective entrants react to.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MarketIntelligenceConfig {
+    /// Insureds anchor the elastic-demand reference rate to their broker's published average
+    /// rate for their territory. Only has an effect together with `demand`.
+    pub anchor_demand: bool,
+    /// New capital enters only when the market-wide decline rate published by brokers is at
+    /// least this high (evidence of unmet demand). 0.0 = no additional entry gate.
+    pub entry_min_decline_rate: f64,
+}
+
+/// A business plan for a new entrant. Fields not covered here (expense ratio, loss estimates,
+/// credibility) still come from the first insurer's config, and the behavioural sensitivities
+/// are still drawn at entry.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EntrantProfile {
+    /// Name recorded on the entrant's `InsurerEntered` event, e.g. "naive" or "disciplined".
+    pub label: String,
+    /// Relative probability of drawing this profile; weights need not sum to 1.
+    pub weight: f64,
+    /// Starting capital is drawn uniformly from `min_capital..=max_capital` (cents, base-year
+    /// money — scaled by asset growth under `inflation` like any entrant's capital).
+    pub min_capital: i64,
+    pub max_capital: i64,
+    /// Pricing aggressiveness: the profit loading on technical premium (template: 0.05).
+    pub profit_loading: f64,
+    /// Internal 1-in-200 damage fraction; a value below the market's is an optimistic cat
+    /// model that raises the cat aggregate limit. None = the market-calibrated value.
+    pub pml_damage_fraction_override: Option<f64>,
+    /// Appetite: largest net line per risk as a fraction of capital. None = no per-risk cap.
+    pub net_line_capacity: Option<f64>,
+}
+
+/// Entry sized to the hard-market opportunity: in a qualifying year the aggregate new capital
+/// is `capital_per_gap × (AP/TP − entry threshold) × the year's market premium`, capped at
+/// `max_capital`. It arrives as `ceil(aggregate / template capital)` entrants (at most
+/// `max_entrants`, at least one) sharing it equally, overriding any profile capital.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EntryCapacityConfig {
+    /// New capital per unit of AP/TP excess per unit of premium written.
+    pub capital_per_gap: f64,
+    /// Most entrants in one year.
+    pub max_entrants: u32,
+    /// Most new capital in one year (cents, current-year money).
+    pub max_capital: i64,
+}
+
+/// Annual investment return on insurer capital, with occasional crash years.
+/// One market-wide return is drawn per year and applied to every insurer, so asset shocks
+/// are systemic and independent of underwriting results.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InvestmentConfig {
+    /// Mean annual return in a normal year (0.04 = 4%).
+    pub mean_return: f64,
+    /// Standard deviation of the annual return in a normal year.
+    pub volatility: f64,
+    /// Probability that a year is a crash year.
+    pub crash_probability: f64,
+    /// Return in a crash year (e.g. −0.20 for a 2008-style drawdown).
+    pub crash_return: f64,
+}
+
+/// Stochastic short rate, stepped once a year as a discretised Vasicek process:
+/// `r(y+1) = r(y) + mean_reversion × (long_run_rate − r(y)) + volatility × ε`, ε ~ N(0, 1).
+/// Each YearStart publishes the year's rate. Investment income earns it: insurer capital earns
+/// the short rate outright, or on top of `InvestmentConfig::mean_return` when that is set. Pricing
+/// discounts expected losses over the claims-development payment lag, so high rates cheapen
+/// long-tail cover (cash-flow underwriting) and leave instantly-settled business unchanged.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InterestRateConfig {
+    /// Rate published in year 1 (0.03 = 3%).
+    pub initial_rate: f64,
+    /// Level the rate reverts towards.
+    pub long_run_rate: f64,
+    /// Fraction of the gap to `long_run_rate` closed each year, in [0, 1].
+    pub mean_reversion: f64,
+    /// Standard deviation of the annual shock.
+    pub volatility: f64,
+    /// Share of full discounting credited in the premium: expected losses are divided by
+    /// `(1 + r)^(discount_credit × mean payment lag)`. 0 = no credit, 1 = fully discounted.
+    pub discount_credit: f64,
+}
+
+impl InterestRateConfig {
+    /// Years over which pricing discounts expected losses: `discount_credit` × the mean lag
+    /// from loss to payment under `development`. Zero when claims settle on the loss day.
+    pub fn discount_years(&self, development: Option<&ClaimsDevelopmentConfig>) -> f64 {
+        let lag_days = development.map_or(0.0, |d| {
+            d.payment_pattern.iter().enumerate().map(|(k, share)| share * (k as u64 * d.payment_interval_days) as f64).sum()
+        });
+        self.discount_credit * lag_days / Day::DAYS_PER_YEAR as f64
+    }
+
+    /// The rate after `current`, given a standard normal shock.
+    pub fn step(&self, current: f64, shock: f64) -> f64 {
+        current + self.mean_reversion * (self.long_run_rate - current) + self.volatility * shock
+    }
+}
+
+/// Voluntary exit into run-off and re-entry when the market hardens.
+/// A run-off insurer declines all new quote requests but keeps settling claims on its
+/// existing book; unlike insolvency the state is reversible.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RunoffConfig {
+    /// An insurer enters run-off when its trailing own combined ratio (EWMA) exceeds this.
+    pub runoff_cr_threshold: f64,
+    /// An insurer enters run-off when capital falls below this fraction of its initial capital.
+    pub capital_exit_floor: f64,
+    /// A run-off insurer may re-enter once the market AP/TP factor reaches this level.
+    pub reentry_ap_tp_factor: f64,
+    /// Annual probability that an eligible run-off insurer actually re-enters. Values below 1.0
+    /// stagger re-entries so a hardening market does not pull all exited capital back at once.
+    pub reentry_probability: f64,
+    /// Withdrawal of marginal capacity from a prolonged soft market. None = exits are driven
+    /// only by each insurer's own capital and experience.
+    pub soft_market_exit: Option<SoftMarketExitConfig>,
+}
+
+/// Soft-market exit: once the market AP/TP factor has stayed below `ap_tp_floor` for
+/// `consecutive_years` year-ends, the weakest writing insurers withdraw into run-off and the
+/// count starts again. The last writing insurer never withdraws.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SoftMarketExitConfig {
+    pub ap_tp_floor: f64,
+    pub consecutive_years: u32,
+    /// Most insurers that withdraw each time the trigger fires.
+    pub max_exits: u32,
+    /// Which insurers count as the weakest.
+    pub ranking: SoftMarketExitRanking,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SoftMarketExitRanking {
+    /// Highest own combined ratio EWMA first (insurers without one rank last).
+    WorstCombinedRatio,
+    /// Smallest capital first.
+    SmallestCapital,
+}
+
+/// Post-loss capital raising by distressed but viable insurers.
+/// Evaluated at each YearEnd; raised capital flows straight into the insurer's capital.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecapitalisationConfig {
+    /// Minimum capital depletion (1 − capital / initial capital) before an insurer seeks capital.
+    pub depletion_threshold: f64,
+    /// Highest own CR EWMA at which investors still see franchise value worth funding.
+    pub max_cr: f64,
+    /// Annual probability that an eligible insurer completes a raise.
+    pub recap_probability: f64,
+    /// Fraction of the shortfall to initial capital that a raise restores.
+    pub recap_fraction: f64,
+    /// Fraction of each raise lost to the issuance discount and fees of distressed capital.
+    pub cost_of_capital: f64,
+}
+
+/// Transfer of an exiting insurer's in-force book and renewal rights (see `transfer.rs`).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PortfolioTransferConfig {
+    /// Years of expected underwriting profit the buyer pays for the renewal rights.
+    pub renewal_multiple: f64,
+}
+
+/// Periodic `SimHealth` events for monitoring long runs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// Simulated days between `SimHealth` events.
+    pub interval_days: u64,
+}
+
+/// Periodic `ExposureSnapshot` events: each insurer's live sum insured by territory and peril.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExposureSnapshotConfig {
+    /// Simulated days between snapshots. Canonical example: 90 (quarterly).
+    pub interval_days: u64,
+}
+
+/// Multi-year policy terms. Cover is bound for `term_years` at a locked annual premium paid in
+/// one instalment per year. At each anniversary the contract is cancelled (insurer-initiated)
+/// if a panel insurer has become insolvent, or (insured-initiated) if the lead now quotes the
+/// risk more than `rate_drop_threshold` below the locked premium; the insured then re-shops.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PolicyTermConfig {
+    /// Policy term in years. 1 = annual policies.
+    pub term_years: u32,
+    /// Fractional fall in the lead's current quote, relative to the locked premium, at which the
+    /// insured cancels at an anniversary (e.g. 0.15 = cancel when the lead quotes 15% less).
+    pub rate_drop_threshold: f64,
+}
+
+/// Annual trend in values and claim costs. Insured asset values grow at `asset_growth` a year
+/// (sum insured, deductible and limit revalued at each coverage request), and attritional
+/// severity trends at `claims_inflation` on top via a shift in the LogNormal mu. Year 1 is the
+/// base year: indices are `(1 + rate)^(year − 1)`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InflationConfig {
+    /// Annual growth of insured asset values (e.g. 0.03 = 3% a year).
+    pub asset_growth: f64,
+    /// Annual attritional claim-cost inflation in excess of asset values.
+    pub claims_inflation: f64,
+}
+
+impl InflationConfig {
+    /// Asset value index for `year` relative to year 1; the deflator for real reporting.
+    pub fn asset_index(&self, year: Year) -> f64 {
+        (1.0 + self.asset_growth).powi(year.0 as i32 - 1)
+    }
+
+    /// Shift added to the attritional LogNormal mu in `year`: damage fractions scale by
+    /// `(1 + claims_inflation)^(year − 1)`.
+    pub fn severity_shift(&self, year: Year) -> f64 {
+        (year.0 as f64 - 1.0) * (1.0 + self.claims_inflation).ln()
+    }
+}
+
+/// Exposure migration into cat zones: insured values in each listed territory grow at
+/// `annual_growth` a year in real terms, on top of any `InflationConfig.asset_growth`. Revalued
+/// at each coverage request like inflation; unlisted territories do not grow. Year 1 is the
+/// base year: indices are `(1 + annual_growth)^(year − 1)`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExposureGrowthConfig {
+    pub territories: Vec<TerritoryGrowth>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TerritoryGrowth {
+    pub territory: String,
+    /// Annual real growth of insured values in `territory` (e.g. 0.02 = 2% a year).
+    pub annual_growth: f64,
+}
+
+impl ExposureGrowthConfig {
+    /// Exposure index of `territory` in `year` relative to year 1; 1.0 if it is not listed.
+    pub fn index(&self, territory: &str, year: Year) -> f64 {
+        self.territories
+            .iter()
+            .find(|t| t.territory == territory)
+            .map_or(1.0, |t| (1.0 + t.annual_growth).powi(year.0 as i32 - 1))
+    }
+}
+
+/// Collateralised cat capacity (see `ils.rs`). At each YearEnd every writing insurer without a
+/// live bond sponsors one with probability `issuance_probability × ap_tp^rate_elasticity`, so
+/// issuance climbs as rates harden after large losses. The principal is fully collateralised and
+/// the sponsor pays the whole term's coupon at issue.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IlsConfig {
+    pub trigger: CatBondTrigger,
+    /// Bond term in years; the bond matures at the YearEnd `term_years` after issue.
+    pub term_years: u32,
+    /// Principal as a fraction of the sponsor's capital at issue.
+    pub principal_capital_fraction: f64,
+    /// Annual coupon as a fraction of principal (the risk spread over the collateral yield).
+    pub spread: f64,
+    /// Annual issuance probability per eligible insurer at a neutral AP/TP factor.
+    pub issuance_probability: f64,
+    /// Elasticity of issuance to the AP/TP factor. 0 = issuance ignores the rate cycle.
+    pub rate_elasticity: f64,
+}
+
+/// Annual cat excess-of-loss reinsurance (see `reinsurance.rs`). At each YearStart every
+/// writing insurer buys a one-year occurrence XoL attaching at `retention_capital_fraction` ×
+/// capital and exhausting at its modelled occurrence PML at `return_period`. The limit is scaled
+/// by `(base_rate_on_line / rate_on_line)^price_elasticity` (never above the full layer), and
+/// the premium `rate_on_line × limit` is paid at purchase.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReinsuranceConfig {
+    /// Return period (years) of the occurrence PML the programme exhausts at.
+    pub return_period: f64,
+    /// Per-occurrence retention as a fraction of the cedant's capital at purchase.
+    pub retention_capital_fraction: f64,
+    /// Rate on line (premium / limit) at a neutral reinsurance cycle; includes the loading.
+    pub base_rate_on_line: f64,
+    /// Reinsurers' target loss ratio. Their loss ratio EWMA above it hardens rates.
+    pub target_loss_ratio: f64,
+    /// Weight of the latest year in the reinsurers' loss ratio EWMA.
+    pub cycle_weight: f64,
+    /// Floor on the cycle factor multiplying `base_rate_on_line`.
+    pub min_rate_factor: f64,
+    /// Cap on the cycle factor multiplying `base_rate_on_line`.
+    pub max_rate_factor: f64,
+    /// Elasticity of the limit bought to the rate on line. 0 = buy the full layer at any price.
+    pub price_elasticity: f64,
+}
+
+/// Partial lead lines. An insurer asked to lead a risk that would take its WindstormAtlantic
+/// aggregate past its solvency limit offers the remaining headroom as a `LeadQuotePartial`
+/// instead of declining. The partial line is firm: the broker fills the residual from the other
+/// candidates and never scales it up, so a panel with nothing else on it is dropped.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PartialLineConfig {
+    /// Smallest headroom line (fraction of the risk) worth offering; below it the insurer declines.
+    pub min_line: f64,
+}
+
+/// Hard-market quota rationing. Instead of quoting cat-exposed lead requests in dispatch
+/// order, each insurer collects them over a window of `window_days` and answers the batch at
+/// the window's last day, highest `priority` first, so its remaining cat headroom goes to the
+/// business it values most. Requests not covering WindstormAtlantic are quoted at once.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RationingConfig {
+    /// Batching window (days); 1 = one batch per day, 90 = per quarter.
+    pub window_days: u64,
+    pub priority: RationingPriority,
+}
+
+/// Order in which a rationing insurer answers its batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RationingPriority {
+    /// The placing broker's relationship score with the insurer, highest first.
+    RelationshipScore,
+    /// Technical premium per unit of cat aggregate the line would use, highest first.
+    RateAdequacy,
+}
+
+/// Heterogeneous insured portfolio. Each insured's asset value, territory and attritional
+/// frequency are drawn at construction from the master seed, so pooling and dispersion
+/// statistics have something to measure.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PortfolioConfig {
+    /// Log-space σ of the asset value: sum insured = `ASSET_VALUE × LogNormal(−σ²/2, σ)`, so the
+    /// mean stays at `ASSET_VALUE`. 0 = every asset at `ASSET_VALUE`.
+    pub sum_insured_sigma: f64,
+    /// Relative territory weights. Empty = round-robin over `catastrophe.territories`.
+    pub territory_weights: Vec<(String, f64)>,
+    /// Log-space σ of each insured's attritional frequency multiplier (mean 1). 0 = uniform.
+    pub attritional_rate_sigma: f64,
+    /// Separately insurable assets per insured, each with its own territory, sum insured and
+    /// attritional multiplier draws, submission and policy. `n_insureds` counts owners, so the
+    /// market places `n_insureds × assets_per_insured` risks. Missing or 0 = one asset.
+    #[serde(default)]
+    pub assets_per_insured: u32,
+}
+
+/// Solvency-ratio capital tiering. Each insurer's solvency capital requirement is
+/// `SCR = cat PML + premium_risk_factor × premium volume + reserve_risk_factor × outstanding reserves`
+/// (no diversification credit), where the cat PML is the 1-in-200 loss on its cat aggregate and
+/// the premium volume is the larger of this and last year's written premium. Line and cat
+/// aggregate limits scale continuously with `capital / SCR`: full at `target_ratio`, none at
+/// `min_ratio`, linear in between.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SolvencyConfig {
+    /// SCR charge per unit of premium volume.
+    pub premium_risk_factor: f64,
+    /// SCR charge per unit of outstanding claims reserves.
+    pub reserve_risk_factor: f64,
+    /// Solvency ratio at or above which the capital-based limits apply in full.
+    pub target_ratio: f64,
+    /// Solvency ratio at or below which the insurer writes no new business.
+    pub min_ratio: f64,
+}
+
+/// Regulatory intervention ladder on the solvency ratio (see `regulator.rs`). Thresholds are
+/// ratios of capital to SCR, descending; requires `SolvencyConfig` to compute the ratio.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RegulatorConfig {
+    /// Below this the insurer may only renew business it already carries. Canonical example: 1.0.
+    pub restrict_below: f64,
+    /// Below this the insurer is put into run-off. Canonical example: 0.7.
+    pub runoff_below: f64,
+    /// Below this the insurer's licence is revoked (closed as insolvent). Canonical example: 0.3.
+    pub revoke_below: f64,
+}
+
+/// Expenses split into a volume-driven and a fixed part, replacing each insurer's flat
+/// `expense_ratio`. Acquisition costs are deducted from premium at binding as before; the fixed
+/// overhead is charged at YearEnd whatever the insurer wrote, so a shrinking book carries a
+/// rising expense ratio.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExpenseConfig {
+    /// Acquisition costs (brokerage and commission) per policy, in basis points of premium.
+    /// Canonical example: 2260 (Lloyd's 2024 acquisition ratio 22.6%).
+    pub acquisition_bps: u32,
+    /// Fixed management overhead per insurer per year (cents). Canonical example:
+    /// 550_000_000 (5.5M USD, ~11.8% of a median canonical book).
+    pub annual_overhead: u64,
+}
+
+impl ExpenseConfig {
+    /// Acquisition costs as a fraction of premium.
+    pub fn acquisition_fraction(&self) -> f64 {
+        self.acquisition_bps as f64 / 10_000.0
+    }
+}
+
+/// Brokerage: the placing broker's commission on premium, deducted from each panel member's
+/// net premium on top of its expense ratio (set `expense_ratio` or `acquisition_bps` net of
+/// brokerage to keep total expenses unchanged).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BrokerageConfig {
+    /// Commission in basis points of gross premium. Lloyd's open-market brokerage runs at
+    /// roughly 1500–2500.
+    pub commission_bps: u32,
+}
+
+impl BrokerageConfig {
+    /// Commission as a fraction of premium.
+    pub fn commission_fraction(&self) -> f64 {
+        self.commission_bps as f64 / 10_000.0
+    }
+
+    /// Commission on `premium` (cents).
+    pub fn commission(&self, premium: u64) -> u64 {
+        premium * self.commission_bps as u64 / 10_000
+    }
+}
+
+/// Insured loyalty at renewal: the incumbent lead is asked to quote first and is kept if its
+/// premium rises by no more than `max_premium_increase` over the expiring premium; otherwise
+/// the insured shops the rest of the market.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LoyaltyConfig {
+    /// Largest proportional premium increase the insured accepts from its incumbent without
+    /// shopping (0.10 = up to 10% over the expiring premium).
+    pub max_premium_increase: f64,
+}
+
+/// Subscription renewal by rollover: the expiring lead re-prices the renewal, and if its premium
+/// rises by no more than `max_premium_increase` the rest of the expiring panel is offered its
+/// lines again at that price (`RenewalOffered`). Only the shortfall left by followers that
+/// decline (`RenewalDeclined`) is marketed to new capacity. A lead that declines or prices past
+/// the cap sends the whole placement back to market.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RolloverConfig {
+    /// Largest proportional premium increase over the expiring premium at which the panel is
+    /// rolled over (0.10 = up to 10%).
+    pub max_premium_increase: f64,
+}
+
+/// Insured risk management: when an insured renews with its incumbent placement at a premium
+/// more than `trigger_increase` above the expiring premium, it spends `spend_fraction` of the
+/// new premium on mitigation. From the next YearEnd its attritional frequency multiplier and
+/// its cat damage factor are cut by `attritional_reduction` and `cat_reduction`, never below
+/// `min_factor` of their starting values.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MitigationConfig {
+    /// Proportional premium rise that prompts an investment (0.15 = 15% over expiring).
+    pub trigger_increase: f64,
+    /// Spend as a fraction of the renewal premium.
+    pub spend_fraction: f64,
+    /// Proportional cut in the attritional frequency per investment.
+    pub attritional_reduction: f64,
+    /// Proportional cut in the damage a catastrophe does to the asset per investment.
+    pub cat_reduction: f64,
+    /// Floor on both factors, as a fraction of their starting values.
+    pub min_factor: f64,
+}
+
+/// Mix-adjusted rate index: at each YearEnd every writing insurer prices a constant basket of
+/// reference risks as it would quote them now, and `RateIndexPublished` reports the mean rate
+/// on line, rebased to 100 in the first published year. Unlike bound premium / sum insured it
+/// does not move when the mix of risks placed changes.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RateIndexConfig {
+    /// Reference risks. Empty = one full-value `ASSET_VALUE` risk per cat territory, equally
+    /// weighted.
+    pub basket: Vec<ReferenceRisk>,
+}
+
+/// A full-value reference risk in the rate index basket, covering both perils.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReferenceRisk {
+    pub territory: String,
+    /// Base-year value (cents); revalued with asset growth under `inflation`.
+    pub sum_insured: u64,
+    /// Relative weight of this risk's rate in the basket.
+    pub weight: f64,
+}
+
+/// Insurer-level occurrence exceedance curves. At each YearEnd every solvent insurer's
+/// in-force cat aggregate by territory is run through the configured event classes
+/// analytically (`perils::occurrence_pml`) and its 1-in-100, 1-in-200 and 1-in-250 losses are
+/// logged as `PmlReported`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PmlReportingConfig {
+    /// Replace the static `pml_damage_fraction_200` in the SCR cat charge with the modelled
+    /// 1-in-200 loss as a share of the reported cat aggregate, until the next report.
+    pub use_in_scr: bool,
+}
+
+/// Per-territory cat exposure limits. A cat event strikes a single territory, so each
+/// territory's WindstormAtlantic aggregate is capped on its own 1-in-200 loss:
+/// `territory_aggregate × pml_200 ≤ pml_capital_fraction × capital`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TerritoryLimitConfig {
+    /// Fraction of capital a 1-in-200 event in any one territory may consume.
+    pub pml_capital_fraction: f64,
+    /// Per-territory overrides of `pml_capital_fraction` (e.g. a tighter cap on "US-SE").
+    pub overrides: BTreeMap<String, f64>,
+}
+
+impl TerritoryLimitConfig {
+    /// The PML capital fraction that applies to `territory`.
+    pub fn fraction_for(&self, territory: &str) -> f64 {
+        self.overrides.get(territory).copied().unwrap_or(self.pml_capital_fraction)
+    }
+}
+
+/// How insurers set prices. `Frozen` is a counterfactual baseline: every insurer quotes its
+/// technical premium (ATP at its initial loss estimate × (1 + profit_loading)) with no AP/TP
+/// loading, market or own, and the attritional experience EWMA never moves. Own combined
+/// ratios are still tracked for run-off and recapitalisation decisions. Losses are
+/// unaffected, so differencing a `Frozen` and an `Adaptive` run on one seed with split RNG
+/// streams isolates what adaptive pricing does to the market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PricingMode {
+    #[default]
+    Adaptive,
+    Frozen,
+}
+
+/// Random-number backend and per-component substreams (see `rng.rs`).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RngConfig {
+    pub backend: RngBackend,
+    /// Give each `Stream` its own generator instead of sharing one. Changes the draws of
+    /// every component relative to the shared stream.
+    pub split_streams: bool,
+    /// Streams seeded independently of the run seed, so they replay identically across runs.
+    /// Only has an effect with `split_streams`.
+    pub frozen: Vec<Stream>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SimulationConfig {
     pub seed: u64,
     /// Number of analysis years. The simulation runs `warmup_years + years` in total;
@@ -148,11 +1042,190 @@ pub struct SimulationConfig {
     /// When true, no cat `LossEvent`s are scheduled. Attritional losses still run.
     /// Useful for isolating attritional dynamics without cat noise.
     pub disable_cats: bool,
+    /// Brokers servicing the insured population; insured `i` is placed by broker `i % n`.
+    /// Empty = a single broker (BrokerId(0)) servicing every insured.
+    /// Canonical: empty. Tests: empty unless exercising multi-broker routing.
+    pub brokers: Vec<BrokerConfig>,
+    /// Stochastic quote turnaround driven by insurer workload. None = instant quoting.
+    /// Canonical: None. Tests: None unless exercising service-level constraints.
+    pub quote_latency: Option<QuoteLatencyConfig>,
+    /// Delayed claim payment with insurer reserving. None = claims settle on the loss day.
+    /// Canonical: None. Tests: None unless exercising reserve development.
+    pub claims_development: Option<ClaimsDevelopmentConfig>,
+    /// Premium instalments with insured default. None = premium paid in full at binding.
+    /// Canonical: None. Tests: None unless exercising premium default.
+    pub premium_finance: Option<PremiumFinanceConfig>,
+    /// Insured deductible / limit selection. None = full-value cover, no deductible.
+    /// Canonical: None. Tests: None unless exercising retention choice.
+    pub retention: Option<RetentionConfig>,
+    /// Investment return / asset-shock process on insurer capital. None = no investment return.
+    /// Canonical: None. Tests: None unless exercising asset shocks.
+    pub investment: Option<InvestmentConfig>,
+    /// Price-elastic demand on top of the hard reservation price. None = accept any quote at
+    /// or below `effective_max_rol()`. Canonical: None. Tests: None unless exercising elasticity.
+    pub demand: Option<DemandConfig>,
+    /// Annual broker `MarketIntelligence` events and reactions to them. None = not published.
+    /// Canonical: None. Tests: None unless exercising market intelligence.
+    pub market_intelligence: Option<MarketIntelligenceConfig>,
+    /// RNG backend and stream splitting. None = one shared ChaCha20 stream.
+    /// Canonical: None. Tests: None unless exercising backends or substreams.
+    pub rng: Option<RngConfig>,
+    /// Voluntary exit into run-off and re-entry. None = insurers only leave through insolvency.
+    /// Canonical: None. Tests: None unless exercising run-off.
+    pub runoff: Option<RunoffConfig>,
+    /// Sell each exiting insurer's book to a writing insurer. None = the book runs off with the
+    /// exiting insurer. Only has an effect together with `runoff`.
+    /// Canonical: None. Tests: None unless exercising portfolio transfers.
+    pub portfolio_transfer: Option<PortfolioTransferConfig>,
+    /// Post-loss recapitalisation. None = capital only grows from retained profit.
+    /// Canonical: None. Tests: None unless exercising capital raising.
+    pub recapitalisation: Option<RecapitalisationConfig>,
+    /// Engine health metrics in the event log. None = not emitted. Observability only: the
+    /// events are logged outside the queue and never change the simulated market.
+    /// Canonical: None. Tests: None unless exercising health metrics.
+    pub health: Option<HealthConfig>,
+    /// Exposure accumulation snapshots in the event log. None = not emitted. Observability
+    /// only, like `health`. Canonical: None. Tests: None unless exercising snapshots.
+    pub exposure_snapshots: Option<ExposureSnapshotConfig>,
+    /// Per-territory exposure concentration limits. None = only the portfolio-wide cat
+    /// aggregate limit applies. Canonical: None. Tests: None unless exercising territory limits.
+    pub territory_limits: Option<TerritoryLimitConfig>,
+    /// Multi-year policy terms with anniversary cancellation. None = annual policies.
+    /// Canonical: None. Tests: None unless exercising multi-year terms.
+    pub policy_terms: Option<PolicyTermConfig>,
+    /// Asset value growth and attritional claims inflation. None = flat nominal values.
+    /// Canonical: None. Tests: None unless exercising trend.
+    pub inflation: Option<InflationConfig>,
+    /// Cat bonds sponsored by insurers. None = no insurance-linked securities.
+    /// Canonical: None. Tests: None unless exercising cat bonds.
+    pub ils: Option<IlsConfig>,
+    /// Partial lead lines at the cat aggregate limit. None = an insurer at its limit declines.
+    /// Canonical: None. Tests: None unless exercising partial placements.
+    pub partial_lines: Option<PartialLineConfig>,
+    /// Stochastic asset values, territory mix and attritional rates. None = identical insureds
+    /// spread round-robin over the territories.
+    /// Canonical: None. Tests: None unless exercising portfolio heterogeneity.
+    pub portfolio: Option<PortfolioConfig>,
+    /// Annual aggregate deductibles on every policy. None = per-occurrence deductibles only.
+    /// Canonical: None. Tests: None unless exercising aggregate deductibles.
+    pub aggregate_deductible: Option<AggregateDeductibleConfig>,
+    /// Scale quoting capacity with each insurer's solvency ratio. None = limits proportional to
+    /// raw capital.
+    /// Canonical: None. Tests: None unless exercising capital tiering.
+    pub solvency: Option<SolvencyConfig>,
+    /// Graduated regulatory intervention on the solvency ratio. None = insolvency only at zero
+    /// capital. Has no effect without `solvency`.
+    /// Canonical: None. Tests: None unless exercising regulatory intervention.
+    pub regulator: Option<RegulatorConfig>,
+    /// Insureds renew with their incumbent lead unless its premium rises past a threshold.
+    /// None = every renewal is routed on broker relationship scores alone.
+    /// Canonical: None. Tests: None unless exercising insured loyalty.
+    pub loyalty: Option<LoyaltyConfig>,
+    /// Acquisition costs per policy plus a fixed annual overhead. None = each insurer's flat
+    /// `expense_ratio` of premium.
+    /// Canonical: None. Tests: None unless exercising the expense split.
+    pub expenses: Option<ExpenseConfig>,
+    /// Premium credited to capital as it is received rather than at binding. Instalments and
+    /// defaults come from `premium_finance` when set. None = premium credited at binding.
+    /// Canonical: None. Tests: None unless exercising payment timing.
+    pub payment_terms: Option<PaymentTermsConfig>,
+    /// Adaptive (experience- and cycle-driven) or frozen technical pricing. Pair `Frozen` with
+    /// `rng.split_streams` so the loss draws match the adaptive run it is compared with.
+    /// Canonical: Adaptive. Missing from older config files = Adaptive.
+    #[serde(default)]
+    pub pricing_mode: PricingMode,
+    /// Stress events injected at fixed days. None = catastrophes are all drawn.
+    /// Canonical: None. Tests: None unless exercising scenarios.
+    pub scenario: Option<ScenarioConfig>,
+    /// Business plans for dynamically spawned entrants; one is drawn (by weight) per entry.
+    /// None = every entrant clones the first insurer's config.
+    /// Canonical: None. Tests: None unless exercising entrant heterogeneity.
+    pub entrant_profiles: Option<Vec<EntrantProfile>>,
+    /// Size entry to the hard-market opportunity. None = one entrant per qualifying year with
+    /// its template (or profile) capital.
+    /// Canonical: None. Tests: None unless exercising entry sizing.
+    pub entry_capacity: Option<EntryCapacityConfig>,
+    /// Broker commission on placed premium. None = no brokerage flows are modelled.
+    /// Canonical: None. Tests: None unless exercising brokerage.
+    pub brokerage: Option<BrokerageConfig>,
+    /// Insureds invest in risk mitigation when their renewal premium jumps. None = each
+    /// insured's loss propensity is fixed.
+    /// Canonical: None. Tests: None unless exercising mitigation.
+    pub mitigation: Option<MitigationConfig>,
+    /// Annual mix-adjusted rate index on a fixed basket of reference risks. None = not
+    /// published. Observability only: pricing the basket changes nothing.
+    /// Canonical: None. Tests: None unless exercising the rate index.
+    pub rate_index: Option<RateIndexConfig>,
+    /// Modelled per-insurer cat PML from the in-force book, reported at each YearEnd. None =
+    /// no `PmlReported`; the SCR uses the static `pml_damage_fraction_200`.
+    /// Canonical: None. Tests: None unless exercising PML reporting.
+    pub pml_reporting: Option<PmlReportingConfig>,
+    /// Single-risk large fire losses, covered by every insured's policy. None = non-cat losses
+    /// are attritional only.
+    /// Canonical: None. Tests: None unless exercising large losses.
+    pub large_loss: Option<LargeLossConfig>,
+    /// Annual cat XoL purchased by each writing insurer at YearStart. None = every insurer
+    /// retains its whole book.
+    /// Canonical: None. Tests: None unless exercising reinsurance.
+    pub reinsurance: Option<ReinsuranceConfig>,
+    /// Real growth of insured values in chosen territories, logged as `ExposureGrowth` at each
+    /// YearStart. None = values move with inflation only.
+    /// Canonical: None. Tests: None unless exercising exposure growth.
+    pub exposure_growth: Option<ExposureGrowthConfig>,
+    /// Batch and ration cat-exposed lead requests per insurer. None = quotes are answered in
+    /// dispatch order as they arrive.
+    /// Canonical: None. Tests: None unless exercising rationing.
+    pub rationing: Option<RationingConfig>,
+    /// Renewals re-sign the expiring panel and market only the shortfall. Takes precedence
+    /// over `loyalty` when both are set. None = renewals are placed as new submissions.
+    /// Canonical: None. Tests: None unless exercising panel rollover.
+    pub rollover: Option<RolloverConfig>,
+    /// Stochastic interest rates driving investment income and a pricing discount credit on
+    /// long-tail cover. None = no rate environment. Canonical: None. Tests: None unless
+    /// exercising interest rates.
+    pub interest_rates: Option<InterestRateConfig>,
+    /// Long-tail casualty line with delayed claim reporting and reserve uncertainty. Requires
+    /// `claims_development`. None = property perils only.
+    /// Canonical: None. Tests: None unless exercising casualty.
+    pub casualty: Option<CasualtyConfig>,
+    /// Growth-oriented underwriting: chosen insurers target annual growth in written premium
+    /// and undercut their price when the last year fell short. None = no growth objective.
+    /// Canonical: None. Tests: None unless exercising growth targets.
+    pub growth_targets: Option<GrowthTargetConfig>,
+    /// Log a `QuoteComparisonRecorded` at every binding: each writing insurer's price for the
+    /// bound risk beside the winning premium, for winner's-curse analysis. No effect on the run.
+    /// Canonical: false. Tests: false unless exercising quote comparison.
+    pub quote_comparison: bool,
+    /// Model uncertainty: each insurer's cat ELF is perturbed at creation. None = every insurer
+    /// prices on the configured cat ELF. Canonical: None. Tests: None unless exercising risk views.
+    pub risk_views: Option<RiskViewConfig>,
+    /// Log every change to an insurer's capital as a `CapitalFlow` with its reason, so capital
+    /// reported anywhere in the log reconciles to initial capital plus flows (Inv 29). No
+    /// effect on the run. Canonical: false. Tests: false unless exercising the capital ledger.
+    pub capital_ledger: bool,
+    /// Log a `QuarterEnd` with interim market stats on the last day of each quarter, so
+    /// within-year capital drawdowns show between year ends. No effect on the run.
+    /// Canonical: false. Tests: false unless exercising quarterly reporting.
+    pub quarterly_reporting: bool,
 }
 
 /// Insured asset value: 25M USD in cents.
 pub const ASSET_VALUE: u64 = 2_500_000_000;
 
+/// One problem `SimulationConfig::validate` found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    /// Path to the offending field, e.g. `insurers[2].cat_elf`.
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
 impl SimulationConfig {
     pub fn canonical() -> Self {
         SimulationConfig {
@@ -188,6 +1261,7 @@ impl SimulationConfig {
                     payout_ratio: 0.70,
                     distribution_floor_multiple: 1.5,
                     leader_participation_cap: 0.25,
+                    appetite: vec![],
                 })
                 .collect(),
             n_insureds: 100,
@@ -197,6 +1271,8 @@ impl SimulationConfig {
                 sigma: 0.3,         // tight spread — attritional = high-frequency, small losses;
                                     // CV_per_claim ≈ 0.31 → aggregate CV across 57 policies ≈ 3%
                                     // (was sigma=1.0 → CV≈15%, masking cat signal)
+                contagion: 0.0,
+                overrides: vec![],
             },
             catastrophe: CatConfig {
                 event_classes: vec![
@@ -209,6 +1285,7 @@ impl SimulationConfig {
                         pareto_scale: 0.003,  // minimum 0.3% df — below att noise
                         pareto_shape: 3.5,    // E[df] = 0.003 × 3.5/2.5 = 0.42%
                         max_damage_fraction: 0.08,
+                        footprint: vec![],
                     },
                     // Major events (Cat 3–5): lower frequency, capital-depleting severity.
                     // Return period: 1-in-200 → scale × (200 × 0.8)^(1/2.5) ≈ 0.495
@@ -218,6 +1295,7 @@ impl SimulationConfig {
                         pareto_scale: 0.065,  // minimum 6.5% df ($1.625M on $25M)
                         pareto_shape: 2.5,    // E[df] = 0.065 × 2.5/1.5 = 10.83%
                         max_damage_fraction: 0.50,
+                        footprint: vec![],
                     },
                 ],
                 territories: vec![
@@ -225,6 +1303,9 @@ impl SimulationConfig {
                     "US-SE".to_string(),
                     "US-Gulf".to_string(),
                 ],
+                season: None,
+                clustering: None,
+                catalogue: None,
             },
             quotes_per_submission: Some(4), // solicit top-4 (by relationship score) per submission
             // LogNormal(ln(0.25), 0.40): median reservation price = 25% RoL.
@@ -232,6 +1313,363 @@ impl SimulationConfig {
             max_rol_mu: f64::ln(0.25),  // ≈ -1.386; median = 0.25
             max_rol_sigma: 0.40,
             disable_cats: false,
+            brokers: vec![],
+            quote_latency: None,
+            claims_development: None,
+            premium_finance: None,
+            retention: None,
+            investment: None,
+            demand: None,
+            market_intelligence: None,
+            rng: None,
+            runoff: None,
+            portfolio_transfer: None,
+            recapitalisation: None,
+            health: None,
+            exposure_snapshots: None,
+            territory_limits: None,
+            policy_terms: None,
+            inflation: None,
+            ils: None,
+            partial_lines: None,
+            portfolio: None,
+            aggregate_deductible: None,
+            solvency: None,
+            regulator: None,
+            loyalty: None,
+            expenses: None,
+            payment_terms: None,
+            pricing_mode: PricingMode::Adaptive,
+            scenario: None,
+            entrant_profiles: None,
+            entry_capacity: None,
+            brokerage: None,
+            mitigation: None,
+            rate_index: None,
+            pml_reporting: None,
+            large_loss: None,
+            reinsurance: None,
+            exposure_growth: None,
+            rationing: None,
+            rollover: None,
+            interest_rates: None,
+            casualty: None,
+            growth_targets: None,
+            quote_comparison: false,
+            risk_views: None,
+            capital_ledger: false,
+            quarterly_reporting: false,
+        }
+    }
+
+    /// The canonical config with `overrides`, a partial config as JSON, merged over it: objects
+    /// merge key by key and anything else replaces the canonical value, so `{"years": 20}`
+    /// changes only the horizon. Used where configs arrive as JSON (Python, `rins serve`).
+    pub fn canonical_with(overrides: serde_json::Value) -> Result<Self, serde_json::Error> {
+        fn merge(base: &mut serde_json::Value, overrides: serde_json::Value) {
+            match (base, overrides) {
+                (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+                    for (key, value) in overrides {
+                        merge(base.entry(key).or_insert(serde_json::Value::Null), value);
+                    }
+                }
+                (base, value) => *base = value,
+            }
+        }
+        let mut config = serde_json::to_value(Self::canonical())?;
+        merge(&mut config, overrides);
+        serde_json::from_value(config)
+    }
+
+    /// Pricing discount term for every insurer: `InterestRateConfig::discount_years` under this
+    /// config's claims development, or 0.0 without interest rates.
+    pub fn discount_years(&self) -> f64 {
+        self.interest_rates.as_ref().map_or(0.0, |r| r.discount_years(self.claims_development.as_ref()))
+    }
+
+    /// Every territory name the config mentions: the cat territories in order (or the
+    /// "US-SE" fallback without any), then portfolio weights, the rate index basket, the cat
+    /// catalogue, scenario events, exposure growth, attritional overrides and territory limits.
+    fn territory_names(&self) -> impl Iterator<Item = &str> {
+        let cat = &self.catastrophe;
+        let fallback = cat.territories.is_empty().then_some("US-SE");
+        cat.territories
+            .iter()
+            .map(String::as_str)
+            .chain(fallback)
+            .chain(self.portfolio.iter().flat_map(|p| p.territory_weights.iter().map(|(t, _)| t.as_str())))
+            .chain(self.rate_index.iter().flat_map(|r| r.basket.iter().map(|b| b.territory.as_str())))
+            .chain(cat.catalogue.iter().flatten().map(|e| e.territory.as_str()))
+            .chain(self.scenario.iter().flat_map(|s| s.events.iter().map(|e| e.territory.as_str())))
+            .chain(self.exposure_growth.iter().flat_map(|g| g.territories.iter().map(|t| t.territory.as_str())))
+            .chain(self.attritional.overrides.iter().filter_map(|o| o.territory.as_deref()))
+            .chain(self.territory_limits.iter().flat_map(|l| l.overrides.keys().map(String::as_str)))
+    }
+
+    /// The run's territory table: every name in the config, cat territories first so
+    /// `Territory(i)` is `catastrophe.territories[i]`. Deterministic, so a run and the
+    /// `RunMetadata` header of its log build the same table.
+    pub fn territory_table(&self) -> TerritoryTable {
+        let mut table = TerritoryTable::default();
+        for name in self.territory_names() {
+            table.intern(name);
+        }
+        table
+    }
+
+    /// Stable fingerprint of the parameter set: FNV-1a over the JSON encoding, with `seed`
+    /// excluded so replicate batches of one experiment share a hash. 16 hex digits.
+    pub fn config_hash(&self) -> String {
+        let mut config = self.clone();
+        config.seed = 0;
+        let bytes = serde_json::to_vec(&config).expect("config serializes");
+        let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, &b| {
+            (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        format!("{hash:016x}")
+    }
+
+    /// Cross-field checks, returning every problem found rather than the first: a run with no
+    /// simulated years, no insurers or non-positive capital, ELFs outside [0, 1], cat classes
+    /// with infinite-mean (shape ≤ 1) or degenerate severities, no territories, more quotes per
+    /// submission than insurers, negative or non-finite rates and σs, and scenario or catalogue
+    /// events in unknown territories. `Simulation::from_config` calls this before building
+    /// anything; without it these configs fail deep inside the run as panics or NaNs.
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+        let mut fail = |field: String, message: &str| errors.push(ConfigError { field, message: message.to_string() });
+        let non_negative = |x: f64| x.is_finite() && x >= 0.0;
+        let unit = |x: f64| (0.0..=1.0).contains(&x);
+
+        if self.years == 0 {
+            fail("years".into(), "must be ≥ 1: warmup_years alone produce no analysed years");
+        }
+        if self.insurers.is_empty() {
+            fail("insurers".into(), "at least one insurer is required");
+        }
+        for (i, ic) in self.insurers.iter().enumerate() {
+            if ic.initial_capital <= 0 {
+                fail(format!("insurers[{i}].initial_capital"), "must be > 0");
+            }
+            if !unit(ic.attritional_elf) {
+                fail(format!("insurers[{i}].attritional_elf"), "must be in [0, 1]");
+            }
+            if !unit(ic.cat_elf) {
+                fail(format!("insurers[{i}].cat_elf"), "must be in [0, 1]");
+            }
+            for (j, a) in ic.appetite.iter().enumerate() {
+                if ic.appetite[..j].iter().any(|b| b.line == a.line) {
+                    fail(format!("insurers[{i}].appetite[{j}].line"), "is listed twice");
+                }
+                if a.target_share.is_some_and(|s| !(s > 0.0 && s <= 1.0)) {
+                    fail(format!("insurers[{i}].appetite[{j}].target_share"), "must be in (0, 1]");
+                }
+                if !(a.loading.is_finite() && a.loading > -1.0) {
+                    fail(format!("insurers[{i}].appetite[{j}].loading"), "must be finite and > -1");
+                }
+            }
+        }
+        if let Some(q) = self.quotes_per_submission
+            && (q == 0 || q > self.insurers.len())
+        {
+            fail("quotes_per_submission".into(), "must be between 1 and the number of insurers");
+        }
+
+        let cat = &self.catastrophe;
+        if cat.territories.is_empty() {
+            fail("catastrophe.territories".into(), "at least one territory is required");
+        }
+        for (i, t) in cat.territories.iter().enumerate() {
+            if cat.territories[..i].contains(t) {
+                fail(format!("catastrophe.territories[{i}]"), "is listed twice");
+            }
+        }
+        if self.territory_names().collect::<std::collections::HashSet<_>>().len() > TerritoryTable::CAPACITY {
+            fail("catastrophe.territories".into(), "at most 65,536 distinct territory names across the config");
+        }
+        for (i, c) in cat.event_classes.iter().enumerate() {
+            let field = |name: &str| format!("catastrophe.event_classes[{i}].{name}");
+            if !non_negative(c.annual_frequency) {
+                fail(field("annual_frequency"), "must be finite and ≥ 0");
+            }
+            if !(c.pareto_scale.is_finite() && c.pareto_scale > 0.0) {
+                fail(field("pareto_scale"), "must be finite and > 0");
+            }
+            if !(c.pareto_shape.is_finite() && c.pareto_shape > 1.0) {
+                fail(field("pareto_shape"), "must be > 1: shape ≤ 1 has an infinite mean damage fraction");
+            }
+            if !(c.max_damage_fraction > 0.0 && c.max_damage_fraction <= 1.0) {
+                fail(field("max_damage_fraction"), "must be in (0, 1]");
+            }
+            if !c.footprint.iter().all(|&w| non_negative(w)) {
+                fail(field("footprint"), "weights must be finite and ≥ 0");
+            }
+        }
+        if cat.clustering.as_ref().is_some_and(|c| !non_negative(c.mean_secondaries)) {
+            fail("catastrophe.clustering.mean_secondaries".into(), "must be finite and ≥ 0");
+        }
+        for (i, e) in cat.catalogue.iter().flatten().enumerate() {
+            if !cat.territories.contains(&e.territory) {
+                fail(format!("catastrophe.catalogue[{i}].territory"), &format!("{:?} is not in catastrophe.territories", e.territory));
+            }
+        }
+        if let Some(Err(e)) = self.scenario.as_ref().map(|s| s.check(cat)) {
+            fail("scenario".into(), &e);
+        }
+
+        if !non_negative(self.attritional.annual_rate) {
+            fail("attritional.annual_rate".into(), "must be finite and ≥ 0");
+        }
+        if !non_negative(self.attritional.sigma) {
+            fail("attritional.sigma".into(), "must be finite and ≥ 0");
+        }
+        if let Some(l) = &self.large_loss {
+            if !non_negative(l.annual_rate) {
+                fail("large_loss.annual_rate".into(), "must be finite and ≥ 0");
+            }
+            if !(l.scale > 0.0 && l.scale <= 1.0) {
+                fail("large_loss.scale".into(), "must be in (0, 1]");
+            }
+            if !(l.shape.is_finite() && l.shape > 0.0) {
+                fail("large_loss.shape".into(), "must be finite and > 0");
+            }
+        }
+        if let Some(r) = &self.reinsurance {
+            if !(r.return_period.is_finite() && r.return_period >= 1.0) {
+                fail("reinsurance.return_period".into(), "must be finite and ≥ 1");
+            }
+            for (field, v) in [
+                ("retention_capital_fraction", r.retention_capital_fraction),
+                ("base_rate_on_line", r.base_rate_on_line),
+                ("target_loss_ratio", r.target_loss_ratio),
+                ("price_elasticity", r.price_elasticity),
+            ] {
+                if !non_negative(v) {
+                    fail(format!("reinsurance.{field}"), "must be finite and ≥ 0");
+                }
+            }
+            if !unit(r.cycle_weight) {
+                fail("reinsurance.cycle_weight".into(), "must be in [0, 1]");
+            }
+            if !(r.min_rate_factor > 0.0 && r.min_rate_factor <= r.max_rate_factor && r.max_rate_factor.is_finite()) {
+                fail("reinsurance.min_rate_factor".into(), "must be > 0 and ≤ max_rate_factor");
+            }
+        }
+        if self.rationing.as_ref().is_some_and(|r| r.window_days == 0) {
+            fail("rationing.window_days".into(), "must be ≥ 1");
+        }
+        if self.rollover.as_ref().is_some_and(|r| !(r.max_premium_increase.is_finite() && r.max_premium_increase >= 0.0)) {
+            fail("rollover.max_premium_increase".into(), "must be finite and ≥ 0");
+        }
+        if let Some(g) = &self.exposure_growth {
+            for (i, t) in g.territories.iter().enumerate() {
+                if !cat.territories.contains(&t.territory) {
+                    fail(
+                        format!("exposure_growth.territories[{i}].territory"),
+                        &format!("{:?} is not in catastrophe.territories", t.territory),
+                    );
+                }
+                if g.territories[..i].iter().any(|u| u.territory == t.territory) {
+                    fail(format!("exposure_growth.territories[{i}].territory"), "is listed twice");
+                }
+                if !(t.annual_growth.is_finite() && t.annual_growth > -1.0) {
+                    fail(format!("exposure_growth.territories[{i}].annual_growth"), "must be finite and > -1");
+                }
+            }
+        }
+        if !non_negative(self.attritional.contagion) {
+            fail("attritional.contagion".into(), "must be finite and ≥ 0");
+        }
+        for (i, o) in self.attritional.overrides.iter().enumerate() {
+            if o.insured_id.is_none() == o.territory.is_none() {
+                fail(format!("attritional.overrides[{i}]"), "must name exactly one of insured_id or territory");
+            }
+            if let Some(t) = &o.territory
+                && !cat.territories.contains(t)
+            {
+                fail(format!("attritional.overrides[{i}].territory"), &format!("{t:?} is not in catastrophe.territories"));
+            }
+            for (field, v) in [("annual_rate", o.annual_rate), ("sigma", o.sigma), ("contagion", o.contagion)] {
+                if v.is_some_and(|v| !non_negative(v)) {
+                    fail(format!("attritional.overrides[{i}].{field}"), "must be finite and ≥ 0");
+                }
+            }
+            if o.mu.is_some_and(|v| !v.is_finite()) {
+                fail(format!("attritional.overrides[{i}].mu"), "must be finite");
+            }
+        }
+        if !non_negative(self.max_rol_sigma) {
+            fail("max_rol_sigma".into(), "must be finite and ≥ 0");
+        }
+        if let Some(p) = &self.portfolio {
+            if !non_negative(p.sum_insured_sigma) {
+                fail("portfolio.sum_insured_sigma".into(), "must be finite and ≥ 0");
+            }
+            if !non_negative(p.attritional_rate_sigma) {
+                fail("portfolio.attritional_rate_sigma".into(), "must be finite and ≥ 0");
+            }
+        }
+        if let Some(q) = &self.quote_latency {
+            if !non_negative(q.base_turnaround_days) {
+                fail("quote_latency.base_turnaround_days".into(), "must be finite and ≥ 0");
+            }
+            if q.capacity_per_window.is_nan() || q.capacity_per_window <= 0.0 {
+                fail("quote_latency.capacity_per_window".into(), "must be > 0");
+            }
+        }
+        if let Some(inv) = &self.investment
+            && !(inv.mean_return.is_finite() && inv.volatility.is_finite())
+        {
+            fail("investment".into(), "mean_return and volatility must be finite");
+        }
+        if let Some(r) = &self.interest_rates {
+            if ![r.initial_rate, r.long_run_rate, r.volatility, r.discount_credit].iter().all(|x| x.is_finite()) {
+                fail("interest_rates".into(), "rates, volatility and discount_credit must be finite");
+            }
+            if !(0.0..=1.0).contains(&r.mean_reversion) {
+                fail("interest_rates.mean_reversion".into(), "must be in [0, 1]");
+            }
+            if r.volatility < 0.0 || r.discount_credit < 0.0 {
+                fail("interest_rates".into(), "volatility and discount_credit must be ≥ 0");
+            }
+        }
+        if let Some(g) = &self.growth_targets {
+            if !(g.annual_growth.is_finite() && g.annual_growth > -1.0) {
+                fail("growth_targets.annual_growth".into(), "must be finite and > -1");
+            }
+            if !non_negative(g.undercut_per_shortfall) {
+                fail("growth_targets.undercut_per_shortfall".into(), "must be finite and ≥ 0");
+            }
+            if !non_negative(g.price_floor) {
+                fail("growth_targets.price_floor".into(), "must be finite and ≥ 0");
+            }
+        }
+        if let Some(v) = &self.risk_views
+            && !non_negative(v.cat_elf_sigma)
+        {
+            fail("risk_views.cat_elf_sigma".into(), "must be finite and ≥ 0");
+        }
+        if let Some(c) = &self.casualty {
+            if self.claims_development.is_none() {
+                fail("casualty".into(), "requires claims_development (late reports are reserved and paid over time)");
+            }
+            if !non_negative(c.annual_rate) {
+                fail("casualty.annual_rate".into(), "must be finite and ≥ 0");
+            }
+            if !(c.scale > 0.0 && c.scale <= 1.0) {
+                fail("casualty.scale".into(), "must be in (0, 1]");
+            }
+            if !(c.shape.is_finite() && c.shape > 0.0) {
+                fail("casualty.shape".into(), "must be finite and > 0");
+            }
+            let total: f64 = c.reporting_pattern.iter().sum();
+            if c.reporting_pattern.is_empty() || !c.reporting_pattern.iter().all(|&s| non_negative(s)) || (total - 1.0).abs() > 1e-6 {
+                fail("casualty.reporting_pattern".into(), "must be non-empty, non-negative and sum to 1");
+            }
+            if !non_negative(c.reserve_sigma) {
+                fail("casualty.reserve_sigma".into(), "must be finite and ≥ 0");
+            }
         }
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
     }
 }
@@ -0,0 +1,314 @@
+//! Underwriting-cycle diagnostics: does the simulated rate on line cycle, and if so how long
+//! and how deep is the cycle?
+//!
+//! Each run's RoL series is first detrended (least-squares line removed, so inflation or slow
+//! drift does not read as a cycle) and expressed relative to the run's mean RoL. On that series:
+//! - the autocorrelation function (ACF) measures persistence — a cycle shows as positive
+//!   short lags turning negative around half the period;
+//! - zero-crossings split the series into half-cycles; the period is twice their mean length;
+//! - the peak-to-trough amplitude is the mean gap between the extreme of each half-cycle and
+//!   the opposite extreme of the next, as a fraction of mean RoL.
+//!
+//! Pooled figures combine every run: the ACF is averaged over runs, and the period and
+//! amplitude are taken over all half-cycles of all runs.
+//!
+//! [`rate_response`] conditions on the loss side: it buckets every run-year by its cat
+//! ground-up loss (none / moderate / severe) and reports the quantiles of the RoL change one
+//! and two years later in each bucket — a quantile regression on a three-level covariate, and a
+//! direct test of "rates spike 1–2 years after high-loss years".
+
+use serde::Serialize;
+
+use crate::analysis::{DistStats, YearStats, percentile_stats};
+
+/// Cycle diagnostics for one RoL series, or pooled over several.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CycleStats {
+    /// Years in the series (summed over runs when pooled).
+    pub years: usize,
+    /// Autocorrelation of detrended RoL at lags 0..=max_lag; `acf[0]` is 1.0 unless the series
+    /// is flat. Lags the series is too short for are 0.0.
+    pub acf: Vec<f64>,
+    /// Estimated cycle length in years. `None` with fewer than two zero-crossings.
+    pub period: Option<f64>,
+    /// Mean peak-to-trough swing as a fraction of mean RoL. `None` without a full half-cycle
+    /// on each side of a crossing.
+    pub peak_to_trough: Option<f64>,
+}
+
+/// Per-run and pooled cycle diagnostics for a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleReport {
+    /// One entry per non-empty run, in input order.
+    pub per_run: Vec<CycleStats>,
+    pub pooled: CycleStats,
+    /// Spread of the per-run periods over the runs that have one. `None` if none do.
+    pub run_periods: Option<DistStats>,
+}
+
+/// Half-cycle decomposition of one detrended series: the years between the first and last
+/// crossing, the number of crossings, and the extreme of each half-cycle.
+struct HalfCycles {
+    span: f64,
+    crossings: usize,
+    extremes: Vec<f64>,
+}
+
+/// Cycle diagnostics for every run's `YearStats::rate_on_line` series, and pooled.
+pub fn cycle(runs: &[Vec<YearStats>], max_lag: usize) -> CycleReport {
+    let series: Vec<Vec<f64>> = runs
+        .iter()
+        .filter(|r| !r.is_empty())
+        .map(|run| detrended_index(&run.iter().map(|s| s.rate_on_line()).collect::<Vec<_>>()))
+        .collect();
+    let per_run: Vec<CycleStats> = series.iter().map(|xs| stats_of(&[xs], max_lag)).collect();
+    let all: Vec<&Vec<f64>> = series.iter().collect();
+    let run_periods = percentile_stats(&mut per_run.iter().filter_map(|r| r.period).collect());
+    CycleReport { per_run, pooled: stats_of(&all, max_lag), run_periods }
+}
+
+/// Sample autocorrelation at `lag`. Zero for series shorter than `lag + 2` or with no variance.
+pub fn autocorrelation(xs: &[f64], lag: usize) -> f64 {
+    if xs.len() < lag + 2 {
+        return 0.0;
+    }
+    let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+    let var: f64 = xs.iter().map(|x| (x - mean).powi(2)).sum();
+    if var == 0.0 {
+        return 0.0;
+    }
+    let cov: f64 = xs.iter().zip(&xs[lag..]).map(|(a, b)| (a - mean) * (b - mean)).sum();
+    cov / var
+}
+
+fn stats_of(series: &[&Vec<f64>], max_lag: usize) -> CycleStats {
+    let years = series.iter().map(|xs| xs.len()).sum();
+    // Pooled ACF weights each run by its length.
+    let acf = (0..=max_lag)
+        .map(|lag| {
+            if years == 0 {
+                return 0.0;
+            }
+            series.iter().map(|xs| autocorrelation(xs, lag) * xs.len() as f64).sum::<f64>() / years as f64
+        })
+        .collect();
+
+    let halves: Vec<HalfCycles> = series.iter().map(|xs| half_cycles(xs)).collect();
+    // Each run contributes (crossings − 1) half-cycles over its span.
+    let half_count: usize = halves.iter().map(|h| h.crossings.saturating_sub(1)).sum();
+    let period = (half_count > 0).then(|| 2.0 * halves.iter().map(|h| h.span).sum::<f64>() / half_count as f64);
+    let swings: Vec<f64> =
+        halves.iter().flat_map(|h| h.extremes.windows(2).map(|w| (w[0] - w[1]).abs())).collect();
+    let peak_to_trough = (!swings.is_empty()).then(|| swings.iter().sum::<f64>() / swings.len() as f64);
+
+    CycleStats { years, acf, period, peak_to_trough }
+}
+
+/// Remove the least-squares linear trend and divide by the series mean. A series with a zero
+/// mean is detrended only.
+fn detrended_index(xs: &[f64]) -> Vec<f64> {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    let t_mean = (n - 1.0) / 2.0;
+    let stt: f64 = (0..xs.len()).map(|t| (t as f64 - t_mean).powi(2)).sum();
+    let slope = if stt > 0.0 {
+        xs.iter().enumerate().map(|(t, x)| (t as f64 - t_mean) * (x - mean)).sum::<f64>() / stt
+    } else {
+        0.0
+    };
+    let scale = if mean != 0.0 { mean } else { 1.0 };
+    xs.iter().enumerate().map(|(t, x)| (x - mean - slope * (t as f64 - t_mean)) / scale).collect()
+}
+
+/// Split a detrended series at its sign changes. Crossings are placed by linear interpolation
+/// between the two straddling years; exact zeros join the preceding half-cycle.
+fn half_cycles(xs: &[f64]) -> HalfCycles {
+    let mut crossings: Vec<f64> = Vec::new();
+    let mut extremes: Vec<f64> = Vec::new();
+    let mut current: Option<f64> = None;
+    for (t, w) in xs.windows(2).enumerate() {
+        if (w[0] > 0.0 && w[1] < 0.0) || (w[0] < 0.0 && w[1] > 0.0) {
+            crossings.push(t as f64 + w[0] / (w[0] - w[1]));
+            // The half-cycle before the first crossing is incomplete and has no extreme.
+            if let (Some(e), false) = (current, crossings.len() == 1) {
+                extremes.push(e);
+            }
+            current = Some(w[1]);
+        } else if let Some(e) = current.as_mut()
+            && w[1].abs() > e.abs()
+        {
+            *e = w[1];
+        }
+    }
+    let span = match (crossings.first(), crossings.last()) {
+        (Some(a), Some(b)) => b - a,
+        _ => 0.0,
+    };
+    HalfCycles { span, crossings: crossings.len(), extremes }
+}
+
+/// Cat-loss severity of a year, by cat GUL as a fraction of sum insured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CatLossBucket {
+    /// No cat ground-up loss.
+    None,
+    /// Some cat loss, below the severe threshold.
+    Moderate,
+    /// Cat loss at or above the severe threshold.
+    Severe,
+}
+
+/// RoL changes following the years in one bucket.
+#[derive(Debug, Clone, Serialize)]
+pub struct RateResponseRow {
+    pub bucket: CatLossBucket,
+    /// Run-years in the bucket (with a positive RoL and a following year).
+    pub years: usize,
+    /// Relative RoL change to the next year (0.10 = +10%). `None` if the bucket is empty.
+    pub next_year: Option<DistStats>,
+    /// Relative RoL change over two years; fewer observations, as the last two years have none.
+    pub two_year: Option<DistStats>,
+}
+
+/// Conditional distribution of rate changes on current-year cat losses, pooled over runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct RateResponse {
+    /// Cat GUL / sum insured at which a year counts as severe: the `severe_quantile` of the
+    /// cat-hit years. 0.0 when no year had a cat loss.
+    pub severe_threshold: f64,
+    /// One row per bucket, None → Severe.
+    pub rows: Vec<RateResponseRow>,
+}
+
+/// Next-year and two-year RoL changes conditional on each year's cat loss, pooled over `runs`.
+/// Years with a cat loss split into moderate and severe at the `severe_quantile` (e.g. 0.75 =
+/// the worst quarter) of cat GUL / sum insured over all cat-hit years of all runs, so the
+/// buckets adapt to the cat model. Years with no sum insured or no RoL are skipped.
+pub fn rate_response(runs: &[Vec<YearStats>], severe_quantile: f64) -> RateResponse {
+    let loss = |s: &YearStats| (s.sum_insured > 0).then(|| s.cat_gul as f64 / s.sum_insured as f64);
+    let mut hit: Vec<f64> = runs.iter().flatten().filter_map(loss).filter(|&l| l > 0.0).collect();
+    hit.sort_by(f64::total_cmp);
+    let severe_threshold = match hit.len() {
+        0 => 0.0,
+        n => hit[((n - 1) as f64 * severe_quantile.clamp(0.0, 1.0)).round() as usize],
+    };
+    let bucket = |l: f64| match l {
+        l if l <= 0.0 => CatLossBucket::None,
+        l if l < severe_threshold => CatLossBucket::Moderate,
+        _ => CatLossBucket::Severe,
+    };
+
+    let mut changes: Vec<(CatLossBucket, usize, f64)> = Vec::new();
+    for run in runs {
+        for (t, s) in run.iter().enumerate() {
+            let (Some(l), rol) = (loss(s), s.rate_on_line()) else { continue };
+            if rol <= 0.0 {
+                continue;
+            }
+            for lag in [1, 2] {
+                if let Some(later) = run.get(t + lag) {
+                    changes.push((bucket(l), lag, later.rate_on_line() / rol - 1.0));
+                }
+            }
+        }
+    }
+    let rows = [CatLossBucket::None, CatLossBucket::Moderate, CatLossBucket::Severe]
+        .into_iter()
+        .map(|b| {
+            let at = |lag| changes.iter().filter(|c| c.0 == b && c.1 == lag).map(|c| c.2).collect::<Vec<_>>();
+            let (mut next, mut two) = (at(1), at(2));
+            RateResponseRow {
+                bucket: b,
+                years: next.len(),
+                next_year: percentile_stats(&mut next),
+                two_year: percentile_stats(&mut two),
+            }
+        })
+        .collect();
+    RateResponse { severe_threshold, rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_with_rol(rol: impl Iterator<Item = f64>) -> Vec<YearStats> {
+        rol.enumerate()
+            .map(|(i, r)| {
+                let mut s = YearStats::zero(i as u32 + 1);
+                s.sum_insured = 1_000_000;
+                s.bound_premium = (r * 1_000_000.0).round() as u64;
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_trending_sine_reports_its_period_amplitude_and_alternating_acf() {
+        // 8-year cycle of ±10% around a 5% RoL, riding a trend that detrending must remove.
+        // Sampled off the zeros, so the yearly extremes are sin(3π/8) of the true peak.
+        let rol = (0..64).map(|t| {
+            let t = t as f64;
+            0.05 * (1.0 + 0.1 * (2.0 * std::f64::consts::PI * (t + 0.5) / 8.0).sin()) + 0.0002 * (t - 31.5)
+        });
+        let report = cycle(&[run_with_rol(rol)], 8);
+        let s = &report.pooled;
+
+        assert_eq!(s.years, 64);
+        assert_eq!(s.acf[0], 1.0);
+        assert!(s.acf[1] > 0.5, "adjacent years move together: {:?}", s.acf);
+        assert!(s.acf[4] < -0.5, "half a period apart they oppose: {:?}", s.acf);
+        assert!(s.acf[8] > 0.5, "a full period apart they agree again: {:?}", s.acf);
+        let period = s.period.expect("a cycle");
+        assert!((period - 8.0).abs() < 0.5, "period {period}");
+        let amplitude = s.peak_to_trough.expect("full half-cycles");
+        let expected = 0.2 * (3.0 * std::f64::consts::PI / 8.0).sin();
+        assert!((amplitude - expected).abs() < 0.01, "peak-to-trough {amplitude}, expected {expected}");
+        assert_eq!(report.per_run, vec![s.clone()], "one run pools to itself");
+        assert_eq!(report.run_periods.map(|d| (d.n, d.p50)), Some((1, period)));
+    }
+
+    #[test]
+    fn rate_response_separates_the_years_after_severe_cat_losses() {
+        // Rates rise 20% after a severe year, 5% after a moderate one and fall 2% otherwise.
+        let cat_gul = [0, 10_000, 0, 100_000].repeat(5);
+        let mut rol = 0.05;
+        let mut run = Vec::new();
+        for (t, &gul) in cat_gul.iter().enumerate() {
+            let mut s = run_with_rol(std::iter::once(rol)).remove(0);
+            s.year = t as u32 + 1;
+            s.cat_gul = gul;
+            run.push(s);
+            rol *= match gul {
+                0 => 0.98,
+                10_000 => 1.05,
+                _ => 1.20,
+            };
+        }
+        let response = rate_response(&[run], 0.75);
+        assert_eq!(response.severe_threshold, 0.1);
+        let medians: Vec<(CatLossBucket, usize, f64)> = response
+            .rows
+            .iter()
+            .map(|r| (r.bucket, r.years, r.next_year.as_ref().map_or(f64::NAN, |d| d.p50)))
+            .collect();
+        let expected = [(CatLossBucket::None, 10, -0.02), (CatLossBucket::Moderate, 5, 0.05), (CatLossBucket::Severe, 4, 0.20)];
+        for ((bucket, years, p50), (b, n, change)) in medians.into_iter().zip(expected) {
+            assert_eq!((bucket, years), (b, n));
+            assert!((p50 - change).abs() < 1e-3, "{bucket:?}: median next-year change {p50}, expected {change}");
+        }
+        let severe_two_year = response.rows[2].two_year.as_ref().expect("two-year changes").p50;
+        assert!((severe_two_year - (1.20 * 0.98 - 1.0)).abs() < 1e-3, "{severe_two_year}");
+        assert_eq!(rate_response(&[], 0.75).rows.iter().map(|r| r.years).sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn a_flat_market_has_no_cycle() {
+        let report = cycle(&[run_with_rol((0..20).map(|_| 0.05)), vec![]], 3);
+        assert_eq!(report.per_run.len(), 1, "empty runs are skipped");
+        let s = &report.pooled;
+        assert_eq!(s.acf, vec![0.0; 4], "no variance, no autocorrelation");
+        assert_eq!((s.period, s.peak_to_trough), (None, None));
+        assert!(report.run_periods.is_none());
+    }
+}
@@ -0,0 +1,129 @@
+//! The library's error type.
+//!
+//! Entry points that touch the filesystem, parse input or build a simulation from a config
+//! return [`Result`]. The module errors ([`BundleError`], [`SweepError`], …) convert into
+//! [`Error`] with `?`, so a caller driving several stages needs one error type. Panicking on
+//! an error is left to the `rins` binary.
+
+use std::io;
+use std::path::PathBuf;
+
+use crate::bundle::BundleError;
+use crate::calibration::HistoryParseError;
+use crate::config::ConfigError;
+use crate::perils::CatalogueParseError;
+use crate::simulation::{CheckpointError, ScheduleError};
+use crate::sweep::SweepError;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Why a library call failed.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing `path` failed.
+    Io { path: PathBuf, source: io::Error },
+    /// A value could not be serialised to or parsed from JSON.
+    Json(serde_json::Error),
+    /// The config failed validation; every problem found, in field order.
+    Config(Vec<ConfigError>),
+    /// A log breaks mechanics or integrity invariants; one display-form line per violation.
+    Invariant(Vec<String>),
+    Bundle(BundleError),
+    Sweep(SweepError),
+    Checkpoint(CheckpointError),
+    Schedule(ScheduleError),
+    Catalogue(CatalogueParseError),
+    History(HistoryParseError),
+}
+
+impl Error {
+    /// Tags an `io::Error` with the path it concerns, for `map_err`.
+    pub fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| Self::Io { path, source }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Json(e) => write!(f, "JSON error: {e}"),
+            Self::Config(errors) => {
+                let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "invalid config: {}", errors.join("; "))
+            }
+            Self::Invariant(violations) => {
+                write!(f, "{} invariant violation(s)", violations.len())?;
+                if let Some(first) = violations.first() {
+                    write!(f, ", first: {first}")?;
+                }
+                Ok(())
+            }
+            Self::Bundle(e) => e.fmt(f),
+            Self::Sweep(e) => e.fmt(f),
+            Self::Checkpoint(e) => e.fmt(f),
+            Self::Schedule(e) => e.fmt(f),
+            Self::Catalogue(e) => e.fmt(f),
+            Self::History(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Vec<ConfigError>> for Error {
+    fn from(errors: Vec<ConfigError>) -> Self {
+        Self::Config(errors)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<BundleError> for Error {
+    fn from(e: BundleError) -> Self {
+        Self::Bundle(e)
+    }
+}
+
+impl From<SweepError> for Error {
+    fn from(e: SweepError) -> Self {
+        Self::Sweep(e)
+    }
+}
+
+impl From<CheckpointError> for Error {
+    fn from(e: CheckpointError) -> Self {
+        Self::Checkpoint(e)
+    }
+}
+
+impl From<ScheduleError> for Error {
+    fn from(e: ScheduleError) -> Self {
+        Self::Schedule(e)
+    }
+}
+
+impl From<CatalogueParseError> for Error {
+    fn from(e: CatalogueParseError) -> Self {
+        Self::Catalogue(e)
+    }
+}
+
+impl From<HistoryParseError> for Error {
+    fn from(e: HistoryParseError) -> Self {
+        Self::History(e)
+    }
+}
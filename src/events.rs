@@ -2,30 +2,401 @@ use std::cmp::Ordering;
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{Day, InsuredId, InsurerId, PolicyId, SubmissionId, Year};
+use crate::config::SimulationConfig;
+use crate::types::{BrokerId, ClaimId, Day, InsuredId, InsurerId, PolicyId, SubmissionId, Territory, Year};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Peril {
     WindstormAtlantic,
     Attritional,
+    /// A single-risk large loss (a fire destroying much or all of one asset).
+    LargeFire,
+    /// Third-party liability on the long-tail casualty line: charged to the policy in force on
+    /// the accident day, reported in a later year.
+    Casualty,
+}
+
+impl Peril {
+    /// Catastrophe perils strike many insureds in one occurrence; the rest are single-risk.
+    pub fn is_cat(self) -> bool {
+        matches!(self, Peril::WindstormAtlantic)
+    }
+
+    /// The line of business this peril is written under.
+    pub fn line(self) -> Line {
+        match self {
+            Peril::WindstormAtlantic | Peril::Attritional | Peril::LargeFire => Line::Property,
+            Peril::Casualty => Line::Casualty,
+        }
+    }
+}
+
+/// A line of business: the perils an insurer plans, prices and sets appetite for together.
+/// Serialized as its id, an index into `RunMetadata.lines`; hand-written configs may name it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Line {
+    /// First-party damage to the insured asset: cat, attritional and large-loss perils.
+    Property,
+    /// Third-party liability (`Peril::Casualty`).
+    Casualty,
+}
+
+impl Line {
+    /// Every line, indexed by id.
+    pub const ALL: [Line; 2] = [Line::Property, Line::Casualty];
+
+    pub fn id(self) -> u16 {
+        self as u16
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Line::Property => "Property",
+            Line::Casualty => "Casualty",
+        }
+    }
+}
+
+impl Serialize for Line {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Line {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LineVisitor;
+
+        impl serde::de::Visitor<'_> for LineVisitor {
+            type Value = Line;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a line id or name")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, id: u64) -> Result<Line, E> {
+                Line::ALL.get(id as usize).copied().ok_or_else(|| E::custom(format!("unknown line id {id}")))
+            }
+
+            // Names from hand-written configs; ids as strings from map keys.
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Line, E> {
+                match name.parse::<u64>() {
+                    Ok(id) => self.visit_u64(id),
+                    Err(_) => Line::ALL
+                        .into_iter()
+                        .find(|l| l.name() == name)
+                        .ok_or_else(|| E::unknown_variant(name, &["Property", "Casualty"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(LineVisitor)
+    }
+}
+
+/// Curvature of the exposure curve used to credit deductibles and limits (MBBEFD-style
+/// `G(x) = ln(1 + (g − 1)x) / ln g`). Larger `g` = losses concentrated in the first few
+/// percent of value: g = 50 credits a 1%-of-value deductible with ~10% of expected loss.
+pub const EXPOSURE_CURVE_G: f64 = 50.0;
+
+/// Version of the event-log schema this build writes, recorded in `SimulationStart`. Logs
+/// that predate the field are version 1. Fields added to an existing event carry a serde
+/// default so older logs still parse; bump the version when an event changes shape.
+pub const SCHEMA_VERSION: u32 = 4;
+
+fn legacy_schema_version() -> u32 {
+    1
 }
 
 /// The risk being submitted for coverage.
-/// Full coverage: the insurer writes limit = sum_insured, attachment = 0.
+/// Each occurrence pays `min(ground_up − attachment, limit)`. Full coverage: attachment = 0,
+/// limit = sum_insured.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RiskRecord")]
 pub struct Risk {
     pub sum_insured: u64, // monetary units (e.g. USD cents)
-    pub territory: String,
+    pub territory: Territory,
     pub perils_covered: Vec<Peril>,
+    /// Per-occurrence deductible retained by the insured (cents).
+    pub attachment: u64,
+    /// Per-occurrence limit above the attachment (cents).
+    pub limit: u64,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// `Risk` as logged. Logs written before deductibles and limits carry neither and are full
+/// coverage.
+#[derive(Deserialize)]
+struct RiskRecord {
+    sum_insured: u64,
+    territory: Territory,
+    perils_covered: Vec<Peril>,
+    #[serde(default)]
+    attachment: u64,
+    limit: Option<u64>,
+}
+
+impl From<RiskRecord> for Risk {
+    fn from(r: RiskRecord) -> Self {
+        Risk {
+            sum_insured: r.sum_insured,
+            territory: r.territory,
+            perils_covered: r.perils_covered,
+            attachment: r.attachment,
+            limit: r.limit.unwrap_or(r.sum_insured),
+        }
+    }
+}
+
+impl Risk {
+    /// Insured claim for one occurrence of `ground_up_loss`.
+    pub fn claim_for(&self, ground_up_loss: u64) -> u64 {
+        ground_up_loss.saturating_sub(self.attachment).min(self.limit)
+    }
+
+    /// Largest possible claim from one occurrence.
+    pub fn max_claim(&self) -> u64 {
+        self.limit.min(self.sum_insured.saturating_sub(self.attachment))
+    }
+
+    /// Share of ground-up expected loss that falls in the layer `attachment xs limit`,
+    /// read off the exposure curve. 1.0 for full coverage.
+    pub fn layer_factor(&self) -> f64 {
+        if self.sum_insured == 0 {
+            return 0.0;
+        }
+        let si = self.sum_insured as f64;
+        let g = |x: f64| (1.0 + (EXPOSURE_CURVE_G - 1.0) * x.clamp(0.0, 1.0)).ln() / EXPOSURE_CURVE_G.ln();
+        let lo = self.attachment as f64 / si;
+        let hi = (self.attachment.saturating_add(self.limit)) as f64 / si;
+        (g(hi) - g(lo)).max(0.0)
+    }
+
+    /// The line of business the risk is placed under. A risk covers the perils of one line;
+    /// an insured buying several lines places one risk per line.
+    pub fn line(&self) -> Line {
+        self.perils_covered.first().map_or(Line::Property, |p| p.line())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum DeclineReason {
     MaxLineSizeExceeded,
     MaxCatAggregateBreached,
     Insolvent,
     /// Follower declines because the lead's premium is below the follower's own Technical Premium.
     RateBelowTP,
+    /// The insurer's quote turnaround exceeded the broker's deadline (underwriting workload).
+    TurnaroundExceeded,
+    /// The insurer is in voluntary run-off and writes no new business.
+    InRunoff,
+    /// The risk's territory is at its per-territory PML limit (`TerritoryLimitConfig`).
+    TerritoryAggregateBreached,
+    /// The insurer is restricted to renewals by the regulator and the risk is new business.
+    RegulatoryRestriction,
+    /// The risk covers a line the insurer does not write (`LineAppetite::write`).
+    OutsideAppetite,
+}
+
+/// A rung of the regulatory intervention ladder (see `regulator.rs`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegulatoryAction {
+    /// Solvency ratio below `restrict_below`: the insurer may only renew existing business.
+    RestrictedToRenewals,
+    /// Solvency ratio below `runoff_below`: no new business; the book runs off.
+    ForcedRunoff,
+    /// Solvency ratio below `revoke_below`: the insurer is closed as insolvent.
+    LicenceRevoked,
+    /// Solvency ratio back above `restrict_below`: standing intervention lifted.
+    Reinstated,
+}
+
+/// Why an insurer voluntarily stopped writing new business.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitReason {
+    /// Trailing own combined ratio above `runoff_cr_threshold`.
+    AdverseExperience,
+    /// Capital below `capital_exit_floor` × initial capital.
+    CapitalFloor,
+    /// Among the weakest writers after a prolonged soft market (`soft_market_exit`).
+    SoftMarket,
+}
+
+/// What moved an insurer's capital, on a [`Event::CapitalFlow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CapitalFlowReason {
+    /// Premium credited net of the expense ratio, at binding or anniversary (cash basis: on receipt).
+    Premium,
+    /// Uncollected premium charged back at cancellation.
+    PremiumReversal,
+    /// A claim or reserve charge (incurred basis).
+    Claim,
+    /// A case reserve released on payment.
+    ReserveRelease,
+    /// Fixed annual overhead under an expense split.
+    Expense,
+    /// Profit paid out to Names at YearEnd.
+    Distribution,
+    /// The year's investment return, positive or negative.
+    InvestmentIncome,
+    /// Fresh capital raised, net of its issuance cost.
+    CapitalRaise,
+    ReinsurancePremium,
+    ReinsuranceRecovery,
+    CatBondPremium,
+    CatBondPayout,
+    /// The price of a portfolio transfer: received by the seller, paid by the buyer.
+    PortfolioTransfer,
+}
+
+/// Provenance of one run, written as the first line of every event-log file so a directory of
+/// logs can be audited without the command lines that produced them. Never part of
+/// `Simulation.log`: two runs of one config still produce identical logs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    /// `CARGO_PKG_VERSION` of the writer.
+    pub crate_version: String,
+    /// Commit the writer was built from. `None` for builds outside a git checkout.
+    pub git_hash: Option<String>,
+    pub seed: u64,
+    /// `SimulationConfig::config_hash` of `config`.
+    pub config_hash: String,
+    /// Seconds since the Unix epoch when the log was written.
+    pub started_at: u64,
+    pub hostname: Option<String>,
+    pub os: String,
+    pub arch: String,
+    /// The fully resolved config, as JSON (kept untyped so logs stay readable after the config
+    /// gains fields).
+    pub config: serde_json::Value,
+    /// The run's territory names (`SimulationConfig::territory_table`), indexed by the
+    /// `Territory` ids its events carry. Empty in logs that predate interning.
+    #[serde(default)]
+    pub territories: Vec<String>,
+    /// Line names, indexed by `Line::id`. Empty in logs that predate interning.
+    #[serde(default)]
+    pub lines: Vec<String>,
+}
+
+impl RunMetadata {
+    /// Metadata for a run of `config` written now, on this host.
+    pub fn capture(config: &SimulationConfig) -> Self {
+        let started_at =
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let hostname = std::env::var("HOSTNAME")
+            .ok()
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty());
+        RunMetadata {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: option_env!("RINS_GIT_HASH").map(str::to_string),
+            seed: config.seed,
+            config_hash: config.config_hash(),
+            started_at,
+            hostname,
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            config: serde_json::to_value(config).expect("config serializes"),
+            territories: config.territory_table().names().to_vec(),
+            lines: Line::ALL.iter().map(|l| l.name().to_string()).collect(),
+        }
+    }
+}
+
+/// A broker's view of one territory over the year, from the lead quotes it received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerritoryIntelligence {
+    pub territory: Territory,
+    /// Lead quotes issued for risks in this territory.
+    pub quotes: u32,
+    /// Mean quoted rate on line (premium / sum_insured).
+    pub avg_rate: f64,
+    /// Standard deviation of quoted rates on line (0.0 with fewer than two quotes).
+    pub rate_dispersion: f64,
+}
+
+/// Why an insured turned down a presented quote.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RejectionReason {
+    /// Rate on line exceeds the insured's hard reservation price (`effective_max_rol`). The
+    /// only reason before price-elastic demand, so logs without a reason read as this.
+    #[default]
+    ReservationPriceExceeded,
+    /// Price-elastic demand: the insured could afford the rate but chose not to buy.
+    PriceTooHigh,
+}
+
+/// How a cancelled policy's premium is settled: after a premium default, or (always
+/// `AbInitio`, since the next instalment is never collected) at a multi-year anniversary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CancellationBasis {
+    /// Cover is void from inception on the default day: no premium is earned, so all
+    /// uncollected premium is reversed and there is no bad debt.
+    AbInitio,
+    /// Cover runs to the end of the grace period: premium is earned for the time on risk;
+    /// earned-but-uncollected premium is bad debt, the unearned remainder is reversed.
+    ProRata,
+}
+
+/// How a cat bond decides it has been hit (see `ils.rs`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CatBondTrigger {
+    /// Pays the remaining principal on any WindstormAtlantic occurrence whose damage fraction
+    /// reaches `damage_fraction`, whatever the sponsor's own loss.
+    Parametric { damage_fraction: f64 },
+    /// Pays the sponsor's insured loss from one occurrence in excess of an attachment of
+    /// `attachment_capital_fraction` × its capital at issue, up to the remaining principal.
+    Indemnity { attachment_capital_fraction: f64 },
+}
+
+/// Why a policy was cancelled before its expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CancellationReason {
+    /// The insured missed a premium instalment (`PremiumDefaulted`).
+    PremiumDefault,
+    /// Insured-initiated at a multi-year anniversary: the lead now quotes below the locked rate.
+    RateDrop,
+    /// Insurer-initiated at a multi-year anniversary: a panel insurer is insolvent.
+    InsurerInsolvent,
+}
+
+/// Payload of [`Event::CatBondIssued`], boxed so a yearly event does not widen every other
+/// variant. The sponsor pays `premium`, the whole term's coupon, at issue; `attachment` is the
+/// indemnity attachment (cents, 0 for parametric bonds). `remaining_capital` is back-filled
+/// after the premium is paid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatBondIssue {
+    pub bond_id: u64,
+    pub insurer_id: InsurerId,
+    pub trigger: CatBondTrigger,
+    pub principal: u64,
+    pub attachment: u64,
+    pub premium: u64,
+    pub maturity_year: Year,
+    pub remaining_capital: u64,
+}
+
+/// Payload of [`Event::PortfolioTransferred`], boxed like [`CatBondIssue`]: the valuation
+/// inputs behind the price (see `transfer.rs`). Emitted right after the seller's
+/// `InsurerExited`; capitals and broker relationships have already moved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioTransfer {
+    pub seller_id: InsurerId,
+    pub buyer_id: InsurerId,
+    /// In-force policies on which the seller's line moved to the buyer.
+    pub policies: u32,
+    /// Unearned premium net of expenses on the transferred lines (cents).
+    pub unearned_premium: u64,
+    /// Buyer's expected losses on the unexpired risk (cents).
+    pub expected_losses: u64,
+    /// Annual gross premium of the book at renewal (cents).
+    pub renewal_premium: u64,
+    /// Value of the renewal rights (cents).
+    pub franchise_value: u64,
+    /// Outstanding claim reserves the seller keeps in run-off (cents).
+    pub retained_reserves: u64,
+    /// Net amount the buyer paid the seller (cents); negative = the seller paid.
+    pub price: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -33,20 +404,117 @@ pub enum Event {
     /// Fires once at Day(0) to bootstrap the simulation. Schedules YearStart(year_start).
     /// `warmup_years` warm-up years are prepended before the `analysis_years` analysis period;
     /// analysis scripts skip years ≤ warmup_years when generating output tables.
-    SimulationStart { year_start: Year, warmup_years: u32, analysis_years: u32 },
+    /// `schema_version` is the writer's [`SCHEMA_VERSION`] (1 for logs that predate it).
+    SimulationStart {
+        year_start: Year,
+        warmup_years: u32,
+        analysis_years: u32,
+        #[serde(default = "legacy_schema_version")]
+        schema_version: u32,
+    },
+    /// Provenance header: the first line of a written log file, at Day(0). Added by the log
+    /// writers, never dispatched or logged by the simulation.
+    RunMetadata(Box<RunMetadata>),
+    /// Initial conditions of one starting insurer, logged at Day(0) by `start()` (never queued)
+    /// so a saved log can be analysed without the config that produced it.
+    InsurerCreated {
+        insurer_id: InsurerId,
+        initial_capital: u64,
+        expense_ratio: f64,
+        target_loss_ratio: f64,
+        profit_loading: f64,
+        /// The insurer's own view of the cat ELF: the configured value, times its model error
+        /// under `risk_views`. 0.0 in logs that predate the field.
+        #[serde(default)]
+        cat_elf: f64,
+    },
+    /// Initial conditions of one insured, logged at Day(0) by `start()` (never queued).
+    InsuredCreated {
+        insured_id: InsuredId,
+        territory: Territory,
+        sum_insured: u64,
+        /// Insureds holding several units only (`assets_per_insured > 1`, or `casualty`): each
+        /// asset and the liability cover is created as its own `insured_id`, and `owner` is
+        /// the id of the owner's first asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        owner: Option<InsuredId>,
+    },
     /// Fires at the start of each simulated year.
     YearStart { year: Year },
     /// Fires at the end of each simulated year.
     YearEnd { year: Year },
+    /// Quarterly reporting only: interim market stats on the last day of `quarter` (1–4), Q4
+    /// ahead of `YearEnd`. `written_premium` (`PolicyBound` and `PolicyAnniversary` premium)
+    /// and `claims_incurred` (`ClaimSettled` amounts and `ClaimReported` ultimates) are
+    /// quarter-to-date; `capital` is the market's capital (each insurer floored at zero). All
+    /// three are back-filled at dispatch.
+    QuarterEnd { year: Year, quarter: u8, written_premium: u64, claims_incurred: u64, capital: u64 },
+    /// A broker's annual digest of its placement activity, published at YearEnd.
+    /// `decline_rate` = declines / quote responses (lead and follower); territories in id order.
+    MarketIntelligence {
+        broker_id: BrokerId,
+        year: Year,
+        avg_quoted_rate: f64,
+        quote_responses: u32,
+        decline_rate: f64,
+        territories: Vec<TerritoryIntelligence>,
+    },
+    /// PML reporting only: the insurer's modelled occurrence losses (cents) at the 1-in-100,
+    /// 1-in-200 and 1-in-250 return periods on its in-force cat aggregate, at YearEnd before
+    /// its `SolvencyRatioReported`.
+    PmlReported { insurer_id: InsurerId, rp_100: u64, rp_200: u64, rp_250: u64 },
+    /// Rate index only: the mean rate on line at which the writing insurers would quote the
+    /// fixed reference basket now, at YearEnd after the AP/TP update. `index` = 100 × rate /
+    /// the first published rate.
+    RateIndexPublished { year: Year, rate_on_line: f64, index: f64, insurers: u32 },
+    /// Engine health snapshot, logged every `HealthConfig.interval_days` before the first event
+    /// on or after each boundary. Logged directly (never queued), so it does not perturb the run.
+    SimHealth {
+        /// Events pending in the queue.
+        queue_depth: u64,
+        /// Events dispatched since the simulation started.
+        events_dispatched: u64,
+        /// Policies currently in force.
+        active_policies: u32,
+        /// Submissions brokers are still placing.
+        pending_submissions: u32,
+    },
+    /// One insurer's live exposure in one territory to one peril, logged every
+    /// `ExposureSnapshotConfig.interval_days` before the first event on or after each boundary:
+    /// Σ sum_insured × signed line over its in-force policies covering the peril. One event per
+    /// non-zero (insurer, territory, peril), in that order. Logged directly (never queued).
+    ExposureSnapshot { insurer_id: InsurerId, territory: Territory, peril: Peril, aggregate_sum_insured: u64 },
+    /// Exposure growth only: the real exposure index of one listed territory, logged directly
+    /// (never queued) at each YearStart after year 1, one per territory in config order. Insured
+    /// values there are revalued to `index` × base (times any inflation index) at their next
+    /// coverage request.
+    ExposureGrowth { year: Year, territory: Territory, annual_growth: f64, index: f64 },
+    /// Interest rates only: the year's short rate, logged directly (never queued) at each
+    /// YearStart before anything is priced. Insurers discount long-tail losses at it and earn it
+    /// on capital at the year's `InvestmentReturnDrawn`.
+    InterestRatePublished { year: Year, rate: f64 },
+    /// Growth targets only: an insurer's underwriting plan for the year, logged directly (never
+    /// queued) at YearStart. `target_premium` is `prior_premium` (last year's written premium)
+    /// grown at the target rate; `undercut` is the fractional price cut it will apply this year
+    /// because last year missed its target (0.0 when it was met).
+    UnderwritingPlanSet { insurer_id: InsurerId, year: Year, prior_premium: u64, target_premium: u64, undercut: f64 },
     /// An insured requests coverage for the year. Broker routes to a lead insurer.
-    CoverageRequested { insured_id: InsuredId, risk: Risk },
+    CoverageRequested { insured_id: InsuredId, risk: Box<Risk> },
+    /// The insured's broker has opened a submission. Same day as CoverageRequested;
+    /// `broker_id` identifies the distribution channel that will place the risk.
+    SubmissionRouted { submission_id: SubmissionId, insured_id: InsuredId, broker_id: BrokerId },
     /// Broker asks the selected lead insurer to price a risk.
     LeadQuoteRequested {
         submission_id: SubmissionId,
         insured_id: InsuredId,
         insurer_id: InsurerId,
-        risk: Risk,
+        risk: Box<Risk>,
     },
+    /// Quota rationing only: an insurer answered the cat-exposed lead requests it batched over
+    /// the rationing window, in priority order, against `cat_headroom` (cents, back-filled at
+    /// dispatch). Its `LeadQuoteIssued` / `LeadQuotePartial` / `LeadQuoteDeclined` responses
+    /// follow on the same day, `requests` of them.
+    LeadQuoteBatchClosed { insurer_id: InsurerId, requests: u32, cat_headroom: u64 },
     /// Lead insurer declined to quote — exposure limit breached.
     /// Broker will re-route to the next insurer.
     LeadQuoteDeclined {
@@ -67,6 +535,20 @@ pub enum Event {
         /// Derived from capital headroom and pricing adequacy; see Phase 5 formula.
         line_size: f64,
     },
+    /// Lead insurer at its cat aggregate limit offers the remaining headroom instead of declining
+    /// (`partial_lines` set). Sets terms like `LeadQuoteIssued`, but `line_size` is firm: the
+    /// broker fills the residual from the other candidates and never scales this line up.
+    LeadQuotePartial {
+        submission_id: SubmissionId,
+        insured_id: InsuredId,
+        insurer_id: InsurerId,
+        atp: u64,
+        premium: u64,
+        cat_exposure_at_quote: u64,
+        /// Headroom under the cat aggregate limit as a fraction of the risk's largest claim,
+        /// capped by the insurer's usual lead line.
+        line_size: f64,
+    },
     /// Broker solicits a follower insurer to participate at the lead's rate.
     /// Emitted same day as `LeadQuoteIssued` for each follower in the candidate list.
     FollowerQuoteRequested {
@@ -75,7 +557,7 @@ pub enum Event {
         /// The follower being solicited.
         insurer_id: InsurerId,
         /// Needed for follower capacity checks (line and cat aggregate limits).
-        risk: Risk,
+        risk: Box<Risk>,
         /// The premium the follower would write at if it accepts (= lead's quoted premium).
         lead_premium: u64,
         /// Lead's actuarial technical price, carried for audit and Phase D observability.
@@ -96,6 +578,26 @@ pub enum Event {
         insurer_id: InsurerId,
         reason: DeclineReason,
     },
+    /// Broker offers an expiring follower its line again at the re-priced lead premium
+    /// (panel rollover). Emitted same day as the incumbent lead's `LeadQuoteIssued`; the
+    /// follower re-signs with `FollowerQuoteIssued` or answers `RenewalDeclined`.
+    RenewalOffered {
+        submission_id: SubmissionId,
+        insured_id: InsuredId,
+        insurer_id: InsurerId,
+        risk: Box<Risk>,
+        /// The renewal premium set by the expiring lead.
+        lead_premium: u64,
+        /// The follower's share of the expiring panel; the most it re-signs.
+        expiring_line: f64,
+    },
+    /// An expiring follower declined to renew its line. Its share is marketed to new capacity.
+    RenewalDeclined {
+        submission_id: SubmissionId,
+        insured_id: InsuredId,
+        insurer_id: InsurerId,
+        reason: DeclineReason,
+    },
     /// Broker presents the assembled panel quote to the insured.
     QuotePresented {
         submission_id: SubmissionId,
@@ -119,9 +621,14 @@ pub enum Event {
         panel: Vec<(InsurerId, f64)>,
         premium: u64,
     },
-    /// Insured rejects the quote (rate on line exceeds max_rate_on_line).
-    /// The simulation schedules a renewal CoverageRequested at the same annual offset.
-    QuoteRejected { submission_id: SubmissionId, insured_id: InsuredId },
+    /// Insured rejects the quote (rate on line exceeds max_rate_on_line, or elastic demand
+    /// declines it). The simulation schedules a renewal CoverageRequested at the same annual offset.
+    QuoteRejected {
+        submission_id: SubmissionId,
+        insured_id: InsuredId,
+        #[serde(default)]
+        reason: RejectionReason,
+    },
     /// All insurers declined this submission (capacity constraint or insolvency).
     /// The insured is uninsured for the year; the simulation schedules a retry at next renewal.
     SubmissionDropped { submission_id: SubmissionId, insured_id: InsuredId },
@@ -135,9 +642,49 @@ pub enum Event {
         premium: u64,
         sum_insured: u64, // makes the event self-contained for exposure analysis
     },
+    /// Insured loyalty: the policy bound alongside it renews cover the insured already held.
+    /// `incumbent` is true when it is led by the expiring policy's lead, false when the insured
+    /// moved. Placements by uncovered insureds are new business and carry no `PolicyRenewed`.
+    PolicyRenewed { policy_id: PolicyId, insured_id: InsuredId, incumbent: bool },
+    /// Quote comparison only: what every writing insurer would have charged for the risk just
+    /// bound, at its own price on the binding day. `premium` is the bound (winning) premium and
+    /// `median_quote` the median of `quotes`, so `premium / median_quote − 1` is the policy's
+    /// quote spread — deeply negative spreads are where the winner's curse shows.
+    QuoteComparisonRecorded { policy_id: PolicyId, premium: u64, median_quote: u64, quotes: Vec<(InsurerId, u64)> },
     PolicyExpired {
         policy_id: PolicyId,
     },
+    /// The insured failed to pay premium instalment `instalment` (0-based; instalment 0 is the
+    /// deposit paid at binding, so defaults are on instalment ≥ 1).
+    PremiumDefaulted { policy_id: PolicyId, insured_id: InsuredId, instalment: u32 },
+    /// Payment terms only: cash for instalment `instalment` of a policy's premium (policy level,
+    /// before expenses) reached the panel, `settlement_delay_days` after it fell due. Each panel
+    /// member credits its line share to capital now. Multi-year anniversary premium arrives as
+    /// a single instalment 0.
+    PremiumReceived { policy_id: PolicyId, insured_id: InsuredId, instalment: u32, amount: u64 },
+    /// Brokerage only: the placing broker's commission on premium written at a binding or a
+    /// multi-year anniversary, same day. Already deducted from the panel's net premium.
+    BrokerageEarned { broker_id: BrokerId, policy_id: PolicyId, premium: u64, amount: u64 },
+    /// Mitigation only: an insured facing a premium jump invested `spend` (cents) in risk
+    /// management, same day as its `QuoteAccepted`. From the next YearEnd its attritional
+    /// frequency multiplier and cat damage factor are the values given here.
+    MitigationInvested { insured_id: InsuredId, spend: u64, attritional_multiplier: f64, cat_damage_factor: f64 },
+    /// A policy is cancelled before expiry. Policy-level gross amounts, split across the
+    /// panel by line share: `premium_reversed` is uncollected premium that was never earned,
+    /// `bad_debt` is earned premium that will never be collected.
+    PolicyCancelled {
+        policy_id: PolicyId,
+        insured_id: InsuredId,
+        reason: CancellationReason,
+        basis: CancellationBasis,
+        premium_reversed: u64,
+        bad_debt: u64,
+    },
+    /// Multi-year terms: the renewal window of an in-force policy (the day an annual policy's
+    /// renewal request would fire), when the next annual instalment falls due. `premium` is the
+    /// instalment collected — the locked annual premium, or 0 when the contract ends here
+    /// (already cancelled, or cancelled now); back-filled at dispatch.
+    PolicyAnniversary { policy_id: PolicyId, insured_id: InsuredId, premium: u64, sum_insured: u64 },
     #[allow(clippy::enum_variant_names)] // LossEvent is a domain term, not a naming error
     LossEvent {
         event_id: u64,
@@ -145,17 +692,31 @@ pub enum Event {
         /// Geographic territory struck by this event. Drawn uniformly from
         /// `CatConfig.territories` at scheduling time; `on_loss_event` filters
         /// `insured_registry` to only emit `AssetDamage` for matching insureds.
-        territory: String,
+        territory: Territory,
         /// Damage fraction for this event, sampled at scheduling time from the
         /// event class's Pareto distribution. Shared across all insureds in the
         /// struck territory — the intensity field of the physical occurrence.
         /// Invariant: in (0.0, 1.0] (checked by `verify_mechanics` Inv 7).
         damage_fraction: f64,
+        /// Other territories struck by the same occurrence, with their damage fractions (the
+        /// event class's `footprint`). Empty for a single-territory event.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        footprint: Vec<(Territory, f64)>,
     },
+    /// Industry loss index print for one catastrophe occurrence (PCS-style), fired on the
+    /// `LossEvent` day after every `AssetDamage` it caused has been routed. `total_gul` sums
+    /// the ground-up loss across struck insureds, insured or not; `insured_loss` sums the
+    /// policy-level losses after deductible and limit (the ultimate claims across panels,
+    /// back-filled at dispatch). Trigger data for industry-loss-indexed contracts.
+    IndustryLossEstimate { event_id: u64, total_gul: u64, insured_loss: u64 },
     /// A peril has damaged an insured's assets. Fired for every registered insured
     /// regardless of whether they hold an active policy. The market handler
     /// `on_asset_damage` routes to `ClaimSettled` only for covered insureds.
     AssetDamage { insured_id: InsuredId, peril: Peril, ground_up_loss: u64 },
+    /// Annual aggregate deductible only: `amount` of a covered loss was retained by the insured
+    /// against the policy's aggregate for the year, leaving `remaining` to erode. Claims flow
+    /// only once `remaining` reaches zero.
+    DeductibleEroded { policy_id: PolicyId, insured_id: InsuredId, peril: Peril, amount: u64, remaining: u64 },
     ClaimSettled {
         policy_id: PolicyId,
         insurer_id: InsurerId,
@@ -164,9 +725,72 @@ pub enum Event {
         /// Insurer's capital remaining after this claim is paid (floored at zero).
         remaining_capital: u64,
     },
+    /// Claims development only: an insurer's share of a covered loss is reported (replaces
+    /// `ClaimSettled`). `amount` is the ultimate claim, paid later via `ClaimPaid` instalments.
+    ClaimReported {
+        claim_id: ClaimId,
+        policy_id: PolicyId,
+        insurer_id: InsurerId,
+        peril: Peril,
+        amount: u64,
+    },
+    /// The insurer has set its initial case reserve for a reported claim (same day).
+    /// Reserves are charged to capital (incurred basis) when established.
+    ReserveEstablished { claim_id: ClaimId, insurer_id: InsurerId, reserve: u64 },
+    /// One instalment of a developing claim. `cumulative_paid_fraction` is the share of the
+    /// ultimate paid to date including this instalment (1.0 on the final payment).
+    ClaimPaid {
+        claim_id: ClaimId,
+        policy_id: PolicyId,
+        insurer_id: InsurerId,
+        peril: Peril,
+        amount: u64,
+        cumulative_paid_fraction: f64,
+    },
+    /// The year's market-wide investment return, drawn at YearStart and applied on the day
+    /// before YearEnd. `crash` marks a crash-year draw.
+    InvestmentReturnDrawn { year: Year, rate: f64, crash: bool },
+    /// Investment return credited to (or, if negative, charged against) one insurer's capital.
+    InvestmentIncome { insurer_id: InsurerId, amount: i64 },
     /// Emitted the first time a claim drives an insurer's capital to zero.
     /// From this point on the insurer declines all new quote requests.
     InsurerInsolvent { insurer_id: InsurerId },
+    /// A distressed but viable insurer raised fresh capital at YearEnd. `amount` is the gross
+    /// raise and `cost` the part lost to the issuance discount; capital grows by the difference.
+    CapitalRaised { insurer_id: InsurerId, amount: u64, cost: u64, remaining_capital: u64 },
+    /// An insurer sponsored a collateralised cat bond at YearEnd (see [`CatBondIssue`]).
+    CatBondIssued(Box<CatBondIssue>),
+    /// A cat bond's trigger was met by occurrence `event_id`. Scheduled ahead of the
+    /// occurrence's `AssetDamage` so the collateral reaches the sponsor before its claims.
+    /// `remaining_capital` is back-filled after the payout is credited.
+    CatBondTriggered { bond_id: u64, insurer_id: InsurerId, event_id: u64, payout: u64, remaining_capital: u64 },
+    /// A cat bond reached the end of its term; the unexhausted principal is returned to
+    /// investors from the collateral account (back-filled at dispatch).
+    CatBondMatured { bond_id: u64, insurer_id: InsurerId, principal_returned: u64 },
+    /// An insurer bought its annual cat XoL at YearStart: `limit` excess of `attachment` per
+    /// occurrence at `rate_on_line`, paying `premium` now. `remaining_capital` is back-filled
+    /// after the premium is paid.
+    ReinsurancePurchased {
+        treaty_id: u64,
+        insurer_id: InsurerId,
+        attachment: u64,
+        limit: u64,
+        rate_on_line: f64,
+        premium: u64,
+        remaining_capital: u64,
+    },
+    /// A treaty recovered `amount` of the cedant's loss from occurrence `event_id`. Scheduled
+    /// ahead of the occurrence's `AssetDamage`, like a cat bond payout. `remaining_capital`
+    /// is back-filled after the recovery is credited.
+    ReinsuranceRecovered { treaty_id: u64, insurer_id: InsurerId, event_id: u64, amount: u64, remaining_capital: u64 },
+    /// An insurer has voluntarily entered run-off at YearEnd: it declines all new quote
+    /// requests from now on but continues settling claims on its existing book.
+    InsurerExited { insurer_id: InsurerId, reason: ExitReason, capital: u64 },
+    /// A run-off insurer has resumed writing new business after the market hardened.
+    InsurerReEntered { insurer_id: InsurerId, capital: u64 },
+    /// An exiting insurer's in-force lines and renewal rights passed to a writing insurer (see
+    /// [`PortfolioTransfer`]).
+    PortfolioTransferred(Box<PortfolioTransfer>),
     /// A new insurer has entered the market, spawned by the coordinator after observing
     /// sustained market profitability. Logged at the YearEnd day that triggered entry.
     /// Also emitted at Day(0) for the initial insurers so the event stream is self-contained.
@@ -176,7 +800,26 @@ pub enum Event {
         cr_sensitivity: f64,
         capacity_sensitivity: f64,
         market_weight_floor: f64,
+        /// The insurer's view of the cat ELF, as in `InsurerCreated`. 0.0 in logs that predate
+        /// the field.
+        #[serde(default)]
+        cat_elf: f64,
+        /// Label of the `EntrantProfile` drawn for this entrant. Absent for the initial
+        /// insurers and when no profiles are configured.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        profile: Option<String>,
     },
+    /// Expense split only: an insurer's expenses for the year, at YearEnd. `acquisition` was
+    /// deducted from premium as it was written; `overhead` is the fixed charge taken from capital
+    /// now (less only if capital ran out first).
+    ExpensesPaid { insurer_id: InsurerId, acquisition: u64, overhead: u64 },
+    /// Capital ledger only: one movement of an insurer's capital, `amount` cents (negative =
+    /// outflow) for `reason`. Logged directly after the event whose dispatch moved the capital,
+    /// never queued. `queue_seq` is the seq the next queued event would have taken when the
+    /// capital moved, so a capital snapshot queued with `seq >= queue_seq` was taken after it.
+    /// Initial capital (`InsurerCreated`, `InsurerEntered`) plus the flows so far is the
+    /// insurer's capital: Inv 29 checks it against every capital the log reports.
+    CapitalFlow { insurer_id: InsurerId, reason: CapitalFlowReason, amount: i64, queue_seq: u64 },
     /// Annual profit distribution to Names (Lloyd's 3-year account practice).
     /// Emitted at YearEnd only when the insurer is profitable and `payout_ratio > 0`.
     /// Zero-amount distributions are never logged (Inv 20).
@@ -200,32 +843,207 @@ pub enum Event {
         ytd_premium: u64,
         /// Claims paid this year by this insurer (cents).
         ytd_claims: u64,
+        /// Unearned premium reserve held within `capital` (cents): the insurer's share of
+        /// in-force premium not yet earned. Zero in logs written before it was reported.
+        #[serde(default)]
+        unearned_premium: u64,
     },
+    /// Solvency tiering only: an insurer's solvency position at YearEnd, after distributions.
+    /// `solvency_ratio = capital / scr`; not emitted while the SCR is zero.
+    SolvencyRatioReported { insurer_id: InsurerId, capital: u64, scr: u64, solvency_ratio: f64 },
+    /// The regulator moved an insurer to a new rung of the intervention ladder at YearEnd.
+    /// `LicenceRevoked` is followed by `InsurerInsolvent` on the same day.
+    RegulatoryIntervention { insurer_id: InsurerId, action: RegulatoryAction },
 }
 
 // Manual `Eq` impls: `f64` doesn't implement `Eq` due to NaN, but damage_fraction
 // is always a valid finite float in this domain, so PartialEq is an equivalence relation.
-// `SimEvent: Eq` is required by `Ord` (used in the BinaryHeap priority queue).
 impl Eq for Event {}
 
+/// Same-day dispatch order. Events on the same day are dispatched class by class in this
+/// order, so day-boundary semantics hold by construction: the clock ticks first, an expiring
+/// policy leaves the book before its renewal binds, losses hit the book as it stands after
+/// binding, and year-end accounting sees every other event of its day.
+///
+/// An event scheduled for the current day by a handler can only run after the event that
+/// scheduled it, whatever its class; `may_follow_same_day` says which late arrivals
+/// `Simulation::schedule` accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PriorityClass {
+    /// Simulation and year start.
+    Clock,
+    /// Policies leaving the book.
+    Expiry,
+    /// Policies joining the book.
+    Binding,
+    /// Submissions, quotes and the insured's decision; premium instalments.
+    Placement,
+    /// Occurrences, ground-up damage and claim movements.
+    Loss,
+    /// Capital movements outside underwriting: investment, entry, insolvency, distributions.
+    Capital,
+    /// Year-end accounting and publications.
+    YearClose,
+}
+
+impl PriorityClass {
+    /// Whether a handler of class `current` may schedule an event of this class for its own
+    /// day. Consequences normally run after their cause, but two phases are barriers: nothing
+    /// joins the clock phase once it has passed, and once year-end accounting has begun only
+    /// capital movements and further year-close events may follow it — a claim or a bind
+    /// arriving after `YearEnd` would be missing from that year's figures.
+    pub fn may_follow_same_day(self, current: PriorityClass) -> bool {
+        use PriorityClass::*;
+        match self {
+            Clock => current == Clock,
+            Expiry | Binding | Placement | Loss => current < YearClose,
+            Capital | YearClose => true,
+        }
+    }
+}
+
+impl Event {
+    /// The single table of same-day priorities. Exhaustive on purpose: a new event type
+    /// must be placed explicitly.
+    pub fn priority_class(&self) -> PriorityClass {
+        use PriorityClass::*;
+        match self {
+            Event::SimulationStart { .. }
+            | Event::RunMetadata(_)
+            | Event::InsurerCreated { .. }
+            | Event::InsuredCreated { .. }
+            | Event::YearStart { .. }
+            | Event::SimHealth { .. }
+            | Event::ExposureSnapshot { .. }
+            | Event::ExposureGrowth { .. }
+            | Event::InterestRatePublished { .. }
+            | Event::UnderwritingPlanSet { .. }
+            | Event::CapitalFlow { .. } => Clock,
+            Event::PolicyExpired { .. } | Event::PolicyCancelled { .. } => Expiry,
+            Event::PolicyBound { .. }
+            | Event::PolicyRenewed { .. }
+            | Event::QuoteComparisonRecorded { .. }
+            | Event::PolicyAnniversary { .. } => Binding,
+            Event::CoverageRequested { .. }
+            | Event::SubmissionRouted { .. }
+            | Event::LeadQuoteRequested { .. }
+            | Event::LeadQuoteBatchClosed { .. }
+            | Event::LeadQuoteDeclined { .. }
+            | Event::LeadQuoteIssued { .. }
+            | Event::LeadQuotePartial { .. }
+            | Event::FollowerQuoteRequested { .. }
+            | Event::FollowerQuoteIssued { .. }
+            | Event::FollowerQuoteDeclined { .. }
+            | Event::RenewalOffered { .. }
+            | Event::RenewalDeclined { .. }
+            | Event::QuotePresented { .. }
+            | Event::QuoteAccepted { .. }
+            | Event::QuoteRejected { .. }
+            | Event::SubmissionDropped { .. }
+            | Event::PremiumDefaulted { .. }
+            | Event::PremiumReceived { .. }
+            | Event::BrokerageEarned { .. }
+            | Event::MitigationInvested { .. } => Placement,
+            Event::LossEvent { .. }
+            | Event::AssetDamage { .. }
+            | Event::IndustryLossEstimate { .. }
+            | Event::CatBondTriggered { .. }
+            | Event::ReinsuranceRecovered { .. }
+            | Event::DeductibleEroded { .. }
+            | Event::ClaimSettled { .. }
+            | Event::ClaimReported { .. }
+            | Event::ReserveEstablished { .. }
+            | Event::ClaimPaid { .. } => Loss,
+            Event::InvestmentReturnDrawn { .. }
+            | Event::InvestmentIncome { .. }
+            | Event::InsurerInsolvent { .. }
+            | Event::CapitalRaised { .. }
+            | Event::CatBondIssued { .. }
+            | Event::CatBondMatured { .. }
+            | Event::ReinsurancePurchased { .. }
+            | Event::InsurerExited { .. }
+            | Event::InsurerReEntered { .. }
+            | Event::RegulatoryIntervention { .. }
+            | Event::PortfolioTransferred { .. }
+            | Event::InsurerEntered { .. }
+            | Event::ExpensesPaid { .. }
+            | Event::CapitalDistributed { .. } => Capital,
+            Event::YearEnd { .. }
+            | Event::QuarterEnd { .. }
+            | Event::MarketIntelligence { .. }
+            | Event::RateIndexPublished { .. }
+            | Event::PmlReported { .. }
+            | Event::YearEndCapital { .. }
+            | Event::SolvencyRatioReported { .. } => {
+                YearClose
+            }
+        }
+    }
+
+    /// The insurer whose capital this event records as it stood when the event was queued:
+    /// year-end reports, distributions, raises, exits and re-entries. Other capital reports
+    /// (`ClaimSettled`, `CatBondTriggered`, ...) are stamped by their own dispatch.
+    pub fn capital_snapshot(&self) -> Option<InsurerId> {
+        match self {
+            Event::YearEndCapital { insurer_id, .. }
+            | Event::SolvencyRatioReported { insurer_id, .. }
+            | Event::CapitalDistributed { insurer_id, .. }
+            | Event::CapitalRaised { insurer_id, .. }
+            | Event::InsurerExited { insurer_id, .. }
+            | Event::InsurerReEntered { insurer_id, .. } => Some(*insurer_id),
+            _ => None,
+        }
+    }
+}
+
 /// A dispatched event with its simulation day. Position in `Simulation.log` is its implicit sequence number.
 ///
-/// Serves as both the immutable log entry and the priority queue entry. Ordering is by `day` only.
+/// The immutable log entry. While pending it sits in the queue as a [`QueuedEvent`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimEvent {
     pub day: Day,
+    /// Scheduling sequence number (`OrderingKey::seq`) of a dispatched event: same-day events
+    /// of one priority class were dispatched in increasing `seq`. `None` for events logged
+    /// directly rather than queued (`InsurerCreated`, `SimHealth`, ...) and in logs that
+    /// predate the field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
     pub event: Event,
 }
 
 impl Eq for SimEvent {}
 
-impl Ord for SimEvent {
+/// Queue ordering key: day, then `PriorityClass`, then scheduling sequence (FIFO within a
+/// class), so dispatch order is total and independent of heap internals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct OrderingKey {
+    pub day: Day,
+    pub class: PriorityClass,
+    pub seq: u64,
+}
+
+/// A pending event in the simulation queue, ordered by its `OrderingKey` alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedEvent {
+    pub key: OrderingKey,
+    pub ev: SimEvent,
+}
+
+impl QueuedEvent {
+    /// Queue `event` on `day`; `seq` must increase with every call.
+    pub fn new(day: Day, event: Event, seq: u64) -> Self {
+        let key = OrderingKey { day, class: event.priority_class(), seq };
+        QueuedEvent { key, ev: SimEvent { day, seq: Some(seq), event } }
+    }
+}
+
+impl Ord for QueuedEvent {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.day.cmp(&other.day)
+        self.key.cmp(&other.key)
     }
 }
 
-impl PartialOrd for SimEvent {
+impl PartialOrd for QueuedEvent {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -236,7 +1054,7 @@ impl PartialOrd for SimEvent {
 ///
 /// Mutation is restricted to `push`.  Use `from_history` to seed the log
 /// from a pre-built slice (testing and checkpointing only).
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EventLog(Vec<SimEvent>);
 
 impl EventLog {
@@ -266,6 +1084,12 @@ impl EventLog {
         self.0.iter()
     }
 
+    /// Remove and yield every entry. Used by aggregate-only simulations to hand dispatched
+    /// events to an `analysis::Accumulator` instead of keeping them.
+    pub fn drain(&mut self) -> std::vec::Drain<'_, SimEvent> {
+        self.0.drain(..)
+    }
+
     /// Mutable reference to the most recently pushed entry.
     /// Used by dispatch handlers to back-fill computed fields (e.g. remaining_capital)
     /// into an event immediately after it is processed.
@@ -294,14 +1118,16 @@ mod tests {
     use std::io::{BufWriter, Write};
 
     use super::*;
-    use crate::types::{InsurerId, SubmissionId};
+    use crate::types::{InsurerId, SubmissionId, TerritoryTable};
 
     #[test]
     fn peril_covered_membership() {
         let risk = Risk {
             sum_insured: 1_000_000,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: 1_000_000,
         };
         assert!(risk.perils_covered.contains(&Peril::WindstormAtlantic));
         assert!(!risk.perils_covered.contains(&Peril::Attritional));
@@ -311,6 +1137,7 @@ mod tests {
     fn sim_event_serializes_day_and_event_fields() {
         let ev = SimEvent {
             day: Day(42),
+            seq: None,
             event: Event::YearEnd { year: Year(3) },
         };
         let json = serde_json::to_string(&ev).unwrap();
@@ -321,16 +1148,35 @@ mod tests {
     fn simulation_start_json_shape() {
         let ev = SimEvent {
             day: Day(0),
-            event: Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1 },
+            seq: None,
+            event: Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1, schema_version: SCHEMA_VERSION },
         };
         let json = serde_json::to_string(&ev).unwrap();
-        assert_eq!(json, r#"{"day":0,"event":{"SimulationStart":{"year_start":1,"warmup_years":0,"analysis_years":1}}}"#);
+        assert_eq!(json, r#"{"day":0,"event":{"SimulationStart":{"year_start":1,"warmup_years":0,"analysis_years":1,"schema_version":4}}}"#);
+    }
+
+    #[test]
+    fn logs_without_newer_fields_take_their_defaults() {
+        let json = r#"{"day":0,"event":{"SimulationStart":{"year_start":1,"warmup_years":5,"analysis_years":20}}}"#;
+        let ev: SimEvent = serde_json::from_str(json).unwrap();
+        assert!(matches!(ev.event, Event::SimulationStart { schema_version: 1, .. }));
+
+        let json = r#"{"day":3,"event":{"CoverageRequested":{"insured_id":1,"risk":{"sum_insured":500,"territory":0,"perils_covered":["Attritional"]}}}}"#;
+        let Event::CoverageRequested { risk, .. } = serde_json::from_str::<SimEvent>(json).unwrap().event else {
+            panic!("expected CoverageRequested");
+        };
+        assert_eq!((risk.attachment, risk.limit), (0, 500), "full coverage");
+
+        let json = r#"{"day":4,"event":{"QuoteRejected":{"submission_id":1,"insured_id":1}}}"#;
+        let ev: SimEvent = serde_json::from_str(json).unwrap();
+        assert!(matches!(ev.event, Event::QuoteRejected { reason: RejectionReason::ReservationPriceExceeded, .. }));
     }
 
     #[test]
     fn policy_bound_serializes() {
         let ev = SimEvent {
             day: Day(10),
+            seq: None,
             event: Event::PolicyBound {
                 policy_id: PolicyId(0),
                 submission_id: SubmissionId(1),
@@ -353,15 +1199,18 @@ mod tests {
         let events = vec![
             SimEvent {
                 day: Day(0),
-                event: Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1 },
+                seq: None,
+                event: Event::SimulationStart { year_start: Year(1), warmup_years: 0, analysis_years: 1, schema_version: SCHEMA_VERSION },
             },
             SimEvent {
                 day: Day(359),
+                seq: None,
                 event: Event::YearEnd { year: Year(1) },
             },
             SimEvent {
                 day: Day(180),
-                event: Event::LossEvent { event_id: 1, peril: Peril::WindstormAtlantic, territory: "US-SE".to_string(), damage_fraction: 0.10 },
+                seq: None,
+                event: Event::LossEvent { event_id: 1, peril: Peril::WindstormAtlantic, territory: Territory(0), damage_fraction: 0.10, footprint: vec![] },
             },
         ];
 
@@ -388,6 +1237,7 @@ mod tests {
     fn decline_reason_rate_below_tp_serializes() {
         let ev = SimEvent {
             day: Day(1),
+            seq: None,
             event: Event::LeadQuoteDeclined {
                 submission_id: SubmissionId(0),
                 insured_id: InsuredId(1),
@@ -405,15 +1255,18 @@ mod tests {
     fn follower_quote_requested_serializes() {
         let ev = SimEvent {
             day: Day(1),
+            seq: None,
             event: Event::FollowerQuoteRequested {
                 submission_id: SubmissionId(0),
                 insured_id: InsuredId(1),
                 insurer_id: InsurerId(2),
-                risk: Risk {
+                risk: Box::new(Risk {
                     sum_insured: 1_000_000,
-                    territory: "US-SE".to_string(),
+                    territory: Territory(0),
                     perils_covered: vec![Peril::WindstormAtlantic],
-                },
+                    attachment: 0,
+                    limit: 1_000_000,
+                }),
                 lead_premium: 50_000,
                 lead_atp: 48_000,
             },
@@ -428,6 +1281,7 @@ mod tests {
     fn follower_quote_issued_serializes() {
         let ev = SimEvent {
             day: Day(1),
+            seq: None,
             event: Event::FollowerQuoteIssued {
                 submission_id: SubmissionId(0),
                 insured_id: InsuredId(1),
@@ -445,6 +1299,7 @@ mod tests {
     fn follower_quote_declined_serializes() {
         let ev = SimEvent {
             day: Day(1),
+            seq: None,
             event: Event::FollowerQuoteDeclined {
                 submission_id: SubmissionId(0),
                 insured_id: InsuredId(1),
@@ -458,22 +1313,533 @@ mod tests {
         assert!(json.contains("FollowerQuoteDeclined"));
     }
 
+    #[test]
+    fn submission_routed_serializes() {
+        let ev = SimEvent {
+            day: Day(0),
+            seq: None,
+            event: Event::SubmissionRouted {
+                submission_id: SubmissionId(3),
+                insured_id: InsuredId(1),
+                broker_id: BrokerId(2),
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""broker_id":2"#));
+    }
+
+    #[test]
+    fn risk_layer_terms() {
+        let full = Risk {
+            sum_insured: 1_000_000,
+            territory: Territory(0),
+            perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: 1_000_000,
+        };
+        assert_eq!(full.layer_factor(), 1.0);
+        assert_eq!(full.max_claim(), 1_000_000);
+
+        let layer = Risk { attachment: 10_000, limit: 500_000, ..full };
+        assert_eq!(layer.claim_for(5_000), 0);
+        assert_eq!(layer.claim_for(30_000), 20_000);
+        assert_eq!(layer.claim_for(900_000), 500_000);
+        assert_eq!(layer.max_claim(), 500_000);
+        let f = layer.layer_factor();
+        assert!(f > 0.0 && f < 1.0);
+        // A higher deductible always buys a smaller share of expected loss.
+        assert!(Risk { attachment: 50_000, ..layer }.layer_factor() < f);
+    }
+
+    #[test]
+    fn quote_rejected_serializes_reason() {
+        let ev = SimEvent {
+            day: Day(4),
+            seq: None,
+            event: Event::QuoteRejected {
+                submission_id: SubmissionId(1),
+                insured_id: InsuredId(2),
+                reason: RejectionReason::PriceTooHigh,
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        assert!(json.contains("PriceTooHigh"));
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+    }
+
+    #[test]
+    fn market_intelligence_serializes() {
+        let ev = SimEvent {
+            day: Day(359),
+            seq: None,
+            event: Event::MarketIntelligence {
+                broker_id: BrokerId(1),
+                year: Year(1),
+                avg_quoted_rate: 0.07,
+                quote_responses: 12,
+                decline_rate: 0.25,
+                territories: vec![TerritoryIntelligence {
+                    territory: Territory(0),
+                    quotes: 9,
+                    avg_rate: 0.07,
+                    rate_dispersion: 0.01,
+                }],
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+    }
+
+    #[test]
+    fn investment_events_serialize() {
+        for event in [
+            Event::InvestmentReturnDrawn { year: Year(3), rate: -0.2, crash: true },
+            Event::InvestmentIncome { insurer_id: InsurerId(1), amount: -12_345 },
+        ] {
+            let ev = SimEvent { day: Day(1_078), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn sim_health_serializes() {
+        let ev = SimEvent {
+            day: Day(30),
+            seq: None,
+            event: Event::SimHealth {
+                queue_depth: 812,
+                events_dispatched: 10_450,
+                active_policies: 96,
+                pending_submissions: 3,
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+    }
+
+    #[test]
+    fn capital_raised_serializes() {
+        let ev = SimEvent {
+            day: Day(1_079),
+            seq: None,
+            event: Event::CapitalRaised {
+                insurer_id: InsurerId(3),
+                amount: 20_000_000,
+                cost: 2_000_000,
+                remaining_capital: 78_000_000,
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+    }
+
+    #[test]
+    fn lead_quote_partial_serializes() {
+        let ev = SimEvent {
+            day: Day(12),
+            seq: None,
+            event: Event::LeadQuotePartial {
+                submission_id: SubmissionId(4),
+                insured_id: InsuredId(2),
+                insurer_id: InsurerId(1),
+                atp: 80_000,
+                premium: 100_000,
+                cat_exposure_at_quote: 9_000_000,
+                line_size: 0.15,
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+    }
+
+    #[test]
+    fn initial_condition_events_serialize() {
+        for event in [
+            Event::InsurerCreated {
+                insurer_id: InsurerId(3),
+                initial_capital: 100_000_000_000,
+                expense_ratio: 0.344,
+                target_loss_ratio: 0.62,
+                profit_loading: 0.05,
+                cat_elf: 0.033,
+            },
+            Event::InsuredCreated { insured_id: InsuredId(9), territory: Territory(0), sum_insured: 5_000_000_000, owner: None },
+        ] {
+            let ev = SimEvent { day: Day(0), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn cat_bond_events_serialize() {
+        for event in [
+            Event::CatBondIssued(Box::new(CatBondIssue {
+                bond_id: 0,
+                insurer_id: InsurerId(1),
+                trigger: CatBondTrigger::Indemnity { attachment_capital_fraction: 0.2 },
+                principal: 25_000_000,
+                attachment: 20_000_000,
+                premium: 3_750_000,
+                maturity_year: Year(5),
+                remaining_capital: 96_250_000,
+            })),
+            Event::CatBondTriggered {
+                bond_id: 0,
+                insurer_id: InsurerId(1),
+                event_id: 7,
+                payout: 12_000_000,
+                remaining_capital: 108_250_000,
+            },
+            Event::CatBondMatured { bond_id: 0, insurer_id: InsurerId(1), principal_returned: 13_000_000 },
+        ] {
+            let ev = SimEvent { day: Day(1_439), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn reinsurance_events_serialize() {
+        for event in [
+            Event::ReinsurancePurchased {
+                treaty_id: 0,
+                insurer_id: InsurerId(1),
+                attachment: 10_000_000,
+                limit: 50_000_000,
+                rate_on_line: 0.12,
+                premium: 6_000_000,
+                remaining_capital: 94_000_000,
+            },
+            Event::ReinsuranceRecovered {
+                treaty_id: 0,
+                insurer_id: InsurerId(1),
+                event_id: 7,
+                amount: 30_000_000,
+                remaining_capital: 124_000_000,
+            },
+        ] {
+            let ev = SimEvent { day: Day(720), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn runoff_events_serialize() {
+        for event in [
+            Event::InsurerExited {
+                insurer_id: InsurerId(2),
+                reason: ExitReason::AdverseExperience,
+                capital: 40_000_000,
+            },
+            Event::InsurerReEntered { insurer_id: InsurerId(2), capital: 41_000_000 },
+            Event::PortfolioTransferred(Box::new(PortfolioTransfer {
+                seller_id: InsurerId(2),
+                buyer_id: InsurerId(1),
+                policies: 12,
+                unearned_premium: 3_500_000,
+                expected_losses: 2_500_000,
+                renewal_premium: 10_000_000,
+                franchise_value: 4_000_000,
+                retained_reserves: 800_000,
+                price: 1_500_000,
+            })),
+        ] {
+            let ev = SimEvent { day: Day(1_079), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn premium_default_events_serialize() {
+        for event in [
+            Event::PremiumDefaulted { policy_id: PolicyId(4), insured_id: InsuredId(2), instalment: 3 },
+            Event::PolicyCancelled {
+                policy_id: PolicyId(4),
+                insured_id: InsuredId(2),
+                reason: CancellationReason::PremiumDefault,
+                basis: CancellationBasis::ProRata,
+                premium_reversed: 2_500,
+                bad_debt: 300,
+            },
+        ] {
+            let ev = SimEvent { day: Day(200), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn multi_year_events_serialize() {
+        for event in [
+            Event::PolicyAnniversary { policy_id: PolicyId(4), insured_id: InsuredId(2), premium: 9_000, sum_insured: 100_000 },
+            Event::PolicyCancelled {
+                policy_id: PolicyId(4),
+                insured_id: InsuredId(2),
+                reason: CancellationReason::RateDrop,
+                basis: CancellationBasis::AbInitio,
+                premium_reversed: 0,
+                bad_debt: 0,
+            },
+        ] {
+            let ev = SimEvent { day: Day(717), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+        }
+    }
+
+    #[test]
+    fn industry_loss_estimate_serializes() {
+        let ev = SimEvent {
+            day: Day(250),
+            seq: None,
+            event: Event::IndustryLossEstimate { event_id: 7, total_gul: 5_000_000, insured_loss: 3_200_000 },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+    }
+
+    #[test]
+    fn regulatory_intervention_serializes() {
+        let ev = SimEvent {
+            day: Day(359),
+            seq: None,
+            event: Event::RegulatoryIntervention { insurer_id: InsurerId(1), action: RegulatoryAction::ForcedRunoff },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""action":"ForcedRunoff""#));
+    }
+
+    #[test]
+    fn solvency_ratio_reported_serializes() {
+        let ev = SimEvent {
+            day: Day(359),
+            seq: None,
+            event: Event::SolvencyRatioReported {
+                insurer_id: InsurerId(1),
+                capital: 3_000,
+                scr: 2_000,
+                solvency_ratio: 1.5,
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""solvency_ratio":1.5"#));
+    }
+
+    #[test]
+    fn premium_received_serializes() {
+        let ev = SimEvent {
+            day: Day(152),
+            seq: None,
+            event: Event::PremiumReceived { policy_id: PolicyId(3), insured_id: InsuredId(1), instalment: 1, amount: 25_000 },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""amount":25000"#));
+    }
+
+    #[test]
+    fn expenses_paid_serializes() {
+        let ev = SimEvent {
+            day: Day(359),
+            seq: None,
+            event: Event::ExpensesPaid { insurer_id: InsurerId(2), acquisition: 2_260, overhead: 550 },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""overhead":550"#));
+    }
+
+    #[test]
+    fn policy_renewed_serializes() {
+        let ev = SimEvent {
+            day: Day(363),
+            seq: None,
+            event: Event::PolicyRenewed { policy_id: PolicyId(9), insured_id: InsuredId(4), incumbent: false },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""incumbent":false"#));
+    }
+
+    #[test]
+    fn deductible_eroded_serializes() {
+        let ev = SimEvent {
+            day: Day(7),
+            seq: None,
+            event: Event::DeductibleEroded {
+                policy_id: PolicyId(2),
+                insured_id: InsuredId(3),
+                peril: Peril::Attritional,
+                amount: 1_000,
+                remaining: 4_000,
+            },
+        };
+        let json = serde_json::to_string(&ev).unwrap();
+        let back: SimEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(ev, back);
+        assert!(json.contains(r#""remaining":4000"#));
+    }
+
+    #[test]
+    fn claim_development_events_serialize() {
+        for event in [
+            Event::ClaimReported {
+                claim_id: ClaimId(1),
+                policy_id: PolicyId(2),
+                insurer_id: InsurerId(3),
+                peril: Peril::WindstormAtlantic,
+                amount: 1_000,
+            },
+            Event::ReserveEstablished { claim_id: ClaimId(1), insurer_id: InsurerId(3), reserve: 800 },
+            Event::ClaimPaid {
+                claim_id: ClaimId(1),
+                policy_id: PolicyId(2),
+                insurer_id: InsurerId(3),
+                peril: Peril::WindstormAtlantic,
+                amount: 400,
+                cumulative_paid_fraction: 0.4,
+            },
+        ] {
+            let ev = SimEvent { day: Day(7), seq: None, event };
+            let json = serde_json::to_string(&ev).unwrap();
+            let back: SimEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(ev, back);
+            assert!(json.contains(r#""claim_id":1"#));
+        }
+    }
+
     #[test]
     fn quote_chain_events_serialize() {
         let ev = SimEvent {
             day: Day(1),
+            seq: None,
             event: Event::LeadQuoteRequested {
                 submission_id: SubmissionId(0),
                 insured_id: InsuredId(1),
                 insurer_id: InsurerId(1),
-                risk: Risk {
+                risk: Box::new(Risk {
                     sum_insured: 1_000_000,
-                    territory: "US-SE".to_string(),
+                    territory: Territory(0),
                     perils_covered: vec![Peril::WindstormAtlantic],
-                },
+                    attachment: 0,
+                    limit: 1_000_000,
+                }),
             },
         };
         let value = serde_json::to_value(&ev).unwrap();
         assert!(value["event"]["LeadQuoteRequested"].is_object());
     }
+
+    #[test]
+    fn year_close_and_clock_are_same_day_barriers() {
+        use PriorityClass::*;
+        assert!(Loss.may_follow_same_day(Capital));
+        assert!(!Loss.may_follow_same_day(YearClose));
+        assert!(!Placement.may_follow_same_day(YearClose));
+        assert!(Capital.may_follow_same_day(YearClose), "year-end consequences follow YearEnd");
+        assert!(Expiry.may_follow_same_day(Placement), "a default cancels on the day it lands");
+        assert!(Clock.may_follow_same_day(Clock));
+        assert!(!Clock.may_follow_same_day(Expiry));
+    }
+
+    #[test]
+    fn same_day_queue_order_is_class_then_fifo() {
+        let day = Day(359);
+        let expired = Event::PolicyExpired { policy_id: PolicyId(1) };
+        let bound = Event::PolicyBound {
+            policy_id: PolicyId(2),
+            submission_id: SubmissionId(0),
+            insured_id: InsuredId(1),
+            panel: vec![],
+            premium: 1,
+            sum_insured: 1,
+        };
+        let year_end = Event::YearEnd { year: Year(1) };
+        let mut queue = [
+            QueuedEvent::new(day, year_end, 0),
+            QueuedEvent::new(day, bound.clone(), 1),
+            QueuedEvent::new(day, expired.clone(), 2),
+            QueuedEvent::new(day, bound, 3),
+            QueuedEvent::new(Day(358), expired, 4),
+        ];
+        queue.sort();
+        let order: Vec<(u64, PriorityClass, u64)> =
+            queue.iter().map(|q| (q.key.day.0, q.key.class, q.key.seq)).collect();
+        assert_eq!(
+            order,
+            vec![
+                (358, PriorityClass::Expiry, 4),
+                (359, PriorityClass::Expiry, 2),
+                (359, PriorityClass::Binding, 1),
+                (359, PriorityClass::Binding, 3),
+                (359, PriorityClass::YearClose, 0),
+            ],
+            "expiry before new binds before year-end; FIFO within a class"
+        );
+
+        // The scheduling seq travels with the event into the log.
+        let q = &queue[1];
+        assert_eq!(q.ev.seq, Some(2));
+        let json = serde_json::to_string(&q.ev).unwrap();
+        assert!(json.starts_with(r#"{"day":359,"seq":2,"event":"#), "{json}");
+        assert_eq!(serde_json::from_str::<SimEvent>(&json).unwrap(), q.ev);
+    }
+
+    #[test]
+    fn events_stay_compact() {
+        // Territories are u16 ids and wide, rare payloads boxed; growth here widens every event
+        // the log holds.
+        assert_eq!(std::mem::size_of::<Territory>(), 2);
+        assert!(std::mem::size_of::<Event>() <= 72, "Event is {} bytes", std::mem::size_of::<Event>());
+        assert!(std::mem::size_of::<SimEvent>() <= 96, "SimEvent is {} bytes", std::mem::size_of::<SimEvent>());
+    }
+
+    #[test]
+    fn territories_and_lines_log_as_ids_into_the_run_metadata_tables() {
+        let mut table = TerritoryTable::new(&["US-SE", "US-NE"]);
+        assert_eq!((table.intern("US-NE"), table.intern("EU")), (Some(Territory(1)), Some(Territory(2))));
+        assert_eq!((table.name(Territory(2)), table.get("Nowhere")), (Some("EU"), None));
+        assert_eq!(serde_json::to_string(&Territory(1)).unwrap(), "1");
+        assert!(serde_json::from_str::<Territory>("\"US-SE\"").is_err(), "events carry ids, not names");
+        let names: Vec<String> = (0..TerritoryTable::CAPACITY).map(|i| i.to_string()).collect();
+        let mut full = TerritoryTable::new(&names);
+        assert_eq!((full.intern("65535"), full.intern("one more")), (Some(Territory(u16::MAX)), None));
+
+        // The header's tables name the ids a run's events carry.
+        let config = SimulationConfig::canonical();
+        let header = RunMetadata::capture(&config);
+        assert_eq!(header.territories, config.catastrophe.territories);
+        assert_eq!(config.territory_table().get("US-SE"), Some(Territory(1)));
+        assert_eq!(header.lines[Line::Casualty.id() as usize], "Casualty");
+        assert_eq!(serde_json::to_string(&Line::Casualty).unwrap(), "1");
+        assert_eq!(serde_json::from_str::<Line>("1").unwrap(), Line::Casualty);
+        assert_eq!(serde_json::from_str::<Line>("\"Casualty\"").unwrap(), Line::Casualty, "configs may name lines");
+        assert!(serde_json::from_str::<Line>("2").is_err());
+    }
 }
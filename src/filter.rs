@@ -0,0 +1,218 @@
+//! Event-log filtering: keep the events of given kinds, in a day range, or concerning one
+//! insurer, insured or policy.
+//!
+//! [`EventFilter`] matches an in-memory [`SimEvent`] or one NDJSON line parsed as a
+//! `serde_json::Value`, so [`filter_file`] can stream a multi-GB log line by line without
+//! loading it, writing the matching lines unchanged. Id predicates match any field naming that
+//! kind of agent anywhere in the event's payload (see [`INSURER_FIELDS`] and friends), so an
+//! insurer filter keeps the policies it leads or follows on a panel and the transfers it is
+//! party to. The `RunMetadata` header always passes, so a filtered log keeps its provenance;
+//! a subset of a log will not, in general, pass `verify`.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde_json::Value;
+
+use crate::bundle::BundleError;
+use crate::error::Error;
+use crate::events::{Event, SimEvent};
+use crate::types::{Day, InsuredId, InsurerId, PolicyId, Year};
+
+/// Payload fields holding an insurer id. `panel` lists `(insurer_id, share)` pairs.
+pub const INSURER_FIELDS: &[&str] = &["insurer_id", "leader_id", "seller_id", "buyer_id", "panel"];
+/// Payload fields holding an insured id; `owner` is a multi-asset insured's owning id.
+pub const INSURED_FIELDS: &[&str] = &["insured_id", "owner"];
+pub const POLICY_FIELDS: &[&str] = &["policy_id"];
+
+/// Conjunction of predicates; unset ones match everything.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    /// Event kinds (`Event` variant names, e.g. `ClaimSettled`). Empty = every kind.
+    pub kinds: Vec<String>,
+    /// First and last day kept, inclusive.
+    pub days: Option<(Day, Day)>,
+    pub insurer: Option<InsurerId>,
+    pub insured: Option<InsuredId>,
+    pub policy: Option<PolicyId>,
+}
+
+impl EventFilter {
+    /// Restrict to years `first..=last` (1-based, warm-up included).
+    pub fn years(mut self, first: Year, last: Year) -> Self {
+        self.days = Some((Day::year_start(first), Day::year_end(last)));
+        self
+    }
+
+    pub fn matches(&self, ev: &SimEvent) -> bool {
+        self.matches_value(&serde_json::to_value(ev).expect("SimEvent serializes"))
+    }
+
+    /// Match one log line as JSON (`{"day": …, "event": {"Kind": {…}}}`).
+    pub fn matches_value(&self, ev: &Value) -> bool {
+        let (kind, payload) = match &ev["event"] {
+            Value::Object(m) if m.len() == 1 => m.iter().next().map(|(k, v)| (k.as_str(), v)).unwrap(),
+            Value::String(k) => (k.as_str(), &Value::Null),
+            _ => return false,
+        };
+        if kind == "RunMetadata" {
+            return true;
+        }
+        if !self.kinds.is_empty() && !self.kinds.iter().any(|k| k == kind) {
+            return false;
+        }
+        if let Some((first, last)) = self.days
+            && !ev["day"].as_u64().is_some_and(|d| (first.0..=last.0).contains(&d))
+        {
+            return false;
+        }
+        let ids = [
+            (INSURER_FIELDS, self.insurer.map(|i| i.0)),
+            (INSURED_FIELDS, self.insured.map(|i| i.0)),
+            (POLICY_FIELDS, self.policy.map(|p| p.0)),
+        ];
+        ids.iter().all(|&(fields, id)| id.is_none_or(|id| has_id(payload, fields, id)))
+    }
+}
+
+/// Whether one of `fields` holds `id` anywhere in `v`: as the value itself, or as the first
+/// element of a pair in a list of pairs.
+fn has_id(v: &Value, fields: &[&str], id: u64) -> bool {
+    let holds = |v: &Value| match v {
+        Value::Array(pairs) => pairs.iter().any(|p| p[0].as_u64() == Some(id)),
+        v => v.as_u64() == Some(id),
+    };
+    match v {
+        Value::Object(m) => m.iter().any(|(k, v)| (fields.contains(&k.as_str()) && holds(v)) || has_id(v, fields, id)),
+        Value::Array(a) => a.iter().any(|v| has_id(v, fields, id)),
+        _ => false,
+    }
+}
+
+/// The events of `log` that `filter` keeps, in log order.
+pub fn filter<'a>(log: &'a [SimEvent], filter: &'a EventFilter) -> impl Iterator<Item = &'a SimEvent> {
+    log.iter().filter(|ev| filter.matches(ev))
+}
+
+/// Stream the NDJSON log at `input` to `output`, keeping the lines `filter` matches. Returns
+/// (lines kept, lines read), blank lines excluded.
+pub fn filter_file(input: &Path, output: &Path, filter: &EventFilter) -> crate::Result<(usize, usize)> {
+    let reader = BufReader::new(File::open(input).map_err(Error::io(input))?);
+    let mut writer = BufWriter::new(File::create(output).map_err(Error::io(output))?);
+    let (mut kept, mut read) = (0, 0);
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(Error::io(input))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        read += 1;
+        let ev: Value = serde_json::from_str(&line).map_err(|e| BundleError::BadEvent {
+            path: input.to_path_buf(),
+            line: i + 1,
+            message: e.to_string(),
+        })?;
+        if filter.matches_value(&ev) {
+            writeln!(writer, "{line}").map_err(Error::io(output))?;
+            kept += 1;
+        }
+    }
+    writer.flush().map_err(Error::io(output))?;
+    Ok((kept, read))
+}
+
+/// Every `Event` variant name, as it appears in the log.
+pub fn event_kinds() -> &'static [&'static str] {
+    // Ask the derived `Deserialize` impl for its variant list rather than repeat it here.
+    struct Variants(&'static [&'static str]);
+    impl<'de> Deserializer<'de> for &mut Variants {
+        type Error = de::value::Error;
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("variant names only"))
+        }
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            variants: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0 = variants;
+            Err(de::Error::custom("variant names only"))
+        }
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct newtype_struct seq tuple tuple_struct map struct identifier
+            ignored_any
+        }
+    }
+    let mut variants = Variants(&[]);
+    let _ = Event::deserialize(&mut variants);
+    variants.0
+}
+
+/// A day or year range from the command line: `5`, `5..10` (exclusive end) or `5..=10`.
+/// Returns the inclusive bounds.
+pub fn parse_range(s: &str) -> Option<(u64, u64)> {
+    let (lo, hi) = match (s.split_once("..="), s.split_once("..")) {
+        (Some((lo, hi)), _) => (lo.parse().ok()?, hi.parse().ok()?),
+        (None, Some((lo, hi))) => (lo.parse().ok()?, hi.parse::<u64>().ok()?.checked_sub(1)?),
+        (None, None) => {
+            let n = s.parse().ok()?;
+            (n, n)
+        }
+    };
+    (lo <= hi).then_some((lo, hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SimulationConfig;
+    use crate::simulation::Simulation;
+
+    #[test]
+    fn filters_by_kind_year_and_insurer_in_memory_and_streamed() {
+        let config = SimulationConfig { seed: 3, years: 2, warmup_years: 0, ..SimulationConfig::canonical() };
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        let f = EventFilter {
+            kinds: vec!["ClaimSettled".to_string(), "PolicyBound".to_string()],
+            insurer: Some(InsurerId(3)),
+            ..EventFilter::default()
+        }
+        .years(Year(2), Year(2));
+        let kept: Vec<&SimEvent> = filter(&sim.log, &f).collect();
+        assert!(!kept.is_empty());
+        for ev in &kept {
+            assert_eq!(ev.day.year(), Year(2));
+            match &ev.event {
+                Event::ClaimSettled { insurer_id, .. } => assert_eq!(*insurer_id, InsurerId(3)),
+                Event::PolicyBound { panel, .. } => assert!(panel.iter().any(|&(id, _)| id == InsurerId(3))),
+                other => panic!("kept {other:?}"),
+            }
+        }
+
+        let dir = std::env::temp_dir();
+        let (input, output) = (dir.join("rins_filter_in.ndjson"), dir.join("rins_filter_out.ndjson"));
+        crate::batch::write_log(&sim, &input).unwrap();
+        let (n_kept, n_read) = filter_file(&input, &output, &f).unwrap();
+        assert_eq!(n_read, sim.log.len() + 1, "log plus its header");
+        assert_eq!(n_kept, kept.len() + 1, "the header always passes");
+        let streamed = crate::bundle::load_events(&output).unwrap();
+        assert!(matches!(streamed[0].event, Event::RunMetadata(_)));
+        assert_eq!(streamed[1..].iter().collect::<Vec<_>>(), kept);
+    }
+
+    #[test]
+    fn kinds_and_ranges_parse() {
+        let kinds = event_kinds();
+        assert!(kinds.contains(&"ClaimSettled") && kinds.contains(&"RunMetadata"));
+        assert_eq!(parse_range("5..10"), Some((5, 9)));
+        assert_eq!(parse_range("5..=10"), Some((5, 10)));
+        assert_eq!(parse_range("7"), Some((7, 7)));
+        assert_eq!(parse_range("5..5"), None);
+        assert_eq!(parse_range("x..3"), None);
+    }
+}
@@ -0,0 +1,231 @@
+//! Insurance-linked securities: collateralised cat bonds.
+//!
+//! A cat bond is capacity from outside the insurer panel. Investors fund the principal into a
+//! collateral account; the sponsoring insurer pays a coupon for the right to draw on it when a
+//! cat occurrence meets the bond's trigger:
+//!
+//! - **Parametric**: the occurrence's damage fraction reaches a threshold. The whole remaining
+//!   principal pays out regardless of the sponsor's own loss — fast settlement, basis risk.
+//! - **Indemnity**: the sponsor's insured loss from the occurrence exceeds an attachment. The
+//!   bond pays the excess up to the remaining principal.
+//!
+//! Payouts reduce the remaining principal; whatever is left at maturity goes back to investors.
+//! A bond whose sponsor has gone insolvent does not trigger: the collateral is never paid into
+//! the run-off estate and the untouched principal returns to investors at maturity.
+//! Issuance is decided at YearEnd and rises with the market AP/TP factor, so collateralised
+//! capacity floods in after the losses that harden rates.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::IlsConfig;
+use crate::events::{CatBondIssue, CatBondTrigger, Event};
+use crate::types::{Day, InsurerId, Year};
+
+/// One outstanding bond.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatBond {
+    pub bond_id: u64,
+    pub insurer_id: InsurerId,
+    pub trigger: CatBondTrigger,
+    /// Principal still in the collateral account (cents).
+    pub principal: u64,
+    /// Indemnity attachment (cents); 0 for parametric bonds.
+    pub attachment: u64,
+    pub maturity_year: Year,
+}
+
+/// Probability that an eligible insurer sponsors a bond this YearEnd.
+pub fn issuance_probability(config: &IlsConfig, ap_tp_factor: f64) -> f64 {
+    (config.issuance_probability * ap_tp_factor.max(0.0).powf(config.rate_elasticity)).clamp(0.0, 1.0)
+}
+
+/// What `bond` pays for an occurrence with `damage_fraction` that cost the sponsor
+/// `sponsor_loss` (cents) of insured loss.
+pub fn payout(bond: &CatBond, damage_fraction: f64, sponsor_loss: u64) -> u64 {
+    match bond.trigger {
+        CatBondTrigger::Parametric { damage_fraction: threshold } if damage_fraction >= threshold => {
+            bond.principal
+        }
+        CatBondTrigger::Parametric { .. } => 0,
+        CatBondTrigger::Indemnity { .. } => sponsor_loss.saturating_sub(bond.attachment).min(bond.principal),
+    }
+}
+
+/// All outstanding bonds, in issue order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CatBondBook {
+    bonds: Vec<CatBond>,
+    next_bond_id: u64,
+}
+
+impl CatBondBook {
+    pub fn is_empty(&self) -> bool {
+        self.bonds.is_empty()
+    }
+
+    /// True if `insurer_id` sponsors a bond that has not yet matured.
+    pub fn has_live_bond(&self, insurer_id: InsurerId) -> bool {
+        self.bonds.iter().any(|b| b.insurer_id == insurer_id)
+    }
+
+    /// Issue a bond for `insurer_id` sized on its current `capital`. Returns `CatBondIssued`
+    /// on `day` and `CatBondMatured` at the YearEnd `term_years` later, or nothing if the
+    /// principal rounds to zero.
+    pub fn issue(&mut self, day: Day, insurer_id: InsurerId, capital: u64, config: &IlsConfig) -> Vec<(Day, Event)> {
+        let principal = (capital as f64 * config.principal_capital_fraction).round() as u64;
+        if principal == 0 {
+            return vec![];
+        }
+        let attachment = match config.trigger {
+            CatBondTrigger::Parametric { .. } => 0,
+            CatBondTrigger::Indemnity { attachment_capital_fraction } => {
+                (capital as f64 * attachment_capital_fraction).round() as u64
+            }
+        };
+        let term = config.term_years.max(1);
+        let premium = (principal as f64 * config.spread * term as f64).round() as u64;
+        let maturity_year = Year(day.year().0 + term);
+        let bond_id = self.next_bond_id;
+        self.next_bond_id += 1;
+        self.bonds.push(CatBond { bond_id, insurer_id, trigger: config.trigger, principal, attachment, maturity_year });
+        vec![
+            (day, Event::CatBondIssued(Box::new(CatBondIssue {
+                bond_id,
+                insurer_id,
+                trigger: config.trigger,
+                principal,
+                attachment,
+                premium,
+                maturity_year,
+                remaining_capital: 0, // back-filled by simulation
+            }))),
+            (Day::year_end(maturity_year), Event::CatBondMatured { bond_id, insurer_id, principal_returned: 0 }),
+        ]
+    }
+
+    /// Test every bond with principal left against cat occurrence `event_id`. `sponsor_loss`
+    /// gives each insurer's insured loss from the occurrence, or `None` once the insurer is
+    /// insolvent; those sponsors' bonds are skipped. Draws down the principal of triggered bonds
+    /// and returns one `CatBondTriggered` each.
+    pub fn on_loss_event(
+        &mut self,
+        day: Day,
+        event_id: u64,
+        damage_fraction: f64,
+        sponsor_loss: impl Fn(InsurerId) -> Option<u64>,
+    ) -> Vec<(Day, Event)> {
+        let mut events = vec![];
+        for bond in self.bonds.iter_mut().filter(|b| b.principal > 0) {
+            let Some(loss) = sponsor_loss(bond.insurer_id) else { continue };
+            let amount = payout(bond, damage_fraction, loss);
+            if amount == 0 {
+                continue;
+            }
+            bond.principal -= amount;
+            events.push((day, Event::CatBondTriggered {
+                bond_id: bond.bond_id,
+                insurer_id: bond.insurer_id,
+                event_id,
+                payout: amount,
+                remaining_capital: 0, // back-filled by simulation
+            }));
+        }
+        events
+    }
+
+    /// Retire bond `bond_id`, returning the principal handed back to investors.
+    pub fn mature(&mut self, bond_id: u64) -> u64 {
+        match self.bonds.iter().position(|b| b.bond_id == bond_id) {
+            Some(i) => self.bonds.remove(i).principal,
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(trigger: CatBondTrigger) -> IlsConfig {
+        IlsConfig {
+            trigger,
+            term_years: 3,
+            principal_capital_fraction: 0.25,
+            spread: 0.05,
+            issuance_probability: 0.2,
+            rate_elasticity: 4.0,
+        }
+    }
+
+    #[test]
+    fn issuance_rises_with_hardening_and_is_capped() {
+        let c = config(CatBondTrigger::Parametric { damage_fraction: 0.2 });
+        assert!((issuance_probability(&c, 1.0) - 0.2).abs() < 1e-12);
+        assert!(issuance_probability(&c, 1.2) > issuance_probability(&c, 1.0));
+        assert!(issuance_probability(&c, 0.9) < issuance_probability(&c, 1.0));
+        assert_eq!(issuance_probability(&c, 2.0), 1.0);
+        let flat = IlsConfig { rate_elasticity: 0.0, ..c };
+        assert_eq!(issuance_probability(&flat, 1.5), issuance_probability(&flat, 1.0));
+    }
+
+    #[test]
+    fn issue_sizes_bond_on_capital_and_schedules_maturity() {
+        let mut book = CatBondBook::default();
+        let c = config(CatBondTrigger::Indemnity { attachment_capital_fraction: 0.1 });
+        let events = book.issue(Day::year_end(Year(2)), InsurerId(4), 100_000_000, &c);
+        match events.as_slice() {
+            [
+                (issued_day, Event::CatBondIssued(bond)),
+                (matured_day, Event::CatBondMatured { bond_id: 0, .. }),
+            ] => {
+                assert_eq!(*issued_day, Day::year_end(Year(2)));
+                assert_eq!(bond.bond_id, 0);
+                assert_eq!(bond.principal, 25_000_000);
+                assert_eq!(bond.attachment, 10_000_000);
+                assert_eq!(bond.premium, 3_750_000, "3 years × 5% × principal, paid upfront");
+                assert_eq!(bond.maturity_year, Year(5));
+                assert_eq!(*matured_day, Day::year_end(Year(5)));
+            }
+            other => panic!("unexpected events {other:?}"),
+        }
+        assert!(book.has_live_bond(InsurerId(4)));
+        assert!(!book.has_live_bond(InsurerId(5)));
+    }
+
+    #[test]
+    fn parametric_pays_full_principal_once_threshold_is_reached() {
+        let mut book = CatBondBook::default();
+        book.issue(Day(359), InsurerId(1), 100_000_000, &config(CatBondTrigger::Parametric { damage_fraction: 0.2 }));
+        assert!(book.on_loss_event(Day(400), 1, 0.15, |_| Some(u64::MAX)).is_empty(), "below threshold");
+        let hit = book.on_loss_event(Day(500), 2, 0.25, |_| Some(0));
+        assert!(matches!(hit.as_slice(), [(_, Event::CatBondTriggered { event_id: 2, payout: 25_000_000, .. })]));
+        assert!(book.on_loss_event(Day(600), 3, 0.5, |_| Some(0)).is_empty(), "exhausted bonds stay silent");
+        assert_eq!(book.mature(0), 0);
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn indemnity_pays_sponsor_loss_excess_of_attachment_until_exhausted() {
+        let mut book = CatBondBook::default();
+        let c = config(CatBondTrigger::Indemnity { attachment_capital_fraction: 0.1 });
+        book.issue(Day(359), InsurerId(1), 100_000_000, &c);
+        book.issue(Day(359), InsurerId(2), 100_000_000, &c);
+        let loss = |id: InsurerId| Some(if id == InsurerId(1) { 30_000_000 } else { 5_000_000 });
+        let hit = book.on_loss_event(Day(400), 9, 0.0, loss);
+        assert!(
+            matches!(hit.as_slice(), [(_, Event::CatBondTriggered { bond_id: 0, payout: 20_000_000, .. })]),
+            "only the sponsor above its attachment recovers: {hit:?}"
+        );
+        let hit = book.on_loss_event(Day(450), 10, 0.0, loss);
+        assert!(matches!(hit.as_slice(), [(_, Event::CatBondTriggered { payout: 5_000_000, .. })]), "capped at what is left");
+        assert_eq!(book.mature(1), 25_000_000, "untouched bond returns its principal");
+    }
+
+    #[test]
+    fn insolvent_sponsors_do_not_trigger_and_keep_their_principal() {
+        let mut book = CatBondBook::default();
+        book.issue(Day(359), InsurerId(1), 100_000_000, &config(CatBondTrigger::Parametric { damage_fraction: 0.2 }));
+        assert!(book.on_loss_event(Day(400), 1, 0.5, |_| None).is_empty());
+        assert_eq!(book.mature(0), 25_000_000, "the collateral goes back to investors, not the estate");
+    }
+}
@@ -0,0 +1,232 @@
+//! Lookup index over a finished event log.
+//!
+//! Analyses that answer questions about one policy or insurer otherwise scan the whole log and
+//! build their own maps. [`EventIndex`] does that once: events by policy and by insurer as
+//! lists of log positions, day ranges by binary search (the log is in day order), and each
+//! policy's lifecycle from binding to expiry or cancellation. Which fields name an insurer
+//! follows `filter::INSURER_FIELDS`, so an index lookup and an `EventFilter` on the same id
+//! select the same events.
+
+use std::collections::HashMap;
+
+use crate::events::{Event, SimEvent};
+use crate::types::{Day, InsurerId, PolicyId};
+
+/// Log positions of one policy's milestones.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PolicyLifecycle {
+    /// The `PolicyBound`.
+    pub bound: usize,
+    /// The `PolicyRenewed` logged alongside the binding, if it renewed existing cover.
+    pub renewed: Option<usize>,
+    /// Each `PolicyAnniversary` of a multi-year term.
+    pub anniversaries: Vec<usize>,
+    /// The `PolicyExpired` or `PolicyCancelled` that ended it; None if in force when the log ends.
+    pub ended: Option<usize>,
+}
+
+/// Positional index over a log slice; lookups return the log's own events.
+#[derive(Debug)]
+pub struct EventIndex<'a> {
+    log: &'a [SimEvent],
+    by_policy: HashMap<PolicyId, Vec<usize>>,
+    by_insurer: HashMap<InsurerId, Vec<usize>>,
+    lifecycles: HashMap<PolicyId, PolicyLifecycle>,
+}
+
+impl<'a> EventIndex<'a> {
+    /// Index `log` in one pass.
+    pub fn new(log: &'a [SimEvent]) -> Self {
+        let mut index =
+            EventIndex { log, by_policy: HashMap::new(), by_insurer: HashMap::new(), lifecycles: HashMap::new() };
+        let mut insurers = Vec::new();
+        for (i, ev) in log.iter().enumerate() {
+            if let Some(policy_id) = policy_of(&ev.event) {
+                index.by_policy.entry(policy_id).or_default().push(i);
+                match &ev.event {
+                    Event::PolicyBound { .. } => {
+                        index.lifecycles.insert(policy_id, PolicyLifecycle { bound: i, ..PolicyLifecycle::default() });
+                    }
+                    Event::PolicyRenewed { .. } => {
+                        index.lifecycles.entry(policy_id).or_default().renewed = Some(i);
+                    }
+                    Event::PolicyAnniversary { .. } => {
+                        index.lifecycles.entry(policy_id).or_default().anniversaries.push(i);
+                    }
+                    Event::PolicyExpired { .. } | Event::PolicyCancelled { .. } => {
+                        index.lifecycles.entry(policy_id).or_default().ended.get_or_insert(i);
+                    }
+                    _ => {}
+                }
+            }
+            insurers.clear();
+            insurers_of(&ev.event, &mut insurers);
+            insurers.sort_unstable();
+            insurers.dedup();
+            for &insurer_id in &insurers {
+                index.by_insurer.entry(insurer_id).or_default().push(i);
+            }
+        }
+        index
+    }
+
+    /// Events naming `policy_id`, in log order.
+    pub fn policy(&self, policy_id: PolicyId) -> impl Iterator<Item = &'a SimEvent> + '_ {
+        self.positions(self.by_policy.get(&policy_id))
+    }
+
+    /// Events naming `insurer_id` — as the insurer, a panel member, a lead or a transfer party.
+    pub fn insurer(&self, insurer_id: InsurerId) -> impl Iterator<Item = &'a SimEvent> + '_ {
+        self.positions(self.by_insurer.get(&insurer_id))
+    }
+
+    /// Events on days `first..=last`.
+    pub fn days(&self, first: Day, last: Day) -> &'a [SimEvent] {
+        let start = self.log.partition_point(|e| e.day < first);
+        let end = self.log.partition_point(|e| e.day <= last);
+        &self.log[start..end.max(start)]
+    }
+
+    pub fn lifecycle(&self, policy_id: PolicyId) -> Option<&PolicyLifecycle> {
+        self.lifecycles.get(&policy_id)
+    }
+
+    /// Every bound policy's lifecycle, in no particular order.
+    pub fn lifecycles(&self) -> impl Iterator<Item = (PolicyId, &PolicyLifecycle)> {
+        self.lifecycles.iter().map(|(id, l)| (*id, l))
+    }
+
+    /// The event at log position `i`, as stored in a `PolicyLifecycle`.
+    pub fn get(&self, i: usize) -> Option<&'a SimEvent> {
+        self.log.get(i)
+    }
+
+    fn positions<'b>(&self, positions: Option<&'b Vec<usize>>) -> impl Iterator<Item = &'a SimEvent> + 'b
+    where
+        'a: 'b,
+    {
+        let log = self.log;
+        positions.into_iter().flatten().map(move |&i| &log[i])
+    }
+}
+
+fn policy_of(event: &Event) -> Option<PolicyId> {
+    match event {
+        Event::PolicyBound { policy_id, .. }
+        | Event::PolicyRenewed { policy_id, .. }
+        | Event::QuoteComparisonRecorded { policy_id, .. }
+        | Event::PolicyExpired { policy_id }
+        | Event::PremiumDefaulted { policy_id, .. }
+        | Event::PremiumReceived { policy_id, .. }
+        | Event::BrokerageEarned { policy_id, .. }
+        | Event::PolicyCancelled { policy_id, .. }
+        | Event::PolicyAnniversary { policy_id, .. }
+        | Event::DeductibleEroded { policy_id, .. }
+        | Event::ClaimSettled { policy_id, .. }
+        | Event::ClaimReported { policy_id, .. }
+        | Event::ClaimPaid { policy_id, .. } => Some(*policy_id),
+        _ => None,
+    }
+}
+
+fn insurers_of(event: &Event, out: &mut Vec<InsurerId>) {
+    match event {
+        Event::QuotePresented { leader_id, panel, .. } | Event::QuoteAccepted { leader_id, panel, .. } => {
+            out.push(*leader_id);
+            out.extend(panel.iter().map(|(id, _)| *id));
+        }
+        Event::PolicyBound { panel, .. } => out.extend(panel.iter().map(|(id, _)| *id)),
+        Event::PortfolioTransferred(transfer) => out.extend([transfer.seller_id, transfer.buyer_id]),
+        Event::CatBondIssued(bond) => out.push(bond.insurer_id),
+        Event::InsurerCreated { insurer_id, .. }
+        | Event::PmlReported { insurer_id, .. }
+        | Event::UnderwritingPlanSet { insurer_id, .. }
+        | Event::CapitalFlow { insurer_id, .. }
+        | Event::ExposureSnapshot { insurer_id, .. }
+        | Event::LeadQuoteRequested { insurer_id, .. }
+        | Event::LeadQuoteBatchClosed { insurer_id, .. }
+        | Event::LeadQuoteDeclined { insurer_id, .. }
+        | Event::LeadQuoteIssued { insurer_id, .. }
+        | Event::LeadQuotePartial { insurer_id, .. }
+        | Event::FollowerQuoteRequested { insurer_id, .. }
+        | Event::FollowerQuoteIssued { insurer_id, .. }
+        | Event::FollowerQuoteDeclined { insurer_id, .. }
+        | Event::RenewalOffered { insurer_id, .. }
+        | Event::RenewalDeclined { insurer_id, .. }
+        | Event::ClaimSettled { insurer_id, .. }
+        | Event::ClaimReported { insurer_id, .. }
+        | Event::ReserveEstablished { insurer_id, .. }
+        | Event::ClaimPaid { insurer_id, .. }
+        | Event::InvestmentIncome { insurer_id, .. }
+        | Event::InsurerInsolvent { insurer_id }
+        | Event::CapitalRaised { insurer_id, .. }
+        | Event::CatBondTriggered { insurer_id, .. }
+        | Event::CatBondMatured { insurer_id, .. }
+        | Event::ReinsurancePurchased { insurer_id, .. }
+        | Event::ReinsuranceRecovered { insurer_id, .. }
+        | Event::InsurerExited { insurer_id, .. }
+        | Event::InsurerReEntered { insurer_id, .. }
+        | Event::InsurerEntered { insurer_id, .. }
+        | Event::ExpensesPaid { insurer_id, .. }
+        | Event::CapitalDistributed { insurer_id, .. }
+        | Event::YearEndCapital { insurer_id, .. }
+        | Event::SolvencyRatioReported { insurer_id, .. }
+        | Event::RegulatoryIntervention { insurer_id, .. } => out.push(*insurer_id),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SimulationConfig;
+    use crate::filter::EventFilter;
+    use crate::simulation::Simulation;
+    use crate::types::Year;
+
+    fn canonical_log() -> Vec<SimEvent> {
+        let config = SimulationConfig { seed: 5, years: 3, warmup_years: 0, ..SimulationConfig::canonical() };
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        sim.log.iter().cloned().collect()
+    }
+
+    #[test]
+    fn lookups_match_a_full_scan() {
+        let log = canonical_log();
+        let index = EventIndex::new(&log);
+        for id in [InsurerId(1), InsurerId(4), InsurerId(99)] {
+            let f = EventFilter { insurer: Some(id), ..EventFilter::default() };
+            let scanned: Vec<&SimEvent> = log.iter().filter(|e| f.matches(e)).collect();
+            assert_eq!(index.insurer(id).collect::<Vec<_>>(), scanned, "insurer {id:?}");
+        }
+        let f = EventFilter { policy: Some(PolicyId(7)), ..EventFilter::default() };
+        assert_eq!(index.policy(PolicyId(7)).collect::<Vec<_>>(), log.iter().filter(|e| f.matches(e)).collect::<Vec<_>>());
+
+        let (first, last) = (Day::year_start(Year(2)), Day::year_end(Year(2)));
+        let year_two = index.days(first, last);
+        assert_eq!(year_two.len(), log.iter().filter(|e| (first..=last).contains(&e.day)).count());
+        assert!(year_two.iter().all(|e| e.day.year() == Year(2)));
+        assert!(index.days(Day(10_000), Day(20_000)).is_empty());
+    }
+
+    #[test]
+    fn lifecycles_run_from_binding_to_expiry() {
+        let log = canonical_log();
+        let index = EventIndex::new(&log);
+        let mut ended = 0;
+        for (policy_id, life) in index.lifecycles() {
+            assert!(matches!(index.get(life.bound).unwrap().event, Event::PolicyBound { policy_id: p, .. } if p == policy_id));
+            if let Some(end) = life.ended {
+                assert!(end > life.bound);
+                assert!(matches!(
+                    index.get(end).unwrap().event,
+                    Event::PolicyExpired { policy_id: p } | Event::PolicyCancelled { policy_id: p, .. } if p == policy_id
+                ));
+                ended += 1;
+            }
+        }
+        assert!(ended > 0, "annual policies bound in year 1 expire within the run");
+    }
+}
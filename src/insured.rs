@@ -1,6 +1,8 @@
-use crate::config::ASSET_VALUE;
-use crate::events::{Event, Peril, Risk};
-use crate::types::{Day, InsuredId, InsurerId, SubmissionId};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ASSET_VALUE, DemandConfig, LoyaltyConfig, MitigationConfig, RetentionConfig, RolloverConfig};
+use crate::events::{Event, Peril, RejectionReason, Risk};
+use crate::types::{Day, InsuredId, InsurerId, SubmissionId, Territory};
 
 /// Uplift added to acceptance threshold per unit of damage fraction suffered.
 const UPLIFT_FACTOR: f64 = 0.5;
@@ -9,30 +11,299 @@ const UPLIFT_DECAY: f64 = 0.65;
 /// Maximum additional acceptance headroom above `base_max_rate_on_line`.
 const MAX_UPLIFT: f64 = 0.50;
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Insured {
     pub id: InsuredId,
-    /// The asset this insured holds and seeks coverage for.
+    /// Id of the owner's first asset when the owner holds several units (`assets_per_insured`,
+    /// or an asset and its casualty cover); None for a single-asset insured.
+    owner: Option<InsuredId>,
+    /// The asset (or, on the casualty line, the liability) this insured seeks coverage for.
     pub risk: Risk,
     /// Baseline reservation price (set at construction, never mutated).
     base_max_rate_on_line: f64,
     /// Additional acceptance headroom accumulated from recent losses; decays each year.
     rol_uplift: f64,
+    /// Deductible / limit choice. None = full-value cover, fixed.
+    retention: Option<RetentionConfig>,
+    /// Current rung of `retention.deductible_ladder`.
+    retention_rung: usize,
+    /// Price-elastic demand. None = accept anything within the reservation price.
+    demand: Option<DemandConfig>,
+    /// Reduced limit taken on the last accepted quote (partial cover); None = full terms.
+    partial_limit: Option<u64>,
+    /// Broker-published market rate that replaces `demand.reference_rate` once known.
+    market_reference_rate: Option<f64>,
+    /// Asset value at construction; `revalue` scales from it.
+    base_sum_insured: u64,
+    /// Scale on the market attritional frequency for this asset. 1.0 = market rate.
+    attritional_multiplier: f64,
+    /// Renewal loyalty to the incumbent lead. None = no preference at renewal.
+    loyalty: Option<LoyaltyConfig>,
+    /// Lead and premium of the current placement; cleared when the insured goes uncovered.
+    incumbent: Option<(InsurerId, u64)>,
+    /// Panel rollover at renewal. None = renewals are placed as new submissions.
+    rollover: Option<RolloverConfig>,
+    /// Panel of the current placement (leader first), kept under rollover; cleared with
+    /// `incumbent`.
+    expiring_panel: Vec<(InsurerId, f64)>,
+    /// Risk-management investment on premium jumps. None = fixed loss propensity.
+    mitigation: Option<MitigationConfig>,
+    /// Scale on the damage a catastrophe does to this asset. 1.0 = unmitigated.
+    cat_damage_factor: f64,
+    /// Attritional multiplier and cat damage factor bought by this year's investment,
+    /// effective from the next YearEnd.
+    pending_mitigation: Option<(f64, f64)>,
+    /// Attritional multiplier before any mitigation (the floor is relative to it).
+    base_attritional_multiplier: f64,
 }
 
 impl Insured {
-    pub fn new(id: InsuredId, territory: String, perils_covered: Vec<Peril>, max_rate_on_line: f64) -> Self {
+    pub fn new(id: InsuredId, territory: Territory, perils_covered: Vec<Peril>, max_rate_on_line: f64) -> Self {
         Self {
             id,
-            risk: Risk { sum_insured: ASSET_VALUE, territory, perils_covered },
+            owner: None,
+            risk: Risk {
+                sum_insured: ASSET_VALUE,
+                territory,
+                perils_covered,
+                attachment: 0,
+                limit: ASSET_VALUE,
+            },
             base_max_rate_on_line: max_rate_on_line,
             rol_uplift: 0.0,
+            retention: None,
+            retention_rung: 0,
+            demand: None,
+            partial_limit: None,
+            market_reference_rate: None,
+            base_sum_insured: ASSET_VALUE,
+            attritional_multiplier: 1.0,
+            loyalty: None,
+            incumbent: None,
+            rollover: None,
+            expiring_panel: vec![],
+            mitigation: None,
+            cat_damage_factor: 1.0,
+            pending_mitigation: None,
+            base_attritional_multiplier: 1.0,
+        }
+    }
+
+    /// Hold an asset worth `sum_insured` instead of `ASSET_VALUE`. Cover terms are re-derived
+    /// from the retention, so the builders may be applied in any order.
+    pub fn with_sum_insured(mut self, sum_insured: u64) -> Self {
+        self.base_sum_insured = sum_insured;
+        self.risk.sum_insured = sum_insured;
+        self.apply_retention();
+        self
+    }
+
+    /// Mark this asset as one of several held by the insured whose first asset is `owner`.
+    pub fn with_owner(mut self, owner: Option<InsuredId>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Scale the market attritional frequency for this asset by `multiplier`.
+    pub fn with_attritional_multiplier(mut self, multiplier: f64) -> Self {
+        self.attritional_multiplier = multiplier;
+        self.base_attritional_multiplier = multiplier;
+        self
+    }
+
+    /// Invest in mitigation when the renewal premium jumps (see `MitigationConfig`).
+    pub fn with_mitigation(mut self, mitigation: Option<MitigationConfig>) -> Self {
+        self.mitigation = mitigation;
+        self
+    }
+
+    /// Apply a price-elastic demand curve on top of the hard reservation price.
+    pub fn with_demand(mut self, demand: Option<DemandConfig>) -> Self {
+        self.demand = demand;
+        self
+    }
+
+    /// Prefer the incumbent lead at renewal unless its premium rises past the loyalty threshold.
+    pub fn with_loyalty(mut self, loyalty: Option<LoyaltyConfig>) -> Self {
+        self.loyalty = loyalty;
+        self
+    }
+
+    /// Offer the expiring panel its lines again at renewal (see `RolloverConfig`).
+    pub fn with_rollover(mut self, rollover: Option<RolloverConfig>) -> Self {
+        self.rollover = rollover;
+        self
+    }
+
+    /// Buy cover excess of a deductible from `retention.deductible_ladder` (starting on the
+    /// first rung) up to `limit_fraction × sum_insured`.
+    pub fn with_retention(mut self, retention: Option<RetentionConfig>) -> Self {
+        self.retention = retention;
+        self.retention_rung = 0;
+        self.apply_retention();
+        self
+    }
+
+    /// React to the outcome of a placement: `Some(rate)` = accepted at `premium / sum_insured`,
+    /// `None` = rejected as too expensive. Climbs one rung of the deductible ladder when the
+    /// rate is above `step_up_at × effective_max_rol()` (or on rejection) and descends one when
+    /// it is below `step_down_at ×`, so hardening rates are absorbed by retaining more risk.
+    /// The new terms apply from the next renewal. No-op without a retention config.
+    pub fn adjust_retention(&mut self, accepted_rate: Option<f64>) {
+        let Some(retention) = self.retention.as_ref() else {
+            return;
+        };
+        let budget = self.effective_max_rol();
+        let top = retention.deductible_ladder.len().saturating_sub(1);
+        match accepted_rate {
+            None => self.retention_rung = (self.retention_rung + 1).min(top),
+            Some(rate) if rate > retention.step_up_at * budget => {
+                self.retention_rung = (self.retention_rung + 1).min(top)
+            }
+            Some(rate) if rate < retention.step_down_at * budget => {
+                self.retention_rung = self.retention_rung.saturating_sub(1)
+            }
+            Some(_) => {}
+        }
+        self.apply_retention();
+    }
+
+    /// The incumbent lead and the highest premium the insured will take from it without
+    /// shopping the market. None without loyalty or when the insured holds no placement.
+    pub fn renewal_anchor(&self) -> Option<(InsurerId, u64)> {
+        let loyalty = self.loyalty.as_ref()?;
+        let (leader_id, premium) = self.incumbent?;
+        Some((leader_id, (premium as f64 * (1.0 + loyalty.max_premium_increase)).round() as u64))
+    }
+
+    /// The expiring panel (leader first) and the highest premium at which the insured rolls it
+    /// over. None without rollover or when the insured holds no placement.
+    pub fn rollover_terms(&self) -> Option<(Vec<(InsurerId, f64)>, u64)> {
+        let rollover = self.rollover.as_ref()?;
+        let (_, premium) = self.incumbent?;
+        if self.expiring_panel.is_empty() {
+            return None;
+        }
+        let max_premium = (premium as f64 * (1.0 + rollover.max_premium_increase)).round() as u64;
+        Some((self.expiring_panel.clone(), max_premium))
+    }
+
+    /// Remember the panel of an accepted placement for the next rollover. No-op without rollover.
+    pub fn record_panel(&mut self, panel: &[(InsurerId, f64)]) {
+        if self.rollover.is_some() {
+            self.expiring_panel = panel.to_vec();
+        }
+    }
+
+    /// A quote led by `leader_id` was accepted at `premium`. Returns the lead of the expiring
+    /// placement (None for a new placement).
+    pub fn record_placement(&mut self, leader_id: InsurerId, premium: u64) -> Option<InsurerId> {
+        self.incumbent.replace((leader_id, premium)).map(|(id, _)| id)
+    }
+
+    /// A renewal at `premium` is about to be recorded. Under mitigation, a rise of more than
+    /// `trigger_increase` over the expiring premium buys a cut in loss propensity, effective
+    /// from the next YearEnd; returns the `MitigationInvested` event. One investment per year.
+    pub fn consider_mitigation(&mut self, day: Day, premium: u64) -> Option<(Day, Event)> {
+        let m = self.mitigation.as_ref()?;
+        let (_, expiring) = self.incumbent?;
+        if self.pending_mitigation.is_some() || (premium as f64) <= expiring as f64 * (1.0 + m.trigger_increase) {
+            return None;
         }
+        let (attritional, cat) = (self.attritional_multiplier, self.cat_damage_factor);
+        let attritional_multiplier =
+            (attritional * (1.0 - m.attritional_reduction)).max(self.base_attritional_multiplier * m.min_factor);
+        let cat_damage_factor = (cat * (1.0 - m.cat_reduction)).max(m.min_factor);
+        self.pending_mitigation = Some((attritional_multiplier, cat_damage_factor));
+        let spend = (premium as f64 * m.spend_fraction).round() as u64;
+        Some((day, Event::MitigationInvested { insured_id: self.id, spend, attritional_multiplier, cat_damage_factor }))
+    }
+
+    /// The insured is going uncovered: its next placement is new business.
+    pub fn lapse(&mut self) {
+        self.incumbent = None;
+        self.expiring_panel.clear();
+    }
+
+    /// Revalue the asset to `index × ` its construction value. The deductible and limit are
+    /// re-derived from the current retention terms, so they keep their share of the value.
+    pub fn revalue(&mut self, index: f64) {
+        self.risk.sum_insured = (self.base_sum_insured as f64 * index).round() as u64;
+        self.apply_retention();
+    }
+
+    /// Current per-occurrence deductible as a fraction of sum insured.
+    pub fn deductible_fraction(&self) -> f64 {
+        self.risk.attachment as f64 / self.risk.sum_insured as f64
+    }
+
+    fn apply_retention(&mut self) {
+        let si = self.risk.sum_insured;
+        let (deductible, limit) = match self.retention.as_ref() {
+            Some(r) => (
+                r.deductible_ladder.get(self.retention_rung).copied().unwrap_or(0.0),
+                r.limit_fraction,
+            ),
+            None => (0.0, 1.0),
+        };
+        self.risk.attachment = (deductible.clamp(0.0, 1.0) * si as f64).round() as u64;
+        self.risk.limit = (limit.clamp(0.0, 1.0) * si as f64).round() as u64;
+    }
+
+    /// Probability of buying at `rate`: 1.0 up to the reference rate, then
+    /// `(reference_rate / rate)^elasticity`. Always 1.0 without a demand config.
+    /// The reference is the broker-published market rate once one has been received.
+    pub fn acceptance_probability(&self, rate: f64) -> f64 {
+        let Some(d) = self.demand.as_ref() else {
+            return 1.0;
+        };
+        let reference = self.market_reference_rate.unwrap_or(d.reference_rate);
+        if rate > reference { (reference / rate).powf(d.elasticity) } else { 1.0 }
+    }
+
+    /// Adopt the broker's published average rate for this insured's territory as the
+    /// demand reference: quotes above what the market is paying now look expensive.
+    pub fn on_market_intelligence(&mut self, market_rate: f64) {
+        if market_rate > 0.0 {
+            self.market_reference_rate = Some(market_rate);
+        }
+    }
+
+    /// The terms actually bought on the last accepted quote: `risk` with the limit cut to
+    /// the partial-cover limit, if one was taken.
+    pub fn bound_risk(&self) -> Risk {
+        let mut risk = self.risk.clone();
+        if let Some(limit) = self.partial_limit {
+            risk.limit = limit;
+        }
+        risk
     }
 
     pub fn sum_insured(&self) -> u64 {
         self.risk.sum_insured
     }
 
+    /// Id of the owner's first asset for an insured holding several units; None for a single
+    /// asset.
+    pub fn owner(&self) -> Option<InsuredId> {
+        self.owner
+    }
+
+    /// Asset value at construction, before any revaluation.
+    pub fn base_sum_insured(&self) -> u64 {
+        self.base_sum_insured
+    }
+
+    /// Scale on the market attritional frequency for this asset.
+    pub fn attritional_multiplier(&self) -> f64 {
+        self.attritional_multiplier
+    }
+
+    /// Scale on the damage a catastrophe does to this asset (1.0 without mitigation).
+    pub fn cat_damage_factor(&self) -> f64 {
+        self.cat_damage_factor
+    }
+
     /// The insured's baseline reservation price (set at construction, never mutated).
     pub fn base_max_rol(&self) -> f64 {
         self.base_max_rate_on_line
@@ -49,36 +320,61 @@ impl Insured {
         self.rol_uplift = (self.rol_uplift + UPLIFT_FACTOR * damage_fraction).min(MAX_UPLIFT);
     }
 
-    /// Called at each `YearEnd`. Decays the uplift so memories fade over ~1.5 years.
+    /// Called at each `YearEnd`. Decays the uplift so memories fade over ~1.5 years, and
+    /// completes any mitigation invested in during the year.
     pub fn on_year_end(&mut self) {
         self.rol_uplift *= UPLIFT_DECAY;
+        if let Some((attritional, cat)) = self.pending_mitigation.take() {
+            self.attritional_multiplier = attritional;
+            self.cat_damage_factor = cat;
+        }
     }
 
     /// The insured decides whether to accept the quote based on its reservation price.
-    /// Emits `QuoteRejected` if `premium / sum_insured > effective_max_rol()`; `QuoteAccepted` otherwise.
+    /// Emits `QuoteRejected { reason: ReservationPriceExceeded }` if
+    /// `premium / sum_insured > effective_max_rol()`. Otherwise, with a demand config and a
+    /// uniform `demand_draw`, the quote is bought in full when the draw falls below
+    /// `acceptance_probability(rate)`; failing that, the insured buys that fraction of its limit
+    /// (premium scaled by the exposure-curve layer factor) or, below `min_cover_fraction`,
+    /// emits `QuoteRejected { reason: PriceTooHigh }`.
     pub fn on_quote_presented(
-        &self,
+        &mut self,
         day: Day,
         submission_id: SubmissionId,
         leader_id: InsurerId,
         panel: Vec<(InsurerId, f64)>,
         premium: u64,
+        demand_draw: Option<f64>,
     ) -> Vec<(Day, Event)> {
+        self.partial_limit = None;
         let rate = premium as f64 / self.risk.sum_insured as f64;
+        let reject = |reason| vec![(day, Event::QuoteRejected { submission_id, insured_id: self.id, reason })];
         if rate > self.effective_max_rol() {
-            vec![(day, Event::QuoteRejected { submission_id, insured_id: self.id })]
-        } else {
-            vec![(
-                day,
-                Event::QuoteAccepted {
-                    submission_id,
-                    insured_id: self.id,
-                    leader_id,
-                    panel,
-                    premium,
-                },
-            )]
+            return reject(RejectionReason::ReservationPriceExceeded);
+        }
+
+        let mut premium = premium;
+        let p = self.acceptance_probability(rate);
+        if let (Some(demand), Some(u)) = (self.demand.as_ref(), demand_draw)
+            && u >= p
+        {
+            if p < demand.min_cover_fraction {
+                return reject(RejectionReason::PriceTooHigh);
+            }
+            let partial = Risk { limit: (self.risk.limit as f64 * p).round() as u64, ..self.risk.clone() };
+            premium = (premium as f64 * partial.layer_factor() / self.risk.layer_factor()).round() as u64;
+            self.partial_limit = Some(partial.limit);
         }
+        vec![(
+            day,
+            Event::QuoteAccepted {
+                submission_id,
+                insured_id: self.id,
+                leader_id,
+                panel,
+                premium,
+            },
+        )]
     }
 }
 
@@ -89,7 +385,7 @@ mod tests {
     fn make_insured(id: u64) -> Insured {
         Insured::new(
             InsuredId(id),
-            "US-SE".to_string(),
+            Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional],
             1.0, // accepts all quotes
         )
@@ -100,7 +396,7 @@ mod tests {
     #[test]
     fn on_asset_damage_raises_effective_max_rol() {
         let mut insured = Insured::new(
-            InsuredId(1), "US-SE".to_string(),
+            InsuredId(1), Territory(0),
             vec![Peril::WindstormAtlantic], 0.10,
         );
         insured.on_asset_damage(0.20);
@@ -111,7 +407,7 @@ mod tests {
     #[test]
     fn zero_damage_does_not_change_uplift() {
         let mut insured = Insured::new(
-            InsuredId(2), "US-SE".to_string(),
+            InsuredId(2), Territory(0),
             vec![Peril::WindstormAtlantic], 0.10,
         );
         insured.on_asset_damage(0.0);
@@ -121,7 +417,7 @@ mod tests {
     #[test]
     fn on_year_end_decays_rol_uplift() {
         let mut insured = Insured::new(
-            InsuredId(3), "US-SE".to_string(),
+            InsuredId(3), Territory(0),
             vec![Peril::WindstormAtlantic], 0.10,
         );
         insured.on_asset_damage(0.40); // uplift = 0.20
@@ -134,7 +430,7 @@ mod tests {
     #[test]
     fn uplift_decays_toward_zero_over_years() {
         let mut insured = Insured::new(
-            InsuredId(4), "US-SE".to_string(),
+            InsuredId(4), Territory(0),
             vec![Peril::WindstormAtlantic], 0.10,
         );
         insured.on_asset_damage(1.0); // uplift = 0.50 (capped)
@@ -146,7 +442,7 @@ mod tests {
     #[test]
     fn uplift_capped_at_max_uplift() {
         let mut insured = Insured::new(
-            InsuredId(5), "US-SE".to_string(),
+            InsuredId(5), Territory(0),
             vec![Peril::WindstormAtlantic], 0.10,
         );
         for _ in 0..5 { insured.on_asset_damage(0.50); } // uncapped sum = 1.25
@@ -157,12 +453,12 @@ mod tests {
     fn quote_accepted_above_base_after_large_loss() {
         // Base=0.10, quote at 18% RoL, damage fraction=0.50 → uplift=0.25, effective=0.35 → accept
         let mut insured = Insured::new(
-            InsuredId(6), "US-SE".to_string(),
+            InsuredId(6), Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional], 0.10,
         );
         insured.on_asset_damage(0.50); // uplift = 0.25
         let premium = (ASSET_VALUE as f64 * 0.18) as u64;
-        let events = insured.on_quote_presented(Day(1), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium);
+        let events = insured.on_quote_presented(Day(1), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium, None);
         assert!(matches!(events[0].1, Event::QuoteAccepted { .. }),
             "quote at 18% RoL should be accepted after uplift to 35%, got {:?}", events[0].1);
     }
@@ -171,12 +467,12 @@ mod tests {
     fn quote_rejected_above_effective_after_small_loss() {
         // Base=0.10, uplift=0.02 (damage=0.04), effective=0.12; quote at 13% → reject
         let mut insured = Insured::new(
-            InsuredId(7), "US-SE".to_string(),
+            InsuredId(7), Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional], 0.10,
         );
         insured.on_asset_damage(0.04); // uplift = 0.5 × 0.04 = 0.02
         let premium = (ASSET_VALUE as f64 * 0.13) as u64;
-        let events = insured.on_quote_presented(Day(1), SubmissionId(2), InsurerId(1), vec![(InsurerId(1), 1.0)], premium);
+        let events = insured.on_quote_presented(Day(1), SubmissionId(2), InsurerId(1), vec![(InsurerId(1), 1.0)], premium, None);
         assert!(matches!(events[0].1, Event::QuoteRejected { .. }),
             "quote at 13% should be rejected when effective threshold is 12%");
     }
@@ -184,7 +480,7 @@ mod tests {
     #[test]
     fn uplift_accumulates_across_multiple_losses() {
         let mut insured = Insured::new(
-            InsuredId(8), "US-SE".to_string(),
+            InsuredId(8), Territory(0),
             vec![Peril::WindstormAtlantic], 0.10,
         );
         insured.on_asset_damage(0.10); // uplift = 0.05
@@ -194,7 +490,7 @@ mod tests {
 
     #[test]
     fn asset_sum_insured() {
-        let insured = Insured::new(InsuredId(1), "US-SE".to_string(), vec![Peril::WindstormAtlantic], 1.0);
+        let insured = Insured::new(InsuredId(1), Territory(0), vec![Peril::WindstormAtlantic], 1.0);
         assert_eq!(insured.sum_insured(), ASSET_VALUE);
     }
 
@@ -203,12 +499,12 @@ mod tests {
     #[test]
     fn on_quote_presented_accepts_below_threshold() {
         // max_rate_on_line=0.10; premium at 8% RoL → accepts.
-        let insured = Insured::new(
-            InsuredId(1), "US-SE".to_string(),
+        let mut insured = Insured::new(
+            InsuredId(1), Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional], 0.10,
         );
         let premium = (ASSET_VALUE as f64 * 0.08) as u64; // 8% RoL < 10%
-        let events = insured.on_quote_presented(Day(3), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium);
+        let events = insured.on_quote_presented(Day(3), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium, None);
         assert_eq!(events.len(), 1);
         assert!(
             matches!(events[0].1, Event::QuoteAccepted { .. }),
@@ -219,24 +515,24 @@ mod tests {
     #[test]
     fn on_quote_presented_accepts_at_threshold() {
         // max_rate_on_line=0.10; premium exactly at 10% RoL → accepts (≤ threshold).
-        let insured = Insured::new(
-            InsuredId(1), "US-SE".to_string(),
+        let mut insured = Insured::new(
+            InsuredId(1), Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional], 0.10,
         );
         let premium = (ASSET_VALUE as f64 * 0.10) as u64;
-        let events = insured.on_quote_presented(Day(3), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium);
+        let events = insured.on_quote_presented(Day(3), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium, None);
         assert!(matches!(events[0].1, Event::QuoteAccepted { .. }), "at-threshold quote must be accepted");
     }
 
     #[test]
     fn on_quote_presented_rejects_above_threshold() {
         // max_rate_on_line=0.05; premium at 6% RoL → rejects.
-        let insured = Insured::new(
-            InsuredId(1), "US-SE".to_string(),
+        let mut insured = Insured::new(
+            InsuredId(1), Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional], 0.05,
         );
         let premium = (ASSET_VALUE as f64 * 0.06) as u64; // 6% RoL > 5%
-        let events = insured.on_quote_presented(Day(3), SubmissionId(10), InsurerId(2), vec![(InsurerId(2), 1.0)], premium);
+        let events = insured.on_quote_presented(Day(3), SubmissionId(10), InsurerId(2), vec![(InsurerId(2), 1.0)], premium, None);
         assert_eq!(events.len(), 1);
         assert!(
             matches!(events[0].1, Event::QuoteRejected { .. }),
@@ -246,13 +542,14 @@ mod tests {
 
     #[test]
     fn on_quote_rejected_carries_correct_ids() {
-        let insured = Insured::new(
-            InsuredId(42), "US-SE".to_string(),
+        let mut insured = Insured::new(
+            InsuredId(42), Territory(0),
             vec![Peril::WindstormAtlantic, Peril::Attritional], 0.01,
         );
         let premium = ASSET_VALUE; // 100% RoL — always rejected
-        let events = insured.on_quote_presented(Day(5), SubmissionId(99), InsurerId(3), vec![(InsurerId(3), 1.0)], premium);
-        if let Event::QuoteRejected { submission_id, insured_id } = events[0].1 {
+        let events = insured.on_quote_presented(Day(5), SubmissionId(99), InsurerId(3), vec![(InsurerId(3), 1.0)], premium, None);
+        if let Event::QuoteRejected { submission_id, insured_id, reason } = events[0].1 {
+            assert_eq!(reason, RejectionReason::ReservationPriceExceeded);
             assert_eq!(submission_id, SubmissionId(99));
             assert_eq!(insured_id, InsuredId(42));
         } else {
@@ -262,18 +559,18 @@ mod tests {
 
     #[test]
     fn on_quote_presented_accepted_same_day() {
-        let insured = make_insured(1);
+        let mut insured = make_insured(1);
         let day = Day(7);
-        let events = insured.on_quote_presented(day, SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], 1_000);
+        let events = insured.on_quote_presented(day, SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], 1_000, None);
         assert_eq!(events[0].0, day, "QuoteAccepted must fire on the same day as QuotePresented");
     }
 
     #[test]
     fn on_quote_presented_carries_correct_fields() {
-        let insured = make_insured(42);
+        let mut insured = make_insured(42);
         let panel = vec![(InsurerId(3), 1.0)];
         let events =
-            insured.on_quote_presented(Day(5), SubmissionId(99), InsurerId(3), panel, 75_000);
+            insured.on_quote_presented(Day(5), SubmissionId(99), InsurerId(3), panel, 75_000, None);
         if let Event::QuoteAccepted { submission_id, insured_id, leader_id, panel, premium } =
             events[0].1.clone()
         {
@@ -287,4 +584,177 @@ mod tests {
             panic!("expected QuoteAccepted");
         }
     }
+
+    // ── retention choice ──────────────────────────────────────────────────────
+
+    fn retention() -> RetentionConfig {
+        RetentionConfig {
+            deductible_ladder: vec![0.0, 0.01, 0.05],
+            limit_fraction: 0.5,
+            step_up_at: 0.8,
+            step_down_at: 0.4,
+        }
+    }
+
+    #[test]
+    fn retention_sets_layer_terms_from_first_rung() {
+        let insured = Insured::new(InsuredId(1), Territory(0), vec![Peril::Attritional], 0.10)
+            .with_retention(Some(retention()));
+        assert_eq!(insured.risk.attachment, 0);
+        assert_eq!(insured.risk.limit, ASSET_VALUE / 2);
+    }
+
+    #[test]
+    fn hardening_rates_climb_the_deductible_ladder_and_softening_descends() {
+        let mut insured = Insured::new(InsuredId(1), Territory(0), vec![Peril::Attritional], 0.10)
+            .with_retention(Some(retention()));
+        insured.adjust_retention(Some(0.09)); // 90% of budget → step up
+        assert!((insured.deductible_fraction() - 0.01).abs() < 1e-9);
+        insured.adjust_retention(Some(0.06)); // inside the band → hold
+        assert!((insured.deductible_fraction() - 0.01).abs() < 1e-9);
+        insured.adjust_retention(None); // rejected → step up
+        insured.adjust_retention(None); // already at the top rung
+        assert!((insured.deductible_fraction() - 0.05).abs() < 1e-9);
+        insured.adjust_retention(Some(0.02)); // 20% of budget → step down
+        assert!((insured.deductible_fraction() - 0.01).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sum_insured_builder_rederives_terms_and_revalues_from_the_new_base() {
+        let insured = Insured::new(InsuredId(1), Territory(0), vec![Peril::Attritional], 0.10)
+            .with_retention(Some(retention()))
+            .with_sum_insured(ASSET_VALUE * 2);
+        assert_eq!(insured.risk.limit, ASSET_VALUE, "limit keeps its fraction of the new value");
+        let mut insured = insured;
+        insured.revalue(1.5);
+        assert_eq!(insured.sum_insured(), ASSET_VALUE * 3);
+        assert_eq!(insured.base_sum_insured(), ASSET_VALUE * 2);
+    }
+
+    #[test]
+    fn adjust_retention_is_noop_without_config() {
+        let mut insured = make_insured(1);
+        insured.adjust_retention(None);
+        assert_eq!(insured.risk.attachment, 0);
+        assert_eq!(insured.risk.limit, insured.risk.sum_insured);
+    }
+
+    // ── renewal loyalty ───────────────────────────────────────────────────────
+
+    #[test]
+    fn renewal_anchor_caps_the_incumbent_premium_and_lapses_with_cover() {
+        let mut insured = make_insured(1).with_loyalty(Some(LoyaltyConfig { max_premium_increase: 0.10 }));
+        assert_eq!(insured.renewal_anchor(), None, "no incumbent before the first placement");
+        assert_eq!(insured.record_placement(InsurerId(3), 50_000), None, "first placement is new business");
+        assert_eq!(insured.renewal_anchor(), Some((InsurerId(3), 55_000)));
+        assert_eq!(insured.record_placement(InsurerId(4), 52_000), Some(InsurerId(3)));
+        insured.lapse();
+        assert_eq!(insured.renewal_anchor(), None);
+
+        let mut disloyal = make_insured(2);
+        disloyal.record_placement(InsurerId(3), 50_000);
+        assert_eq!(disloyal.renewal_anchor(), None, "no anchor without loyalty");
+    }
+
+    #[test]
+    fn rollover_terms_carry_the_expiring_panel_until_cover_lapses() {
+        let mut insured = make_insured(1).with_rollover(Some(RolloverConfig { max_premium_increase: 0.20 }));
+        let panel = vec![(InsurerId(3), 0.6), (InsurerId(5), 0.4)];
+        insured.record_panel(&panel);
+        assert_eq!(insured.rollover_terms(), None, "no terms before the placement is recorded");
+        insured.record_placement(InsurerId(3), 50_000);
+        assert_eq!(insured.rollover_terms(), Some((panel.clone(), 60_000)));
+        insured.lapse();
+        assert_eq!(insured.rollover_terms(), None);
+
+        let mut plain = make_insured(2);
+        plain.record_panel(&panel);
+        plain.record_placement(InsurerId(3), 50_000);
+        assert_eq!(plain.rollover_terms(), None, "no terms without rollover");
+    }
+
+    // ── risk mitigation ───────────────────────────────────────────────────────
+
+    #[test]
+    fn premium_jump_buys_mitigation_effective_from_year_end_down_to_the_floor() {
+        let mitigation = MitigationConfig {
+            trigger_increase: 0.20,
+            spend_fraction: 0.5,
+            attritional_reduction: 0.5,
+            cat_reduction: 0.25,
+            min_factor: 0.4,
+        };
+        let mut insured = make_insured(1).with_attritional_multiplier(2.0).with_mitigation(Some(mitigation));
+        assert_eq!(insured.consider_mitigation(Day(3), 80_000), None, "new business never triggers");
+        insured.record_placement(InsurerId(1), 50_000);
+        assert_eq!(insured.consider_mitigation(Day(363), 60_000), None, "a 20% rise is within tolerance");
+
+        let Some((_, Event::MitigationInvested { spend, attritional_multiplier, cat_damage_factor, .. })) =
+            insured.consider_mitigation(Day(363), 60_001)
+        else {
+            panic!("a rise above 20% must invest")
+        };
+        assert_eq!((spend, attritional_multiplier, cat_damage_factor), (30_001, 1.0, 0.75));
+        assert_eq!(insured.consider_mitigation(Day(363), 90_000), None, "one investment per year");
+        assert_eq!((insured.attritional_multiplier(), insured.cat_damage_factor()), (2.0, 1.0), "not yet in force");
+        insured.on_year_end();
+        assert_eq!((insured.attritional_multiplier(), insured.cat_damage_factor()), (1.0, 0.75));
+
+        insured.record_placement(InsurerId(1), 60_001);
+        insured.consider_mitigation(Day(723), 100_000);
+        insured.on_year_end();
+        assert_eq!(insured.attritional_multiplier(), 0.8, "floored at 40% of the starting multiplier of 2.0");
+        assert_eq!(insured.cat_damage_factor(), 0.5625);
+    }
+
+    // ── price-elastic demand ──────────────────────────────────────────────────
+
+    fn elastic(min_cover_fraction: f64) -> Insured {
+        Insured::new(InsuredId(1), Territory(0), vec![Peril::WindstormAtlantic], 0.50)
+            .with_demand(Some(DemandConfig { reference_rate: 0.05, elasticity: 2.0, min_cover_fraction }))
+    }
+
+    #[test]
+    fn acceptance_probability_falls_above_reference_rate() {
+        let insured = elastic(1.0);
+        assert_eq!(insured.acceptance_probability(0.04), 1.0);
+        // (0.05 / 0.10)^2 = 0.25
+        assert!((insured.acceptance_probability(0.10) - 0.25).abs() < 1e-12);
+        assert_eq!(make_insured(1).acceptance_probability(0.90), 1.0, "inelastic without config");
+    }
+
+    #[test]
+    fn elastic_demand_rejects_with_price_too_high() {
+        let mut insured = elastic(1.0);
+        let premium = ASSET_VALUE / 10; // 10% RoL: within the 50% reservation price, p = 0.25
+        let panel = vec![(InsurerId(1), 1.0)];
+        let events = insured.on_quote_presented(Day(1), SubmissionId(1), InsurerId(1), panel.clone(), premium, Some(0.9));
+        assert!(matches!(events[0].1, Event::QuoteRejected { reason: RejectionReason::PriceTooHigh, .. }));
+        let events = insured.on_quote_presented(Day(1), SubmissionId(2), InsurerId(1), panel, premium, Some(0.1));
+        assert!(matches!(events[0].1, Event::QuoteAccepted { premium: p, .. } if p == premium));
+    }
+
+    #[test]
+    fn elastic_demand_buys_partial_limit_at_layer_priced_premium() {
+        let mut insured = elastic(0.2);
+        let premium = ASSET_VALUE / 10;
+        let events =
+            insured.on_quote_presented(Day(1), SubmissionId(1), InsurerId(1), vec![(InsurerId(1), 1.0)], premium, Some(0.9));
+        let bound = insured.bound_risk();
+        assert_eq!(bound.limit, ASSET_VALUE / 4, "limit cut to the acceptance probability");
+        let expected = (premium as f64 * bound.layer_factor()).round() as u64;
+        assert!(matches!(events[0].1, Event::QuoteAccepted { premium: p, .. } if p == expected));
+        assert!(expected < premium && expected > premium / 4, "first-loss layer costs more than pro rata");
+        assert_eq!(insured.risk.limit, ASSET_VALUE, "renewal requests full terms again");
+    }
+
+    #[test]
+    fn market_intelligence_replaces_demand_reference_rate() {
+        let mut insured = elastic(1.0);
+        assert_eq!(insured.acceptance_probability(0.08), (0.05f64 / 0.08).powi(2));
+        insured.on_market_intelligence(0.08);
+        assert_eq!(insured.acceptance_probability(0.08), 1.0, "paying the market rate is not expensive");
+        insured.on_market_intelligence(0.0); // broker saw no quotes: keep the last anchor
+        assert_eq!(insured.acceptance_probability(0.08), 1.0);
+    }
 }
@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-use crate::events::{DeclineReason, Event, Peril, Risk};
-use crate::types::{Day, InsuredId, InsurerId, PolicyId, SubmissionId, YearAccumulator};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{BrokerageConfig, ExpenseConfig, GrowthTargetConfig, LineAppetite, PartialLineConfig, PaymentTermsConfig, PricingMode, RecapitalisationConfig, RunoffConfig, SolvencyConfig, TerritoryLimitConfig};
+use crate::events::{CapitalFlowReason, DeclineReason, Event, ExitReason, Line, Peril, Risk};
+use crate::types::{ClaimId, Day, InsuredId, InsurerId, PolicyId, SubmissionId, Territory, TerritoryTable, Year, YearAccumulator};
 
 /// A single insurer in the minimal property market.
-/// Writes 100% of each risk it quotes (lead-only, no follow market).
+/// Quotes as lead (sets terms) or follower (writes at the lead's rate) and books its signed
+/// line share of each bound policy's premium, claims and cat aggregate.
 /// Capital is endowed once at construction and persists year-over-year; premiums add, claims deduct.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Insurer {
     pub id: InsurerId,
     /// Current capital (unsigned floor at zero; cannot go negative).
@@ -13,6 +18,10 @@ pub struct Insurer {
     /// Set to true the first time a claim drives capital to zero.
     /// An insolvent insurer declines all new quote requests but continues settling claims.
     pub insolvent: bool,
+    /// Set while the insurer is in voluntary run-off (see `evaluate_runoff`).
+    /// Like insolvency it declines all new quote requests and keeps settling claims,
+    /// but the insurer may re-enter.
+    pub runoff: bool,
     /// Actuarial channel: E[attritional_loss] / sum_insured.
     /// Updated each YearEnd via EWMA from realized attritional burning cost.
     attritional_elf: f64,
@@ -21,12 +30,24 @@ pub struct Insurer {
     /// A quiet cat period is not evidence of a lower rate; EWMA would produce systematic
     /// soft-market erosion. Mirrors Lloyd's MS3 Technical Premium requirements.
     cat_elf: f64,
+    /// Actuarial channel for the casualty line: E[casualty_loss] / limit. Anchored to the
+    /// casualty model like `cat_elf`; 0.0 without casualty.
+    casualty_elf: f64,
     /// Actuarial channel: ATP = (attritional_elf + cat_elf) / target_loss_ratio.
     target_loss_ratio: f64,
     /// EWMA credibility weight α: new_att_elf = α × realized_att_lf + (1-α) × old_att_elf.
     ewma_credibility: f64,
-    /// Fraction of gross premium consumed by acquisition costs + overhead.
+    /// Fraction of gross premium consumed by acquisition costs + overhead (acquisition costs
+    /// only under an expense split).
     expense_ratio: f64,
+    /// Acquisition / fixed-overhead expense split. None = `expense_ratio` covers all expenses.
+    expenses: Option<ExpenseConfig>,
+    /// Cash-basis premium. None = net premium credited to capital at binding.
+    payment_terms: Option<PaymentTermsConfig>,
+    /// `Frozen`: quote at TP with no AP/TP loading and keep `attritional_elf` at its initial value.
+    pricing_mode: PricingMode,
+    /// Cash basis: per policy, (line share, policy-level premium written but not yet received).
+    premium_receivables: HashMap<PolicyId, (f64, u64)>,
     /// Multiplicative loading above ATP: premium = ATP × (1 + profit_loading).
     profit_loading: f64,
     /// Year-to-date premium and claims accumulators; reset at each YearEnd.
@@ -39,8 +60,33 @@ pub struct Insurer {
     solvency_capital_fraction: Option<f64>,
     /// Pareto 1-in-200 damage fraction derived from cat model at construction.
     pml_damage_fraction_200: f64,
+    /// Modelled 1-in-200 loss over the cat aggregate at the last `PmlReported`; replaces
+    /// `pml_damage_fraction_200` in the SCR when set.
+    modelled_pml_fraction: Option<f64>,
     /// Map from policy_id to its WindstormAtlantic sum_insured, for release on expiry.
     cat_policy_map: HashMap<PolicyId, u64>,
+    /// Exposure management: live WindstormAtlantic aggregate per territory.
+    pub territory_aggregates: HashMap<Territory, u64>,
+    /// Territory of each policy in `cat_policy_map` bound with one, for release on expiry.
+    cat_policy_territory: HashMap<PolicyId, Territory>,
+    /// Per-territory PML limits (None = only the portfolio-wide cat aggregate limit applies).
+    territory_limits: Option<TerritoryLimitConfig>,
+    /// `territory_limits` PML capital fraction per territory id, resolved against the run's
+    /// territory table; ids past the end take `pml_capital_fraction`.
+    territory_fractions: Vec<f64>,
+    /// Undiversified 1-in-200 damage fraction: the loss on a territory's whole aggregate when
+    /// an event strikes it. Unlike `pml_damage_fraction_200`, not divided across territories.
+    territory_pml_damage_fraction_200: f64,
+    /// Offer cat headroom as a partial lead line instead of declining (None = decline).
+    partial_lines: Option<PartialLineConfig>,
+    /// Scale line and cat limits with the solvency ratio (None = limits follow raw capital).
+    solvency: Option<SolvencyConfig>,
+    /// Gross premium written last year; with this year's, sets the SCR premium volume.
+    last_year_premium: u64,
+    /// Unearned premium reserve: this insurer's share of in-force premium not yet earned, set
+    /// at each YearEnd from `Market::unearned_premium`. Premium is credited to capital when
+    /// written, so the reserve is held within `capital`, not on top of it.
+    pub unearned_premium: u64,
     /// Capital at construction — used to compute depletion ratio.
     initial_capital: i64,
     /// Sensitivity of capital-depletion adjustment: cap_depletion_adj = depletion × sensitivity.
@@ -78,6 +124,43 @@ pub struct Insurer {
     /// When this insurer acts as lead, capacity_line is capped at this value.
     /// Canonical: 0.25. Tests use 1.0 (preserve existing solo-writer behaviour).
     leader_participation_cap: f64,
+    /// Days on which quote requests (lead or follower) arrived, oldest first.
+    /// Trimmed to the trailing workload window by `record_quote_request`.
+    recent_quote_requests: VecDeque<Day>,
+    /// Open claims under claims development, keyed by claim id.
+    open_claims: HashMap<ClaimId, OpenClaim>,
+    /// Short rate from the last `InterestRatePublished` (0.0 without interest rates).
+    interest_rate: f64,
+    /// Years over which `actuarial_price` discounts expected losses at `interest_rate`
+    /// (`InterestRateConfig::discount_years`; 0.0 = no discount credit).
+    discount_years: f64,
+    /// Growth objective (None = the insurer does not target premium growth).
+    growth_target: Option<GrowthTargetConfig>,
+    /// Per-line underwriting appetite (empty = every line at the standard rate and line).
+    appetite: Vec<LineAppetite>,
+    /// Written premium targeted for the current year by the last `UnderwritingPlanSet`
+    /// (0 = no plan yet).
+    premium_target: u64,
+    /// Fractional cut to the underwriter premium for the current year while chasing the target.
+    undercut: f64,
+    /// Capital ledger on: capital movements are kept in `capital_flows` for the log.
+    capital_ledger: bool,
+    /// Capital movements not yet taken by `take_capital_flows` (empty between dispatches).
+    capital_flows: Vec<(CapitalFlowReason, i64)>,
+}
+
+/// Reserve position on a reported, not yet fully paid claim.
+#[derive(Clone, Serialize, Deserialize)]
+struct OpenClaim {
+    /// Case reserve set at report; the a-priori estimate of the ultimate.
+    initial_reserve: u64,
+    /// Current outstanding reserve.
+    outstanding: u64,
+    /// Instalments paid so far.
+    paid: u64,
+    /// Incurred actually charged to capital so far. Falls short of `paid + outstanding` once
+    /// capital runs out, and bounds what later releases can hand back.
+    charged: u64,
 }
 
 /// EWMA smoothing factor for the per-insurer combined-ratio signal.
@@ -110,18 +193,34 @@ impl Insurer {
             id,
             capital: initial_capital,
             insolvent: false,
+            runoff: false,
             attritional_elf,
             cat_elf,
+            casualty_elf: 0.0,
             target_loss_ratio,
             ewma_credibility,
             expense_ratio,
+            expenses: None,
+            payment_terms: None,
+            pricing_mode: PricingMode::Adaptive,
+            premium_receivables: HashMap::new(),
             profit_loading,
             ytd: YearAccumulator::default(),
             cat_aggregate: 0,
             net_line_capacity,
             solvency_capital_fraction,
             pml_damage_fraction_200,
+            modelled_pml_fraction: None,
             cat_policy_map: HashMap::new(),
+            territory_aggregates: HashMap::new(),
+            cat_policy_territory: HashMap::new(),
+            territory_limits: None,
+            territory_fractions: Vec::new(),
+            territory_pml_damage_fraction_200: pml_damage_fraction_200,
+            partial_lines: None,
+            solvency: None,
+            last_year_premium: 0,
+            unearned_premium: 0,
             initial_capital,
             depletion_sensitivity,
             capacity_sensitivity,
@@ -134,9 +233,233 @@ impl Insurer {
             payout_ratio,
             distribution_floor_multiple,
             leader_participation_cap,
+            recent_quote_requests: VecDeque::new(),
+            open_claims: HashMap::new(),
+            interest_rate: 0.0,
+            discount_years: 0.0,
+            growth_target: None,
+            appetite: Vec::new(),
+            premium_target: 0,
+            undercut: 0.0,
+            capital_ledger: false,
+            capital_flows: Vec::new(),
+        }
+    }
+
+    /// Cap each territory's cat aggregate at the PML limits in `limits`, measured at the
+    /// undiversified 1-in-200 damage fraction `pml_damage_fraction_200`. `territories` names
+    /// the run's territory ids, for the per-territory overrides.
+    pub fn with_territory_limits(
+        mut self,
+        limits: Option<TerritoryLimitConfig>,
+        pml_damage_fraction_200: f64,
+        territories: &TerritoryTable,
+    ) -> Self {
+        self.territory_fractions = limits
+            .as_ref()
+            .map_or_else(Vec::new, |l| territories.names().iter().map(|name| l.fraction_for(name)).collect());
+        self.territory_limits = limits;
+        self.territory_pml_damage_fraction_200 = pml_damage_fraction_200;
+        self
+    }
+
+    /// Offer the remaining cat aggregate headroom as a partial lead line (`LeadQuotePartial`)
+    /// instead of declining for `MaxCatAggregateBreached`.
+    pub fn with_partial_lines(mut self, partial_lines: Option<PartialLineConfig>) -> Self {
+        self.partial_lines = partial_lines;
+        self
+    }
+
+    /// Split expenses into acquisition costs, deducted from premium at binding in place of
+    /// `expense_ratio`, and a fixed overhead charged to capital at each YearEnd.
+    pub fn with_expenses(mut self, expenses: Option<ExpenseConfig>) -> Self {
+        if let Some(e) = &expenses {
+            self.expense_ratio = e.acquisition_fraction();
+        }
+        self.expenses = expenses;
+        self
+    }
+
+    /// Deduct the placing broker's commission from net premium alongside the expense ratio.
+    /// Applied after `with_expenses`, which resets the ratio to acquisition costs.
+    pub fn with_brokerage(mut self, brokerage: Option<BrokerageConfig>) -> Self {
+        if let Some(b) = &brokerage {
+            self.expense_ratio += b.commission_fraction();
+        }
+        self
+    }
+
+    /// Account for premium on a cash basis: written premium is booked at binding, but capital
+    /// is credited only by `on_premium_received`.
+    pub fn with_payment_terms(mut self, payment_terms: Option<PaymentTermsConfig>) -> Self {
+        self.payment_terms = payment_terms;
+        self
+    }
+
+    /// Freeze pricing at technical for a counterfactual baseline (see `PricingMode`).
+    pub fn with_pricing_mode(mut self, pricing_mode: PricingMode) -> Self {
+        self.pricing_mode = pricing_mode;
+        self
+    }
+
+    /// Credit the investment income on reserves in pricing: expected losses are discounted at
+    /// the published short rate over `discount_years`.
+    pub fn with_discount_credit(mut self, discount_years: f64) -> Self {
+        self.discount_years = discount_years;
+        self
+    }
+
+    /// The year's short rate (`InterestRatePublished`), used by the pricing discount credit.
+    pub fn set_interest_rate(&mut self, rate: f64) {
+        self.interest_rate = rate;
+    }
+
+    /// Record every capital movement for the capital ledger (see `take_capital_flows`).
+    pub fn with_capital_ledger(mut self, capital_ledger: bool) -> Self {
+        self.capital_ledger = capital_ledger;
+        self
+    }
+
+    /// Capital ledger: the movements since the last call, oldest first.
+    pub fn take_capital_flows(&mut self) -> Vec<(CapitalFlowReason, i64)> {
+        std::mem::take(&mut self.capital_flows)
+    }
+
+    /// Move capital by `delta` cents, recording it under `reason` when the ledger is on. Every
+    /// change to capital goes through here.
+    fn move_capital(&mut self, reason: CapitalFlowReason, delta: i64) {
+        self.capital += delta;
+        if self.capital_ledger && delta != 0 {
+            self.capital_flows.push((reason, delta));
+        }
+    }
+
+    /// Receive (positive) or pay (negative) the price of a portfolio transfer.
+    pub fn settle_portfolio_transfer(&mut self, amount: i64) {
+        self.move_capital(CapitalFlowReason::PortfolioTransfer, amount);
+    }
+
+    /// Pursue a written-premium growth target (see `set_underwriting_plan`).
+    pub fn with_growth_target(mut self, growth_target: Option<GrowthTargetConfig>) -> Self {
+        self.growth_target = growth_target;
+        self
+    }
+
+    /// Price the casualty line at `casualty_elf` (see `CasualtyConfig::expected_loss_fraction`).
+    pub fn with_casualty_elf(mut self, casualty_elf: f64) -> Self {
+        self.casualty_elf = casualty_elf;
+        self
+    }
+
+    /// Write, cap or load risks by their line (see `LineAppetite`).
+    pub fn with_appetite(mut self, appetite: Vec<LineAppetite>) -> Self {
+        self.appetite = appetite;
+        self
+    }
+
+    /// The appetite entry for `risk`'s line, if the insurer has one.
+    fn appetite_for(&self, risk: &Risk) -> Option<&LineAppetite> {
+        let line = risk.line();
+        self.appetite.iter().find(|a| a.line == line)
+    }
+
+    /// True if `risk` is on a line this insurer does not write.
+    fn outside_appetite(&self, risk: &Risk) -> bool {
+        self.appetite_for(risk).is_some_and(|a| !a.write)
+    }
+
+    /// Largest share of `risk` the appetite for its line allows.
+    fn appetite_share(&self, risk: &Risk) -> f64 {
+        self.appetite_for(risk).and_then(|a| a.target_share).unwrap_or(1.0)
+    }
+
+    /// Premium multiplier for `risk`: `1 + loading` of its line.
+    fn appetite_loading(&self, risk: &Risk) -> f64 {
+        self.appetite_for(risk).map_or(1.0, |a| 1.0 + a.loading)
+    }
+
+    /// Growth targets: set this year's underwriting plan at YearStart. The target is last
+    /// year's written premium grown at `annual_growth`; the undercut is `undercut_per_shortfall`
+    /// × the fraction by which last year missed its own target. No plan without a growth
+    /// objective, while insolvent, or before a year of premium has been written.
+    pub fn set_underwriting_plan(&mut self, year: Year) -> Option<Event> {
+        let growth = self.growth_target.as_ref()?;
+        if self.insolvent || self.last_year_premium == 0 {
+            return None;
+        }
+        let shortfall = if self.premium_target > 0 {
+            (1.0 - self.last_year_premium as f64 / self.premium_target as f64).max(0.0)
+        } else {
+            0.0
+        };
+        self.undercut = (growth.undercut_per_shortfall * shortfall).min(1.0);
+        self.premium_target = (self.last_year_premium as f64 * (1.0 + growth.annual_growth)).round() as u64;
+        Some(Event::UnderwritingPlanSet {
+            insurer_id: self.id,
+            year,
+            prior_premium: self.last_year_premium,
+            target_premium: self.premium_target,
+            undercut: self.undercut,
+        })
+    }
+
+    /// Tie line and cat aggregate limits to the solvency ratio rather than raw capital.
+    pub fn with_solvency(mut self, solvency: Option<SolvencyConfig>) -> Self {
+        self.solvency = solvency;
+        self
+    }
+
+    /// Use the modelled 1-in-200 occurrence loss `rp_200` on the current cat aggregate for the
+    /// SCR cat charge (PML reporting with `use_in_scr`). The ratio carries forward, so the
+    /// charge still follows the aggregate as it grows or runs off until the next report.
+    pub fn set_modelled_pml(&mut self, rp_200: u64) {
+        self.modelled_pml_fraction =
+            (self.cat_aggregate > 0).then(|| rp_200 as f64 / self.cat_aggregate as f64);
+    }
+
+    /// Solvency capital requirement: 1-in-200 loss on the cat aggregate, plus premium risk on
+    /// the larger of this and last year's written premium, plus reserve risk on outstanding
+    /// reserves. None without a solvency config.
+    pub fn scr(&self) -> Option<u64> {
+        let solvency = self.solvency.as_ref()?;
+        let cat = self.cat_aggregate as f64 * self.modelled_pml_fraction.unwrap_or(self.pml_damage_fraction_200);
+        let premium = solvency.premium_risk_factor * self.ytd.premium.max(self.last_year_premium) as f64;
+        let reserve = solvency.reserve_risk_factor * self.outstanding_reserves() as f64;
+        Some((cat + premium + reserve).round() as u64)
+    }
+
+    /// Capital over SCR (infinite while the SCR is zero). None without a solvency config.
+    pub fn solvency_ratio(&self) -> Option<f64> {
+        let scr = self.scr()?;
+        let capital = self.capital.max(0) as f64;
+        Some(if scr == 0 { f64::INFINITY } else { capital / scr as f64 })
+    }
+
+    /// Share of the capital-based limits available at the current solvency ratio: 1.0 at or
+    /// above `target_ratio`, 0.0 at or below `min_ratio`, linear in between. 1.0 without tiering.
+    fn capacity_scale(&self) -> f64 {
+        let (Some(solvency), Some(ratio)) = (self.solvency.as_ref(), self.solvency_ratio()) else {
+            return 1.0;
+        };
+        if ratio >= solvency.target_ratio {
+            1.0
+        } else if solvency.target_ratio <= solvency.min_ratio {
+            0.0
+        } else {
+            ((ratio - solvency.min_ratio) / (solvency.target_ratio - solvency.min_ratio)).clamp(0.0, 1.0)
         }
     }
 
+    /// Largest single-risk claim this insurer will carry: `nlc × capital`, tiered.
+    fn line_limit(&self, nlc: f64) -> f64 {
+        nlc * self.capital.max(0) as f64 * self.capacity_scale()
+    }
+
+    /// Cat aggregate limit: `scf × capital / pml_damage_fraction_200`, tiered.
+    fn cat_limit(&self, scf: f64) -> u64 {
+        (scf * self.capital.max(0) as f64 / self.pml_damage_fraction_200 * self.capacity_scale()) as u64
+    }
+
     /// Returns the insurer's CR sensitivity parameter (for observability).
     pub fn cr_sensitivity(&self) -> f64 { self.cr_sensitivity }
 
@@ -149,11 +472,89 @@ impl Insurer {
     /// Returns the insurer's own combined-ratio EWMA (for tests and observability).
     pub fn own_cr_ewma(&self) -> Option<f64> { self.own_cr_ewma }
 
+    /// Returns the insurer's expense ratio (for portfolio valuation).
+    pub fn expense_ratio(&self) -> f64 { self.expense_ratio }
+
+    /// Returns the insurer's target loss ratio (for observability).
+    pub fn target_loss_ratio(&self) -> f64 { self.target_loss_ratio }
+
+    /// Returns the insurer's profit loading (for observability).
+    pub fn profit_loading(&self) -> f64 { self.profit_loading }
+
+    /// Returns the insurer's view of the cat ELF (for observability).
+    pub fn cat_elf(&self) -> f64 { self.cat_elf }
+
+    /// Expected annual loss cost of a full line on `risk` (cents): the actuarial price before
+    /// the target loss ratio is applied.
+    pub fn expected_loss(&self, risk: &Risk) -> f64 {
+        self.elf(risk) * risk.sum_insured as f64 * risk.layer_factor()
+    }
+
+    /// Expected loss fraction of `risk`'s line: attritional + cat for property, the anchored
+    /// casualty ELF for casualty.
+    fn elf(&self, risk: &Risk) -> f64 {
+        match risk.line() {
+            Line::Property => self.attritional_elf + self.cat_elf,
+            Line::Casualty => self.casualty_elf,
+        }
+    }
+
+    /// Record an incoming quote request and return the underwriting workload: the number of
+    /// requests (including this one) received in the trailing `window_days`.
+    pub fn record_quote_request(&mut self, day: Day, window_days: u64) -> usize {
+        while let Some(&oldest) = self.recent_quote_requests.front() {
+            if oldest.0 + window_days > day.0 {
+                break;
+            }
+            self.recent_quote_requests.pop_front();
+        }
+        self.recent_quote_requests.push_back(day);
+        self.recent_quote_requests.len()
+    }
+
     /// Called at each YearStart. Capital is NOT reset — it persists from prior year.
     pub fn on_year_start(&mut self) {}
 
-    /// Price and issue a lead quote for a risk, or decline if an exposure limit is breached.
-    /// Returns a single `LeadQuoteIssued` or `LeadQuoteDeclined` event.
+    /// Scale the capital benchmark (`initial_capital`) by one year's asset value growth, so the
+    /// distribution floor, depletion signal, recapitalisation target and exit floor keep pace
+    /// with nominal exposure. Without it a flat nominal floor pays out the growth and per-risk
+    /// line capacity falls behind rising sums insured.
+    pub fn index_capital_base(&mut self, growth_factor: f64) {
+        self.initial_capital = (self.initial_capital as f64 * growth_factor).round() as i64;
+    }
+
+    /// Pay a sponsored cat bond's upfront coupon out of capital. Returns the capital left.
+    pub fn pay_cat_bond_premium(&mut self, premium: u64) -> u64 {
+        self.move_capital(CapitalFlowReason::CatBondPremium, (self.capital - premium as i64).max(0) - self.capital);
+        self.capital as u64
+    }
+
+    /// Credit a cat bond payout to capital. Lands ahead of the occurrence's claims, so the
+    /// collateral absorbs loss that would otherwise breach the capital floor. Bonds of insolvent
+    /// sponsors never trigger (`CatBondBook::on_loss_event`), so the sponsor is solvent here.
+    /// Returns the capital after the credit.
+    pub fn recover_cat_bond(&mut self, payout: u64) -> u64 {
+        self.move_capital(CapitalFlowReason::CatBondPayout, self.capital.max(0) + payout as i64 - self.capital);
+        self.capital as u64
+    }
+
+    /// Pay the year's cat XoL premium out of capital. Returns the capital left.
+    pub fn pay_reinsurance_premium(&mut self, premium: u64) -> u64 {
+        self.move_capital(CapitalFlowReason::ReinsurancePremium, (self.capital - premium as i64).max(0) - self.capital);
+        self.capital as u64
+    }
+
+    /// Credit a cat XoL recovery to capital, ahead of the occurrence's claims as for a cat
+    /// bond payout. Returns the capital after the credit.
+    pub fn recover_reinsurance(&mut self, amount: u64) -> u64 {
+        self.move_capital(CapitalFlowReason::ReinsuranceRecovery, self.capital.max(0) + amount as i64 - self.capital);
+        self.capital as u64
+    }
+
+    /// Price and issue a lead quote for a risk, or decline if it is outside appetite or an
+    /// exposure limit is breached.
+    /// Returns a single `LeadQuoteIssued` or `LeadQuoteDeclined` event — or, with partial lines
+    /// enabled and the cat aggregate limit breached, `LeadQuotePartial` for the headroom.
     /// `market_ap_tp_factor`: coordinator-published AP/TP ratio; 1.0 = neutral.
     pub fn on_lead_quote_requested(
         &self,
@@ -163,20 +564,73 @@ impl Insurer {
         risk: &Risk,
         market_ap_tp_factor: f64,
     ) -> Vec<(Day, Event)> {
-        if self.insolvent {
+        self.lead_quote(day, submission_id, insured_id, risk, market_ap_tp_factor, 0)
+    }
+
+    /// Quota rationing: answer a batch of lead requests in the order given (highest priority
+    /// first), each against the cat headroom left after the lines quoted to those ahead of it.
+    /// Once headroom runs out the rest decline (or take a partial line) as they would at the
+    /// aggregate limit, so scarce capacity goes to the front of the queue rather than to
+    /// whichever request was dispatched first.
+    pub fn ration_lead_quotes(
+        &self,
+        day: Day,
+        batch: &[(SubmissionId, InsuredId, Risk)],
+        market_ap_tp_factor: f64,
+    ) -> Vec<(Day, Event)> {
+        let mut reserved = 0u64;
+        let mut events = Vec::with_capacity(batch.len());
+        for (submission_id, insured_id, risk) in batch {
+            for (d, e) in self.lead_quote(day, *submission_id, *insured_id, risk, market_ap_tp_factor, reserved) {
+                if risk.perils_covered.contains(&Peril::WindstormAtlantic)
+                    && let Event::LeadQuoteIssued { line_size, .. } | Event::LeadQuotePartial { line_size, .. } = e
+                {
+                    reserved += (risk.max_claim() as f64 * line_size).round() as u64;
+                }
+                events.push((d, e));
+            }
+        }
+        events
+    }
+
+    /// Cat headroom this insurer has left under its aggregate limit (cents); None without a
+    /// solvency-based limit.
+    pub fn cat_headroom(&self) -> Option<u64> {
+        self.solvency_capital_fraction.map(|scf| self.cat_limit(scf).saturating_sub(self.cat_aggregate))
+    }
+
+    /// `on_lead_quote_requested` with `reserved` of the cat headroom already promised to other
+    /// quotes in the same rationing batch.
+    fn lead_quote(
+        &self,
+        day: Day,
+        submission_id: SubmissionId,
+        insured_id: InsuredId,
+        risk: &Risk,
+        market_ap_tp_factor: f64,
+        reserved: u64,
+    ) -> Vec<(Day, Event)> {
+        if self.insolvent || self.runoff {
+            let reason = if self.insolvent { DeclineReason::Insolvent } else { DeclineReason::InRunoff };
+            return vec![(
+                day,
+                Event::LeadQuoteDeclined { submission_id, insured_id, insurer_id: self.id, reason },
+            )];
+        }
+        if self.outside_appetite(risk) {
             return vec![(
                 day,
                 Event::LeadQuoteDeclined {
                     submission_id,
                     insured_id,
                     insurer_id: self.id,
-                    reason: DeclineReason::Insolvent,
+                    reason: DeclineReason::OutsideAppetite,
                 },
             )];
         }
         if let Some(nlc) = self.net_line_capacity {
-            let effective_line_limit = (nlc * self.capital.max(0) as f64) as u64;
-            if risk.sum_insured > effective_line_limit {
+            let effective_line_limit = self.line_limit(nlc) as u64;
+            if risk.max_claim() > effective_line_limit {
                 return vec![(
                     day,
                     Event::LeadQuoteDeclined {
@@ -189,11 +643,26 @@ impl Insurer {
             }
         }
         if let Some(scf) = self.solvency_capital_fraction {
-            let effective_cat_limit =
-                (scf * self.capital.max(0) as f64 / self.pml_damage_fraction_200) as u64;
+            let effective_cat_limit = self.cat_limit(scf);
             if risk.perils_covered.contains(&Peril::WindstormAtlantic)
-                && self.cat_aggregate + risk.sum_insured > effective_cat_limit
+                && self.cat_aggregate + reserved + risk.max_claim() > effective_cat_limit
             {
+                if let Some(line_size) = self.partial_line(risk, effective_cat_limit - reserved.min(effective_cat_limit), market_ap_tp_factor)
+                    && !self.territory_limit_breached(risk)
+                {
+                    return vec![(
+                        day,
+                        Event::LeadQuotePartial {
+                            submission_id,
+                            insured_id,
+                            insurer_id: self.id,
+                            atp: self.actuarial_price(risk),
+                            premium: self.underwriter_premium(risk, market_ap_tp_factor),
+                            cat_exposure_at_quote: self.cat_aggregate,
+                            line_size,
+                        },
+                    )];
+                }
                 return vec![(
                     day,
                     Event::LeadQuoteDeclined {
@@ -205,6 +674,17 @@ impl Insurer {
                 )];
             }
         }
+        if self.territory_limit_breached(risk) {
+            return vec![(
+                day,
+                Event::LeadQuoteDeclined {
+                    submission_id,
+                    insured_id,
+                    insurer_id: self.id,
+                    reason: DeclineReason::TerritoryAggregateBreached,
+                },
+            )];
+        }
         let atp = self.actuarial_price(risk);
         let premium = self.underwriter_premium(risk, market_ap_tp_factor);
         let cat_exposure_at_quote = if risk.perils_covered.contains(&Peril::WindstormAtlantic) {
@@ -231,12 +711,14 @@ impl Insurer {
     ///
     /// Followers write at `lead_premium` (no independent pricing); the only gating checks are:
     /// 1. Insolvency
-    /// 2. Net line capacity (single-risk exposure limit)
-    /// 3. Cat aggregate (portfolio concentration limit)
-    /// 4. TP check: if `lead_premium < own_tp` → `RateBelowTP`
+    /// 2. Appetite: a line the insurer does not write → `OutsideAppetite`
+    /// 3. Net line capacity (single-risk exposure limit)
+    /// 4. Cat aggregate (portfolio concentration limit)
+    /// 5. TP check: if `lead_premium < own_tp` → `RateBelowTP`
     ///
-    /// If all checks pass, `FollowerQuoteIssued` is emitted with capacity_line only
-    /// (no `leader_participation_cap` and no `pricing_line` — followers take what they can).
+    /// If all checks pass, `FollowerQuoteIssued` is emitted with capacity_line, capped only by
+    /// the appetite target share (no `leader_participation_cap` and no `pricing_line` —
+    /// followers take what they can).
     pub fn on_follower_quote_requested(
         &self,
         day: Day,
@@ -248,20 +730,27 @@ impl Insurer {
     ) -> Vec<(Day, Event)> {
         use crate::events::{DeclineReason, Event};
 
-        if self.insolvent {
+        if self.insolvent || self.runoff {
+            let reason = if self.insolvent { DeclineReason::Insolvent } else { DeclineReason::InRunoff };
+            return vec![(
+                day,
+                Event::FollowerQuoteDeclined { submission_id, insured_id, insurer_id: self.id, reason },
+            )];
+        }
+        if self.outside_appetite(risk) {
             return vec![(
                 day,
                 Event::FollowerQuoteDeclined {
                     submission_id,
                     insured_id,
                     insurer_id: self.id,
-                    reason: DeclineReason::Insolvent,
+                    reason: DeclineReason::OutsideAppetite,
                 },
             )];
         }
         if let Some(nlc) = self.net_line_capacity {
-            let effective_line_limit = (nlc * self.capital.max(0) as f64) as u64;
-            if risk.sum_insured > effective_line_limit {
+            let effective_line_limit = self.line_limit(nlc) as u64;
+            if risk.max_claim() > effective_line_limit {
                 return vec![(
                     day,
                     Event::FollowerQuoteDeclined {
@@ -274,10 +763,9 @@ impl Insurer {
             }
         }
         if let Some(scf) = self.solvency_capital_fraction {
-            let effective_cat_limit =
-                (scf * self.capital.max(0) as f64 / self.pml_damage_fraction_200) as u64;
+            let effective_cat_limit = self.cat_limit(scf);
             if risk.perils_covered.contains(&Peril::WindstormAtlantic)
-                && self.cat_aggregate + risk.sum_insured > effective_cat_limit
+                && self.cat_aggregate + risk.max_claim() > effective_cat_limit
             {
                 return vec![(
                     day,
@@ -290,8 +778,20 @@ impl Insurer {
                 )];
             }
         }
+        if self.territory_limit_breached(risk) {
+            return vec![(
+                day,
+                Event::FollowerQuoteDeclined {
+                    submission_id,
+                    insured_id,
+                    insurer_id: self.id,
+                    reason: DeclineReason::TerritoryAggregateBreached,
+                },
+            )];
+        }
         // TP check: follower only participates if the lead's rate ≥ own Technical Premium.
-        let own_tp = (self.actuarial_price(risk) as f64 * (1.0 + self.profit_loading)).round() as u64;
+        let own_tp =
+            (self.actuarial_price(risk) as f64 * (1.0 + self.profit_loading) * self.appetite_loading(risk)).round() as u64;
         if lead_premium < own_tp {
             return vec![(
                 day,
@@ -303,14 +803,15 @@ impl Insurer {
                 },
             )];
         }
-        // Followers write at capacity only; no leader_participation_cap, no pricing_line.
+        // Followers write at capacity (within appetite); no leader_participation_cap, no pricing_line.
         let line_size = if let Some(nlc) = self.net_line_capacity {
-            (nlc * self.capital.max(0) as f64 / risk.sum_insured as f64)
+            (self.line_limit(nlc) / risk.max_claim() as f64)
                 .min(1.0)
                 .max(0.0)
         } else {
             1.0
-        };
+        }
+        .min(self.appetite_share(risk));
         vec![(
             day,
             Event::FollowerQuoteIssued {
@@ -322,18 +823,72 @@ impl Insurer {
         )]
     }
 
+    /// Handle `RenewalOffered`: the expiring follower runs the follower checks at the renewal
+    /// premium. It re-signs at most its expiring line (`FollowerQuoteIssued`) or answers
+    /// `RenewalDeclined` with the follower decline reason.
+    pub fn on_renewal_offered(
+        &self,
+        day: Day,
+        submission_id: SubmissionId,
+        insured_id: InsuredId,
+        risk: &Risk,
+        lead_premium: u64,
+        expiring_line: f64,
+    ) -> Vec<(Day, Event)> {
+        self.on_follower_quote_requested(day, submission_id, insured_id, risk, lead_premium, 0)
+            .into_iter()
+            .map(|(d, e)| match e {
+                Event::FollowerQuoteIssued { submission_id, insured_id, insurer_id, line_size } => {
+                    (d, Event::FollowerQuoteIssued {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        line_size: line_size.min(expiring_line),
+                    })
+                }
+                Event::FollowerQuoteDeclined { submission_id, insured_id, insurer_id, reason } => {
+                    (d, Event::RenewalDeclined { submission_id, insured_id, insurer_id, reason })
+                }
+                other => (d, other),
+            })
+            .collect()
+    }
+
+    /// Lead line that still fits under `effective_cat_limit`: the headroom as a share of the
+    /// risk's largest claim, capped by the usual lead line. None when partial lines are off or
+    /// the line would be below `min_line`.
+    fn partial_line(&self, risk: &Risk, effective_cat_limit: u64, market_ap_tp_factor: f64) -> Option<f64> {
+        let config = self.partial_lines.as_ref()?;
+        let headroom = effective_cat_limit.saturating_sub(self.cat_aggregate) as f64 / risk.max_claim() as f64;
+        let line_size = headroom.min(self.compute_line_size(risk, market_ap_tp_factor, true));
+        (line_size > 0.0 && line_size >= config.min_line).then_some(line_size)
+    }
+
+    /// True if writing a full line on `risk` would take its territory's 1-in-200 cat loss
+    /// above that territory's share of capital.
+    fn territory_limit_breached(&self, risk: &Risk) -> bool {
+        let Some(limits) = &self.territory_limits else { return false };
+        if !risk.perils_covered.contains(&Peril::WindstormAtlantic) {
+            return false;
+        }
+        let fraction = self.territory_fractions.get(risk.territory.0 as usize).copied();
+        let limit = fraction.unwrap_or(limits.pml_capital_fraction) * self.capital.max(0) as f64
+            / self.territory_pml_damage_fraction_200;
+        let aggregate = self.territory_aggregates.get(&risk.territory).copied().unwrap_or(0);
+        (aggregate + risk.max_claim()) as f64 > limit
+    }
+
     /// Compute the fractional line this insurer will write on a risk.
     ///
     /// ```text
-    /// raw_cap       = min(net_line_capacity * capital / sum_insured, 1.0)   (or 1.0 if no limit)
+    /// raw_cap       = min(net_line_capacity * capital * capacity_scale / max_claim, 1.0)   (or 1.0 if no limit)
     /// capacity_line = if is_lead { raw_cap.min(leader_participation_cap) } else { raw_cap }
     /// pricing_line  = clamp((own_ap_tp_factor - floor_factor) / (1 - floor_factor), 0.0, 1.0)
-    /// line_size     = min(capacity_line, pricing_line)
+    /// line_size     = min(capacity_line, pricing_line, appetite target_share)
     /// ```
     fn compute_line_size(&self, risk: &Risk, market_ap_tp_factor: f64, is_lead: bool) -> f64 {
         let raw_cap = if let Some(nlc) = self.net_line_capacity {
-            let dollar_limit = nlc * self.capital.max(0) as f64;
-            (dollar_limit / risk.sum_insured as f64).min(1.0).max(0.0)
+            (self.line_limit(nlc) / risk.max_claim() as f64).min(1.0).max(0.0)
         } else {
             1.0
         };
@@ -350,11 +905,12 @@ impl Insurer {
             ((own_factor - self.floor_factor) / (1.0 - self.floor_factor)).clamp(0.0, 1.0)
         };
 
-        capacity_line.min(pricing_line)
+        capacity_line.min(pricing_line).min(self.appetite_share(risk))
     }
 
     /// A policy has been bound. Credit this insurer's share of the net premium to capital,
     /// accumulate written exposure for EWMA; update cat aggregate scaled by line_share.
+    /// Full-value cover; see `on_risk_bound` for policies with a deductible or limit.
     pub fn on_policy_bound(
         &mut self,
         policy_id: PolicyId,
@@ -363,30 +919,212 @@ impl Insurer {
         perils: &[Peril],
         line_share: f64,
     ) {
+        let priced_exposure = if perils.iter().any(|p| p.line() == Line::Property) { sum_insured as f64 } else { 0.0 };
+        self.book_binding(policy_id, priced_exposure, sum_insured, premium, perils, line_share);
+    }
+
+    /// A policy with layer terms has been bound. Written exposure is the layer's share of
+    /// value (`sum_insured × layer_factor`), so the attritional EWMA keeps estimating a
+    /// ground-up ELF that `actuarial_price` re-applies the layer credit to; the cat aggregate
+    /// grows by the largest possible claim rather than the full value, in total and for the
+    /// risk's territory.
+    pub fn on_risk_bound(&mut self, policy_id: PolicyId, risk: &Risk, premium: u64, line_share: f64) {
+        let priced_exposure = Self::priced_exposure(risk);
+        self.book_binding(
+            policy_id,
+            priced_exposure,
+            risk.max_claim(),
+            premium,
+            &risk.perils_covered,
+            line_share,
+        );
+        if let Some(&aggregate_share) = self.cat_policy_map.get(&policy_id) {
+            *self.territory_aggregates.entry(risk.territory).or_insert(0) += aggregate_share;
+            self.cat_policy_territory.insert(policy_id, risk.territory);
+        }
+    }
+
+    /// Written exposure behind the attritional EWMA: the layer's share of value on property, none
+    /// on casualty (priced at its own anchored ELF).
+    fn priced_exposure(risk: &Risk) -> f64 {
+        match risk.line() {
+            Line::Property => risk.sum_insured as f64 * risk.layer_factor(),
+            Line::Casualty => 0.0,
+        }
+    }
+
+    fn book_binding(
+        &mut self,
+        policy_id: PolicyId,
+        priced_exposure: f64,
+        max_claim: u64,
+        premium: u64,
+        perils: &[Peril],
+        line_share: f64,
+    ) {
+        self.book_premium(policy_id, priced_exposure, premium, line_share);
+        if perils.contains(&Peril::WindstormAtlantic) {
+            let aggregate_share = (max_claim as f64 * line_share).round() as u64;
+            self.cat_aggregate += aggregate_share;
+            self.cat_policy_map.insert(policy_id, aggregate_share);
+        }
+    }
+
+    /// Multi-year terms: a bound policy's annual instalment has been collected at its
+    /// anniversary. Books premium and a year of written exposure like a binding; the cat
+    /// aggregate is already held for the whole term.
+    pub fn on_policy_anniversary(&mut self, policy_id: PolicyId, risk: &Risk, premium: u64, line_share: f64) {
+        self.book_premium(policy_id, Self::priced_exposure(risk), premium, line_share);
+    }
+
+    fn book_premium(&mut self, policy_id: PolicyId, priced_exposure: f64, premium: u64, line_share: f64) {
         let premium_share = (premium as f64 * line_share).round() as u64;
-        let net_premium = (premium_share as f64 * (1.0 - self.expense_ratio)).round() as i64;
-        self.capital += net_premium;
-        let exposure_share = (sum_insured as f64 * line_share).round() as u64;
-        self.ytd.exposure += exposure_share;
+        if self.payment_terms.is_some() {
+            let receivable = self.premium_receivables.entry(policy_id).or_insert((line_share, 0));
+            receivable.1 += premium;
+        } else {
+            self.move_capital(CapitalFlowReason::Premium, (premium_share as f64 * (1.0 - self.expense_ratio)).round() as i64);
+        }
+        self.ytd.exposure += (priced_exposure * line_share).round() as u64;
         self.ytd.premium += premium_share;
-        if perils.contains(&Peril::WindstormAtlantic) {
-            self.cat_aggregate += exposure_share;
-            self.cat_policy_map.insert(policy_id, exposure_share);
+    }
+
+    /// Cash basis: `amount` of a policy's premium has been received. Credit this insurer's line
+    /// share, net of the expense ratio, to capital (nothing once insolvent). No-op for a policy
+    /// with nothing outstanding on this insurer's book.
+    pub fn on_premium_received(&mut self, policy_id: PolicyId, amount: u64) {
+        let Some((line_share, outstanding)) = self.premium_receivables.get_mut(&policy_id) else {
+            return;
+        };
+        let received = amount.min(*outstanding);
+        let share = (received as f64 * *line_share).round() as u64;
+        *outstanding -= received;
+        if *outstanding == 0 {
+            self.premium_receivables.remove(&policy_id);
+        }
+        if !self.insolvent {
+            self.move_capital(CapitalFlowReason::Premium, (share as f64 * (1.0 - self.expense_ratio)).round() as i64);
         }
     }
 
+    /// Cash basis: this insurer's share of premium written but not yet received.
+    pub fn premium_receivable(&self) -> u64 {
+        self.premium_receivables
+            .values()
+            .map(|&(line_share, outstanding)| (outstanding as f64 * line_share).round() as u64)
+            .sum()
+    }
+
     /// A policy has expired. Release its WindstormAtlantic aggregate contribution.
     pub fn on_policy_expired(&mut self, policy_id: PolicyId) {
         if let Some(sum_insured) = self.cat_policy_map.remove(&policy_id) {
             self.cat_aggregate = self.cat_aggregate.saturating_sub(sum_insured);
+            self.release_territory_aggregate(policy_id, sum_insured);
+        }
+    }
+
+    fn release_territory_aggregate(&mut self, policy_id: PolicyId, aggregate: u64) {
+        if let Some(territory) = self.cat_policy_territory.remove(&policy_id)
+            && let Some(t) = self.territory_aggregates.get_mut(&territory)
+        {
+            *t = t.saturating_sub(aggregate);
+        }
+    }
+
+    /// Portfolio transfer: hand this insurer's lines on `policy_ids` to a buyer. Releases their
+    /// cat aggregate and returns it per policy, with its territory, for `assume_policies`.
+    pub fn cede_policies(&mut self, policy_ids: &[PolicyId]) -> Vec<(PolicyId, u64, Option<Territory>)> {
+        policy_ids
+            .iter()
+            .filter_map(|&id| {
+                let aggregate = self.cat_policy_map.remove(&id)?;
+                self.cat_aggregate = self.cat_aggregate.saturating_sub(aggregate);
+                let territory = self.cat_policy_territory.get(&id).copied();
+                self.release_territory_aggregate(id, aggregate);
+                Some((id, aggregate, territory))
+            })
+            .collect()
+    }
+
+    /// Portfolio transfer: take on lines ceded by another insurer, adding their cat aggregate
+    /// (merged with any line this insurer already writes on the same policy).
+    pub fn assume_policies(&mut self, ceded: &[(PolicyId, u64, Option<Territory>)]) {
+        for (id, aggregate, territory) in ceded {
+            *self.cat_policy_map.entry(*id).or_insert(0) += aggregate;
+            self.cat_aggregate += aggregate;
+            if let Some(territory) = territory {
+                *self.territory_aggregates.entry(*territory).or_insert(0) += aggregate;
+                self.cat_policy_territory.insert(*id, *territory);
+            }
+        }
+    }
+
+    /// A policy on this insurer's book was cancelled for non-payment. Charge this insurer's
+    /// share of the uncollected premium against capital, net of the expense ratio (brokerage on
+    /// uncollected premium is clawed back), and release the policy's cat aggregate.
+    /// Reversed premium also comes off YTD written premium; bad debt is an expense and does not.
+    /// On a cash basis the uncollected premium was never credited: it is written off the
+    /// receivable instead of being charged to capital.
+    pub fn on_policy_cancelled(
+        &mut self,
+        day: Day,
+        policy_id: PolicyId,
+        premium_reversed: u64,
+        bad_debt: u64,
+        line_share: f64,
+    ) -> Vec<(Day, Event)> {
+        self.on_policy_expired(policy_id);
+        let reversed_share = (premium_reversed as f64 * line_share).round() as u64;
+        let bad_debt_share = (bad_debt as f64 * line_share).round() as u64;
+        self.ytd.premium = self.ytd.premium.saturating_sub(reversed_share);
+        if self.payment_terms.is_some() {
+            if let Some((_, outstanding)) = self.premium_receivables.get_mut(&policy_id) {
+                *outstanding = outstanding.saturating_sub(premium_reversed + bad_debt);
+                if *outstanding == 0 {
+                    self.premium_receivables.remove(&policy_id);
+                }
+            }
+            return vec![];
         }
+        let charge = ((reversed_share + bad_debt_share) as f64 * (1.0 - self.expense_ratio)).round() as i64;
+        self.move_capital(CapitalFlowReason::PremiumReversal, -charge.min(self.capital.max(0))); // floors at 0
+
+        if charge > 0 && self.capital == 0 && !self.insolvent {
+            self.insolvent = true;
+            vec![(day, Event::InsurerInsolvent { insurer_id: self.id })]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Apply the year's market-wide investment return to capital. Returns are earned on
+    /// positive capital only; a crash-year loss that exhausts capital makes the insurer insolvent
+    /// independently of underwriting. Insolvent insurers hold no invested assets.
+    pub fn on_investment_return(&mut self, day: Day, rate: f64) -> Vec<(Day, Event)> {
+        if self.insolvent {
+            return vec![];
+        }
+        let amount = (self.capital.max(0) as f64 * rate).round() as i64;
+        self.move_capital(CapitalFlowReason::InvestmentIncome, (self.capital + amount).max(0) - self.capital);
+        self.ytd.investment_income += amount;
+        let mut events = vec![(day, Event::InvestmentIncome { insurer_id: self.id, amount })];
+        if amount < 0 && self.capital == 0 {
+            self.insolvent = true;
+            events.push((day, Event::InsurerInsolvent { insurer_id: self.id }));
+        }
+        events
     }
 
-    /// Actuarial channel: (attritional_elf + cat_elf) × sum_insured / target_loss_ratio.
-    /// cat_elf is anchored; attritional_elf drifts via EWMA.
+    /// Actuarial channel: ELF × sum_insured × layer_factor / target_loss_ratio, where the ELF is
+    /// attritional_elf + cat_elf on property and casualty_elf on casualty.
+    /// cat_elf and casualty_elf are anchored; attritional_elf drifts via EWMA. `layer_factor` credits the
+    /// insured's deductible and limit off the exposure curve (1.0 for full coverage). With a
+    /// discount credit the expected loss is divided by (1 + r)^discount_years: cash-flow
+    /// underwriting, cheaper long-tail cover when rates are high.
     fn actuarial_price(&self, risk: &Risk) -> u64 {
-        let elf = self.attritional_elf + self.cat_elf;
-        (elf * risk.sum_insured as f64 / self.target_loss_ratio).round() as u64
+        let elf = self.elf(risk);
+        let discount = (1.0 + self.interest_rate.max(-0.99)).powf(-self.discount_years);
+        (elf * risk.sum_insured as f64 * risk.layer_factor() * discount / self.target_loss_ratio).round() as u64
     }
 
     /// Blend market factor with per-insurer capital state and loss history.
@@ -398,7 +1136,12 @@ impl Insurer {
     ///
     /// `credibility = min(own_years / 5, 1.0)`
     /// `market_weight = max(1 − credibility, market_weight_floor)`
+    ///
+    /// Always 1.0 under `PricingMode::Frozen`.
     fn own_ap_tp_factor(&self, market_factor: f64) -> f64 {
+        if self.pricing_mode == PricingMode::Frozen {
+            return 1.0;
+        }
         let credibility = (self.own_years as f64 / 5.0).min(1.0);
         let market_weight = (1.0 - credibility).max(self.market_weight_floor);
 
@@ -434,10 +1177,19 @@ impl Insurer {
     }
 
     /// Underwriter channel: TP × own_ap_tp_factor (blend of market signal and own state).
-    /// TP = ATP × (1 + profit_loading) — the per-insurer Technical Premium.
-    fn underwriter_premium(&self, risk: &Risk, market_ap_tp_factor: f64) -> u64 {
-        let tp = self.actuarial_price(risk) as f64 * (1.0 + self.profit_loading);
-        (tp * self.own_ap_tp_factor(market_ap_tp_factor)).round() as u64
+    /// TP = ATP × (1 + profit_loading) × the appetite loading of the risk's lines — the
+    /// per-insurer Technical Premium.
+    /// An insurer behind its growth target cuts this by `undercut`, down to at most
+    /// `price_floor` × ATP; a premium already below the floor is left where it is.
+    pub fn underwriter_premium(&self, risk: &Risk, market_ap_tp_factor: f64) -> u64 {
+        let atp = self.actuarial_price(risk) as f64;
+        let premium = atp * (1.0 + self.profit_loading) * self.appetite_loading(risk) * self.own_ap_tp_factor(market_ap_tp_factor);
+        match self.growth_target.as_ref() {
+            Some(growth) if self.undercut > 0.0 && self.pricing_mode != PricingMode::Frozen => {
+                (premium * (1.0 - self.undercut)).max(atp * growth.price_floor).min(premium).round() as u64
+            }
+            _ => premium.round() as u64,
+        }
     }
 
     /// Deduct a settled claim from capital (floored at zero).
@@ -445,9 +1197,85 @@ impl Insurer {
     /// because cat_elf is anchored and not updated from experience.
     /// Returns `InsurerInsolvent` on the first crossing to zero; empty otherwise.
     pub fn on_claim_settled(&mut self, day: Day, amount: u64, peril: Peril) -> Vec<(Day, Event)> {
-        let payable = amount.min(self.capital.max(0) as u64);
-        self.capital -= payable as i64; // floors at 0 naturally
-        if peril == Peril::Attritional {
+        self.book_incurred(day, amount as i64, peril)
+    }
+
+    /// Claims development: a claim has been reported. Establish a case reserve of
+    /// `amount × initial_reserve_ratio` and charge it to capital (incurred basis).
+    pub fn on_claim_reported(
+        &mut self,
+        day: Day,
+        claim_id: ClaimId,
+        amount: u64,
+        peril: Peril,
+        initial_reserve_ratio: f64,
+    ) -> Vec<(Day, Event)> {
+        let reserve = (amount as f64 * initial_reserve_ratio).round() as u64;
+        let mut events = vec![(day, Event::ReserveEstablished { claim_id, insurer_id: self.id, reserve })];
+        let before = self.capital;
+        events.extend(self.book_incurred(day, reserve as i64, peril));
+        let charged = (before - self.capital) as u64;
+        self.open_claims.insert(claim_id, OpenClaim { initial_reserve: reserve, outstanding: reserve, paid: 0, charged });
+        events
+    }
+
+    /// Claims development: an instalment has been paid. The outstanding reserve is re-estimated
+    /// Bornhuetter–Ferguson style as `initial_reserve × (1 − cumulative_paid_fraction)`; any
+    /// payment beyond the reserve it consumes is strengthening, any shortfall a release. The
+    /// movement flows through capital and the YTD claims that drive the pricing EWMAs. It is
+    /// measured against what the claim has actually charged, so a claim booked short while
+    /// capital was exhausted never releases more than it took.
+    pub fn on_claim_paid(
+        &mut self,
+        day: Day,
+        claim_id: ClaimId,
+        amount: u64,
+        peril: Peril,
+        cumulative_paid_fraction: f64,
+    ) -> Vec<(Day, Event)> {
+        let Some(claim) = self.open_claims.get_mut(&claim_id) else {
+            return vec![];
+        };
+        let remaining = (1.0 - cumulative_paid_fraction).max(0.0);
+        claim.outstanding = (claim.initial_reserve as f64 * remaining).round() as u64;
+        claim.paid += amount;
+        let movement = (claim.paid + claim.outstanding) as i64 - claim.charged as i64;
+        let before = self.capital;
+        let events = self.book_incurred(day, movement, peril);
+        let charged = before - self.capital;
+        if cumulative_paid_fraction >= 1.0 {
+            self.open_claims.remove(&claim_id);
+        } else if let Some(claim) = self.open_claims.get_mut(&claim_id) {
+            claim.charged = (claim.charged as i64 + charged) as u64;
+        }
+        events
+    }
+
+    /// Total outstanding case reserves across open claims.
+    pub fn outstanding_reserves(&self) -> u64 {
+        self.open_claims.values().map(|c| c.outstanding).sum()
+    }
+
+    /// Charge an incurred movement to capital and YTD claims. Positive = loss (capped at
+    /// remaining capital); negative = reserve release (restores capital, reduces YTD claims).
+    /// An insolvent insurer's releases go to the claims it already left unpaid, not to capital.
+    /// Emits `InsurerInsolvent` the first time capital reaches zero.
+    fn book_incurred(&mut self, day: Day, incurred: i64, peril: Peril) -> Vec<(Day, Event)> {
+        if incurred < 0 {
+            if self.insolvent {
+                return vec![];
+            }
+            let release = incurred.unsigned_abs();
+            self.move_capital(CapitalFlowReason::ReserveRelease, release as i64);
+            if Self::is_burning_cost(peril) {
+                self.ytd.attritional_claims = self.ytd.attritional_claims.saturating_sub(release);
+            }
+            self.ytd.total_claims = self.ytd.total_claims.saturating_sub(release);
+            return vec![];
+        }
+        let payable = (incurred as u64).min(self.capital.max(0) as u64);
+        self.move_capital(CapitalFlowReason::Claim, -(payable as i64)); // floors at 0 naturally
+        if Self::is_burning_cost(peril) {
             self.ytd.attritional_claims += payable;
         }
         self.ytd.total_claims += payable;
@@ -460,11 +1288,35 @@ impl Insurer {
         }
     }
 
+    /// Claims that feed the attritional burning cost: non-cat property perils. Casualty claims
+    /// stay out, so the property rate does not move with casualty reserving.
+    fn is_burning_cost(peril: Peril) -> bool {
+        !peril.is_cat() && peril.line() == Line::Property
+    }
+
     /// Update attritional_elf via EWMA from this year's realized attritional burning cost,
     /// then reset YTD accumulators. cat_elf is never updated. No-op if no exposure written.
     /// Also detects "zombie" state: capital > 0 but max_line < min_sum_insured — the insurer
     /// can no longer write any new business. Marks it insolvent and emits InsurerInsolvent.
+    /// Under an expense split the fixed overhead is charged first and reported in `ExpensesPaid`;
+    /// it counts in the own CR and against the year's distributable profit.
     pub fn on_year_end(&mut self, day: Day, min_sum_insured: u64) -> Vec<(Day, Event)> {
+        let mut events: Vec<(Day, Event)> = vec![];
+        let mut overhead = 0;
+        if let Some(e) = self.expenses.as_ref().filter(|_| !self.insolvent) {
+            overhead = e.annual_overhead.min(self.capital.max(0) as u64); // floors capital at 0
+            self.move_capital(CapitalFlowReason::Expense, -(overhead as i64));
+            events.push((day, Event::ExpensesPaid {
+                insurer_id: self.id,
+                acquisition: (self.ytd.premium as f64 * self.expense_ratio).round() as u64,
+                overhead,
+            }));
+            if overhead > 0 && self.capital == 0 {
+                self.insolvent = true;
+                events.push((day, Event::InsurerInsolvent { insurer_id: self.id }));
+            }
+        }
+
         // Volume weight: scale EWMA updates by current-year book size relative to the historical
         // norm. Prevents a brief period of low volume (e.g., post-cat market exit by competitors
         // forcing this insurer to also write fewer policies) from producing enormous EWMA swings
@@ -478,15 +1330,17 @@ impl Insurer {
         if self.ytd.exposure > 0 {
             // Update exposure norm using prior vol_weight reference (before this year's data).
             self.exposure_ewma = 0.3 * self.ytd.exposure as f64 + 0.7 * self.exposure_ewma;
-            let realized_att_lf = self.ytd.attritional_loss_fraction();
-            let effective_alpha = self.ewma_credibility * vol_weight;
-            self.attritional_elf = effective_alpha * realized_att_lf
-                + (1.0 - effective_alpha) * self.attritional_elf;
+            if self.pricing_mode == PricingMode::Adaptive {
+                let realized_att_lf = self.ytd.attritional_loss_fraction();
+                let effective_alpha = self.ewma_credibility * vol_weight;
+                self.attritional_elf = effective_alpha * realized_att_lf
+                    + (1.0 - effective_alpha) * self.attritional_elf;
+            }
         }
         // Accumulate per-insurer combined ratio into EWMA for own CR pricing signal.
         if self.ytd.premium > 0 {
             let own_lr = self.ytd.total_claims as f64 / self.ytd.premium as f64;
-            let own_cr = own_lr + self.expense_ratio;
+            let own_cr = own_lr + self.expense_ratio + overhead as f64 / self.ytd.premium as f64;
             let effective_alpha = OWN_CR_EWMA_ALPHA * vol_weight;
             self.own_cr_ewma = Some(match self.own_cr_ewma {
                 // First year: blend toward neutral (1.0) on low volume; full weight when vol_weight=1.
@@ -499,7 +1353,7 @@ impl Insurer {
         // Distribute fraction of annual underwriting profit to Names.
         // net_written = ytd.premium × (1 - expense_ratio) — expenses already deducted at bind,
         // so this reconstructs the net capital credited from this year's written business.
-        // year_profit = net_written − ytd.total_claims; floor at zero via saturating_sub.
+        // year_profit = net_written − ytd.total_claims − overhead + investment income; floored at zero.
         //
         // Capital floor: under Solvency II, distributions are prohibited if they would breach
         // the SCR. We proxy this with initial_capital — distributions are only paid when the
@@ -507,15 +1361,16 @@ impl Insurer {
         // capital has been eroded by losses retains profits to rebuild rather than paying them
         // out. This matches Lloyd's practice: profit release requires that all liabilities are
         // provided for and that the member's FAL remains above the ECA floor.
-        let mut events: Vec<(Day, Event)> = vec![];
         if !self.insolvent && self.payout_ratio > 0.0 {
             let net_written = (self.ytd.premium as f64 * (1.0 - self.expense_ratio)).round() as u64;
-            let year_profit = net_written.saturating_sub(self.ytd.total_claims);
+            let year_profit = (net_written as i64 - self.ytd.total_claims as i64 - overhead as i64
+                + self.ytd.investment_income)
+                .max(0) as u64;
             if year_profit > 0 {
                 let distributable = (year_profit as f64 * self.payout_ratio).round() as u64;
                 let distribution_floor = (self.initial_capital as f64 * self.distribution_floor_multiple).round() as i64;
                 if distributable > 0 && self.capital - distributable as i64 >= distribution_floor {
-                    self.capital -= distributable as i64;
+                    self.move_capital(CapitalFlowReason::Distribution, -(distributable as i64));
                     events.push((day, Event::CapitalDistributed {
                         insurer_id: self.id,
                         amount: distributable,
@@ -531,8 +1386,19 @@ impl Insurer {
             initial_capital: self.initial_capital.max(0) as u64,
             ytd_premium: self.ytd.premium,
             ytd_claims: self.ytd.total_claims,
+            unearned_premium: self.unearned_premium,
         }));
+        if let Some(scr) = self.scr().filter(|&scr| scr > 0) {
+            let capital = self.capital.max(0) as u64;
+            events.push((day, Event::SolvencyRatioReported {
+                insurer_id: self.id,
+                capital,
+                scr,
+                solvency_ratio: capital as f64 / scr as f64,
+            }));
+        }
 
+        self.last_year_premium = self.ytd.premium;
         self.ytd.reset();
 
         // Zombie check: capital > 0 but max_line < min writeable policy size.
@@ -551,27 +1417,110 @@ impl Insurer {
 
         events
     }
+
+    /// Whether investors would recapitalise this insurer: it is still writing, its capital has
+    /// fallen by at least `depletion_threshold` of initial capital, and its own CR EWMA is no
+    /// worse than `max_cr` (no own history counts as acceptable).
+    pub fn is_recap_candidate(&self, config: &RecapitalisationConfig) -> bool {
+        if self.insolvent || self.runoff || self.initial_capital <= 0 {
+            return false;
+        }
+        let depletion = 1.0 - self.capital as f64 / self.initial_capital as f64;
+        depletion >= config.depletion_threshold && self.own_cr_ewma.is_none_or(|cr| cr <= config.max_cr)
+    }
+
+    /// Raise `recap_fraction` of the shortfall to initial capital. `cost_of_capital` of the raise
+    /// is lost to the issuance discount; the rest is credited to capital. Returns `CapitalRaised`,
+    /// or nothing if there is no shortfall.
+    pub fn raise_capital(&mut self, day: Day, config: &RecapitalisationConfig) -> Vec<(Day, Event)> {
+        let shortfall = (self.initial_capital - self.capital).max(0) as f64;
+        let amount = (shortfall * config.recap_fraction).round() as u64;
+        if amount == 0 {
+            return vec![];
+        }
+        let cost = (amount as f64 * config.cost_of_capital).round() as u64;
+        self.move_capital(CapitalFlowReason::CapitalRaise, amount.saturating_sub(cost) as i64);
+        vec![(day, Event::CapitalRaised {
+            insurer_id: self.id,
+            amount,
+            cost,
+            remaining_capital: self.capital.max(0) as u64,
+        })]
+    }
+
+    /// Voluntary run-off, evaluated at YearEnd after `on_year_end` has updated the trailing CR.
+    /// A writing insurer enters run-off when capital falls below `capital_exit_floor` × initial
+    /// capital or its own CR EWMA exceeds `runoff_cr_threshold`. A run-off insurer re-enters once
+    /// the market AP/TP factor reaches `reentry_ap_tp_factor`, its capital is back above the
+    /// floor, and `reentry_draw` (uniform on [0, 1)) falls below `reentry_probability`.
+    /// Re-entry clears the own-CR history: the signal that forced the exit is stale and would
+    /// otherwise trigger an immediate second exit. Insolvent insurers never change state.
+    pub fn evaluate_runoff(
+        &mut self,
+        day: Day,
+        config: &RunoffConfig,
+        market_ap_tp_factor: f64,
+        reentry_draw: f64,
+    ) -> Vec<(Day, Event)> {
+        if self.insolvent {
+            return vec![];
+        }
+        let capital_floor = (self.initial_capital as f64 * config.capital_exit_floor).round() as i64;
+        let capital = self.capital.max(0) as u64;
+        if !self.runoff {
+            let reason = if self.capital < capital_floor {
+                ExitReason::CapitalFloor
+            } else if self.own_cr_ewma.is_some_and(|cr| cr > config.runoff_cr_threshold) {
+                ExitReason::AdverseExperience
+            } else {
+                return vec![];
+            };
+            self.runoff = true;
+            return vec![(day, Event::InsurerExited { insurer_id: self.id, reason, capital })];
+        }
+        if market_ap_tp_factor >= config.reentry_ap_tp_factor
+            && self.capital >= capital_floor
+            && reentry_draw < config.reentry_probability
+        {
+            self.runoff = false;
+            self.own_cr_ewma = None;
+            return vec![(day, Event::InsurerReEntered { insurer_id: self.id, capital })];
+        }
+        vec![]
+    }
+
+    /// Withdraw into run-off as marginal capacity in a prolonged soft market. The simulation
+    /// picks which insurers go; this only records the exit. No-op unless writing.
+    pub fn exit_soft_market(&mut self, day: Day) -> Vec<(Day, Event)> {
+        if self.insolvent || self.runoff {
+            return vec![];
+        }
+        self.runoff = true;
+        let capital = self.capital.max(0) as u64;
+        vec![(day, Event::InsurerExited { insurer_id: self.id, reason: ExitReason::SoftMarket, capital })]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ASSET_VALUE;
-    use crate::events::Peril;
+    use crate::config::{ExpenseConfig, PartialLineConfig, PaymentTermsConfig, SolvencyConfig, ASSET_VALUE};
+    use crate::events::{Line, Peril};
 
     fn small_risk() -> Risk {
         Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         }
     }
 
     fn make_insurer(id: InsurerId, capital: i64) -> Insurer {
         // attritional_elf=0.239, cat_elf=0.0, profit_loading=0.0, depletion_sensitivity=0.0
         // depletion_sensitivity=0.0 → no depletion effect; preserves all existing test behaviour.
-        // runoff_cr_threshold=2.0 → never triggers exit in single-year tests.
-        // capital_exit_floor=0.0 → floor always passes.
+        // Run-off is only entered via evaluate_runoff, which these helpers never call.
         // leader_participation_cap=1.0 → no leader cap → preserves existing test behaviour.
         Insurer::new(id, capital, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, None, None, 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0)
     }
@@ -580,8 +1529,10 @@ mod tests {
     fn quote_atp(ins: &Insurer) -> u64 {
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, 1.0);
         let (_, event) = events.into_iter().next().unwrap();
@@ -686,13 +1637,17 @@ mod tests {
         let ins = make_insurer(InsurerId(1), 0);
         let small = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let large = Risk {
             sum_insured: ASSET_VALUE * 10,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE * 10,
         };
         let (_, e_small) =
             first_event(ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &small, 1.0));
@@ -751,16 +1706,20 @@ mod tests {
     fn cat_risk() -> Risk {
         Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: ASSET_VALUE,
         }
     }
 
     fn att_only_risk() -> Risk {
         Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         }
     }
 
@@ -849,6 +1808,178 @@ mod tests {
         );
     }
 
+    #[test]
+    fn renewal_offer_resigns_at_most_the_expiring_line_or_declines_as_a_renewal() {
+        let ins = Insurer::new(InsurerId(1), 10_000_000_000, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, None, None, 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0);
+        let (_, event) = first_event(ins.on_renewal_offered(Day(0), SubmissionId(1), InsuredId(1), &small_risk(), u64::MAX, 0.4));
+        assert!(matches!(event, Event::FollowerQuoteIssued { line_size, .. } if line_size == 0.4), "got {event:?}");
+
+        let (_, event) = first_event(ins.on_renewal_offered(Day(0), SubmissionId(1), InsuredId(1), &small_risk(), 0, 0.4));
+        assert!(matches!(event, Event::RenewalDeclined { reason: DeclineReason::RateBelowTP, .. }), "got {event:?}");
+    }
+
+    #[test]
+    fn solvency_tiering_scales_line_capacity_with_the_ratio() {
+        // Cat SCR per full line = 25M × 0.252 = 6.3M; capital 100M; untiered line limit 30M.
+        // A 25M risk needs a tier of at least 25/30, i.e. a ratio of 1.0 + 5/6 ≈ 1.83.
+        let solvency = SolvencyConfig { premium_risk_factor: 0.0, reserve_risk_factor: 0.0, target_ratio: 2.0, min_ratio: 1.0 };
+        let make = |solvency: Option<SolvencyConfig>| {
+            let mut ins = Insurer::new(InsurerId(1), 10_000_000_000, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, Some(0.30), None, 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0)
+                .with_solvency(solvency);
+            for pid in 0..7 {
+                ins.on_policy_bound(PolicyId(pid), ASSET_VALUE, 0, &[Peril::WindstormAtlantic], 1.0);
+            }
+            ins
+        };
+        let follow = |ins: &Insurer| {
+            first_event(ins.on_follower_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &small_risk(), u64::MAX, 0)).1
+        };
+
+        let mut tiered = make(Some(solvency));
+        let ratio = tiered.solvency_ratio().unwrap();
+        assert!((ratio - 10_000.0 / (7.0 * 630.0)).abs() < 1e-6, "ratio {ratio}");
+        assert!(matches!(follow(&tiered), Event::FollowerQuoteIssued { .. }), "ratio ≈ 2.27 is above target");
+
+        let mut plain = make(None);
+        for ins in [&mut tiered, &mut plain] {
+            for pid in 7..9 {
+                ins.on_policy_bound(PolicyId(pid), ASSET_VALUE, 0, &[Peril::WindstormAtlantic], 1.0);
+            }
+        }
+        // Ratio ≈ 1.76 → tier ≈ 0.76 → line limit ≈ 22.9M < 25M.
+        assert!(matches!(
+            follow(&tiered),
+            Event::FollowerQuoteDeclined { reason: DeclineReason::MaxLineSizeExceeded, .. }
+        ));
+        assert!(matches!(follow(&plain), Event::FollowerQuoteIssued { .. }), "raw capital still allows the line");
+    }
+
+    #[test]
+    fn solvency_ratio_is_reported_at_year_end_only_with_tiering() {
+        let solvency = SolvencyConfig { premium_risk_factor: 0.5, reserve_risk_factor: 0.0, target_ratio: 2.0, min_ratio: 1.0 };
+        let mut ins = make_insurer(InsurerId(1), 1_000_000).with_solvency(Some(solvency));
+        ins.on_policy_bound(PolicyId(1), ASSET_VALUE, 400_000, &[Peril::Attritional], 1.0);
+        let events = ins.on_year_end(Day(359), 0);
+        let reported = events.iter().find_map(|(_, e)| match e {
+            Event::SolvencyRatioReported { scr, solvency_ratio, .. } => Some((*scr, *solvency_ratio)),
+            _ => None,
+        });
+        let (scr, ratio) = reported.expect("SolvencyRatioReported at YearEnd");
+        assert_eq!(scr, 200_000, "premium risk on this year's premium");
+        assert!((ratio - ins.capital as f64 / 200_000.0).abs() < 1e-9);
+        assert_eq!(ins.scr(), Some(200_000), "last year's premium keeps the volume after the reset");
+
+        let mut plain = make_insurer(InsurerId(2), 1_000_000);
+        plain.on_policy_bound(PolicyId(1), ASSET_VALUE, 400_000, &[Peril::Attritional], 1.0);
+        assert!(plain.solvency_ratio().is_none());
+        assert!(!plain.on_year_end(Day(359), 0).iter().any(|(_, e)| matches!(e, Event::SolvencyRatioReported { .. })));
+    }
+
+    #[test]
+    fn expense_split_charges_acquisition_at_bind_and_overhead_whatever_the_volume() {
+        let expenses = ExpenseConfig { acquisition_bps: 2_000, annual_overhead: 300_000 };
+        let mut ins = make_insurer(InsurerId(1), 1_000_000).with_expenses(Some(expenses));
+        ins.on_policy_bound(PolicyId(1), ASSET_VALUE, 400_000, &[Peril::Attritional], 1.0);
+        assert_eq!(ins.capital, 1_320_000, "80% of premium credited after acquisition costs");
+        let events = ins.on_year_end(Day(359), 0);
+        assert!(matches!(
+            events[0].1,
+            Event::ExpensesPaid { acquisition: 80_000, overhead: 300_000, .. }
+        ));
+        assert_eq!(ins.capital, 1_020_000);
+        // CR = 0 claims + 20% acquisition + 300k / 400k overhead.
+        assert!((ins.own_cr_ewma().unwrap() - 0.95).abs() < 1e-9);
+
+        // A year with no business still pays the overhead, until capital runs out.
+        for day in [719, 1079, 1439] {
+            ins.on_year_end(Day(day), 0);
+        }
+        assert_eq!(ins.capital, 120_000);
+        let events = ins.on_year_end(Day(1799), 0);
+        assert!(matches!(events[0].1, Event::ExpensesPaid { acquisition: 0, overhead: 120_000, .. }));
+        assert!(matches!(events[1].1, Event::InsurerInsolvent { .. }));
+        assert!(ins.insolvent);
+        assert!(ins.on_year_end(Day(2159), 0).iter().all(|(_, e)| !matches!(e, Event::ExpensesPaid { .. })));
+    }
+
+    #[test]
+    fn payment_terms_credit_premium_as_it_is_received() {
+        let terms = PaymentTermsConfig { settlement_delay_days: 60 };
+        let mut ins = make_insurer(InsurerId(1), 1_000_000).with_payment_terms(Some(terms));
+        ins.on_policy_bound(PolicyId(1), ASSET_VALUE, 400_000, &[Peril::Attritional], 0.5);
+        assert_eq!(ins.capital, 1_000_000, "nothing credited until cash arrives");
+        assert_eq!(ins.ytd.premium, 200_000, "written premium is booked at binding");
+        assert_eq!(ins.premium_receivable(), 200_000);
+
+        ins.on_premium_received(PolicyId(1), 100_000);
+        assert_eq!(ins.capital, 1_050_000, "line share of the instalment");
+        assert_eq!(ins.premium_receivable(), 150_000);
+        ins.on_premium_received(PolicyId(9), 100_000); // not on this book
+        assert_eq!(ins.capital, 1_050_000);
+
+        // Default on the rest: written off the receivable, never charged to capital.
+        ins.on_policy_cancelled(Day(90), PolicyId(1), 250_000, 50_000, 0.5);
+        assert_eq!(ins.capital, 1_050_000);
+        assert_eq!(ins.premium_receivable(), 0);
+    }
+
+    #[test]
+    fn partial_lines_offer_cat_headroom_instead_of_declining() {
+        // effective_cat = 0.30 × 20B / 0.252 ≈ 23.8B; nine full lines leave ≈ 1.31B of headroom.
+        let make = |min_line: f64| {
+            let mut ins = Insurer::new(InsurerId(1), 20_000_000_000, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, None, Some(0.30), 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0)
+                .with_partial_lines(Some(PartialLineConfig { min_line }));
+            for pid in 0..9 {
+                ins.on_policy_bound(PolicyId(pid), ASSET_VALUE, 0, &[Peril::WindstormAtlantic], 1.0);
+            }
+            ins
+        };
+        let headroom = (0.30 * 20_000_000_000.0 / 0.252) as u64 - 9 * ASSET_VALUE;
+        let risk = cat_risk();
+
+        let (_, event) = first_event(make(0.1).on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, 1.0));
+        match event {
+            Event::LeadQuotePartial { line_size, cat_exposure_at_quote, .. } => {
+                assert!((line_size - headroom as f64 / ASSET_VALUE as f64).abs() < 1e-9, "line = headroom share: {line_size}");
+                assert_eq!(cat_exposure_at_quote, 9 * ASSET_VALUE);
+            }
+            other => panic!("expected LeadQuotePartial, got {other:?}"),
+        }
+
+        let (_, event) = first_event(make(0.6).on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, 1.0));
+        assert!(
+            matches!(event, Event::LeadQuoteDeclined { reason: DeclineReason::MaxCatAggregateBreached, .. }),
+            "headroom below min_line still declines, got {event:?}"
+        );
+    }
+
+    #[test]
+    fn rationed_batch_gives_the_headroom_to_the_front_of_the_queue() {
+        // Limit ≈ 23.8B with 8 full lines bound (20B): headroom for one more 2.5B line.
+        let mut ins = Insurer::new(InsurerId(1), 20_000_000_000, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, None, Some(0.30), 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0);
+        for pid in 0..8 {
+            ins.on_policy_bound(PolicyId(pid), ASSET_VALUE, 0, &[Peril::WindstormAtlantic], 1.0);
+        }
+        assert_eq!(ins.cat_headroom(), Some((0.30 * 20_000_000_000.0 / 0.252) as u64 - 8 * ASSET_VALUE));
+        let batch: Vec<_> = [3, 1, 2].map(|i| (SubmissionId(i), InsuredId(i), cat_risk())).to_vec();
+        let events = ins.ration_lead_quotes(Day(5), &batch, 1.0);
+        let issued: Vec<SubmissionId> = events
+            .iter()
+            .filter_map(|(_, e)| match e {
+                Event::LeadQuoteIssued { submission_id, .. } => Some(*submission_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(issued, vec![SubmissionId(3)], "only the first in priority order fits");
+        assert_eq!(events.len(), 3);
+        assert!(events[1..].iter().all(|(_, e)| matches!(e, Event::LeadQuoteDeclined { reason: DeclineReason::MaxCatAggregateBreached, .. })));
+        // Quoted one at a time, each request would have seen the full headroom.
+        assert!(batch.iter().all(|(s, i, r)| matches!(
+            first_event(ins.on_lead_quote_requested(Day(5), *s, *i, r, 1.0)).1,
+            Event::LeadQuoteIssued { .. }
+        )));
+    }
+
     #[test]
     fn within_limits_after_partial_fill_emits_quote_issued() {
         // capital=200M USD; effective_cat = 0.30 × 20B / 0.252 ≈ 23.8B > 2×ASSET_VALUE=10B → room for second policy.
@@ -863,6 +1994,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn territory_limit_declines_full_territory_but_writes_others() {
+        // capital=100M USD, undiversified pml=0.5 → each territory may hold 0.30 × 10B / 0.5 = 6B,
+        // i.e. two 2.5B lines but not three.
+        let limits = TerritoryLimitConfig {
+            pml_capital_fraction: 0.30,
+            overrides: [("US-Gulf".to_string(), 0.0)].into_iter().collect(),
+        };
+        let mut ins = Insurer::new(InsurerId(1), 10_000_000_000, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, None, None, 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0)
+            .with_territory_limits(Some(limits), 0.5, &TerritoryTable::new(&["US-SE", "EU", "US-Gulf"]));
+        let us_se = cat_risk();
+        ins.on_risk_bound(PolicyId(1), &us_se, 0, 1.0);
+        ins.on_risk_bound(PolicyId(2), &us_se, 0, 1.0);
+        assert_eq!(ins.territory_aggregates[&Territory(0)], 2 * ASSET_VALUE);
+
+        let quote = |ins: &Insurer, risk: &Risk| {
+            first_event(ins.on_lead_quote_requested(Day(0), SubmissionId(3), InsuredId(3), risk, 1.0)).1
+        };
+        assert!(matches!(
+            quote(&ins, &us_se),
+            Event::LeadQuoteDeclined { reason: DeclineReason::TerritoryAggregateBreached, .. }
+        ));
+        let follower = first_event(ins.on_follower_quote_requested(Day(0), SubmissionId(3), InsuredId(3), &us_se, u64::MAX, 0)).1;
+        assert!(matches!(
+            follower,
+            Event::FollowerQuoteDeclined { reason: DeclineReason::TerritoryAggregateBreached, .. }
+        ));
+        let eu = Risk { territory: Territory(1), ..cat_risk() };
+        assert!(matches!(quote(&ins, &eu), Event::LeadQuoteIssued { .. }), "other territories keep their own room");
+        let gulf = Risk { territory: Territory(2), ..cat_risk() };
+        assert!(matches!(
+            quote(&ins, &gulf),
+            Event::LeadQuoteDeclined { reason: DeclineReason::TerritoryAggregateBreached, .. }
+        ), "override closes the territory");
+
+        ins.on_policy_expired(PolicyId(1));
+        assert_eq!(ins.territory_aggregates[&Territory(0)], ASSET_VALUE);
+        assert!(matches!(quote(&ins, &us_se), Event::LeadQuoteIssued { .. }), "expiry frees territory room");
+    }
+
+    // ── Underwriting appetite ─────────────────────────────────────────────────
+
+    fn casualty_risk() -> Risk {
+        Risk { perils_covered: vec![Peril::Casualty], ..small_risk() }
+    }
+
+    #[test]
+    fn appetite_declines_lines_the_insurer_does_not_write() {
+        let closed = |line| LineAppetite { line, write: false, target_share: None, loading: 0.0 };
+        let ins = make_insurer(InsurerId(1), 10_000_000_000).with_casualty_elf(0.02).with_appetite(vec![closed(Line::Casualty)]);
+        let lead = |ins: &Insurer, risk: &Risk| first_event(ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), risk, 1.0)).1;
+        assert!(matches!(lead(&ins, &casualty_risk()), Event::LeadQuoteDeclined { reason: DeclineReason::OutsideAppetite, .. }));
+        let follower = first_event(ins.on_follower_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &casualty_risk(), u64::MAX, 0)).1;
+        assert!(matches!(follower, Event::FollowerQuoteDeclined { reason: DeclineReason::OutsideAppetite, .. }));
+        assert!(matches!(lead(&ins, &small_risk()), Event::LeadQuoteIssued { .. }), "property is still written");
+
+        // Closing property leaves the casualty line open.
+        let casualty_only = make_insurer(InsurerId(1), 10_000_000_000).with_casualty_elf(0.02).with_appetite(vec![closed(Line::Property)]);
+        assert!(matches!(lead(&casualty_only, &small_risk()), Event::LeadQuoteDeclined { reason: DeclineReason::OutsideAppetite, .. }));
+        assert!(matches!(lead(&casualty_only, &casualty_risk()), Event::LeadQuoteIssued { .. }));
+    }
+
+    #[test]
+    fn appetite_caps_the_line_and_loads_the_premium() {
+        let casualty = LineAppetite { line: Line::Casualty, write: true, target_share: Some(0.4), loading: 0.25 };
+        let plain = make_insurer(InsurerId(1), 10_000_000_000).with_casualty_elf(0.02);
+        let keen = make_insurer(InsurerId(1), 10_000_000_000).with_casualty_elf(0.02).with_appetite(vec![casualty]);
+        let lead = |ins: &Insurer, risk: &Risk| {
+            let Event::LeadQuoteIssued { premium, line_size, .. } =
+                first_event(ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), risk, 1.0)).1
+            else {
+                panic!("expected LeadQuoteIssued")
+            };
+            (premium, line_size)
+        };
+        let (base_premium, base_line) = lead(&plain, &casualty_risk());
+        let (premium, line) = lead(&keen, &casualty_risk());
+        assert_eq!(base_line, 1.0);
+        assert_eq!(line, 0.4, "line capped at the target share");
+        assert_eq!(premium, (base_premium as f64 * 1.25).round() as u64);
+        assert_eq!(lead(&keen, &small_risk()), lead(&plain, &small_risk()), "the property line is untouched");
+
+        // Followers sign at most the target share and want the loading on their TP.
+        let follow = |lead_premium| first_event(keen.on_follower_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &casualty_risk(), lead_premium, 0)).1;
+        assert!(matches!(follow(base_premium), Event::FollowerQuoteDeclined { reason: DeclineReason::RateBelowTP, .. }));
+        assert!(matches!(follow(premium), Event::FollowerQuoteIssued { line_size, .. } if line_size == 0.4));
+    }
+
     // ── EWMA experience update ────────────────────────────────────────────────
 
     #[test]
@@ -890,6 +2109,18 @@ mod tests {
         assert!(atp_after < atp_before, "ATP must fall after a 0% LF year: {atp_after} vs {atp_before}");
     }
 
+    #[test]
+    fn frozen_pricing_quotes_technical_premium_and_ignores_experience() {
+        let mut ins = make_insurer(InsurerId(1), ASSET_VALUE as i64 * 10).with_pricing_mode(PricingMode::Frozen);
+        let tp = quote_premium(&ins, 1.0);
+        assert_eq!(quote_premium(&ins, 1.5), tp, "a hard market must not load frozen prices");
+        ins.on_policy_bound(PolicyId(1), ASSET_VALUE, tp, &[Peril::Attritional], 1.0);
+        let _ = ins.on_claim_settled(Day(0), ASSET_VALUE, Peril::Attritional);
+        let _ = ins.on_year_end(Day(0), ASSET_VALUE);
+        assert_eq!(quote_premium(&ins, 1.0), tp, "a 100% loss year must not move a frozen price");
+        assert!(ins.own_cr_ewma().is_some(), "own results are still tracked for run-off decisions");
+    }
+
     #[test]
     fn ewma_formula_matches_exact_calculation() {
         // α=0.3, realized LF = 0.5 (claim = ASSET_VALUE/2, exposure = ASSET_VALUE).
@@ -1108,6 +2339,102 @@ mod tests {
         assert!(matches!(events[0].1, Event::YearEndCapital { .. }));
     }
 
+    // ── Recapitalisation ──────────────────────────────────────────────────────
+
+    fn recap_config() -> RecapitalisationConfig {
+        RecapitalisationConfig {
+            depletion_threshold: 0.3,
+            max_cr: 1.1,
+            recap_probability: 1.0,
+            recap_fraction: 0.5,
+            cost_of_capital: 0.1,
+        }
+    }
+
+    #[test]
+    fn depleted_insurer_with_franchise_raises_part_of_its_shortfall_net_of_cost() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000);
+        ins.capital = 600_000_000;
+        ins.own_cr_ewma = Some(1.05);
+        let config = recap_config();
+        assert!(ins.is_recap_candidate(&config));
+        let events = ins.raise_capital(Day(359), &config);
+        assert!(matches!(
+            events[..],
+            [(Day(359), Event::CapitalRaised { amount: 200_000_000, cost: 20_000_000, remaining_capital: 780_000_000, .. })]
+        ));
+        assert_eq!(ins.capital, 780_000_000);
+    }
+
+    #[test]
+    fn recap_needs_both_depletion_and_an_acceptable_cr() {
+        let config = recap_config();
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000);
+        ins.capital = 800_000_000;
+        assert!(!ins.is_recap_candidate(&config), "20% depletion is below the threshold");
+        ins.capital = 500_000_000;
+        ins.own_cr_ewma = Some(1.4);
+        assert!(!ins.is_recap_candidate(&config), "no franchise value at a 140% CR");
+        ins.own_cr_ewma = Some(0.9);
+        ins.runoff = true;
+        assert!(!ins.is_recap_candidate(&config), "run-off insurers do not raise");
+    }
+
+    // ── Voluntary run-off ─────────────────────────────────────────────────────
+
+    fn runoff_config() -> RunoffConfig {
+        RunoffConfig {
+            runoff_cr_threshold: 1.2,
+            capital_exit_floor: 0.5,
+            reentry_ap_tp_factor: 1.1,
+            reentry_probability: 0.5,
+            soft_market_exit: None,
+        }
+    }
+
+    #[test]
+    fn adverse_experience_enters_runoff_and_keeps_settling_claims() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000);
+        ins.own_cr_ewma = Some(1.5);
+        let events = ins.evaluate_runoff(Day(359), &runoff_config(), 1.0, 0.0);
+        assert!(ins.runoff);
+        assert!(matches!(
+            events[..],
+            [(Day(359), Event::InsurerExited { reason: ExitReason::AdverseExperience, capital: 1_000_000_000, .. })]
+        ));
+
+        let quote = ins.on_lead_quote_requested(Day(400), SubmissionId(1), InsuredId(1), &small_risk(), 1.0);
+        assert!(matches!(quote[..], [(_, Event::LeadQuoteDeclined { reason: DeclineReason::InRunoff, .. })]));
+
+        ins.on_claim_settled(Day(410), 1_000_000, Peril::Attritional);
+        assert_eq!(ins.capital, 999_000_000, "run-off insurer still pays claims on its book");
+    }
+
+    #[test]
+    fn runoff_insurer_reenters_only_when_market_hardens() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000);
+        ins.runoff = true;
+        ins.own_cr_ewma = Some(1.5);
+        let config = runoff_config();
+        assert!(ins.evaluate_runoff(Day(719), &config, 1.0, 0.0).is_empty(), "soft market: stay out");
+        assert!(ins.evaluate_runoff(Day(719), &config, 1.2, 0.9).is_empty(), "draw above reentry_probability");
+        let events = ins.evaluate_runoff(Day(719), &config, 1.2, 0.1);
+        assert!(matches!(events[..], [(_, Event::InsurerReEntered { insurer_id: InsurerId(1), .. })]));
+        assert!(!ins.runoff);
+        assert_eq!(ins.own_cr_ewma(), None, "stale experience must not force a second exit");
+    }
+
+    #[test]
+    fn capital_floor_exit_blocks_reentry_until_recapitalised() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000);
+        ins.capital = 400_000_000;
+        let config = runoff_config();
+        let events = ins.evaluate_runoff(Day(359), &config, 1.0, 0.0);
+        assert!(matches!(events[..], [(_, Event::InsurerExited { reason: ExitReason::CapitalFloor, .. })]));
+        assert!(ins.evaluate_runoff(Day(719), &config, 1.5, 0.0).is_empty());
+        assert!(ins.runoff);
+    }
+
     // ── Heterogeneous experience divergence ───────────────────────────────────
 
     #[test]
@@ -1212,8 +2539,10 @@ mod tests {
     fn quote_premium(ins: &Insurer, market_factor: f64) -> u64 {
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, market_factor);
         let (_, event) = events.into_iter().next().unwrap();
@@ -1363,8 +2692,10 @@ mod tests {
         // Premium must equal TP (ATP × 1.0 × blend factor with capacity_adj=0)
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, 1.0);
         let (_, event) = events.into_iter().next().unwrap();
@@ -1401,8 +2732,10 @@ mod tests {
 
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, 1.0);
         let (_, event) = events.into_iter().next().unwrap();
@@ -1431,8 +2764,10 @@ mod tests {
 
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(0), SubmissionId(1), InsuredId(1), &risk, 1.0);
         let (_, event) = events.into_iter().next().unwrap();
@@ -1513,8 +2848,10 @@ mod tests {
         use crate::types::SubmissionId;
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(1), SubmissionId(1), InsuredId(1), &risk, 0.90);
         let line_size = events.iter().find_map(|(_, e)| {
@@ -1541,8 +2878,10 @@ mod tests {
         use crate::types::SubmissionId;
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(1), SubmissionId(1), InsuredId(1), &risk, 1.10);
         let line_size = events.iter().find_map(|(_, e)| {
@@ -1598,8 +2937,10 @@ mod tests {
         use crate::types::SubmissionId;
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = ins.on_lead_quote_requested(Day(1), SubmissionId(1), InsuredId(1), &risk, 1.0);
         let line_size = events.iter().find_map(|(_, e)| {
@@ -1773,4 +3114,170 @@ mod tests {
         );
     }
 
+    #[test]
+    fn record_quote_request_counts_trailing_window_only() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000);
+        assert_eq!(ins.record_quote_request(Day(0), 30), 1);
+        assert_eq!(ins.record_quote_request(Day(10), 30), 2);
+        assert_eq!(ins.record_quote_request(Day(29), 30), 3);
+        // Day 0 falls out of the 30-day window ending at day 30.
+        assert_eq!(ins.record_quote_request(Day(30), 30), 3);
+        assert_eq!(ins.record_quote_request(Day(100), 30), 1, "stale requests must be trimmed");
+    }
+
+    #[test]
+    fn under_reserved_claim_strengthens_as_it_pays() {
+        // Ultimate 1_000 reserved at 80%: capital falls by 800 at report, then each
+        // instalment strengthens by its share of the 200 shortfall until 1_000 is incurred.
+        let mut ins = make_insurer(InsurerId(1), 10_000);
+        let events = ins.on_claim_reported(Day(10), ClaimId(0), 1_000, Peril::WindstormAtlantic, 0.8);
+        assert!(matches!(events[0].1, Event::ReserveEstablished { reserve: 800, .. }));
+        assert_eq!(ins.capital, 9_200);
+        assert_eq!(ins.outstanding_reserves(), 800);
+
+        ins.on_claim_paid(Day(10), ClaimId(0), 600, Peril::WindstormAtlantic, 0.6);
+        // Outstanding = 800 × 0.4 = 320; movement = 600 + 320 − 800 = +120.
+        assert_eq!(ins.outstanding_reserves(), 320);
+        assert_eq!(ins.capital, 9_080);
+
+        ins.on_claim_paid(Day(370), ClaimId(0), 400, Peril::WindstormAtlantic, 1.0);
+        assert_eq!(ins.outstanding_reserves(), 0, "final payment closes the claim");
+        assert_eq!(ins.capital, 9_000, "total incurred must equal the ultimate");
+    }
+
+    #[test]
+    fn over_reserved_claim_releases_into_capital() {
+        let mut ins = make_insurer(InsurerId(1), 10_000);
+        ins.on_claim_reported(Day(0), ClaimId(5), 1_000, Peril::Attritional, 1.5);
+        assert_eq!(ins.capital, 8_500);
+        ins.on_claim_paid(Day(0), ClaimId(5), 1_000, Peril::Attritional, 1.0);
+        assert_eq!(ins.capital, 9_000, "excess reserve of 500 must be released");
+    }
+
+    #[test]
+    fn releases_never_exceed_what_the_claim_charged() {
+        // Capital 500 covers only half the 1_000 reserve. Development is measured against the
+        // 500 actually charged, and capital stays at the floor once insolvent.
+        let mut ins = make_insurer(InsurerId(1), 500);
+        let events = ins.on_claim_reported(Day(0), ClaimId(1), 1_000, Peril::Attritional, 1.0);
+        assert!(events.iter().any(|(_, e)| matches!(e, Event::InsurerInsolvent { .. })));
+        assert_eq!(ins.capital, 0);
+        ins.on_claim_paid(Day(30), ClaimId(1), 200, Peril::Attritional, 0.5);
+        assert_eq!(ins.capital, 0, "200 paid + 500 outstanding still exceeds the 500 charged");
+        ins.on_claim_paid(Day(60), ClaimId(1), 0, Peril::Attritional, 1.0);
+        assert_eq!(ins.capital, 0, "an insolvent insurer's release is not credited");
+    }
+
+    #[test]
+    fn cancelled_policy_reverses_uncollected_premium_and_cat_aggregate() {
+        let mut ins = make_insurer(InsurerId(1), 10_000);
+        ins.on_policy_bound(PolicyId(1), 1_000_000, 10_000, &[Peril::WindstormAtlantic], 0.5);
+        assert!(ins.capital > 10_000);
+        assert_eq!(ins.cat_aggregate, 500_000);
+
+        // Nothing collected beyond what was reversed: capital and YTD premium return to start.
+        let events = ins.on_policy_cancelled(Day(100), PolicyId(1), 10_000, 0, 0.5);
+        assert!(events.is_empty());
+        assert_eq!(ins.capital, 10_000);
+        assert_eq!(ins.ytd.premium, 0);
+        assert_eq!(ins.cat_aggregate, 0, "cancellation must release the cat aggregate");
+    }
+
+    #[test]
+    fn bad_debt_can_trigger_insolvency() {
+        let mut ins = make_insurer(InsurerId(1), 100);
+        let events = ins.on_policy_cancelled(Day(100), PolicyId(9), 0, 1_000_000, 1.0);
+        assert_eq!(ins.capital, 0);
+        assert!(matches!(events[..], [(Day(100), Event::InsurerInsolvent { .. })]));
+    }
+
+    #[test]
+    fn deductible_lowers_atp_via_exposure_curve() {
+        let ins = make_insurer(InsurerId(1), 1_000_000_000);
+        let full = ins.actuarial_price(&small_risk());
+        let ded = ins.actuarial_price(&Risk { attachment: ASSET_VALUE / 100, ..small_risk() });
+        // 1%-of-value deductible removes G(0.01) ≈ 10% of expected loss.
+        let credit = 1.0 - ded as f64 / full as f64;
+        assert!((0.08..0.12).contains(&credit), "deductible credit {credit}");
+    }
+
+    #[test]
+    fn risk_bound_books_layer_exposure_and_max_claim_aggregate() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000);
+        let risk = Risk { attachment: ASSET_VALUE / 100, limit: ASSET_VALUE / 2, ..small_risk() };
+        ins.on_risk_bound(PolicyId(1), &risk, 10_000, 1.0);
+        assert_eq!(ins.cat_aggregate, ASSET_VALUE / 2, "aggregate grows by the largest possible claim");
+        let expected = (ASSET_VALUE as f64 * risk.layer_factor()).round() as u64;
+        assert_eq!(ins.ytd.exposure, expected);
+    }
+
+    #[test]
+    fn investment_return_moves_capital_and_ytd_income() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000);
+        let events = ins.on_investment_return(Day(358), 0.05);
+        assert_eq!(ins.capital, 1_050_000);
+        assert_eq!(ins.ytd.investment_income, 50_000);
+        assert!(matches!(events[..], [(_, Event::InvestmentIncome { amount: 50_000, .. })]));
+    }
+
+    #[test]
+    fn discount_credit_cheapens_the_actuarial_price_as_rates_rise() {
+        let plain = make_insurer(InsurerId(1), 1_000_000_000);
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000).with_discount_credit(2.0);
+        assert_eq!(ins.actuarial_price(&small_risk()), plain.actuarial_price(&small_risk()), "no credit at a zero rate");
+        ins.set_interest_rate(0.05);
+        let expected = plain.actuarial_price(&small_risk()) as f64 / 1.05f64.powi(2);
+        assert!((ins.actuarial_price(&small_risk()) as f64 - expected).abs() <= 1.0);
+        ins.set_interest_rate(-0.01);
+        assert!(ins.actuarial_price(&small_risk()) > plain.actuarial_price(&small_risk()), "negative rates load the price");
+    }
+
+    #[test]
+    fn missed_growth_target_undercuts_price_down_to_the_floor() {
+        use crate::config::GrowthTargetConfig;
+        let growth = GrowthTargetConfig { insurer_ids: vec![], annual_growth: 0.2, undercut_per_shortfall: 1.0, price_floor: 0.9 };
+        let mut ins = make_insurer(InsurerId(1), 1_000_000_000).with_growth_target(Some(growth));
+        let atp = ins.actuarial_price(&small_risk()) as f64;
+        assert!(ins.set_underwriting_plan(Year(1)).is_none(), "no plan before a year of premium");
+
+        ins.last_year_premium = 1_000;
+        let plan = ins.set_underwriting_plan(Year(2));
+        assert!(matches!(plan, Some(Event::UnderwritingPlanSet { prior_premium: 1_000, target_premium: 1_200, undercut: 0.0, .. })));
+        assert_eq!(ins.underwriter_premium(&small_risk(), 1.0), atp.round() as u64, "no undercut without a missed target");
+
+        // 900 written against 1_200: 25% short, but the floor holds the cut to 10%.
+        ins.last_year_premium = 900;
+        let Some(Event::UnderwritingPlanSet { target_premium: 1_080, undercut, .. }) = ins.set_underwriting_plan(Year(3)) else {
+            panic!("expected a plan");
+        };
+        assert!((undercut - 0.25).abs() < 1e-12);
+        assert_eq!(ins.underwriter_premium(&small_risk(), 1.0), (atp * 0.9).round() as u64);
+
+        // 1_053 against 1_080: a 2.5% cut sits above the floor.
+        ins.last_year_premium = 1_053;
+        ins.set_underwriting_plan(Year(4));
+        assert_eq!(ins.underwriter_premium(&small_risk(), 1.0), (atp * 0.975).round() as u64);
+    }
+
+    #[test]
+    fn asset_crash_can_trigger_insolvency() {
+        let mut ins = make_insurer(InsurerId(1), 1_000_000);
+        let events = ins.on_investment_return(Day(358), -1.5);
+        assert_eq!(ins.capital, 0);
+        assert!(matches!(events[..], [_, (_, Event::InsurerInsolvent { .. })]));
+        assert!(ins.on_investment_return(Day(718), 0.05).is_empty(), "insolvent insurer holds no assets");
+    }
+
+    #[test]
+    fn investment_income_counts_towards_distributable_profit() {
+        // No underwriting at all: the whole distribution comes from investment income.
+        let mut ins = Insurer::new(
+            InsurerId(1), 1_000_000, 0.239, 0.0, 0.70, 0.3,
+            0.0, 0.0, None, None, 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.50,
+            1.0, 1.0,
+        );
+        ins.on_investment_return(Day(358), 0.10);
+        let events = ins.on_year_end(Day(359), ASSET_VALUE);
+        assert!(events.iter().any(|(_, e)| matches!(e, Event::CapitalDistributed { amount: 50_000, .. })));
+    }
 }
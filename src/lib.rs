@@ -1,10 +1,39 @@
+#[cfg(test)]
+#[macro_use]
+mod testing;
+
 pub mod analysis;
+pub mod batch;
 pub mod broker;
+pub mod bundle;
+pub mod calibration;
+pub mod cli;
 pub mod config;
+pub mod cycle;
+pub mod error;
 pub mod events;
+pub mod filter;
+pub mod index;
+pub mod ils;
+pub mod live_check;
 pub mod insured;
 pub mod insurer;
 pub mod market;
+pub mod observer;
+pub mod optimise;
 pub mod perils;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod regulator;
+pub mod reinsurance;
+pub mod report;
+pub mod rng;
+pub mod serve;
 pub mod simulation;
+pub mod stylized_facts;
+pub mod sweep;
+pub mod telemetry;
+pub mod transfer;
 pub mod types;
+
+pub use error::{Error, Result};
@@ -0,0 +1,486 @@
+//! Invariant checking while a simulation runs.
+//!
+//! `verify_mechanics` and `verify_integrity` need the whole log; a violation in year 78 of a
+//! long run surfaces only once the run is over, with nothing to say what led up to it.
+//! [`LiveChecker`] consumes events as they are dispatched (`Simulation::check_live`) and checks
+//! the invariants that an event decides on its own given what came before it: quoting-protocol
+//! order, binding, panel shares and premium split, claims against a bound panel within sum
+//! insured, and damage fractions. Each violation carries the offending event and the events
+//! logged just before it.
+//!
+//! Invariants that need the rest of the log — a request never answered, a quote accepted but
+//! never bound, expiry timing, claim splits and exposure snapshots — are left to `verify`.
+//! Violation messages are the `MechanicsViolation` and `IntegrityViolation` display forms, so
+//! a live report and a post-hoc one read the same.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{IntegrityViolation, MechanicsViolation};
+use crate::events::{Event, Peril, SimEvent};
+use crate::market::FULL_LINE_BPS;
+use crate::types::{InsuredId, InsurerId, PolicyId, SubmissionId};
+
+/// Events kept before each violation as context.
+pub const CONTEXT_EVENTS: usize = 16;
+
+/// What to do at a violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveCheckMode {
+    /// Stop the run at the first violation.
+    Abort,
+    /// Record every violation and keep running.
+    Log,
+}
+
+/// One violation caught as it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveViolation {
+    /// Position of `event` in the full event stream (0 = first event logged).
+    pub position: u64,
+    pub event: SimEvent,
+    /// Display form of the violated invariant.
+    pub violation: String,
+    /// Up to `CONTEXT_EVENTS` events logged immediately before `event`, oldest first.
+    pub context: Vec<SimEvent>,
+}
+
+impl std::fmt::Display for LiveViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} at event {} (day {}, year {})", self.violation, self.position, self.event.day.0, self.event.day.year().0)?;
+        for ev in &self.context {
+            writeln!(f, "      {}", serde_json::to_string(ev).expect("SimEvent serializes"))?;
+        }
+        write!(f, "  >>> {}", serde_json::to_string(&self.event).expect("SimEvent serializes"))
+    }
+}
+
+/// What the checker remembers of a bound policy.
+#[derive(Debug, Clone)]
+struct BoundPolicy {
+    insured_id: InsuredId,
+    sum_insured: u64,
+    panel: HashSet<InsurerId>,
+    leader_id: InsurerId,
+    expiry_day: Option<u64>,
+}
+
+/// Streaming invariant checker; feed it every event in log order with [`LiveChecker::push`].
+#[derive(Debug, Clone)]
+pub struct LiveChecker {
+    mode: LiveCheckMode,
+    position: u64,
+    recent: VecDeque<SimEvent>,
+    violations: Vec<LiveViolation>,
+
+    insured_sum_insured: HashMap<InsuredId, u64>,
+    // Quoting flow, per submission and (submission, insurer).
+    lqr_day: HashMap<SubmissionId, u64>,
+    sub_lead_insurer: HashMap<SubmissionId, InsurerId>,
+    lead_requested: HashSet<(SubmissionId, InsurerId)>,
+    lead_responses: HashMap<(SubmissionId, InsurerId), u32>,
+    lead_premium: HashMap<(SubmissionId, InsurerId), u64>,
+    sub_lead_issued: HashSet<SubmissionId>,
+    follower_requested: HashSet<(SubmissionId, InsurerId)>,
+    follower_responses: HashMap<(SubmissionId, InsurerId), u32>,
+    accepted_leader: HashMap<SubmissionId, InsurerId>,
+    // Binding and claims.
+    policies: HashMap<PolicyId, BoundPolicy>,
+    /// Insureds damaged on the current day; a claim must follow a same-day loss.
+    losses_today: (u64, HashSet<InsuredId>),
+    claim_agg: HashMap<(PolicyId, u32), u64>,
+}
+
+impl LiveChecker {
+    pub fn new(mode: LiveCheckMode) -> Self {
+        Self {
+            mode,
+            position: 0,
+            recent: VecDeque::with_capacity(CONTEXT_EVENTS),
+            violations: Vec::new(),
+            insured_sum_insured: HashMap::new(),
+            lqr_day: HashMap::new(),
+            sub_lead_insurer: HashMap::new(),
+            lead_requested: HashSet::new(),
+            lead_responses: HashMap::new(),
+            lead_premium: HashMap::new(),
+            sub_lead_issued: HashSet::new(),
+            follower_requested: HashSet::new(),
+            follower_responses: HashMap::new(),
+            accepted_leader: HashMap::new(),
+            policies: HashMap::new(),
+            losses_today: (0, HashSet::new()),
+            claim_agg: HashMap::new(),
+        }
+    }
+
+    pub fn mode(&self) -> LiveCheckMode {
+        self.mode
+    }
+
+    /// Violations found so far, in the order they occurred.
+    pub fn violations(&self) -> &[LiveViolation] {
+        &self.violations
+    }
+
+    /// True once an `Abort` checker has found a violation; later events are ignored.
+    pub fn halted(&self) -> bool {
+        self.mode == LiveCheckMode::Abort && !self.violations.is_empty()
+    }
+
+    /// Events checked so far.
+    pub fn events_checked(&self) -> u64 {
+        self.position
+    }
+
+    /// Check one event against everything before it. Returns whether it broke an invariant.
+    pub fn push(&mut self, ev: &SimEvent) -> bool {
+        if self.halted() {
+            return false;
+        }
+        let found = self.check(ev);
+        let broke = !found.is_empty();
+        for violation in found {
+            self.violations.push(LiveViolation {
+                position: self.position,
+                event: ev.clone(),
+                violation,
+                context: self.recent.iter().cloned().collect(),
+            });
+        }
+        if self.recent.len() == CONTEXT_EVENTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(ev.clone());
+        self.position += 1;
+        broke
+    }
+
+    fn check(&mut self, ev: &SimEvent) -> Vec<String> {
+        let mut found: Vec<String> = Vec::new();
+        let day = ev.day.0;
+        match &ev.event {
+            Event::LossEvent { event_id, damage_fraction, footprint, .. } => {
+                for &d in std::iter::once(damage_fraction).chain(footprint.iter().map(|(_, d)| d)) {
+                    if d <= 0.0 || d > 1.0 {
+                        found.push(MechanicsViolation::InvalidDamageFraction { event_id: *event_id, damage_fraction: d }.to_string());
+                    }
+                }
+            }
+            Event::CoverageRequested { insured_id, risk } => {
+                let si = self.insured_sum_insured.entry(*insured_id).or_insert(0);
+                *si = (*si).max(risk.sum_insured);
+            }
+            Event::AssetDamage { insured_id, peril, ground_up_loss } => {
+                if self.losses_today.0 != day {
+                    self.losses_today = (day, HashSet::new());
+                }
+                self.losses_today.1.insert(*insured_id);
+                if let Some(&si) = self.insured_sum_insured.get(insured_id)
+                    && *ground_up_loss > si
+                {
+                    if matches!(peril, Peril::WindstormAtlantic) {
+                        found.push(
+                            MechanicsViolation::CatFractionInconsistent {
+                                peril: "WindstormAtlantic".to_string(),
+                                day,
+                                detail: format!("insured {} gul {} > sum_insured {}", insured_id.0, ground_up_loss, si),
+                            }
+                            .to_string(),
+                        );
+                    }
+                    found.push(
+                        IntegrityViolation::GulExceedsSumInsured {
+                            policy_id: insured_id.0,
+                            day,
+                            peril: format!("{peril:?}"),
+                            gul: *ground_up_loss,
+                            sum_insured: si,
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+            Event::LeadQuoteRequested { submission_id, insurer_id, .. } => {
+                self.lqr_day.entry(*submission_id).or_insert(day);
+                self.sub_lead_insurer.entry(*submission_id).or_insert(*insurer_id);
+                self.lead_requested.insert((*submission_id, *insurer_id));
+            }
+            Event::LeadQuoteIssued { submission_id, insurer_id, .. }
+            | Event::LeadQuotePartial { submission_id, insurer_id, .. }
+            | Event::LeadQuoteDeclined { submission_id, insurer_id, .. } => {
+                let key = (*submission_id, *insurer_id);
+                let kind = match &ev.event {
+                    Event::LeadQuoteIssued { premium, .. } | Event::LeadQuotePartial { premium, .. } => {
+                        self.lead_premium.insert(key, *premium);
+                        self.sub_lead_issued.insert(*submission_id);
+                        if matches!(ev.event, Event::LeadQuotePartial { .. }) { "LeadQuotePartial" } else { "LeadQuoteIssued" }
+                    }
+                    _ => "LeadQuoteDeclined",
+                };
+                if !self.lead_requested.contains(&key) {
+                    found.push(
+                        IntegrityViolation::LeadQuoteOrphanResponse {
+                            submission_id: submission_id.0,
+                            insurer_id: insurer_id.0,
+                            day,
+                            kind: kind.to_string(),
+                        }
+                        .to_string(),
+                    );
+                }
+                let count = self.lead_responses.entry(key).or_insert(0);
+                *count += 1;
+                if *count == 2 {
+                    found.push(
+                        IntegrityViolation::LeadQuoteDuplicateResponse {
+                            submission_id: submission_id.0,
+                            insurer_id: insurer_id.0,
+                            count: *count,
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+            Event::FollowerQuoteRequested { submission_id, insurer_id, .. }
+            | Event::RenewalOffered { submission_id, insurer_id, .. } => {
+                self.follower_requested.insert((*submission_id, *insurer_id));
+                if !self.sub_lead_issued.contains(submission_id) {
+                    found.push(
+                        IntegrityViolation::FollowerRequestWithoutLeadIssued {
+                            submission_id: submission_id.0,
+                            insurer_id: insurer_id.0,
+                            day,
+                        }
+                        .to_string(),
+                    );
+                }
+                if self.sub_lead_insurer.get(submission_id) == Some(insurer_id) {
+                    found.push(
+                        IntegrityViolation::InsurerBothLeadAndFollower {
+                            submission_id: submission_id.0,
+                            insurer_id: insurer_id.0,
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+            Event::FollowerQuoteIssued { submission_id, insurer_id, .. }
+            | Event::FollowerQuoteDeclined { submission_id, insurer_id, .. }
+            | Event::RenewalDeclined { submission_id, insurer_id, .. } => {
+                let key = (*submission_id, *insurer_id);
+                let kind = match ev.event {
+                    Event::FollowerQuoteIssued { .. } => "FollowerQuoteIssued",
+                    Event::RenewalDeclined { .. } => "RenewalDeclined",
+                    _ => "FollowerQuoteDeclined",
+                };
+                if !self.follower_requested.contains(&key) {
+                    found.push(
+                        IntegrityViolation::FollowerOrphanResponse {
+                            submission_id: submission_id.0,
+                            insurer_id: insurer_id.0,
+                            day,
+                            kind: kind.to_string(),
+                        }
+                        .to_string(),
+                    );
+                }
+                let count = self.follower_responses.entry(key).or_insert(0);
+                *count += 1;
+                if *count == 2 {
+                    found.push(
+                        IntegrityViolation::FollowerDuplicateResponse {
+                            submission_id: submission_id.0,
+                            insurer_id: insurer_id.0,
+                            count: *count,
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+            Event::QuoteAccepted { submission_id, leader_id, .. } => {
+                self.accepted_leader.insert(*submission_id, *leader_id);
+            }
+            Event::PolicyBound { policy_id, submission_id, panel, insured_id, sum_insured, premium } => {
+                if self.policies.contains_key(policy_id) {
+                    found.push(IntegrityViolation::DuplicatePolicyBound { policy_id: policy_id.0 }.to_string());
+                }
+                if let Some(&lqr) = self.lqr_day.get(submission_id)
+                    && day != lqr + 2
+                {
+                    found.push(
+                        MechanicsViolation::DayOffsetChain {
+                            submission_id: submission_id.0,
+                            detail: format!("PolicyBound at day {day}, expected {} (LeadQuoteRequested at {lqr})", lqr + 2),
+                        }
+                        .to_string(),
+                    );
+                }
+                let leader_id = panel.first().map_or(InsurerId(0), |(id, _)| *id);
+                if let Some(&accepted) = self.accepted_leader.get(submission_id)
+                    && !panel.is_empty()
+                    && accepted != leader_id
+                {
+                    found.push(
+                        IntegrityViolation::PolicyBoundInsurerMismatch {
+                            submission_id: submission_id.0,
+                            policy_id: policy_id.0,
+                            bound_insurer: leader_id.0,
+                            accepted_insurer: accepted.0,
+                        }
+                        .to_string(),
+                    );
+                }
+                let total_share: f64 = panel.iter().map(|(_, share)| share).sum();
+                if (total_share - 1.0).abs() * FULL_LINE_BPS as f64 > 0.5 {
+                    found.push(IntegrityViolation::PanelSharesNotWhole { policy_id: policy_id.0, total_share }.to_string());
+                }
+                if let Some(&lead) = self.lead_premium.get(&(*submission_id, leader_id)) {
+                    let expected = panel.iter().map(|(_, share)| (lead as f64 * share).round() as u64).sum::<u64>();
+                    if premium.abs_diff(expected) > panel.len() as u64 {
+                        found.push(
+                            IntegrityViolation::PremiumSplitMismatch { policy_id: policy_id.0, premium: *premium, expected }
+                                .to_string(),
+                        );
+                    }
+                }
+                self.policies.insert(
+                    *policy_id,
+                    BoundPolicy {
+                        insured_id: *insured_id,
+                        sum_insured: *sum_insured,
+                        panel: panel.iter().map(|(id, _)| *id).collect(),
+                        leader_id,
+                        expiry_day: None,
+                    },
+                );
+            }
+            Event::PolicyExpired { policy_id } => match self.policies.get_mut(policy_id) {
+                Some(policy) => policy.expiry_day = Some(day),
+                None => found.push(IntegrityViolation::PolicyExpiredWithoutBound { policy_id: policy_id.0 }.to_string()),
+            },
+            Event::ClaimSettled { policy_id, insurer_id, amount, .. } => {
+                let policy = self.policies.get(policy_id);
+                if let Some(expiry_day) = policy.and_then(|p| p.expiry_day)
+                    && day > expiry_day
+                {
+                    found.push(
+                        MechanicsViolation::ClaimAfterExpiry { policy_id: policy_id.0, claim_day: day, expiry_day }.to_string(),
+                    );
+                }
+                let has_loss = policy.is_some_and(|p| self.losses_today.0 == day && self.losses_today.1.contains(&p.insured_id));
+                if !has_loss {
+                    found.push(IntegrityViolation::ClaimWithoutMatchingLoss { policy_id: policy_id.0, day }.to_string());
+                }
+                if *amount == 0 {
+                    found.push(IntegrityViolation::ClaimAmountZero { policy_id: policy_id.0, day }.to_string());
+                }
+                if let Some(p) = policy {
+                    if !p.panel.contains(insurer_id) {
+                        found.push(
+                            IntegrityViolation::ClaimInsurerMismatch {
+                                policy_id: policy_id.0,
+                                day,
+                                claim_insurer: insurer_id.0,
+                                bound_insurer: p.leader_id.0,
+                            }
+                            .to_string(),
+                        );
+                    }
+                    let year = ev.day.year().0;
+                    let aggregate = self.claim_agg.entry((*policy_id, year)).or_insert(0);
+                    let before = *aggregate;
+                    *aggregate += amount;
+                    // Reported once, on the claim that takes the year's aggregate over.
+                    if before <= p.sum_insured && *aggregate > p.sum_insured {
+                        found.push(
+                            IntegrityViolation::AggregateClaimExceedsSumInsured {
+                                policy_id: policy_id.0,
+                                year,
+                                aggregate: *aggregate,
+                                sum_insured: p.sum_insured,
+                            }
+                            .to_string(),
+                        );
+                    }
+                }
+            }
+            Event::CapitalDistributed { insurer_id, amount, .. } if *amount == 0 => {
+                found.push(IntegrityViolation::DistributionAmountZero { insurer_id: insurer_id.0, day }.to_string());
+            }
+            _ => {}
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Day;
+
+    fn at(day: u64, event: Event) -> SimEvent {
+        SimEvent { day: Day(day), seq: None, event }
+    }
+
+    fn bound(policy_id: u64) -> Event {
+        Event::PolicyBound {
+            policy_id: PolicyId(policy_id),
+            submission_id: SubmissionId(policy_id),
+            insured_id: InsuredId(1),
+            panel: vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)],
+            sum_insured: 1_000,
+            premium: 50,
+        }
+    }
+
+    fn claim(insurer_id: u64, amount: u64) -> Event {
+        Event::ClaimSettled {
+            policy_id: PolicyId(1),
+            insurer_id: InsurerId(insurer_id),
+            amount,
+            peril: Peril::Attritional,
+            remaining_capital: 0,
+        }
+    }
+
+    #[test]
+    fn reports_each_violation_with_the_events_before_it() {
+        let mut checker = LiveChecker::new(LiveCheckMode::Log);
+        let damage = Event::AssetDamage { insured_id: InsuredId(1), peril: Peril::Attritional, ground_up_loss: 500 };
+        assert!(!checker.push(&at(3, bound(1))));
+        assert!(!checker.push(&at(10, damage.clone())));
+        assert!(!checker.push(&at(10, claim(1, 300))));
+        assert!(checker.push(&at(10, claim(3, 200))), "insurer 3 is not on the panel");
+        assert!(checker.push(&at(11, claim(2, 0))), "zero amount, and no loss on day 11");
+        assert!(checker.push(&at(12, bound(1))), "bound twice");
+
+        let found: Vec<&str> = checker.violations().iter().map(|v| v.violation.as_str()).collect();
+        assert_eq!(
+            found,
+            [
+                "ClaimInsurerMismatch policy=1 day=10 claim_insurer=3 bound_insurer=1",
+                "ClaimWithoutMatchingLoss policy=1 day=11",
+                "ClaimAmountZero policy=1 day=11",
+                "DuplicatePolicyBound policy=1",
+            ]
+        );
+        let first = &checker.violations()[0];
+        assert_eq!(first.position, 3);
+        assert_eq!(first.context.len(), 3);
+        assert_eq!(first.context[1].event, damage);
+        assert_eq!(checker.events_checked(), 6);
+    }
+
+    #[test]
+    fn abort_mode_stops_at_the_first_violation() {
+        let mut checker = LiveChecker::new(LiveCheckMode::Abort);
+        checker.push(&at(1, Event::PolicyExpired { policy_id: PolicyId(9) }));
+        assert!(checker.halted());
+        assert!(!checker.push(&at(2, Event::PolicyExpired { policy_id: PolicyId(10) })));
+        assert_eq!(checker.violations().len(), 1);
+        assert_eq!(checker.violations()[0].violation, "PolicyExpiredWithoutBound policy=9");
+    }
+}
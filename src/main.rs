@@ -1,144 +1,1022 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
-use std::io::{BufWriter, Write};
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::PathBuf;
 
 use rins::analysis::{self, IntegrityViolation, MechanicsViolation};
-use rins::config::SimulationConfig;
-use rins::simulation::Simulation;
-use rins::types::InsurerId;
+use rins::batch::{self, BatchOptions, BatchSummary, RunSummary};
+use rins::events::Event;
+use rins::bundle;
+use rins::calibration::{self, HistoricalYear};
+use rins::cli::{CliError, Command, Flag, Matches};
+use rins::config::{
+    ExposureGrowthConfig, ExposureSnapshotConfig, GrowthTargetConfig, HealthConfig, InflationConfig, InterestRateConfig, LargeLossConfig, PricingMode, RationingConfig,
+    RationingPriority, RiskViewConfig, RngConfig, RolloverConfig, ScenarioConfig, SimulationConfig, TerritoryGrowth,
+};
+use rins::cycle;
+use rins::filter;
+use rins::live_check::{LiveCheckMode, LiveViolation};
+use rins::optimise;
+use rins::perils;
+use rins::report::{self, InvariantCheck};
+use rins::rng::{RngBackend, Stream};
+use rins::serve;
+use rins::simulation::{Simulation, SimulationState};
+use rins::stylized_facts;
+use rins::sweep;
+use rins::telemetry::{CountingAlloc, Telemetry};
+use rins::types::{Day, InsuredId, InsurerId, PolicyId, Year};
+
+/// Counts allocations only while `--perf` is recording (see `telemetry`).
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const RUN: Command = Command {
+    name: "rins [run]",
+    args: "",
+    about: "Simulate one run, or a --runs batch, of the canonical market and print the analysis.",
+    min_args: 0,
+    max_args: 0,
+    flags: &[
+        Flag::value("--seed", "n", "master seed (first seed of a batch)"),
+        Flag::value("--years", "n", "analysis years after warm-up"),
+        Flag::value("--output", "path", "event log of a single run (default events.ndjson)"),
+        Flag::switch("--quiet", "no progress output"),
+        Flag::switch("--no-cats", "disable catastrophe losses"),
+        Flag::value("--runs", "n", "run a batch of n seeds in parallel"),
+        Flag::value("--seeds", "a,b,...", "run a batch of exactly these seeds"),
+        Flag::value("--seed-file", "path", "run a batch of the seeds listed in a file"),
+        Flag::value("--output-dir", "dir", "write each batch run's event log here"),
+        Flag::value("--csv", "path", "year stats CSV (sweep results with --sweep)"),
+        Flag::value("--summary-json", "path", "per-run summary and batch aggregates as JSON"),
+        Flag::value("--calibrate", "history.csv", "compare the runs against observed market history"),
+        Flag::value("--report", "path", "self-contained HTML report"),
+        Flag::value("--rng", "chacha20|pcg64", "random number generator backend"),
+        Flag::value("--scenario", "path", "JSON stress scenario of injected cat events"),
+        Flag::value("--cat-catalogue", "path", "replay a fixed cat event list (NDJSON or CSV)"),
+        Flag::value("--pricing", "adaptive|frozen", "pricing mode"),
+        Flag::value("--checkpoint-every", "years", "write a checkpoint every n years"),
+        Flag::value("--resume", "checkpoint.json", "resume a run from a checkpoint"),
+        Flag::value("--health", "days", "log SimHealth every n days"),
+        Flag::value("--exposure-snapshots", "days", "log ExposureSnapshot every n days"),
+        Flag::value("--check-live", "abort|log", "check invariants as events are dispatched"),
+        Flag::switch("--perf", "print events per kind, wall time per year, peak queue and allocations"),
+        Flag::value("--perf-json", "path", "write the performance summary as JSON"),
+        Flag::value("--insured-panel", "path", "per-insured panel CSV"),
+        Flag::value("--csv-by-insurer", "path", "per-insurer year stats CSV"),
+        Flag::value("--inflation", "asset,claims", "annual asset growth and claims inflation"),
+        Flag::switch("--real", "report in year-1 values (with --inflation)"),
+        Flag::value("--large-loss", "rate,scale,shape", "single-risk large fire losses (Pareto)"),
+        Flag::value("--exposure-growth", "territory=rate,...", "annual real value growth per territory"),
+        Flag::value("--rationing", "days,relationship|rate", "batch cat lead requests and ration headroom"),
+        Flag::value("--rollover", "max_increase", "renew the expiring panel within a premium rise"),
+        Flag::value("--interest-rates", "r0,long_run,reversion,vol,credit", "Vasicek short rate and pricing discount credit"),
+        Flag::value("--growth-target", "growth,undercut,floor", "every insurer targets premium growth, undercutting to a floor"),
+        Flag::switch("--quote-comparison", "log every insurer's quote for each bound policy"),
+        Flag::value("--risk-views", "sigma", "perturb each insurer's cat ELF by a lognormal model error"),
+        Flag::switch("--capital-ledger", "log every capital movement as a CapitalFlow"),
+        Flag::switch("--quarterly", "log a QuarterEnd with interim market stats each quarter"),
+        Flag::switch("--no-log", "keep no event log (batch aggregates only)"),
+        Flag::switch("--stylized-facts", "test the runs against the stylised facts"),
+        Flag::value("--sweep", "grid.json", "run a parameter sweep"),
+        Flag::value("--optimise", "spec.json", "run a parameter optimisation"),
+        Flag::value("--freeze", "streams", "hold these random streams fixed (cats,attritional,broker,pricing,market)"),
+    ],
+};
+
+const VERIFY: Command = Command {
+    name: "rins verify",
+    args: "<events.ndjson>",
+    about: "Check a saved event log against the mechanics and integrity invariants.",
+    min_args: 1,
+    max_args: 1,
+    flags: &[Flag::switch("--json", "print a machine-readable report")],
+};
+
+const ANALYSE: Command = Command {
+    name: "rins analyse",
+    args: "<events.ndjson>",
+    about: "Print the invariant summary and year table for a saved event log.",
+    min_args: 1,
+    max_args: 1,
+    flags: &[
+        Flag::value("--csv", "path", "year stats CSV"),
+        Flag::value("--csv-by-insurer", "path", "per-insurer year stats CSV"),
+        Flag::value("--insured-panel", "path", "per-insured panel CSV"),
+        Flag::value("--postmortem", "event_id", "print the JSON post-mortem of one cat occurrence"),
+        Flag::switch("--funnel", "print the submission funnel and lead declines by reason"),
+        Flag::switch("--winners-curse", "print loss ratios by quote-spread decile"),
+        Flag::switch("--quarterly", "print premium, claims and capital by quarter"),
+        Flag::value("--expense-ratio", "r", "override the expense ratio read from the log"),
+        Flag::value("--seed", "n", "seed column of the CSV"),
+    ],
+};
+
+const BUNDLE: Command = Command {
+    name: "rins bundle",
+    args: "<run dirs...>",
+    about: "Package several experiments' run directories into one bundle for the UI.",
+    min_args: 1,
+    max_args: usize::MAX,
+    flags: &[Flag::value("--output", "path", "bundle file (default bundle.json)").short("-o")],
+};
+
+const FILTER: Command = Command {
+    name: "rins filter",
+    args: "<in.ndjson> <out.ndjson>",
+    about: "Copy the events matching every given filter from one event log to another.",
+    min_args: 2,
+    max_args: 2,
+    flags: &[
+        Flag::value("--type", "kinds", "comma-separated event kinds (e.g. ClaimSettled,PolicyBound)"),
+        Flag::value("--year", "range", "years 5, 5..10 (end excluded) or 5..=10"),
+        Flag::value("--day", "range", "days, as for --year"),
+        Flag::value("--insurer", "id", "events concerning this insurer"),
+        Flag::value("--insured", "id", "events concerning this insured"),
+        Flag::value("--policy", "id", "events concerning this policy"),
+    ],
+};
+
+const SERVE: Command = Command {
+    name: "rins serve",
+    args: "",
+    about: "Serve runs over HTTP: POST a config, stream its events, query its analysis.",
+    min_args: 0,
+    max_args: 0,
+    flags: &[
+        Flag::value("--bind", "addr", "address to listen on (default 127.0.0.1:8080)"),
+        Flag::value("--max-running", "n", "runs simulated at once; more are refused with 503 (default 4)"),
+        Flag::value("--keep-finished", "n", "finished runs kept before the oldest are evicted (default 64)"),
+    ],
+};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("bundle") => run_bundle(&args[2..]),
+        Some("verify") => run_verify(&args[2..]),
+        Some("analyse") => run_analyse(&args[2..]),
+        Some("filter") => run_filter(&args[2..]),
+        Some("serve") => run_serve(&args[2..]),
+        Some("run") => run_simulation(&args[2..]),
+        Some("help" | "-h" | "--help") => print_commands(),
+        // No subcommand: the flags are for `run`.
+        _ => run_simulation(&args[1..]),
+    }
+}
 
-    let mut seed_override: Option<u64> = None;
-    let mut years_override: Option<u32> = None;
-    let mut output_path = "events.ndjson".to_string();
-    let mut quiet = false;
-    let mut no_cats = false; // set by --no-cats flag
-    let mut runs: Option<u64> = None;
-    let mut output_dir_opt: Option<String> = None;
-    let mut csv_path_opt: Option<String> = None;
-
-    let mut i = 1;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--seed" => {
-                i += 1;
-                seed_override = Some(args[i].parse().expect("--seed requires a u64"));
-            }
-            "--years" => {
-                i += 1;
-                years_override = Some(args[i].parse().expect("--years requires a u32"));
-            }
-            "--output" => {
-                i += 1;
-                output_path = args[i].clone();
-            }
-            "--quiet" => quiet = true,
-            "--no-cats" => no_cats = true,
-            "--runs" => {
-                i += 1;
-                runs = Some(args[i].parse().expect("--runs requires a positive integer"));
-            }
-            "--output-dir" => {
-                i += 1;
-                output_dir_opt = Some(args[i].clone());
-            }
-            "--csv" => {
-                i += 1;
-                csv_path_opt = Some(args[i].clone());
-            }
-            _ => {}
+fn print_commands() {
+    println!("usage: rins [run] [options] | rins <command> ...\n\ncommands:");
+    for cmd in [&RUN, &VERIFY, &ANALYSE, &FILTER, &BUNDLE, &SERVE] {
+        println!("  {:<14} {}", cmd.name, cmd.about);
+    }
+    println!("\n`rins <command> --help` lists a command's options.");
+}
+
+/// Parse `args` for `cmd`, printing its usage and exiting on `--help` or a bad command line.
+fn parse_args(cmd: &Command, args: &[String]) -> Matches {
+    cmd.parse(args).unwrap_or_else(|e| usage_exit(cmd, e))
+}
+
+/// A flag's value parsed as `T`, exiting with usage on a bad value.
+fn flag_value<T: std::str::FromStr>(cmd: &Command, m: &Matches, name: &'static str, expected: &'static str) -> Option<T> {
+    m.parse(name, expected).unwrap_or_else(|e| usage_exit(cmd, e))
+}
+
+fn usage_exit(cmd: &Command, e: CliError) -> ! {
+    if e == CliError::Help {
+        print!("{}", cmd.usage());
+        std::process::exit(0);
+    }
+    eprintln!("error: {e}\n\n{}", cmd.usage());
+    std::process::exit(2);
+}
+
+/// `rins [run] [flags]`: simulate one run or a `--runs` batch and print the analysis.
+fn run_simulation(args: &[String]) {
+    let m = parse_args(&RUN, args);
+    let seed_override: Option<u64> = flag_value(&RUN, &m, "--seed", "a u64");
+    let years_override: Option<u32> = flag_value(&RUN, &m, "--years", "a u32");
+    let output_path = m.value("--output").unwrap_or("events.ndjson").to_string();
+    let quiet = m.has("--quiet");
+    let no_cats = m.has("--no-cats");
+    let quote_comparison = m.has("--quote-comparison");
+    let capital_ledger = m.has("--capital-ledger");
+    let quarterly = m.has("--quarterly");
+    let runs: Option<u64> = flag_value(&RUN, &m, "--runs", "a positive integer");
+    let seed_list: Option<Vec<u64>> = match (m.value("--seeds"), m.value("--seed-file")) {
+        (Some(_), Some(_)) => usage_exit(&RUN, CliError::Conflict("--seeds", "--seed-file")),
+        (Some(list), None) => Some(batch::parse_seed_list(list).unwrap_or_else(|_| {
+            let value = list.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--seeds", value, expected: "comma-separated u64 seeds" })
+        })),
+        (None, Some(path)) => {
+            let text = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+            Some(batch::parse_seed_list(&text).unwrap_or_else(|e| panic!("{path}: {e}")))
         }
-        i += 1;
+        (None, None) => None,
+    };
+    if let Some(flag) = ["--runs", "--seed"].into_iter().find(|f| seed_list.is_some() && m.has(f)) {
+        let list = if m.has("--seeds") { "--seeds" } else { "--seed-file" };
+        usage_exit(&RUN, CliError::Conflict(list, flag));
     }
+    let path = |name| m.value(name).map(String::from);
+    let mut output_dir_opt = path("--output-dir");
+    let csv_path_opt = path("--csv");
+    let summary_path_opt = path("--summary-json");
+    let calibrate_path_opt = path("--calibrate");
+    let report_path_opt = path("--report");
+    let rng_backend = m.value("--rng").map(|b| match b {
+        "chacha20" => RngBackend::ChaCha20,
+        "pcg64" => RngBackend::Pcg64,
+        other => usage_exit(&RUN, CliError::BadValue { flag: "--rng", value: other.to_string(), expected: "chacha20 or pcg64" }),
+    });
+    let scenario_path_opt = path("--scenario");
+    let catalogue_path_opt = path("--cat-catalogue");
+    let pricing_mode = m.value("--pricing").map(|p| match p {
+        "adaptive" => PricingMode::Adaptive,
+        "frozen" => PricingMode::Frozen,
+        other => usage_exit(&RUN, CliError::BadValue { flag: "--pricing", value: other.to_string(), expected: "adaptive or frozen" }),
+    });
+    let mut checkpoint_every: Option<u32> =
+        flag_value(&RUN, &m, "--checkpoint-every", "a positive number of years");
+    let mut resume_path_opt = path("--resume");
+    let health_interval: Option<u64> = flag_value(&RUN, &m, "--health", "a number of days");
+    let exposure_interval: Option<u64> = flag_value(&RUN, &m, "--exposure-snapshots", "a number of days");
+    let live_check = m.value("--check-live").map(|v| match v {
+        "abort" => LiveCheckMode::Abort,
+        "log" => LiveCheckMode::Log,
+        other => usage_exit(&RUN, CliError::BadValue { flag: "--check-live", value: other.to_string(), expected: "abort or log" }),
+    });
+    let perf = m.has("--perf");
+    let perf_path_opt = path("--perf-json");
+    let mut panel_path_opt = path("--insured-panel");
+    let mut insurer_csv_path_opt = path("--csv-by-insurer");
+    let inflation = m.value("--inflation").map(|v| {
+        let rates: Option<Vec<f64>> = v.split(',').map(|r| r.parse().ok()).collect();
+        let Some(&[asset_growth, claims_inflation]) = rates.as_deref() else {
+            let value = v.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--inflation", value, expected: "<asset_growth>,<claims_inflation>" })
+        };
+        InflationConfig { asset_growth, claims_inflation }
+    });
+    let real = m.has("--real");
+    let large_loss = m.value("--large-loss").map(|v| {
+        let params: Option<Vec<f64>> = v.split(',').map(|r| r.parse().ok()).collect();
+        let Some(&[annual_rate, scale, shape]) = params.as_deref() else {
+            let value = v.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--large-loss", value, expected: "<annual_rate>,<scale>,<shape>" })
+        };
+        LargeLossConfig { annual_rate, scale, shape }
+    });
+    let exposure_growth = m.value("--exposure-growth").map(|v| {
+        let territories: Option<Vec<TerritoryGrowth>> = v
+            .split(',')
+            .map(|t| {
+                let (territory, rate) = t.split_once('=')?;
+                Some(TerritoryGrowth { territory: territory.to_string(), annual_growth: rate.parse().ok()? })
+            })
+            .collect();
+        let Some(territories) = territories else {
+            let value = v.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--exposure-growth", value, expected: "<territory>=<rate>,..." })
+        };
+        ExposureGrowthConfig { territories }
+    });
+    let rationing = m.value("--rationing").map(|v| {
+        let parsed = v.split_once(',').and_then(|(days, priority)| {
+            let priority = match priority {
+                "relationship" => RationingPriority::RelationshipScore,
+                "rate" => RationingPriority::RateAdequacy,
+                _ => return None,
+            };
+            Some(RationingConfig { window_days: days.parse().ok()?, priority })
+        });
+        parsed.unwrap_or_else(|| {
+            let value = v.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--rationing", value, expected: "<days>,relationship|rate" })
+        })
+    });
+    let rollover = m.value("--rollover").map(|v| match v.parse() {
+        Ok(max_premium_increase) => RolloverConfig { max_premium_increase },
+        Err(_) => {
+            let value = v.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--rollover", value, expected: "<max_premium_increase>" })
+        }
+    });
+    let interest_rates = m.value("--interest-rates").map(|v| {
+        let params: Option<Vec<f64>> = v.split(',').map(|r| r.parse().ok()).collect();
+        let Some(&[initial_rate, long_run_rate, mean_reversion, volatility, discount_credit]) = params.as_deref() else {
+            let value = v.to_string();
+            let expected = "<initial_rate>,<long_run_rate>,<mean_reversion>,<volatility>,<discount_credit>";
+            usage_exit(&RUN, CliError::BadValue { flag: "--interest-rates", value, expected })
+        };
+        InterestRateConfig { initial_rate, long_run_rate, mean_reversion, volatility, discount_credit }
+    });
+    let growth_targets = m.value("--growth-target").map(|v| {
+        let params: Option<Vec<f64>> = v.split(',').map(|r| r.parse().ok()).collect();
+        let Some(&[annual_growth, undercut_per_shortfall, price_floor]) = params.as_deref() else {
+            let value = v.to_string();
+            let expected = "<annual_growth>,<undercut_per_shortfall>,<price_floor>";
+            usage_exit(&RUN, CliError::BadValue { flag: "--growth-target", value, expected })
+        };
+        GrowthTargetConfig { insurer_ids: vec![], annual_growth, undercut_per_shortfall, price_floor }
+    });
+    let risk_views = m.value("--risk-views").map(|v| match v.parse() {
+        Ok(cat_elf_sigma) => RiskViewConfig { cat_elf_sigma },
+        Err(_) => {
+            let value = v.to_string();
+            usage_exit(&RUN, CliError::BadValue { flag: "--risk-views", value, expected: "<cat_elf_sigma>" })
+        }
+    });
+    let no_log = m.has("--no-log");
+    let stylized_facts = m.has("--stylized-facts");
+    let sweep_path_opt = path("--sweep");
+    let optimise_path_opt = path("--optimise");
+    let frozen_streams: Option<Vec<Stream>> =
+        m.value("--freeze").map(|v| v.split(',').filter(|s| !s.is_empty()).map(parse_stream).collect());
 
     let mut base_config = SimulationConfig::canonical();
     let start_seed = seed_override.unwrap_or(base_config.seed);
+    let batch_seeds: Option<Vec<u64>> = seed_list.or_else(|| runs.map(|n| (start_seed..start_seed + n).collect()));
     if let Some(y) = years_override {
         base_config.years = y;
     }
     if no_cats {
         base_config.disable_cats = true;
     }
+    if quote_comparison {
+        base_config.quote_comparison = true;
+    }
+    if capital_ledger {
+        base_config.capital_ledger = true;
+    }
+    if quarterly {
+        base_config.quarterly_reporting = true;
+    }
+    if rng_backend.is_some() || frozen_streams.is_some() || pricing_mode.is_some() {
+        // Freezing implies split substreams, and so does an explicit pricing mode: both sides
+        // of a pricing counterfactual must draw losses from the same per-component streams.
+        // A backend alone keeps the shared stream.
+        base_config.rng = Some(RngConfig {
+            backend: rng_backend.unwrap_or_default(),
+            split_streams: frozen_streams.is_some() || pricing_mode.is_some(),
+            frozen: frozen_streams.unwrap_or_default(),
+        });
+    }
+    if let Some(mode) = pricing_mode {
+        base_config.pricing_mode = mode;
+    }
 
-    // Extract analysis inputs before base_config is (potentially) moved.
-    let initial_capitals: HashMap<InsurerId, u64> = base_config
-        .insurers
-        .iter()
-        .map(|ic| (ic.id, ic.initial_capital.max(0) as u64))
-        .collect();
-    let expense_ratio =
-        base_config.insurers.first().map(|ic| ic.expense_ratio).unwrap_or(0.344);
+    if real && inflation.is_none() {
+        eprintln!("Warning: --real has no effect without --inflation");
+    }
+    base_config.inflation = inflation;
+    if large_loss.is_some() {
+        base_config.large_loss = large_loss;
+    }
+    if exposure_growth.is_some() {
+        base_config.exposure_growth = exposure_growth;
+    }
+    if rationing.is_some() {
+        base_config.rationing = rationing;
+    }
+    if rollover.is_some() {
+        base_config.rollover = rollover;
+    }
+    if interest_rates.is_some() {
+        base_config.interest_rates = interest_rates;
+    }
+    if growth_targets.is_some() {
+        base_config.growth_targets = growth_targets;
+    }
+    if risk_views.is_some() {
+        base_config.risk_views = risk_views;
+    }
+    // Real terms deflate by the asset value index; without a trend nominal = real.
+    let real_terms = if real { base_config.inflation.clone() } else { None };
+    let in_terms = |stats: Vec<rins::analysis::YearStats>| match real_terms {
+        Some(ref i) => analysis::deflate(&stats, i),
+        None => stats,
+    };
+
+    if let Some(interval_days) = health_interval {
+        base_config.health = Some(HealthConfig { interval_days });
+    }
+    if let Some(interval_days) = exposure_interval {
+        base_config.exposure_snapshots = Some(ExposureSnapshotConfig { interval_days });
+    }
+
+    if let Some(ref path) = scenario_path_opt {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+        let scenario: ScenarioConfig = serde_json::from_str(&text).unwrap_or_else(|e| panic!("{path}: {e}"));
+        base_config.scenario = Some(scenario);
+    }
+
+    if let Some(ref path) = catalogue_path_opt {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+        let catalogue = perils::parse_catalogue(&text).unwrap_or_else(|e| panic!("{path}: {e}"));
+        base_config.catastrophe.catalogue = Some(catalogue);
+    }
 
-    if let Some(n) = runs {
-        use rayon::prelude::*;
+    if let Err(errors) = base_config.validate() {
+        eprintln!("error: invalid config");
+        for e in &errors {
+            eprintln!("  {e}");
+        }
+        std::process::exit(2);
+    }
+
+    let history = calibrate_path_opt.as_ref().map(|path| {
+        let text = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+        calibration::parse_history(&text).unwrap_or_else(|e| panic!("{path}: {e}"))
+    });
+
+    if let Some(ref path) = sweep_path_opt {
+        base_config.seed = start_seed;
+        run_sweep(&base_config, path, csv_path_opt.as_deref().unwrap_or("sweep.csv"), quiet);
+        return;
+    }
+    if let Some(ref path) = optimise_path_opt {
+        base_config.seed = start_seed;
+        run_optimise(&base_config, path, quiet);
+        return;
+    }
+
+    // Extract analysis inputs before base_config is (potentially) moved.
+    let expense_ratio = batch::expense_ratio(&base_config);
 
-        if let Some(ref dir) = output_dir_opt {
-            std::fs::create_dir_all(dir).expect("failed to create output directory");
+    if batch_seeds.is_some() && (checkpoint_every.is_some() || resume_path_opt.is_some()) {
+        eprintln!("Warning: --checkpoint-every / --resume apply to single runs; ignored in a batch");
+    }
+    if batch_seeds.is_some() && panel_path_opt.is_some() {
+        eprintln!("Warning: --insured-panel applies to single runs; ignored in a batch");
+    }
+    if batch_seeds.is_some() && insurer_csv_path_opt.is_some() {
+        eprintln!("Warning: --csv-by-insurer applies to single runs; ignored in a batch");
+    }
+    if batch_seeds.is_some() && live_check.is_some() {
+        eprintln!("Warning: --check-live applies to single runs; ignored in a batch");
+    }
+    if batch_seeds.is_some() && (perf || perf_path_opt.is_some()) {
+        eprintln!("Warning: --perf / --perf-json apply to single runs; ignored in a batch");
+    }
+    if no_log {
+        // Everything that reads the raw log is unavailable without one.
+        let needs_log = [
+            ("--output-dir", output_dir_opt.take().is_some()),
+            ("--checkpoint-every", checkpoint_every.take().is_some()),
+            ("--resume", resume_path_opt.take().is_some()),
+            ("--insured-panel", panel_path_opt.take().is_some()),
+            ("--csv-by-insurer", insurer_csv_path_opt.take().is_some()),
+        ];
+        for (flag, _) in needs_log.iter().filter(|(_, set)| *set) {
+            eprintln!("Warning: {flag} needs the event log; ignored with --no-log");
         }
+        if report_path_opt.is_some() {
+            eprintln!("Warning: --report omits invariant checks with --no-log");
+        }
+    }
 
-        let all_stats: Vec<Vec<rins::analysis::YearStats>> = (0u64..n)
-            .into_par_iter()
-            .map(|i| {
-                let seed = start_seed + i;
-                let mut config = base_config.clone();
-                config.seed = seed;
-                let mut sim = Simulation::from_config(config);
-                sim.start();
-                sim.run();
-
-                if let Some(ref dir) = output_dir_opt {
-                    let path = format!("{dir}/events_seed_{seed}.ndjson");
-                    let file = File::create(&path)
-                        .unwrap_or_else(|e| panic!("failed to create {path}: {e}"));
-                    let mut writer = BufWriter::new(file);
-                    for ev in &sim.log {
-                        serde_json::to_writer(&mut writer, ev).expect("serialize");
-                        writeln!(writer).expect("newline");
-                    }
-                    if !quiet {
-                        println!("Seed {seed}: {} events → {path}", sim.log.len());
-                    }
+    if let Some(seeds) = batch_seeds {
+        let n = seeds.len() as u64;
+        let mut config = base_config.clone();
+        config.seed = seeds[0];
+        let opts = BatchOptions {
+            output_dir: output_dir_opt.as_ref().map(PathBuf::from),
+            invariant_checks: report_path_opt.is_some(),
+            aggregate_only: no_log,
+        };
+        // Redraw a bar in place on a terminal; otherwise log a line every tenth of the batch.
+        let bar = std::io::stderr().is_terminal();
+        let step = (n / 10).max(1);
+        let shown = std::sync::Mutex::new(0u64);
+        let results = batch::run_seeds(&config, &seeds, &opts, |p| {
+            if quiet {
+                return;
+            }
+            // Serialise output across workers, and never redraw over a later report.
+            let mut shown = shown.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(ref path) = p.log_path {
+                if bar {
+                    eprint!("\r\x1b[K");
+                }
+                println!("Seed {}: {} events → {}", p.seed, p.events, path.display());
+            }
+            if p.completed < *shown {
+                return;
+            }
+            *shown = p.completed;
+            if bar {
+                eprint!("\r{}", progress_line(&p));
+                if p.completed == p.total {
+                    eprintln!();
                 }
+            } else if p.completed % step == 0 || p.completed == p.total {
+                eprintln!("{}", progress_line(&p));
+            }
+        })
+        .unwrap_or_else(|e| panic!("batch run failed: {e}"));
+        let all_stats = results.stats();
+        let shown_stats: Vec<_> = all_stats.iter().cloned().map(in_terms).collect();
 
-                analysis::analyse(&sim.log, &initial_capitals, expense_ratio).1
-            })
-            .collect();
+        let ruin = analysis::ruin_probabilities(&all_stats);
 
         if let Some(ref csv_path) = csv_path_opt {
-            write_runs_csv(&all_stats, start_seed, expense_ratio, csv_path);
+            write_runs_csv(&shown_stats, &seeds, expense_ratio, csv_path);
+            if n >= 2 {
+                write_tail_csv(&results.distributions, &ruin, csv_path);
+            }
+        }
+        if let Some(ref path) = summary_path_opt {
+            write_summary_json(&results.summary(), path);
         }
 
         if !quiet {
-            print_all_run_years(&all_stats, start_seed, expense_ratio);
+            print_all_run_years(&shown_stats, &seeds, expense_ratio);
             if n < 2 {
                 eprintln!("Warning: Distribution requires >= 2 runs");
             } else {
-                let dists = analysis::analyse_distributions(&all_stats, expense_ratio);
-                print_distributions(&dists, n);
+                print_distributions(&results.distributions, &ruin, n);
+                print_cycle(&cycle::cycle(&all_stats, CYCLE_MAX_LAG));
+                print_rate_response(&cycle::rate_response(&all_stats, SEVERE_CAT_QUANTILE));
             }
         }
+        if let Some(ref history) = history {
+            print_calibration(history, &all_stats, expense_ratio);
+        }
+        if stylized_facts {
+            print_stylized_facts(&all_stats, expense_ratio);
+        }
+        if let Some(ref path) = report_path_opt {
+            let title = if seeds.windows(2).all(|w| w[1] == w[0] + 1) {
+                format!("rins — {n} runs from seed {}", seeds[0])
+            } else {
+                let list: Vec<String> = seeds.iter().map(u64::to_string).collect();
+                format!("rins — {n} runs, seeds {}", list.join(", "))
+            };
+            write_report(path, &title, &all_stats, &results.merged_checks(), expense_ratio);
+        }
     } else {
-        let mut config = base_config;
-        config.seed = start_seed;
+        let mut sim = match resume_path_opt {
+            Some(ref path) => {
+                let text = std::fs::read_to_string(path)
+                    .unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+                let state: SimulationState =
+                    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{path}: {e}"));
+                let sim = Simulation::restore(state).unwrap_or_else(|e| panic!("{path}: {e}"));
+                if !quiet {
+                    println!("Resumed {path} at day {}", sim.log.last().map_or(0, |e| e.day.0));
+                }
+                sim
+            }
+            None => {
+                let mut config = base_config;
+                config.seed = start_seed;
+                let mut sim = Simulation::from_config(config).unwrap_or_else(|e| panic!("{e}"));
+                if no_log {
+                    sim = sim.aggregate_only();
+                }
+                sim.start();
+                sim
+            }
+        };
+        if let Some(mode) = live_check {
+            sim = sim.check_live(mode);
+        }
+        let telemetry = (perf || perf_path_opt.is_some()).then(|| std::sync::Arc::new(std::sync::Mutex::new(Telemetry::new())));
+        if let Some(ref telemetry) = telemetry {
+            sim = sim.with_observer(telemetry.clone());
+        }
+        // A resumed run keeps the checkpoint's config, not the command-line one.
+        let initial_capitals: HashMap<InsurerId, u64> = sim
+            .config()
+            .insurers
+            .iter()
+            .map(|ic| (ic.id, ic.initial_capital.max(0) as u64))
+            .collect();
+        let seed = sim.config().seed;
+
+        if let Some(every) = checkpoint_every.filter(|&n| n > 0) {
+            let total_years = sim.config().warmup_years + sim.config().years;
+            let path = format!("{output_path}.checkpoint.json");
+            let done = sim.log.last().map_or(0, |e| ((e.day.0 + 1) / Day::DAYS_PER_YEAR) as u32);
+            let mut year = (done / every + 1) * every;
+            while year < total_years {
+                sim.run_until(Day::year_end(Year(year)));
+                if live_check == Some(LiveCheckMode::Abort) && !sim.live_violations().is_empty() {
+                    break;
+                }
+                let json = serde_json::to_string(&sim.snapshot()).expect("serialize checkpoint");
+                std::fs::write(&path, json).unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+                if !quiet {
+                    println!("Checkpoint after year {year} → {path}");
+                }
+                year += every;
+            }
+        }
+        match sim.config().health.as_ref().map(|h| h.interval_days.max(1)) {
+            // Advance one interval at a time so each SimHealth is echoed as soon as it is logged.
+            Some(interval) if !quiet && !no_log => {
+                let mut reported = sim.log.len();
+                let mut until = sim.log.last().map_or(0, |e| e.day.0);
+                while !sim.is_finished() {
+                    until += interval;
+                    sim.run_until(Day(until));
+                    for e in &sim.log[reported..] {
+                        if let Event::SimHealth { queue_depth, events_dispatched, active_policies, pending_submissions } = e.event {
+                            eprintln!(
+                                "[health] day {:>6}  queue {queue_depth:>7}  dispatched {events_dispatched:>10}  policies {active_policies:>6}  pending {pending_submissions:>4}",
+                                e.day.0
+                            );
+                        }
+                    }
+                    reported = sim.log.len();
+                }
+            }
+            _ => sim.run(),
+        }
+
+        // Taken before the log is written, so the figures cover the run alone.
+        let perf_summary = telemetry.map(|t| t.lock().expect("telemetry poisoned").summary());
+        if !no_log {
+            batch::write_log(&sim, std::path::Path::new(&output_path))
+                .unwrap_or_else(|e| panic!("failed to write the event log: {e}"));
+        }
+        if let Some(e) = sim.schedule_error() {
+            panic!("{e}");
+        }
+        if !sim.live_violations().is_empty() {
+            print_live_violations(sim.live_violations());
+            if live_check == Some(LiveCheckMode::Abort) {
+                if !no_log {
+                    eprintln!("Event log up to the violation → {output_path}");
+                }
+                std::process::exit(1);
+            }
+        }
+        let (warmup, stats) = match sim.year_stats() {
+            Some(aggregated) => aggregated,
+            None => analysis::analyse(&sim.log, &initial_capitals, expense_ratio),
+        };
+
+        if !quiet {
+            println!("Events fired: {}", sim.events_logged());
+            if !no_log {
+                print_invariants(&sim.log);
+            }
+            print_year_table(warmup, &stats, expense_ratio, &sim.sensitivity_by_year, real_terms.as_ref());
+        }
+        if let Some(ref path) = summary_path_opt {
+            write_summary_json(&BatchSummary::new(vec![RunSummary::new(seed, &stats, expense_ratio)]), path);
+        }
+        if let Some(ref path) = panel_path_opt {
+            write_insured_panel_csv(&analysis::insured_panel(&sim.log), path);
+        }
+        if let Some(ref path) = insurer_csv_path_opt {
+            write_insurer_csv(&analysis::analyse_by_insurer(&sim.log), path);
+        }
+        if let Some(ref history) = history {
+            print_calibration(history, std::slice::from_ref(&stats), expense_ratio);
+        }
+        if stylized_facts {
+            print_stylized_facts(std::slice::from_ref(&stats), expense_ratio);
+        }
+        if let Some(ref path) = report_path_opt {
+            let checks = if no_log { vec![] } else { report::invariant_checks(&sim.log) };
+            write_report(path, &format!("rins — seed {seed}"), &[in_terms(stats)], &checks, expense_ratio);
+        }
+        if let Some(ref summary) = perf_summary {
+            if perf {
+                println!("\n{summary}");
+            }
+            if let Some(ref path) = perf_path_opt {
+                let json = serde_json::to_string_pretty(summary).expect("serialize performance summary");
+                std::fs::write(path, json).unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+            }
+        }
+    }
+}
 
-        let mut sim = Simulation::from_config(config);
+/// `rins serve [--bind addr] [--max-running n] [--keep-finished n]`: run the HTTP service
+/// (see `rins::serve`) until interrupted.
+fn run_serve(args: &[String]) {
+    let m = parse_args(&SERVE, args);
+    let addr = m.value("--bind").unwrap_or("127.0.0.1:8080");
+    let count = |flag: &'static str, default: usize| {
+        m.value(flag).map_or(default, |v| {
+            v.parse().unwrap_or_else(|_| usage_exit(&SERVE, CliError::BadValue { flag, value: v.to_string(), expected: "<n>" }))
+        })
+    };
+    let max_running = count("--max-running", serve::DEFAULT_MAX_RUNNING);
+    let keep_finished = count("--keep-finished", serve::DEFAULT_KEEP_FINISHED);
+    let server = serve::Server::bind(addr).unwrap_or_else(|e| {
+        eprintln!("error: cannot listen on {addr}: {e}");
+        std::process::exit(2);
+    });
+    let server = server.with_max_running(max_running).with_keep_finished(keep_finished);
+    let addr = server.local_addr().map_or_else(|_| addr.to_string(), |a| a.to_string());
+    println!("Serving on http://{addr} (POST /runs, GET /runs/<id>/events|years|funnel)");
+    server.run();
+}
 
-        sim.start();
-        sim.run();
+/// Each violation `--check-live` caught, with the events leading up to it.
+fn print_live_violations(violations: &[LiveViolation]) {
+    eprintln!("Live check: {} violation(s)", violations.len());
+    for v in violations {
+        eprintln!("  {v}");
+    }
+}
 
-        let file = File::create(&output_path).expect("failed to create output file");
-        let mut writer = BufWriter::new(file);
-        for e in &sim.log {
-            serde_json::to_writer(&mut writer, e).expect("failed to serialize event");
-            writeln!(writer).expect("failed to write newline");
+/// `--sweep spec.toml`: run every sampled combination on top of the command-line config and
+/// write the long-format summary CSV.
+fn run_sweep(base: &SimulationConfig, spec_path: &str, csv_path: &str, quiet: bool) {
+    let text = std::fs::read_to_string(spec_path).unwrap_or_else(|e| panic!("failed to read {spec_path}: {e}"));
+    let spec = sweep::parse_spec(&text).unwrap_or_else(|e| panic!("{spec_path}: {e}"));
+    let rows = sweep::run_sweep(base, &spec, |done, total| {
+        if !quiet {
+            println!("Sweep: {done}/{total} combinations ({} seeds each)", spec.seeds);
         }
+    })
+    .unwrap_or_else(|e| panic!("{spec_path}: {e}"));
+    let file = File::create(csv_path).unwrap_or_else(|e| panic!("failed to create {csv_path}: {e}"));
+    sweep::write_csv(&spec.params, &rows, BufWriter::new(file)).expect("write");
+    if !quiet {
+        println!("Sweep summary → {csv_path}");
+    }
+}
+
+/// `--optimise spec.toml`: Nelder–Mead calibration against the stylised facts. The best
+/// candidate so far, with its full config, is checkpointed to `<spec>.best.json`.
+fn run_optimise(base: &SimulationConfig, spec_path: &str, quiet: bool) {
+    let text = std::fs::read_to_string(spec_path).unwrap_or_else(|e| panic!("failed to read {spec_path}: {e}"));
+    let spec = sweep::parse_spec(&text).unwrap_or_else(|e| panic!("{spec_path}: {e}"));
+    let best_path = format!("{spec_path}.best.json");
+    let start = sweep::base_config(base, &spec);
+    let names: Vec<&str> = spec.params.iter().map(|p| p.path.as_str()).collect();
+    let show = |values: &[f64]| -> String {
+        names.iter().zip(values).map(|(n, v)| format!("{n}={v:.4}")).collect::<Vec<_>>().join(" ")
+    };
 
+    let result = optimise::optimise(base, &spec, |c, improved| {
         if !quiet {
-            println!("Events fired: {}", sim.log.len());
-            print_analysis(&sim.log, &initial_capitals, expense_ratio, &sim.sensitivity_by_year);
+            let mark = if improved { " *" } else { "" };
+            println!("Eval {:>3}: objective {:.4}  {}{mark}", c.evaluation, c.objective, show(&c.values));
+        }
+        if improved {
+            let config = sweep::apply(&start, &spec.params, &c.values).expect("path validated before the search");
+            let checkpoint = serde_json::json!({ "params": names, "candidate": c, "config": config });
+            let json = serde_json::to_string_pretty(&checkpoint).expect("serialize checkpoint");
+            std::fs::write(&best_path, json).unwrap_or_else(|e| panic!("failed to write {best_path}: {e}"));
+        }
+    })
+    .unwrap_or_else(|e| panic!("{spec_path}: {e}"));
+
+    let best = &result.best;
+    println!("\n=== Optimisation ({} evaluations, {} seeds each) ===", result.evaluations, spec.seeds);
+    println!("  Best (eval {}): objective {:.4}  {}", best.evaluation, best.objective, show(&best.values));
+    print_fact_results(&stylized_facts::canonical_facts(), &best.facts);
+    println!("  Best candidate and config → {best_path}");
+}
+
+/// `[#####-----] 37/100 runs  12345 events/s  ETA 1m02s`
+fn progress_line(p: &batch::BatchProgress) -> String {
+    const WIDTH: u64 = 30;
+    let filled = (p.completed * WIDTH / p.total.max(1)) as usize;
+    let eta = if p.completed == p.total { "done".to_string() } else { format!("ETA {}", fmt_duration(p.eta())) };
+    format!(
+        "[{}{}] {}/{} runs  {:.0} events/s  {eta}",
+        "#".repeat(filled),
+        "-".repeat(WIDTH as usize - filled),
+        p.completed,
+        p.total,
+        p.events_per_sec(),
+    )
+}
+
+fn fmt_duration(d: std::time::Duration) -> String {
+    let secs = d.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+fn parse_stream(name: &str) -> Stream {
+    match name {
+        "cats" => Stream::Cats,
+        "attritional" => Stream::Attritional,
+        "broker" => Stream::Broker,
+        "pricing" => Stream::Pricing,
+        "market" => Stream::Market,
+        other => usage_exit(&RUN, CliError::BadValue {
+            flag: "--freeze",
+            value: other.to_string(),
+            expected: "streams from cats, attritional, broker, pricing, market",
+        }),
+    }
+}
+
+fn write_report(
+    path: &str,
+    title: &str,
+    runs: &[Vec<rins::analysis::YearStats>],
+    checks: &[InvariantCheck],
+    expense_ratio: f64,
+) {
+    let html = report::render(title, runs, checks, expense_ratio);
+    std::fs::write(path, html).unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+    eprintln!("Report → {path}");
+}
+
+/// `rins bundle <run dirs...> -o bundle.json`: package several experiments for the UI.
+fn run_bundle(args: &[String]) {
+    let m = parse_args(&BUNDLE, args);
+    let dirs: Vec<PathBuf> = m.positionals.iter().map(PathBuf::from).collect();
+    let output = m.value("--output").unwrap_or("bundle.json").to_string();
+
+    let bundle = bundle::build_bundle(&dirs).unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    });
+    let file = File::create(&output).unwrap_or_else(|e| panic!("failed to create {output}: {e}"));
+    serde_json::to_writer(BufWriter::new(file), &bundle).expect("failed to write bundle");
+    for exp in &bundle.experiments {
+        println!(
+            "{}: {} run(s), config {}",
+            exp.name,
+            exp.runs.len(),
+            exp.config_hash.as_deref().unwrap_or("unknown"),
+        );
+    }
+    println!("Bundle v{} → {output}", bundle.version);
+}
+
+/// `rins verify <events.ndjson> [--json]`: check a saved event log against the mechanics and
+/// integrity invariants. Exits 1 if any invariant is violated, 2 if the log cannot be read.
+fn run_verify(args: &[String]) {
+    let m = parse_args(&VERIFY, args);
+    let json = m.has("--json");
+    let path = &m.positionals[0];
+
+    let log = bundle::load_events(std::path::Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(2);
+    });
+    let verification = report::verify(&log);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&verification).expect("serialize verification"));
+    } else {
+        println!("{path}: {} events", verification.events);
+        print_provenance(&log);
+        for check in &verification.checks {
+            let status = if check.violations == 0 { "PASS".to_string() } else { format!("FAIL ({})", check.violations) };
+            println!("  {:<36} {status}", check.name);
+        }
+        for v in &verification.violations {
+            println!("    {v}");
         }
+        println!("{}", if verification.passed { "All invariants: PASS" } else { "Invariants violated" });
+    }
+    if !verification.passed {
+        std::process::exit(1);
+    }
+}
+
+/// `rins filter`: stream the events of a log that match every flag to a new log.
+fn run_filter(args: &[String]) {
+    let m = parse_args(&FILTER, args);
+    let range = |flag: &'static str| {
+        m.value(flag).map(|v| {
+            filter::parse_range(v).unwrap_or_else(|| {
+                let value = v.to_string();
+                usage_exit(&FILTER, CliError::BadValue { flag, value, expected: "n, a..b or a..=b" })
+            })
+        })
+    };
+    let kinds: Vec<String> = m.value("--type").map_or(vec![], |v| v.split(',').map(String::from).collect());
+    if let Some(kind) = kinds.iter().find(|k| !filter::event_kinds().contains(&k.as_str())) {
+        let value = kind.clone();
+        usage_exit(&FILTER, CliError::BadValue { flag: "--type", value, expected: "event kinds such as ClaimSettled" });
+    }
+    let days = match (range("--year"), range("--day")) {
+        (Some(_), Some(_)) => usage_exit(&FILTER, CliError::Conflict("--year", "--day")),
+        (Some((first, last)), None) => {
+            Some((Day::year_start(Year(first as u32)), Day::year_end(Year(last as u32))))
+        }
+        (None, days) => days.map(|(first, last)| (Day(first), Day(last))),
+    };
+    let f = filter::EventFilter {
+        kinds,
+        days,
+        insurer: flag_value(&FILTER, &m, "--insurer", "an insurer id").map(InsurerId),
+        insured: flag_value(&FILTER, &m, "--insured", "an insured id").map(InsuredId),
+        policy: flag_value(&FILTER, &m, "--policy", "a policy id").map(PolicyId),
+    };
+    let (input, output) = (&m.positionals[0], &m.positionals[1]);
+    let (kept, read) = filter::filter_file(std::path::Path::new(input), std::path::Path::new(output), &f)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {e}");
+            std::process::exit(2);
+        });
+    println!("{kept} of {read} lines → {output}");
+}
+
+/// One line from the log's `RunMetadata` header, if it has one.
+fn print_provenance(log: &[rins::events::SimEvent]) {
+    if let Some(Event::RunMetadata(m)) = log.first().map(|e| &e.event) {
+        println!(
+            "  written by rins {} ({}) on {} ({}/{}) at {} s since epoch; seed {}, config {}",
+            m.crate_version,
+            m.git_hash.as_deref().unwrap_or("unknown commit"),
+            m.hostname.as_deref().unwrap_or("unknown host"),
+            m.os,
+            m.arch,
+            m.started_at,
+            m.seed,
+            m.config_hash,
+        );
+    }
+}
+
+/// `rins analyse <events.ndjson> [--csv path] [--csv-by-insurer path] [--insured-panel path] [--postmortem event_id] [--funnel] [--winners-curse] [--quarterly] [--expense-ratio r] [--seed n]`: print the
+/// invariant summary and year character table for a saved event log, with `--funnel` the
+/// placement funnel, with `--winners-curse` the loss ratio by quote-spread decile (logs run
+/// with `--quote-comparison`) and with `--quarterly` the quarterly table (logs run with
+/// `--quarterly`). Initial capitals come from
+/// the log itself, as does the expense ratio unless overridden (logs written before
+/// `InsurerCreated` fall back to the canonical config's). `--seed` only labels the CSV rows.
+/// `--postmortem` prints the JSON post-mortem of one cat occurrence instead.
+fn run_analyse(args: &[String]) {
+    let m = parse_args(&ANALYSE, args);
+    let path = &m.positionals[0];
+    let csv_path = m.value("--csv").map(String::from);
+    let insurer_csv_path = m.value("--csv-by-insurer").map(String::from);
+    let panel_path = m.value("--insured-panel").map(String::from);
+    let postmortem: Option<u64> = flag_value(&ANALYSE, &m, "--postmortem", "an event id");
+    let funnel = m.has("--funnel");
+    let winners_curse = m.has("--winners-curse");
+    let quarterly = m.has("--quarterly");
+    let expense_ratio: Option<f64> = flag_value(&ANALYSE, &m, "--expense-ratio", "a number");
+    let seed: u64 = flag_value(&ANALYSE, &m, "--seed", "a u64").unwrap_or(0);
+
+    let log = bundle::load_events(std::path::Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(2);
+    });
+    if let Some(event_id) = postmortem {
+        let Some(report) = analysis::event_postmortem(&log, event_id) else {
+            eprintln!("error: {path} has no LossEvent {event_id}");
+            std::process::exit(2);
+        };
+        println!("{}", serde_json::to_string_pretty(&report).expect("serialize post-mortem"));
+        return;
+    }
+    let initial_capitals = analysis::initial_capitals(&log);
+    let expense_ratio = expense_ratio
+        .or_else(|| analysis::expense_ratio(&log))
+        .unwrap_or_else(|| batch::expense_ratio(&SimulationConfig::canonical()));
+    let stats = analysis::analyse(&log, &initial_capitals, expense_ratio).1;
+    // The simulation's sensitivity summary is not logged; rebuild it from the year stats.
+    let sensitivity_by_year = stats
+        .iter()
+        .map(|s| {
+            let spread = (
+                s.cr_sensitivity_mean,
+                s.cr_sensitivity_std,
+                s.capacity_sensitivity_mean,
+                s.capacity_sensitivity_std,
+                s.market_weight_floor_mean,
+            );
+            (s.year, spread)
+        })
+        .collect();
+
+    println!("{path}: {} events, {} initial insurers", log.len(), initial_capitals.len());
+    print_provenance(&log);
+    print_analysis(&log, &initial_capitals, expense_ratio, &sensitivity_by_year, None);
+    if funnel {
+        print_funnel(&analysis::submission_funnel(&log));
+    }
+    if winners_curse {
+        print_winners_curse(&analysis::winners_curse(&log));
+    }
+    if quarterly {
+        print_quarterly(&analysis::quarterly_stats(&log));
+    }
+    if let Some(ref csv) = csv_path {
+        write_runs_csv(&[stats], &[seed], expense_ratio, csv);
+        println!("Year stats → {csv}");
+    }
+    if let Some(ref csv) = insurer_csv_path {
+        write_insurer_csv(&analysis::analyse_by_insurer(&log), csv);
+        println!("Per-insurer year stats → {csv}");
+    }
+    if let Some(ref path) = panel_path {
+        write_insured_panel_csv(&analysis::insured_panel(&log), path);
+        println!("Insured panel → {path}");
     }
 }
 
@@ -147,7 +1025,14 @@ fn print_analysis(
     initial_capitals: &HashMap<InsurerId, u64>,
     expense_ratio: f64,
     sensitivity_by_year: &std::collections::HashMap<u32, (f64, f64, f64, f64, f64)>,
+    real_terms: Option<&InflationConfig>,
 ) {
+    print_invariants(log);
+    let (warmup, stats) = analysis::analyse(log, initial_capitals, expense_ratio);
+    print_year_table(warmup, &stats, expense_ratio, sensitivity_by_year, real_terms);
+}
+
+fn print_invariants(log: &[rins::events::SimEvent]) {
     // ── Mechanics invariants ──────────────────────────────────────────────────
     let violations = analysis::verify_mechanics(log);
 
@@ -187,6 +1072,10 @@ fn print_analysis(
     println!("  [13] PolicyBound insurer matches LeadQuoteIssued insurer:      {}", iinv(|v| matches!(v, IntegrityViolation::PolicyBoundInsurerMismatch { .. })));
     println!("  [14] No duplicate PolicyBound for same policy_id:              {}", iinv(|v| matches!(v, IntegrityViolation::DuplicatePolicyBound { .. })));
     println!("  [15] Every PolicyExpired references a bound policy:            {}", iinv(|v| matches!(v, IntegrityViolation::PolicyExpiredWithoutBound { .. })));
+    println!("  [16] PolicyBound panel shares sum to 100%:                     {}", iinv(|v| matches!(v, IntegrityViolation::PanelSharesNotWhole { .. })));
+    println!("  [17] Each panel member's claim = insured loss × its line:      {}", iinv(|v| matches!(v, IntegrityViolation::ClaimShareMismatch { .. })));
+    println!("  [18] Bound premium = lead premium × panel shares:              {}", iinv(|v| matches!(v, IntegrityViolation::PremiumSplitMismatch { .. })));
+    println!("  [19] ExposureSnapshot = Σ sum insured × line of live policies: {}", iinv(|v| matches!(v, IntegrityViolation::ExposureSnapshotMismatch { .. })));
     if int_violations.is_empty() {
         println!("  All integrity invariants: PASS");
     } else {
@@ -196,8 +1085,105 @@ fn print_analysis(
         }
     }
 
+}
+
+/// Submission funnel per year, then lead declines by reason for the market and per insurer.
+fn print_funnel(funnel: &[rins::analysis::FunnelYear]) {
+    if funnel.is_empty() {
+        return;
+    }
+    println!("\n=== Submission funnel ===");
+    println!(
+        "{:>4} | {:>7} | {:>7} | {:>8} | {:>9} | {:>8} | {:>8} | {:>7} | {:>7} | {:>6}",
+        "Year", "Subs", "Quoted", "Declined", "Presented", "Accepted", "Rejected", "Bound", "Dropped", "Q→B%"
+    );
+    println!("{}", "-".repeat(4 + 7 + 7 + 8 + 9 + 8 + 8 + 7 + 7 + 6 + 9 * 3));
+    for y in funnel {
+        println!(
+            "{:>4} | {:>7} | {:>7} | {:>8} | {:>9} | {:>8} | {:>8} | {:>7} | {:>7} | {:>5.1}%",
+            y.year, y.submissions, y.quoted, y.declined(), y.presented, y.accepted, y.rejected, y.bound, y.dropped,
+            y.quote_to_bind() * 100.0,
+        );
+    }
+
+    let mut market: BTreeMap<rins::events::DeclineReason, u32> = BTreeMap::new();
+    let mut by_insurer: BTreeMap<InsurerId, BTreeMap<rins::events::DeclineReason, u32>> = BTreeMap::new();
+    for y in funnel {
+        for (&reason, &n) in &y.lead_declines {
+            *market.entry(reason).or_insert(0) += n;
+        }
+        for (&insurer_id, reasons) in &y.lead_declines_by_insurer {
+            for (&reason, &n) in reasons {
+                *by_insurer.entry(insurer_id).or_default().entry(reason).or_insert(0) += n;
+            }
+        }
+    }
+    if market.is_empty() {
+        return;
+    }
+    let describe = |reasons: &BTreeMap<rins::events::DeclineReason, u32>| {
+        reasons.iter().map(|(reason, n)| format!("{reason:?} {n}")).collect::<Vec<_>>().join(", ")
+    };
+    println!("\n  Lead declines by reason: {}", describe(&market));
+    for (insurer_id, reasons) in &by_insurer {
+        println!("    insurer {:>3}: {}", insurer_id.0, describe(reasons));
+    }
+}
+
+/// Loss ratio by quote-spread decile, cheapest wins first.
+fn print_winners_curse(deciles: &[rins::analysis::SpreadDecile]) {
+    println!("\n=== Winner's curse: loss ratio by quote spread ===");
+    if deciles.is_empty() {
+        println!("  no QuoteComparisonRecorded events (run with --quote-comparison)");
+        return;
+    }
+    println!("{:>6} | {:>8} | {:>16} | {:>7} | {:>6}", "Decile", "Policies", "Spread range", "Mean", "LR%");
+    println!("{}", "-".repeat(6 + 8 + 16 + 7 + 6 + 4 * 3));
+    for d in deciles {
+        println!(
+            "{:>6} | {:>8} | {:>+6.1}%..{:>+6.1}% | {:>+6.1}% | {:>5.1}%",
+            d.decile, d.policies, d.min_spread * 100.0, d.max_spread * 100.0, d.mean_spread * 100.0, d.loss_ratio() * 100.0,
+        );
+    }
+}
+
+/// Premium, claims and market capital by quarter.
+fn print_quarterly(quarters: &[rins::analysis::QuarterStats]) {
+    const CENTS_PER_BUSD: f64 = 100_000_000_000.0; // cents per billion USD
+    println!("\n=== Quarterly ===");
+    if quarters.is_empty() {
+        println!("  no QuarterEnd events (run with --quarterly)");
+        return;
+    }
+    println!("{:>7} | {:>8} | {:>9} | {:>7} | {:>11} | {:>10}", "Quarter", "Prem(B)", "Claims(B)", "LossR%", "TotalCap(B)", "CapDelta(B)");
+    println!("{}", "-".repeat(7 + 8 + 9 + 7 + 11 + 10 + 5 * 3));
+    for q in quarters {
+        println!(
+            "{:>4} Q{} | {:>8.2} | {:>9.2} | {:>6.1}% | {:>11.2} | {:>+10.2}",
+            q.year,
+            q.quarter,
+            q.written_premium as f64 / CENTS_PER_BUSD,
+            q.claims_incurred as f64 / CENTS_PER_BUSD,
+            q.loss_ratio() * 100.0,
+            q.capital as f64 / CENTS_PER_BUSD,
+            q.capital_change as f64 / CENTS_PER_BUSD,
+        );
+    }
+}
+
+/// Year character table, then the cycle diagnostics.
+fn print_year_table(
+    warmup: u32,
+    stats: &[rins::analysis::YearStats],
+    expense_ratio: f64,
+    sensitivity_by_year: &std::collections::HashMap<u32, (f64, f64, f64, f64, f64)>,
+    real_terms: Option<&InflationConfig>,
+) {
     // ── Year character table ──────────────────────────────────────────────────
-    let (warmup, stats) = analysis::analyse(log, initial_capitals, expense_ratio);
+    let stats = match real_terms {
+        Some(inflation) => analysis::deflate(stats, inflation),
+        None => stats.to_vec(),
+    };
 
     if stats.is_empty() {
         return;
@@ -205,14 +1191,15 @@ fn print_analysis(
 
     let last_year = stats.last().map(|s| s.year).unwrap_or(0);
     println!(
-        "\n=== Year character table (warmup: {warmup}, analysis: years {}–{last_year}) ===",
-        warmup + 1
+        "\n=== Year character table (warmup: {warmup}, analysis: years {}–{last_year}){} ===",
+        warmup + 1,
+        if real_terms.is_some() { ", real year-1 values" } else { "" }
     );
     println!(
-        "{:>4} | {:>9} | {:>8} | {:>8} | {:>8} | {:>9} | {:>8} | {:>8} | {:>8} | {:>8} | {:>7} | {:>5} | {:>11} | {:>10} | {:>9} | {:>9} | {:>7} | {:>8} | {:>8} | {:>6} | {:>10} | {:>6} | {:>7} | {:>7} | {:>8}",
-        "Year", "Assets(B)", "GUL(B)", "CatGUL%", "Cov(B)", "Claims(B)", "LossR%", "FeLR%", "CombR%", "CrEwma%", "Rate%", "Cats#", "TotalCap(B)", "Distrib(B)", "CapDelta(B)", "NetRet(B)", "InForce", "Dropped#", "Reject#", "ApTp", "Insurers", "Gini", "CrSens", "CapSens", "AvgLine%"
+        "{:>4} | {:>9} | {:>8} | {:>8} | {:>8} | {:>9} | {:>8} | {:>8} | {:>8} | {:>8} | {:>8} | {:>7} | {:>5} | {:>11} | {:>10} | {:>9} | {:>9} | {:>7} | {:>8} | {:>8} | {:>6} | {:>10} | {:>6} | {:>7} | {:>7} | {:>8}",
+        "Year", "Assets(B)", "GUL(B)", "CatGUL%", "Cov(B)", "Claims(B)", "LossR%", "FeLR%", "EarnLR%", "CombR%", "CrEwma%", "Rate%", "Cats#", "TotalCap(B)", "Distrib(B)", "CapDelta(B)", "NetRet(B)", "InForce", "Dropped#", "Reject#", "ApTp", "Insurers", "Gini", "CrSens", "CapSens", "AvgLine%"
     );
-    println!("{}", "-".repeat(4 + 3 + 11 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 11 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 9 + 3 + 7 + 3 + 13 + 3 + 12 + 3 + 11 + 3 + 11 + 3 + 9 + 3 + 10 + 3 + 8 + 3 + 10 + 3 + 6 + 3 + 7 + 3 + 7 + 3 + 8));
+    println!("{}", "-".repeat(4 + 3 + 11 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 11 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 10 + 3 + 9 + 3 + 7 + 3 + 13 + 3 + 12 + 3 + 11 + 3 + 11 + 3 + 9 + 3 + 10 + 3 + 8 + 3 + 10 + 3 + 6 + 3 + 7 + 3 + 7 + 3 + 8));
 
     const CENTS_PER_BUSD: f64 = 100_000_000_000.0; // cents per billion USD
 
@@ -222,7 +1209,7 @@ fn print_analysis(
 
     for s in &stats {
         let assets_b = s.total_assets as f64 / CENTS_PER_BUSD;
-        let total_gul = s.attr_gul + s.cat_gul;
+        let total_gul = s.attr_gul + s.cat_gul + s.large_gul + s.casualty_gul;
         let gul_b = total_gul as f64 / CENTS_PER_BUSD;
         let cat_gul_pct = if total_gul > 0 { s.cat_gul as f64 / total_gul as f64 * 100.0 } else { 0.0 };
         let cov_b = s.sum_insured as f64 / CENTS_PER_BUSD;
@@ -267,7 +1254,7 @@ fn print_analysis(
         let net_ret_b = (s.bound_premium as f64 * (1.0 - expense_ratio) - s.claims as f64) / CENTS_PER_BUSD;
         prev_cap = Some(s.total_capital);
         println!(
-            "{:>4} | {:>9.2} | {:>8.2} | {:>7.1}% | {:>8.2} | {:>9.2} | {:>7.1}% | {:>7.1}% | {:>7.1}% | {} | {:>6.2}% | {:>5} | {:>11.2} | {:>10.2} | {:>+9.2} | {:>9.2} | {:>7} | {:>8} | {:>8} | {} | {} | {:>6.3} | {:>7.2} | {:>7.2} | {:>7.1}%",
+            "{:>4} | {:>9.2} | {:>8.2} | {:>7.1}% | {:>8.2} | {:>9.2} | {:>7.1}% | {:>7.1}% | {:>7.1}% | {:>7.1}% | {} | {:>6.2}% | {:>5} | {:>11.2} | {:>10.2} | {:>+9.2} | {:>9.2} | {:>7} | {:>8} | {:>8} | {} | {} | {:>6.3} | {:>7.2} | {:>7.2} | {:>7.1}%",
             s.year,
             assets_b,
             gul_b,
@@ -276,6 +1263,7 @@ fn print_analysis(
             claims_b,
             s.loss_ratio() * 100.0,
             s.loss_ratio_full_exposure() * 100.0,
+            s.loss_ratio_earned() * 100.0,
             s.combined_ratio(expense_ratio) * 100.0,
             avg_cr_str,
             s.rate_on_line() * 100.0,
@@ -295,25 +1283,86 @@ fn print_analysis(
             s.avg_line_pct,
         );
     }
+
+    print_cycle(&cycle::cycle(std::slice::from_ref(&stats), CYCLE_MAX_LAG));
+}
+
+/// Longest ACF lag reported in the cycle diagnostics (years).
+const CYCLE_MAX_LAG: usize = 10;
+
+/// Cat-hit years at or above this quantile of cat GUL count as severe in the rate response.
+const SEVERE_CAT_QUANTILE: f64 = 0.75;
+
+fn print_rate_response(response: &cycle::RateResponse) {
+    println!(
+        "\n=== Rate response to cat losses (severe: cat GUL ≥ {:.2}% of sum insured) ===",
+        response.severe_threshold * 100.0
+    );
+    println!("{:>9} | {:>5} | {:>7} {:>7} {:>7} {:>7} {:>7} | {:>7}", "Cat year", "Years", "p5", "p25", "p50", "p75", "p95", "2y p50");
+    for row in &response.rows {
+        let label = match row.bucket {
+            cycle::CatLossBucket::None => "none",
+            cycle::CatLossBucket::Moderate => "moderate",
+            cycle::CatLossBucket::Severe => "severe",
+        };
+        let two_year = row.two_year.as_ref().map_or("n/a".to_string(), |d| format!("{:+.1}%", d.p50 * 100.0));
+        match row.next_year {
+            Some(ref d) => println!(
+                "{label:>9} | {:>5} | {:>+6.1}% {:>+6.1}% {:>+6.1}% {:>+6.1}% {:>+6.1}% | {two_year:>7}",
+                row.years,
+                d.p5 * 100.0,
+                d.p25 * 100.0,
+                d.p50 * 100.0,
+                d.p75 * 100.0,
+                d.p95 * 100.0,
+            ),
+            None => println!("{label:>9} | {:>5} | {:>39} | {two_year:>7}", 0, "no years"),
+        }
+    }
+}
+
+fn print_cycle(report: &cycle::CycleReport) {
+    let c = &report.pooled;
+    println!("\n=== Underwriting cycle (detrended rate on line) ===");
+    let acf: Vec<String> = c.acf.iter().skip(1).map(|r| format!("{r:+.2}")).collect();
+    println!("  ACF lags 1–{}: {}", c.acf.len().saturating_sub(1), acf.join(" "));
+    match (c.period, c.peak_to_trough) {
+        (Some(period), Some(swing)) => {
+            println!("  Period: {period:.1} years   Peak-to-trough: {:.1}% of mean RoL", swing * 100.0)
+        }
+        (Some(period), None) => println!("  Period: {period:.1} years   Peak-to-trough: n/a"),
+        _ => println!("  No cycle: detrended RoL crosses its trend fewer than twice"),
+    }
+    if report.per_run.len() > 1
+        && let Some(ref d) = report.run_periods
+    {
+        println!(
+            "  Runs with a cycle: {}/{}   Per-run period p5/p50/p95: {:.1} / {:.1} / {:.1} years",
+            d.n,
+            report.per_run.len(),
+            d.p5,
+            d.p50,
+            d.p95,
+        );
+    }
 }
 
 fn write_runs_csv(
     all_stats: &[Vec<rins::analysis::YearStats>],
-    start_seed: u64,
+    seeds: &[u64],
     expense_ratio: f64,
     path: &str,
 ) {
     const CENTS_PER_BUSD: f64 = 100_000_000_000.0;
     let file = File::create(path).unwrap_or_else(|e| panic!("failed to create {path}: {e}"));
     let mut w = BufWriter::new(file);
-    writeln!(w, "seed,year,loss_ratio,combined_ratio,rate_on_line,total_cap_b,cat_events,insolvent_count,dropped_count,entrant_count")
+    writeln!(w, "seed,year,loss_ratio,combined_ratio,rate_on_line,total_cap_b,cat_events,insolvent_count,dropped_count,entrant_count,earned_loss_ratio")
         .expect("write");
-    for (i, run) in all_stats.iter().enumerate() {
-        let seed = start_seed + i as u64;
+    for (&seed, run) in seeds.iter().zip(all_stats) {
         for s in run {
             writeln!(
                 w,
-                "{},{},{:.6},{:.6},{:.6},{:.6},{},{},{},{}",
+                "{},{},{:.6},{:.6},{:.6},{:.6},{},{},{},{},{:.6}",
                 seed,
                 s.year,
                 s.loss_ratio(),
@@ -324,6 +1373,81 @@ fn write_runs_csv(
                 s.insolvent_count,
                 s.dropped_count,
                 s.entrant_count,
+                s.loss_ratio_earned(),
+            )
+            .expect("write");
+        }
+    }
+}
+
+/// Cross-run tail metrics for `--csv <path>` with `--runs`: `<stem>_tail.csv` per year and
+/// `<stem>_ruin.csv` per insurer, next to the per-run CSV.
+fn write_tail_csv(dists: &[rins::analysis::YearDist], ruin: &BTreeMap<InsurerId, f64>, path: &str) {
+    let stem = path.strip_suffix(".csv").unwrap_or(path);
+    let tail_path = format!("{stem}_tail.csv");
+    let file = File::create(&tail_path).unwrap_or_else(|e| panic!("failed to create {tail_path}: {e}"));
+    let mut w = BufWriter::new(file);
+    writeln!(w, "year,runs,p_insolvency,expected_insolvencies,cap_var99_b,cap_tvar99_b").expect("write");
+    for yd in dists {
+        writeln!(
+            w,
+            "{},{},{:.6},{:.6},{:.6},{:.6}",
+            yd.year, yd.total_cap_b.n, yd.p_insolvency, yd.insolvents.mean, yd.cap_var99_b, yd.cap_tvar99_b,
+        )
+        .expect("write");
+    }
+
+    let ruin_path = format!("{stem}_ruin.csv");
+    let file = File::create(&ruin_path).unwrap_or_else(|e| panic!("failed to create {ruin_path}: {e}"));
+    let mut w = BufWriter::new(file);
+    writeln!(w, "insurer_id,ruin_probability").expect("write");
+    for (insurer_id, p) in ruin {
+        writeln!(w, "{},{:.6}", insurer_id.0, p).expect("write");
+    }
+}
+
+fn write_summary_json(summary: &BatchSummary, path: &str) {
+    let json = serde_json::to_string_pretty(summary).expect("serialize summary");
+    std::fs::write(path, json).unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+}
+
+fn write_insured_panel_csv(panel: &[rins::analysis::InsuredYear], path: &str) {
+    let file = File::create(path).unwrap_or_else(|e| panic!("failed to create {path}: {e}"));
+    let mut w = BufWriter::new(file);
+    writeln!(w, "year,insured_id,premium_paid,ground_up_losses,attr_gul,cat_gul,large_gul,casualty_gul,recoveries,uninsured_loss,insured")
+        .expect("write");
+    for r in panel {
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            r.year,
+            r.insured_id.0,
+            r.premium_paid,
+            r.ground_up_losses,
+            r.attr_gul,
+            r.cat_gul,
+            r.large_gul,
+            r.casualty_gul,
+            r.recoveries,
+            r.uninsured_loss,
+            r.insured as u8,
+        )
+        .expect("write");
+    }
+}
+
+fn write_insurer_csv(by_insurer: &HashMap<InsurerId, Vec<rins::analysis::InsurerYearStats>>, path: &str) {
+    let file = File::create(path).unwrap_or_else(|e| panic!("failed to create {path}: {e}"));
+    let mut w = BufWriter::new(file);
+    writeln!(w, "insurer_id,year,premium,claims,loss_ratio,capital,cat_share,policies_written").expect("write");
+    let mut insurer_ids: Vec<&InsurerId> = by_insurer.keys().collect();
+    insurer_ids.sort();
+    for insurer_id in insurer_ids {
+        for r in &by_insurer[insurer_id] {
+            writeln!(
+                w,
+                "{},{},{},{},{:.6},{},{:.6},{}",
+                insurer_id.0, r.year, r.premium, r.claims, r.loss_ratio(), r.capital, r.cat_share(), r.policies_written,
             )
             .expect("write");
         }
@@ -332,7 +1456,7 @@ fn write_runs_csv(
 
 fn print_all_run_years(
     all_stats: &[Vec<rins::analysis::YearStats>],
-    start_seed: u64,
+    seeds: &[u64],
     expense_ratio: f64,
 ) {
     const CENTS_PER_BUSD: f64 = 100_000_000_000.0;
@@ -345,8 +1469,7 @@ fn print_all_run_years(
     );
     println!("{}", "-".repeat(80));
 
-    for (i, run) in all_stats.iter().enumerate() {
-        let seed = start_seed + i as u64;
+    for (&seed, run) in seeds.iter().zip(all_stats) {
         for s in run {
             println!(
                 "{:>6} | {:>4} | {:>6.1}% | {:>6.1}% | {:>5.2}% | {:>11.2} | {:>5} | {:>6} | {:>5} | {:>5}",
@@ -392,7 +1515,7 @@ where
     }
 }
 
-fn print_distributions(dists: &[rins::analysis::YearDist], n_runs: u64) {
+fn print_distributions(dists: &[rins::analysis::YearDist], ruin: &BTreeMap<InsurerId, f64>, n_runs: u64) {
     println!("\n=== Multi-Run Distribution (N={n_runs} runs) ===");
 
     print_dist_section("LossR%", dists, 100.0, |yd| &yd.loss_ratio);
@@ -420,4 +1543,98 @@ fn print_distributions(dists: &[rins::analysis::YearDist], n_runs: u64) {
             yd.entrants.max,
         );
     }
+
+    println!("\n--- Tail Risk ---");
+    println!(
+        "{:>4} | {:>8} | {:>8} | {:>12} | {:>13}",
+        "Year", "P(insol)", "E[insol]", "Cap VaR99(B)", "Cap TVaR99(B)"
+    );
+    for yd in dists {
+        println!(
+            "{:>4} | {:>7.1}% | {:>8.2} | {:>12.2} | {:>13.2}",
+            yd.year,
+            yd.p_insolvency * 100.0,
+            yd.insolvents.mean,
+            yd.cap_var99_b,
+            yd.cap_tvar99_b,
+        );
+    }
+    if ruin.is_empty() {
+        println!("  No insurer failed in any run.");
+    } else {
+        let by_insurer: Vec<String> =
+            ruin.iter().map(|(id, p)| format!("{}: {:.1}%", id.0, p * 100.0)).collect();
+        println!("  Ruin probability by insurer — {}", by_insurer.join(", "));
+    }
+}
+
+fn print_stylized_facts(all_stats: &[Vec<rins::analysis::YearStats>], expense_ratio: f64) {
+    let facts = stylized_facts::canonical_facts();
+    let results = stylized_facts::evaluate(&facts, all_stats, expense_ratio);
+    println!("\n=== Stylised facts ({} run(s)) ===", all_stats.len());
+    print_fact_results(&facts, &results);
+}
+
+fn print_fact_results(facts: &[stylized_facts::StylisedFact], results: &[stylized_facts::FactResult]) {
+    println!(
+        "{:>26} | {:>13} | {:>8} | {:>4} | {:>7} | {:>7} | Result",
+        "Fact", "Target", "Value", "Runs", "InBand%", "Effect"
+    );
+    for (fact, r) in facts.iter().zip(results) {
+        let value = r.value.map_or("n/a".to_string(), |v| format!("{v:.2}"));
+        println!(
+            "{:>26} | {:>13} | {:>8} | {:>4} | {:>6.0}% | {:>+7.2} | {}",
+            r.name,
+            format!("{}–{}", r.lo, r.hi),
+            value,
+            r.runs,
+            r.in_band * 100.0,
+            r.effect_size,
+            if r.pass { "PASS" } else { "FAIL" },
+        );
+        println!("{:>26}   {}", "", fact.description);
+    }
+}
+
+fn print_calibration(
+    history: &[HistoricalYear],
+    all_stats: &[Vec<rins::analysis::YearStats>],
+    expense_ratio: f64,
+) {
+    let targets = calibration::calibration_targets(history);
+    let first = history.first().map(|h| h.year).unwrap_or(0);
+    let last = history.last().map(|h| h.year).unwrap_or(0);
+    println!("\n=== Calibration vs history ({first}–{last}, {} years) ===", targets.years);
+    println!(
+        "  Targets: CR mean {:.1}% (p5 {:.1}%, p95 {:.1}%), CR lag-1 AC {:.2}, rate-index CV {:.3}, rate lag-1 AC {:.2}, cat losses mean {:.1}B (CV {:.2})",
+        targets.combined_ratio.mean * 100.0,
+        targets.combined_ratio.p5 * 100.0,
+        targets.combined_ratio.p95 * 100.0,
+        targets.cr_lag1_autocorr,
+        targets.rate_index.std_dev,
+        targets.rate_lag1_autocorr,
+        targets.cat_losses_b.mean,
+        targets.cat_loss_index.std_dev,
+    );
+    println!(
+        "{:>14} | {:>8} | {:>8} | {:>8} | {:>8} | {:>8} | {:>8} | {:>6} | {:>7} | {:>7} | {:>7}",
+        "Metric", "HistMean", "SimMean", "HistSD", "SimSD", "HistP95", "SimP95", "KS D", "KS p", "HistAC", "SimAC"
+    );
+    println!("{}", "-".repeat(14 + 8 * 6 + 6 + 7 * 3 + 3 * 10));
+    for f in calibration::goodness_of_fit(history, all_stats, expense_ratio) {
+        println!(
+            "{:>14} | {:>8.3} | {:>8.3} | {:>8.3} | {:>8.3} | {:>8.3} | {:>8.3} | {:>6.3} | {:>7.4} | {:>7.2} | {:>7.2}",
+            f.metric,
+            f.historical.mean,
+            f.simulated.mean,
+            f.historical.std_dev,
+            f.simulated.std_dev,
+            f.historical.p95,
+            f.simulated.p95,
+            f.ks_statistic,
+            f.ks_p_value,
+            f.historical_lag1_autocorr,
+            f.simulated_lag1_autocorr,
+        );
+    }
 }
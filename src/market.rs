@@ -1,24 +1,96 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AggregateDeductibleConfig, ClaimsDevelopmentConfig, PolicyTermConfig, PremiumFinanceConfig};
+use crate::events::{CancellationBasis, CancellationReason, Event, Peril, Risk};
+use crate::types::{ClaimId, Day, InsuredId, InsurerId, PolicyId, SubmissionId, Territory, Year};
+
+/// Basis points in a whole (100%) line.
+pub const FULL_LINE_BPS: u32 = 10_000;
+
+/// Cat fan-outs over at least this many insureds compute their per-insured work on the rayon
+/// pool. Smaller ones stay serial, where the pool's overhead outweighs the work.
+pub const PARALLEL_FAN_OUT: usize = 1_024;
+
+/// Sign a panel's fractional lines in whole basis points (leader first, order preserved).
+///
+/// Largest-remainder rounding guarantees the signed lines sum to exactly `FULL_LINE_BPS`;
+/// ties go to the earlier (higher-ranked) panel member. Lines that round to zero are dropped.
+pub fn sign_lines(panel: &[(InsurerId, f64)]) -> Vec<(InsurerId, u32)> {
+    let total: f64 = panel.iter().map(|&(_, s)| s).sum();
+    if panel.is_empty() || total <= 0.0 {
+        return vec![];
+    }
+    let exact: Vec<f64> = panel.iter().map(|&(_, s)| s / total * FULL_LINE_BPS as f64).collect();
+    let mut bps: Vec<u32> = exact.iter().map(|x| x.floor() as u32).collect();
+    let short = FULL_LINE_BPS - bps.iter().sum::<u32>();
+    let mut by_remainder: Vec<usize> = (0..panel.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        let ra = exact[a] - exact[a].floor();
+        let rb = exact[b] - exact[b].floor();
+        rb.partial_cmp(&ra).unwrap_or(std::cmp::Ordering::Equal).then(a.cmp(&b))
+    });
+    for &i in by_remainder.iter().take(short as usize) {
+        bps[i] += 1;
+    }
+    panel
+        .iter()
+        .zip(bps)
+        .filter(|&(_, b)| b > 0)
+        .map(|(&(id, _), b)| (id, b))
+        .collect()
+}
 
-use crate::events::{Event, Peril, Risk};
-use crate::types::{Day, InsuredId, InsurerId, PolicyId, SubmissionId, Year};
+/// Split a policy's insured loss across its signed panel (amounts in panel order).
+///
+/// Integer split: each member pays ⌊loss × bps / 10_000⌋ and the leader absorbs the rounding
+/// residual, so the amounts sum to `insured_loss` exactly. Members whose amount is zero (tiny
+/// loss × small line) get no claim.
+fn panel_amounts(insured_loss: u64, signed_bps: &[(InsurerId, u32)]) -> Vec<u64> {
+    let mut amounts: Vec<u64> = signed_bps
+        .iter()
+        .map(|&(_, bps)| (insured_loss as u128 * bps as u128 / FULL_LINE_BPS as u128) as u64)
+        .collect();
+    let residual = insured_loss - amounts.iter().sum::<u64>();
+    if let Some(lead) = amounts.first_mut() {
+        *lead += residual;
+    }
+    amounts
+}
 
 /// A successfully bound policy.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BoundPolicy {
     pub policy_id: PolicyId,
     pub submission_id: SubmissionId,
     pub insured_id: InsuredId,
     /// Panel of insurers writing this policy: (insurer_id, line_share), shares sum to 1.0.
+    /// Each share is the signed line (`signed_bps / 10_000`).
     pub panel: Vec<(InsurerId, f64)>,
+    /// Signed lines in basis points, leader first; sum to exactly `FULL_LINE_BPS`.
+    /// Claims are split on these integer lines so a panel's claims sum to the loss exactly.
+    pub signed_bps: Vec<(InsurerId, u32)>,
     pub risk: Risk,
     pub premium: u64,
     pub bound_year: Year,
-    /// The day the matching `PolicyExpired` event fires (= bound_day + 360).
+    /// Policy term in years (1 = annual).
+    pub term_years: u32,
+    /// The day the matching `PolicyExpired` event fires (= bound_day + 360 × term_years).
     /// Used by `on_loss_event` to guard against the DES race where a `LossEvent`
     /// and `PolicyExpired` share the same day but the loss fires first.
     pub expire_day: Day,
 }
 
+impl BoundPolicy {
+    /// The day the policy was bound (its `PolicyBound` day).
+    pub fn bind_day(&self) -> Day {
+        Day(self.expire_day.0 - self.term_years as u64 * Day::DAYS_PER_YEAR)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Market {
     next_policy_id: u64,
     /// Policies created by QuoteAccepted but not yet activated (PolicyBound not yet fired).
@@ -29,10 +101,64 @@ pub struct Market {
     pub insured_active_policies: HashMap<InsuredId, PolicyId>,
     /// Per-(policy, year) remaining insurable asset value.
     /// Initialized to sum_insured on first hit; decremented to prevent aggregate GUL > sum_insured.
+    #[serde(with = "pair_keyed")]
     remaining_asset_value: HashMap<(PolicyId, Year), u64>,
+    /// Per-(policy, year) annual aggregate deductible eroded so far. Only populated when
+    /// `aggregate_deductible` is set.
+    #[serde(with = "pair_keyed")]
+    aggregate_eroded: HashMap<(PolicyId, Year), u64>,
     /// insured_id → (territory, sum_insured). Populated via register_insured() at CoverageRequested time.
     /// Used by on_loss_event to emit AssetDamage only for insureds in the struck territory.
-    pub insured_registry: HashMap<InsuredId, (String, u64)>,
+    pub insured_registry: HashMap<InsuredId, (Territory, u64)>,
+    /// When set, panel claims are reported and paid in instalments instead of settling instantly.
+    claims_development: Option<ClaimsDevelopmentConfig>,
+    next_claim_id: u64,
+    /// When set, premium is collected in instalments and a default cancels the policy.
+    premium_finance: Option<PremiumFinanceConfig>,
+    /// When set, policies run for `term_years` instead of one year.
+    policy_terms: Option<PolicyTermConfig>,
+    /// When set, each policy retains an annual aggregate deductible before claims flow.
+    aggregate_deductible: Option<AggregateDeductibleConfig>,
+    /// Cat occurrences routed by `route_occurrence` whose claims `close_occurrence` has yet to
+    /// release, by event_id.
+    routed_occurrences: HashMap<u64, RoutedOccurrence>,
+}
+
+/// One cat occurrence's claims, routed when its `LossEvent` fires.
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct RoutedOccurrence {
+    /// Insured loss across the struck policies, after deductibles and limits.
+    insured_loss: u64,
+    /// `DeductibleEroded`, `ClaimSettled` / `ClaimReported` / `ClaimPaid`, in damage order.
+    claims: Vec<(Day, Event)>,
+}
+
+/// A damage's claim against its policy: `Market::covered_loss` and the panel split.
+type Route = (Day, InsuredId, Peril, (PolicyId, u64, u64, u64), Vec<u64>);
+
+/// Serde adapter for maps keyed by tuples, which JSON cannot use as object keys:
+/// the map is written as a list of `[key, value]` pairs.
+mod pair_keyed {
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K: Serialize, V: Serialize, S: Serializer>(
+        map: &HashMap<K, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
 }
 
 impl Default for Market {
@@ -49,18 +175,51 @@ impl Market {
             policies: HashMap::new(),
             insured_active_policies: HashMap::new(),
             remaining_asset_value: HashMap::new(),
+            aggregate_eroded: HashMap::new(),
             insured_registry: HashMap::new(),
+            claims_development: None,
+            next_claim_id: 0,
+            premium_finance: None,
+            policy_terms: None,
+            aggregate_deductible: None,
+            routed_occurrences: HashMap::new(),
         }
     }
 
+    /// Report and pay claims over a development pattern rather than settling on the loss day.
+    pub fn with_claims_development(mut self, development: Option<ClaimsDevelopmentConfig>) -> Self {
+        self.claims_development = development;
+        self
+    }
+
+    /// Collect premium in instalments; `PremiumDefaulted` cancels the policy.
+    pub fn with_premium_finance(mut self, premium_finance: Option<PremiumFinanceConfig>) -> Self {
+        self.premium_finance = premium_finance;
+        self
+    }
+
+    /// Bind policies for `policy_terms.term_years` rather than one year.
+    pub fn with_policy_terms(mut self, policy_terms: Option<PolicyTermConfig>) -> Self {
+        self.policy_terms = policy_terms;
+        self
+    }
+
+    /// Retain an annual aggregate deductible of `fraction × sum_insured` on every policy.
+    pub fn with_aggregate_deductible(mut self, aggregate_deductible: Option<AggregateDeductibleConfig>) -> Self {
+        self.aggregate_deductible = aggregate_deductible;
+        self
+    }
+
     /// Register an insured in the market registry. Called at `CoverageRequested` time.
-    /// Idempotent — only the first call for each `insured_id` takes effect.
-    pub fn register_insured(&mut self, insured_id: InsuredId, territory: &str, sum_insured: u64) {
-        self.insured_registry.entry(insured_id).or_insert((territory.to_string(), sum_insured));
+    /// Re-registering updates the sum insured, so cat losses follow revalued assets.
+    pub fn register_insured(&mut self, insured_id: InsuredId, territory: Territory, sum_insured: u64) {
+        self.insured_registry.insert(insured_id, (territory, sum_insured));
     }
 
-    /// Insured has accepted a quote. Create the policy record (not yet loss-eligible) and
-    /// schedule `PolicyBound` at `day+1` and `PolicyExpired` at `day+361`.
+    /// Insured has accepted a quote. Sign the panel's lines in basis points, create the policy
+    /// record (not yet loss-eligible) and schedule `PolicyBound` at `day+1` and `PolicyExpired`
+    /// at `day+361` (`day + 1 + 360 × term_years` under multi-year terms).
+    /// `PolicyBound.panel` carries the signed shares.
     pub fn on_quote_accepted(
         &mut self,
         day: Day,
@@ -75,8 +234,14 @@ impl Market {
         self.next_policy_id += 1;
 
         let bind_day = day.offset(1);
-        let expire_day = day.offset(361);
+        let term_years = self.policy_terms.as_ref().map_or(1, |t| t.term_years.max(1));
+        let expire_day = day.offset(1 + term_years as u64 * Day::DAYS_PER_YEAR);
         let sum_insured = risk.sum_insured;
+        let signed_bps = sign_lines(&panel);
+        let panel: Vec<(InsurerId, f64)> = signed_bps
+            .iter()
+            .map(|&(id, bps)| (id, bps as f64 / FULL_LINE_BPS as f64))
+            .collect();
 
         self.pending_policies.insert(
             policy_id,
@@ -85,9 +250,11 @@ impl Market {
                 submission_id,
                 insured_id,
                 panel: panel.clone(),
+                signed_bps,
                 risk,
                 premium,
                 bound_year: year,
+                term_years,
                 expire_day,
             },
         );
@@ -128,6 +295,122 @@ impl Market {
         }
     }
 
+    /// Live exposure per (insurer, territory, peril): Σ sum_insured × signed line over in-force
+    /// policies covering the peril. Lines are taken in whole basis points, so the figure can be
+    /// rebuilt exactly from the log.
+    pub fn exposure_by_insurer(&self) -> BTreeMap<(InsurerId, Territory, Peril), u64> {
+        let mut exposure = BTreeMap::new();
+        for policy in self.policies.values() {
+            for &(insurer_id, bps) in &policy.signed_bps {
+                let line = policy.risk.sum_insured * bps as u64 / FULL_LINE_BPS as u64;
+                for &peril in &policy.risk.perils_covered {
+                    *exposure.entry((insurer_id, policy.risk.territory, peril)).or_default() += line;
+                }
+            }
+        }
+        exposure
+    }
+
+    /// In-force policies on which `insurer_id` writes a line, in policy-id order.
+    pub fn policies_written_by(&self, insurer_id: InsurerId) -> Vec<PolicyId> {
+        let mut ids: Vec<PolicyId> = self
+            .policies
+            .values()
+            .filter(|p| p.panel.iter().any(|&(id, _)| id == insurer_id))
+            .map(|p| p.policy_id)
+            .collect();
+        ids.sort_unstable_by_key(|id| id.0);
+        ids
+    }
+
+    /// Each insurer's share of the gross premium on its in-force lines not yet earned at the end
+    /// of `day`. A policy earns its premium pro rata over each 360-day cover year; every year
+    /// of a multi-year term is valued at the bound premium.
+    pub fn unearned_premium(&self, day: Day) -> HashMap<InsurerId, u64> {
+        let mut unearned: HashMap<InsurerId, f64> = HashMap::new();
+        for policy in self.policies.values() {
+            let Some(left) = policy.expire_day.0.checked_sub(day.0 + 1) else { continue };
+            let fraction = (left % Day::DAYS_PER_YEAR) as f64 / Day::DAYS_PER_YEAR as f64;
+            for &(insurer_id, share) in &policy.panel {
+                *unearned.entry(insurer_id).or_insert(0.0) += policy.premium as f64 * share * fraction;
+            }
+        }
+        unearned.into_iter().map(|(id, u)| (id, u.round() as u64)).collect()
+    }
+
+    /// Portfolio transfer: move `from`'s line on each of `policy_ids` to `to`, keeping the
+    /// panel order. If `to` already writes the policy the two lines merge into its entry.
+    /// Future claims on these policies route to `to`.
+    pub fn transfer_lines(&mut self, from: InsurerId, to: InsurerId, policy_ids: &[PolicyId]) {
+        fn merge<T: Copy + std::ops::AddAssign>(panel: &mut Vec<(InsurerId, T)>, from: InsurerId, to: InsurerId) {
+            let Some(i) = panel.iter().position(|&(id, _)| id == from) else { return };
+            match panel.iter().position(|&(id, _)| id == to) {
+                Some(j) => {
+                    let (_, line) = panel.remove(i);
+                    let j = if j > i { j - 1 } else { j };
+                    panel[j].1 += line;
+                }
+                None => panel[i].0 = to,
+            }
+        }
+        for id in policy_ids {
+            if let Some(policy) = self.policies.get_mut(id) {
+                merge(&mut policy.panel, from, to);
+                merge(&mut policy.signed_bps, from, to);
+            }
+        }
+    }
+
+    /// The insured missed premium instalment `instalment`; instalments before it were collected.
+    /// Schedule `PolicyCancelled` with policy-level gross amounts:
+    /// - `AbInitio`: cancelled on the default day; all uncollected premium is reversed.
+    /// - `ProRata`: cancelled after `grace_days` (no later than the day before the first
+    ///   anniversary, which is expiry for an annual policy); premium
+    ///   earned to the cancellation day but not collected is bad debt, the rest is reversed.
+    ///
+    /// No-op without premium finance or if the policy is no longer active.
+    pub fn on_premium_defaulted(
+        &self,
+        day: Day,
+        policy_id: PolicyId,
+        instalment: u32,
+    ) -> Vec<(Day, Event)> {
+        let (Some(pf), Some(policy)) = (self.premium_finance.as_ref(), self.policies.get(&policy_id))
+        else {
+            return vec![];
+        };
+        let n = pf.instalments.max(1) as u64;
+        let collected = policy.premium * (instalment as u64).min(n) / n;
+        let uncollected = policy.premium - collected;
+        let (cancel_day, bad_debt) = match pf.basis {
+            CancellationBasis::AbInitio => (day, 0),
+            CancellationBasis::ProRata => {
+                let bind_day = policy.bind_day().0;
+                let cancel_day = (day.0 + pf.grace_days).min(bind_day + Day::DAYS_PER_YEAR - 1);
+                let earned = (policy.premium as u128 * (cancel_day - bind_day) as u128
+                    / Day::DAYS_PER_YEAR as u128) as u64;
+                (Day(cancel_day), earned.saturating_sub(collected).min(uncollected))
+            }
+        };
+        vec![(
+            cancel_day,
+            Event::PolicyCancelled {
+                policy_id,
+                insured_id: policy.insured_id,
+                reason: CancellationReason::PremiumDefault,
+                basis: pf.basis,
+                premium_reversed: uncollected - bad_debt,
+                bad_debt,
+            },
+        )]
+    }
+
+    /// Remove a cancelled policy. No further claims route to it; its `PolicyExpired` later
+    /// finds nothing to remove.
+    pub fn on_policy_cancelled(&mut self, policy_id: PolicyId) {
+        self.on_policy_expired(policy_id);
+    }
+
     /// A catastrophe loss event has fired. Emit `AssetDamage` for every registered
     /// insured **in the matching territory**.
     ///
@@ -139,24 +422,124 @@ impl Market {
         &self,
         day: Day,
         peril: Peril,
-        territory: &str,
+        territory: Territory,
         damage_fraction: f64,
     ) -> Vec<(Day, Event)> {
-        self.insured_registry
+        // Registry iteration order is per-process; sort so identical configs log identically.
+        let in_territory = |(&insured_id, &(t, sum_insured)): (&InsuredId, &(Territory, u64))| {
+            (t == territory).then_some((insured_id, sum_insured))
+        };
+        let mut hit: Vec<(InsuredId, u64)> = if self.insured_registry.len() >= PARALLEL_FAN_OUT {
+            self.insured_registry.par_iter().filter_map(in_territory).collect()
+        } else {
+            self.insured_registry.iter().filter_map(in_territory).collect()
+        };
+        hit.sort_unstable_by_key(|&(insured_id, _)| insured_id);
+        let damage = |(insured_id, sum_insured): (InsuredId, u64)| {
+            let gul = (damage_fraction * sum_insured as f64) as u64;
+            (gul > 0).then_some((day, Event::AssetDamage { insured_id, peril, ground_up_loss: gul }))
+        };
+        // An indexed parallel collect keeps the sorted order.
+        if hit.len() >= PARALLEL_FAN_OUT {
+            hit.into_par_iter().filter_map(damage).collect()
+        } else {
+            hit.into_iter().filter_map(damage).collect()
+        }
+    }
+
+    /// Route cat occurrence `event_id`'s `AssetDamage`s to the policies they claim against and
+    /// hold the claims until `close_occurrence`. Returns the occurrence's total GUL.
+    ///
+    /// The per-damage work — cover, layer and panel split — only reads the book, and an
+    /// occurrence strikes each insured once, so large occurrences compute it on the rayon pool.
+    /// The results are applied serially in damage order (eroding asset values and deductibles,
+    /// numbering developed claims), so the claims do not depend on the split.
+    pub fn route_occurrence(&mut self, event_id: u64, damages: &[(Day, Event)]) -> u64 {
+        let route = |(day, e): &(Day, Event)| -> Option<Route> {
+            let &Event::AssetDamage { insured_id, peril, ground_up_loss } = e else { return None };
+            let covered = self.covered_loss(*day, insured_id, ground_up_loss, peril)?;
+            let amounts = panel_amounts(covered.3, &self.policies[&covered.0].signed_bps);
+            Some((*day, insured_id, peril, covered, amounts))
+        };
+        let routes: Vec<Option<Route>> = if damages.len() >= PARALLEL_FAN_OUT {
+            damages.par_iter().map(route).collect()
+        } else {
+            damages.iter().map(route).collect()
+        };
+        let mut occurrence = RoutedOccurrence::default();
+        for (day, insured_id, peril, covered, amounts) in routes.into_iter().flatten() {
+            occurrence.insured_loss += covered.3;
+            occurrence.claims.extend(self.apply_route(day, day, insured_id, peril, covered, amounts));
+        }
+        self.routed_occurrences.insert(event_id, occurrence);
+        damages
             .iter()
-            .filter(|(_, (t, _))| t.as_str() == territory)
-            .filter_map(|(&insured_id, &(_, sum_insured))| {
-                let gul = (damage_fraction * sum_insured as f64) as u64;
-                if gul == 0 {
-                    return None;
-                }
-                Some((day, Event::AssetDamage { insured_id, peril, ground_up_loss: gul }))
+            .map(|(_, e)| match e {
+                Event::AssetDamage { ground_up_loss, .. } => *ground_up_loss,
+                _ => 0,
             })
-            .collect()
+            .sum()
+    }
+
+    /// Each insurer's share of routed occurrence `event_id`'s insured loss (the ultimate, under
+    /// claims development).
+    pub fn occurrence_losses_by_insurer(&self, event_id: u64) -> HashMap<InsurerId, u64> {
+        let mut losses = HashMap::new();
+        for (_, e) in self.routed_occurrences.get(&event_id).map_or(&[][..], |o| &o.claims) {
+            if let Event::ClaimSettled { insurer_id, amount, .. } | Event::ClaimReported { insurer_id, amount, .. } = e {
+                *losses.entry(*insurer_id).or_default() += amount;
+            }
+        }
+        losses
+    }
+
+    /// Stop tracking occurrence `event_id`: its insured loss and the claims to schedule.
+    pub fn close_occurrence(&mut self, event_id: u64) -> (u64, Vec<(Day, Event)>) {
+        self.routed_occurrences.remove(&event_id).map_or((0, vec![]), |o| (o.insured_loss, o.claims))
+    }
+
+    /// The policy a damage to `insured_id` claims against, the asset value left on it after
+    /// the damage, the amount retained against its annual aggregate deductible, and the insured
+    /// loss after the policy's deductibles and limit. None when the insured is uninsured (no
+    /// active policy, policy expired, or peril not covered).
+    fn covered_loss(
+        &self,
+        day: Day,
+        insured_id: InsuredId,
+        ground_up_loss: u64,
+        peril: Peril,
+    ) -> Option<(PolicyId, u64, u64, u64)> {
+        let &policy_id = self.insured_active_policies.get(&insured_id)?;
+        let policy = self.policies.get(&policy_id)?;
+        // expire_day race guard: policy covers [bound_day, expire_day).
+        if day >= policy.expire_day || !policy.risk.perils_covered.contains(&peril) {
+            return None;
+        }
+        // Liability does not consume the asset: each casualty claim sees the full limit.
+        let remaining = match peril {
+            Peril::Casualty => policy.risk.sum_insured,
+            _ => self.remaining_asset_value.get(&(policy_id, day.year())).copied().unwrap_or(policy.risk.sum_insured),
+        };
+        let effective_gul = ground_up_loss.min(remaining);
+        // Apply the policy's per-occurrence deductible and limit.
+        let layer_loss = effective_gul.saturating_sub(policy.risk.attachment).min(policy.risk.limit);
+        // The layer loss erodes the annual aggregate deductible before any of it is claimed.
+        let retained = layer_loss.min(self.aggregate_remaining(policy_id, policy, day.year()));
+        Some((policy_id, remaining - effective_gul, retained, layer_loss - retained))
+    }
+
+    /// Annual aggregate deductible still to erode on `policy` in `year`; 0 without one.
+    fn aggregate_remaining(&self, policy_id: PolicyId, policy: &BoundPolicy, year: Year) -> u64 {
+        let Some(aad) = self.aggregate_deductible.as_ref() else {
+            return 0;
+        };
+        let total = (aad.fraction.max(0.0) * policy.risk.sum_insured as f64).round() as u64;
+        total.saturating_sub(self.aggregate_eroded.get(&(policy_id, year)).copied().unwrap_or(0))
     }
 
     /// An `AssetDamage` event has fired for an insured. Routes to `ClaimSettled` only
-    /// when the insured holds an active policy that covers the peril.
+    /// when the insured holds an active policy that covers the peril. Cat damages are routed
+    /// by their occurrence instead (`route_occurrence`).
     /// Uninsured insureds (no active policy, policy expired, or peril not covered) generate
     /// no claim — the loss is counted in analysis but not passed to any insurer.
     pub fn on_asset_damage(
@@ -166,59 +549,136 @@ impl Market {
         ground_up_loss: u64,
         peril: Peril,
     ) -> Vec<(Day, Event)> {
-        // No active policy → uninsured; no claim.
-        let Some(&policy_id) = self.insured_active_policies.get(&insured_id) else {
+        self.route_damage(day, day, insured_id, ground_up_loss, peril)
+    }
+
+    /// A `Casualty` occurrence on accident day `day`, reported `report_lag_days` later. Cover
+    /// is decided on the accident day by the policy then in force (occurrence basis); the
+    /// panel's claims are reported, reserved and paid from the report day, possibly long after
+    /// the policy has expired.
+    pub fn on_casualty_damage(
+        &mut self,
+        day: Day,
+        report_lag_days: u64,
+        insured_id: InsuredId,
+        ground_up_loss: u64,
+    ) -> Vec<(Day, Event)> {
+        self.route_damage(day, day.offset(report_lag_days), insured_id, ground_up_loss, Peril::Casualty)
+    }
+
+    fn route_damage(
+        &mut self,
+        day: Day,
+        report_day: Day,
+        insured_id: InsuredId,
+        ground_up_loss: u64,
+        peril: Peril,
+    ) -> Vec<(Day, Event)> {
+        let Some(covered) = self.covered_loss(day, insured_id, ground_up_loss, peril) else {
             return vec![];
         };
-        let policy = match self.policies.get(&policy_id) {
-            Some(p) => p,
-            None => return vec![],
-        };
-        // expire_day race guard: policy covers [bound_day, expire_day).
-        if day >= policy.expire_day {
-            return vec![];
+        let amounts = panel_amounts(covered.3, &self.policies[&covered.0].signed_bps);
+        self.apply_route(day, report_day, insured_id, peril, covered, amounts)
+    }
+
+    /// Book a damage's claim: erode the asset value and aggregate deductible it consumes and
+    /// emit the panel's claims from `report_day`.
+    fn apply_route(
+        &mut self,
+        day: Day,
+        report_day: Day,
+        insured_id: InsuredId,
+        peril: Peril,
+        (policy_id, remaining, retained, insured_loss): (PolicyId, u64, u64, u64),
+        amounts: Vec<u64>,
+    ) -> Vec<(Day, Event)> {
+        if peril != Peril::Casualty {
+            self.remaining_asset_value.insert((policy_id, day.year()), remaining);
         }
-        if !policy.risk.perils_covered.contains(&peril) {
-            return vec![];
+        let mut events = Vec::new();
+        if retained > 0 {
+            *self.aggregate_eroded.entry((policy_id, day.year())).or_default() += retained;
+            let remaining = self.aggregate_remaining(policy_id, &self.policies[&policy_id], day.year());
+            events.push((day, Event::DeductibleEroded { policy_id, insured_id, peril, amount: retained, remaining }));
         }
-        let sum_insured = policy.risk.sum_insured;
-        let panel = policy.panel.clone();
-
-        let year = day.year();
-        let remaining = self
-            .remaining_asset_value
-            .entry((policy_id, year))
-            .or_insert(sum_insured);
-        let effective_gul = ground_up_loss.min(*remaining);
-        *remaining = remaining.saturating_sub(effective_gul);
-
-        if effective_gul == 0 {
-            return vec![];
+        if insured_loss == 0 {
+            return events;
         }
 
-        // Emit one ClaimSettled per panel member with amount proportional to line_share.
-        // Members whose share rounds to zero (tiny loss × small line) are skipped entirely.
-        panel
-            .into_iter()
-            .filter_map(|(insurer_id, line_share)| {
-                let amount = (effective_gul as f64 * line_share).round() as u64;
-                if amount == 0 {
-                    return None;
-                }
-                Some((
-                    day,
-                    Event::ClaimSettled {
-                        policy_id,
-                        insurer_id,
-                        amount,
-                        peril,
-                        remaining_capital: 0, // back-filled by simulation
-                    },
-                ))
-            })
-            .collect()
+        let signed_bps = self.policies[&policy_id].signed_bps.clone();
+        if self.claims_development.is_some() {
+            events.extend(
+                signed_bps
+                    .into_iter()
+                    .zip(amounts)
+                    .filter(|&(_, amount)| amount > 0)
+                    .flat_map(|((insurer_id, _), amount)| {
+                        self.develop_claim(report_day, policy_id, insurer_id, peril, amount)
+                    }),
+            );
+            return events;
+        }
+        events.extend(signed_bps.into_iter().zip(amounts).filter_map(|((insurer_id, _), amount)| {
+            if amount == 0 {
+                return None;
+            }
+            Some((
+                report_day,
+                Event::ClaimSettled {
+                    policy_id,
+                    insurer_id,
+                    amount,
+                    peril,
+                    remaining_capital: 0, // back-filled by simulation
+                },
+            ))
+        }));
+        events
     }
 
+    /// Emit `ClaimReported` on the loss day plus one `ClaimPaid` per development period.
+    /// Instalments are floored to whole cents; the final one absorbs the residual so the
+    /// instalments sum to the ultimate exactly.
+    fn develop_claim(
+        &mut self,
+        day: Day,
+        policy_id: PolicyId,
+        insurer_id: InsurerId,
+        peril: Peril,
+        amount: u64,
+    ) -> Vec<(Day, Event)> {
+        let Some(dev) = self.claims_development.as_ref() else {
+            return vec![];
+        };
+        let claim_id = ClaimId(self.next_claim_id);
+        self.next_claim_id += 1;
+
+        let mut events = vec![(day, Event::ClaimReported { claim_id, policy_id, insurer_id, peril, amount })];
+        let n = dev.payment_pattern.len();
+        let mut paid = 0u64;
+        let mut cumulative = 0.0f64;
+        for (k, &fraction) in dev.payment_pattern.iter().enumerate() {
+            cumulative += fraction;
+            let instalment = if k + 1 == n {
+                amount - paid
+            } else {
+                ((amount as f64 * fraction).floor() as u64).min(amount - paid)
+            };
+            paid += instalment;
+            events.push((
+                day.offset(dev.payment_interval_days * k as u64),
+                Event::ClaimPaid {
+                    claim_id,
+                    policy_id,
+                    insurer_id,
+                    peril,
+                    amount: instalment,
+                    cumulative_paid_fraction: if k + 1 == n { 1.0 } else { cumulative.min(1.0) },
+                },
+            ));
+        }
+        events
+    }
 }
 
 #[cfg(test)]
@@ -226,11 +686,17 @@ mod tests {
     use super::*;
     use crate::config::ASSET_VALUE;
 
+    const US_SE: Territory = Territory(0);
+    const US_NE: Territory = Territory(1);
+    const US_GULF: Territory = Territory(2);
+
     fn small_risk() -> Risk {
         Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: US_SE,
             perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         }
     }
 
@@ -239,7 +705,7 @@ mod tests {
         let sid = SubmissionId(submission_id);
         let iid = InsuredId(insured_id);
         // Register insured so on_loss_event emits AssetDamage for them.
-        market.register_insured(iid, "US-SE", ASSET_VALUE);
+        market.register_insured(iid, US_SE, ASSET_VALUE);
         let events = market.on_quote_accepted(
             Day(0),
             sid,
@@ -331,6 +797,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multi_year_policy_expires_after_its_full_term() {
+        use crate::config::PolicyTermConfig;
+        let mut market =
+            Market::new().with_policy_terms(Some(PolicyTermConfig { term_years: 3, rate_drop_threshold: 0.15 }));
+        let events = market.on_quote_accepted(
+            Day(10), SubmissionId(1), InsuredId(1), vec![(InsurerId(1), 1.0)], 50_000, small_risk(), Year(1),
+        );
+        let Event::PolicyBound { policy_id, .. } = events[0].1 else { panic!("expected PolicyBound") };
+        assert!(matches!(events[1], (Day(1091), Event::PolicyExpired { .. })), "3 × 360 days after binding");
+        market.on_policy_bound(policy_id);
+        assert_eq!(market.policies[&policy_id].bind_day(), Day(11));
+    }
+
     // ── on_policy_bound ───────────────────────────────────────────────────────
 
     #[test]
@@ -399,7 +879,7 @@ mod tests {
         bind_policy(&mut market, 1, 1);
         bind_policy(&mut market, 2, 2);
         let df = 0.10;
-        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", df);
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, df);
         assert_eq!(events.len(), 2);
         let guls: Vec<u64> = events
             .iter()
@@ -421,13 +901,45 @@ mod tests {
         bind_policy(&mut market, 2, 2);
 
         let events =
-            market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", 1.0);
+            market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 1.0);
         assert_eq!(events.len(), 2, "one AssetDamage per registered insured");
         for (_, e) in &events {
             assert!(matches!(e, Event::AssetDamage { peril: Peril::WindstormAtlantic, .. }));
         }
     }
 
+    /// Large fan-outs run on the rayon pool but match the serial path: damages in insured-id
+    /// order, and the same per-insurer losses as summing serial-sized chunks.
+    #[test]
+    fn parallel_fan_out_matches_the_serial_path() {
+        let mut market = Market::new();
+        let n = PARALLEL_FAN_OUT as u64 + 500;
+        for i in 1..=n {
+            bind_policy(&mut market, i, i);
+        }
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 0.1);
+        let ids: Vec<u64> = events
+            .iter()
+            .map(|(_, e)| match e {
+                Event::AssetDamage { insured_id, .. } => insured_id.0,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(ids, (1..=n).collect::<Vec<_>>());
+
+        let mut serial = market.clone();
+        market.route_occurrence(1, &events);
+        assert_eq!(market.occurrence_losses_by_insurer(1)[&InsurerId(1)], n * ASSET_VALUE / 10);
+        let parallel = market.close_occurrence(1);
+        let mut claims = vec![];
+        for (i, chunk) in events.chunks(PARALLEL_FAN_OUT / 4).enumerate() {
+            serial.route_occurrence(i as u64, chunk);
+            claims.extend(serial.close_occurrence(i as u64).1);
+        }
+        assert_eq!(parallel.1, claims, "the same claims in damage order");
+        assert_eq!(parallel.0, n * ASSET_VALUE / 10);
+    }
+
     #[test]
     fn on_loss_event_emits_asset_damage_regardless_of_expiry() {
         // on_loss_event fires for all registered insureds; expiry guard lives in on_asset_damage.
@@ -436,7 +948,7 @@ mod tests {
         bind_policy(&mut market, 1, 1);
         // Loss on expiry day: on_loss_event still emits AssetDamage (expiry is checked later).
         let events =
-            market.on_loss_event(Day(361), Peril::WindstormAtlantic, "US-SE", 1.0);
+            market.on_loss_event(Day(361), Peril::WindstormAtlantic, US_SE, 1.0);
         assert_eq!(events.len(), 1, "on_loss_event emits AssetDamage even on expiry day");
     }
 
@@ -470,7 +982,7 @@ mod tests {
             Year(1),
         );
         // Insured not registered → no AssetDamage.
-        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", 1.0);
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 1.0);
         assert!(events.is_empty(), "unregistered insured must not receive AssetDamage");
     }
 
@@ -479,7 +991,7 @@ mod tests {
         // damage_fraction=0.0 → gul=0 for all insureds → no AssetDamage emitted.
         let mut market = Market::new();
         bind_policy(&mut market, 1, 1);
-        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", 0.0);
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 0.0);
         assert!(events.is_empty(), "no events when damage fraction is zero");
     }
 
@@ -488,7 +1000,7 @@ mod tests {
         let mut market = Market::new();
         bind_policy(&mut market, 1, 1);
         let events =
-            market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", 1.0);
+            market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 1.0);
         for (_, e) in &events {
             if let Event::AssetDamage { ground_up_loss, .. } = e {
                 assert!(
@@ -508,10 +1020,10 @@ mod tests {
         let si_small = ASSET_VALUE;
         let si_large = ASSET_VALUE * 2;
         // register_insured directly — on_loss_event only needs insured_registry.
-        market.register_insured(InsuredId(1), "US-SE", si_small);
-        market.register_insured(InsuredId(2), "US-SE", si_large);
+        market.register_insured(InsuredId(1), US_SE, si_small);
+        market.register_insured(InsuredId(2), US_SE, si_large);
 
-        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", 1.0);
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 1.0);
         assert_eq!(events.len(), 2);
         let guls: HashMap<InsuredId, u64> = events
             .iter()
@@ -537,9 +1049,9 @@ mod tests {
     #[test]
     fn loss_event_to_empty_territory_emits_nothing() {
         let mut market = Market::new();
-        market.register_insured(InsuredId(1), "US-SE", ASSET_VALUE);
+        market.register_insured(InsuredId(1), US_SE, ASSET_VALUE);
         // Strike US-Gulf — no insureds there.
-        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-Gulf", 1.0);
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_GULF, 1.0);
         assert!(
             events.is_empty(),
             "no AssetDamage when struck territory has no registered insureds"
@@ -554,14 +1066,14 @@ mod tests {
         let iid_ne = InsuredId(10);
         let iid_se = InsuredId(11);
         let iid_gulf = InsuredId(12);
-        market.register_insured(iid_ne, "US-NE", ASSET_VALUE);
-        market.register_insured(iid_se, "US-SE", ASSET_VALUE);
-        market.register_insured(iid_gulf, "US-Gulf", ASSET_VALUE);
+        market.register_insured(iid_ne, US_NE, ASSET_VALUE);
+        market.register_insured(iid_se, US_SE, ASSET_VALUE);
+        market.register_insured(iid_gulf, US_GULF, ASSET_VALUE);
 
         for (territory, expected_iid) in [
-            ("US-SE", iid_se),
-            ("US-Gulf", iid_gulf),
-            ("US-NE", iid_ne),
+            (US_SE, iid_se),
+            (US_GULF, iid_gulf),
+            (US_NE, iid_ne),
         ] {
             let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, territory, 1.0);
             assert_eq!(events.len(), 1, "territory {territory}: expected exactly 1 AssetDamage");
@@ -584,10 +1096,10 @@ mod tests {
         let mut market = Market::new();
         let iid_a = InsuredId(10);
         let iid_b = InsuredId(11);
-        market.register_insured(iid_a, "US-SE", ASSET_VALUE);
-        market.register_insured(iid_b, "US-NE", ASSET_VALUE);
+        market.register_insured(iid_a, US_SE, ASSET_VALUE);
+        market.register_insured(iid_b, US_NE, ASSET_VALUE);
 
-        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, "US-SE", 1.0);
+        let events = market.on_loss_event(Day(100), Peril::WindstormAtlantic, US_SE, 1.0);
 
         assert_eq!(events.len(), 1, "only insured A (US-SE) should be hit");
         if let (_, Event::AssetDamage { insured_id, .. }) = &events[0] {
@@ -627,6 +1139,34 @@ mod tests {
         assert_eq!(total, ASSET_VALUE, "aggregate annual GUL must not exceed sum_insured");
     }
 
+    #[test]
+    fn aggregate_deductible_erodes_before_claims_flow_and_resets_each_year() {
+        use crate::config::AggregateDeductibleConfig;
+        let mut market = Market::new().with_aggregate_deductible(Some(AggregateDeductibleConfig { fraction: 0.01 }));
+        let policy_id = bind_policy(&mut market, 1, 1);
+        let aad = ASSET_VALUE / 100;
+
+        let first = market.on_asset_damage(Day(10), InsuredId(1), aad / 2, Peril::Attritional);
+        assert_eq!(first.len(), 1, "wholly retained: no claim");
+        assert!(matches!(
+            first[0].1,
+            Event::DeductibleEroded { policy_id: p, amount, remaining, .. }
+                if p == policy_id && amount == aad / 2 && remaining == aad - aad / 2
+        ));
+
+        let second = market.on_asset_damage(Day(20), InsuredId(1), aad, Peril::Attritional);
+        assert!(matches!(second[0].1, Event::DeductibleEroded { remaining: 0, .. }));
+        assert!(matches!(second[1].1, Event::ClaimSettled { amount, .. } if amount == aad / 2));
+
+        let eroded = market.on_asset_damage(Day(30), InsuredId(1), 1_000, Peril::Attritional);
+        assert_eq!(eroded.len(), 1);
+        assert!(matches!(eroded[0].1, Event::ClaimSettled { amount: 1_000, .. }));
+
+        // A new year starts a fresh aggregate.
+        let next_year = market.on_asset_damage(Day::year_start(Year(2)), InsuredId(1), 1_000, Peril::Attritional);
+        assert!(matches!(next_year[..], [(_, Event::DeductibleEroded { amount: 1_000, .. })]));
+    }
+
     #[test]
     fn on_asset_damage_unknown_insured_produces_no_event() {
         let mut market = Market::new();
@@ -638,7 +1178,7 @@ mod tests {
     fn on_asset_damage_uninsured_returns_empty() {
         // Insured is registered but has no active policy (SubmissionDropped / unbound).
         let mut market = Market::new();
-        market.register_insured(InsuredId(1), "US-SE", ASSET_VALUE);
+        market.register_insured(InsuredId(1), US_SE, ASSET_VALUE);
         let events = market.on_asset_damage(Day(10), InsuredId(1), 100_000, Peril::WindstormAtlantic);
         assert!(events.is_empty(), "uninsured insured must not generate a ClaimSettled");
     }
@@ -648,11 +1188,13 @@ mod tests {
         // Policy covers only WindstormAtlantic; Attritional damage must not generate a claim.
         let mut market = Market::new();
         let iid = InsuredId(1);
-        market.register_insured(iid, "US-SE", ASSET_VALUE);
+        market.register_insured(iid, US_SE, ASSET_VALUE);
         let cat_only_risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: US_SE,
             perils_covered: vec![Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = market.on_quote_accepted(
             Day(0), SubmissionId(1), iid, vec![(InsurerId(1), 1.0)], 100_000, cat_only_risk, Year(1),
@@ -689,8 +1231,10 @@ mod tests {
         let panel = vec![(InsurerId(1), 0.6), (InsurerId(2), 0.4)];
         let risk = Risk {
             sum_insured: 1_000_000,
-            territory: "US-SE".to_string(),
+            territory: US_SE,
             perils_covered: vec![Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: 1_000_000,
         };
         let bound_events = market.on_quote_accepted(Day(0), sid, iid, panel, 10_000, risk, Year(1));
         let policy_id = bound_events.iter().find_map(|(_, e)| {
@@ -716,4 +1260,208 @@ mod tests {
         assert_eq!(b_amount, 40_000, "InsurerId(2) share=0.4 → 40_000");
         assert_eq!(a_amount + b_amount, 100_000, "amounts must sum to total loss");
     }
+
+    #[test]
+    fn transferred_line_merges_into_buyer_and_routes_future_claims() {
+        use crate::types::{InsurerId, InsuredId, SubmissionId, Year};
+        let mut market = Market::new();
+        let panel = vec![(InsurerId(1), 0.5), (InsurerId(2), 0.3), (InsurerId(3), 0.2)];
+        let risk = Risk {
+            sum_insured: 1_000_000,
+            territory: US_SE,
+            perils_covered: vec![Peril::Attritional],
+            attachment: 0,
+            limit: 1_000_000,
+        };
+        let bound = market.on_quote_accepted(Day(0), SubmissionId(1), InsuredId(1), panel, 10_000, risk, Year(1));
+        let Event::PolicyBound { policy_id, .. } = bound[0].1 else { panic!("expected PolicyBound") };
+        market.on_policy_bound(policy_id);
+        assert_eq!(market.policies_written_by(InsurerId(1)), vec![policy_id]);
+
+        market.transfer_lines(InsurerId(1), InsurerId(3), &[policy_id]);
+        let policy = &market.policies[&policy_id];
+        assert_eq!(policy.signed_bps, vec![(InsurerId(2), 3_000), (InsurerId(3), 7_000)]);
+        assert!(market.policies_written_by(InsurerId(1)).is_empty());
+
+        let claims = market.on_asset_damage(Day(5), InsuredId(1), 100_000, Peril::Attritional);
+        let paid: Vec<_> = claims
+            .iter()
+            .filter_map(|(_, e)| match e {
+                Event::ClaimSettled { insurer_id, amount, .. } => Some((*insurer_id, *amount)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(paid, vec![(InsurerId(2), 30_000), (InsurerId(3), 70_000)]);
+    }
+
+    #[test]
+    fn sign_lines_sums_to_full_line_leader_first() {
+        use crate::types::InsurerId;
+        let third = 1.0 / 3.0;
+        let signed = sign_lines(&[(InsurerId(7), third), (InsurerId(2), third), (InsurerId(5), third)]);
+        assert_eq!(signed, vec![(InsurerId(7), 3334), (InsurerId(2), 3333), (InsurerId(5), 3333)]);
+        assert_eq!(signed.iter().map(|&(_, b)| b).sum::<u32>(), FULL_LINE_BPS);
+
+        // A sliver that rounds to zero basis points is not signed at all.
+        let signed = sign_lines(&[(InsurerId(1), 1.0), (InsurerId(2), 1e-6)]);
+        assert_eq!(signed, vec![(InsurerId(1), FULL_LINE_BPS)]);
+    }
+
+    #[test]
+    fn panel_claim_split_sums_exactly_to_loss() {
+        use crate::events::Peril;
+        use crate::types::{InsurerId, InsuredId, SubmissionId, Year};
+        let mut market = Market::new();
+        let third = 1.0 / 3.0;
+        let panel = vec![(InsurerId(1), third), (InsurerId(2), third), (InsurerId(3), third)];
+        let risk = Risk {
+            sum_insured: 1_000_000,
+            territory: US_SE,
+            perils_covered: vec![Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: 1_000_000,
+        };
+        let bound = market.on_quote_accepted(Day(0), SubmissionId(1), InsuredId(1), panel, 10_000, risk, Year(1));
+        let Event::PolicyBound { policy_id, ref panel, .. } = bound[0].1 else { panic!("expected PolicyBound") };
+        assert!((panel[0].1 - 0.3334).abs() < 1e-12, "PolicyBound must carry the signed share");
+        market.on_policy_bound(policy_id);
+
+        let claims = market.on_asset_damage(Day(5), InsuredId(1), 100_001, Peril::WindstormAtlantic);
+        let amounts: Vec<u64> = claims
+            .iter()
+            .filter_map(|(_, e)| if let Event::ClaimSettled { amount, .. } = e { Some(*amount) } else { None })
+            .collect();
+        assert_eq!(amounts.iter().sum::<u64>(), 100_001, "panel claims must sum to the loss: {amounts:?}");
+        // Signed lines 3334/3333/3333 bps → 33_340 + 33_330 + 33_330; the 1-cent residual goes to the lead.
+        assert_eq!(amounts, vec![33_341, 33_330, 33_330], "leader absorbs the rounding residual");
+    }
+
+    #[test]
+    fn claims_development_reports_then_pays_instalments() {
+        use crate::config::ClaimsDevelopmentConfig;
+        use crate::events::Peril;
+        use crate::types::{InsurerId, InsuredId, SubmissionId, Year};
+        let mut market = Market::new().with_claims_development(Some(ClaimsDevelopmentConfig {
+            payment_pattern: vec![0.4, 0.3, 0.2, 0.1],
+            payment_interval_days: 360,
+            initial_reserve_ratio: 1.0,
+        }));
+        let risk = Risk {
+            sum_insured: 1_000_000,
+            territory: US_SE,
+            perils_covered: vec![Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: 1_000_000,
+        };
+        let bound = market.on_quote_accepted(
+            Day(0), SubmissionId(1), InsuredId(1), vec![(InsurerId(1), 1.0)], 10_000, risk, Year(1),
+        );
+        let Event::PolicyBound { policy_id, .. } = bound[0].1 else { panic!("expected PolicyBound") };
+        market.on_policy_bound(policy_id);
+
+        let events = market.on_asset_damage(Day(5), InsuredId(1), 100_001, Peril::WindstormAtlantic);
+        assert!(events.iter().all(|(_, e)| !matches!(e, Event::ClaimSettled { .. })));
+        assert!(matches!(events[0], (Day(5), Event::ClaimReported { amount: 100_001, .. })));
+        let paid: Vec<(u64, u64)> = events
+            .iter()
+            .filter_map(|(d, e)| if let Event::ClaimPaid { amount, .. } = e { Some((d.0, *amount)) } else { None })
+            .collect();
+        assert_eq!(paid, vec![(5, 40_000), (365, 30_000), (725, 20_000), (1085, 10_001)]);
+    }
+
+    #[test]
+    fn casualty_is_covered_on_the_accident_day_and_reported_after_the_lag() {
+        use crate::config::ClaimsDevelopmentConfig;
+        use crate::events::Peril;
+        use crate::types::{InsurerId, InsuredId, SubmissionId, Year};
+        let mut market = Market::new().with_claims_development(Some(ClaimsDevelopmentConfig {
+            payment_pattern: vec![0.5, 0.5],
+            payment_interval_days: 360,
+            initial_reserve_ratio: 1.0,
+        }));
+        let risk = Risk {
+            sum_insured: 1_000_000,
+            territory: US_SE,
+            perils_covered: vec![Peril::Casualty],
+            attachment: 0,
+            limit: 1_000_000,
+        };
+        let bound = market.on_quote_accepted(
+            Day(0), SubmissionId(1), InsuredId(1), vec![(InsurerId(1), 1.0)], 10_000, risk, Year(1),
+        );
+        let Event::PolicyBound { policy_id, .. } = bound[0].1 else { panic!("expected PolicyBound") };
+        market.on_policy_bound(policy_id);
+
+        let events = market.on_casualty_damage(Day(5), 720, InsuredId(1), 1_000_000);
+        assert!(matches!(events[0], (Day(725), Event::ClaimReported { amount: 1_000_000, peril: Peril::Casualty, .. })));
+        let paid: Vec<u64> = events.iter().filter(|(_, e)| matches!(e, Event::ClaimPaid { .. })).map(|(d, _)| d.0).collect();
+        assert_eq!(paid, vec![725, 1085], "instalments run from the report day");
+
+        // Liability does not erode: a second total loss in the year sees the full limit again.
+        let again = market.on_casualty_damage(Day(6), 360, InsuredId(1), 1_000_000);
+        assert!(matches!(again[0], (Day(366), Event::ClaimReported { amount: 1_000_000, .. })));
+        let property = market.on_asset_damage(Day(7), InsuredId(1), 1_000_000, Peril::Attritional);
+        assert!(property.is_empty(), "the liability policy does not cover the asset");
+        let uncovered = market.on_casualty_damage(Day(361), 360, InsuredId(1), 1_000);
+        assert!(uncovered.is_empty(), "no cover in force on the accident day");
+    }
+
+    #[test]
+    fn premium_default_cancels_policy_with_basis_amounts() {
+        use crate::config::PremiumFinanceConfig;
+        let bound_market = |basis| {
+            let mut market = Market::new().with_premium_finance(Some(PremiumFinanceConfig {
+                instalments: 4,
+                default_probability: 1.0,
+                grace_days: 30,
+                basis,
+            }));
+            let bound = market.on_quote_accepted(
+                Day(10), SubmissionId(1), InsuredId(1), vec![(InsurerId(1), 1.0)], 36_000, small_risk(), Year(1),
+            );
+            let Event::PolicyBound { policy_id, .. } = bound[0].1 else { panic!("expected PolicyBound") };
+            market.on_policy_bound(policy_id);
+            (market, policy_id)
+        };
+
+        // Bound day 11; instalment 2 of 4 falls due at day 191 with 18_000 collected.
+        let (market, pid) = bound_market(CancellationBasis::ProRata);
+        let events = market.on_premium_defaulted(Day(191), pid, 2);
+        // Cancelled at 221: earned 36_000 × 210/360 = 21_000 → 3_000 bad debt, 15_000 reversed.
+        assert!(matches!(
+            events[..],
+            [(Day(221), Event::PolicyCancelled { premium_reversed: 15_000, bad_debt: 3_000, .. })]
+        ));
+
+        let (mut market, pid) = bound_market(CancellationBasis::AbInitio);
+        let events = market.on_premium_defaulted(Day(191), pid, 2);
+        assert!(matches!(
+            events[..],
+            [(Day(191), Event::PolicyCancelled { premium_reversed: 18_000, bad_debt: 0, .. })]
+        ));
+        market.on_policy_cancelled(pid);
+        assert!(market.on_asset_damage(Day(200), InsuredId(1), 1_000, Peril::Attritional).is_empty());
+        assert!(market.on_premium_defaulted(Day(281), pid, 3).is_empty(), "cancelled policy cannot default again");
+    }
+
+    #[test]
+    fn deductible_and_limit_apply_per_occurrence() {
+        let mut market = Market::new();
+        let risk = Risk { attachment: 1_000, limit: 5_000, ..small_risk() };
+        let bound = market.on_quote_accepted(
+            Day(0), SubmissionId(1), InsuredId(1), vec![(InsurerId(1), 1.0)], 10_000, risk, Year(1),
+        );
+        let Event::PolicyBound { policy_id, .. } = bound[0].1 else { panic!("expected PolicyBound") };
+        market.on_policy_bound(policy_id);
+
+        let claim = |events: Vec<(Day, Event)>| -> Option<u64> {
+            events.into_iter().find_map(|(_, e)| match e {
+                Event::ClaimSettled { amount, .. } => Some(amount),
+                _ => None,
+            })
+        };
+        assert_eq!(claim(market.on_asset_damage(Day(5), InsuredId(1), 800, Peril::Attritional)), None, "below deductible");
+        assert_eq!(claim(market.on_asset_damage(Day(6), InsuredId(1), 3_000, Peril::Attritional)), Some(2_000));
+        assert_eq!(claim(market.on_asset_damage(Day(7), InsuredId(1), 50_000, Peril::Attritional)), Some(5_000), "capped at limit");
+    }
 }
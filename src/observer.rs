@@ -0,0 +1,90 @@
+//! Custom instrumentation without forking dispatch.
+//!
+//! One-off measurements — per-day queue depth, quote-to-bind latency, a statistic only one
+//! study needs — do not belong in core or in the event log. An [`Observer`] registered with
+//! `Simulation::with_observer` is called after every dispatched event and after each
+//! `YearEnd`, with a read-only [`MarketView`] of the agents at that moment.
+//!
+//! Observers only read: anything they record lives in the observer. To get the results back
+//! after the run, register an `Arc<Mutex<O>>` and keep a clone of the `Arc`. Observers are not
+//! part of the simulation state: a snapshot, a clone or a restored checkpoint carries none.
+
+use std::sync::{Arc, Mutex};
+
+use crate::broker::Broker;
+use crate::events::SimEvent;
+use crate::insurer::Insurer;
+use crate::market::Market;
+use crate::types::{Day, Year};
+
+/// Read-only view of the simulation between events.
+pub struct MarketView<'a> {
+    /// Day of the event just dispatched.
+    pub day: Day,
+    /// Events still queued, including any the handler just scheduled.
+    pub queue_depth: usize,
+    /// AP/TP factor published to the insurers (1.0 = neutral).
+    pub ap_tp_factor: f64,
+    pub insurers: &'a [Insurer],
+    pub brokers: &'a [Broker],
+    pub market: &'a Market,
+}
+
+/// Callbacks for custom instrumentation. Both default to doing nothing.
+pub trait Observer: Send {
+    /// An event was dispatched. `event` is as logged, with any fields its handler back-filled;
+    /// events logged without dispatch (`SimHealth`, `ExposureSnapshot`, ...) are not seen.
+    fn on_event_dispatched(&mut self, event: &SimEvent, view: &MarketView) {
+        let _ = (event, view);
+    }
+
+    /// The `YearEnd` handler for `year` returned. Called after `on_event_dispatched` for the
+    /// `YearEnd` event; events it scheduled for the same day have not been dispatched yet.
+    fn on_year_end(&mut self, year: Year, view: &MarketView) {
+        let _ = (year, view);
+    }
+}
+
+/// Share an observer with the caller, who reads it once the run is over.
+impl<O: Observer> Observer for Arc<Mutex<O>> {
+    fn on_event_dispatched(&mut self, event: &SimEvent, view: &MarketView) {
+        self.lock().expect("observer poisoned").on_event_dispatched(event, view);
+    }
+
+    fn on_year_end(&mut self, year: Year, view: &MarketView) {
+        self.lock().expect("observer poisoned").on_year_end(year, view);
+    }
+}
+
+/// The observers registered on a simulation. Cloning yields none: a clone is a snapshot of
+/// the state, and observers are not state.
+#[derive(Default)]
+pub(crate) struct Observers(Vec<Box<dyn Observer>>);
+
+impl Observers {
+    pub(crate) fn push(&mut self, observer: Box<dyn Observer>) {
+        self.0.push(observer);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn event_dispatched(&mut self, event: &SimEvent, view: &MarketView) {
+        for observer in &mut self.0 {
+            observer.on_event_dispatched(event, view);
+        }
+    }
+
+    pub(crate) fn year_end(&mut self, year: Year, view: &MarketView) {
+        for observer in &mut self.0 {
+            observer.on_year_end(year, view);
+        }
+    }
+}
+
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Observers::default()
+    }
+}
@@ -0,0 +1,246 @@
+//! Automatic calibration: Nelder–Mead search over swept config parameters, minimising the
+//! distance between a batch's stylised-fact statistics and their target bands.
+//!
+//! The search reads the same spec as [`crate::sweep`] — `[params]` ranges, `seeds`, `seed`,
+//! `years` — plus `evaluations`, the objective-evaluation budget (`samples` and `method` are
+//! ignored); it stops early once the simplex's objectives agree. Every evaluation runs seeds
+//! `seed .. seed + seeds`, so candidates are compared on common random numbers and a search is
+//! reproducible from its spec.
+//!
+//! The objective sums, over the canonical stylised facts, the squared distance of the batch
+//! mean outside the band in band widths; a fact the batch cannot observe costs 1.0. Zero means
+//! every fact is in band.
+
+use serde::Serialize;
+
+use crate::batch::{self, BatchOptions};
+use crate::config::SimulationConfig;
+use crate::stylized_facts::{self, FactResult};
+use crate::sweep::{self, SweepError, SweepParam, SweepSpec};
+
+/// Objective evaluations when the spec sets no `evaluations`.
+pub const DEFAULT_EVALUATIONS: usize = 50;
+
+/// One evaluated point of the search.
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    /// 1-based evaluation number.
+    pub evaluation: usize,
+    /// Parameter values, one per spec param.
+    pub values: Vec<f64>,
+    pub objective: f64,
+    pub facts: Vec<FactResult>,
+}
+
+/// The outcome of a search.
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimiseResult {
+    pub best: Candidate,
+    pub evaluations: usize,
+    /// Objective of every evaluation, in order.
+    pub trace: Vec<f64>,
+}
+
+/// Distance of fact results from their bands (see module docs).
+pub fn objective(facts: &[FactResult]) -> f64 {
+    facts
+        .iter()
+        .map(|r| {
+            let width = (r.hi - r.lo).max(f64::EPSILON);
+            match r.value {
+                Some(v) if v < r.lo => ((r.lo - v) / width).powi(2),
+                Some(v) if v > r.hi => ((v - r.hi) / width).powi(2),
+                Some(_) => 0.0,
+                None => 1.0,
+            }
+        })
+        .sum()
+}
+
+/// Search `spec.params` on top of `base`. `on_evaluation(candidate, improved)` is called after
+/// every evaluation; `improved` is true when the candidate is the best so far — the point to
+/// checkpoint it.
+pub fn optimise(
+    base: &SimulationConfig,
+    spec: &SweepSpec,
+    mut on_evaluation: impl FnMut(&Candidate, bool),
+) -> Result<OptimiseResult, SweepError> {
+    let base = sweep::base_config(base, spec);
+    let budget = spec.evaluations.unwrap_or(DEFAULT_EVALUATIONS);
+    let facts = stylized_facts::canonical_facts();
+    // Reject bad paths before any simulation runs.
+    sweep::apply(&base, &spec.params, &spec.params.iter().map(|p| p.lo).collect::<Vec<_>>())?;
+
+    let mut best: Option<Candidate> = None;
+    let mut trace = Vec::new();
+    let mut failure: Option<SweepError> = None;
+    nelder_mead(spec.params.len(), budget, |unit| {
+        if failure.is_some() {
+            return f64::INFINITY;
+        }
+        let values = to_values(&spec.params, unit);
+        let opts = BatchOptions { aggregate_only: true, ..BatchOptions::default() };
+        let outcome = sweep::apply(&base, &spec.params, &values)
+            .and_then(|config| Ok(batch::run_batch(&config, spec.seeds, &opts, |_| {})?));
+        let results = match outcome {
+            Ok(results) => results,
+            Err(e) => {
+                failure = Some(e);
+                return f64::INFINITY;
+            }
+        };
+        let fact_results = stylized_facts::evaluate(&facts, &results.stats(), results.expense_ratio);
+        let candidate =
+            Candidate { evaluation: trace.len() + 1, values, objective: objective(&fact_results), facts: fact_results };
+        trace.push(candidate.objective);
+        let improved = best.as_ref().is_none_or(|b| candidate.objective < b.objective);
+        on_evaluation(&candidate, improved);
+        if improved {
+            best = Some(candidate);
+        }
+        trace.last().copied().unwrap_or(f64::INFINITY)
+    });
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    let best = best.ok_or(SweepError::Empty)?;
+    Ok(OptimiseResult { best, evaluations: trace.len(), trace })
+}
+
+fn to_values(params: &[SweepParam], unit: &[f64]) -> Vec<f64> {
+    params.iter().zip(unit).map(|(p, u)| p.lo + u * (p.hi - p.lo)).collect()
+}
+
+/// Minimise `f` over the unit cube [0, 1]^dim with Nelder–Mead, clamping every trial point
+/// into the cube, for at most `budget` evaluations. Deterministic: the initial simplex is the
+/// centre plus a quarter-step along each axis. Returns the best point and its value.
+fn nelder_mead(dim: usize, budget: usize, mut f: impl FnMut(&[f64]) -> f64) -> (Vec<f64>, f64) {
+    const REFLECT: f64 = 1.0;
+    const EXPAND: f64 = 2.0;
+    const CONTRACT: f64 = 0.5;
+    const SHRINK: f64 = 0.5;
+    const TOLERANCE: f64 = 1e-9;
+
+    let mut evals = 0;
+    let mut eval = |x: &[f64], evals: &mut usize| {
+        *evals += 1;
+        f(x)
+    };
+    let clamp = |x: Vec<f64>| x.into_iter().map(|v| v.clamp(0.0, 1.0)).collect::<Vec<_>>();
+    // x + t (y − x), clamped.
+    let along = |x: &[f64], y: &[f64], t: f64| clamp(x.iter().zip(y).map(|(a, b)| a + t * (b - a)).collect());
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(dim + 1);
+    for k in 0..=dim {
+        if evals >= budget {
+            break;
+        }
+        let mut x = vec![0.5; dim];
+        if k > 0 {
+            x[k - 1] += 0.25;
+        }
+        let fx = eval(&x, &mut evals);
+        simplex.push((x, fx));
+    }
+
+    while evals < budget && simplex.len() == dim + 1 {
+        simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        if simplex[dim].1 - simplex[0].1 < TOLERANCE && simplex[0].1.is_finite() {
+            break;
+        }
+        let centroid: Vec<f64> =
+            (0..dim).map(|i| simplex[..dim].iter().map(|(x, _)| x[i]).sum::<f64>() / dim as f64).collect();
+        let worst = simplex[dim].clone();
+
+        let reflected = along(&centroid, &worst.0, -REFLECT);
+        let fr = eval(&reflected, &mut evals);
+        if fr < simplex[0].1 {
+            if evals >= budget {
+                simplex[dim] = (reflected, fr);
+                break;
+            }
+            let expanded = along(&centroid, &worst.0, -EXPAND);
+            let fe = eval(&expanded, &mut evals);
+            simplex[dim] = if fe < fr { (expanded, fe) } else { (reflected, fr) };
+        } else if fr < simplex[dim - 1].1 {
+            simplex[dim] = (reflected, fr);
+        } else {
+            if evals >= budget {
+                break;
+            }
+            let contracted = along(&centroid, &worst.0, CONTRACT);
+            let fc = eval(&contracted, &mut evals);
+            if fc < worst.1 {
+                simplex[dim] = (contracted, fc);
+            } else {
+                for k in 1..=dim {
+                    if evals >= budget {
+                        break;
+                    }
+                    let x = along(&simplex[0].0, &simplex[k].0, SHRINK);
+                    let fx = eval(&x, &mut evals);
+                    simplex[k] = (x, fx);
+                }
+            }
+        }
+    }
+    simplex
+        .into_iter()
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .unwrap_or((vec![0.5; dim], f64::INFINITY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fact(value: Option<f64>) -> FactResult {
+        FactResult { name: "x", lo: 5.0, hi: 15.0, runs: 1, value, in_band: 0.0, effect_size: 0.0, pass: false }
+    }
+
+    #[test]
+    fn objective_is_squared_band_widths_outside_the_band() {
+        assert_eq!(objective(&[fact(Some(10.0))]), 0.0);
+        assert_eq!(objective(&[fact(Some(0.0))]), 0.25, "half a band width below");
+        assert_eq!(objective(&[fact(Some(35.0)), fact(None)]), 4.0 + 1.0);
+    }
+
+    #[test]
+    fn nelder_mead_finds_an_interior_minimum_within_budget() {
+        let mut calls = 0;
+        let (x, fx) = nelder_mead(2, 200, |x| {
+            calls += 1;
+            (x[0] - 0.3).powi(2) + 2.0 * (x[1] - 0.8).powi(2)
+        });
+        assert!(calls <= 200);
+        assert!((x[0] - 0.3).abs() < 1e-3 && (x[1] - 0.8).abs() < 1e-3, "x = {x:?}");
+        assert!(fx < 1e-6);
+
+        // A minimum outside the cube is found on its boundary.
+        let (x, _) = nelder_mead(1, 60, |x| (x[0] - 2.0).powi(2));
+        assert!((x[0] - 1.0).abs() < 1e-6, "x = {x:?}");
+    }
+
+    #[test]
+    fn optimise_is_reproducible_and_reports_improvements() {
+        let mut base = SimulationConfig::canonical();
+        base.years = 3;
+        base.warmup_years = 1;
+        base.n_insureds = 20;
+        let spec = sweep::parse_spec("evaluations = 4\nseeds = 2\n[params]\nmax_rol_mu = [0.1, 0.2]").unwrap();
+
+        let mut improvements = Vec::new();
+        let first = optimise(&base, &spec, |c, improved| {
+            if improved {
+                improvements.push(c.objective);
+            }
+        })
+        .unwrap();
+        assert!(first.evaluations <= 4 && first.evaluations == first.trace.len());
+        assert!(improvements.windows(2).all(|w| w[1] < w[0]), "each checkpoint beats the last");
+        assert_eq!(Some(&first.best.objective), improvements.last());
+
+        let again = optimise(&base, &spec, |_, _| {}).unwrap();
+        assert_eq!(again.trace, first.trace, "same spec, same search");
+    }
+}
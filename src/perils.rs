@@ -1,10 +1,10 @@
 use rand::Rng;
-use rand_distr::{Distribution, LogNormal, Pareto, Poisson};
-use serde::Serialize;
+use rand_distr::{Distribution, Gamma, LogNormal, Pareto, Poisson};
+use serde::{Deserialize, Serialize};
 
-use crate::config::{AttritionalConfig, CatConfig};
+use crate::config::{AttritionalConfig, CasualtyConfig, CatConfig, CatEventClass, InflationConfig, LargeLossConfig, SeasonProfile};
 use crate::events::{Event, Peril, Risk};
-use crate::types::{Day, InsuredId, Year};
+use crate::types::{Day, InsuredId, Territory, TerritoryTable, Year};
 
 
 /// A damage fraction model: `sample()` returns a value in `[0.0, 1.0]`
@@ -43,10 +43,11 @@ impl DamageFractionModel {
 /// Schedule market-wide catastrophe `LossEvent`s for `year`.
 ///
 /// Iterates over `cat.event_classes`, running one independent Poisson draw per class.
-/// For each event, a day-offset and territory are drawn uniformly, and a damage fraction
+/// For each event, a day-offset and territory are drawn, and a damage fraction
 /// is sampled from the class's Pareto distribution at scheduling time. The damage fraction
 /// is embedded in the `LossEvent` so the event is self-contained — `Market::on_loss_event`
-/// uses it directly without further sampling.
+/// uses it directly without further sampling. Day-offsets follow `cat.season` and each event
+/// spawns secondaries under `cat.clustering`; see [`sample_class_year`].
 ///
 /// `next_id` is mutated in-place; the caller owns the event-id counter.
 pub fn schedule_loss_events(
@@ -55,6 +56,32 @@ pub fn schedule_loss_events(
     rng: &mut impl Rng,
     next_id: &mut u64,
 ) -> Vec<(Day, Event)> {
+    if let Some(catalogue) = &cat.catalogue {
+        return catalogue
+            .iter()
+            .filter(|e| e.year == year.0)
+            .filter_map(|e| {
+                // Rows in territories the cat model does not list strike nothing.
+                let territory = Territory(cat.territories.iter().position(|t| *t == e.territory)? as u16);
+                let event_id = *next_id;
+                *next_id += 1;
+                // A catalogue row spreads with the footprint of the configured class it names.
+                let footprint = cat
+                    .event_classes
+                    .iter()
+                    .find(|c| c.label == e.class)
+                    .map_or_else(Vec::new, |c| footprint(cat, c, territory, e.damage_fraction));
+                let event = Event::LossEvent {
+                    event_id,
+                    peril: Peril::WindstormAtlantic,
+                    territory,
+                    damage_fraction: e.damage_fraction,
+                    footprint,
+                };
+                Some((Day::year_start(year).offset(e.day), event))
+            })
+            .collect();
+    }
     if cat.territories.is_empty() || cat.event_classes.is_empty() {
         return vec![];
     }
@@ -62,23 +89,11 @@ pub fn schedule_loss_events(
     let mut events = Vec::new();
 
     for class in &cat.event_classes {
-        if class.annual_frequency <= 0.0 {
-            continue;
-        }
-        let model = DamageFractionModel::Pareto {
-            scale: class.pareto_scale,
-            shape: class.pareto_shape,
-            cap: class.max_damage_fraction,
-        };
-        let poisson = Poisson::new(class.annual_frequency).expect("invalid Poisson lambda");
-        let n = poisson.sample(rng) as u64;
-        for _ in 0..n {
-            let offset = rng.random_range(1_u64..360);
+        for (offset, territory, damage_fraction) in sample_class_year(cat, class, rng) {
             let event_id = *next_id;
             *next_id += 1;
-            let territory_idx = rng.random_range(0..cat.territories.len());
-            let territory = cat.territories[territory_idx].clone();
-            let damage_fraction = model.sample(rng);
+            let territory = Territory(territory as u16);
+            let footprint = footprint(cat, class, territory, damage_fraction);
             events.push((
                 year_start.offset(offset),
                 Event::LossEvent {
@@ -86,6 +101,7 @@ pub fn schedule_loss_events(
                     peril: Peril::WindstormAtlantic,
                     territory,
                     damage_fraction,
+                    footprint,
                 },
             ));
         }
@@ -93,67 +109,458 @@ pub fn schedule_loss_events(
     events
 }
 
-/// Schedule attritional `AssetDamage` events for a single insured.
+/// The other territories an occurrence of `class` striking `territory` at `damage_fraction`
+/// also strikes, with their damage fractions: the territories `k` places either side in
+/// `cat.territories` at `class.footprint[k − 1]` of the damage. Deterministic, so a footprint
+/// draws no random numbers and seeds replay exactly. Cat territories lead the run's territory
+/// table, so `Territory(i)` is `cat.territories[i]`.
+pub fn footprint(cat: &CatConfig, class: &CatEventClass, territory: Territory, damage_fraction: f64) -> Vec<(Territory, f64)> {
+    let idx = territory.0 as usize;
+    if idx >= cat.territories.len() {
+        return vec![];
+    }
+    let mut out = Vec::new();
+    for (k, &weight) in class.footprint.iter().enumerate() {
+        let damage = (damage_fraction * weight).min(1.0);
+        if damage <= 0.0 {
+            continue;
+        }
+        let k = k + 1;
+        for neighbour in [idx.checked_sub(k), Some(idx + k)].into_iter().flatten() {
+            if neighbour < cat.territories.len() {
+                out.push((Territory(neighbour as u16), damage));
+            }
+        }
+    }
+    out
+}
+
+/// Footprint weight of territory `j` in an occurrence of `class` centred on territory `i`.
+fn footprint_weight(class: &CatEventClass, i: usize, j: usize) -> f64 {
+    match i.abs_diff(j) {
+        0 => 1.0,
+        k => class.footprint.get(k - 1).copied().unwrap_or(0.0).max(0.0),
+    }
+}
+
+/// One year of arrivals for a single event class: `(day offset in 1..360, index into
+/// cat.territories, damage fraction)`, each parent followed by its secondaries.
 ///
-/// Called at `CoverageRequested` time so all insureds accumulate attritional
-/// exposure for the year regardless of whether they ultimately bind a policy.
-/// `from_day` is the `CoverageRequested` day; all losses are scheduled strictly
-/// after it so the DES log remains day-ordered (no event is in the past).
-/// Draws a Poisson count from `config.annual_rate`, then for each occurrence
-/// draws a random day in `(from_day, year_end]` and a damage fraction.
-pub fn schedule_attritional_losses_for_insured(
-    insured_id: InsuredId,
-    risk: &Risk,
-    from_day: Day,
+/// Without `cat.season` or `cat.clustering` the draw sequence per event is day, territory,
+/// damage — unchanged from independent uniform arrivals, so existing seeds replay exactly.
+fn sample_class_year(
+    cat: &CatConfig,
+    class: &CatEventClass,
     rng: &mut impl Rng,
-    config: &AttritionalConfig,
-) -> Vec<(Day, Event)> {
-    if !risk.perils_covered.contains(&Peril::Attritional) {
+) -> Vec<(u64, usize, f64)> {
+    if class.annual_frequency <= 0.0 {
         return vec![];
     }
-    let year_end = Day::year_end(from_day.year());
-    if from_day >= year_end {
-        return vec![];
+    let model = DamageFractionModel::Pareto {
+        scale: class.pareto_scale,
+        shape: class.pareto_shape,
+        cap: class.max_damage_fraction,
+    };
+    let poisson = Poisson::new(class.annual_frequency).expect("invalid Poisson lambda");
+    let secondaries = cat
+        .clustering
+        .as_ref()
+        .filter(|c| c.mean_secondaries > 0.0)
+        .map(|c| (c, Poisson::new(c.mean_secondaries).expect("invalid Poisson lambda")));
+    let n = poisson.sample(rng) as u64;
+    let mut out = Vec::with_capacity(n as usize);
+    for _ in 0..n {
+        let day = arrival_day(cat.season.as_ref(), rng);
+        let territory = rng.random_range(0..cat.territories.len());
+        let damage_fraction = model.sample(rng);
+        out.push((day, territory, damage_fraction));
+        if let Some((cluster, ref count)) = secondaries {
+            for _ in 0..count.sample(rng) as u64 {
+                let lag = rng.random_range(1..=cluster.max_lag_days.max(1));
+                let damage = model.sample(rng) * cluster.damage_scale;
+                if day + lag < Day::DAYS_PER_YEAR {
+                    out.push((day + lag, territory, damage));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Draw a day offset in `1..360`: uniform without a profile, else `window_share` of draws
+/// land uniformly inside the season and the rest uniformly on the remaining days.
+fn arrival_day(season: Option<&SeasonProfile>, rng: &mut impl Rng) -> u64 {
+    let Some(season) = season else { return rng.random_range(1_u64..360) };
+    let start = season.window_start.clamp(1, 359);
+    let end = season.window_end.clamp(start, 359);
+    let off_days = 359 - (end - start + 1);
+    if off_days == 0 || rng.random::<f64>() < season.window_share {
+        return rng.random_range(start..=end);
     }
+    let k = rng.random_range(1..=off_days);
+    if k < start { k } else { k + (end - start + 1) }
+}
+
+/// Schedule the year's attritional `AssetDamage` events for a whole book in one pass.
+///
+/// Called at `YearStart` with each insured's exposure start — its `CoverageRequested` day in
+/// that year — so all insureds accumulate attritional exposure regardless of whether they
+/// ultimately bind a policy. Per insured: a claim count from its `AttritionalConfig::params_for`
+/// rate scaled by the exposure's rate multiplier (see `PortfolioConfig`) — Poisson, or negative
+/// binomial under contagion — then for each occurrence a random day in `(from_day, year_end]`
+/// and a damage fraction. Losses are strictly after `from_day` so none precedes the insured's
+/// registration with the market. The output buffer is pre-sized from the expected occurrence count.
+/// `territories` names the risks' territory ids for per-territory overrides.
+pub fn schedule_attritional_losses(
+    exposures: &[(InsuredId, &Risk, Day, f64)],
+    rng: &mut impl Rng,
+    config: &AttritionalConfig,
+    territories: &TerritoryTable,
+) -> Vec<(Day, Event)> {
+    let Ok(poisson) = Poisson::new(config.annual_rate) else { return vec![] };
     let model = DamageFractionModel::LogNormal { mu: config.mu, sigma: config.sigma };
+    let expected = exposures.len() as f64 * config.annual_rate;
+    let mut out = Vec::with_capacity((expected * 1.2).ceil() as usize);
+    for &(insured_id, risk, from_day, rate_multiplier) in exposures {
+        if !risk.perils_covered.contains(&Peril::Attritional) {
+            continue;
+        }
+        let year_end = Day::year_end(from_day.year());
+        if from_day >= year_end {
+            continue;
+        }
+        let params = if config.overrides.is_empty() {
+            None
+        } else {
+            Some(config.params_for(insured_id, territories.name(risk.territory).unwrap_or_default()))
+        };
+        let (rate, contagion) = params.map_or((config.annual_rate, config.contagion), |p| (p.annual_rate, p.contagion));
+        let n = if contagion > 0.0 {
+            negative_binomial(rate * rate_multiplier, contagion, rng)
+        } else if rate_multiplier == 1.0 && params.is_none() {
+            poisson.sample(rng) as u64
+        } else {
+            Poisson::new(rate * rate_multiplier).map_or(0, |p| p.sample(rng) as u64)
+        };
+        let own_model = params.map(|p| DamageFractionModel::LogNormal { mu: p.mu, sigma: p.sigma });
+        for _ in 0..n {
+            let day = Day(rng.random_range(from_day.0 + 1..=year_end.0));
+            let damage_fraction = own_model.as_ref().unwrap_or(&model).sample(rng);
+            let ground_up_loss = (damage_fraction * risk.sum_insured as f64) as u64;
+            if ground_up_loss > 0 {
+                out.push((day, Event::AssetDamage { insured_id, peril: Peril::Attritional, ground_up_loss }));
+            }
+        }
+    }
+    out
+}
+
+/// Schedule the year's `LargeFire` `AssetDamage` events for the same exposures as
+/// [`schedule_attritional_losses`]: per insured covering the peril, a Poisson count at
+/// `config.annual_rate`, each loss on a random day in `(from_day, year_end]` with a Pareto
+/// damage fraction truncated at 1.0 (the asset destroyed). The attritional rate multiplier
+/// does not apply.
+pub fn schedule_large_losses(
+    exposures: &[(InsuredId, &Risk, Day, f64)],
+    rng: &mut impl Rng,
+    config: &LargeLossConfig,
+) -> Vec<(Day, Event)> {
     let Ok(poisson) = Poisson::new(config.annual_rate) else { return vec![] };
-    let n = poisson.sample(rng) as u64;
+    let model = DamageFractionModel::Pareto { scale: config.scale, shape: config.shape, cap: 1.0 };
+    let mut out = Vec::new();
+    for &(insured_id, risk, from_day, _) in exposures {
+        if !risk.perils_covered.contains(&Peril::LargeFire) {
+            continue;
+        }
+        let year_end = Day::year_end(from_day.year());
+        if from_day >= year_end {
+            continue;
+        }
+        for _ in 0..poisson.sample(rng) as u64 {
+            let day = Day(rng.random_range(from_day.0 + 1..=year_end.0));
+            let ground_up_loss = (model.sample(rng) * risk.sum_insured as f64) as u64;
+            if ground_up_loss > 0 {
+                out.push((day, Event::AssetDamage { insured_id, peril: Peril::LargeFire, ground_up_loss }));
+            }
+        }
+    }
+    out
+}
 
-    (0..n)
-        .filter_map(|_| {
+/// Schedule the year's `Casualty` `AssetDamage` events for the same exposures as
+/// [`schedule_large_losses`]: per insured covering the peril, a Poisson count at
+/// `config.annual_rate`, each on a random accident day in `(from_day, year_end]` with a Pareto
+/// severity truncated at the sum insured. The events fire on the accident day; the report
+/// delay is drawn when they are routed ([`casualty_report_lag`]).
+pub fn schedule_casualty_losses(
+    exposures: &[(InsuredId, &Risk, Day, f64)],
+    rng: &mut impl Rng,
+    config: &CasualtyConfig,
+) -> Vec<(Day, Event)> {
+    let Ok(poisson) = Poisson::new(config.annual_rate) else { return vec![] };
+    let model = DamageFractionModel::Pareto { scale: config.scale, shape: config.shape, cap: 1.0 };
+    let mut out = Vec::new();
+    for &(insured_id, risk, from_day, _) in exposures {
+        if !risk.perils_covered.contains(&Peril::Casualty) {
+            continue;
+        }
+        let year_end = Day::year_end(from_day.year());
+        if from_day >= year_end {
+            continue;
+        }
+        for _ in 0..poisson.sample(rng) as u64 {
             let day = Day(rng.random_range(from_day.0 + 1..=year_end.0));
-            let damage_fraction = model.sample(rng);
-            let ground_up_loss = (damage_fraction * risk.sum_insured as f64) as u64;
-            if ground_up_loss == 0 {
-                return None;
+            let ground_up_loss = (model.sample(rng) * risk.sum_insured as f64) as u64;
+            if ground_up_loss > 0 {
+                out.push((day, Event::AssetDamage { insured_id, peril: Peril::Casualty, ground_up_loss }));
             }
-            Some((
-                day,
-                Event::AssetDamage { insured_id, peril: Peril::Attritional, ground_up_loss },
-            ))
+        }
+    }
+    out
+}
+
+/// Reporting delay of one casualty claim in days: `k` whole years after the accident with
+/// probability `reporting_pattern[k - 1]`, so a claim lands on the same day of year Y+k.
+pub fn casualty_report_lag(config: &CasualtyConfig, rng: &mut impl Rng) -> u64 {
+    let u: f64 = rng.random();
+    let mut cumulative = 0.0;
+    let years = config
+        .reporting_pattern
+        .iter()
+        .position(|share| {
+            cumulative += share;
+            u < cumulative
         })
-        .collect()
+        .unwrap_or(config.reporting_pattern.len().saturating_sub(1));
+    (years as u64 + 1) * Day::DAYS_PER_YEAR
 }
 
-/// A single entry in a standalone catastrophe event catalog.
-#[derive(Serialize)]
+/// Negative binomial count with mean `mean` and Var = mean + contagion·mean²: a Poisson draw
+/// whose mean is Gamma(1/contagion, contagion·mean).
+fn negative_binomial(mean: f64, contagion: f64, rng: &mut impl Rng) -> u64 {
+    let Ok(gamma) = Gamma::new(1.0 / contagion, contagion * mean) else { return 0 };
+    let lambda = gamma.sample(rng);
+    Poisson::new(lambda).map_or(0, |p| p.sample(rng) as u64)
+}
+
+/// The attritional model for `year` under claims inflation: `mu` shifted by
+/// `InflationConfig::severity_shift`, so every damage fraction scales by the cumulative
+/// inflation factor. Overridden `mu`s shift with it. Unchanged without an inflation config.
+pub fn trended_attritional(
+    config: &AttritionalConfig,
+    inflation: Option<&InflationConfig>,
+    year: Year,
+) -> AttritionalConfig {
+    let shift = inflation.map_or(0.0, |i| i.severity_shift(year));
+    let mut trended = AttritionalConfig { mu: config.mu + shift, ..config.clone() };
+    for o in &mut trended.overrides {
+        if let Some(mu) = o.mu.as_mut() {
+            *mu += shift;
+        }
+    }
+    trended
+}
+
+/// Schedule attritional `AssetDamage` events for a single insured from `from_day`.
+/// Single-exposure form of [`schedule_attritional_losses`]; used when an insured's
+/// coverage request lands in a year whose `YearStart` pass has already run.
+pub fn schedule_attritional_losses_for_insured(
+    insured_id: InsuredId,
+    risk: &Risk,
+    from_day: Day,
+    rate_multiplier: f64,
+    rng: &mut impl Rng,
+    config: &AttritionalConfig,
+    territories: &TerritoryTable,
+) -> Vec<(Day, Event)> {
+    schedule_attritional_losses(&[(insured_id, risk, from_day, rate_multiplier)], rng, config, territories)
+}
+
+/// Occurrence loss at `return_period` years on a cat aggregate spread across territories
+/// (`exposure`, cents of sum insured per territory), from the analytic occurrence exceedance
+/// curve of `cat.event_classes`. Each class strikes one of `cat.territories` uniformly at
+/// Poisson rate `annual_frequency`, with a Pareto damage fraction truncated at
+/// `max_damage_fraction`, and spreads over its `footprint`. An occurrence centred on `t`
+/// loses `D × X_t`, where `X_t = Σ_u w_c(t, u) E_u` is the footprint-weighted exposure, so the
+/// annual rate of occurrences over loss `L` is
+///
+///   ν(L) = Σ_c λ_c / n × Σ_t P(D_c > L / X_t)
+///
+/// and the 1-in-N loss solves `1 − exp(−ν(L)) = 1/N`. Clustering secondaries and deductibles
+/// are ignored; exposure outside `cat.territories` is never struck.
+pub fn occurrence_pml(cat: &CatConfig, exposure: &std::collections::HashMap<Territory, u64>, return_period: f64) -> u64 {
+    let n = cat.territories.len();
+    let exposures: Vec<f64> =
+        (0..n).map(|i| exposure.get(&Territory(i as u16)).copied().unwrap_or(0) as f64).collect();
+    if n == 0 || exposures.iter().all(|&e| e <= 0.0) || return_period <= 1.0 {
+        return 0;
+    }
+    // Footprint-weighted exposure of an occurrence of each class centred on each territory.
+    let struck: Vec<Vec<f64>> = cat
+        .event_classes
+        .iter()
+        .map(|c| {
+            (0..n)
+                .map(|i| (0..n).map(|j| footprint_weight(c, i, j) * exposures[j]).sum::<f64>())
+                .filter(|&x| x > 0.0)
+                .collect()
+        })
+        .collect();
+    let exceedance_rate = |loss: f64| -> f64 {
+        cat.event_classes
+            .iter()
+            .zip(&struck)
+            .map(|(c, exposures)| {
+                let survival: f64 = exposures
+                    .iter()
+                    .map(|e| {
+                        let d = loss / e;
+                        if d >= c.max_damage_fraction {
+                            0.0
+                        } else if d <= c.pareto_scale {
+                            1.0
+                        } else {
+                            (c.pareto_scale / d).powf(c.pareto_shape)
+                        }
+                    })
+                    .sum();
+                c.annual_frequency.max(0.0) / n as f64 * survival
+            })
+            .sum()
+    };
+    let target = -(1.0 - 1.0 / return_period).ln();
+    if exceedance_rate(0.0) < target {
+        return 0;
+    }
+    let max_damage = cat.event_classes.iter().map(|c| c.max_damage_fraction).fold(0.0_f64, f64::max);
+    let (mut lo, mut hi) = (0.0, struck.iter().flatten().copied().fold(0.0_f64, f64::max) * max_damage);
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if exceedance_rate(mid) >= target { lo = mid } else { hi = mid }
+    }
+    lo.round() as u64
+}
+
+/// A single entry in a standalone catastrophe event catalog: generated by `cat_catalog`, or
+/// read by `parse_catalogue` and replayed through `CatConfig.catalogue`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CatCatalogEntry {
+    /// Simulation year, counting warm-up years (1 = the first simulated year).
     pub year: u32,
     /// Absolute day within the year (1–359).
     pub day: u64,
     pub territory: String,
     pub damage_fraction: f64,
+    /// Always "WindstormAtlantic", the only catastrophe peril.
+    #[serde(default = "windstorm")]
     pub peril: String,
-    /// Event class label (e.g. "minor" or "major") from the `CatEventClass`.
+    /// Event class label (e.g. "minor" or "major") from the `CatEventClass`; free text in a
+    /// historical catalogue.
+    #[serde(default)]
     pub class: String,
 }
 
+fn windstorm() -> String {
+    "WindstormAtlantic".to_string()
+}
+
+/// Why an event catalogue could not be read.
+#[derive(Debug, PartialEq)]
+pub enum CatalogueParseError {
+    Empty,
+    MissingColumn(&'static str),
+    BadValue { line: usize, column: &'static str, value: String },
+    BadRecord { line: usize, message: String },
+}
+
+impl std::fmt::Display for CatalogueParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "catalogue is empty"),
+            Self::MissingColumn(c) => write!(f, "catalogue header has no {c:?} column"),
+            Self::BadValue { line, column, value } => write!(f, "line {line}: bad {column} {value:?}"),
+            Self::BadRecord { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+const CATALOGUE_COLUMNS: [&str; 4] = ["year", "day", "territory", "damage_fraction"];
+
+/// Read an event catalogue for playback: NDJSON as written by `cat_catalog`, or a CSV whose
+/// header names `year,day,territory,damage_fraction` (optionally `peril` and `class`) in any
+/// order. Blank lines and `#` comments are skipped. Days must lie in 1..360, damage fractions
+/// in (0, 1] and the peril, if given, must be WindstormAtlantic. Entries are returned in
+/// (year, day) order, ties in file order.
+pub fn parse_catalogue(text: &str) -> Result<Vec<CatCatalogEntry>, CatalogueParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+        .peekable();
+    let &(_, first) = lines.peek().ok_or(CatalogueParseError::Empty)?;
+
+    let mut entries = Vec::new();
+    if first.starts_with('{') {
+        for (line, row) in lines {
+            let entry: CatCatalogEntry = serde_json::from_str(row)
+                .map_err(|e| CatalogueParseError::BadRecord { line, message: e.to_string() })?;
+            entries.push((line, entry));
+        }
+    } else {
+        let (_, header) = lines.next().ok_or(CatalogueParseError::Empty)?;
+        let names: Vec<String> = header.split(',').map(|h| h.trim().to_ascii_lowercase()).collect();
+        let mut idx = [0usize; 4];
+        for (slot, col) in idx.iter_mut().zip(CATALOGUE_COLUMNS) {
+            *slot = names.iter().position(|n| n == col).ok_or(CatalogueParseError::MissingColumn(col))?;
+        }
+        let optional = |col: &str| names.iter().position(|n| n == col);
+        let (peril_idx, class_idx) = (optional("peril"), optional("class"));
+        for (line, row) in lines {
+            let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+            let field = |k: usize| fields.get(idx[k]).copied().unwrap_or("");
+            let bad = |k: usize| CatalogueParseError::BadValue {
+                line,
+                column: CATALOGUE_COLUMNS[k],
+                value: field(k).to_string(),
+            };
+            let entry = CatCatalogEntry {
+                year: field(0).parse().map_err(|_| bad(0))?,
+                day: field(1).parse().map_err(|_| bad(1))?,
+                territory: field(2).to_string(),
+                damage_fraction: field(3).parse().map_err(|_| bad(3))?,
+                peril: peril_idx.and_then(|i| fields.get(i)).map_or_else(windstorm, |p| p.to_string()),
+                class: class_idx.and_then(|i| fields.get(i)).map_or_else(String::new, |c| c.to_string()),
+            };
+            entries.push((line, entry));
+        }
+    }
+
+    for (line, e) in &entries {
+        let message = if e.year == 0 || !(1..Day::DAYS_PER_YEAR).contains(&e.day) {
+            "year must be ≥ 1 and day in 1..360"
+        } else if !(e.damage_fraction > 0.0 && e.damage_fraction <= 1.0) {
+            "damage_fraction must be in (0, 1]"
+        } else if e.peril != "WindstormAtlantic" {
+            "peril must be WindstormAtlantic"
+        } else if e.territory.is_empty() {
+            "territory is empty"
+        } else {
+            continue;
+        };
+        return Err(CatalogueParseError::BadRecord { line: *line, message: message.to_string() });
+    }
+    let mut entries: Vec<CatCatalogEntry> = entries.into_iter().map(|(_, e)| e).collect();
+    entries.sort_by_key(|e| (e.year, e.day));
+    Ok(entries)
+}
+
 /// Generate `n_years` of stochastic cat events independent of the market simulation.
 ///
 /// Iterates over `cat.event_classes` and runs one independent Poisson draw per class.
 /// Damage fractions are sampled at generation time from each class's Pareto model,
 /// consistent with the main simulation's approach of embedding `damage_fraction` in
-/// `LossEvent` at scheduling time.
+/// `LossEvent` at scheduling time. Seasonality and clustering apply as in the simulation.
 pub fn generate_cat_catalog(
     cat: &CatConfig,
     n_years: u32,
@@ -165,25 +572,11 @@ pub fn generate_cat_catalog(
     let mut entries = Vec::new();
     for year in 1..=n_years {
         for class in &cat.event_classes {
-            if class.annual_frequency <= 0.0 {
-                continue;
-            }
-            let damage_model = DamageFractionModel::Pareto {
-                scale: class.pareto_scale,
-                shape: class.pareto_shape,
-                cap: class.max_damage_fraction,
-            };
-            let poisson = Poisson::new(class.annual_frequency).expect("invalid Poisson lambda");
-            let n = poisson.sample(rng) as u64;
-            for _ in 0..n {
-                let day = rng.random_range(1_u64..360);
-                let territory_idx = rng.random_range(0..cat.territories.len());
-                let territory = cat.territories[territory_idx].clone();
-                let damage_fraction = damage_model.sample(rng);
+            for (day, territory, damage_fraction) in sample_class_year(cat, class, rng) {
                 entries.push(CatCatalogEntry {
                     year,
                     day,
-                    territory,
+                    territory: cat.territories[territory].clone(),
                     damage_fraction,
                     peril: "WindstormAtlantic".to_string(),
                     class: class.label.clone(),
@@ -200,15 +593,15 @@ mod tests {
     use rand_chacha::ChaCha20Rng;
 
     use super::*;
-    use crate::config::{AttritionalConfig, CatConfig, CatEventClass, ASSET_VALUE};
-    use crate::types::{Day, InsuredId, Year};
+    use crate::config::{AttritionalConfig, AttritionalOverride, CatConfig, LargeLossConfig, CatEventClass, ClusterConfig, ASSET_VALUE};
+    use crate::types::{Day, InsuredId, Territory, TerritoryTable, Year};
 
     fn rng() -> ChaCha20Rng {
         ChaCha20Rng::seed_from_u64(42)
     }
 
     fn att_config() -> AttritionalConfig {
-        AttritionalConfig { annual_rate: 10.0, mu: -3.0, sigma: 1.0 }
+        AttritionalConfig { annual_rate: 10.0, mu: -3.0, sigma: 1.0, contagion: 0.0, overrides: vec![] }
     }
 
     /// Single-class cat config (λ=2.0, Pareto(0.05, 1.5), cap=1.0). Used by legacy tests.
@@ -220,8 +613,12 @@ mod tests {
                 pareto_scale: 0.05,
                 pareto_shape: 1.5,
                 max_damage_fraction: 1.0,
+                footprint: vec![],
             }],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         }
     }
 
@@ -235,6 +632,7 @@ mod tests {
                     pareto_scale: 0.01,
                     pareto_shape: 3.5,
                     max_damage_fraction: 0.05, // minor cap
+                    footprint: vec![],
                 },
                 CatEventClass {
                     label: "major".to_string(),
@@ -242,17 +640,23 @@ mod tests {
                     pareto_scale: 0.10,
                     pareto_shape: 2.5,
                     max_damage_fraction: 0.50,
+                    footprint: vec![],
                 },
             ],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         }
     }
 
     fn small_risk() -> Risk {
         Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+            attachment: 0,
+            limit: ASSET_VALUE,
         }
     }
 
@@ -334,8 +738,12 @@ mod tests {
                 pareto_scale: 0.05,
                 pareto_shape: 1.5,
                 max_damage_fraction: 1.0,
+                footprint: vec![],
             }],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let years = 100u32;
@@ -376,8 +784,12 @@ mod tests {
                 pareto_scale: 0.05,
                 pareto_shape: 1.5,
                 max_damage_fraction: 1.0,
+                footprint: vec![],
             }],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let mut next_id = 0u64;
@@ -393,8 +805,186 @@ mod tests {
         }
     }
 
+    #[test]
+    fn season_profile_concentrates_arrivals_in_window() {
+        let mut cfg = cat_config();
+        cfg.event_classes[0].annual_frequency = 20.0;
+        cfg.season = Some(SeasonProfile { window_start: 210, window_end: 330, window_share: 0.9 });
+        let mut rng = rng();
+        let mut next_id = 0u64;
+        let (mut inside, mut total) = (0usize, 0usize);
+        for y in 1..=50 {
+            let year_start = Day::year_start(Year(y));
+            for (day, _) in schedule_loss_events(&cfg, Year(y), &mut rng, &mut next_id) {
+                let offset = day.0 - year_start.0;
+                assert!((1..360).contains(&offset), "offset {offset} outside the year");
+                inside += (210..=330).contains(&offset) as usize;
+                total += 1;
+            }
+        }
+        let share = inside as f64 / total as f64;
+        assert!((0.86..0.94).contains(&share), "window share {share:.3}, expected ≈ 0.9");
+    }
+
+    #[test]
+    fn clustering_spawns_secondaries_near_parent_in_same_territory() {
+        let mut cfg = cat_config();
+        cfg.territories = vec!["US-NE".to_string(), "US-SE".to_string(), "US-Gulf".to_string()];
+        cfg.clustering = Some(ClusterConfig { mean_secondaries: 2.0, max_lag_days: 10, damage_scale: 0.5 });
+        let mut rng = rng();
+        let mut parents = 0usize;
+        let mut secondaries = 0usize;
+        for _ in 0..200 {
+            let arrivals = sample_class_year(&cfg, &cfg.event_classes[0], &mut rng);
+            // Each parent is followed by its secondaries: same territory, 1..=10 days later.
+            let mut parent: Option<(u64, usize)> = None;
+            for (day, territory, df) in arrivals {
+                match parent {
+                    Some((pday, pterr)) if day > pday && day - pday <= 10 && territory == pterr => {
+                        assert!(df <= 0.5, "secondary damage is scaled by 0.5");
+                        secondaries += 1;
+                    }
+                    _ => {
+                        parents += 1;
+                        parent = Some((day, territory));
+                    }
+                }
+            }
+        }
+        let per_parent = secondaries as f64 / parents as f64;
+        assert!(
+            (1.6..2.2).contains(&per_parent),
+            "{per_parent:.2} secondaries per parent; expected just under 2 (year-end truncation)"
+        );
+    }
+
     // ── schedule_attritional_losses_for_insured tests ────────────────────────
 
+    /// The batch pass gives each exposure its own window and skips exposures with none left.
+    #[test]
+    fn batch_attritional_respects_each_exposure_window() {
+        let risk = small_risk();
+        let late = Day::year_start(Year(1)).offset(300);
+        let exposures = [
+            (InsuredId(1), &risk, Day::year_start(Year(1)), 1.0),
+            (InsuredId(2), &risk, late, 1.0),
+            (InsuredId(3), &risk, Day::year_end(Year(1)), 1.0),
+        ];
+        let events = schedule_attritional_losses(&exposures, &mut rng(), &att_config(), &TerritoryTable::new(&["US-SE"]));
+        assert!(events.iter().any(|(_, e)| matches!(e, Event::AssetDamage { insured_id: InsuredId(1), .. })));
+        for (day, e) in &events {
+            let Event::AssetDamage { insured_id, .. } = e else { panic!("only AssetDamage") };
+            assert_ne!(*insured_id, InsuredId(3), "no window left at year end");
+            if *insured_id == InsuredId(2) {
+                assert!(*day > late && *day <= Day::year_end(Year(1)));
+            }
+        }
+    }
+
+    /// A rate multiplier scales the exposure's attritional frequency; 0 switches it off.
+    #[test]
+    fn attritional_rate_multiplier_scales_frequency() {
+        let risk = small_risk();
+        let count = |m: f64| {
+            let exposures: Vec<_> =
+                (1..=200).map(|i| (InsuredId(i), &risk, Day::year_start(Year(1)), m)).collect();
+            schedule_attritional_losses(&exposures, &mut rng(), &att_config(), &TerritoryTable::new(&["US-SE"])).len() as f64
+        };
+        let ratio = count(2.0) / count(1.0);
+        assert!((1.8..2.2).contains(&ratio), "doubling the rate gave {ratio:.2}× the losses");
+        assert_eq!(count(0.0), 0.0);
+    }
+
+    /// Contagion keeps the mean claim count but overdisperses it: Var ≈ mean + c·mean².
+    #[test]
+    fn attritional_contagion_overdisperses_claim_counts() {
+        let risk = small_risk();
+        let moments = |contagion: f64| {
+            let config = AttritionalConfig { contagion, ..att_config() };
+            let exposures: Vec<_> =
+                (1..=2_000).map(|i| (InsuredId(i), &risk, Day::year_start(Year(1)), 1.0)).collect();
+            let mut counts = vec![0.0; exposures.len()];
+            for (_, e) in schedule_attritional_losses(&exposures, &mut rng(), &config, &TerritoryTable::new(&["US-SE"])) {
+                let Event::AssetDamage { insured_id, .. } = e else { panic!("only AssetDamage") };
+                counts[insured_id.0 as usize - 1] += 1.0;
+            }
+            let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+            let var = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+            (mean, var)
+        };
+        let (mean, var) = moments(0.0);
+        assert!((var / mean - 1.0).abs() < 0.15, "Poisson: var/mean = {:.2}", var / mean);
+        let (nb_mean, nb_var) = moments(0.5);
+        assert!((nb_mean / 10.0 - 1.0).abs() < 0.1, "mean preserved, got {nb_mean:.2}");
+        assert!(nb_var / nb_mean > 4.0, "c=0.5 at mean 10 implies var/mean ≈ 6, got {:.2}", nb_var / nb_mean);
+    }
+
+    /// An insured's own override wins over its territory's; others keep the base parameters.
+    #[test]
+    fn attritional_overrides_apply_per_insured_then_per_territory() {
+        let config = AttritionalConfig {
+            overrides: vec![
+                AttritionalOverride { territory: Some("US-SE".into()), annual_rate: Some(0.0), ..Default::default() },
+                AttritionalOverride { insured_id: Some(InsuredId(2)), mu: Some(-5.0), ..Default::default() },
+            ],
+            ..att_config()
+        };
+        assert_eq!(config.params_for(InsuredId(1), "US-SE").annual_rate, 0.0);
+        assert_eq!(config.params_for(InsuredId(1), "US-NE"), config.params_for(InsuredId(3), "US-NE"));
+        let own = config.params_for(InsuredId(2), "US-SE");
+        assert_eq!((own.annual_rate, own.mu), (10.0, -5.0), "insured override, not the territory's");
+
+        let risk = small_risk();
+        let exposures = [(InsuredId(1), &risk, Day::year_start(Year(1)), 1.0), (InsuredId(2), &risk, Day::year_start(Year(1)), 1.0)];
+        let events = schedule_attritional_losses(&exposures, &mut rng(), &config, &TerritoryTable::new(&["US-SE"]));
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|(_, e)| matches!(e, Event::AssetDamage { insured_id: InsuredId(2), .. })));
+    }
+
+    /// Large losses only strike risks covering LargeFire, are capped at total loss, and the
+    /// heavy Pareto tail destroys some assets outright.
+    #[test]
+    fn large_losses_are_single_risk_and_capped_at_sum_insured() {
+        let config = LargeLossConfig { annual_rate: 0.5, scale: 0.2, shape: 1.0 };
+        let covered = Risk { perils_covered: vec![Peril::Attritional, Peril::LargeFire], ..small_risk() };
+        let uncovered = small_risk();
+        let mut exposures: Vec<_> = (1..=500).map(|i| (InsuredId(i), &covered, Day::year_start(Year(1)), 1.0)).collect();
+        exposures.push((InsuredId(501), &uncovered, Day::year_start(Year(1)), 1.0));
+        let events = schedule_large_losses(&exposures, &mut rng(), &config);
+        assert!((200..300).contains(&events.len()), "≈250 expected, got {}", events.len());
+        let mut total_losses = 0;
+        for (_, e) in &events {
+            let Event::AssetDamage { insured_id, peril, ground_up_loss } = e else { panic!("only AssetDamage") };
+            assert_eq!(*peril, Peril::LargeFire);
+            assert_ne!(*insured_id, InsuredId(501), "peril not covered");
+            assert!(*ground_up_loss >= ASSET_VALUE / 5 && *ground_up_loss <= ASSET_VALUE);
+            total_losses += usize::from(*ground_up_loss == ASSET_VALUE);
+        }
+        assert!(total_losses > 0);
+    }
+
+    /// Casualty claims report in whole years after the accident, in the pattern's proportions.
+    #[test]
+    fn casualty_report_lags_follow_the_reporting_pattern() {
+        let config = CasualtyConfig {
+            annual_rate: 0.1,
+            scale: 0.05,
+            shape: 1.5,
+            reporting_pattern: vec![0.5, 0.3, 0.0, 0.2],
+            reserve_sigma: 0.0,
+        };
+        let mut rng = rng();
+        let mut counts = [0usize; 4];
+        for _ in 0..10_000 {
+            let lag = casualty_report_lag(&config, &mut rng);
+            assert_eq!(lag % Day::DAYS_PER_YEAR, 0);
+            counts[(lag / Day::DAYS_PER_YEAR) as usize - 1] += 1;
+        }
+        assert!((4_800..5_200).contains(&counts[0]) && (2_800..3_200).contains(&counts[1]), "{counts:?}");
+        assert_eq!(counts[2], 0, "a zero share is never drawn");
+        assert!((config.mean_report_lag() - 1.9).abs() < 1e-9);
+    }
+
     /// Scheduler emits AssetDamage events with ground_up_loss ≤ sum_insured.
     #[test]
     fn attritional_produces_bounded_asset_damages() {
@@ -404,8 +994,10 @@ mod tests {
             InsuredId(1),
             &risk,
             Day::year_start(Year(1)),
+            1.0,
             &mut rng,
             &att_config(),
+            &TerritoryTable::new(&["US-SE"]),
         );
         assert!(!events.is_empty(), "expected events with rate=10.0");
         for (_, e) in &events {
@@ -426,15 +1018,19 @@ mod tests {
         let mut rng = rng();
         let risk = Risk {
             sum_insured: ASSET_VALUE,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![Peril::WindstormAtlantic], // no Attritional
+            attachment: 0,
+            limit: ASSET_VALUE,
         };
         let events = schedule_attritional_losses_for_insured(
             InsuredId(1),
             &risk,
             Day::year_start(Year(1)),
+            1.0,
             &mut rng,
             &att_config(),
+            &TerritoryTable::new(&["US-SE"]),
         );
         assert!(events.is_empty(), "must return no events when Attritional not covered");
     }
@@ -447,8 +1043,10 @@ mod tests {
             InsuredId(1),
             &small_risk(),
             Day::year_start(Year(1)),
+            1.0,
             &mut rng,
             &att_config(),
+            &TerritoryTable::new(&["US-SE"]),
         );
         assert!(!events.is_empty());
         for (_, e) in &events {
@@ -501,8 +1099,12 @@ mod tests {
                 pareto_scale: 0.04,
                 pareto_shape: 2.5,
                 max_damage_fraction: 0.50,
+                footprint: vec![],
             }],
             territories: territories.clone(),
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let mut next_id = 0u64;
@@ -510,8 +1112,8 @@ mod tests {
             for (_, e) in schedule_loss_events(&cfg, Year(y), &mut rng, &mut next_id) {
                 if let Event::LossEvent { territory, .. } = e {
                     assert!(
-                        territories.contains(&territory),
-                        "event territory '{territory}' not in config list"
+                        (territory.0 as usize) < territories.len(),
+                        "event territory {territory} not in config list"
                     );
                 }
             }
@@ -533,12 +1135,16 @@ mod tests {
                 pareto_scale: 0.04,
                 pareto_shape: 2.5,
                 max_damage_fraction: 0.50,
+                footprint: vec![],
             }],
             territories: territories.clone(),
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let mut next_id = 0u64;
-        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut counts: HashMap<Territory, usize> = HashMap::new();
         for y in 1..=20u32 {
             for (_, e) in schedule_loss_events(&cfg, Year(y), &mut rng, &mut next_id) {
                 if let Event::LossEvent { territory, .. } = e {
@@ -546,8 +1152,8 @@ mod tests {
                 }
             }
         }
-        for t in &territories {
-            let n = counts.get(t).copied().unwrap_or(0);
+        for (i, t) in territories.iter().enumerate() {
+            let n = counts.get(&Territory(i as u16)).copied().unwrap_or(0);
             assert!(n >= 50, "territory '{t}' received only {n} events (expected ≈133)");
         }
     }
@@ -557,15 +1163,17 @@ mod tests {
     #[test]
     fn full_damage_fraction_gives_sum_insured() {
         // Use a high mu that forces damage_fraction → 1.0 after clipping.
-        let config = AttritionalConfig { annual_rate: 5.0, mu: 10.0, sigma: 0.01 };
+        let config = AttritionalConfig { annual_rate: 5.0, mu: 10.0, sigma: 0.01, contagion: 0.0, overrides: vec![] };
         let mut rng = rng();
         let risk = small_risk();
         let events = schedule_attritional_losses_for_insured(
             InsuredId(1),
             &risk,
             Day::year_start(Year(1)),
+            1.0,
             &mut rng,
             &config,
+            &TerritoryTable::new(&["US-SE"]),
         );
         assert!(!events.is_empty());
         for (_, e) in &events {
@@ -618,6 +1226,7 @@ mod tests {
                     pareto_scale: 0.01,
                     pareto_shape: 3.5,
                     max_damage_fraction: 0.05,
+                    footprint: vec![],
                 },
                 CatEventClass {
                     label: "major".to_string(),
@@ -625,9 +1234,13 @@ mod tests {
                     pareto_scale: 0.20,
                     pareto_shape: 2.5,
                     max_damage_fraction: 0.50,
+                    footprint: vec![],
                 },
             ],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let mut next_id = 0u64;
@@ -654,6 +1267,7 @@ mod tests {
                     pareto_scale: 0.01,
                     pareto_shape: 3.5,
                     max_damage_fraction: 0.05,
+                    footprint: vec![],
                 },
                 CatEventClass {
                     label: "major".to_string(),
@@ -661,9 +1275,13 @@ mod tests {
                     pareto_scale: 0.10,
                     pareto_shape: 2.5,
                     max_damage_fraction: 0.50,
+                    footprint: vec![],
                 },
             ],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let mut next_id = 0u64;
@@ -692,6 +1310,7 @@ mod tests {
                     pareto_scale: 0.01,
                     pareto_shape: 3.5,
                     max_damage_fraction: 0.05,
+                    footprint: vec![],
                 },
                 CatEventClass {
                     label: "major".to_string(),
@@ -699,9 +1318,13 @@ mod tests {
                     pareto_scale: 0.10,
                     pareto_shape: 2.5,
                     max_damage_fraction: 0.50,
+                    footprint: vec![],
                 },
             ],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let mut rng = rng();
         let mut next_id = 0u64;
@@ -736,4 +1359,113 @@ mod tests {
             }
         }
     }
+
+    // ── catalogue playback ────────────────────────────────────────────────────
+
+    #[test]
+    fn parse_catalogue_reads_csv_and_ndjson_alike() {
+        let csv = "# storms\nday,year,territory,damage_fraction,class\n240,2,US-Gulf,0.2,major\n100,1,US-SE,0.05,\n";
+        let ndjson = concat!(
+            r#"{"year":2,"day":240,"territory":"US-Gulf","damage_fraction":0.2,"class":"major"}"#,
+            "\n",
+            r#"{"year":1,"day":100,"territory":"US-SE","damage_fraction":0.05}"#,
+        );
+        let from_csv = parse_catalogue(csv).unwrap();
+        assert_eq!(from_csv, parse_catalogue(ndjson).unwrap());
+        assert_eq!((from_csv[0].year, from_csv[0].day), (1, 100), "sorted by year then day");
+        assert_eq!(from_csv[1].peril, "WindstormAtlantic");
+        assert_eq!(from_csv[1].class, "major");
+    }
+
+    #[test]
+    fn parse_catalogue_rejects_bad_rows() {
+        assert_eq!(parse_catalogue("\n# nothing\n"), Err(CatalogueParseError::Empty));
+        assert_eq!(
+            parse_catalogue("year,day,damage_fraction\n1,10,0.1\n"),
+            Err(CatalogueParseError::MissingColumn("territory"))
+        );
+        assert!(matches!(
+            parse_catalogue("year,day,territory,damage_fraction\n1,ten,US-SE,0.1\n"),
+            Err(CatalogueParseError::BadValue { line: 2, column: "day", .. })
+        ));
+        for row in ["1,360,US-SE,0.1", "1,10,US-SE,1.5", "0,10,US-SE,0.1", "1,10,US-SE,0.1,Flood"] {
+            let text = format!("year,day,territory,damage_fraction,peril\n{row}\n");
+            assert!(
+                matches!(parse_catalogue(&text), Err(CatalogueParseError::BadRecord { line: 2, .. })),
+                "{row} accepted"
+            );
+        }
+    }
+
+    /// A catalogue replaces the stochastic draw: each year gets exactly its own entries, on
+    /// their days, with fresh event ids and no RNG consumed.
+    #[test]
+    fn catalogue_is_replayed_exactly_without_draws() {
+        let catalogue =
+            parse_catalogue("year,day,territory,damage_fraction\n1,50,US-SE,0.1\n1,300,US-NE,0.3\n3,5,US-SE,0.02\n")
+                .unwrap();
+        let territories = vec!["US-SE".to_string(), "US-NE".to_string()];
+        let cfg = CatConfig { catalogue: Some(catalogue), territories, ..cat_config() };
+        let mut rng = rng();
+        let mut next_id = 7;
+        let year1 = schedule_loss_events(&cfg, Year(1), &mut rng, &mut next_id);
+        assert_eq!(year1.len(), 2);
+        assert_eq!(year1[1].0, Day::year_start(Year(1)).offset(300));
+        let Event::LossEvent { event_id, territory, damage_fraction, .. } = year1[1].1 else {
+            panic!("expected LossEvent")
+        };
+        assert_eq!((event_id, territory, damage_fraction), (8, Territory(1), 0.3));
+        assert!(schedule_loss_events(&cfg, Year(2), &mut rng, &mut next_id).is_empty());
+        assert_eq!(schedule_loss_events(&cfg, Year(3), &mut rng, &mut next_id).len(), 1);
+        assert_eq!(next_id, 10);
+        assert_eq!(rng.random::<u64>(), super::tests::rng().random::<u64>(), "no draws");
+    }
+
+    #[test]
+    fn occurrence_pml_matches_the_closed_form_and_rewards_spread() {
+        let mut cat = cat_config();
+        cat.event_classes[0].annual_frequency = 0.5;
+        cat.event_classes[0].pareto_scale = 0.01;
+        let one = std::collections::HashMap::from([(Territory(0), ASSET_VALUE)]);
+        // One territory, below the cap: λ (s E / L)^α = −ln(1 − 1/N)  ⇒  L = E s (λ / ν)^(1/α).
+        let nu = -(1.0_f64 - 1.0 / 200.0).ln();
+        let expected = ASSET_VALUE as f64 * 0.01 * (0.5 / nu).powf(1.0 / 1.5);
+        let pml = occurrence_pml(&cat, &one, 200.0);
+        assert!((pml as f64 - expected).abs() <= 1.0, "{pml} vs {expected}");
+        assert!(occurrence_pml(&cat, &one, 100.0) < pml && pml < occurrence_pml(&cat, &one, 250.0));
+
+        // Two territories: the same aggregate split evenly has a lower 1-in-200 loss.
+        cat.territories.push("US-NE".to_string());
+        let concentrated = std::collections::HashMap::from([(Territory(0), 2 * ASSET_VALUE)]);
+        let spread = std::collections::HashMap::from([(Territory(0), ASSET_VALUE), (Territory(1), ASSET_VALUE)]);
+        assert!(occurrence_pml(&cat, &spread, 200.0) < occurrence_pml(&cat, &concentrated, 200.0));
+        assert_eq!(occurrence_pml(&cat, &std::collections::HashMap::new(), 200.0), 0);
+    }
+
+    #[test]
+    fn footprints_spread_an_occurrence_without_changing_the_draws() {
+        let mut cat = cat_config();
+        cat.territories = ["US-NE", "US-SE", "US-Gulf", "US-West"].map(String::from).to_vec();
+        let plain = schedule_loss_events(&cat, Year(1), &mut rng(), &mut 0);
+        cat.event_classes[0].footprint = vec![0.5, 0.25];
+        let spread = schedule_loss_events(&cat, Year(1), &mut rng(), &mut 0);
+        assert_eq!(plain.len(), spread.len());
+        for ((_, a), (_, b)) in plain.iter().zip(&spread) {
+            let Event::LossEvent { territory: ta, damage_fraction: da, footprint: fa, .. } = a else { unreachable!() };
+            let Event::LossEvent { territory: tb, damage_fraction: db, footprint: fb, .. } = b else { unreachable!() };
+            assert!(ta == tb && da == db && fa.is_empty(), "the footprint draws no random numbers");
+            assert_eq!(fb, &footprint(&cat, &cat.event_classes[0], *tb, *db));
+        }
+
+        // Territory(i) is cat.territories[i]: US-SE strikes US-NE and US-Gulf, then US-West.
+        let struck = footprint(&cat, &cat.event_classes[0], Territory(1), 0.2);
+        assert_eq!(struck, vec![(Territory(0), 0.1), (Territory(2), 0.1), (Territory(3), 0.05)]);
+
+        // Correlated territories diversify less: the same spread book has a higher PML.
+        let book: std::collections::HashMap<Territory, u64> =
+            (0..cat.territories.len()).map(|i| (Territory(i as u16), ASSET_VALUE)).collect();
+        let correlated = occurrence_pml(&cat, &book, 200.0);
+        cat.event_classes[0].footprint.clear();
+        assert!(correlated > occurrence_pml(&cat, &book, 200.0));
+    }
 }
@@ -0,0 +1,118 @@
+//! Python bindings, built with the `python` feature (`maturin develop --features python`).
+//!
+//! `rins.run_simulation(config=None, seed=None)` runs one simulation and returns its event log
+//! as a list of dicts, each shaped like a line of the NDJSON log, `RunMetadata` header first.
+//! `config` is merged over the canonical config, so `{"years": 20}` changes only the horizon.
+//! `rins.analyse(events)` takes such a list and returns the post-warmup year statistics as a
+//! dict of equal-length column lists, ready for `pandas.DataFrame(...)`.
+//!
+//! Values cross the boundary as JSON, through Python's `json` module, so the dicts carry the
+//! same field names and shapes as the log files and the serde impls stay the one schema.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::{Map, Value};
+
+use crate::analysis;
+use crate::config::SimulationConfig;
+use crate::events::{Event, RunMetadata, SimEvent};
+use crate::simulation::Simulation;
+use crate::types::Day;
+
+#[pymodule]
+fn rins(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(run_simulation, m)?)?;
+    m.add_function(wrap_pyfunction!(analyse, m)?)?;
+    Ok(())
+}
+
+/// Run one simulation and return its event log as a list of dicts.
+#[pyfunction]
+#[pyo3(signature = (config=None, seed=None))]
+fn run_simulation(py: Python<'_>, config: Option<&Bound<'_, PyDict>>, seed: Option<u64>) -> PyResult<Py<PyAny>> {
+    let overrides = match config {
+        Some(dict) => serde_json::from_str(&dumps(py, dict.as_any())?).map_err(value_error)?,
+        None => Value::Object(Map::new()),
+    };
+    let events = py.detach(|| simulate(overrides, seed)).map_err(value_error)?;
+    loads(py, &serde_json::to_string(&events).map_err(value_error)?)
+}
+
+/// Post-warmup year statistics of an event log, as a dict of column lists.
+#[pyfunction]
+fn analyse(py: Python<'_>, events: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    let events: Vec<SimEvent> = serde_json::from_str(&dumps(py, events)?).map_err(value_error)?;
+    let columns = py.detach(|| year_columns(&events)).map_err(value_error)?;
+    loads(py, &serde_json::to_string(&columns).map_err(value_error)?)
+}
+
+/// `SimulationConfig::canonical_with(overrides)`, run to the horizon: the log as
+/// `batch::write_log` would write it.
+fn simulate(overrides: Value, seed: Option<u64>) -> crate::Result<Vec<SimEvent>> {
+    let mut config = SimulationConfig::canonical_with(overrides)?;
+    if let Some(seed) = seed {
+        config.seed = seed;
+    }
+    let mut sim = Simulation::from_config(config)?;
+    sim.start();
+    sim.run();
+    if let Some(e) = sim.schedule_error() {
+        return Err(e.clone().into());
+    }
+    let header = SimEvent { day: Day(0), seq: None, event: Event::RunMetadata(Box::new(RunMetadata::capture(sim.config()))) };
+    Ok(std::iter::once(header).chain(sim.log.iter().cloned()).collect())
+}
+
+/// `analysis::analyse` on a log, pivoted from one object per year to one list per field.
+fn year_columns(events: &[SimEvent]) -> crate::Result<Map<String, Value>> {
+    let expense_ratio = analysis::expense_ratio(events)
+        .unwrap_or_else(|| crate::batch::expense_ratio(&SimulationConfig::canonical()));
+    let (_, stats) = analysis::analyse(events, &analysis::initial_capitals(events), expense_ratio);
+    let mut columns = Map::new();
+    for row in stats {
+        let Value::Object(fields) = serde_json::to_value(row)? else { unreachable!("YearStats serializes as an object") };
+        for (field, value) in fields {
+            match columns.entry(field).or_insert_with(|| Value::Array(vec![])) {
+                Value::Array(column) => column.push(value),
+                _ => unreachable!(),
+            }
+        }
+    }
+    Ok(columns)
+}
+
+fn dumps(py: Python<'_>, value: &Bound<'_, PyAny>) -> PyResult<String> {
+    py.import("json")?.call_method1("dumps", (value,))?.extract()
+}
+
+fn loads(py: Python<'_>, json: &str) -> PyResult<Py<PyAny>> {
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn value_error(e: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_merge_over_the_canonical_config_and_analyse_pivots_to_columns() {
+        let overrides = serde_json::json!({ "years": 2, "warmup_years": 1, "n_insureds": 20 });
+        let events = simulate(overrides, Some(11)).unwrap();
+        let Event::RunMetadata(ref metadata) = events[0].event else { panic!("header first") };
+        assert_eq!(metadata.seed, 11);
+        assert_eq!(metadata.config["years"], 2);
+        let canonical = serde_json::to_value(SimulationConfig::canonical()).unwrap();
+        assert_eq!(metadata.config["insurers"], canonical["insurers"], "unnamed fields keep their defaults");
+
+        let columns = year_columns(&events).unwrap();
+        assert_eq!(columns["year"], serde_json::json!([2, 3]));
+        assert!(columns.values().all(|c| c.as_array().is_some_and(|c| c.len() == 2)));
+
+        let bad = simulate(serde_json::json!({ "years": "ten" }), None);
+        assert!(matches!(bad, Err(crate::Error::Json(_))));
+    }
+}
@@ -0,0 +1,170 @@
+//! Prudential regulator: a graduated intervention ladder on the solvency ratio.
+//!
+//! At each YearEnd the regulator reads every writing insurer's `capital / SCR` (see
+//! `SolvencyConfig`) and places it on one rung:
+//!
+//! - **Restricted to renewals** below `restrict_below`: the insurer may quote only risks it
+//!   already carries. New business is declined with `DeclineReason::RegulatoryRestriction`.
+//! - **Forced run-off** below `runoff_below`: no new business at all; the book runs off.
+//! - **Licence revoked** below `revoke_below`: the insurer is closed as insolvent.
+//!
+//! An insurer whose ratio recovers moves back up the ladder at the next review, and is
+//! reinstated in full at or above `restrict_below`. Revocation is final. Insurers in voluntary
+//! run-off are left alone unless their ratio falls below `revoke_below`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RegulatorConfig;
+use crate::events::{Event, RegulatoryAction};
+use crate::insurer::Insurer;
+use crate::types::{Day, InsurerId};
+
+/// Intervention state per insurer; empty without `config.regulator`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Regulator {
+    config: Option<RegulatorConfig>,
+    /// Standing intervention per insurer. Absent = full licence.
+    actions: HashMap<InsurerId, RegulatoryAction>,
+}
+
+impl Regulator {
+    pub fn new(config: Option<RegulatorConfig>) -> Self {
+        Regulator { config, actions: HashMap::new() }
+    }
+
+    /// Whether `insurer_id` may only renew business it already carries.
+    pub fn is_restricted(&self, insurer_id: InsurerId) -> bool {
+        self.actions.get(&insurer_id) == Some(&RegulatoryAction::RestrictedToRenewals)
+    }
+
+    /// Whether `insurer_id` is under any standing intervention.
+    pub fn is_intervening(&self, insurer_id: InsurerId) -> bool {
+        self.actions.contains_key(&insurer_id)
+    }
+
+    /// YearEnd review: move each insurer to the rung its solvency ratio calls for and apply it.
+    /// Emits one `RegulatoryIntervention` per change of rung, in insurer order, and
+    /// `InsurerInsolvent` after a revocation. No-op without a regulator config, and for insurers
+    /// without a solvency ratio (no `SolvencyConfig`).
+    pub fn review(&mut self, day: Day, insurers: &mut [Insurer]) -> Vec<(Day, Event)> {
+        let Some(config) = self.config.as_ref() else {
+            return vec![];
+        };
+        let mut events = vec![];
+        for insurer in insurers.iter_mut() {
+            if insurer.insolvent {
+                continue;
+            }
+            let Some(ratio) = insurer.solvency_ratio() else { continue };
+            let current = self.actions.get(&insurer.id).copied();
+            let voluntary_runoff = insurer.runoff && current != Some(RegulatoryAction::ForcedRunoff);
+            let rung = if ratio < config.revoke_below {
+                Some(RegulatoryAction::LicenceRevoked)
+            } else if voluntary_runoff {
+                continue;
+            } else if ratio < config.runoff_below {
+                Some(RegulatoryAction::ForcedRunoff)
+            } else if ratio < config.restrict_below {
+                Some(RegulatoryAction::RestrictedToRenewals)
+            } else {
+                None
+            };
+            if rung == current {
+                continue;
+            }
+            let action = match rung {
+                Some(action) => {
+                    self.actions.insert(insurer.id, action);
+                    action
+                }
+                None => {
+                    self.actions.remove(&insurer.id);
+                    RegulatoryAction::Reinstated
+                }
+            };
+            events.push((day, Event::RegulatoryIntervention { insurer_id: insurer.id, action }));
+            match action {
+                RegulatoryAction::LicenceRevoked => {
+                    insurer.insolvent = true;
+                    events.push((day, Event::InsurerInsolvent { insurer_id: insurer.id }));
+                }
+                RegulatoryAction::ForcedRunoff => insurer.runoff = true,
+                RegulatoryAction::RestrictedToRenewals | RegulatoryAction::Reinstated => insurer.runoff = false,
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SolvencyConfig, ASSET_VALUE};
+    use crate::events::Peril;
+    use crate::types::PolicyId;
+
+    fn config() -> RegulatorConfig {
+        RegulatorConfig { restrict_below: 1.0, runoff_below: 0.7, revoke_below: 0.3 }
+    }
+
+    /// An insurer whose SCR is its cat PML: 25M × 0.252 = 6.3M per full line written.
+    fn insurer(capital: i64, lines: u64) -> Insurer {
+        let solvency = SolvencyConfig { premium_risk_factor: 0.0, reserve_risk_factor: 0.0, target_ratio: 1.0, min_ratio: 0.0 };
+        let mut ins = Insurer::new(InsurerId(1), capital, 0.239, 0.0, 0.70, 0.3, 0.0, 0.0, None, None, 0.252, 0.0, 0.0, 1.0, 0.30, 0.0, 0.0, 1.0, 1.0)
+            .with_solvency(Some(solvency));
+        for pid in 0..lines {
+            ins.on_policy_bound(PolicyId(pid), ASSET_VALUE, 0, &[Peril::WindstormAtlantic], 1.0);
+        }
+        ins
+    }
+
+    fn actions(events: &[(Day, Event)]) -> Vec<RegulatoryAction> {
+        events
+            .iter()
+            .filter_map(|(_, e)| match e {
+                Event::RegulatoryIntervention { action, .. } => Some(*action),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ladder_restricts_then_forces_runoff_then_reinstates() {
+        let mut regulator = Regulator::new(Some(config()));
+        // 10 lines: SCR 63M. Capital 50M → ratio ≈ 0.79: restricted to renewals.
+        let mut insurers = vec![insurer(5_000_000_000, 10)];
+        assert_eq!(actions(&regulator.review(Day(359), &mut insurers)), [RegulatoryAction::RestrictedToRenewals]);
+        assert!(regulator.is_restricted(InsurerId(1)));
+        assert!(regulator.review(Day(719), &mut insurers).is_empty(), "no change of rung, no event");
+
+        insurers[0].capital = 4_000_000_000; // ratio ≈ 0.63
+        assert_eq!(actions(&regulator.review(Day(719), &mut insurers)), [RegulatoryAction::ForcedRunoff]);
+        assert!(insurers[0].runoff && !regulator.is_restricted(InsurerId(1)));
+
+        insurers[0].capital = 7_000_000_000; // ratio ≈ 1.11
+        assert_eq!(actions(&regulator.review(Day(1079), &mut insurers)), [RegulatoryAction::Reinstated]);
+        assert!(!insurers[0].runoff && !regulator.is_intervening(InsurerId(1)));
+    }
+
+    #[test]
+    fn licence_revocation_closes_the_insurer_as_insolvent() {
+        let mut regulator = Regulator::new(Some(config()));
+        let mut insurers = vec![insurer(1_000_000_000, 10)]; // ratio ≈ 0.16
+        let events = regulator.review(Day(359), &mut insurers);
+        assert_eq!(actions(&events), [RegulatoryAction::LicenceRevoked]);
+        assert!(matches!(events[1].1, Event::InsurerInsolvent { insurer_id: InsurerId(1) }));
+        assert!(insurers[0].insolvent);
+        assert!(regulator.review(Day(719), &mut insurers).is_empty(), "revocation is final");
+    }
+
+    #[test]
+    fn voluntary_runoff_is_left_alone_above_the_revocation_floor() {
+        let mut regulator = Regulator::new(Some(config()));
+        let mut insurers = vec![insurer(4_000_000_000, 10)]; // ratio ≈ 0.63
+        insurers[0].runoff = true;
+        assert!(regulator.review(Day(359), &mut insurers).is_empty());
+        assert!(insurers[0].runoff);
+    }
+}
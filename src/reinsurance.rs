@@ -0,0 +1,194 @@
+//! Cat excess-of-loss reinsurance: the insurers' annual purchasing decision and the price
+//! cycle of the reinsurance market that sells to them.
+//!
+//! At each YearStart every writing insurer buys a one-year occurrence XoL on its
+//! WindstormAtlantic book. The retention is a fraction of capital; the exhaustion point is the
+//! modelled occurrence PML of the in-force book at the chosen return period. The limit bought
+//! shrinks as reinsurance hardens (`price_elasticity`), so a post-loss reinsurance market
+//! leaves insurers retaining more of the next occurrence — net results diverge from gross.
+//!
+//! Reinsurers price off their own experience: the rate on line is `base_rate_on_line` times
+//! a cycle factor tracking an EWMA of the reinsurers' loss ratio against a target. Each
+//! treaty recovers at most its limit over the year (no reinstatements) and lapses at YearEnd.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ReinsuranceConfig;
+use crate::events::Event;
+use crate::types::{Day, InsurerId};
+
+/// One in-force annual treaty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XolTreaty {
+    pub treaty_id: u64,
+    pub insurer_id: InsurerId,
+    /// Per-occurrence retention (cents).
+    pub attachment: u64,
+    /// Limit still available for the rest of the year (cents).
+    pub remaining_limit: u64,
+}
+
+/// The reinsurance market: in-force treaties and the price cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReinsuranceMarket {
+    treaties: Vec<XolTreaty>,
+    next_treaty_id: u64,
+    /// EWMA of the reinsurers' annual loss ratio; starts at the target (a neutral cycle).
+    loss_ratio_ewma: f64,
+    premium_ytd: u64,
+    recoveries_ytd: u64,
+}
+
+impl ReinsuranceMarket {
+    pub fn new(config: &ReinsuranceConfig) -> Self {
+        ReinsuranceMarket {
+            treaties: vec![],
+            next_treaty_id: 0,
+            loss_ratio_ewma: config.target_loss_ratio,
+            premium_ytd: 0,
+            recoveries_ytd: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.treaties.is_empty()
+    }
+
+    /// Current rate on line: `base_rate_on_line` × the cycle factor.
+    pub fn rate_on_line(&self, config: &ReinsuranceConfig) -> f64 {
+        let factor = if config.target_loss_ratio > 0.0 { self.loss_ratio_ewma / config.target_loss_ratio } else { 1.0 };
+        config.base_rate_on_line * factor.clamp(config.min_rate_factor, config.max_rate_factor)
+    }
+
+    /// The limit `insurer_id` buys on a book with occurrence PML `pml` at `config.return_period`
+    /// and current `capital`, at today's rate. Zero when the PML sits inside the retention.
+    pub fn limit_demanded(&self, config: &ReinsuranceConfig, capital: u64, pml: u64) -> (u64, u64) {
+        let attachment = (capital as f64 * config.retention_capital_fraction).round() as u64;
+        let rate = self.rate_on_line(config);
+        let appetite = if rate > 0.0 { (config.base_rate_on_line / rate).powf(config.price_elasticity) } else { 1.0 };
+        let limit = (pml.saturating_sub(attachment) as f64 * appetite.min(1.0)).round() as u64;
+        (attachment, limit)
+    }
+
+    /// Place `insurer_id`'s treaty for the year. Returns `ReinsurancePurchased` on `day`, or
+    /// nothing when the limit demanded is zero.
+    pub fn purchase(
+        &mut self,
+        day: Day,
+        insurer_id: InsurerId,
+        capital: u64,
+        pml: u64,
+        config: &ReinsuranceConfig,
+    ) -> Vec<(Day, Event)> {
+        let (attachment, limit) = self.limit_demanded(config, capital, pml);
+        if limit == 0 {
+            return vec![];
+        }
+        let rate_on_line = self.rate_on_line(config);
+        let premium = (limit as f64 * rate_on_line).round() as u64;
+        let treaty_id = self.next_treaty_id;
+        self.next_treaty_id += 1;
+        self.premium_ytd += premium;
+        self.treaties.push(XolTreaty { treaty_id, insurer_id, attachment, remaining_limit: limit });
+        vec![(day, Event::ReinsurancePurchased {
+            treaty_id,
+            insurer_id,
+            attachment,
+            limit,
+            rate_on_line,
+            premium,
+            remaining_capital: 0, // back-filled by simulation
+        })]
+    }
+
+    /// Recover each cedant's loss from cat occurrence `event_id` in excess of its retention, up
+    /// to the limit left. `cedant_loss` gives each insurer's insured loss from the occurrence.
+    pub fn on_loss_event(&mut self, day: Day, event_id: u64, cedant_loss: impl Fn(InsurerId) -> u64) -> Vec<(Day, Event)> {
+        let mut events = vec![];
+        for treaty in self.treaties.iter_mut().filter(|t| t.remaining_limit > 0) {
+            let amount = cedant_loss(treaty.insurer_id).saturating_sub(treaty.attachment).min(treaty.remaining_limit);
+            if amount == 0 {
+                continue;
+            }
+            treaty.remaining_limit -= amount;
+            self.recoveries_ytd += amount;
+            events.push((day, Event::ReinsuranceRecovered {
+                treaty_id: treaty.treaty_id,
+                insurer_id: treaty.insurer_id,
+                event_id,
+                amount,
+                remaining_capital: 0, // back-filled by simulation
+            }));
+        }
+        events
+    }
+
+    /// YearEnd: fold the year's reinsurance loss ratio into the cycle and lapse every treaty.
+    /// A year with no premium written leaves the cycle where it was.
+    pub fn on_year_end(&mut self, config: &ReinsuranceConfig) {
+        if self.premium_ytd > 0 {
+            let loss_ratio = self.recoveries_ytd as f64 / self.premium_ytd as f64;
+            self.loss_ratio_ewma = (1.0 - config.cycle_weight) * self.loss_ratio_ewma + config.cycle_weight * loss_ratio;
+        }
+        self.premium_ytd = 0;
+        self.recoveries_ytd = 0;
+        self.treaties.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ReinsuranceConfig {
+        ReinsuranceConfig {
+            return_period: 200.0,
+            retention_capital_fraction: 0.1,
+            base_rate_on_line: 0.1,
+            target_loss_ratio: 0.5,
+            cycle_weight: 0.5,
+            min_rate_factor: 0.7,
+            max_rate_factor: 2.0,
+            price_elasticity: 1.0,
+        }
+    }
+
+    #[test]
+    fn treaty_recovers_excess_of_retention_up_to_one_limit() {
+        let c = config();
+        let mut market = ReinsuranceMarket::new(&c);
+        let bought = market.purchase(Day(0), InsurerId(1), 100_000_000, 60_000_000, &c);
+        assert!(matches!(
+            bought.as_slice(),
+            [(_, Event::ReinsurancePurchased { attachment: 10_000_000, limit: 50_000_000, premium: 5_000_000, .. })]
+        ));
+        assert!(market.purchase(Day(0), InsurerId(2), 100_000_000, 8_000_000, &c).is_empty(), "PML inside retention");
+
+        let hit = market.on_loss_event(Day(100), 3, |_| 40_000_000);
+        assert!(matches!(hit.as_slice(), [(_, Event::ReinsuranceRecovered { amount: 30_000_000, .. })]));
+        let hit = market.on_loss_event(Day(200), 4, |_| 40_000_000);
+        assert!(matches!(hit.as_slice(), [(_, Event::ReinsuranceRecovered { amount: 20_000_000, .. })]), "no reinstatement");
+        assert!(market.on_loss_event(Day(300), 5, |_| 40_000_000).is_empty());
+        market.on_year_end(&c);
+        assert!(market.is_empty(), "treaties lapse at YearEnd");
+    }
+
+    #[test]
+    fn loss_years_harden_the_rate_and_cut_the_limit_bought() {
+        let c = config();
+        let mut market = ReinsuranceMarket::new(&c);
+        assert!((market.rate_on_line(&c) - 0.1).abs() < 1e-12);
+        market.purchase(Day(0), InsurerId(1), 100_000_000, 60_000_000, &c);
+        market.on_loss_event(Day(100), 0, |_| 60_000_000);
+        market.on_year_end(&c);
+        // Loss ratio 10 → EWMA 5.25 → factor capped at 2.
+        assert!((market.rate_on_line(&c) - 0.2).abs() < 1e-12);
+        assert_eq!(market.limit_demanded(&c, 100_000_000, 60_000_000), (10_000_000, 25_000_000));
+
+        for _ in 0..10 {
+            market.purchase(Day(0), InsurerId(1), 100_000_000, 60_000_000, &c);
+            market.on_year_end(&c);
+        }
+        assert!((market.rate_on_line(&c) - 0.07).abs() < 1e-12, "clean years soften to the floor");
+    }
+}
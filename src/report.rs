@@ -0,0 +1,385 @@
+//! Self-contained HTML report: invariant results, year-character table, cross-run
+//! percentiles and inline SVG charts — one file, no scripts or external assets, so it can be
+//! opened or shared without a Rust toolchain.
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::analysis::{self, IntegrityViolation, MechanicsViolation, YearDist, YearStats};
+use crate::events::SimEvent;
+
+const CENTS_PER_BUSD: f64 = 100_000_000_000.0;
+
+/// Outcome of one invariant check, summed over every run included in the report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InvariantCheck {
+    pub name: &'static str,
+    pub violations: usize,
+}
+
+type MechanicsCheck = (&'static str, fn(&MechanicsViolation) -> bool);
+type IntegrityCheck = (&'static str, fn(&IntegrityViolation) -> bool);
+
+const MECHANICS_CHECKS: [MechanicsCheck; 7] = [
+    ("Day-offset chain", |v| matches!(v, MechanicsViolation::DayOffsetChain { .. })),
+    ("Loss before bound", |v| matches!(v, MechanicsViolation::LossBeforeBound { .. })),
+    ("Attritional strictly post-bound", |v| matches!(v, MechanicsViolation::AttrNotStrictlyPostBound { .. })),
+    ("PolicyExpired timing", |v| matches!(v, MechanicsViolation::PolicyExpiredTiming { .. })),
+    ("Claim after expiry", |v| matches!(v, MechanicsViolation::ClaimAfterExpiry { .. })),
+    ("Cat fraction consistency", |v| matches!(v, MechanicsViolation::CatFractionInconsistent { .. })),
+    ("Damage fraction valid (0,1]", |v| matches!(v, MechanicsViolation::InvalidDamageFraction { .. })),
+];
+
+const INTEGRITY_CHECKS: [IntegrityCheck; 14] = [
+    ("GUL ≤ sum insured", |v| matches!(v, IntegrityViolation::GulExceedsSumInsured { .. })),
+    ("Aggregate claim ≤ sum insured", |v| matches!(v, IntegrityViolation::AggregateClaimExceedsSumInsured { .. })),
+    ("Claim has matching loss", |v| matches!(v, IntegrityViolation::ClaimWithoutMatchingLoss { .. })),
+    ("Claim amount > 0", |v| matches!(v, IntegrityViolation::ClaimAmountZero { .. })),
+    ("Claim insurer on panel", |v| matches!(v, IntegrityViolation::ClaimInsurerMismatch { .. })),
+    ("QuoteAccepted has PolicyBound", |v| matches!(v, IntegrityViolation::QuoteAcceptedWithoutPolicyBound { .. })),
+    ("Bound panel matches accepted", |v| matches!(v, IntegrityViolation::PolicyBoundInsurerMismatch { .. })),
+    ("No duplicate PolicyBound", |v| matches!(v, IntegrityViolation::DuplicatePolicyBound { .. })),
+    ("PolicyExpired has PolicyBound", |v| matches!(v, IntegrityViolation::PolicyExpiredWithoutBound { .. })),
+    ("Panel shares sum to 100%", |v| matches!(v, IntegrityViolation::PanelSharesNotWhole { .. })),
+    ("Claim = loss × line", |v| matches!(v, IntegrityViolation::ClaimShareMismatch { .. })),
+    ("Premium = lead premium × shares", |v| matches!(v, IntegrityViolation::PremiumSplitMismatch { .. })),
+    ("Exposure snapshot = live lines", |v| matches!(v, IntegrityViolation::ExposureSnapshotMismatch { .. })),
+    ("Capital = initial + flows", |v| matches!(v, IntegrityViolation::CapitalLedgerMismatch { .. })),
+];
+
+/// Run the mechanics and integrity verifiers over one log. Quoting-protocol integrity
+/// violations not listed individually are counted under "Quoting protocol".
+pub fn invariant_checks(log: &[SimEvent]) -> Vec<InvariantCheck> {
+    count_checks(&analysis::verify_mechanics(log), &analysis::verify_integrity(log))
+}
+
+/// Result of verifying one event log offline (`rins verify`); serializes as the JSON report.
+#[derive(Debug, Clone, Serialize)]
+pub struct Verification {
+    pub events: usize,
+    pub passed: bool,
+    pub checks: Vec<InvariantCheck>,
+    /// Every violation found, mechanics first, in display form.
+    pub violations: Vec<String>,
+}
+
+/// Verify one log: per-check counts plus each violation.
+pub fn verify(log: &[SimEvent]) -> Verification {
+    let mechanics = analysis::verify_mechanics(log);
+    let integrity = analysis::verify_integrity(log);
+    let violations: Vec<String> =
+        mechanics.iter().map(ToString::to_string).chain(integrity.iter().map(ToString::to_string)).collect();
+    Verification {
+        events: log.len(),
+        passed: violations.is_empty(),
+        checks: count_checks(&mechanics, &integrity),
+        violations,
+    }
+}
+
+impl Verification {
+    /// `Err(Error::Invariant)` carrying every violation unless the log passed.
+    pub fn ensure_passed(&self) -> crate::Result<()> {
+        if self.passed { Ok(()) } else { Err(crate::Error::Invariant(self.violations.clone())) }
+    }
+}
+
+fn count_checks(mechanics: &[MechanicsViolation], integrity: &[IntegrityViolation]) -> Vec<InvariantCheck> {
+    let mut checks: Vec<InvariantCheck> = MECHANICS_CHECKS
+        .iter()
+        .map(|&(name, f)| InvariantCheck { name, violations: mechanics.iter().filter(|v| f(v)).count() })
+        .chain(INTEGRITY_CHECKS.iter().map(|&(name, f)| InvariantCheck {
+            name,
+            violations: integrity.iter().filter(|v| f(v)).count(),
+        }))
+        .collect();
+    let listed: usize = checks[MECHANICS_CHECKS.len()..].iter().map(|c| c.violations).sum();
+    checks.push(InvariantCheck { name: "Quoting protocol", violations: integrity.len() - listed });
+    checks
+}
+
+/// Sum per-run check results (same order, as produced by `invariant_checks`).
+pub fn merge_checks(runs: &[Vec<InvariantCheck>]) -> Vec<InvariantCheck> {
+    let mut merged = runs.first().cloned().unwrap_or_default();
+    for run in runs.iter().skip(1) {
+        for (total, check) in merged.iter_mut().zip(run) {
+            total.violations += check.violations;
+        }
+    }
+    merged
+}
+
+/// Render the report. The year table shows the first run; with two or more runs the charts
+/// plot the cross-run median inside a p5–p95 band and a percentile table is added.
+pub fn render(
+    title: &str,
+    runs: &[Vec<YearStats>],
+    checks: &[InvariantCheck],
+    expense_ratio: f64,
+) -> String {
+    let mut html = String::new();
+    let title = escape(title);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    let years = runs.first().map_or(0, Vec::len);
+    let _ = writeln!(
+        html,
+        "<p>{} run(s) · {years} analysis year(s) · expense ratio {:.1}%</p>",
+        runs.len(),
+        expense_ratio * 100.0
+    );
+
+    render_invariants(&mut html, checks);
+    let dists = if runs.len() >= 2 { analysis::analyse_distributions(runs, expense_ratio) } else { vec![] };
+    render_charts(&mut html, runs, &dists, expense_ratio);
+    if let Some(first) = runs.first() {
+        render_year_table(&mut html, first, expense_ratio);
+    }
+    if !dists.is_empty() {
+        render_percentiles(&mut html, &dists);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse;font-size:13px;margin-bottom:2em}\
+th,td{border:1px solid #ccc;padding:3px 8px;text-align:right}th{background:#f2f2f2}\
+td.l{text-align:left}.pass{color:#1a7f37;font-weight:bold}.fail{color:#cf222e;font-weight:bold}\
+svg{display:block;margin-bottom:1.5em}";
+
+fn render_invariants(html: &mut String, checks: &[InvariantCheck]) {
+    html.push_str("<h2>Invariants</h2>\n<table>\n<tr><th>Check</th><th>Status</th><th>Violations</th></tr>\n");
+    for c in checks {
+        let (class, status) = if c.violations == 0 { ("pass", "PASS") } else { ("fail", "FAIL") };
+        let _ = writeln!(
+            html,
+            "<tr><td class=\"l\">{}</td><td class=\"{class}\">{status}</td><td>{}</td></tr>",
+            escape(c.name),
+            c.violations
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn render_charts(html: &mut String, runs: &[Vec<YearStats>], dists: &[YearDist], expense_ratio: f64) {
+    html.push_str("<h2>Charts</h2>\n");
+    if !dists.is_empty() {
+        let xs: Vec<f64> = dists.iter().map(|d| d.year as f64).collect();
+        let band = |f: fn(&YearDist) -> (f64, f64, f64)| -> Vec<(f64, f64, f64)> {
+            dists.iter().map(f).collect()
+        };
+        html.push_str(&line_chart("Combined ratio (%)", &xs, &band(|d| pct(&d.combined_ratio))));
+        html.push_str(&line_chart("Rate on line (%)", &xs, &band(|d| pct(&d.rate_on_line))));
+        html.push_str(&line_chart("Total capital (B USD)", &xs, &band(|d| {
+            (d.total_cap_b.p5, d.total_cap_b.p50, d.total_cap_b.p95)
+        })));
+    } else if let Some(run) = runs.first() {
+        let xs: Vec<f64> = run.iter().map(|s| s.year as f64).collect();
+        let point = |v: f64| (v, v, v);
+        let series = |f: &dyn Fn(&YearStats) -> f64| -> Vec<(f64, f64, f64)> {
+            run.iter().map(|s| point(f(s))).collect()
+        };
+        html.push_str(&line_chart("Combined ratio (%)", &xs, &series(&|s| s.combined_ratio(expense_ratio) * 100.0)));
+        html.push_str(&line_chart("Rate on line (%)", &xs, &series(&|s| s.rate_on_line() * 100.0)));
+        html.push_str(&line_chart("Total capital (B USD)", &xs, &series(&|s| s.total_capital as f64 / CENTS_PER_BUSD)));
+    }
+}
+
+fn pct(d: &analysis::DistStats) -> (f64, f64, f64) {
+    (d.p5 * 100.0, d.p50 * 100.0, d.p95 * 100.0)
+}
+
+/// SVG line chart of `(low, mid, high)` per x: the mid line, plus a shaded band when
+/// low ≠ high anywhere.
+fn line_chart(title: &str, xs: &[f64], ys: &[(f64, f64, f64)]) -> String {
+    const W: f64 = 760.0;
+    const H: f64 = 220.0;
+    const L: f64 = 56.0; // left margin for axis labels
+    const T: f64 = 24.0;
+    const B: f64 = 24.0;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{W}\" height=\"{H}\" role=\"img\">\
+         <text x=\"{L}\" y=\"16\" font-size=\"13\" font-weight=\"bold\">{}</text>",
+        escape(title)
+    );
+    if xs.is_empty() {
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+    let (x0, x1) = (xs[0], xs[xs.len() - 1].max(xs[0] + 1.0));
+    let lo = ys.iter().map(|y| y.0).fold(f64::INFINITY, f64::min);
+    let hi = ys.iter().map(|y| y.2).fold(f64::NEG_INFINITY, f64::max);
+    let (y0, y1) = if hi > lo { (lo, hi) } else { (lo - 1.0, lo + 1.0) };
+    let px = |x: f64| L + (x - x0) / (x1 - x0) * (W - L - 8.0);
+    let py = |y: f64| T + (1.0 - (y - y0) / (y1 - y0)) * (H - T - B);
+
+    let _ = write!(
+        svg,
+        "<line x1=\"{L}\" y1=\"{T}\" x2=\"{L}\" y2=\"{yb}\" stroke=\"#999\"/>\
+         <line x1=\"{L}\" y1=\"{yb}\" x2=\"{xr}\" y2=\"{yb}\" stroke=\"#999\"/>\
+         <text x=\"{lx}\" y=\"{ty}\" font-size=\"11\" text-anchor=\"end\">{y1:.1}</text>\
+         <text x=\"{lx}\" y=\"{yb}\" font-size=\"11\" text-anchor=\"end\">{y0:.1}</text>\
+         <text x=\"{L}\" y=\"{H}\" font-size=\"11\">{x0}</text>\
+         <text x=\"{xr}\" y=\"{H}\" font-size=\"11\" text-anchor=\"end\">{xl}</text>",
+        yb = H - B,
+        xr = W - 8.0,
+        lx = L - 4.0,
+        ty = T + 4.0,
+        xl = xs[xs.len() - 1],
+    );
+    if ys.iter().any(|y| y.0 != y.2) {
+        let upper = xs.iter().zip(ys).map(|(&x, y)| format!("{:.1},{:.1}", px(x), py(y.2)));
+        let lower = xs.iter().zip(ys).rev().map(|(&x, y)| format!("{:.1},{:.1}", px(x), py(y.0)));
+        let points: Vec<String> = upper.chain(lower).collect();
+        let _ = write!(svg, "<polygon points=\"{}\" fill=\"#4a7ab8\" fill-opacity=\"0.2\"/>", points.join(" "));
+    }
+    let mid: Vec<String> = xs.iter().zip(ys).map(|(&x, y)| format!("{:.1},{:.1}", px(x), py(y.1))).collect();
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#1f4e8c\" stroke-width=\"1.5\"/></svg>",
+        mid.join(" ")
+    );
+    svg
+}
+
+fn render_year_table(html: &mut String, stats: &[YearStats], expense_ratio: f64) {
+    html.push_str(
+        "<h2>Year character table</h2>\n<table>\n<tr><th>Year</th><th>Premium(B)</th><th>Claims(B)</th>\
+         <th>LossR%</th><th>CombR%</th><th>Rate%</th><th>Cats#</th><th>TotalCap(B)</th>\
+         <th>Insolvent#</th><th>Entrants#</th><th>Dropped#</th><th>Reject#</th><th>InForce</th></tr>\n",
+    );
+    for s in stats {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{:.3}</td><td>{:.3}</td><td>{:.1}</td><td>{:.1}</td><td>{:.2}</td>\
+             <td>{}</td><td>{:.2}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            s.year,
+            s.bound_premium as f64 / CENTS_PER_BUSD,
+            s.claims as f64 / CENTS_PER_BUSD,
+            s.loss_ratio() * 100.0,
+            s.combined_ratio(expense_ratio) * 100.0,
+            s.rate_on_line() * 100.0,
+            s.cat_event_count,
+            s.total_capital as f64 / CENTS_PER_BUSD,
+            s.insolvent_count,
+            s.entrant_count,
+            s.dropped_count,
+            s.rejected_count,
+            s.policies_in_force,
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn render_percentiles(html: &mut String, dists: &[YearDist]) {
+    html.push_str(
+        "<h2>Distribution percentiles (p5 / p50 / p95)</h2>\n<table>\n<tr><th>Year</th>\
+         <th>CombR%</th><th>Rate%</th><th>TotalCap(B)</th><th>Insolvent# p50 / max</th></tr>\n",
+    );
+    for d in dists {
+        let band = |(a, b, c): (f64, f64, f64)| format!("{a:.1} / {b:.1} / {c:.1}");
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{} / {}</td></tr>",
+            d.year,
+            band(pct(&d.combined_ratio)),
+            band(pct(&d.rate_on_line)),
+            band((d.total_cap_b.p5, d.total_cap_b.p50, d.total_cap_b.p95)),
+            d.insolvents.p50,
+            d.insolvents.max,
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SimulationConfig;
+    use crate::simulation::Simulation;
+    use crate::types::InsurerId;
+    use std::collections::HashMap;
+
+    fn run(seed: u64) -> (Vec<YearStats>, Vec<InvariantCheck>) {
+        let config = SimulationConfig { seed, years: 3, warmup_years: 0, ..SimulationConfig::canonical() };
+        let capitals: HashMap<InsurerId, u64> =
+            config.insurers.iter().map(|ic| (ic.id, ic.initial_capital as u64)).collect();
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        (analysis::analyse(&sim.log, &capitals, 0.344).1, invariant_checks(&sim.log))
+    }
+
+    #[test]
+    fn single_run_report_has_tables_and_charts_but_no_percentiles() {
+        let (stats, checks) = run(1);
+        assert!(checks.iter().all(|c| c.violations == 0), "canonical run passes every invariant");
+        let html = render("Seed <1>", &[stats], &checks, 0.344);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Seed &lt;1&gt;</title>"));
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert_eq!(html.matches("class=\"pass\"").count(), checks.len());
+        assert!(html.contains("Year character table"));
+        assert!(!html.contains("Distribution percentiles"));
+        assert!(!html.contains("<script"), "report must be self-contained");
+    }
+
+    #[test]
+    fn verify_reports_each_violation_of_a_tampered_log() {
+        let config = SimulationConfig { seed: 1, years: 2, warmup_years: 0, ..SimulationConfig::canonical() };
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        let mut log: Vec<SimEvent> = sim.log.iter().cloned().collect();
+        let clean = verify(&log);
+        assert!(clean.passed && clean.violations.is_empty());
+        assert!(clean.ensure_passed().is_ok());
+        assert_eq!(clean.events, log.len());
+        assert_eq!(clean.checks, invariant_checks(&log));
+
+        let claim = log
+            .iter_mut()
+            .find_map(|e| match &mut e.event {
+                crate::events::Event::ClaimSettled { amount, .. } => Some(amount),
+                _ => None,
+            })
+            .expect("canonical run settles claims");
+        *claim = 0;
+        let tampered = verify(&log);
+        assert!(!tampered.passed);
+        assert!(tampered.violations.iter().any(|v| v.starts_with("ClaimAmountZero")));
+        assert!(matches!(tampered.ensure_passed(), Err(crate::Error::Invariant(v)) if v == tampered.violations));
+        let zero = tampered.checks.iter().find(|c| c.name == "Claim amount > 0").unwrap();
+        assert_eq!(zero.violations, 1);
+        let json = serde_json::to_value(&tampered).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["checks"].as_array().unwrap().len(), tampered.checks.len());
+    }
+
+    #[test]
+    fn multi_run_report_adds_bands_and_percentiles() {
+        let (a, ca) = run(1);
+        let (b, cb) = run(2);
+        let html = render("two", &[a, b], &merge_checks(&[ca, cb]), 0.344);
+        assert!(html.contains("Distribution percentiles"));
+        assert!(html.contains("<polygon"), "cross-run band is shaded");
+    }
+
+    #[test]
+    fn failing_check_is_flagged_and_merged_across_runs() {
+        let fail = vec![InvariantCheck { name: "X", violations: 2 }];
+        let merged = merge_checks(&[fail.clone(), fail]);
+        assert_eq!(merged[0].violations, 4);
+        let html = render("r", &[], &merged, 0.3);
+        assert!(html.contains("<td class=\"fail\">FAIL</td><td>4</td>"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Random-number backends and named substreams.
+//!
+//! Every stochastic component draws through `rand::RngCore`, so the generator is chosen once
+//! at construction: [`SimRng`] dispatches to ChaCha20 (the default, and the only backend the
+//! canonical output is defined against) or an in-tree PCG64 that is several times faster.
+//!
+//! [`Streams`] hands out one generator per [`Stream`]. By default every stream is the same
+//! shared generator, which preserves the canonical draw order. With `split_streams` each
+//! component gets its own independently seeded substream, so configuring or consuming one
+//! component no longer shifts the draws of the others; a frozen stream is additionally seeded
+//! independently of the run seed, so it replays the same draws in every run.
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::RngConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RngBackend {
+    #[default]
+    ChaCha20,
+    Pcg64,
+}
+
+/// A stochastic component with its own substream when streams are split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stream {
+    /// Annual cat occurrence and severity.
+    Cats,
+    /// Per-insured attritional losses.
+    Attritional,
+    /// Placement: quote turnaround, elastic-demand acceptance, premium default.
+    Broker,
+    /// Pricing noise: sensitivities drawn for new entrants.
+    Pricing,
+    /// Market-wide draws: investment returns, interest rates, run-off re-entry.
+    Market,
+}
+
+impl Stream {
+    pub const ALL: [Stream; 5] =
+        [Stream::Cats, Stream::Attritional, Stream::Broker, Stream::Pricing, Stream::Market];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Run seed substituted for frozen streams.
+const FROZEN_SEED: u64 = 0;
+
+/// Backend-agnostic simulation generator.
+#[derive(Clone, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)] // a handful live per simulation; boxing would slow the default path
+pub enum SimRng {
+    ChaCha20(ChaCha20Rng),
+    Pcg64(Pcg64),
+}
+
+impl SimRng {
+    pub fn new(backend: RngBackend, seed: u64) -> Self {
+        match backend {
+            RngBackend::ChaCha20 => SimRng::ChaCha20(ChaCha20Rng::seed_from_u64(seed)),
+            RngBackend::Pcg64 => SimRng::Pcg64(Pcg64::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            SimRng::ChaCha20(r) => r.next_u32(),
+            SimRng::Pcg64(r) => r.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            SimRng::ChaCha20(r) => r.next_u64(),
+            SimRng::Pcg64(r) => r.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            SimRng::ChaCha20(r) => r.fill_bytes(dest),
+            SimRng::Pcg64(r) => r.fill_bytes(dest),
+        }
+    }
+}
+
+/// PCG64 in its 128-bit multiplicative-congruential form with XSL-RR output
+/// (O'Neill 2014; `Mcg128Xsl64` in `rand_pcg`).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Pcg64 {
+    state: u128,
+}
+
+impl Pcg64 {
+    const MULTIPLIER: u128 = 0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645;
+}
+
+impl RngCore for Pcg64 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul(Self::MULTIPLIER);
+        let rot = (self.state >> 122) as u32;
+        let xsl = ((self.state >> 64) as u64) ^ (self.state as u64);
+        xsl.rotate_right(rot)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+impl SeedableRng for Pcg64 {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        // An MCG state must be odd.
+        Pcg64 { state: u128::from_le_bytes(seed) | 1 }
+    }
+}
+
+/// Named generators for the simulation's stochastic components.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Streams {
+    shared: SimRng,
+    /// One generator per `Stream::ALL` entry when split; None = all streams share `shared`.
+    split: Option<Vec<SimRng>>,
+}
+
+impl Streams {
+    pub fn new(seed: u64, config: Option<&RngConfig>) -> Self {
+        let backend = config.map(|c| c.backend).unwrap_or_default();
+        let split = config.filter(|c| c.split_streams).map(|c| {
+            Stream::ALL
+                .iter()
+                .map(|&s| {
+                    let base = if c.frozen.contains(&s) { FROZEN_SEED } else { seed };
+                    SimRng::new(backend, substream_seed(base, s))
+                })
+                .collect()
+        });
+        Streams { shared: SimRng::new(backend, seed), split }
+    }
+
+    /// The generator for `stream`.
+    pub fn get(&mut self, stream: Stream) -> &mut SimRng {
+        match self.split.as_mut() {
+            Some(split) => &mut split[stream.index()],
+            None => &mut self.shared,
+        }
+    }
+}
+
+/// SplitMix64 finaliser over the run seed and stream index, so substream seeds are
+/// well separated even for adjacent run seeds.
+fn substream_seed(seed: u64, stream: Stream) -> u64 {
+    let mut z = seed ^ (stream.index() as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng as _;
+
+    fn split_config(frozen: Vec<Stream>) -> RngConfig {
+        RngConfig { backend: RngBackend::ChaCha20, split_streams: true, frozen }
+    }
+
+    #[test]
+    fn default_streams_share_the_canonical_chacha_generator() {
+        let mut streams = Streams::new(42, None);
+        let mut reference = ChaCha20Rng::seed_from_u64(42);
+        assert_eq!(streams.get(Stream::Cats).next_u64(), reference.next_u64());
+        assert_eq!(streams.get(Stream::Broker).next_u64(), reference.next_u64());
+    }
+
+    #[test]
+    fn split_streams_are_isolated_from_each_other() {
+        let config = split_config(vec![]);
+        let mut a = Streams::new(7, Some(&config));
+        let mut b = Streams::new(7, Some(&config));
+        for _ in 0..100 {
+            a.get(Stream::Broker).next_u64();
+        }
+        assert_eq!(a.get(Stream::Cats).next_u64(), b.get(Stream::Cats).next_u64());
+        assert_ne!(b.get(Stream::Cats).next_u64(), b.get(Stream::Attritional).next_u64());
+    }
+
+    #[test]
+    fn frozen_stream_ignores_the_run_seed() {
+        let config = split_config(vec![Stream::Cats]);
+        let mut a = Streams::new(1, Some(&config));
+        let mut b = Streams::new(2, Some(&config));
+        assert_eq!(a.get(Stream::Cats).next_u64(), b.get(Stream::Cats).next_u64());
+        assert_ne!(a.get(Stream::Attritional).next_u64(), b.get(Stream::Attritional).next_u64());
+    }
+
+    #[test]
+    fn pcg_backend_is_deterministic_and_uniform() {
+        let mut a = SimRng::new(RngBackend::Pcg64, 42);
+        let mut b = SimRng::new(RngBackend::Pcg64, 42);
+        let xs: Vec<f64> = (0..10_000).map(|_| a.random::<f64>()).collect();
+        assert!(xs.iter().all(|&x| x == b.random::<f64>()));
+        let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+        assert!((mean - 0.5).abs() < 0.01, "mean {mean}");
+        let mut c = SimRng::new(RngBackend::ChaCha20, 42);
+        assert_ne!(xs[0], c.random::<f64>());
+    }
+}
@@ -0,0 +1,511 @@
+//! `rins serve`: the simulation as a long-running HTTP service, so a dashboard can launch runs
+//! and follow them without sharing a file system with the simulator.
+//!
+//! | Method   | Path                 | Response |
+//! |----------|----------------------|----------|
+//! | `POST`   | `/runs`              | Start a run. The body is a partial config merged over the canonical one (`SimulationConfig::canonical_with`); 202 `{"run_id": n}`, 400 with the parse or validation errors, or 503 while `max_running` runs are in progress. |
+//! | `GET`    | `/runs`              | The status of every run. |
+//! | `GET`    | `/runs/{id}`         | Its status: seed, events logged, last year completed, finished, error. |
+//! | `GET`    | `/runs/{id}/events`  | The event log, streamed as the run progresses: chunked NDJSON, or server-sent events with `Accept: text/event-stream`. `?type=YearEnd,LossEvent` keeps only those kinds (`filter::EventFilter`). |
+//! | `GET`    | `/runs/{id}/years`   | `analysis::analyse` of the events so far. |
+//! | `GET`    | `/runs/{id}/funnel`  | `analysis::submission_funnel` of the events so far. |
+//! | `DELETE` | `/runs/{id}`         | Forget a finished run. |
+//!
+//! Each run gets a thread and logs a year at a time, so streams and analyses advance by whole
+//! years. The log starts with the `RunMetadata` header, as `events.ndjson` does. Finished runs
+//! stay in memory until deleted or, once more than `keep_finished` of them have piled up, until
+//! a new run evicts the oldest. The HTTP is a std-only HTTP/1.1 subset: one request per
+//! connection, no TLS or authentication, so bind it to a local address. A client that stalls
+//! while sending its request is dropped after `READ_TIMEOUT`.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::analysis;
+use crate::config::SimulationConfig;
+use crate::events::{Event, RunMetadata, SimEvent};
+use crate::filter::EventFilter;
+use crate::simulation::Simulation;
+use crate::types::{Day, Year};
+
+/// Largest request body accepted (a config with an inline cat catalogue fits easily).
+const MAX_BODY: usize = 16 << 20;
+
+/// How long a connection may take to deliver each read of its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs simulated at once unless `Server::with_max_running` says otherwise.
+pub const DEFAULT_MAX_RUNNING: usize = 4;
+
+/// Finished runs kept unless `Server::with_keep_finished` says otherwise.
+pub const DEFAULT_KEEP_FINISHED: usize = 64;
+
+/// A bound listener; `run` serves it.
+pub struct Server {
+    listener: TcpListener,
+    runs: Runs,
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let runs = Runs {
+            next_id: AtomicU64::new(0),
+            runs: Mutex::default(),
+            max_running: DEFAULT_MAX_RUNNING,
+            keep_finished: DEFAULT_KEEP_FINISHED,
+        };
+        Ok(Server { listener: TcpListener::bind(addr)?, runs })
+    }
+
+    /// Refuse new runs with 503 while `n` are in progress.
+    pub fn with_max_running(mut self, n: usize) -> Self {
+        self.runs.max_running = n;
+        self
+    }
+
+    /// Evict the oldest finished runs beyond `n` when a new run starts.
+    pub fn with_keep_finished(mut self, n: usize) -> Self {
+        self.runs.keep_finished = n;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections until the process exits, each on its own thread.
+    pub fn run(self) {
+        let runs = Arc::new(self.runs);
+        for stream in self.listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+                continue;
+            }
+            let runs = Arc::clone(&runs);
+            // A client hanging up mid-response is its business, not the server's.
+            thread::spawn(move || {
+                let _ = handle(stream, &runs);
+            });
+        }
+    }
+}
+
+struct Runs {
+    next_id: AtomicU64,
+    runs: Mutex<BTreeMap<u64, Arc<Run>>>,
+    max_running: usize,
+    keep_finished: usize,
+}
+
+impl Runs {
+    fn get(&self, id: u64) -> Option<Arc<Run>> {
+        self.runs.lock().expect("runs poisoned").get(&id).cloned()
+    }
+}
+
+/// One run's log so far; `progress` is notified after each year and when the run ends.
+struct Run {
+    state: Mutex<RunState>,
+    progress: Condvar,
+}
+
+struct RunState {
+    seed: u64,
+    log: Vec<SimEvent>,
+    /// Last year whose `YearEnd` has been logged; 0 before the first.
+    year: u32,
+    finished: bool,
+    /// The panic message if the run died.
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct RunStatus {
+    run_id: u64,
+    seed: u64,
+    events: usize,
+    year: u32,
+    finished: bool,
+    error: Option<String>,
+}
+
+impl Run {
+    fn state(&self) -> MutexGuard<'_, RunState> {
+        self.state.lock().expect("run poisoned")
+    }
+
+    fn status(&self, run_id: u64) -> RunStatus {
+        let s = self.state();
+        RunStatus { run_id, seed: s.seed, events: s.log.len(), year: s.year, finished: s.finished, error: s.error.clone() }
+    }
+}
+
+/// Why a run was not started.
+#[derive(Debug)]
+enum Refusal {
+    /// The body does not parse or validate: the client's mistake.
+    Invalid(String),
+    /// `max_running` runs are already in progress.
+    Busy,
+}
+
+/// Build the run from the request body and start it, evicting the oldest finished runs beyond
+/// `keep_finished`.
+fn start_run(runs: &Runs, body: &[u8]) -> Result<u64, Refusal> {
+    let overrides = if body.iter().all(u8::is_ascii_whitespace) {
+        Value::Object(Default::default())
+    } else {
+        serde_json::from_slice(body).map_err(|e| Refusal::Invalid(format!("body is not JSON: {e}")))?
+    };
+    let config = SimulationConfig::canonical_with(overrides).map_err(|e| Refusal::Invalid(format!("invalid config: {e}")))?;
+    let header = SimEvent { day: Day(0), seq: None, event: Event::RunMetadata(Box::new(RunMetadata::capture(&config))) };
+    let seed = config.seed;
+    let sim = Simulation::from_config(config).map_err(|e| Refusal::Invalid(e.to_string()))?;
+    let run = Arc::new(Run {
+        state: Mutex::new(RunState { seed, log: vec![header], year: 0, finished: false, error: None }),
+        progress: Condvar::new(),
+    });
+    let id = {
+        let mut all = runs.runs.lock().expect("runs poisoned");
+        let finished: Vec<u64> = all.iter().filter(|(_, run)| run.state().finished).map(|(&id, _)| id).collect();
+        if all.len() - finished.len() >= runs.max_running {
+            return Err(Refusal::Busy);
+        }
+        for id in &finished[..finished.len().saturating_sub(runs.keep_finished)] {
+            all.remove(id);
+        }
+        let id = runs.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        all.insert(id, Arc::clone(&run));
+        id
+    };
+    thread::spawn(move || {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| simulate(sim, &run)));
+        let mut state = run.state();
+        if let Err(cause) = outcome {
+            let message = cause.downcast_ref::<&str>().map(|s| s.to_string()).or_else(|| cause.downcast_ref::<String>().cloned());
+            state.error = Some(message.unwrap_or_else(|| "the run panicked".into()));
+        }
+        state.finished = true;
+        run.progress.notify_all();
+    });
+    Ok(id)
+}
+
+/// Run a year at a time, moving each year's events into the shared log.
+fn simulate(mut sim: Simulation, run: &Run) {
+    sim.start();
+    let mut year = 1;
+    while !sim.is_finished() {
+        sim.run_until(Day::year_end(Year(year)));
+        let mut state = run.state();
+        state.log.extend(sim.log.drain());
+        state.year = year;
+        run.progress.notify_all();
+        year += 1;
+    }
+    if let Some(e) = sim.schedule_error() {
+        run.state().error = Some(e.to_string());
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    /// `Accept: text/event-stream`.
+    sse: bool,
+    body: Vec<u8>,
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let bad = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else { return Err(bad("malformed request line")) };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request { method: method.into(), path: path.into(), query: query.into(), sse: false, body: vec![] };
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.parse().map_err(|_| bad("bad Content-Length"))?,
+            "accept" => request.sse = value.contains("text/event-stream"),
+            _ => {}
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(bad("request body too large"));
+    }
+    request.body.resize(content_length, 0);
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+fn handle(mut stream: TcpStream, runs: &Runs) -> io::Result<()> {
+    let request = match read_request(&stream) {
+        Ok(request) => request,
+        Err(e) => return respond_json(&mut stream, "400 Bad Request", &json!({ "error": e.to_string() })),
+    };
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let run = |id: &str| id.parse().ok().and_then(|id| runs.get(id).map(|run| (id, run)));
+    let not_found = json!({ "error": format!("no such resource: {}", request.path) });
+    match (request.method.as_str(), segments.as_slice()) {
+        ("OPTIONS", _) => respond(&mut stream, "204 No Content", "text/plain", b""),
+        ("POST", ["runs"]) => match start_run(runs, &request.body) {
+            Ok(run_id) => respond_json(&mut stream, "202 Accepted", &json!({ "run_id": run_id })),
+            Err(Refusal::Invalid(error)) => respond_json(&mut stream, "400 Bad Request", &json!({ "error": error })),
+            Err(Refusal::Busy) => {
+                let error = format!("{} runs already in progress; retry when one finishes", runs.max_running);
+                respond_json(&mut stream, "503 Service Unavailable", &json!({ "error": error }))
+            }
+        },
+        ("GET", ["runs"]) => {
+            let all: Vec<(u64, Arc<Run>)> = runs.runs.lock().expect("runs poisoned").iter().map(|(&id, run)| (id, Arc::clone(run))).collect();
+            let statuses: Vec<RunStatus> = all.iter().map(|(id, run)| run.status(*id)).collect();
+            respond_json(&mut stream, "200 OK", &statuses)
+        }
+        (method, ["runs", id, rest @ ..]) => {
+            let Some((id, run)) = run(id) else { return respond_json(&mut stream, "404 Not Found", &not_found) };
+            match (method, rest) {
+                ("GET", []) => respond_json(&mut stream, "200 OK", &run.status(id)),
+                ("GET", ["events"]) => stream_events(&mut stream, &run, &request),
+                ("GET", ["years"]) => {
+                    let state = run.state();
+                    let expense_ratio = analysis::expense_ratio(&state.log)
+                        .unwrap_or_else(|| crate::batch::expense_ratio(&SimulationConfig::canonical()));
+                    let (_, years) = analysis::analyse(&state.log, &analysis::initial_capitals(&state.log), expense_ratio);
+                    drop(state);
+                    respond_json(&mut stream, "200 OK", &years)
+                }
+                ("GET", ["funnel"]) => {
+                    let funnel = analysis::submission_funnel(&run.state().log);
+                    respond_json(&mut stream, "200 OK", &funnel)
+                }
+                ("DELETE", []) if !run.state().finished => {
+                    respond_json(&mut stream, "409 Conflict", &json!({ "error": "the run is still going" }))
+                }
+                ("DELETE", []) => {
+                    runs.runs.lock().expect("runs poisoned").remove(&id);
+                    respond(&mut stream, "204 No Content", "text/plain", b"")
+                }
+                _ => respond_json(&mut stream, "404 Not Found", &not_found),
+            }
+        }
+        _ => respond_json(&mut stream, "404 Not Found", &not_found),
+    }
+}
+
+/// Write the log as it grows, waiting on the run between years, until the run finishes.
+fn stream_events(stream: &mut TcpStream, run: &Run, request: &Request) -> io::Result<()> {
+    let kinds = request.query.split('&').find_map(|p| p.strip_prefix("type=")).map(percent_decode).unwrap_or_default();
+    let filter = EventFilter { kinds: kinds.split(',').filter(|k| !k.is_empty()).map(String::from).collect(), ..Default::default() };
+    let content_type = if request.sse { "text/event-stream" } else { "application/x-ndjson" };
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nCache-Control: no-cache\r\nTransfer-Encoding: chunked\r\n{}\r\n",
+        common_headers()
+    )?;
+    let mut sent = 0;
+    loop {
+        let (batch, finished, error) = {
+            let mut state = run.state();
+            while state.log.len() == sent && !state.finished {
+                state = run.progress.wait(state).expect("run poisoned");
+            }
+            (state.log[sent..].to_vec(), state.finished, state.error.clone())
+        };
+        sent += batch.len();
+        let mut chunk = String::new();
+        for ev in &batch {
+            let value = serde_json::to_value(ev).map_err(io::Error::other)?;
+            if !filter.matches_value(&value) {
+                continue;
+            }
+            if request.sse {
+                let kind = match &value["event"] {
+                    Value::Object(m) => m.keys().next().cloned().unwrap_or_default(),
+                    Value::String(k) => k.clone(),
+                    _ => String::new(),
+                };
+                chunk.push_str(&format!("event: {kind}\ndata: {value}\n\n"));
+            } else {
+                chunk.push_str(&value.to_string());
+                chunk.push('\n');
+            }
+        }
+        if finished && request.sse {
+            chunk.push_str(&format!("event: end\ndata: {}\n\n", json!({ "events": sent, "error": error })));
+        }
+        if !chunk.is_empty() {
+            write!(stream, "{:x}\r\n{chunk}\r\n", chunk.len())?;
+            stream.flush()?;
+        }
+        if finished {
+            break;
+        }
+    }
+    write!(stream, "0\r\n\r\n")?;
+    stream.flush()
+}
+
+/// Headers on every response: the dashboard is served from another origin.
+fn common_headers() -> &'static str {
+    "Access-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, DELETE, OPTIONS\r\n\
+     Access-Control-Allow-Headers: Content-Type, Accept\r\nConnection: close\r\n"
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &[u8]) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n{}\r\n",
+        body.len(),
+        common_headers()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+fn respond_json(stream: &mut TcpStream, status: &str, value: &impl Serialize) -> io::Result<()> {
+    let body = serde_json::to_vec(value).map_err(io::Error::other)?;
+    respond(stream, status, "application/json", &body)
+}
+
+/// `%XX` escapes decoded; anything malformed is kept as written.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serve() -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    /// Send one request and return the status code and the body, de-chunked.
+    fn request(addr: SocketAddr, method: &str, path: &str, accept: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "{method} {path} HTTP/1.1\r\nHost: test\r\nAccept: {accept}\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, mut rest) = response.split_once("\r\n\r\n").unwrap();
+        let status = head[9..12].parse().unwrap();
+        if !head.contains("Transfer-Encoding: chunked") {
+            return (status, rest.to_string());
+        }
+        let mut body = String::new();
+        loop {
+            let (size, after) = rest.split_once("\r\n").unwrap();
+            let size = usize::from_str_radix(size, 16).unwrap();
+            if size == 0 {
+                return (status, body);
+            }
+            body.push_str(&after[..size]);
+            rest = &after[size + 2..];
+        }
+    }
+
+    const SMALL: &str = r#"{"years": 2, "warmup_years": 1, "n_insureds": 20, "seed": 3}"#;
+
+    #[test]
+    fn a_posted_run_streams_its_log_and_answers_analysis_queries() {
+        let addr = serve();
+        let (status, body) = request(addr, "POST", "/runs", "*/*", SMALL);
+        assert_eq!(status, 202);
+        let run_id = serde_json::from_str::<Value>(&body).unwrap()["run_id"].as_u64().unwrap();
+
+        // Blocks until the run finishes: the stream ends with the log.
+        let (status, ndjson) = request(addr, "GET", &format!("/runs/{run_id}/events"), "*/*", "");
+        assert_eq!(status, 200);
+        let events: Vec<SimEvent> = ndjson.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert!(matches!(events[0].event, Event::RunMetadata(ref m) if m.seed == 3));
+        assert_eq!(events.iter().filter(|e| matches!(e.event, Event::YearEnd { .. })).count(), 3);
+
+        let status: Value = serde_json::from_str(&request(addr, "GET", &format!("/runs/{run_id}"), "*/*", "").1).unwrap();
+        assert_eq!(status["finished"], true);
+        assert_eq!(status["events"], events.len());
+        assert_eq!(status["year"], 3);
+
+        let (_, years) = request(addr, "GET", &format!("/runs/{run_id}/years"), "*/*", "");
+        let years: Vec<Value> = serde_json::from_str(&years).unwrap();
+        assert_eq!(years.iter().map(|y| y["year"].as_u64().unwrap()).collect::<Vec<_>>(), vec![2, 3]);
+        let (status, funnel) = request(addr, "GET", &format!("/runs/{run_id}/funnel"), "*/*", "");
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Vec<Value>>(&funnel).unwrap().len(), 2);
+
+        // Server-sent events, filtered by kind; the header always passes.
+        let (_, sse) = request(addr, "GET", &format!("/runs/{run_id}/events?type=YearEnd%2CYearStart"), "text/event-stream", "");
+        let names: Vec<&str> = sse.lines().filter_map(|l| l.strip_prefix("event: ")).collect();
+        assert_eq!(names.iter().filter(|&&n| n == "YearEnd").count(), 3);
+        assert_eq!(names.iter().filter(|&&n| n == "YearStart").count(), 3);
+        assert_eq!((names[0], names[names.len() - 1]), ("RunMetadata", "end"));
+        assert_eq!(names.len(), 8);
+
+        assert_eq!(request(addr, "DELETE", &format!("/runs/{run_id}"), "*/*", "").0, 204);
+        assert_eq!(request(addr, "GET", &format!("/runs/{run_id}"), "*/*", "").0, 404);
+    }
+
+    #[test]
+    fn bad_configs_and_unknown_runs_are_client_errors() {
+        let addr = serve();
+        let (status, body) = request(addr, "POST", "/runs", "*/*", r#"{"years": "ten"}"#);
+        assert_eq!(status, 400);
+        assert!(body.contains("invalid config"), "{body}");
+        let (status, body) = request(addr, "POST", "/runs", "*/*", r#"{"years": 0}"#);
+        assert_eq!(status, 400, "fails validation: {body}");
+        assert_eq!(request(addr, "GET", "/runs/99/events", "*/*", "").0, 404);
+        assert_eq!(request(addr, "GET", "/nowhere", "*/*", "").0, 404);
+        assert_eq!(request(addr, "GET", "/runs", "*/*", "").1, "[]");
+    }
+
+    #[test]
+    fn new_runs_are_refused_while_the_cap_is_in_use_and_evict_old_finished_runs() {
+        let server = Server::bind("127.0.0.1:0").unwrap().with_max_running(1).with_keep_finished(0);
+        let stalled = Arc::new(Run {
+            state: Mutex::new(RunState { seed: 0, log: vec![], year: 0, finished: false, error: None }),
+            progress: Condvar::new(),
+        });
+        server.runs.runs.lock().unwrap().insert(0, Arc::clone(&stalled));
+        assert!(matches!(start_run(&server.runs, SMALL.as_bytes()), Err(Refusal::Busy)));
+
+        stalled.state().finished = true;
+        let run_id = start_run(&server.runs, SMALL.as_bytes()).unwrap();
+        assert_eq!(server.runs.runs.lock().unwrap().keys().copied().collect::<Vec<_>>(), vec![run_id], "finished run evicted");
+    }
+}
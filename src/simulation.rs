@@ -1,8 +1,7 @@
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
 
-use rand::SeedableRng;
-use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
 
 /// Days from CoverageRequested to PolicyBound (the quoting chain length).
 const QUOTING_CHAIN_DAYS: u64 = 3;
@@ -27,38 +26,93 @@ fn pml_damage_fraction_compound(
         .fold(0.0_f64, f64::max)
 }
 
+/// One insurer's model error on the cat ELF under `risk_views`: LogNormal(−σ²/2, σ), mean 1.
+/// Exactly 1.0, with no draw, when risk views are off or σ = 0.
+fn draw_risk_view(risk_views: Option<&crate::config::RiskViewConfig>, rng: &mut SimRng) -> f64 {
+    use rand_distr::{Distribution as _, LogNormal};
+
+    match risk_views.map(|v| v.cat_elf_sigma).filter(|&s| s > 0.0).map(|s| LogNormal::new(-s * s / 2.0, s)) {
+        Some(Ok(dist)) => dist.sample(rng),
+        _ => 1.0,
+    }
+}
+
+use crate::analysis::{Accumulator, YearStats};
 use crate::broker::Broker;
-use crate::config::{SimulationConfig, ASSET_VALUE};
-use crate::events::{Event, EventLog, Peril, Risk, SimEvent};
+use crate::config::{CasualtyConfig, PricingMode, RationingPriority, ScenarioSeverity, SimulationConfig, SoftMarketExitRanking, ASSET_VALUE};
+use crate::events::{
+    CancellationBasis, CancellationReason, CapitalFlowReason, DeclineReason, Event, EventLog, Line, Peril, PortfolioTransfer, PriorityClass, QueuedEvent,
+    Risk, SCHEMA_VERSION, SimEvent,
+};
+use crate::ils::{self, CatBondBook};
+use crate::reinsurance::ReinsuranceMarket;
 use crate::insured::Insured;
+use crate::live_check::{LiveCheckMode, LiveChecker, LiveViolation};
 use crate::insurer::Insurer;
 use crate::market::Market;
+use crate::observer::{MarketView, Observer, Observers};
 use crate::perils;
-use crate::types::{Day, InsuredId, InsurerId, Year};
+use crate::regulator::Regulator;
+use crate::rng::{SimRng, Stream, Streams};
+use crate::transfer::{self, TransferLine};
+use crate::types::{BrokerId, Day, InsuredId, InsurerId, PolicyId, SubmissionId, Territory, TerritoryTable, Year};
 
+/// Bumped whenever `SimulationState` changes shape.
+pub const CHECKPOINT_VERSION: u32 = 43;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Simulation {
-    queue: BinaryHeap<Reverse<SimEvent>>,
+    /// Pending events, ordered by `(day, priority class, seq)` — see `events::PriorityClass`.
+    queue: BinaryHeap<Reverse<QueuedEvent>>,
+    /// Scheduling sequence number for the next queued event (FIFO within a priority class).
+    next_seq: u64,
     /// Completed events in dispatch order. `log[i]` has implicit sequence number `i`.
     /// See `docs/event-sourcing.md §5` for the incremental-replay pattern.
     pub log: EventLog,
-    rng: ChaCha20Rng,
+    /// Named generators; one shared stream unless `config.rng` splits them. Not
+    /// reconstructible from the log — replay from the same seed and `RngConfig` instead.
+    streams: Streams,
     max_day: Option<Day>,
     max_events: Option<usize>,
     pub insurers: Vec<Insurer>,
-    pub broker: Broker,
+    /// Distribution channel: each broker places its own segment of insureds.
+    pub brokers: Vec<Broker>,
+    /// Index into `brokers` of the broker that owns each insured.
+    insured_broker: HashMap<InsuredId, usize>,
     pub market: Market,
+    /// Outstanding cat bonds; empty without `config.ils`.
+    cat_bonds: CatBondBook,
+    /// In-force cat XoL treaties and the reinsurance price cycle; None without
+    /// `config.reinsurance`.
+    reinsurance: Option<ReinsuranceMarket>,
+    /// Quota rationing: the cat-exposed lead requests each insurer holds for its next
+    /// `LeadQuoteBatchClosed`; empty without `config.rationing`.
+    lead_batches: BTreeMap<InsurerId, Vec<(SubmissionId, InsuredId, Risk)>>,
+    /// Regulatory interventions in force; inert without `config.regulator`.
+    regulator: Regulator,
     next_event_id: u64,
     config: SimulationConfig,
-    /// (insured_id, year) pairs for which attritional losses have already been scheduled.
-    /// Prevents double-scheduling when the same insured gets multiple CoverageRequested
-    /// in one year (e.g. QuoteRejected retry or QuoteAccepted renewal).
-    attritional_scheduled: HashSet<(InsuredId, Year)>,
+    /// The run's territory names (`SimulationConfig::territory_table`), behind the
+    /// `Territory` ids its risks and events carry.
+    territories: TerritoryTable,
+    /// Day of each insured's most recently scheduled CoverageRequested — its attritional
+    /// exposure start for that year. Read by the YearStart attritional pass.
+    coverage_request_day: HashMap<InsuredId, Day>,
+    /// Latest year whose YearStart attritional pass has run. A CoverageRequested scheduled
+    /// into this year or earlier is exposed immediately instead.
+    attritional_year: Year,
     /// Gross premium written this year (PolicyBound.premium). Reset at YearStart.
     year_premium_written: u64,
     /// Claims settled this year (ClaimSettled.amount). Reset at YearStart.
     year_claims_settled: u64,
     /// Count of SubmissionDropped events this year. Reset at YearStart.
     year_dropped_count: u32,
+    /// Gross premium written this quarter (PolicyBound and PolicyAnniversary premium). Reset
+    /// at each QuarterEnd.
+    quarter_premium_written: u64,
+    /// Claims incurred this quarter (ClaimSettled amounts, ClaimReported ultimates). Reset at
+    /// each QuarterEnd.
+    quarter_claims_incurred: u64,
     /// EWMA of annual combined ratios (α = 1/3, equivalent to 5-year span).
     /// None until the first year of data is available.
     cr_ewma: Option<f64>,
@@ -72,30 +126,132 @@ pub struct Simulation {
     /// Computed at YearEnd from trailing combined ratios + capacity pressure.
     /// Mirrors the MS3 AvT (Actual vs Technical) signal.
     market_ap_tp_factor: f64,
+    /// First published basket rate on line, the rate index base (`rate_index`).
+    rate_index_base: Option<f64>,
+    /// Consecutive year-ends with AP/TP below the soft-market exit floor (`soft_market_exit`).
+    soft_market_years: u32,
+    /// Short rate published at the last YearStart; None without `config.interest_rates`.
+    interest_rate: Option<f64>,
+    /// Market-wide factor on the initial reserve of casualty claims reported this year, drawn
+    /// at YearStart (`casualty.reserve_sigma`); 1.0 without casualty.
+    reserving_factor: f64,
+    /// Events dispatched since the simulation started (reported in `SimHealth`).
+    events_dispatched: u64,
+    /// Day of the next `SimHealth` boundary; None without `config.health`.
+    next_health_day: Option<Day>,
+    /// Day of the next `ExposureSnapshot` boundary; None without `config.exposure_snapshots`.
+    next_exposure_day: Option<Day>,
     /// Sensitivity distribution snapshots per year-end: (cr_sens_mean, cr_sens_std,
     /// cap_sens_mean, cap_sens_std, mwf_mean) across active (non-insolvent) insurers.
     pub sensitivity_by_year: HashMap<u32, (f64, f64, f64, f64, f64)>,
+    /// Set by `aggregate_only`: every dispatched event is folded in here and dropped from
+    /// `log`. Not checkpointed.
+    #[serde(skip)]
+    accumulator: Option<Accumulator>,
+    /// Set by `check_live`: checks each event as it is logged. Not checkpointed.
+    #[serde(skip)]
+    live_check: Option<LiveChecker>,
+    /// Log entries already handed to `live_check`.
+    #[serde(skip)]
+    live_checked: usize,
+    /// Day and class of the event being dispatched, checked by `schedule`. None between events.
+    #[serde(skip)]
+    dispatching: Option<(Day, PriorityClass)>,
+    /// The day a `YearClose` event was last dispatched: from then until the day ends, only
+    /// capital and year-close events may be scheduled for it, whoever schedules them. Not
+    /// checkpointed; checkpoints fall between days.
+    #[serde(skip)]
+    year_closed: Option<Day>,
+    /// The first out-of-phase `schedule` made by a handler; the run halts on it.
+    #[serde(skip)]
+    schedule_error: Option<ScheduleError>,
+    /// Capital ledger: flows taken from the insurers during this dispatch, each with the
+    /// `queue_seq` it is logged with. Empty between events.
+    #[serde(skip)]
+    staged_flows: Vec<(InsurerId, CapitalFlowReason, i64, u64)>,
+    /// Set by `with_observer`: called after each dispatched event. Not checkpointed or cloned.
+    #[serde(skip)]
+    observers: Observers,
+}
+
+/// Serializable checkpoint of a running simulation: pending queue, RNG streams, agent
+/// states, counters and the log so far. `Simulation::restore` resumes where `snapshot`
+/// stopped.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimulationState {
+    pub version: u32,
+    /// Day of the last dispatched event (Day(0) before the first).
+    pub day: Day,
+    simulation: Simulation,
+}
+
+/// Why a checkpoint could not be restored.
+#[derive(Debug, PartialEq)]
+pub enum CheckpointError {
+    VersionMismatch { found: u32, expected: u32 },
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VersionMismatch { found, expected } => {
+                write!(f, "checkpoint version {found}, expected {expected}")
+            }
+        }
+    }
+}
+
+/// An event scheduled where `Simulation::schedule` cannot accept it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleError {
+    pub event: Box<Event>,
+    pub day: Day,
+    /// The day being dispatched.
+    pub now: Day,
+    /// The phase that day had reached: the dispatching handler's class, or `YearClose` once
+    /// the year has closed.
+    pub phase: PriorityClass,
+}
+
+impl std::fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { event, day, now, phase } = self;
+        if day < now {
+            write!(f, "{event:?} scheduled for {day:?}, before the current {now:?}")
+        } else {
+            write!(
+                f,
+                "{event:?} ({:?}) scheduled for {day:?} in the {phase:?} phase would run out of phase",
+                event.priority_class()
+            )
+        }
+    }
 }
 
 impl Simulation {
-    /// Construct from a canonical config.
-    pub fn from_config(config: SimulationConfig) -> Self {
+    /// Construct from a config, rejecting it first if `SimulationConfig::validate` fails.
+    pub fn from_config(config: SimulationConfig) -> crate::Result<Self> {
+        config.validate()?;
         let pml_200 = pml_damage_fraction_compound(&config.catastrophe.event_classes, 200.0);
         // Each cat event strikes one territory; max per-event portfolio impact = pml_200 ÷ n_territories.
         // Applying territory_factor to the pml denominator of the cat aggregate limit scales the limit
         // upward to correctly reflect that geographic diversification reduces peak portfolio loss.
         let n_territories = config.catastrophe.territories.len().max(1);
         let territory_factor = 1.0 / n_territories as f64;
+        let territories = config.territory_table();
+        let mut streams = Streams::new(config.seed, config.rng.as_ref());
         let insurers: Vec<Insurer> = config
             .insurers
             .iter()
             .map(|c| {
                 let pml = c.pml_damage_fraction_override.unwrap_or(pml_200) * territory_factor;
+                // Risk views draw on the Pricing stream in config order, before the run starts.
+                let cat_elf = c.cat_elf * draw_risk_view(config.risk_views.as_ref(), streams.get(Stream::Pricing));
                 Insurer::new(
                     c.id,
                     c.initial_capital,
                     c.attritional_elf,
-                    c.cat_elf,
+                    cat_elf,
                     c.target_loss_ratio,
                     c.ewma_credibility,
                     c.expense_ratio,
@@ -112,23 +268,43 @@ impl Simulation {
                     c.distribution_floor_multiple,
                     c.leader_participation_cap,
                 )
+                .with_territory_limits(config.territory_limits.clone(), pml / territory_factor, &territories)
+                .with_partial_lines(config.partial_lines.clone())
+                .with_solvency(config.solvency.clone())
+                .with_expenses(config.expenses.clone())
+                .with_brokerage(config.brokerage.clone())
+                .with_payment_terms(config.payment_terms.clone())
+                .with_pricing_mode(config.pricing_mode)
+                .with_discount_credit(config.discount_years())
+                .with_growth_target(config.growth_targets.clone().filter(|g| g.applies_to(c.id)))
+                .with_appetite(c.appetite.clone())
+                .with_casualty_elf(config.casualty.as_ref().map_or(0.0, CasualtyConfig::expected_loss_fraction))
+                .with_capital_ledger(config.capital_ledger)
             })
             .collect();
 
         let insurer_ids: Vec<InsurerId> = insurers.iter().map(|i| i.id).collect();
 
-        let territories = &config.catastrophe.territories;
-        let mut insureds = Vec::new();
+        let n_cat_territories = config.catastrophe.territories.len().max(1);
+        let n_brokers = config.brokers.len().max(1);
+        let mut segments: Vec<Vec<Insured>> = (0..n_brokers).map(|_| Vec::new()).collect();
+        let mut perils = vec![Peril::WindstormAtlantic, Peril::Attritional];
+        if config.large_loss.is_some() {
+            perils.push(Peril::LargeFire);
+        }
+        let mut insured_broker = HashMap::new();
         // Sample each insured's reservation price from LogNormal(max_rol_mu, max_rol_sigma).
-        // Uses a local RNG seeded from config.seed — fully independent of Simulation.rng,
+        // Uses a local RNG seeded from config.seed — fully independent of Simulation.streams,
         // which is also seeded from config.seed but constructed separately below.
-        let mut insured_rng = ChaCha20Rng::seed_from_u64(config.seed);
+        let backend = config.rng.as_ref().map(|r| r.backend).unwrap_or_default();
+        let mut insured_rng = SimRng::new(backend, config.seed);
+        // Each insured holds `assets_per_insured` separately placed assets. Every asset is its
+        // own insurable unit (InsuredId, submission, policy, exposure-registry entry); the
+        // owner's reservation price and broker are shared by all of its assets. With casualty
+        // the owner's liability is one more unit on its own line, numbered after every asset.
+        let assets_per_insured = config.portfolio.as_ref().map_or(1, |p| p.assets_per_insured.max(1)) as usize;
+        let n_assets = config.n_insureds * assets_per_insured;
         for i in 0..config.n_insureds {
-            let territory = if territories.is_empty() {
-                "US-SE".to_string()
-            } else {
-                territories[i % territories.len()].clone()
-            };
             let base_rol = if config.max_rol_sigma == 0.0 {
                 config.max_rol_mu.exp()
             } else {
@@ -137,19 +313,94 @@ impl Simulation {
                     .expect("invalid LogNormal params for max_rol");
                 dist.sample(&mut insured_rng)
             };
-            insureds.push(Insured::new(
-                InsuredId(i as u64 + 1),
-                territory,
-                vec![Peril::WindstormAtlantic, Peril::Attritional],
-                base_rol,
-            ));
+            let owner = (assets_per_insured > 1 || config.casualty.is_some())
+                .then_some(InsuredId((i * assets_per_insured) as u64 + 1));
+            let mut liability = None;
+            for a in i * assets_per_insured..(i + 1) * assets_per_insured {
+                // Cat territories lead the table; without any, id 0 is the "US-SE" fallback.
+                let territory = Territory((a % n_cat_territories) as u16);
+                // Portfolio heterogeneity draws follow the reservation price on the same RNG, so
+                // configs without a portfolio see exactly the draws they always have.
+                let (territory, sum_insured, attritional_multiplier) = match config.portfolio.as_ref() {
+                    None => (territory, ASSET_VALUE, 1.0),
+                    Some(p) => {
+                        use rand::Rng as _;
+                        use rand_distr::{Distribution as _, LogNormal};
+                        let total: f64 = p.territory_weights.iter().map(|(_, w)| w.max(0.0)).sum();
+                        let territory = if total > 0.0 {
+                            let mut u = insured_rng.random::<f64>() * total;
+                            p.territory_weights
+                                .iter()
+                                .find(|(_, w)| {
+                                    u -= w.max(0.0);
+                                    u < 0.0
+                                })
+                                .or(p.territory_weights.last())
+                                .and_then(|(t, _)| territories.get(t))
+                                .unwrap_or(territory)
+                        } else {
+                            territory
+                        };
+                        let mut mean_one = |sigma: f64| {
+                            if sigma > 0.0 {
+                                LogNormal::new(-sigma * sigma / 2.0, sigma)
+                                    .expect("invalid LogNormal params for portfolio")
+                                    .sample(&mut insured_rng)
+                            } else {
+                                1.0
+                            }
+                        };
+                        let sum_insured = (ASSET_VALUE as f64 * mean_one(p.sum_insured_sigma)).round() as u64;
+                        (territory, sum_insured.max(1), mean_one(p.attritional_rate_sigma))
+                    }
+                };
+                // Brokers own insureds cyclically, mirroring the territory assignment.
+                let id = InsuredId(a as u64 + 1);
+                insured_broker.insert(id, i % n_brokers);
+                liability.get_or_insert((territory, sum_insured));
+                segments[i % n_brokers].push(
+                    Insured::new(id, territory, perils.clone(), base_rol)
+                        .with_owner(owner)
+                        .with_sum_insured(sum_insured)
+                        .with_attritional_multiplier(attritional_multiplier)
+                        .with_retention(config.retention.clone())
+                        .with_demand(config.demand.clone())
+                        .with_loyalty(config.loyalty.clone())
+                        .with_rollover(config.rollover.clone())
+                        .with_mitigation(config.mitigation.clone()),
+                );
+            }
+            // Liability cover: limit = the first asset's value, placed through the same broker.
+            if let (Some(_), Some((territory, limit))) = (&config.casualty, liability) {
+                let id = InsuredId((n_assets + i) as u64 + 1);
+                insured_broker.insert(id, i % n_brokers);
+                segments[i % n_brokers].push(
+                    Insured::new(id, territory, vec![Peril::Casualty], base_rol)
+                        .with_owner(owner)
+                        .with_sum_insured(limit)
+                        .with_retention(config.retention.clone())
+                        .with_demand(config.demand.clone())
+                        .with_loyalty(config.loyalty.clone())
+                        .with_rollover(config.rollover.clone()),
+                );
+            }
         }
-        let qps = config
-            .quotes_per_submission
-            .unwrap_or(insurer_ids.len())
-            .min(insurer_ids.len())
-            .max(1);
-        let broker = Broker::new(insureds, insurer_ids, qps);
+        let brokers: Vec<Broker> = segments
+            .into_iter()
+            .enumerate()
+            .map(|(idx, insureds)| {
+                let broker_cfg = config.brokers.get(idx);
+                let qps = broker_cfg
+                    .and_then(|b| b.quotes_per_submission)
+                    .or(config.quotes_per_submission)
+                    .unwrap_or(insurer_ids.len())
+                    .min(insurer_ids.len())
+                    .max(1);
+                let id = broker_cfg.map(|b| b.id).unwrap_or(BrokerId(0));
+                Broker::new(insureds, insurer_ids.clone(), qps)
+                    .with_id(id, idx as u64, n_brokers as u64)
+            })
+            .collect();
 
         let total_years = config.warmup_years + config.years;
         let max_day = Day::year_end(Year(total_years));
@@ -157,28 +408,73 @@ impl Simulation {
         let next_insurer_id =
             config.insurers.iter().map(|ic| ic.id.0).max().unwrap_or(0) + 1;
 
-        Simulation {
+        let config_health_day = config.health.as_ref().map(|h| Day(h.interval_days.max(1)));
+        let config_exposure_day = config.exposure_snapshots.as_ref().map(|e| Day(e.interval_days.max(1)));
+
+        Ok(Simulation {
             queue: BinaryHeap::new(),
+            next_seq: 0,
             log: EventLog::new(),
-            rng: ChaCha20Rng::seed_from_u64(config.seed),
+            streams,
             max_day: Some(max_day),
             max_events: None,
             insurers,
-            broker,
-            market: Market::new(),
+            brokers,
+            insured_broker,
+            market: Market::new()
+                .with_claims_development(config.claims_development.clone())
+                .with_premium_finance(config.premium_finance.clone())
+                .with_aggregate_deductible(config.aggregate_deductible.clone())
+                .with_policy_terms(config.policy_terms.clone()),
+            cat_bonds: CatBondBook::default(),
+            reinsurance: config.reinsurance.as_ref().map(ReinsuranceMarket::new),
+            lead_batches: BTreeMap::new(),
+            regulator: Regulator::new(config.regulator.clone()),
             next_event_id: 0,
             config,
-            attritional_scheduled: HashSet::new(),
+            territories,
+            coverage_request_day: HashMap::new(),
+            attritional_year: Year(0),
             year_premium_written: 0,
             year_claims_settled: 0,
             year_dropped_count: 0,
+            quarter_premium_written: 0,
+            quarter_claims_incurred: 0,
             cr_ewma: None,
             pml_200,
             next_insurer_id,
             last_entry_year: None,
             market_ap_tp_factor: 1.0,
+            soft_market_years: 0,
+            interest_rate: None,
+            reserving_factor: 1.0,
+            rate_index_base: None,
+            events_dispatched: 0,
+            next_health_day: config_health_day,
+            next_exposure_day: config_exposure_day,
             sensitivity_by_year: HashMap::new(),
-        }
+            accumulator: None,
+            live_check: None,
+            live_checked: 0,
+            dispatching: None,
+            year_closed: None,
+            schedule_error: None,
+            staged_flows: vec![],
+            observers: Observers::default(),
+        })
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// True once no queued event falls within the horizon: `run` would dispatch nothing more.
+    pub fn is_finished(&self) -> bool {
+        self.live_check.as_ref().is_some_and(LiveChecker::halted)
+            || self.schedule_error.is_some()
+            || self.queue
+            .peek()
+            .is_none_or(|Reverse(queued)| self.max_day.is_some_and(|horizon| queued.key.day > horizon))
     }
 
     /// Override the day horizon (used in tests).
@@ -194,41 +490,169 @@ impl Simulation {
         self
     }
 
+    /// Fold events into an `analysis::Accumulator` as they are dispatched instead of keeping
+    /// them: `log` holds at most the current event's output, so memory no longer grows with the
+    /// event count. Read the result with `year_stats`. Call before `start`.
+    pub fn aggregate_only(mut self) -> Self {
+        self.accumulator = Some(Accumulator::default());
+        self
+    }
+
+    /// Check the event-local invariants (see `live_check`) as each event is logged; in `Abort`
+    /// mode the run stops after the event that broke one. A resumed checkpoint is checked from
+    /// its first event. Read the result with `live_violations`.
+    pub fn check_live(mut self, mode: LiveCheckMode) -> Self {
+        self.live_check = Some(LiveChecker::new(mode));
+        self
+    }
+
+    /// Call `observer` after every dispatched event and every `YearEnd` (see `observer`).
+    /// Observers are called in registration order. A restored checkpoint needs them
+    /// registered again.
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Violations found by `check_live` so far; empty without it.
+    pub fn live_violations(&self) -> &[LiveViolation] {
+        self.live_check.as_ref().map_or(&[], LiveChecker::violations)
+    }
+
+    /// Post-warmup year statistics from the accumulator (see `aggregate_only`), else `None`.
+    pub fn year_stats(&self) -> Option<(u32, Vec<YearStats>)> {
+        self.accumulator.as_ref().map(|acc| acc.year_stats())
+    }
+
+    /// Events recorded so far: folded into the accumulator or still in `log`.
+    pub fn events_logged(&self) -> u64 {
+        self.accumulator.as_ref().map_or(0, Accumulator::events) + self.log.len() as u64
+    }
+
     /// Schedule an event to fire at the given day.
-    pub fn schedule(&mut self, day: Day, event: Event) {
-        self.queue.push(Reverse(SimEvent { day, event }));
+    ///
+    /// From inside a handler, `day` may not be earlier than the day being dispatched, and a
+    /// same-day event must pass `PriorityClass::may_follow_same_day` against the phase the day
+    /// has reached — a claim queued behind `YearEnd` would escape that year's accounting, even
+    /// when a capital event dispatched after `YearEnd` is the one queuing it. Either violation
+    /// is returned and the event is not queued.
+    pub fn schedule(&mut self, day: Day, event: Event) -> crate::Result<()> {
+        if let Some((now, class)) = self.dispatching {
+            let phase = if self.year_closed == Some(now) { class.max(PriorityClass::YearClose) } else { class };
+            if day < now || (day == now && !event.priority_class().may_follow_same_day(phase)) {
+                return Err(ScheduleError { event: Box::new(event), day, now, phase }.into());
+            }
+        }
+        if self.config.capital_ledger
+            && let Some(insurer_id) = event.capital_snapshot()
+        {
+            self.stage_capital_flows(Some(insurer_id));
+        }
+        self.queue.push(Reverse(QueuedEvent::new(day, event, self.next_seq)));
+        self.next_seq += 1;
+        Ok(())
+    }
+
+    /// `schedule` from a handler. An out-of-phase event is a simulator bug: the first one is
+    /// kept for `schedule_error` and the run halts before the next dispatch.
+    fn enqueue(&mut self, day: Day, event: Event) {
+        if let Err(crate::Error::Schedule(e)) = self.schedule(day, event) {
+            self.schedule_error.get_or_insert(e);
+        }
+    }
+
+    /// The out-of-phase event that halted the run, if any.
+    pub fn schedule_error(&self) -> Option<&ScheduleError> {
+        self.schedule_error.as_ref()
     }
 
     /// Bootstrap the simulation: schedule the initial SimulationStart event at Day(0).
     /// Prefer this over scheduling SimulationStart manually — it embeds warmup/analysis
     /// metadata from config so analysis scripts can read it from the event stream.
     pub fn start(&mut self) {
-        self.schedule(
+        self.enqueue(
             Day(0),
             Event::SimulationStart {
                 year_start: Year(1),
                 warmup_years: self.config.warmup_years,
                 analysis_years: self.config.years,
+                schema_version: SCHEMA_VERSION,
             },
         );
-        // Emit InsurerEntered for each initial insurer so the event stream is self-contained.
-        // Day(0) distinguishes these from dynamically-spawned entrants (day > 0).
+        // Emit InsurerCreated and InsurerEntered for each initial insurer, and InsuredCreated
+        // for each insured, so the event stream is self-contained. Day(0) distinguishes these
+        // InsurerEntered events from dynamically-spawned entrants (day > 0).
         for insurer in &self.insurers {
             self.log.push(SimEvent {
                 day: Day(0),
+                seq: None,
+                event: Event::InsurerCreated {
+                    insurer_id: insurer.id,
+                    initial_capital: insurer.capital.max(0) as u64,
+                    expense_ratio: insurer.expense_ratio(),
+                    target_loss_ratio: insurer.target_loss_ratio(),
+                    profit_loading: insurer.profit_loading(),
+                    cat_elf: insurer.cat_elf(),
+                },
+            });
+            self.log.push(SimEvent {
+                day: Day(0),
+                seq: None,
                 event: Event::InsurerEntered {
                     insurer_id: insurer.id,
                     initial_capital: insurer.capital.max(0) as u64,
                     cr_sensitivity: insurer.cr_sensitivity(),
                     capacity_sensitivity: insurer.capacity_sensitivity(),
                     market_weight_floor: insurer.market_weight_floor(),
+                    cat_elf: insurer.cat_elf(),
+                    profile: None,
+                },
+            });
+        }
+        let mut insureds: Vec<&Insured> = self.brokers.iter().flat_map(|b| &b.insureds).collect();
+        insureds.sort_by_key(|i| i.id);
+        for insured in insureds {
+            self.log.push(SimEvent {
+                day: Day(0),
+                seq: None,
+                event: Event::InsuredCreated {
+                    insured_id: insured.id,
+                    territory: insured.risk.territory,
+                    sum_insured: insured.risk.sum_insured,
+                    owner: insured.owner(),
                 },
             });
         }
     }
 
+    /// Capture the full state between events.
+    pub fn snapshot(&self) -> SimulationState {
+        SimulationState {
+            version: CHECKPOINT_VERSION,
+            day: self.log.last().map_or(Day(0), |e| e.day),
+            simulation: self.clone(),
+        }
+    }
+
+    /// Rebuild a simulation from a `snapshot`; `run` then continues from the next queued event.
+    pub fn restore(state: SimulationState) -> Result<Self, CheckpointError> {
+        if state.version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::VersionMismatch {
+                found: state.version,
+                expected: CHECKPOINT_VERSION,
+            });
+        }
+        Ok(state.simulation)
+    }
+
     /// Run the simulation until a stopping condition is met.
     pub fn run(&mut self) {
+        self.run_until(Day(u64::MAX));
+    }
+
+    /// Run until the next event falls after `day` (or an earlier stopping condition), so
+    /// every event up to and including `day` has been dispatched. Used for checkpointing.
+    pub fn run_until(&mut self, day: Day) {
         let mut count = 0;
         loop {
             if let Some(max) = self.max_events
@@ -237,28 +661,159 @@ impl Simulation {
                 break;
             }
 
+            if self.live_check.as_ref().is_some_and(LiveChecker::halted) || self.schedule_error.is_some() {
+                break;
+            }
+
             let next_day = match self.queue.peek() {
-                Some(Reverse(ev)) => ev.day,
+                Some(Reverse(queued)) => queued.key.day,
                 None => break,
             };
 
-            if let Some(horizon) = self.max_day
-                && next_day > horizon
-            {
+            if next_day > day || self.max_day.is_some_and(|horizon| next_day > horizon) {
                 break;
             }
+            if self.next_health_day.is_some_and(|boundary| next_day >= boundary) {
+                self.log_health(next_day);
+            }
+            if self.next_exposure_day.is_some_and(|boundary| next_day >= boundary) {
+                self.log_exposure(next_day);
+            }
 
-            let Reverse(ev) = self.queue.pop().unwrap();
+            let Reverse(QueuedEvent { ev, .. }) = self.queue.pop().unwrap();
+            let position = self.log.len();
             self.log.push(ev.clone());
+            self.dispatching = Some((ev.day, ev.event.priority_class()));
+            if ev.event.priority_class() == PriorityClass::YearClose {
+                self.year_closed = Some(ev.day);
+            }
             self.dispatch(ev.day, ev.event);
+            self.dispatching = None;
+            self.log_capital_flows(ev.day);
+            self.events_dispatched += 1;
             count += 1;
+            if !self.observers.is_empty() {
+                self.notify_observers(position);
+            }
+            // Handlers back-fill the event they just logged, so check and fold only once
+            // dispatch returns.
+            if let Some(checker) = self.live_check.as_mut() {
+                for ev in &self.log[self.live_checked..] {
+                    checker.push(ev);
+                }
+                self.live_checked = self.log.len();
+            }
+            if let Some(acc) = self.accumulator.as_mut() {
+                for ev in self.log.drain() {
+                    acc.push(&ev);
+                }
+                self.live_checked = 0;
+            }
         }
     }
 
+    /// Capital ledger: log the capital movements the last dispatch made, right after the
+    /// events it logged.
+    fn log_capital_flows(&mut self, day: Day) {
+        if !self.config.capital_ledger {
+            return;
+        }
+        self.stage_capital_flows(None);
+        for (insurer_id, reason, amount, queue_seq) in self.staged_flows.drain(..) {
+            self.log.push(SimEvent { day, seq: None, event: Event::CapitalFlow { insurer_id, reason, amount, queue_seq } });
+        }
+    }
+
+    /// Take the flows made so far by `insurer_id` (every insurer if `None`), stamped with the
+    /// seq the next queued event will take. Staged before a capital snapshot is queued, so the
+    /// snapshot follows exactly the flows it includes.
+    fn stage_capital_flows(&mut self, insurer_id: Option<InsurerId>) {
+        let queue_seq = self.next_seq;
+        for insurer in self.insurers.iter_mut().filter(|i| insurer_id.is_none_or(|id| i.id == id)) {
+            let id = insurer.id;
+            let flows = insurer.take_capital_flows();
+            self.staged_flows.extend(flows.into_iter().map(|(reason, amount)| (id, reason, amount, queue_seq)));
+        }
+    }
+
+    /// Hand `log[position]`, the event just dispatched, to the observers; after a `YearEnd`,
+    /// call their year-end hook too.
+    fn notify_observers(&mut self, position: usize) {
+        let mut observers = std::mem::take(&mut self.observers);
+        let event = &self.log[position];
+        let view = MarketView {
+            day: event.day,
+            queue_depth: self.queue.len(),
+            ap_tp_factor: self.market_ap_tp_factor,
+            insurers: &self.insurers,
+            brokers: &self.brokers,
+            market: &self.market,
+        };
+        observers.event_dispatched(event, &view);
+        if let Event::YearEnd { year } = event.event {
+            observers.year_end(year, &view);
+        }
+        self.observers = observers;
+    }
+
+    /// Log one `SimHealth` for the latest boundary at or before `next_day` and move the
+    /// boundary past it. Boundaries skipped in an idle stretch are not reported separately.
+    fn log_health(&mut self, next_day: Day) {
+        let (Some(boundary), Some(health)) = (self.next_health_day, self.config.health.as_ref()) else {
+            return;
+        };
+        let interval = health.interval_days.max(1);
+        let day = Day(boundary.0 + (next_day.0 - boundary.0) / interval * interval);
+        self.next_health_day = Some(Day(day.0 + interval));
+        // Keep the log day-ordered when both series skip through an idle stretch.
+        self.log_exposure(day);
+        self.log.push(SimEvent {
+            day,
+            seq: None,
+            event: Event::SimHealth {
+                queue_depth: self.queue.len() as u64,
+                events_dispatched: self.events_dispatched,
+                active_policies: self.market.policies.len() as u32,
+                pending_submissions: self.brokers.iter().map(|b| b.pending_submissions()).sum::<usize>() as u32,
+            },
+        });
+    }
+
+    /// Log an `ExposureSnapshot` set for every boundary up to `until`. Unlike `SimHealth`,
+    /// boundaries in an idle stretch each get their own (unchanged) snapshot, so the series
+    /// stays evenly spaced.
+    fn log_exposure(&mut self, until: Day) {
+        let (Some(mut boundary), Some(config)) = (self.next_exposure_day, self.config.exposure_snapshots.as_ref())
+        else {
+            return;
+        };
+        if boundary > until {
+            return;
+        }
+        let interval = config.interval_days.max(1);
+        let exposure = self.market.exposure_by_insurer();
+        while boundary <= until {
+            for ((insurer_id, territory, peril), &aggregate_sum_insured) in &exposure {
+                self.log.push(SimEvent {
+                    day: boundary,
+                    seq: None,
+                    event: Event::ExposureSnapshot {
+                        insurer_id: *insurer_id,
+                        territory: *territory,
+                        peril: *peril,
+                        aggregate_sum_insured,
+                    },
+                });
+            }
+            boundary = Day(boundary.0 + interval);
+        }
+        self.next_exposure_day = Some(boundary);
+    }
+
     fn dispatch(&mut self, day: Day, event: Event) {
         match event {
             Event::SimulationStart { year_start, .. } => {
-                self.schedule(Day::year_start(year_start), Event::YearStart { year: year_start });
+                self.enqueue(Day::year_start(year_start), Event::YearStart { year: year_start });
             }
 
             Event::YearStart { year } => {
@@ -269,32 +824,76 @@ impl Simulation {
                 self.handle_year_end(day, year);
             }
 
-            Event::CoverageRequested { insured_id, risk } => {
-                // Register insured in market (idempotent — first call wins).
-                self.market.register_insured(insured_id, &risk.territory, risk.sum_insured);
+            Event::QuarterEnd { .. } => {
+                let premium = std::mem::take(&mut self.quarter_premium_written);
+                let claims = std::mem::take(&mut self.quarter_claims_incurred);
+                let market_capital = self.insurers.iter().map(|i| i.capital.max(0) as u64).sum();
+                if let Some(Event::QuarterEnd { written_premium, claims_incurred, capital, .. }) =
+                    self.log.last_mut().map(|last| &mut last.event)
+                {
+                    *written_premium = premium;
+                    *claims_incurred = claims;
+                    *capital = market_capital;
+                }
+            }
 
-                // Schedule attritional losses once per (insured, year) so that
-                // retries (QuoteRejected / SubmissionDropped renewals) don't
-                // double-schedule losses for the same insured in the same year.
-                let year = day.year();
-                if self.attritional_scheduled.insert((insured_id, year)) {
-                    let att = perils::schedule_attritional_losses_for_insured(
-                        insured_id, &risk, day, &mut self.rng, &self.config.attritional,
-                    );
-                    for (d, e) in att {
-                        self.schedule(d, e);
-                    }
+            Event::CoverageRequested { insured_id, risk } => {
+                // Register the asset in the market (idempotent — first call wins); liability
+                // cover is not struck by catastrophes.
+                if risk.line() == Line::Property {
+                    self.market.register_insured(insured_id, risk.territory, risk.sum_insured);
                 }
 
-                let events = self.broker.on_coverage_requested(day, insured_id, risk);
+                // Attritional losses from this day were scheduled when the request was
+                // (see `schedule_coverage_request` and the YearStart pass).
+
+                // A rollover renewal re-signs the expiring panel; a loyal insured renews with its
+                // incumbent lead. Either way the insured shops if the price rises too far.
+                let rollover = self.insured(insured_id).and_then(|i| i.rollover_terms());
+                let anchor = self.insured(insured_id).and_then(|i| i.renewal_anchor());
+                let events = match (self.broker_of_mut(insured_id), rollover, anchor) {
+                    (Some(broker), Some((panel, max_premium)), _) => {
+                        broker.on_rollover_requested(day, insured_id, *risk, panel, max_premium)
+                    }
+                    (Some(broker), None, Some((incumbent, max_premium))) => {
+                        broker.on_renewal_requested(day, insured_id, *risk, incumbent, max_premium)
+                    }
+                    (Some(broker), None, None) => broker.on_coverage_requested(day, insured_id, *risk),
+                    (None, _, _) => vec![],
+                };
                 for (d, e) in events {
-                    self.schedule(d, e);
+                    self.enqueue(d, e);
                 }
             }
 
             Event::LeadQuoteRequested { submission_id, insured_id, insurer_id, risk } => {
                 let factor = self.market_ap_tp_factor;
-                if let Some(insurer) = self.insurers.iter().find(|i| i.id == insurer_id) {
+                if self.quote_turnaround_exceeded(day, insurer_id) {
+                    self.enqueue(day, Event::LeadQuoteDeclined {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        reason: DeclineReason::TurnaroundExceeded,
+                    });
+                } else if self.regulatory_restricted(insurer_id, insured_id) {
+                    self.enqueue(day, Event::LeadQuoteDeclined {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        reason: DeclineReason::RegulatoryRestriction,
+                    });
+                } else if let Some(window) = self.config.rationing.as_ref().map(|r| r.window_days)
+                    && risk.perils_covered.contains(&Peril::WindstormAtlantic)
+                {
+                    // Held for the insurer's batch; the first request of a window opens it.
+                    let batch = self.lead_batches.entry(insurer_id).or_default();
+                    let opens = batch.is_empty();
+                    batch.push((submission_id, insured_id, *risk));
+                    if opens {
+                        let close = Day(day.0 / window * window + window - 1);
+                        self.enqueue(close, Event::LeadQuoteBatchClosed { insurer_id, requests: 0, cat_headroom: 0 });
+                    }
+                } else if let Some(insurer) = self.insurers.iter().find(|i| i.id == insurer_id) {
                     for (d, e) in insurer.on_lead_quote_requested(
                         day,
                         submission_id,
@@ -302,75 +901,157 @@ impl Simulation {
                         &risk,
                         factor,
                     ) {
-                        self.schedule(d, e);
+                        self.enqueue(d, e);
                     }
                 }
             }
 
-            Event::LeadQuoteDeclined { submission_id, insurer_id, .. } => {
-                for (d, e) in self.broker.on_lead_quote_declined(day, submission_id, insurer_id) {
-                    self.schedule(d, e);
+            Event::LeadQuoteBatchClosed { insurer_id, .. } => {
+                self.close_lead_batch(day, insurer_id);
+            }
+
+            // SubmissionRouted is an audit record of the broker's routing decision — no dispatch.
+            Event::SubmissionRouted { .. } => {}
+
+            Event::LeadQuoteDeclined { submission_id, insured_id, insurer_id, .. } => {
+                let events = match self.broker_of_mut(insured_id) {
+                    Some(broker) => broker.on_lead_quote_declined(day, submission_id, insurer_id),
+                    None => vec![],
+                };
+                for (d, e) in events {
+                    self.enqueue(d, e);
                 }
             }
 
             Event::LeadQuoteIssued { submission_id, insured_id, insurer_id, atp, premium, line_size, cat_exposure_at_quote: _ } => {
-                let events =
-                    self.broker.on_lead_quote_issued(day, submission_id, insured_id, insurer_id, atp, premium, line_size);
+                let events = match self.broker_of_mut(insured_id) {
+                    Some(broker) => broker.on_lead_quote_issued(
+                        day, submission_id, insured_id, insurer_id, atp, premium, line_size,
+                    ),
+                    None => vec![],
+                };
+                for (d, e) in events {
+                    self.enqueue(d, e);
+                }
+            }
+
+            Event::LeadQuotePartial { submission_id, insured_id, insurer_id, atp, premium, line_size, cat_exposure_at_quote: _ } => {
+                let events = match self.broker_of_mut(insured_id) {
+                    Some(broker) => broker.on_lead_quote_partial(
+                        day, submission_id, insurer_id, atp, premium, line_size,
+                    ),
+                    None => vec![],
+                };
                 for (d, e) in events {
-                    self.schedule(d, e);
+                    self.enqueue(d, e);
                 }
             }
 
             Event::FollowerQuoteRequested { submission_id, insured_id, insurer_id, ref risk, lead_premium, lead_atp } => {
                 let risk = risk.clone();
-                if let Some(ins) = self.insurers.iter().find(|i| i.id == insurer_id) {
+                if self.quote_turnaround_exceeded(day, insurer_id) {
+                    self.enqueue(day, Event::FollowerQuoteDeclined {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        reason: DeclineReason::TurnaroundExceeded,
+                    });
+                } else if self.regulatory_restricted(insurer_id, insured_id) {
+                    self.enqueue(day, Event::FollowerQuoteDeclined {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        reason: DeclineReason::RegulatoryRestriction,
+                    });
+                } else if let Some(ins) = self.insurers.iter().find(|i| i.id == insurer_id) {
                     for (d, e) in ins.on_follower_quote_requested(day, submission_id, insured_id, &risk, lead_premium, lead_atp) {
-                        self.schedule(d, e);
+                        self.enqueue(d, e);
+                    }
+                }
+            }
+
+            Event::RenewalOffered { submission_id, insured_id, insurer_id, ref risk, lead_premium, expiring_line } => {
+                let risk = risk.clone();
+                if self.quote_turnaround_exceeded(day, insurer_id) {
+                    self.enqueue(day, Event::RenewalDeclined {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        reason: DeclineReason::TurnaroundExceeded,
+                    });
+                } else if self.regulatory_restricted(insurer_id, insured_id) {
+                    self.enqueue(day, Event::RenewalDeclined {
+                        submission_id,
+                        insured_id,
+                        insurer_id,
+                        reason: DeclineReason::RegulatoryRestriction,
+                    });
+                } else if let Some(ins) = self.insurers.iter().find(|i| i.id == insurer_id) {
+                    for (d, e) in ins.on_renewal_offered(day, submission_id, insured_id, &risk, lead_premium, expiring_line) {
+                        self.enqueue(d, e);
                     }
                 }
             }
 
-            Event::FollowerQuoteIssued { submission_id, insurer_id, line_size, .. } => {
-                for (d, e) in self.broker.on_follower_quote_issued(day, submission_id, insurer_id, line_size) {
-                    self.schedule(d, e);
+            Event::FollowerQuoteIssued { submission_id, insured_id, insurer_id, line_size } => {
+                let events = match self.broker_of_mut(insured_id) {
+                    Some(broker) => broker.on_follower_quote_issued(day, submission_id, insurer_id, line_size),
+                    None => vec![],
+                };
+                for (d, e) in events {
+                    self.enqueue(d, e);
                 }
             }
 
-            Event::FollowerQuoteDeclined { submission_id, insurer_id, .. } => {
-                for (d, e) in self.broker.on_follower_quote_declined(day, submission_id, insurer_id) {
-                    self.schedule(d, e);
+            Event::FollowerQuoteDeclined { submission_id, insured_id, insurer_id, .. }
+            | Event::RenewalDeclined { submission_id, insured_id, insurer_id, .. } => {
+                let events = match self.broker_of_mut(insured_id) {
+                    Some(broker) => broker.on_follower_quote_declined(day, submission_id, insurer_id),
+                    None => vec![],
+                };
+                for (d, e) in events {
+                    self.enqueue(d, e);
                 }
             }
 
             Event::QuotePresented { submission_id, insured_id, leader_id, ref panel, premium } => {
-                // Insured decides whether to accept.
+                // Insured decides whether to accept. The demand draw is only taken when
+                // elastic demand is configured, so the RNG stream is otherwise unchanged.
                 let panel = panel.clone();
-                for insured in &self.broker.insureds {
-                    if insured.id == insured_id {
-                        let events =
-                            insured.on_quote_presented(day, submission_id, leader_id, panel, premium);
-                        for (d, e) in events {
-                            self.schedule(d, e);
-                        }
-                        break;
+                let demand_draw = self.config.demand.is_some().then(|| {
+                    use rand::Rng as _;
+                    self.streams.get(Stream::Broker).random::<f64>()
+                });
+                if let Some(insured) = self.insured_mut(insured_id) {
+                    let events = insured.on_quote_presented(day, submission_id, leader_id, panel, premium, demand_draw);
+                    for (d, e) in events {
+                        self.enqueue(d, e);
                     }
                 }
             }
 
-            Event::QuoteAccepted { submission_id, insured_id, leader_id: _, ref panel, premium } => {
+            Event::QuoteAccepted { submission_id, insured_id, leader_id, ref panel, premium } => {
                 let year = day.year();
                 let panel = panel.clone();
-                let risk = self
-                    .broker
-                    .insureds
-                    .iter()
-                    .find(|i| i.id == insured_id)
-                    .map(|i| i.risk.clone());
+                let risk = self.insured(insured_id).map(|i| i.bound_risk());
                 if let Some(risk) = risk {
                     // Schedule renewal CoverageRequested so the new PolicyBound lands
                     // exactly on the old PolicyExpired (day+361), eliminating drift.
+                    // The renewal carries any retention change the insured makes now.
                     let renewal_day = day.offset(361 - QUOTING_CHAIN_DAYS);
-                    let renewal_risk = risk.clone();
+                    let rate = premium as f64 / risk.sum_insured as f64;
+                    let (renewal_risk, expiring_leader, mitigation) = match self.insured_mut(insured_id) {
+                        Some(insured) => {
+                            insured.adjust_retention(Some(rate));
+                            let mitigation = insured.consider_mitigation(day, premium);
+                            insured.record_panel(&panel);
+                            (insured.risk.clone(), insured.record_placement(leader_id, premium), mitigation)
+                        }
+                        None => (risk.clone(), None, None),
+                    };
+                    if let Some((d, e)) = mitigation {
+                        self.enqueue(d, e);
+                    }
 
                     let events = self.market.on_quote_accepted(
                         day,
@@ -381,23 +1062,40 @@ impl Simulation {
                         risk,
                         year,
                     );
-                    for (d, e) in events {
-                        self.schedule(d, e);
+                    // Under loyalty or rollover, record whether a renewing insured stayed with its lead.
+                    let renewal =
+                        expiring_leader.filter(|_| self.config.loyalty.is_some() || self.config.rollover.is_some());
+                    let renewed = events.iter().find_map(|(d, e)| match (e, renewal) {
+                        (Event::PolicyBound { policy_id, .. }, Some(expiring)) => Some((
+                            *d,
+                            Event::PolicyRenewed {
+                                policy_id: *policy_id,
+                                insured_id,
+                                incumbent: expiring == leader_id,
+                            },
+                        )),
+                        _ => None,
+                    });
+                    for (d, e) in events.into_iter().chain(renewed) {
+                        self.enqueue(d, e);
                     }
 
-                    self.schedule(renewal_day, Event::CoverageRequested {
-                        insured_id,
-                        risk: renewal_risk,
-                    });
+                    // Multi-year policies schedule their renewal from the last anniversary.
+                    if !self.multi_year_terms() {
+                        self.schedule_coverage_request(renewal_day, insured_id, renewal_risk);
+                    }
                 }
             }
 
             Event::QuoteRejected { insured_id, .. } => {
                 // Schedule renewal: same annual offset as the QuoteAccepted path.
+                // A priced-out insured may take a higher deductible before retrying.
                 let renewal_day = day.offset(361 - QUOTING_CHAIN_DAYS);
-                if let Some(insured) = self.broker.insureds.iter().find(|i| i.id == insured_id) {
+                if let Some(insured) = self.insured_mut(insured_id) {
+                    insured.adjust_retention(None);
+                    insured.lapse();
                     let risk = insured.risk.clone();
-                    self.schedule(renewal_day, Event::CoverageRequested { insured_id, risk });
+                    self.schedule_coverage_request(renewal_day, insured_id, risk);
                 }
             }
 
@@ -406,9 +1104,10 @@ impl Simulation {
                 // All insurers declined. Schedule the same annual-offset renewal so the
                 // insured retries next year rather than silently vanishing from the model.
                 let renewal_day = day.offset(361 - QUOTING_CHAIN_DAYS);
-                if let Some(insured) = self.broker.insureds.iter().find(|i| i.id == insured_id) {
+                if let Some(insured) = self.insured_mut(insured_id) {
+                    insured.lapse();
                     let risk = insured.risk.clone();
-                    self.schedule(renewal_day, Event::CoverageRequested { insured_id, risk });
+                    self.schedule_coverage_request(renewal_day, insured_id, risk);
                 }
             }
 
@@ -416,24 +1115,160 @@ impl Simulation {
                 // Activate the policy for loss routing.
                 self.market.on_policy_bound(policy_id);
 
-                // Attritional AssetDamage events are scheduled at CoverageRequested time
-                // (see the CoverageRequested arm above) so all insureds accumulate
-                // attritional exposure regardless of policy status.
+                // Attritional AssetDamage events are scheduled from the CoverageRequested day
+                // (see `schedule_year_attritional`) so all insureds accumulate attritional
+                // exposure regardless of policy status.
 
                 if let Some(policy) = self.market.policies.get(&policy_id) {
+                    let insured_id = policy.insured_id;
                     let panel = policy.panel.clone();
-                    let sum_insured = policy.risk.sum_insured;
-                    let perils = policy.risk.perils_covered.clone();
+                    let risk = policy.risk.clone();
                     for (insurer_id, line_share) in &panel {
                         if let Some(ins) = self.insurers.iter_mut().find(|i| i.id == *insurer_id) {
-                            ins.on_policy_bound(policy_id, sum_insured, premium, &perils, *line_share);
+                            ins.on_risk_bound(policy_id, &risk, premium, *line_share);
+                        }
+                        // Update the placing broker's relationship score per panel member.
+                        if let Some(broker) = self.broker_of_mut(insured_id) {
+                            broker.on_policy_bound(*insurer_id);
                         }
-                        // Update broker relationship score per panel member.
-                        self.broker.on_policy_bound(*insurer_id);
                     }
                 }
 
                 self.year_premium_written += premium;
+                self.quarter_premium_written += premium;
+                self.schedule_quote_comparison(day, policy_id, premium);
+                self.schedule_brokerage(day, policy_id, premium);
+                let paid = self.schedule_premium_default(day, policy_id);
+                if let Some(insured_id) = self.market.policies.get(&policy_id).map(|p| p.insured_id) {
+                    let instalments = self.config.premium_finance.as_ref().map_or(1, |pf| pf.instalments.max(1));
+                    self.schedule_premium_receipts(day, policy_id, insured_id, premium, instalments, paid.unwrap_or(instalments));
+                }
+                if self.multi_year_terms() {
+                    self.schedule_renewal_window(day.offset(Day::DAYS_PER_YEAR - QUOTING_CHAIN_DAYS), policy_id);
+                }
+            }
+
+            // PolicyRenewed is an audit record of the insured's renewal choice — no dispatch.
+            Event::PolicyRenewed { .. } => {}
+
+            // QuoteComparisonRecorded is an analysis record — no dispatch.
+            Event::QuoteComparisonRecorded { .. } => {}
+
+            Event::PolicyAnniversary { policy_id, insured_id, premium, .. } => {
+                let policy = self.market.policies.get(&policy_id).map(|p| (p.panel.clone(), p.risk.clone()));
+                let cancellation =
+                    policy.as_ref().and_then(|(panel, risk)| self.anniversary_cancellation(panel, risk, premium));
+                match (policy, cancellation) {
+                    (Some((panel, risk)), None) => {
+                        for (insurer_id, line_share) in &panel {
+                            if let Some(ins) = self.insurers.iter_mut().find(|i| i.id == *insurer_id) {
+                                ins.on_policy_anniversary(policy_id, &risk, premium, *line_share);
+                            }
+                        }
+                        self.year_premium_written += premium;
+                        self.quarter_premium_written += premium;
+                        self.schedule_brokerage(day, policy_id, premium);
+                        self.schedule_premium_receipts(day, policy_id, insured_id, premium, 1, 1);
+                        self.schedule_renewal_window(day.offset(Day::DAYS_PER_YEAR), policy_id);
+                    }
+                    (_, reason) => {
+                        // The contract ends here: nothing is collected. Cover runs to the
+                        // anniversary and the insured re-shops so a replacement binds on it.
+                        // A policy already cancelled for default has no reason and just re-shops.
+                        if let Some(Event::PolicyAnniversary { premium, .. }) =
+                            self.log.last_mut().map(|last| &mut last.event)
+                        {
+                            *premium = 0;
+                        }
+                        if let Some(reason) = reason {
+                            self.enqueue(
+                                day.offset(QUOTING_CHAIN_DAYS),
+                                Event::PolicyCancelled {
+                                    policy_id,
+                                    insured_id,
+                                    reason,
+                                    basis: CancellationBasis::AbInitio,
+                                    premium_reversed: 0,
+                                    bad_debt: 0,
+                                },
+                            );
+                        }
+                        if let Some(risk) = self.insured(insured_id).map(|i| i.risk.clone()) {
+                            self.schedule_coverage_request(day, insured_id, risk);
+                        }
+                    }
+                }
+            }
+
+            Event::MarketIntelligence { broker_id, ref territories, .. } => {
+                // Property insureds in the broker's segment anchor their demand reference to
+                // the published rate for their territory.
+                if self.config.market_intelligence.as_ref().is_some_and(|mi| mi.anchor_demand)
+                    && let Some(broker) = self.brokers.iter_mut().find(|b| b.id == broker_id)
+                {
+                    for insured in broker.insureds.iter_mut().filter(|i| i.risk.line() == Line::Property) {
+                        if let Some(t) = territories.iter().find(|t| t.territory == insured.risk.territory) {
+                            insured.on_market_intelligence(t.avg_rate);
+                        }
+                    }
+                }
+            }
+
+            Event::InvestmentReturnDrawn { rate, .. } => {
+                let events: Vec<(Day, Event)> = self
+                    .insurers
+                    .iter_mut()
+                    .flat_map(|insurer| insurer.on_investment_return(day, rate))
+                    .collect();
+                for (d, e) in events {
+                    self.enqueue(d, e);
+                }
+            }
+
+            // InvestmentIncome is informational (capital already credited) — no further dispatch.
+            Event::InvestmentIncome { .. } => {}
+
+            Event::PremiumReceived { policy_id, amount, .. } => {
+                // Every panel member holding a receivable on the policy credits its share.
+                for ins in &mut self.insurers {
+                    ins.on_premium_received(policy_id, amount);
+                }
+            }
+
+            // MitigationInvested records a choice already applied by the insured — no dispatch.
+            Event::MitigationInvested { .. } => {}
+
+            // RateIndexPublished is a market statistic — no dispatch.
+            Event::RateIndexPublished { .. } => {}
+
+            // PmlReported records a figure already handed to the insurer — no dispatch.
+            Event::PmlReported { .. } => {}
+
+            Event::BrokerageEarned { broker_id, amount, .. } => {
+                if let Some(broker) = self.brokers.iter_mut().find(|b| b.id == broker_id) {
+                    broker.on_brokerage_earned(amount);
+                }
+            }
+
+            Event::PremiumDefaulted { policy_id, instalment, .. } => {
+                for (d, e) in self.market.on_premium_defaulted(day, policy_id, instalment) {
+                    self.enqueue(d, e);
+                }
+            }
+
+            Event::PolicyCancelled { policy_id, premium_reversed, bad_debt, .. } => {
+                // Read panel before market removes the policy record.
+                let panel = self.market.policies.get(&policy_id).map(|p| p.panel.clone());
+                for (ins_id, line_share) in panel.unwrap_or_default() {
+                    let events = match self.insurers.iter_mut().find(|i| i.id == ins_id) {
+                        Some(ins) => ins.on_policy_cancelled(day, policy_id, premium_reversed, bad_debt, line_share),
+                        None => vec![],
+                    };
+                    for (d, e) in events {
+                        self.enqueue(d, e);
+                    }
+                }
+                self.market.on_policy_cancelled(policy_id);
             }
 
             Event::PolicyExpired { policy_id } => {
@@ -449,33 +1284,98 @@ impl Simulation {
                 self.market.on_policy_expired(policy_id);
             }
 
-            Event::LossEvent { peril, territory, damage_fraction, .. } => {
-                let events = self.market.on_loss_event(
-                    day,
-                    peril,
-                    &territory,
-                    damage_fraction,
-                );
+            Event::LossEvent { event_id, peril, territory, damage_fraction, footprint } => {
+                let mut events = self.market.on_loss_event(day, peril, territory, damage_fraction);
+                for &(t, d) in &footprint {
+                    events.extend(self.market.on_loss_event(day, peril, t, d));
+                }
+                // Mitigated assets take a smaller share of the occurrence's damage.
+                if self.config.mitigation.is_some() {
+                    events.retain_mut(|(_, e)| {
+                        let Event::AssetDamage { insured_id, ground_up_loss, .. } = e else { return true };
+                        let factor = self.insured(*insured_id).map_or(1.0, Insured::cat_damage_factor);
+                        *ground_up_loss = (*ground_up_loss as f64 * factor) as u64;
+                        *ground_up_loss > 0
+                    });
+                }
+                let total_gul = self.market.route_occurrence(event_id, &events);
+                // Bond payouts and XoL recoveries are scheduled first so they land before the claims.
+                let reinsured = self.reinsurance.as_ref().is_some_and(|r| !r.is_empty());
+                if !self.cat_bonds.is_empty() || reinsured {
+                    let losses = self.market.occurrence_losses_by_insurer(event_id);
+                    let loss = |id: InsurerId| losses.get(&id).copied().unwrap_or(0);
+                    // Insolvent sponsors' bonds do not trigger; the collateral stays with investors.
+                    let insurers = &self.insurers;
+                    let sponsor_loss = |id: InsurerId| {
+                        (!insurers.iter().any(|i| i.id == id && i.insolvent)).then(|| loss(id))
+                    };
+                    let mut recoveries = self.cat_bonds.on_loss_event(day, event_id, damage_fraction, sponsor_loss);
+                    if let Some(reinsurance) = self.reinsurance.as_mut() {
+                        recoveries.extend(reinsurance.on_loss_event(day, event_id, loss));
+                    }
+                    for (d, e) in recoveries {
+                        self.enqueue(d, e);
+                    }
+                }
                 for (d, e) in events {
-                    self.schedule(d, e);
+                    self.enqueue(d, e);
+                }
+                // Same day and class, scheduled after the damages: releases the occurrence's
+                // claims once they have all fired.
+                self.enqueue(day, Event::IndustryLossEstimate { event_id, total_gul, insured_loss: 0 });
+            }
+
+            Event::IndustryLossEstimate { event_id, .. } => {
+                let (routed, claims) = self.market.close_occurrence(event_id);
+                if let Some(Event::IndustryLossEstimate { insured_loss, .. }) =
+                    self.log.last_mut().map(|last| &mut last.event)
+                {
+                    *insured_loss = routed;
+                }
+                for (d, e) in claims {
+                    self.enqueue(d, e);
+                }
+            }
+
+            Event::CatBondTriggered { insurer_id, payout, .. } => {
+                let capital = self.insurers.iter_mut().find(|i| i.id == insurer_id).map(|i| i.recover_cat_bond(payout));
+                if let (Some(capital), Some(Event::CatBondTriggered { remaining_capital, .. })) =
+                    (capital, self.log.last_mut().map(|last| &mut last.event))
+                {
+                    *remaining_capital = capital;
+                }
+            }
+
+            Event::ReinsuranceRecovered { insurer_id, amount, .. } => {
+                let capital = self.insurers.iter_mut().find(|i| i.id == insurer_id).map(|i| i.recover_reinsurance(amount));
+                if let (Some(capital), Some(Event::ReinsuranceRecovered { remaining_capital, .. })) =
+                    (capital, self.log.last_mut().map(|last| &mut last.event))
+                {
+                    *remaining_capital = capital;
                 }
             }
 
             Event::AssetDamage { insured_id, peril, ground_up_loss } => {
-                // Route to ClaimSettled only for covered insureds.
-                let events =
-                    self.market.on_asset_damage(day, insured_id, ground_up_loss, peril);
+                // Route to ClaimSettled only for covered insureds; casualty claims are reported
+                // in a later year. Cat damages were routed with their occurrence.
+                let events = match self.config.casualty.as_ref().filter(|_| peril == Peril::Casualty) {
+                    _ if peril.is_cat() => vec![],
+                    Some(casualty) => {
+                        let lag = perils::casualty_report_lag(casualty, self.streams.get(Stream::Attritional));
+                        self.market.on_casualty_damage(day, lag, insured_id, ground_up_loss)
+                    }
+                    None => self.market.on_asset_damage(day, insured_id, ground_up_loss, peril),
+                };
                 for (d, e) in events {
-                    self.schedule(d, e);
+                    self.enqueue(d, e);
                 }
 
                 // Notify insured so uplift state reflects the loss severity.
-                let df = ground_up_loss as f64 / ASSET_VALUE as f64;
-                for insured in &mut self.broker.insureds {
-                    if insured.id == insured_id {
-                        insured.on_asset_damage(df);
-                        break;
-                    }
+                if let Some(broker) = self.broker_of_mut(insured_id)
+                    && let Some(insured) = broker.insureds.iter_mut().find(|i| i.id == insured_id)
+                {
+                    let df = ground_up_loss as f64 / insured.sum_insured() as f64;
+                    insured.on_asset_damage(df);
                 }
             }
 
@@ -499,23 +1399,124 @@ impl Simulation {
                         vec![]
                     };
                 for (d, e) in new_events {
-                    self.schedule(d, e);
+                    self.enqueue(d, e);
                 }
                 self.year_claims_settled += amount;
+                self.quarter_claims_incurred += amount;
             }
 
-            Event::InsurerInsolvent { .. } => {}
+            Event::ClaimReported { claim_id, insurer_id, peril, amount, .. } => {
+                self.quarter_claims_incurred += amount;
+                let ratio = self
+                    .config
+                    .claims_development
+                    .as_ref()
+                    .map(|d| d.initial_reserve_ratio)
+                    .unwrap_or(1.0)
+                    * if peril == Peril::Casualty { self.reserving_factor } else { 1.0 };
+                let events = match self.insurers.iter_mut().find(|i| i.id == insurer_id) {
+                    Some(insurer) => insurer.on_claim_reported(day, claim_id, amount, peril, ratio),
+                    None => vec![],
+                };
+                for (d, e) in events {
+                    self.enqueue(d, e);
+                }
+            }
 
-            // InsurerEntered is logged directly by spawn_new_insurer — no further dispatch.
-            Event::InsurerEntered { .. } => {}
+            // ReserveEstablished is an audit record scheduled by the insurer — no further dispatch.
+            Event::ReserveEstablished { .. } => {}
 
-            // CapitalDistributed is logged directly by the insurer in on_year_end — no further dispatch.
-            Event::CapitalDistributed { .. } => {}
+            Event::ClaimPaid { claim_id, insurer_id, peril, amount, cumulative_paid_fraction, .. } => {
+                let events = match self.insurers.iter_mut().find(|i| i.id == insurer_id) {
+                    Some(insurer) => {
+                        insurer.on_claim_paid(day, claim_id, amount, peril, cumulative_paid_fraction)
+                    }
+                    None => vec![],
+                };
+                for (d, e) in events {
+                    self.enqueue(d, e);
+                }
+                self.year_claims_settled += amount;
+            }
 
-            // YearEndCapital is logged directly by the insurer in on_year_end — no further dispatch.
-            Event::YearEndCapital { .. } => {}
-        }
-    }
+            Event::InsurerInsolvent { .. } => {}
+
+            // SimHealth is logged directly by run_until and never queued — no further dispatch.
+            Event::SimHealth { .. }
+            | Event::ExposureSnapshot { .. }
+            | Event::ExposureGrowth { .. }
+            | Event::InterestRatePublished { .. }
+            | Event::UnderwritingPlanSet { .. }
+            | Event::CapitalFlow { .. } => {}
+
+            // CapitalRaised is logged by raise_capital at YearEnd, which has already credited
+            // the insurer — no further dispatch.
+            Event::CapitalRaised { .. } => {}
+
+            // The bond is already on the book (issued at YearEnd); the sponsor pays the coupon now.
+            Event::CatBondIssued(bond) => {
+                let capital = self
+                    .insurers
+                    .iter_mut()
+                    .find(|i| i.id == bond.insurer_id)
+                    .map(|i| i.pay_cat_bond_premium(bond.premium));
+                if let (Some(capital), Some(Event::CatBondIssued(logged))) =
+                    (capital, self.log.last_mut().map(|last| &mut last.event))
+                {
+                    logged.remaining_capital = capital;
+                }
+            }
+
+            // The treaty is already in force (placed at YearStart); the cedant pays the premium now.
+            Event::ReinsurancePurchased { insurer_id, premium, .. } => {
+                let capital =
+                    self.insurers.iter_mut().find(|i| i.id == insurer_id).map(|i| i.pay_reinsurance_premium(premium));
+                if let (Some(capital), Some(Event::ReinsurancePurchased { remaining_capital, .. })) =
+                    (capital, self.log.last_mut().map(|last| &mut last.event))
+                {
+                    *remaining_capital = capital;
+                }
+            }
+
+            Event::CatBondMatured { bond_id, .. } => {
+                let returned = self.cat_bonds.mature(bond_id);
+                if let Some(Event::CatBondMatured { principal_returned, .. }) =
+                    self.log.last_mut().map(|last| &mut last.event)
+                {
+                    *principal_returned = returned;
+                }
+            }
+
+            // InsurerExited / InsurerReEntered are logged by evaluate_runoff at YearEnd, which
+            // has already flipped the insurer's run-off state — no further dispatch.
+            Event::InsurerExited { .. } | Event::InsurerReEntered { .. } => {}
+
+            // PortfolioTransferred is scheduled by transfer_portfolio after the lines, capitals
+            // and broker relationships have moved — no further dispatch.
+            Event::PortfolioTransferred { .. } => {}
+
+            // InsurerEntered is logged directly by spawn_new_insurer — no further dispatch.
+            Event::InsurerEntered { .. } => {}
+            // Initial conditions are logged directly by start() — no further dispatch.
+            Event::InsurerCreated { .. } | Event::InsuredCreated { .. } => {}
+            // RunMetadata exists only in written log files.
+            Event::RunMetadata(_) => {}
+
+            // DeductibleEroded is logged by the market in on_asset_damage — no further dispatch.
+            Event::DeductibleEroded { .. } => {}
+
+            // CapitalDistributed is logged directly by the insurer in on_year_end — no further dispatch.
+            Event::CapitalDistributed { .. } => {}
+
+            // YearEndCapital, SolvencyRatioReported and ExpensesPaid are logged directly by the
+            // insurer in on_year_end — no further dispatch.
+            Event::YearEndCapital { .. } | Event::SolvencyRatioReported { .. } | Event::ExpensesPaid { .. } => {}
+
+            // RegulatoryIntervention is logged by Regulator::review at YearEnd, which has
+            // already applied the action — no further dispatch.
+            Event::RegulatoryIntervention { .. } => {}
+        }
+    }
 
     fn handle_year_start(&mut self, day: Day, year: Year) {
         // Reset annual accumulators used for the entry-criterion loss ratio.
@@ -527,15 +1528,46 @@ impl Simulation {
         for insurer in &mut self.insurers {
             insurer.on_year_start();
         }
+        if let Some(growth) = self.config.inflation.as_ref().filter(|_| year.0 > 1).map(|i| i.asset_growth) {
+            for insurer in &mut self.insurers {
+                insurer.index_capital_base(1.0 + growth);
+            }
+        }
+        if let Some(config) = self.config.exposure_growth.as_ref().filter(|_| year.0 > 1) {
+            for t in &config.territories {
+                let Some(territory) = self.territories.get(&t.territory) else { continue };
+                self.log.push(SimEvent {
+                    day,
+                    seq: None,
+                    event: Event::ExposureGrowth {
+                        year,
+                        territory,
+                        annual_growth: t.annual_growth,
+                        index: config.index(&t.territory, year),
+                    },
+                });
+            }
+        }
+
+        self.publish_interest_rate(day, year);
+        self.draw_reserving_factor();
+        for insurer in &mut self.insurers {
+            if let Some(event) = insurer.set_underwriting_plan(year) {
+                self.log.push(SimEvent { day, seq: None, event });
+            }
+        }
+
+        self.purchase_reinsurance(day);
 
         // Year 1 only: schedule CoverageRequested for each insured, spread over first 180 days.
         // Subsequent years: renewals are triggered by approaching PolicyExpired instead.
         if year.0 == 1 {
-            let n = self.broker.insureds.len();
-            let coverage_events: Vec<(Day, InsuredId, Risk)> = self
-                .broker
-                .insureds
-                .iter()
+            // Stagger in insured-id order across all brokers' segments.
+            let mut insureds: Vec<&Insured> = self.brokers.iter().flat_map(|b| &b.insureds).collect();
+            insureds.sort_by_key(|i| i.id);
+            let n = insureds.len();
+            let coverage_events: Vec<(Day, InsuredId, Risk)> = insureds
+                .into_iter()
                 .enumerate()
                 .map(|(i, insured)| {
                     let offset = if n > 1 { i as u64 * 180 / n as u64 } else { 0 };
@@ -544,52 +1576,142 @@ impl Simulation {
                 .collect();
 
             for (d, insured_id, risk) in coverage_events {
-                self.schedule(d, Event::CoverageRequested { insured_id, risk });
+                self.schedule_coverage_request(d, insured_id, risk);
             }
         }
 
+        self.schedule_year_attritional(year);
+
         // Schedule catastrophe loss events (Poisson draw for the year).
         if !self.config.disable_cats {
             let loss_events = perils::schedule_loss_events(
                 &self.config.catastrophe,
                 year,
-                &mut self.rng,
+                self.streams.get(Stream::Cats),
                 &mut self.next_event_id,
             );
             for (d, e) in loss_events {
-                self.schedule(d, e);
+                self.enqueue(d, e);
+            }
+        }
+
+        // Injected stress events draw nothing, so the background draws above are unchanged.
+        self.schedule_scenario_events(year);
+
+        // Draw the year's investment return; applied the day before YearEnd so it feeds the
+        // year-end distribution decision.
+        self.schedule_investment_return(year);
+
+        // Quarterly reporting: the last day of each quarter, Q4 scheduled ahead of YearEnd.
+        if self.config.quarterly_reporting {
+            for quarter in 1..=4u8 {
+                let day = Day::year_start(year).offset(quarter as u64 * Day::DAYS_PER_YEAR / 4 - 1);
+                let event = Event::QuarterEnd { year, quarter, written_premium: 0, claims_incurred: 0, capital: 0 };
+                self.enqueue(day, event);
             }
         }
 
         // Schedule YearEnd.
-        self.schedule(Day::year_end(year), Event::YearEnd { year });
+        self.enqueue(Day::year_end(year), Event::YearEnd { year });
+    }
+
+    /// YearStart: each writing insurer places its cat XoL for the year on the PML of its
+    /// in-force book, in insurer order (see `ReinsuranceConfig`).
+    fn purchase_reinsurance(&mut self, day: Day) {
+        let (Some(config), Some(reinsurance)) = (self.config.reinsurance.as_ref(), self.reinsurance.as_mut()) else {
+            return;
+        };
+        let cat = &self.config.catastrophe;
+        let mut purchased = vec![];
+        for insurer in self.insurers.iter().filter(|i| !i.insolvent && !i.runoff) {
+            let pml = perils::occurrence_pml(cat, &insurer.territory_aggregates, config.return_period);
+            purchased.extend(reinsurance.purchase(day, insurer.id, insurer.capital.max(0) as u64, pml, config));
+        }
+        for (d, e) in purchased {
+            self.enqueue(d, e);
+        }
     }
 
     fn handle_year_end(&mut self, day: Day, year: Year) {
+        // Brokers publish their annual market intelligence before their accumulators reset.
+        // The market-wide decline rate (response-weighted) gates entry below.
+        let mut market_decline_rate = None;
+        if self.config.market_intelligence.is_some() {
+            let intel: Vec<(Day, Event)> =
+                self.brokers.iter().map(|b| b.market_intelligence(day, year)).collect();
+            let (responses, declines) = intel.iter().fold((0.0, 0.0), |(r, d), (_, e)| match e {
+                Event::MarketIntelligence { quote_responses, decline_rate, .. } => {
+                    (r + *quote_responses as f64, d + decline_rate * *quote_responses as f64)
+                }
+                _ => (r, d),
+            });
+            market_decline_rate = Some(if responses > 0.0 { declines / responses } else { 0.0 });
+            for (d, e) in intel {
+                self.enqueue(d, e);
+            }
+        }
+
         // Decay broker relationship scores at year boundary (before insurer on_year_end).
-        self.broker.on_year_end();
+        for broker in &mut self.brokers {
+            broker.on_year_end();
+        }
 
         // Decay post-loss demand uplift for all insureds.
-        for insured in &mut self.broker.insureds {
+        for insured in self.brokers.iter_mut().flat_map(|b| &mut b.insureds) {
             insured.on_year_end();
         }
 
         // Update each insurer's expected_loss_fraction via EWMA from this year's experience.
         // Also detect zombies (capital > 0 but max_line < min policy size) and mark them insolvent.
         // Collect emitted events before scheduling to avoid conflicting mutable borrows.
+        // The smallest policy is the smallest asset at this year's value.
+        let smallest_asset = match self.config.portfolio {
+            Some(_) => self
+                .brokers
+                .iter()
+                .flat_map(|b| &b.insureds)
+                .map(Insured::base_sum_insured)
+                .min()
+                .unwrap_or(ASSET_VALUE),
+            None => ASSET_VALUE,
+        };
+        let min_sum_insured = match self.config.inflation.as_ref() {
+            Some(i) => (smallest_asset as f64 * i.asset_index(year)).round() as u64,
+            None => smallest_asset,
+        };
+        if self.config.pml_reporting.is_some() {
+            self.report_pmls(day);
+        }
+        let unearned = self.market.unearned_premium(day);
+        for insurer in &mut self.insurers {
+            insurer.unearned_premium = unearned.get(&insurer.id).copied().unwrap_or(0);
+        }
         let year_end_events: Vec<(Day, Event)> = self
             .insurers
             .iter_mut()
-            .flat_map(|insurer| insurer.on_year_end(day, ASSET_VALUE))
+            .flat_map(|insurer| insurer.on_year_end(day, min_sum_insured))
             .collect();
+        let overhead_paid: u64 = year_end_events
+            .iter()
+            .map(|(_, ev)| match ev {
+                Event::ExpensesPaid { overhead, .. } => *overhead,
+                _ => 0,
+            })
+            .sum();
         for (d, ev) in year_end_events {
-            self.schedule(d, ev);
+            self.enqueue(d, ev);
         }
 
         // ── Entry criterion ───────────────────────────────────────────────────
-        let expense_ratio = self.config.insurers.first()
-            .map(|ic| ic.expense_ratio)
-            .unwrap_or(0.344);
+        // Under an expense split the market expense ratio is acquisition plus the overheads
+        // actually paid, spread over the year's premium.
+        let expense_ratio = match &self.config.expenses {
+            Some(e) if self.year_premium_written > 0 => {
+                e.acquisition_fraction() + overhead_paid as f64 / self.year_premium_written as f64
+            }
+            Some(e) => e.acquisition_fraction(),
+            None => self.config.insurers.first().map(|ic| ic.expense_ratio).unwrap_or(0.344),
+        } + self.config.brokerage.as_ref().map_or(0.0, |b| b.commission_fraction());
         let lr = if self.year_premium_written > 0 {
             self.year_claims_settled as f64 / self.year_premium_written as f64
         } else {
@@ -604,8 +1726,9 @@ impl Simulation {
         // ── AP/TP market factor ────────────────────────────────────────────────
         // Reflects where the market clears relative to the actuarial floor.
         // < 1.0 = soft market (AP below TP); > 1.0 = hard market.
-        // Insufficient history (warmup) → neutral (1.0).
+        // Insufficient history (warmup) → neutral (1.0). Frozen pricing clears at TP: 1.0.
         self.market_ap_tp_factor = match self.cr_ewma {
+            _ if self.config.pricing_mode == PricingMode::Frozen => 1.0,
             None => 1.0,
             Some(ewma_cr) => {
                 let cr_signal = (ewma_cr - 1.0_f64).clamp(-0.10, 0.80);
@@ -613,6 +1736,125 @@ impl Simulation {
             }
         };
 
+        if self.config.rate_index.is_some() {
+            self.publish_rate_index(day, year);
+        }
+
+        // ── Recapitalisation ───────────────────────────────────────────────────
+        // Before run-off, so a completed raise can keep an insurer above its exit floor.
+        // One draw per candidate, in insurer order.
+        if let Some(recap) = &self.config.recapitalisation {
+            use rand::Rng as _;
+            let mut raised = vec![];
+            for insurer in &mut self.insurers {
+                if insurer.is_recap_candidate(recap)
+                    && self.streams.get(Stream::Market).random::<f64>() < recap.recap_probability
+                {
+                    raised.extend(insurer.raise_capital(day, recap));
+                }
+            }
+            for (d, e) in raised {
+                self.enqueue(d, e);
+            }
+        }
+
+        // ── Regulatory review ──────────────────────────────────────────────────
+        // After recapitalisation, so a completed raise counts towards the solvency ratio.
+        let interventions = self.regulator.review(day, &mut self.insurers);
+        for (d, e) in interventions {
+            self.enqueue(d, e);
+        }
+
+        // ── Voluntary run-off ──────────────────────────────────────────────────
+        // Evaluated after this year's AP/TP update so re-entry responds to the hardening
+        // immediately. One re-entry draw per run-off insurer, in insurer order. Insurers under
+        // a regulatory intervention neither exit nor re-enter voluntarily.
+        if let Some(runoff) = &self.config.runoff {
+            use rand::Rng as _;
+            let mut runoff_events = vec![];
+            for insurer in &mut self.insurers {
+                if self.regulator.is_intervening(insurer.id) {
+                    continue;
+                }
+                let draw = if insurer.runoff && !insurer.insolvent {
+                    self.streams.get(Stream::Market).random::<f64>()
+                } else {
+                    1.0
+                };
+                runoff_events.extend(insurer.evaluate_runoff(day, runoff, self.market_ap_tp_factor, draw));
+            }
+            // Soft-market exit: after enough consecutive soft year-ends the weakest remaining
+            // writers withdraw, leaving at least one. No draws.
+            if let Some(soft) = &runoff.soft_market_exit {
+                if self.market_ap_tp_factor < soft.ap_tp_floor {
+                    self.soft_market_years += 1;
+                } else {
+                    self.soft_market_years = 0;
+                }
+                if self.soft_market_years >= soft.consecutive_years {
+                    self.soft_market_years = 0;
+                    let mut writers: Vec<usize> = (0..self.insurers.len())
+                        .filter(|&i| {
+                            let ins = &self.insurers[i];
+                            !ins.insolvent && !ins.runoff && !self.regulator.is_intervening(ins.id)
+                        })
+                        .collect();
+                    match soft.ranking {
+                        SoftMarketExitRanking::WorstCombinedRatio => writers.sort_by(|&a, &b| {
+                            let cr = |i: usize| self.insurers[i].own_cr_ewma().unwrap_or(f64::NEG_INFINITY);
+                            cr(b).total_cmp(&cr(a))
+                        }),
+                        SoftMarketExitRanking::SmallestCapital => {
+                            writers.sort_by_key(|&i| self.insurers[i].capital)
+                        }
+                    }
+                    let writing = self.insurers.iter().filter(|i| !i.insolvent && !i.runoff).count();
+                    let exits = (soft.max_exits as usize).min(writing.saturating_sub(1));
+                    for i in writers.into_iter().take(exits) {
+                        runoff_events.extend(self.insurers[i].exit_soft_market(day));
+                    }
+                }
+            }
+            let exited: Vec<InsurerId> = runoff_events
+                .iter()
+                .filter_map(|(_, e)| match e {
+                    Event::InsurerExited { insurer_id, .. } => Some(*insurer_id),
+                    _ => None,
+                })
+                .collect();
+            for (d, e) in runoff_events {
+                self.enqueue(d, e);
+            }
+            for seller_id in exited {
+                self.transfer_portfolio(day, seller_id);
+            }
+        }
+
+        // This year's treaties lapse; the reinsurers' experience moves next year's price.
+        if let (Some(config), Some(reinsurance)) = (self.config.reinsurance.as_ref(), self.reinsurance.as_mut()) {
+            reinsurance.on_year_end(config);
+        }
+
+        // ── Cat bond issuance ──────────────────────────────────────────────────
+        // After this year's AP/TP update, so issuance answers the hardening. One draw per
+        // writing insurer without a live bond, in insurer order.
+        if let Some(ils_config) = &self.config.ils {
+            use rand::Rng as _;
+            let p = ils::issuance_probability(ils_config, self.market_ap_tp_factor);
+            let mut issued = vec![];
+            for insurer in &self.insurers {
+                if insurer.insolvent || insurer.runoff || self.cat_bonds.has_live_bond(insurer.id) {
+                    continue;
+                }
+                if self.streams.get(Stream::Market).random::<f64>() < p {
+                    issued.extend(self.cat_bonds.issue(day, insurer.id, insurer.capital.max(0) as u64, ils_config));
+                }
+            }
+            for (d, e) in issued {
+                self.enqueue(d, e);
+            }
+        }
+
         // Entry fires when market prices above technical (AP/TP > threshold).
         // Capital enters when expected returns exceed the cost of capital — the
         // empirically observed mechanism (Bermuda classes 1993, 2001, 2006).
@@ -623,16 +1865,28 @@ impl Simulation {
             let cooldown_ok = self.last_entry_year
                 .map(|y| year.0.saturating_sub(y) >= 1)
                 .unwrap_or(true);
-            if self.market_ap_tp_factor > AP_TP_ENTRY_THRESHOLD && cooldown_ok {
-                self.spawn_new_insurer(day, year);
+            // With market intelligence, entrants also need brokers to report unmet demand.
+            let demand_ok = match (&self.config.market_intelligence, market_decline_rate) {
+                (Some(mi), Some(rate)) => rate >= mi.entry_min_decline_rate,
+                _ => true,
+            };
+            if self.market_ap_tp_factor > AP_TP_ENTRY_THRESHOLD && cooldown_ok && demand_ok {
+                match self.entry_capitals(year, self.market_ap_tp_factor - AP_TP_ENTRY_THRESHOLD) {
+                    Some(capitals) => {
+                        for capital in capitals {
+                            self.spawn_new_insurer(day, year, Some(capital));
+                        }
+                    }
+                    None => self.spawn_new_insurer(day, year, None),
+                }
             }
         }
 
         // ── Sensitivity distribution snapshot ─────────────────────────────────
-        // Compute mean/std of sensitivity parameters across active (non-insolvent) insurers.
+        // Compute mean/std of sensitivity parameters across active (writing) insurers.
         // Stored in sensitivity_by_year for post-simulation reporting.
         {
-            let active: Vec<_> = self.insurers.iter().filter(|i| !i.insolvent).collect();
+            let active: Vec<_> = self.insurers.iter().filter(|i| !i.insolvent && !i.runoff).collect();
             if !active.is_empty() {
                 let n = active.len() as f64;
                 let cr_mean  = active.iter().map(|i| i.cr_sensitivity()).sum::<f64>()  / n;
@@ -654,11 +1908,591 @@ impl Simulation {
         let total_years = self.config.warmup_years + self.config.years;
         if year.0 < total_years {
             let next = Year(year.0 + 1);
-            self.schedule(Day::year_start(next), Event::YearStart { year: next });
+            self.enqueue(Day::year_start(next), Event::YearStart { year: next });
+        }
+    }
+
+    /// Sell an exiting insurer's in-force lines and renewal rights to the best-capitalised
+    /// writing insurer at the price from `transfer::value_portfolio`, settled between the two
+    /// capitals. Skipped when the seller has no in-force lines, no insurer is writing, or the
+    /// paying side cannot meet the price. Reserves on reported claims stay with the seller.
+    fn transfer_portfolio(&mut self, day: Day, seller_id: InsurerId) {
+        let Some(config) = self.config.portfolio_transfer.as_ref() else {
+            return;
+        };
+        let Some(buyer_idx) = self
+            .insurers
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.id != seller_id && !i.insolvent && !i.runoff)
+            .max_by_key(|(_, i)| (i.capital, Reverse(i.id.0)))
+            .map(|(idx, _)| idx)
+        else {
+            return;
+        };
+        let Some(seller_idx) = self.insurers.iter().position(|i| i.id == seller_id) else {
+            return;
+        };
+        let policy_ids = self.market.policies_written_by(seller_id);
+        if policy_ids.is_empty() {
+            return;
+        }
+
+        let buyer = &self.insurers[buyer_idx];
+        let lines: Vec<TransferLine> = policy_ids
+            .iter()
+            .map(|id| {
+                let policy = &self.market.policies[id];
+                let share = policy.panel.iter().find(|&&(i, _)| i == seller_id).map_or(0.0, |&(_, s)| s);
+                TransferLine {
+                    premium: (policy.premium as f64 * share).round() as u64,
+                    expected_annual_loss: buyer.expected_loss(&policy.risk) * share,
+                    unexpired_fraction: policy.expire_day.0.saturating_sub(day.0) as f64
+                        / Day::DAYS_PER_YEAR as f64,
+                }
+            })
+            .collect();
+        let valuation = transfer::value_portfolio(&lines, buyer.expense_ratio(), config);
+        let payer = if valuation.price >= 0 { buyer_idx } else { seller_idx };
+        if self.insurers[payer].capital < valuation.price.abs() {
+            return;
+        }
+
+        let buyer_id = buyer.id;
+        self.market.transfer_lines(seller_id, buyer_id, &policy_ids);
+        let seller = &mut self.insurers[seller_idx];
+        let ceded = seller.cede_policies(&policy_ids);
+        seller.settle_portfolio_transfer(valuation.price);
+        let retained_reserves = seller.outstanding_reserves();
+        let buyer = &mut self.insurers[buyer_idx];
+        buyer.assume_policies(&ceded);
+        buyer.settle_portfolio_transfer(-valuation.price);
+        for broker in &mut self.brokers {
+            broker.transfer_relationship(seller_id, buyer_id);
+        }
+        self.enqueue(
+            day,
+            Event::PortfolioTransferred(Box::new(PortfolioTransfer {
+                seller_id,
+                buyer_id,
+                policies: policy_ids.len() as u32,
+                unearned_premium: valuation.unearned_premium,
+                expected_losses: valuation.expected_losses,
+                renewal_premium: valuation.renewal_premium,
+                franchise_value: valuation.franchise_value,
+                retained_reserves,
+                price: valuation.price,
+            })),
+        );
+    }
+
+    /// Draw this insurer's turnaround for a quote request arriving on `day` and report whether
+    /// it misses the broker's deadline. Always false when `quote_latency` is not configured.
+    fn quote_turnaround_exceeded(&mut self, day: Day, insurer_id: InsurerId) -> bool {
+        use rand_distr::{Distribution as _, Exp};
+
+        let Some(latency) = self.config.quote_latency.as_ref() else {
+            return false;
+        };
+        let Some(insurer) = self.insurers.iter_mut().find(|i| i.id == insurer_id) else {
+            return false;
+        };
+        let workload = insurer.record_quote_request(day, latency.window_days) as f64;
+        let mean = latency.base_turnaround_days * (1.0 + workload / latency.capacity_per_window);
+        if mean <= 0.0 {
+            return false;
+        }
+        let turnaround = Exp::new(1.0 / mean)
+            .expect("invalid turnaround rate")
+            .sample(self.streams.get(Stream::Broker));
+        turnaround > latency.deadline_days
+    }
+
+    /// Premium finance: draw whether the insured defaults on any instalment after the deposit
+    /// and, on the first default, schedule `PremiumDefaulted` on that instalment's due day.
+    /// Due days are `bind_day + k × 360 / instalments`, so every default precedes expiry.
+    /// Returns the defaulted instalment: those before it are paid.
+    fn schedule_premium_default(&mut self, bind_day: Day, policy_id: PolicyId) -> Option<u32> {
+        use rand::Rng as _;
+
+        let pf = self.config.premium_finance.as_ref()?;
+        let insured_id = self.market.policies.get(&policy_id).map(|p| p.insured_id)?;
+        let (n, p) = (pf.instalments.max(1), pf.default_probability);
+        let interval = Day::DAYS_PER_YEAR / n as u64;
+        let rng = self.streams.get(Stream::Broker);
+        let defaulted = (1..n).find(|_| rng.random::<f64>() < p);
+        if let Some(instalment) = defaulted {
+            self.enqueue(
+                bind_day.offset(interval * instalment as u64),
+                Event::PremiumDefaulted { policy_id, insured_id, instalment },
+            );
         }
+        defaulted
     }
 
-    pub(crate) fn spawn_new_insurer(&mut self, day: Day, year: Year) {
+    /// Payment terms: schedule `PremiumReceived` for the first `paid` of `instalments` equal
+    /// instalments of `premium`, each `settlement_delay_days` after it falls due. Instalment `k`
+    /// falls due `k × 360 / instalments` days after `due_day`.
+    fn schedule_premium_receipts(
+        &mut self,
+        due_day: Day,
+        policy_id: PolicyId,
+        insured_id: InsuredId,
+        premium: u64,
+        instalments: u32,
+        paid: u32,
+    ) {
+        let Some(delay) = self.config.payment_terms.as_ref().map(|pt| pt.settlement_delay_days) else {
+            return;
+        };
+        let n = instalments as u64;
+        let interval = Day::DAYS_PER_YEAR / n;
+        for instalment in 0..paid {
+            let k = instalment as u64;
+            let amount = premium * (k + 1) / n - premium * k / n;
+            self.enqueue(
+                due_day.offset(interval * k + delay),
+                Event::PremiumReceived { policy_id, insured_id, instalment, amount },
+            );
+        }
+    }
+
+    /// Quota rationing: answer `insurer_id`'s batched lead requests, highest priority first,
+    /// against its cat headroom now. Ties go to the earlier submission.
+    fn close_lead_batch(&mut self, day: Day, insurer_id: InsurerId) {
+        let Some(priority) = self.config.rationing.as_ref().map(|r| r.priority) else { return };
+        let batch = self.lead_batches.remove(&insurer_id).unwrap_or_default();
+        let factor = self.market_ap_tp_factor;
+        let Some(insurer) = self.insurers.iter().find(|i| i.id == insurer_id) else { return };
+        let mut keyed: Vec<(f64, (SubmissionId, InsuredId, Risk))> = batch
+            .into_iter()
+            .map(|request| {
+                let key = match priority {
+                    RationingPriority::RelationshipScore => self
+                        .insured_broker
+                        .get(&request.1)
+                        .and_then(|&b| self.brokers[b].score_of(insurer_id))
+                        .unwrap_or(0.0),
+                    RationingPriority::RateAdequacy => {
+                        insurer.underwriter_premium(&request.2, factor) as f64 / request.2.max_claim().max(1) as f64
+                    }
+                };
+                (key, request)
+            })
+            .collect();
+        keyed.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.0.cmp(&b.1.0)));
+        let batch: Vec<_> = keyed.into_iter().map(|(_, request)| request).collect();
+        let headroom = insurer.cat_headroom().unwrap_or(0);
+        let events = insurer.ration_lead_quotes(day, &batch, factor);
+        if let Some(Event::LeadQuoteBatchClosed { requests, cat_headroom, .. }) =
+            self.log.last_mut().map(|last| &mut last.event)
+        {
+            *requests = batch.len() as u32;
+            *cat_headroom = headroom;
+        }
+        for (d, e) in events {
+            self.enqueue(d, e);
+        }
+    }
+
+    /// Value index of an asset in `territory` in `year`: inflation's asset index times the
+    /// territory's exposure growth index. None when neither trend is configured.
+    fn asset_index(&self, territory: Territory, year: Year) -> Option<f64> {
+        let inflation = self.config.inflation.as_ref().map(|i| i.asset_index(year));
+        let name = self.territories.name(territory).unwrap_or_default();
+        let growth = self.config.exposure_growth.as_ref().map(|g| g.index(name, year));
+        match (inflation, growth) {
+            (None, None) => None,
+            (i, g) => Some(i.unwrap_or(1.0) * g.unwrap_or(1.0)),
+        }
+    }
+
+    /// Record an insured's coverage request day and schedule the request. Under asset or
+    /// exposure growth the insured is first revalued to the request's year, and requests cover
+    /// at that value.
+    fn schedule_coverage_request(&mut self, day: Day, insured_id: InsuredId, risk: Risk) {
+        let risk = match self.asset_index(risk.territory, day.year()) {
+            Some(index) => match self.insured_mut(insured_id) {
+                Some(insured) => {
+                    insured.revalue(index);
+                    insured.risk.clone()
+                }
+                None => risk,
+            },
+            None => risk,
+        };
+        self.record_exposure_day(day, insured_id, &risk);
+        self.enqueue(day, Event::CoverageRequested { insured_id, risk: Box::new(risk) });
+    }
+
+    /// Record the day an insured enters (or, under multi-year terms, reviews) its cover for the
+    /// year. Days landing in a year whose YearStart pass has already run get their attritional
+    /// losses drawn now, unless the insured was already exposed in that year.
+    fn record_exposure_day(&mut self, day: Day, insured_id: InsuredId, risk: &Risk) {
+        let year = day.year();
+        let previous = self.coverage_request_day.insert(insured_id, day);
+        if year <= self.attritional_year && previous.is_none_or(|d| d.year() != year) {
+            let attritional = perils::trended_attritional(&self.config.attritional, self.config.inflation.as_ref(), year);
+            let multiplier = self.insured(insured_id).map_or(1.0, Insured::attritional_multiplier);
+            let mut att = perils::schedule_attritional_losses_for_insured(
+                insured_id, risk, day, multiplier, self.streams.get(Stream::Attritional), &attritional, &self.territories,
+            );
+            if let Some(large) = &self.config.large_loss {
+                let exposure = [(insured_id, risk, day, multiplier)];
+                att.extend(perils::schedule_large_losses(&exposure, self.streams.get(Stream::Attritional), large));
+            }
+            if let Some(casualty) = &self.config.casualty {
+                let exposure = [(insured_id, risk, day, multiplier)];
+                att.extend(perils::schedule_casualty_losses(&exposure, self.streams.get(Stream::Attritional), casualty));
+            }
+            for (d, e) in att {
+                self.enqueue(d, e);
+            }
+        }
+    }
+
+    fn multi_year_terms(&self) -> bool {
+        self.config.policy_terms.as_ref().is_some_and(|t| t.term_years > 1)
+    }
+
+    /// Multi-year terms: schedule the renewal window of `policy_id` that falls on `day` — a
+    /// `PolicyAnniversary` while term remains after it, otherwise the insured's renewal request.
+    /// Either way the insured's exposure day is recorded, as for an annual renewal.
+    fn schedule_renewal_window(&mut self, day: Day, policy_id: PolicyId) {
+        let Some(policy) = self.market.policies.get(&policy_id) else {
+            return;
+        };
+        let (insured_id, premium, sum_insured) = (policy.insured_id, policy.premium, policy.risk.sum_insured);
+        let term_remains = day.offset(QUOTING_CHAIN_DAYS) < policy.expire_day;
+        let Some(risk) = self.insured(insured_id).map(|i| i.risk.clone()) else {
+            return;
+        };
+        if term_remains {
+            self.record_exposure_day(day, insured_id, &risk);
+            self.enqueue(day, Event::PolicyAnniversary { policy_id, insured_id, premium, sum_insured });
+        } else {
+            self.schedule_coverage_request(day, insured_id, risk);
+        }
+    }
+
+    /// Multi-year terms: why a policy ends at its anniversary, if it does. Insurer-initiated
+    /// when a panel insurer is insolvent; insured-initiated when the lead now quotes the risk
+    /// more than `rate_drop_threshold` below the locked premium.
+    fn anniversary_cancellation(
+        &self,
+        panel: &[(InsurerId, f64)],
+        risk: &Risk,
+        premium: u64,
+    ) -> Option<CancellationReason> {
+        let terms = self.config.policy_terms.as_ref()?;
+        let insurer = |id: InsurerId| self.insurers.iter().find(|i| i.id == id);
+        if panel.iter().any(|&(id, _)| insurer(id).is_some_and(|i| i.insolvent)) {
+            return Some(CancellationReason::InsurerInsolvent);
+        }
+        let quote = insurer(panel.first()?.0)?.underwriter_premium(risk, self.market_ap_tp_factor);
+        ((quote as f64) < premium as f64 * (1.0 - terms.rate_drop_threshold)).then_some(CancellationReason::RateDrop)
+    }
+
+    /// YearStart pass: draw the year's attritional, large and casualty losses for every insured
+    /// with a coverage request in `year`, in insured-id order, each exposed from its request day.
+    fn schedule_year_attritional(&mut self, year: Year) {
+        self.attritional_year = year;
+        let mut exposures: Vec<(InsuredId, &Risk, Day, f64)> = self
+            .brokers
+            .iter()
+            .flat_map(|b| &b.insureds)
+            .filter_map(|i| {
+                let day = *self.coverage_request_day.get(&i.id)?;
+                (day.year() == year).then_some((i.id, &i.risk, day, i.attritional_multiplier()))
+            })
+            .collect();
+        exposures.sort_by_key(|&(id, _, _, _)| id);
+        let attritional = perils::trended_attritional(&self.config.attritional, self.config.inflation.as_ref(), year);
+        let mut att = perils::schedule_attritional_losses(
+            &exposures,
+            self.streams.get(Stream::Attritional),
+            &attritional,
+            &self.territories,
+        );
+        if let Some(large) = &self.config.large_loss {
+            att.extend(perils::schedule_large_losses(&exposures, self.streams.get(Stream::Attritional), large));
+        }
+        if let Some(casualty) = &self.config.casualty {
+            att.extend(perils::schedule_casualty_losses(&exposures, self.streams.get(Stream::Attritional), casualty));
+        }
+        for (d, e) in att {
+            self.enqueue(d, e);
+        }
+    }
+
+    /// Schedule this year's `ScenarioConfig` events as ordinary `LossEvent`s, in config order.
+    /// Injected even with `disable_cats`, which only switches off the stochastic draw.
+    fn schedule_scenario_events(&mut self, year: Year) {
+        let Some(scenario) = self.config.scenario.as_ref() else {
+            return;
+        };
+        let mut injected = vec![];
+        for e in scenario.events.iter().filter(|e| e.year == year.0) {
+            let Some(territory) = self.territories.get(&e.territory) else { continue };
+            let damage_fraction = match e.severity {
+                ScenarioSeverity::DamageFraction(f) => f,
+                ScenarioSeverity::ReturnPeriod(rp) => {
+                    pml_damage_fraction_compound(&self.config.catastrophe.event_classes, rp).min(1.0)
+                }
+            };
+            let event_id = self.next_event_id;
+            self.next_event_id += 1;
+            injected.push((
+                Day::year_start(year).offset(e.day_of_year),
+                Event::LossEvent {
+                    event_id,
+                    peril: Peril::WindstormAtlantic,
+                    territory,
+                    damage_fraction,
+                    footprint: vec![],
+                },
+            ));
+        }
+        for (d, e) in injected {
+            self.enqueue(d, e);
+        }
+    }
+
+    /// Log each solvent insurer's modelled occurrence PMLs on its in-force cat aggregate and,
+    /// with `use_in_scr`, hand it the 1-in-200 loss for the SCR (see `PmlReportingConfig`).
+    fn report_pmls(&mut self, day: Day) {
+        let Some(use_in_scr) = self.config.pml_reporting.as_ref().map(|p| p.use_in_scr) else { return };
+        let cat = &self.config.catastrophe;
+        let reports: Vec<(usize, u64, u64, u64)> = self
+            .insurers
+            .iter()
+            .enumerate()
+            .filter(|(_, ins)| !ins.insolvent)
+            .map(|(i, ins)| {
+                let pml = |rp| perils::occurrence_pml(cat, &ins.territory_aggregates, rp);
+                (i, pml(100.0), pml(200.0), pml(250.0))
+            })
+            .collect();
+        for (i, rp_100, rp_200, rp_250) in reports {
+            let insurer = &mut self.insurers[i];
+            if use_in_scr {
+                insurer.set_modelled_pml(rp_200);
+            }
+            let insurer_id = insurer.id;
+            self.enqueue(day, Event::PmlReported { insurer_id, rp_100, rp_200, rp_250 });
+        }
+    }
+
+    /// Price the rate index basket with every writing insurer at the current AP/TP factor and
+    /// publish the mean rate on line (see `RateIndexConfig`).
+    fn publish_rate_index(&mut self, day: Day, year: Year) {
+        let Some(config) = self.config.rate_index.as_ref() else { return };
+        let basket: Vec<(Territory, u64, f64)> = if !config.basket.is_empty() {
+            config.basket.iter().filter_map(|r| Some((self.territories.get(&r.territory)?, r.sum_insured, r.weight))).collect()
+        } else {
+            // Cat territories lead the table; without any, id 0 is the "US-SE" fallback.
+            let n = self.config.catastrophe.territories.len().max(1);
+            (0..n).map(|i| (Territory(i as u16), ASSET_VALUE, 1.0)).collect()
+        };
+        let index = self.config.inflation.as_ref().map_or(1.0, |i| i.asset_index(year));
+        let risks: Vec<(Risk, f64)> = basket
+            .into_iter()
+            .map(|(territory, sum_insured, weight)| {
+                let sum_insured = ((sum_insured as f64 * index).round() as u64).max(1);
+                let risk = Risk {
+                    sum_insured,
+                    territory,
+                    perils_covered: vec![Peril::WindstormAtlantic, Peril::Attritional],
+                    attachment: 0,
+                    limit: sum_insured,
+                };
+                (risk, weight.max(0.0))
+            })
+            .collect();
+        let total_weight: f64 = risks.iter().map(|(_, w)| w).sum();
+        let writers: Vec<&Insurer> = self.insurers.iter().filter(|i| !i.insolvent && !i.runoff).collect();
+        if writers.is_empty() || total_weight <= 0.0 {
+            return;
+        }
+        let rate = writers
+            .iter()
+            .map(|ins| {
+                risks
+                    .iter()
+                    .map(|(risk, w)| {
+                        w * ins.underwriter_premium(risk, self.market_ap_tp_factor) as f64 / risk.sum_insured as f64
+                    })
+                    .sum::<f64>()
+                    / total_weight
+            })
+            .sum::<f64>()
+            / writers.len() as f64;
+        let base = *self.rate_index_base.get_or_insert(rate);
+        let event = Event::RateIndexPublished {
+            year,
+            rate_on_line: rate,
+            index: if base > 0.0 { 100.0 * rate / base } else { 100.0 },
+            insurers: writers.len() as u32,
+        };
+        self.enqueue(day, event);
+    }
+
+    /// Step the short rate (when configured), hand it to every insurer before anything is
+    /// priced this year, and log `InterestRatePublished`.
+    fn publish_interest_rate(&mut self, day: Day, year: Year) {
+        use rand_distr::{Distribution as _, StandardNormal};
+
+        let Some(rates) = self.config.interest_rates.as_ref() else {
+            return;
+        };
+        let rate = match self.interest_rate {
+            None => rates.initial_rate,
+            Some(current) => {
+                let shock: f64 = StandardNormal.sample(self.streams.get(Stream::Market));
+                rates.step(current, shock)
+            }
+        };
+        self.interest_rate = Some(rate);
+        for insurer in &mut self.insurers {
+            insurer.set_interest_rate(rate);
+        }
+        self.log.push(SimEvent { day, seq: None, event: Event::InterestRatePublished { year, rate } });
+    }
+
+    /// Draw the year's casualty reserving factor, LogNormal(−σ²/2, σ) with mean 1 (when
+    /// casualty is configured with a non-zero `reserve_sigma`).
+    fn draw_reserving_factor(&mut self) {
+        use rand_distr::{Distribution as _, LogNormal};
+
+        let Some(sigma) = self.config.casualty.as_ref().map(|c| c.reserve_sigma).filter(|&s| s > 0.0) else {
+            return;
+        };
+        if let Ok(dist) = LogNormal::new(-sigma * sigma / 2.0, sigma) {
+            self.reserving_factor = dist.sample(self.streams.get(Stream::Market));
+        }
+    }
+
+    /// Draw the year's market-wide investment return (when configured): a crash year with
+    /// probability `crash_probability`, otherwise Normal(mean_return, volatility). With
+    /// interest rates the normal-year mean sits `mean_return` above the short rate, and without
+    /// an investment config capital earns the short rate itself.
+    fn schedule_investment_return(&mut self, year: Year) {
+        use rand::Rng as _;
+        use rand_distr::{Distribution as _, Normal};
+
+        let (rate, crash) = match (self.config.investment.as_ref(), self.interest_rate) {
+            (None, None) => return,
+            (None, Some(short_rate)) => (short_rate, false),
+            (Some(inv), short_rate) => {
+                let rng = self.streams.get(Stream::Market);
+                let crash = rng.random::<f64>() < inv.crash_probability;
+                let rate = if crash {
+                    inv.crash_return
+                } else {
+                    Normal::new(inv.mean_return + short_rate.unwrap_or(0.0), inv.volatility.max(0.0))
+                        .expect("investment volatility must be finite")
+                        .sample(rng)
+                };
+                (rate, crash)
+            }
+        };
+        self.enqueue(
+            Day(Day::year_end(year).0 - 1),
+            Event::InvestmentReturnDrawn { year, rate, crash },
+        );
+    }
+
+    /// The broker that owns this insured's segment.
+    /// Brokerage: the placing broker earns its commission on premium just written.
+    fn schedule_brokerage(&mut self, day: Day, policy_id: PolicyId, premium: u64) {
+        let Some(brokerage) = &self.config.brokerage else { return };
+        let amount = brokerage.commission(premium);
+        let broker_id = self
+            .market
+            .policies
+            .get(&policy_id)
+            .and_then(|p| self.insured_broker.get(&p.insured_id))
+            .map(|&idx| self.brokers[idx].id);
+        if let Some(broker_id) = broker_id
+            && amount > 0
+        {
+            self.enqueue(day, Event::BrokerageEarned { broker_id, policy_id, premium, amount });
+        }
+    }
+
+    /// Quote comparison only: price the bound risk at every writing insurer's own rate and log
+    /// the quotes beside the winning premium. Pricing reads insurer state without changing it.
+    fn schedule_quote_comparison(&mut self, day: Day, policy_id: PolicyId, premium: u64) {
+        if !self.config.quote_comparison {
+            return;
+        }
+        let Some(risk) = self.market.policies.get(&policy_id).map(|p| &p.risk) else { return };
+        let quotes: Vec<(InsurerId, u64)> = self
+            .insurers
+            .iter()
+            .filter(|i| !i.insolvent && !i.runoff)
+            .map(|i| (i.id, i.underwriter_premium(risk, self.market_ap_tp_factor)))
+            .collect();
+        if quotes.is_empty() {
+            return;
+        }
+        let mut prices: Vec<u64> = quotes.iter().map(|&(_, q)| q).collect();
+        prices.sort_unstable();
+        let mid = prices.len() / 2;
+        let median_quote = if prices.len().is_multiple_of(2) { (prices[mid - 1] + prices[mid]).div_ceil(2) } else { prices[mid] };
+        self.enqueue(day, Event::QuoteComparisonRecorded { policy_id, premium, median_quote, quotes });
+    }
+
+    fn broker_of_mut(&mut self, insured_id: InsuredId) -> Option<&mut Broker> {
+        let idx = *self.insured_broker.get(&insured_id)?;
+        self.brokers.get_mut(idx)
+    }
+
+    /// Look up an insured across all broker segments.
+    pub fn insured(&self, insured_id: InsuredId) -> Option<&Insured> {
+        let idx = *self.insured_broker.get(&insured_id)?;
+        self.brokers[idx].insureds.iter().find(|i| i.id == insured_id)
+    }
+
+    /// True if the regulator restricts `insurer_id` to renewals and it is not on the panel of
+    /// `insured_id`'s current policy.
+    fn regulatory_restricted(&self, insurer_id: InsurerId, insured_id: InsuredId) -> bool {
+        if !self.regulator.is_restricted(insurer_id) {
+            return false;
+        }
+        let carries = self
+            .market
+            .insured_active_policies
+            .get(&insured_id)
+            .and_then(|policy_id| self.market.policies.get(policy_id))
+            .is_some_and(|policy| policy.panel.iter().any(|&(id, _)| id == insurer_id));
+        !carries
+    }
+
+    fn insured_mut(&mut self, insured_id: InsuredId) -> Option<&mut Insured> {
+        let idx = *self.insured_broker.get(&insured_id)?;
+        self.brokers[idx].insureds.iter_mut().find(|i| i.id == insured_id)
+    }
+
+    /// Entrant capitals for a hard-market year under `entry_capacity`: the aggregate new
+    /// capital for this AP/TP gap split equally over as many template-sized entrants as it
+    /// buys (at least one, at most `max_entrants`). None = entry is not sized.
+    fn entry_capitals(&self, year: Year, gap: f64) -> Option<Vec<i64>> {
+        let ec = self.config.entry_capacity.as_ref()?;
+        let aggregate = (ec.capital_per_gap * gap * self.year_premium_written as f64)
+            .min(ec.max_capital as f64)
+            .max(0.0);
+        let template = self.config.insurers.first().map(|t| t.initial_capital).unwrap_or(15_000_000_000);
+        let template = match self.config.inflation.as_ref() {
+            Some(i) => template as f64 * i.asset_index(year),
+            None => template as f64,
+        };
+        let n = ((aggregate / template.max(1.0)).ceil() as u32).clamp(1, ec.max_entrants.max(1));
+        Some(vec![(aggregate / n as f64).round() as i64; n as usize])
+    }
+
+    /// Spawn one entrant. `capital` overrides the template or profile capital (entry sizing).
+    pub(crate) fn spawn_new_insurer(&mut self, day: Day, year: Year, capital: Option<i64>) {
         use rand::Rng as _;
 
         let id = InsurerId(self.next_insurer_id);
@@ -681,11 +2515,45 @@ impl Simulation {
                 .unwrap_or((15_000_000_000i64, 0.030, 0.62, 0.05, pml_200 * territory_factor,
                             0.030, 0.3, 0.344, Some(0.30), Some(0.30), 1.0));
 
+        // Under asset growth, entrants bring capital in current-year money.
+        let initial_capital = match self.config.inflation.as_ref() {
+            Some(i) => (initial_capital as f64 * i.asset_index(year)).round() as i64,
+            None => initial_capital,
+        };
+
         // Draw sensitivity parameters from wide uniform distributions.
         // Maximum heterogeneity at entry → selection pressure filters toward equilibrium.
-        let cr_sensitivity       = self.rng.random_range(0.0_f64..2.5);   // U(0.0, 2.5); canonical=1.0
-        let capacity_sensitivity = self.rng.random_range(0.0_f64..0.25);  // U(0.0, 0.25); canonical=0.10
-        let market_weight_floor  = self.rng.random_range(0.0_f64..0.60);  // U(0.0, 0.60); canonical=0.30
+        let rng = self.streams.get(Stream::Pricing);
+        let cr_sensitivity       = rng.random_range(0.0_f64..2.5);   // U(0.0, 2.5); canonical=1.0
+        let capacity_sensitivity = rng.random_range(0.0_f64..0.25);  // U(0.0, 0.25); canonical=0.10
+        let market_weight_floor  = rng.random_range(0.0_f64..0.60);  // U(0.0, 0.60); canonical=0.30
+
+        // A configured business plan overrides capital, pricing and appetite. Drawn after the
+        // sensitivities so runs without profiles consume the stream exactly as before.
+        let profile = self.config.entrant_profiles.as_ref().and_then(|profiles| {
+            let total: f64 = profiles.iter().map(|p| p.weight.max(0.0)).sum();
+            let mut u = rng.random_range(0.0..1.0) * total;
+            profiles.iter().find(|p| {
+                u -= p.weight.max(0.0);
+                u < 0.0
+            })
+        });
+        let (initial_capital, profit_loading, pml_frac, net_line_capacity) = match profile {
+            Some(p) => {
+                let capital = rng.random_range(p.min_capital..=p.max_capital.max(p.min_capital));
+                let capital = match self.config.inflation.as_ref() {
+                    Some(i) => (capital as f64 * i.asset_index(year)).round() as i64,
+                    None => capital,
+                };
+                let pml = p.pml_damage_fraction_override.unwrap_or(pml_200) * territory_factor;
+                (capital, p.profit_loading, pml, p.net_line_capacity)
+            }
+            None => (initial_capital, profit_loading, pml_frac, net_line_capacity),
+        };
+        // The entrant's own view of cat risk, drawn after its profile.
+        let cat_elf = cat_elf * draw_risk_view(self.config.risk_views.as_ref(), rng);
+        let profile = profile.map(|p| p.label.clone());
+        let initial_capital = capital.unwrap_or(initial_capital);
 
         let floor_factor = self.config.insurers.first().map(|t| t.floor_factor).unwrap_or(0.85);
         let payout_ratio = self.config.insurers.first().map(|t| t.payout_ratio).unwrap_or(0.70);
@@ -693,26 +2561,43 @@ impl Simulation {
             .map(|t| t.distribution_floor_multiple).unwrap_or(1.5);
         let leader_participation_cap = self.config.insurers.first()
             .map(|t| t.leader_participation_cap).unwrap_or(0.25);
-        let insurer = Insurer::new(
+        let mut insurer = Insurer::new(
             id, initial_capital, attritional_elf, cat_elf, target_loss_ratio,
             ewma_credibility, expense_ratio, profit_loading, net_line_capacity, scf, pml_frac,
             depletion_sensitivity, capacity_sensitivity, cr_sensitivity, market_weight_floor,
             floor_factor, payout_ratio, distribution_floor_multiple, leader_participation_cap,
-        );
+        )
+        .with_territory_limits(self.config.territory_limits.clone(), pml_frac / territory_factor, &self.territories)
+        .with_partial_lines(self.config.partial_lines.clone())
+        .with_solvency(self.config.solvency.clone())
+        .with_expenses(self.config.expenses.clone())
+        .with_brokerage(self.config.brokerage.clone())
+        .with_payment_terms(self.config.payment_terms.clone())
+        .with_pricing_mode(self.config.pricing_mode)
+        .with_discount_credit(self.config.discount_years())
+        .with_growth_target(self.config.growth_targets.clone().filter(|g| g.applies_to(id)))
+        .with_casualty_elf(self.config.casualty.as_ref().map_or(0.0, CasualtyConfig::expected_loss_fraction))
+        .with_capital_ledger(self.config.capital_ledger);
+        insurer.set_interest_rate(self.interest_rate.unwrap_or(0.0));
         let initial_capital_u64 = initial_capital.max(0) as u64;
 
         self.insurers.push(insurer);
-        self.broker.add_insurer(id);
+        for broker in &mut self.brokers {
+            broker.add_insurer(id);
+        }
         self.last_entry_year = Some(year.0);
 
         self.log.push(SimEvent {
             day,
+            seq: None,
             event: Event::InsurerEntered {
                 insurer_id: id,
                 initial_capital: initial_capital_u64,
                 cr_sensitivity,
                 capacity_sensitivity,
                 market_weight_floor,
+                cat_elf,
+                profile,
             },
         });
     }
@@ -720,9 +2605,14 @@ impl Simulation {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use super::*;
-    use crate::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, SimulationConfig};
-    use crate::events::Event;
+    use crate::config::{
+        AttritionalConfig, BrokerConfig, CatConfig, ClaimsDevelopmentConfig, CatEventClass, DemandConfig, ExposureGrowthConfig, ExposureSnapshotConfig, HealthConfig, IlsConfig, InflationConfig, InsurerConfig, InterestRateConfig, InvestmentConfig, MarketIntelligenceConfig, PartialLineConfig, PolicyTermConfig, PortfolioConfig, AggregateDeductibleConfig, SolvencyConfig, RegulatorConfig, LoyaltyConfig, RolloverConfig, ExpenseConfig, PaymentTermsConfig, PortfolioTransferConfig, PremiumFinanceConfig, PricingMode, RationingConfig, RationingPriority, ScenarioConfig, ScenarioEvent, QuoteLatencyConfig, RecapitalisationConfig, RetentionConfig,
+        RunoffConfig, SimulationConfig, TerritoryGrowth,
+    };
+    use crate::events::{CancellationBasis, CapitalFlowReason, CatBondIssue, CatBondTrigger, Event, Line, RejectionReason};
 
     fn minimal_config(years: u32, n_insureds: usize) -> SimulationConfig {
         SimulationConfig {
@@ -749,9 +2639,10 @@ mod tests {
                 payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
             }],
             n_insureds,
-            attritional: AttritionalConfig { annual_rate: 2.0, mu: -3.0, sigma: 1.0 },
+            attritional: AttritionalConfig { annual_rate: 2.0, mu: -3.0, sigma: 1.0, contagion: 0.0, overrides: vec![] },
             catastrophe: CatConfig {
                 event_classes: vec![CatEventClass {
                     label: "test".to_string(),
@@ -759,18 +2650,68 @@ mod tests {
                     pareto_scale: 0.05,
                     pareto_shape: 1.5,
                     max_damage_fraction: 1.0, // no truncation in tests
+                    footprint: vec![],
                 }],
                 territories: vec!["US-SE".to_string()], // single territory: all insureds hit
+                season: None,
+                clustering: None,
+                catalogue: None,
             },
             quotes_per_submission: None,
             max_rol_mu: 0.0,    // exp(0) = 1.0: all insureds accept all quotes (tests)
             max_rol_sigma: 0.0, // sigma=0: degenerate — everyone gets exp(mu) exactly
             disable_cats: false,
+            brokers: vec![],
+            quote_latency: None,
+            claims_development: None,
+            premium_finance: None,
+            retention: None,
+            investment: None,
+            demand: None,
+            market_intelligence: None,
+            rng: None,
+            runoff: None,
+            portfolio_transfer: None,
+            recapitalisation: None,
+            health: None,
+            exposure_snapshots: None,
+            territory_limits: None,
+            policy_terms: None,
+            inflation: None,
+            ils: None,
+            partial_lines: None,
+            portfolio: None,
+            aggregate_deductible: None,
+            solvency: None,
+            regulator: None,
+            loyalty: None,
+            expenses: None,
+            payment_terms: None,
+            pricing_mode: PricingMode::Adaptive,
+            scenario: None,
+            entrant_profiles: None,
+            entry_capacity: None,
+            brokerage: None,
+            mitigation: None,
+            rate_index: None,
+            pml_reporting: None,
+            large_loss: None,
+            reinsurance: None,
+            exposure_growth: None,
+            rationing: None,
+            rollover: None,
+            interest_rates: None,
+            casualty: None,
+            growth_targets: None,
+            quote_comparison: false,
+            risk_views: None,
+            capital_ledger: false,
+            quarterly_reporting: false,
         }
     }
 
     fn run_sim(config: SimulationConfig) -> Simulation {
-        let mut sim = Simulation::from_config(config);
+        let mut sim = Simulation::from_config(config).unwrap();
         sim.start();
         sim.run();
         sim
@@ -863,56 +2804,17 @@ mod tests {
         // For a single insured, verify the day progression through the chain.
         let sim = run_sim(minimal_config(1, 1));
 
-        let coverage_day = sim
-            .log
-            .iter()
-            .find(|e| matches!(e.event, Event::CoverageRequested { .. }))
-            .map(|e| e.day)
-            .expect("CoverageRequested missing");
-
-        let lead_req_day = sim
-            .log
-            .iter()
-            .find(|e| matches!(e.event, Event::LeadQuoteRequested { .. }))
-            .map(|e| e.day)
-            .expect("LeadQuoteRequested missing");
-
-        let lead_issued_day = sim
-            .log
-            .iter()
-            .find(|e| matches!(e.event, Event::LeadQuoteIssued { .. }))
-            .map(|e| e.day)
-            .expect("LeadQuoteIssued missing");
-
-        let presented_day = sim
-            .log
-            .iter()
-            .find(|e| matches!(e.event, Event::QuotePresented { .. }))
-            .map(|e| e.day)
-            .expect("QuotePresented missing");
-
-        let accepted_day = sim
-            .log
-            .iter()
-            .find(|e| matches!(e.event, Event::QuoteAccepted { .. }))
-            .map(|e| e.day)
-            .expect("QuoteAccepted missing");
-
-        let bound_day = sim
-            .log
-            .iter()
-            .find(|e| matches!(e.event, Event::PolicyBound { .. }))
-            .map(|e| e.day)
-            .expect("PolicyBound missing");
-
-        assert_eq!(lead_req_day.0, coverage_day.0 + 1, "LeadQuoteRequested must be day+1");
-        assert_eq!(lead_issued_day.0, lead_req_day.0, "LeadQuoteIssued same day as LeadQuoteRequested");
-        assert_eq!(presented_day.0, lead_issued_day.0 + 1, "QuotePresented must be day+1");
-        assert_eq!(accepted_day.0, presented_day.0, "QuoteAccepted same day as QuotePresented");
-        assert_eq!(bound_day.0, accepted_day.0 + 1, "PolicyBound must be day+1");
+        let chain = expect_events!(sim.log, [
+            Event::CoverageRequested { .. },
+            Event::LeadQuoteRequested { .. } => +1,
+            Event::LeadQuoteIssued { .. } => +0,
+            Event::QuotePresented { .. } => +1,
+            Event::QuoteAccepted { .. } => +0,
+            Event::PolicyBound { .. } => +1,
+        ]);
         assert_eq!(
-            bound_day.0,
-            coverage_day.0 + 3,
+            chain[5].day.0,
+            chain[0].day.0 + 3,
             "total cycle CoverageRequested→PolicyBound must be 3 days"
         );
     }
@@ -1061,6 +2963,7 @@ mod tests {
                 payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
             })
             .collect();
         let sim = run_sim(config);
@@ -1174,6 +3077,7 @@ mod tests {
             payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
         }];
         let sim = run_sim(config);
 
@@ -1368,6 +3272,7 @@ mod tests {
                 payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
             },
             InsurerConfig {
                 id: InsurerId(2),
@@ -1389,6 +3294,7 @@ mod tests {
                 payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
             },
         ];
 
@@ -1477,8 +3383,12 @@ mod tests {
                 pareto_scale: 0.05,
                 pareto_shape: 1.5,
                 max_damage_fraction: 1.0,
+                footprint: vec![],
             }],
             territories: vec!["US-SE".to_string()],
+            season: None,
+            clustering: None,
+            catalogue: None,
         };
         let pml_200 = pml_damage_fraction_compound(&cat_cfg.event_classes, 200.0);
 
@@ -1510,8 +3420,10 @@ mod tests {
         let sum_insured = 5_000_000_000u64; // 50M USD
         let risk = Risk {
             sum_insured,
-            territory: "US-SE".to_string(),
+            territory: Territory(0),
             perils_covered: vec![crate::events::Peril::WindstormAtlantic],
+            attachment: 0,
+            limit: sum_insured,
         };
 
         // Helper to load insurer with `n` cat policies then attempt one more quote.
@@ -1555,7 +3467,7 @@ mod tests {
         //
         // Use a config that triggers entry: market hard enough (cr_ewma > threshold).
         // We directly call spawn_new_insurer twice via a synthetic simulation.
-        use crate::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, SimulationConfig};
+        use crate::config::{AttritionalConfig, CatConfig, CatEventClass, InsurerConfig, PricingMode, SimulationConfig};
 
         let config = SimulationConfig {
             seed: 1,
@@ -1581,9 +3493,10 @@ mod tests {
                 payout_ratio: 0.0,
                 distribution_floor_multiple: 1.0,
                 leader_participation_cap: 1.0,
+                appetite: vec![],
             }],
             n_insureds: 5,
-            attritional: AttritionalConfig { annual_rate: 2.0, mu: -3.0, sigma: 1.0 },
+            attritional: AttritionalConfig { annual_rate: 2.0, mu: -3.0, sigma: 1.0, contagion: 0.0, overrides: vec![] },
             catastrophe: CatConfig {
                 event_classes: vec![CatEventClass {
                     label: "test".to_string(),
@@ -1591,22 +3504,72 @@ mod tests {
                     pareto_scale: 0.05,
                     pareto_shape: 1.5,
                     max_damage_fraction: 1.0,
+                    footprint: vec![],
                 }],
                 territories: vec!["US-SE".to_string()],
+                season: None,
+                clustering: None,
+                catalogue: None,
             },
             quotes_per_submission: None,
             max_rol_mu: 0.0,
             max_rol_sigma: 0.0,
             disable_cats: false,
+            brokers: vec![],
+            quote_latency: None,
+            claims_development: None,
+            premium_finance: None,
+            retention: None,
+            investment: None,
+            demand: None,
+            market_intelligence: None,
+            rng: None,
+            runoff: None,
+            portfolio_transfer: None,
+            recapitalisation: None,
+            health: None,
+            exposure_snapshots: None,
+            territory_limits: None,
+            policy_terms: None,
+            inflation: None,
+            ils: None,
+            partial_lines: None,
+            portfolio: None,
+            aggregate_deductible: None,
+            solvency: None,
+            regulator: None,
+            loyalty: None,
+            expenses: None,
+            payment_terms: None,
+            pricing_mode: PricingMode::Adaptive,
+            scenario: None,
+            entrant_profiles: None,
+            entry_capacity: None,
+            brokerage: None,
+            mitigation: None,
+            rate_index: None,
+            pml_reporting: None,
+            large_loss: None,
+            reinsurance: None,
+            exposure_growth: None,
+            rationing: None,
+            rollover: None,
+            interest_rates: None,
+            casualty: None,
+            growth_targets: None,
+            quote_comparison: false,
+            risk_views: None,
+            capital_ledger: false,
+            quarterly_reporting: false,
         };
 
         let day = Day(360);
         let year = Year(1);
 
-        let mut sim = Simulation::from_config(config);
+        let mut sim = Simulation::from_config(config).unwrap();
         // Call spawn_new_insurer twice to get two entrants with different random draws.
-        sim.spawn_new_insurer(day, year);
-        sim.spawn_new_insurer(day, year);
+        sim.spawn_new_insurer(day, year, None);
+        sim.spawn_new_insurer(day, year, None);
 
         // The last two insurers are the entrants.
         let n = sim.insurers.len();
@@ -1620,6 +3583,68 @@ mod tests {
         );
     }
 
+    /// Entrants draw a business plan by weight, take its capital range and pricing, and record
+    /// the profile's label on their InsurerEntered event.
+    #[test]
+    fn entrants_draw_their_business_plan_from_the_profiles() {
+        use crate::config::EntrantProfile;
+
+        let profile = |label: &str, weight: f64, capital: i64, profit_loading: f64| EntrantProfile {
+            label: label.to_string(),
+            weight,
+            min_capital: capital,
+            max_capital: capital * 2,
+            profit_loading,
+            pml_damage_fraction_override: None,
+            net_line_capacity: Some(0.5),
+        };
+        let mut config = minimal_config(1, 5);
+        config.entrant_profiles =
+            Some(vec![profile("naive", 1.0, 1_000_000_000, 0.0), profile("disciplined", 1.0, 50_000_000_000, 0.10)]);
+        let mut sim = Simulation::from_config(config).unwrap();
+        for _ in 0..40 {
+            sim.spawn_new_insurer(Day(360), Year(1), None);
+        }
+
+        let mut seen = std::collections::BTreeMap::new();
+        for e in sim.log.iter().filter(|e| e.day.0 > 0) {
+            let Event::InsurerEntered { insurer_id, initial_capital, profile: Some(label), .. } = &e.event else {
+                panic!("entrant without a profile: {:?}", e.event)
+            };
+            let insurer = sim.insurers.iter().find(|i| i.id == *insurer_id).unwrap();
+            let (min, loading) = if label == "naive" { (1_000_000_000, 0.0) } else { (50_000_000_000, 0.10) };
+            assert!((min..=2 * min).contains(&(*initial_capital as i64)), "{label} capital {initial_capital}");
+            assert_eq!(insurer.profit_loading(), loading);
+            *seen.entry(label.clone()).or_insert(0) += 1;
+        }
+        assert_eq!(seen.len(), 2, "both profiles drawn over 40 entries: {seen:?}");
+    }
+
+    /// Sized entry scales the new capital with the AP/TP gap and the year's premium, splits it
+    /// into template-sized entrants and respects both caps.
+    #[test]
+    fn entry_capital_scales_with_the_hard_market_gap() {
+        use crate::config::EntryCapacityConfig;
+
+        let mut config = minimal_config(1, 5); // template capital 1B USD
+        config.entry_capacity =
+            Some(EntryCapacityConfig { capital_per_gap: 10.0, max_entrants: 3, max_capital: 250_000_000_000 });
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.year_premium_written = 100_000_000_000;
+        // gap 0.05 → 50B: one entrant; gap 0.15 → 150B: two of 75B; gap 0.5 → capped at 250B over 3.
+        assert_eq!(sim.entry_capitals(Year(1), 0.05), Some(vec![50_000_000_000]));
+        assert_eq!(sim.entry_capitals(Year(1), 0.15), Some(vec![75_000_000_000; 2]));
+        let capped = sim.entry_capitals(Year(1), 0.5).unwrap();
+        assert_eq!(capped.len(), 3);
+        assert!(capped.iter().sum::<i64>().abs_diff(250_000_000_000) <= 1);
+
+        sim.spawn_new_insurer(Day(359), Year(1), Some(capped[0]));
+        let Some(Event::InsurerEntered { initial_capital, .. }) = sim.log.last().map(|e| &e.event) else {
+            panic!("expected InsurerEntered")
+        };
+        assert_eq!(*initial_capital as i64, capped[0]);
+    }
+
     #[test]
     fn insured_reservation_prices_are_heterogeneous() {
         // With sigma > 0, insureds must receive distinct LogNormal draws.
@@ -1629,8 +3654,8 @@ mod tests {
             max_rol_sigma: 0.40,
             ..minimal_config(1, 20)
         };
-        let sim = Simulation::from_config(config);
-        let rols: Vec<f64> = sim.broker.insureds.iter().map(|i| i.base_max_rol()).collect();
+        let sim = Simulation::from_config(config).unwrap();
+        let rols: Vec<f64> = sim.brokers.iter().flat_map(|b| &b.insureds).map(|i| i.base_max_rol()).collect();
         assert!(rols.iter().all(|&r| r > 0.0), "all draws must be positive: {rols:?}");
         let first = rols[0];
         assert!(
@@ -1647,10 +3672,2147 @@ mod tests {
             max_rol_sigma: 0.0,
             ..minimal_config(1, 5)
         };
-        let sim = Simulation::from_config(config);
+        let sim = Simulation::from_config(config).unwrap();
         assert!(
-            sim.broker.insureds.iter().all(|i| (i.base_max_rol() - 0.25).abs() < 1e-9),
+            sim.brokers.iter().flat_map(|b| &b.insureds).all(|i| (i.base_max_rol() - 0.25).abs() < 1e-9),
             "sigma=0 must assign exp(mu)=0.25 to every insured"
         );
     }
+
+    #[test]
+    fn multiple_brokers_segment_insureds_and_route_submissions() {
+        // 2 brokers, 6 insureds: broker 0 owns odd ids, broker 1 owns even ids.
+        let config = SimulationConfig {
+            brokers: vec![
+                BrokerConfig { id: BrokerId(10), quotes_per_submission: None },
+                BrokerConfig { id: BrokerId(11), quotes_per_submission: Some(1) },
+            ],
+            ..minimal_config(1, 6)
+        };
+        let sim = run_sim(config);
+        assert_eq!(sim.brokers.len(), 2);
+        assert_eq!(sim.brokers[0].insureds.len(), 3);
+        assert_eq!(sim.brokers[1].insureds.len(), 3);
+
+        let mut submission_ids = HashSet::new();
+        let mut routed = 0;
+        for e in sim.log.iter() {
+            if let Event::SubmissionRouted { submission_id, insured_id, broker_id } = e.event {
+                let expected = if insured_id.0 % 2 == 1 { BrokerId(10) } else { BrokerId(11) };
+                assert_eq!(broker_id, expected, "insured {insured_id:?} routed by wrong broker");
+                assert!(submission_ids.insert(submission_id), "duplicate {submission_id:?}");
+                routed += 1;
+            }
+        }
+        assert_eq!(routed, 6, "one SubmissionRouted per year-1 CoverageRequested");
+
+        let bound = sim.log.iter().filter(|e| matches!(e.event, Event::PolicyBound { .. })).count();
+        assert_eq!(bound, 6, "every insured must be bound through its own broker");
+        assert!(sim.brokers.iter().all(|b| b.score_of(InsurerId(1)).unwrap() > 0.0));
+    }
+
+    #[test]
+    fn quote_latency_overload_produces_turnaround_declines() {
+        // Capacity of 1 request per 30 days with a 1-day deadline: 20 insureds swamp the
+        // single insurer, so most quotes miss the deadline.
+        let latency = |deadline_days| QuoteLatencyConfig {
+            base_turnaround_days: 0.5,
+            capacity_per_window: 1.0,
+            window_days: 30,
+            deadline_days,
+        };
+        let count_turnaround = |sim: &Simulation| {
+            sim.log
+                .iter()
+                .filter(|e| {
+                    matches!(
+                        e.event,
+                        Event::LeadQuoteDeclined { reason: DeclineReason::TurnaroundExceeded, .. }
+                    )
+                })
+                .count()
+        };
+
+        let slow = run_sim(SimulationConfig {
+            quote_latency: Some(latency(1.0)),
+            ..minimal_config(1, 20)
+        });
+        assert!(count_turnaround(&slow) > 0, "overloaded insurer must miss some deadlines");
+
+        let instant = run_sim(minimal_config(1, 20));
+        assert_eq!(count_turnaround(&instant), 0, "no latency config → no turnaround declines");
+
+        let lenient = run_sim(SimulationConfig {
+            quote_latency: Some(latency(f64::INFINITY)),
+            ..minimal_config(1, 20)
+        });
+        assert_eq!(count_turnaround(&lenient), 0, "unbounded deadline → every quote arrives");
+    }
+
+    #[test]
+    fn claims_development_replaces_instant_settlement() {
+        let config = SimulationConfig {
+            claims_development: Some(ClaimsDevelopmentConfig {
+                payment_pattern: vec![0.4, 0.3, 0.2, 0.1],
+                payment_interval_days: 360,
+                initial_reserve_ratio: 0.8,
+            }),
+            ..minimal_config(3, 3)
+        };
+        let sim = run_sim(config);
+        let count = |f: fn(&Event) -> bool| sim.log.iter().filter(|e| f(&e.event)).count();
+        let reported = count(|e| matches!(e, Event::ClaimReported { .. }));
+        assert!(reported > 0, "covered losses must be reported");
+        assert_eq!(count(|e| matches!(e, Event::ClaimSettled { .. })), 0);
+        assert_eq!(count(|e| matches!(e, Event::ReserveEstablished { .. })), reported);
+        assert!(count(|e| matches!(e, Event::ClaimPaid { .. })) > reported, "claims pay in instalments");
+        assert!(sim.insurers[0].outstanding_reserves() > 0, "late-year claims are still open at horizon");
+    }
+
+    #[test]
+    fn interest_rates_step_yearly_earn_on_capital_and_discount_long_tail_pricing() {
+        let with_rates = |interest_rates| SimulationConfig {
+            // Mean payment lag 0.3 × 1 + 0.2 × 2 + 0.1 × 3 = 1 year.
+            claims_development: Some(ClaimsDevelopmentConfig {
+                payment_pattern: vec![0.4, 0.3, 0.2, 0.1],
+                payment_interval_days: 360,
+                initial_reserve_ratio: 1.0,
+            }),
+            interest_rates,
+            ..minimal_config(3, 3)
+        };
+        let rates = InterestRateConfig {
+            initial_rate: 0.02,
+            long_run_rate: 0.06,
+            mean_reversion: 0.5,
+            volatility: 0.0,
+            discount_credit: 1.0,
+        };
+        let sim = run_sim(with_rates(Some(rates)));
+        let published: Vec<f64> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event { Event::InterestRatePublished { rate, .. } => Some(rate), _ => None })
+            .collect();
+        assert_eq!(published.len(), 3);
+        for (rate, expected) in published.iter().zip([0.02, 0.04, 0.05]) {
+            assert!((rate - expected).abs() < 1e-12, "half the gap to 6% closes each year: {published:?}");
+        }
+        // No investment config: capital earns the short rate itself.
+        let drawn: Vec<f64> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event { Event::InvestmentReturnDrawn { rate, crash: false, .. } => Some(rate), _ => None })
+            .collect();
+        assert_eq!(drawn, published);
+
+        let first_atp = |sim: &Simulation| {
+            sim.log.iter().find_map(|e| match e.event { Event::LeadQuoteIssued { atp, .. } => Some(atp as f64), _ => None }).unwrap()
+        };
+        let baseline = run_sim(with_rates(None));
+        assert!((first_atp(&sim) - first_atp(&baseline) / 1.02).abs() <= 1.0, "a year's discount at 2%");
+    }
+
+    #[test]
+    fn premium_default_cancels_cover_after_grace_period() {
+        let config = SimulationConfig {
+            premium_finance: Some(PremiumFinanceConfig {
+                instalments: 4,
+                default_probability: 1.0,
+                grace_days: 30,
+                basis: CancellationBasis::ProRata,
+            }),
+            ..minimal_config(3, 3)
+        };
+        let sim = run_sim(config);
+
+        // Every policy defaults on its first instalment after the deposit (+90) and is
+        // cancelled at the end of the grace period (+30).
+        let chain = expect_events!(sim.log, [
+            Event::PolicyBound { .. },
+            Event::PremiumDefaulted { instalment: 1, .. } => +90,
+            Event::PolicyCancelled { bad_debt, .. } if bad_debt > 0 => +30,
+        ]);
+        let Event::PolicyBound { policy_id, .. } = chain[0].event else { unreachable!() };
+        let cancel_day = chain[2].day;
+        assert!(
+            !sim.log.iter().any(|e| e.day > cancel_day
+                && matches!(e.event, Event::ClaimSettled { policy_id: p, .. } if p == policy_id)),
+            "no claims may route to a cancelled policy"
+        );
+        let bound = sim.log.iter().filter(|e| matches!(e.event, Event::PolicyBound { .. })).count();
+        let cancelled = sim.log.iter().filter(|e| matches!(e.event, Event::PolicyCancelled { .. })).count();
+        assert_eq!(cancelled, bound);
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn payment_terms_receive_each_paid_instalment_after_the_settlement_delay() {
+        let config = SimulationConfig {
+            premium_finance: Some(PremiumFinanceConfig {
+                instalments: 4,
+                default_probability: 0.1,
+                grace_days: 30,
+                basis: CancellationBasis::AbInitio,
+            }),
+            payment_terms: Some(PaymentTermsConfig { settlement_delay_days: 60 }),
+            ..minimal_config(3, 10)
+        };
+        let sim = run_sim(config);
+
+        // Per policy: bind day, premium and the instalment it defaulted on; and its receipts.
+        let mut policies: HashMap<PolicyId, (Day, u64, Option<u32>)> = HashMap::new();
+        let mut receipts: HashMap<PolicyId, Vec<(Day, u32, u64)>> = HashMap::new();
+        for e in &sim.log {
+            match e.event {
+                Event::PolicyBound { policy_id, premium, .. } => {
+                    policies.insert(policy_id, (e.day, premium, None));
+                }
+                Event::PremiumDefaulted { policy_id, instalment, .. } => {
+                    policies.get_mut(&policy_id).unwrap().2 = Some(instalment);
+                }
+                Event::PremiumReceived { policy_id, instalment, amount, .. } => {
+                    receipts.entry(policy_id).or_default().push((e.day, instalment, amount));
+                }
+                _ => {}
+            }
+        }
+        let horizon = sim.log.last().unwrap().day;
+        let mut defaults = 0;
+        for (policy_id, (bind_day, premium, defaulted)) in &policies {
+            let receipts = receipts.remove(policy_id).unwrap_or_default();
+            let paid = defaulted.unwrap_or(4);
+            defaults += defaulted.is_some() as usize;
+            for &(day, instalment, _) in &receipts {
+                assert!(instalment < paid, "a defaulted instalment is never received");
+                assert_eq!(day, bind_day.offset(90 * instalment as u64 + 60));
+            }
+            if bind_day.offset(90 * 3 + 60) <= horizon {
+                let received: u64 = receipts.iter().map(|&(_, _, amount)| amount).sum();
+                assert_eq!(received, premium * paid as u64 / 4);
+            }
+        }
+        assert!(defaults > 0);
+        assert!(
+            sim.insurers.iter().map(Insurer::premium_receivable).sum::<u64>() > 0,
+            "premium written late in the final year is still to be collected"
+        );
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn crash_years_charge_every_insurer_before_year_end() {
+        let config = SimulationConfig {
+            investment: Some(InvestmentConfig {
+                mean_return: 0.04,
+                volatility: 0.0,
+                crash_probability: 1.0,
+                crash_return: -0.25,
+            }),
+            ..minimal_config(3, 3)
+        };
+        let sim = run_sim(config);
+
+        expect_events!(sim.log, [
+            Event::InvestmentReturnDrawn { year: Year(1), crash: true, .. },
+            Event::InvestmentIncome { amount, .. } if amount < 0 => +0,
+            Event::YearEnd { year: Year(1) } => +1,
+        ]);
+        let drawn = sim.log.iter().filter(|e| matches!(e.event, Event::InvestmentReturnDrawn { .. })).count();
+        assert_eq!(drawn, 3, "one return draw per simulated year");
+        assert!(
+            sim.log.iter().all(|e| !matches!(e.event, Event::InvestmentIncome { amount, .. } if amount >= 0)),
+            "a crash year must never credit capital"
+        );
+    }
+
+    #[test]
+    fn runoff_insurer_stops_quoting_but_keeps_settling_claims() {
+        // Threshold 0 sends every insurer that wrote business into run-off at the first YearEnd;
+        // an unreachable re-entry factor keeps it there.
+        let config = SimulationConfig {
+            runoff: Some(RunoffConfig {
+                runoff_cr_threshold: 0.0,
+                capital_exit_floor: 0.0,
+                reentry_ap_tp_factor: f64::INFINITY,
+                reentry_probability: 1.0,
+                soft_market_exit: None,
+            }),
+            ..minimal_config(3, 20)
+        };
+        let sim = run_sim(config);
+
+        let exit_day = sim
+            .log
+            .iter()
+            .find(|e| matches!(e.event, Event::InsurerExited { insurer_id: InsurerId(1), .. }))
+            .map(|e| e.day)
+            .expect("insurer must enter run-off after its first year");
+        assert_eq!(exit_day, Day::year_end(Year(1)));
+        assert!(sim.insurers[0].runoff);
+        let after_exit = sim.log.iter().filter(|e| e.day > exit_day);
+        let (mut quoted, mut settled) = (false, false);
+        for e in after_exit {
+            match e.event {
+                Event::LeadQuoteIssued { .. } | Event::FollowerQuoteIssued { .. } => quoted = true,
+                Event::ClaimSettled { insurer_id: InsurerId(1), .. } => settled = true,
+                _ => {}
+            }
+        }
+        assert!(!quoted, "a run-off insurer must not quote");
+        assert!(settled, "claims on the expiring book must still settle after exit");
+        assert!(!sim.log.iter().any(|e| matches!(e.event, Event::InsurerReEntered { .. })));
+    }
+
+    /// A market held below the soft-market floor sheds its smallest writer every
+    /// `consecutive_years` year-ends, and never its last.
+    #[test]
+    fn prolonged_soft_market_retires_the_smallest_writers() {
+        use crate::config::SoftMarketExitConfig;
+        use crate::events::ExitReason;
+
+        let mut config = SimulationConfig {
+            runoff: Some(RunoffConfig {
+                runoff_cr_threshold: f64::INFINITY,
+                capital_exit_floor: 0.0,
+                reentry_ap_tp_factor: f64::INFINITY,
+                reentry_probability: 0.0,
+                soft_market_exit: Some(SoftMarketExitConfig {
+                    ap_tp_floor: f64::INFINITY,
+                    consecutive_years: 2,
+                    max_exits: 1,
+                    ranking: SoftMarketExitRanking::SmallestCapital,
+                }),
+            }),
+            ..minimal_config(6, 20)
+        };
+        let template = config.insurers[0].clone();
+        config.insurers = [(1, 3), (2, 1), (3, 2)]
+            .map(|(id, scale)| InsurerConfig {
+                id: InsurerId(id),
+                initial_capital: template.initial_capital * scale,
+                ..template.clone()
+            })
+            .to_vec();
+        let sim = run_sim(config);
+
+        let exits: Vec<(Day, InsurerId)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::InsurerExited { insurer_id, reason, .. } => {
+                    assert_eq!(reason, ExitReason::SoftMarket);
+                    Some((e.day, insurer_id))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            exits,
+            [(Day::year_end(Year(2)), InsurerId(2)), (Day::year_end(Year(4)), InsurerId(3))],
+            "smallest first, every second year, and the largest stays"
+        );
+    }
+
+    /// Every placement pays its broker the commission, which the panel bears as extra expense.
+    #[test]
+    fn brokerage_is_earned_on_every_placement() {
+        use crate::config::BrokerageConfig;
+
+        let config = SimulationConfig {
+            brokerage: Some(BrokerageConfig { commission_bps: 2_000 }),
+            ..minimal_config(2, 20)
+        };
+        let base_expense_ratio = config.insurers[0].expense_ratio;
+        let sim = run_sim(config);
+
+        let bound: Vec<u64> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::PolicyBound { premium, .. } => Some(premium),
+                _ => None,
+            })
+            .collect();
+        let earned: Vec<(u64, u64)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::BrokerageEarned { premium, amount, .. } => Some((premium, amount)),
+                _ => None,
+            })
+            .collect();
+        assert!(!bound.is_empty());
+        assert_eq!(earned.iter().map(|&(p, _)| p).collect::<Vec<_>>(), bound);
+        assert!(earned.iter().all(|&(p, a)| a == p / 5));
+        let total: u64 = earned.iter().map(|&(_, a)| a).sum();
+        assert_eq!(sim.brokers.iter().map(|b| b.brokerage_earned).sum::<u64>(), total);
+        assert!((sim.insurers[0].expense_ratio() - (base_expense_ratio + 0.2)).abs() < 1e-12);
+    }
+
+    /// With a trigger every renewal clears, each insured that renews with cover invests once a
+    /// year, and the cut takes hold at the following YearEnd.
+    #[test]
+    fn renewing_insureds_invest_in_mitigation_when_premiums_rise() {
+        use crate::config::MitigationConfig;
+
+        let config = SimulationConfig {
+            mitigation: Some(MitigationConfig {
+                trigger_increase: -1.0,
+                spend_fraction: 0.1,
+                attritional_reduction: 0.2,
+                cat_reduction: 0.2,
+                min_factor: 0.5,
+            }),
+            ..minimal_config(3, 10)
+        };
+        let sim = run_sim(config);
+
+        let invested: Vec<&SimEvent> =
+            sim.log.iter().filter(|e| matches!(e.event, Event::MitigationInvested { .. })).collect();
+        assert!(!invested.is_empty());
+        assert!(invested.iter().all(|e| e.day > Day::year_end(Year(1))), "first-year placements are new business");
+        for insured in sim.brokers.iter().flat_map(|b| &b.insureds) {
+            let n = invested
+                .iter()
+                .filter(|e| matches!(e.event, Event::MitigationInvested { insured_id, .. } if insured_id == insured.id))
+                .count() as i32;
+            let expected = 0.8_f64.powi(n).max(0.5);
+            assert!((insured.cat_damage_factor() - expected).abs() < 1e-12, "{n} investments");
+            assert!((insured.attritional_multiplier() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn rate_index_prices_a_fixed_basket_from_a_base_of_100() {
+        use crate::config::{RateIndexConfig, ReferenceRisk};
+
+        let plain = run_sim(minimal_config(3, 10));
+        let basket = vec![ReferenceRisk { territory: "US-SE".into(), sum_insured: ASSET_VALUE, weight: 1.0 }];
+        let sim = run_sim(SimulationConfig { rate_index: Some(RateIndexConfig { basket }), ..minimal_config(3, 10) });
+
+        let (published, rest): (Vec<_>, Vec<_>) =
+            sim.log.iter().partition(|e| matches!(e.event, Event::RateIndexPublished { .. }));
+        let events = |log: Vec<&SimEvent>| log.into_iter().map(|e| (e.day, e.event.clone())).collect::<Vec<_>>();
+        assert!(events(rest) == events(plain.log.iter().collect()), "pricing the basket must not perturb the run");
+        assert_eq!(published.len(), 3, "one per year");
+        let mut base = None;
+        for (y, e) in published.iter().enumerate() {
+            let Event::RateIndexPublished { year, rate_on_line, index, insurers } = e.event else { unreachable!() };
+            assert_eq!(year, Year(y as u32 + 1));
+            assert_eq!(e.day, Day::year_end(year));
+            assert!(rate_on_line > 0.0 && insurers > 0);
+            let base = *base.get_or_insert(rate_on_line);
+            assert!((index - 100.0 * rate_on_line / base).abs() < 1e-9);
+        }
+        let Event::RateIndexPublished { index, .. } = published[0].event else { unreachable!() };
+        assert_eq!(index, 100.0);
+    }
+
+    #[test]
+    fn health_events_report_engine_state_without_changing_the_run() {
+        let plain = run_sim(minimal_config(2, 20));
+        let monitored = run_sim(SimulationConfig {
+            health: Some(HealthConfig { interval_days: 30 }),
+            ..minimal_config(2, 20)
+        });
+
+        let (health, rest): (Vec<_>, Vec<_>) =
+            monitored.log.iter().partition(|e| matches!(e.event, Event::SimHealth { .. }));
+        assert!(rest.into_iter().eq(plain.log.iter()), "health events must not perturb the run");
+        assert_eq!(health.len(), 23, "one per 30-day boundary in days 30..=690");
+        let mut last_dispatched = 0;
+        for e in health {
+            let Event::SimHealth { queue_depth, events_dispatched, active_policies, .. } = e.event else { unreachable!() };
+            assert_eq!(e.day.0 % 30, 0);
+            assert!(events_dispatched > last_dispatched && queue_depth > 0 && active_policies > 0);
+            last_dispatched = events_dispatched;
+        }
+    }
+
+    #[test]
+    fn scenario_injects_fixed_cats_without_disturbing_the_background_draw() {
+        let split = || Some(crate::config::RngConfig { backend: crate::rng::RngBackend::ChaCha20, split_streams: true, frozen: vec![] });
+        let event = |day_of_year, severity| ScenarioEvent { year: 2, day_of_year, territory: "US-SE".into(), severity };
+        let scenario = ScenarioConfig {
+            events: vec![event(100, ScenarioSeverity::ReturnPeriod(50.0)), event(101, ScenarioSeverity::DamageFraction(0.3))],
+        };
+        let plain = run_sim(SimulationConfig { rng: split(), ..minimal_config(3, 20) });
+        let stressed = run_sim(SimulationConfig { rng: split(), scenario: Some(scenario.clone()), ..minimal_config(3, 20) });
+
+        let cats = |sim: &Simulation| -> Vec<(Day, Territory, f64)> {
+            sim.log
+                .iter()
+                .filter_map(|e| match &e.event {
+                    Event::LossEvent { territory, damage_fraction, .. } => Some((e.day, *territory, *damage_fraction)),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut background = cats(&stressed);
+        let rp50 = pml_damage_fraction_compound(&plain.config.catastrophe.event_classes, 50.0);
+        for injected in [(Day(460), Territory(0), rp50), (Day(461), Territory(0), 0.3)] {
+            let i = background.iter().position(|c| *c == injected).expect("injected event must be logged");
+            background.remove(i);
+        }
+        assert_eq!(background, cats(&plain), "the stochastic cats are drawn as without the scenario");
+        let damaged = stressed.log.iter().filter(|e| e.day == Day(460) && matches!(e.event, Event::AssetDamage { .. })).count();
+        assert_eq!(damaged, 20, "the injected storm strikes every insured in its territory");
+
+        let cat = &plain.config.catastrophe;
+        assert!(scenario.check(cat).is_ok());
+        let unknown = ScenarioConfig { events: vec![ScenarioEvent { territory: "EU".into(), ..event(100, ScenarioSeverity::DamageFraction(0.3)) }] };
+        assert!(unknown.check(cat).is_err());
+        assert!(ScenarioConfig { events: vec![event(360, ScenarioSeverity::DamageFraction(0.3))] }.check(cat).is_err());
+        assert!(ScenarioConfig { events: vec![event(100, ScenarioSeverity::DamageFraction(1.5))] }.check(cat).is_err());
+    }
+
+    #[test]
+    fn frozen_pricing_holds_technical_premium_over_the_same_losses() {
+        let run_mode = |pricing_mode| {
+            run_sim(SimulationConfig {
+                rng: Some(crate::config::RngConfig { backend: crate::rng::RngBackend::ChaCha20, split_streams: true, frozen: vec![] }),
+                pricing_mode,
+                ..minimal_config(4, 20)
+            })
+        };
+        let adaptive = run_mode(PricingMode::Adaptive);
+        let frozen = run_mode(PricingMode::Frozen);
+
+        let losses = |sim: &Simulation| -> Vec<SimEvent> {
+            sim.log.iter().filter(|e| matches!(e.event, Event::LossEvent { .. })).cloned().collect()
+        };
+        assert!(!losses(&adaptive).is_empty());
+        assert_eq!(losses(&adaptive), losses(&frozen), "cat draws must not depend on pricing");
+        let premiums = |sim: &Simulation| -> std::collections::BTreeSet<u64> {
+            sim.log
+                .iter()
+                .filter_map(|e| match e.event {
+                    Event::LeadQuoteIssued { premium, .. } => Some(premium),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(premiums(&frozen).len(), 1, "identical risks, one technical price throughout");
+        assert!(premiums(&adaptive).len() > 1, "adaptive pricing moves with experience");
+        assert_eq!(frozen.market_ap_tp_factor, 1.0);
+    }
+
+    #[test]
+    fn exposure_snapshots_match_the_live_book_without_changing_the_run() {
+        let quarterly = || Some(ExposureSnapshotConfig { interval_days: 90 });
+        let plain = run_sim(minimal_config(2, 20));
+        let snapped = run_sim(SimulationConfig { exposure_snapshots: quarterly(), ..minimal_config(2, 20) });
+
+        let (snapshots, rest): (Vec<_>, Vec<_>) =
+            snapped.log.iter().partition(|e| matches!(e.event, Event::ExposureSnapshot { .. }));
+        assert!(rest.into_iter().eq(plain.log.iter()), "snapshots must not perturb the run");
+        let days: std::collections::BTreeSet<u64> = snapshots.iter().map(|e| e.day.0).collect();
+        assert_eq!(days, (1..=7).map(|q| q * 90).collect(), "one set per quarter in days 90..=630");
+        assert!(snapshots.iter().all(|e| matches!(
+            &e.event,
+            Event::ExposureSnapshot { insurer_id: InsurerId(1), aggregate_sum_insured, .. } if *aggregate_sum_insured > 0
+        )));
+        assert!(crate::analysis::verify_integrity(&snapped.log).is_empty());
+
+        // The seller's lines move to the buyer mid-term; the snapshots follow them.
+        let mut config = SimulationConfig {
+            exposure_snapshots: quarterly(),
+            portfolio_transfer: Some(PortfolioTransferConfig { renewal_multiple: 1.0 }),
+            ..minimal_config(2, 20)
+        };
+        config.insurers.push(InsurerConfig { id: InsurerId(2), ..config.insurers[0].clone() });
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run_until(Day(500));
+        sim.transfer_portfolio(Day(500), InsurerId(1));
+        sim.run();
+        let mismatches: Vec<_> = crate::analysis::verify_integrity(&sim.log)
+            .into_iter()
+            .filter(|v| matches!(v, crate::analysis::IntegrityViolation::ExposureSnapshotMismatch { .. }))
+            .collect();
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+    }
+
+    #[test]
+    fn multi_year_policies_collect_instalments_and_cancel_at_anniversaries() {
+        let run_terms = |rate_drop_threshold| {
+            run_sim(SimulationConfig {
+                policy_terms: Some(PolicyTermConfig { term_years: 3, rate_drop_threshold }),
+                ..minimal_config(4, 10)
+            })
+        };
+        let count = |sim: &Simulation, pred: fn(&Event) -> bool| sim.log.iter().filter(|e| pred(&e.event)).count();
+
+        // Never cancelled: one placement per insured per 3-year term, two instalments in between.
+        let held = run_terms(1.0);
+        assert!(crate::analysis::verify_mechanics(&held.log).is_empty());
+        assert!(crate::analysis::verify_integrity(&held.log).is_empty());
+        let bound = count(&held, |e| matches!(e, Event::PolicyBound { .. }));
+        assert_eq!(bound, 20, "10 insureds × (initial term + renewal in year 4)");
+        assert_eq!(count(&held, |e| matches!(e, Event::PolicyAnniversary { premium, .. } if *premium > 0)), 20);
+        assert_eq!(count(&held, |e| matches!(e, Event::PolicyCancelled { .. })), 0);
+
+        // Threshold −1: the lead always "quotes below" twice the locked premium, so the insured
+        // walks away at the first anniversary and the replacement binds the day cover ends.
+        let shopping = run_terms(-1.0);
+        assert!(crate::analysis::verify_mechanics(&shopping.log).is_empty());
+        assert!(crate::analysis::verify_integrity(&shopping.log).is_empty());
+        let cancellations: Vec<_> = shopping
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::PolicyCancelled { insured_id, reason, .. } => Some((e.day, insured_id, reason)),
+                _ => None,
+            })
+            .collect();
+        assert!(!cancellations.is_empty());
+        for (day, insured_id, reason) in cancellations {
+            assert_eq!(reason, CancellationReason::RateDrop);
+            assert!(
+                shopping.log.iter().any(|e| e.day == day
+                    && matches!(e.event, Event::PolicyBound { insured_id: i, .. } if i == insured_id)),
+                "replacement must bind on the cancellation day"
+            );
+        }
+        assert_eq!(count(&shopping, |e| matches!(e, Event::PolicyAnniversary { premium, .. } if *premium > 0)), 0);
+    }
+
+    #[test]
+    fn industry_loss_estimate_sums_each_occurrences_damage_and_claims() {
+        let mut config = minimal_config(10, 10);
+        config.catastrophe.event_classes[0].annual_frequency = 2.0;
+        let sim = run_sim(config);
+
+        let estimates: Vec<(Day, u64, u64, u64)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::IndustryLossEstimate { event_id, total_gul, insured_loss } => {
+                    Some((e.day, event_id, total_gul, insured_loss))
+                }
+                _ => None,
+            })
+            .collect();
+        let loss_events = sim.log.iter().filter(|e| matches!(e.event, Event::LossEvent { .. })).count();
+        assert_eq!(estimates.len(), loss_events, "one print per occurrence");
+        assert!(estimates.iter().any(|&(_, _, _, insured)| insured > 0));
+
+        for &(day, event_id, total_gul, insured_loss) in &estimates {
+            // Each print follows its LossEvent on the same day.
+            let fired = sim.log.iter().position(|e| matches!(e.event, Event::LossEvent { event_id: id, .. } if id == event_id));
+            let printed = sim.log.iter().position(|e| matches!(e.event, Event::IndustryLossEstimate { event_id: id, .. } if id == event_id));
+            assert!(fired < printed);
+            if estimates.iter().filter(|e| e.0 == day).count() > 1 {
+                continue;
+            }
+            let same_day = sim.log.iter().filter(|e| e.day == day);
+            let (gul, claims) = same_day.fold((0, 0), |(g, c), e| match e.event {
+                Event::AssetDamage { peril: Peril::WindstormAtlantic, ground_up_loss, .. } => (g + ground_up_loss, c),
+                Event::ClaimSettled { peril: Peril::WindstormAtlantic, amount, .. } => (g, c + amount),
+                _ => (g, c),
+            });
+            assert_eq!(total_gul, gul, "event {event_id}");
+            assert_eq!(insured_loss, claims, "event {event_id}");
+        }
+    }
+
+    #[test]
+    fn cat_bonds_pay_sponsor_loss_ahead_of_claims_and_return_the_rest() {
+        let mut config = minimal_config(8, 10);
+        config.catastrophe.event_classes[0].annual_frequency = 2.0;
+        config.ils = Some(IlsConfig {
+            trigger: CatBondTrigger::Indemnity { attachment_capital_fraction: 0.0 },
+            term_years: 2,
+            principal_capital_fraction: 0.5,
+            spread: 0.04,
+            issuance_probability: 1.0,
+            rate_elasticity: 0.0,
+        });
+        let sim = run_sim(config);
+
+        let mut principal: HashMap<u64, u64> = HashMap::new();
+        let mut matured = 0;
+        for (i, e) in sim.log.iter().enumerate() {
+            match e.event {
+                Event::CatBondIssued(ref bond) => {
+                    let CatBondIssue { bond_id, principal: p, premium, maturity_year, .. } = **bond;
+                    assert_eq!(e.day, Day::year_end(e.day.year()), "issued at YearEnd");
+                    assert_eq!(maturity_year, Year(e.day.year().0 + 2));
+                    assert_eq!(premium, (p as f64 * 0.04 * 2.0).round() as u64);
+                    principal.insert(bond_id, p);
+                }
+                Event::CatBondTriggered { bond_id, insurer_id, payout, remaining_capital, .. } => {
+                    assert!(remaining_capital >= payout);
+                    // Fires before any claim that day; with one occurrence that day it pays the
+                    // sponsor's whole cat claim (attachment 0) up to the principal left.
+                    let first_claim = sim.log.iter().position(|c| c.day == e.day && matches!(c.event, Event::ClaimSettled { .. }));
+                    assert!(first_claim.is_none_or(|c| i < c));
+                    let left = principal.get_mut(&bond_id).expect("triggered bond was issued");
+                    let occurrences = sim.log.iter().filter(|l| l.day == e.day && matches!(l.event, Event::LossEvent { .. })).count();
+                    if occurrences == 1 {
+                        let claims: u64 = sim
+                            .log
+                            .iter()
+                            .filter(|c| c.day == e.day)
+                            .filter_map(|c| match c.event {
+                                Event::ClaimSettled { insurer_id: id, peril: Peril::WindstormAtlantic, amount, .. } if id == insurer_id => Some(amount),
+                                _ => None,
+                            })
+                            .sum();
+                        assert_eq!(payout, claims.min(*left), "bond {bond_id} day {}", e.day.0);
+                    }
+                    *left -= payout;
+                }
+                Event::CatBondMatured { bond_id, principal_returned, .. } => {
+                    assert_eq!(Some(principal_returned), principal.remove(&bond_id), "unexhausted principal returns");
+                    matured += 1;
+                }
+                _ => {}
+            }
+        }
+        assert!(matured > 0);
+        assert!(sim.log.iter().any(|e| matches!(e.event, Event::CatBondTriggered { .. })));
+        let stats = crate::analysis::analyse(&sim.log, &crate::batch::initial_capitals(sim.config()), 0.344).1;
+        assert!(stats.iter().any(|s| s.cat_bond_recoveries > 0 && s.cat_bond_premium > 0));
+    }
+
+    #[test]
+    fn reinsurance_is_bought_at_year_start_and_recovers_ahead_of_claims() {
+        use crate::config::ReinsuranceConfig;
+        let mut config = minimal_config(8, 10);
+        config.catastrophe.event_classes[0].annual_frequency = 2.0;
+        config.reinsurance = Some(ReinsuranceConfig {
+            return_period: 200.0,
+            retention_capital_fraction: 0.02,
+            base_rate_on_line: 0.1,
+            target_loss_ratio: 0.5,
+            cycle_weight: 0.5,
+            min_rate_factor: 0.5,
+            max_rate_factor: 3.0,
+            price_elasticity: 1.0,
+        });
+        let sim = run_sim(config);
+
+        let mut limits: HashMap<u64, u64> = HashMap::new();
+        let mut rates: Vec<f64> = vec![];
+        for (i, e) in sim.log.iter().enumerate() {
+            match e.event {
+                Event::ReinsurancePurchased { treaty_id, limit, rate_on_line, premium, .. } => {
+                    assert_eq!(e.day, Day::year_start(e.day.year()), "bought at YearStart");
+                    assert_eq!(premium, (limit as f64 * rate_on_line).round() as u64);
+                    limits.insert(treaty_id, limit);
+                    rates.push(rate_on_line);
+                }
+                Event::ReinsuranceRecovered { treaty_id, amount, remaining_capital, .. } => {
+                    assert!(remaining_capital >= amount);
+                    let first_claim = sim.log.iter().position(|c| c.day == e.day && matches!(c.event, Event::ClaimSettled { .. }));
+                    assert!(first_claim.is_none_or(|c| i < c), "recovery lands before the claims");
+                    let left = limits.get_mut(&treaty_id).expect("recovering treaty was bought");
+                    *left = left.checked_sub(amount).expect("recoveries never exceed the limit");
+                }
+                _ => {}
+            }
+        }
+        assert!(sim.log.iter().any(|e| matches!(e.event, Event::ReinsuranceRecovered { .. })));
+        assert!(rates.windows(2).any(|w| w[0] != w[1]), "the reinsurance price cycles");
+        let stats = crate::analysis::analyse(&sim.log, &crate::batch::initial_capitals(sim.config()), 0.344).1;
+        let hit = stats.iter().max_by_key(|s| s.reinsurance_recoveries).expect("analysed years");
+        assert!(hit.loss_ratio_net() < hit.loss_ratio(), "the heaviest recovery year is better net than gross");
+    }
+
+    #[test]
+    fn partial_lead_lines_keep_their_share_on_the_panel() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 5;
+        config.partial_lines = Some(PartialLineConfig { min_line: 0.05 });
+        let sim = run_sim(config);
+
+        use crate::types::SubmissionId;
+        let mut partial: HashMap<(SubmissionId, InsurerId), f64> = HashMap::new();
+        let mut placed = 0;
+        for e in &sim.log {
+            match &e.event {
+                Event::LeadQuotePartial { submission_id, insurer_id, line_size, .. } => {
+                    assert!(*line_size >= 0.05 && *line_size < 1.0);
+                    partial.insert((*submission_id, *insurer_id), *line_size);
+                }
+                Event::QuotePresented { submission_id, leader_id, panel, .. } => {
+                    if let Some(line) = partial.get(&(*submission_id, *leader_id)) {
+                        assert!(panel.len() > 1, "a partial lead never carries the risk alone");
+                        assert!((panel[0].1 - line).abs() < 1e-9, "lead share is firm: {panel:?} vs {line}");
+                        placed += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(placed > 0, "some partial leads are placed");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn regulator_restricts_weak_insurers_to_their_renewals() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 20;
+        // A heavy premium-risk charge pulls canonical ratios down to the intervention ladder.
+        config.solvency = Some(SolvencyConfig {
+            premium_risk_factor: 3.0,
+            reserve_risk_factor: 0.2,
+            target_ratio: 1.0,
+            min_ratio: 0.0,
+        });
+        config.regulator = Some(RegulatorConfig { restrict_below: 1.0, runoff_below: 0.7, revoke_below: 0.3 });
+        let sim = run_sim(config);
+
+        use crate::events::RegulatoryAction;
+        let mut restricted: HashSet<InsurerId> = HashSet::new();
+        let mut panel_of: HashMap<InsuredId, Vec<InsurerId>> = HashMap::new();
+        let (mut interventions, mut renewals) = (0, 0);
+        for e in &sim.log {
+            match &e.event {
+                Event::RegulatoryIntervention { insurer_id, action } => {
+                    interventions += 1;
+                    if *action == RegulatoryAction::RestrictedToRenewals {
+                        restricted.insert(*insurer_id);
+                    } else {
+                        restricted.remove(insurer_id);
+                    }
+                }
+                Event::PolicyBound { insured_id, panel, .. } => {
+                    panel_of.insert(*insured_id, panel.iter().map(|&(id, _)| id).collect());
+                }
+                Event::LeadQuoteIssued { insured_id, insurer_id, .. }
+                | Event::FollowerQuoteIssued { insured_id, insurer_id, .. }
+                    if restricted.contains(insurer_id) =>
+                {
+                    assert!(
+                        panel_of.get(insured_id).is_some_and(|p| p.contains(insurer_id)),
+                        "restricted insurer {insurer_id:?} quoted new business"
+                    );
+                    renewals += 1;
+                }
+                Event::LeadQuoteDeclined { insurer_id, reason: DeclineReason::RegulatoryRestriction, .. }
+                | Event::FollowerQuoteDeclined { insurer_id, reason: DeclineReason::RegulatoryRestriction, .. } => {
+                    assert!(restricted.contains(insurer_id));
+                }
+                _ => {}
+            }
+        }
+        assert!(interventions > 0 && renewals > 0, "{interventions} interventions, {renewals} renewals");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn loyal_renewals_record_whether_the_insured_stayed_with_its_lead() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 10;
+        config.loyalty = Some(LoyaltyConfig { max_premium_increase: 0.10 });
+        let sim = run_sim(config);
+
+        // Lead of each insured's current cover; an uncovered insured's next placement is new.
+        let mut lead_of: HashMap<InsuredId, InsurerId> = HashMap::new();
+        let mut expected: HashMap<PolicyId, bool> = HashMap::new();
+        let (mut stayed, mut moved) = (0, 0);
+        for e in &sim.log {
+            match &e.event {
+                Event::PolicyBound { policy_id, insured_id, panel, .. } => {
+                    if let Some(expiring) = lead_of.insert(*insured_id, panel[0].0) {
+                        expected.insert(*policy_id, expiring == panel[0].0);
+                    }
+                }
+                Event::QuoteRejected { insured_id, .. } | Event::SubmissionDropped { insured_id, .. } => {
+                    lead_of.remove(insured_id);
+                }
+                Event::PolicyRenewed { policy_id, incumbent, .. } => {
+                    assert_eq!(expected.remove(policy_id), Some(*incumbent), "renewal of {policy_id:?}");
+                    if *incumbent { stayed += 1 } else { moved += 1 }
+                }
+                _ => {}
+            }
+        }
+        assert!(expected.is_empty(), "{} renewals without PolicyRenewed", expected.len());
+        assert!(stayed > moved && moved > 0, "{stayed} stayed, {moved} moved");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn rollover_offers_each_expiring_follower_its_own_line() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 10;
+        config.rollover = Some(RolloverConfig { max_premium_increase: 0.10 });
+        let sim = run_sim(config);
+
+        let mut panel_of: HashMap<InsuredId, Vec<(InsurerId, f64)>> = HashMap::new();
+        let mut offered: HashMap<(SubmissionId, InsurerId), f64> = HashMap::new();
+        let (mut offers, mut resigned) = (0, 0);
+        for e in &sim.log {
+            match &e.event {
+                Event::PolicyBound { insured_id, panel, .. } => {
+                    panel_of.insert(*insured_id, panel.clone());
+                }
+                Event::QuoteRejected { insured_id, .. } | Event::SubmissionDropped { insured_id, .. } => {
+                    panel_of.remove(insured_id);
+                }
+                Event::RenewalOffered { submission_id, insured_id, insurer_id, expiring_line, .. } => {
+                    let panel = &panel_of[insured_id];
+                    let line = panel[1..].iter().find(|(id, _)| id == insurer_id).map(|&(_, l)| l);
+                    assert!(line.is_some_and(|l| (l - expiring_line).abs() < 1e-9), "offer to a follower not on the panel");
+                    offered.insert((*submission_id, *insurer_id), *expiring_line);
+                    offers += 1;
+                }
+                Event::FollowerQuoteIssued { submission_id, insurer_id, line_size, .. } => {
+                    if let Some(&line) = offered.get(&(*submission_id, *insurer_id)) {
+                        assert!(*line_size <= line, "a renewal re-signs at most its expiring line");
+                        resigned += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(offers > 0 && resigned > 0, "{offers} offers, {resigned} re-signed");
+        assert!(crate::analysis::verify_mechanics(&sim.log).is_empty());
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn expense_split_charges_every_live_insurer_its_overhead_each_year() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 10;
+        let expenses = ExpenseConfig { acquisition_bps: 2_260, annual_overhead: 550_000_000 };
+        config.expenses = Some(expenses.clone());
+        let sim = run_sim(config);
+
+        // ExpensesPaid precedes the same insurer's YearEndCapital; a short overhead means the
+        // insurer ran out of capital paying it.
+        let mut paid: HashMap<InsurerId, (u64, u64)> = HashMap::new();
+        let mut insolvent: HashSet<InsurerId> = HashSet::new();
+        let mut charges = 0;
+        for e in &sim.log {
+            match &e.event {
+                Event::ExpensesPaid { insurer_id, acquisition, overhead } => {
+                    assert!(!insolvent.contains(insurer_id), "insolvent {insurer_id:?} charged");
+                    paid.insert(*insurer_id, (*acquisition, *overhead));
+                    charges += 1;
+                }
+                Event::InsurerInsolvent { insurer_id } => {
+                    insolvent.insert(*insurer_id);
+                }
+                Event::YearEndCapital { insurer_id, capital, ytd_premium, .. } => {
+                    match paid.remove(insurer_id) {
+                        Some((acquisition, overhead)) => {
+                            let expected = (*ytd_premium as f64 * expenses.acquisition_fraction()).round() as u64;
+                            assert_eq!(acquisition, expected);
+                            assert!(overhead == expenses.annual_overhead || *capital == 0);
+                        }
+                        None => assert!(insolvent.contains(insurer_id), "{insurer_id:?} paid no expenses"),
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(charges > 0);
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn solvency_ratios_are_reported_for_writing_insurers_each_year() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 5;
+        config.solvency = Some(SolvencyConfig {
+            premium_risk_factor: 0.3,
+            reserve_risk_factor: 0.2,
+            target_ratio: 3.0,
+            min_ratio: 1.5,
+        });
+        let total_years = (config.warmup_years + config.years) as usize;
+        let sim = run_sim(config);
+        let mut years = HashSet::new();
+        for e in &sim.log {
+            if let Event::SolvencyRatioReported { capital, scr, solvency_ratio, .. } = e.event {
+                assert!(scr > 0);
+                assert!((solvency_ratio - capital as f64 / scr as f64).abs() < 1e-9);
+                years.insert(e.day.year());
+            }
+        }
+        assert_eq!(years.len(), total_years, "one report round per YearEnd");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn one_occurrence_damages_every_territory_in_its_footprint() {
+        let mut config = minimal_config(5, 30);
+        config.catastrophe.territories = ["US-NE", "US-SE", "US-Gulf"].map(String::from).to_vec();
+        config.catastrophe.event_classes[0].annual_frequency = 2.0;
+        config.catastrophe.event_classes[0].footprint = vec![0.5];
+        let sim = run_sim(config);
+
+        let territory: HashMap<InsuredId, Territory> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::InsuredCreated { insured_id, territory, .. } => Some((insured_id, territory)),
+                _ => None,
+            })
+            .collect();
+        let us_se = sim.territories.get("US-SE");
+        let mut checked = 0;
+        for (i, e) in sim.log.iter().enumerate() {
+            let Event::LossEvent { event_id, territory: struck, ref footprint, .. } = e.event else { continue };
+            assert_eq!(footprint.len(), if Some(struck) == us_se { 2 } else { 1 }, "neighbours either side");
+            let damaged: HashSet<Territory> = sim.log[i..]
+                .iter()
+                .take_while(|l| !matches!(l.event, Event::IndustryLossEstimate { event_id: id, .. } if id == event_id))
+                .filter_map(|l| match l.event {
+                    Event::AssetDamage { insured_id, peril: Peril::WindstormAtlantic, .. } => Some(territory[&insured_id]),
+                    _ => None,
+                })
+                .collect();
+            // Same-day occurrences interleave their damage; check the days with just one.
+            if sim.log.iter().filter(|l| l.day == e.day && matches!(l.event, Event::LossEvent { .. })).count() == 1 {
+                let expected: HashSet<Territory> =
+                    std::iter::once(struck).chain(footprint.iter().map(|&(t, _)| t)).collect();
+                assert_eq!(damaged, expected);
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn modelled_pmls_are_reported_and_drive_the_scr_cat_charge() {
+        use crate::config::PmlReportingConfig;
+
+        let mut config = SimulationConfig::canonical();
+        config.years = 5;
+        config.solvency = Some(SolvencyConfig {
+            premium_risk_factor: 0.3,
+            reserve_risk_factor: 0.2,
+            target_ratio: 3.0,
+            min_ratio: 1.5,
+        });
+        config.pml_reporting = Some(PmlReportingConfig { use_in_scr: true });
+        let total_years = (config.warmup_years + config.years) as usize;
+        let sim = run_sim(config);
+        let mut years = HashSet::new();
+        let mut rp_200s: HashMap<InsurerId, u64> = HashMap::new();
+        for e in &sim.log {
+            match e.event {
+                Event::PmlReported { insurer_id, rp_100, rp_200, rp_250 } => {
+                    assert!(rp_100 <= rp_200 && rp_200 <= rp_250);
+                    rp_200s.insert(insurer_id, rp_200);
+                    years.insert(e.day.year());
+                }
+                // The SCR is reported right after, on the same aggregate: its cat charge is rp_200.
+                Event::SolvencyRatioReported { insurer_id, scr, .. } => {
+                    assert!(scr >= rp_200s.remove(&insurer_id).expect("PML reported first"));
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(years.len(), total_years, "one report round per YearEnd");
+        assert!(sim.log.iter().any(|e| matches!(e.event, Event::PmlReported { rp_200, .. } if rp_200 > 0)));
+    }
+
+    #[test]
+    fn aggregate_deductible_is_eroded_before_any_claim_in_the_year() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 3;
+        config.aggregate_deductible = Some(AggregateDeductibleConfig { fraction: 0.02 });
+        let sim = run_sim(config);
+
+        use crate::types::PolicyId;
+        let aad = ASSET_VALUE / 50;
+        let mut eroded: HashMap<(PolicyId, Year), u64> = HashMap::new();
+        let mut claims = 0;
+        for e in &sim.log {
+            match &e.event {
+                Event::DeductibleEroded { policy_id, amount, remaining, .. } => {
+                    let total = eroded.entry((*policy_id, e.day.year())).or_default();
+                    *total += amount;
+                    assert_eq!(*total + remaining, aad, "erosion and remainder make up the aggregate");
+                }
+                Event::ClaimSettled { policy_id, .. } => {
+                    assert_eq!(eroded.get(&(*policy_id, e.day.year())), Some(&aad), "claim before the AAD is eroded");
+                    claims += 1;
+                }
+                _ => {}
+            }
+        }
+        assert!(!eroded.is_empty() && claims > 0);
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn portfolio_draws_heterogeneous_insureds_from_the_seed() {
+        let mut config = SimulationConfig::canonical();
+        config.n_insureds = 2_000;
+        config.portfolio = Some(PortfolioConfig {
+            sum_insured_sigma: 0.5,
+            territory_weights: vec![("US-SE".to_string(), 3.0), ("US-NE".to_string(), 1.0)],
+            attritional_rate_sigma: 0.3,
+            assets_per_insured: 0,
+        });
+        let sim = Simulation::from_config(config.clone()).unwrap();
+        let insureds: Vec<&Insured> = sim.brokers.iter().flat_map(|b| &b.insureds).collect();
+        let n = insureds.len() as f64;
+        let mean_si = insureds.iter().map(|i| i.sum_insured() as f64).sum::<f64>() / n;
+        assert!((mean_si / ASSET_VALUE as f64 - 1.0).abs() < 0.05, "mean SI {mean_si}");
+        assert!(insureds.iter().any(|i| i.sum_insured() != ASSET_VALUE));
+        let mean_mult = insureds.iter().map(|i| i.attritional_multiplier()).sum::<f64>() / n;
+        assert!((mean_mult - 1.0).abs() < 0.05, "mean multiplier {mean_mult}");
+        let (us_se, us_ne) = (sim.territories.get("US-SE"), sim.territories.get("US-NE"));
+        let se = insureds.iter().filter(|i| Some(i.risk.territory) == us_se).count() as f64 / n;
+        assert!((se - 0.75).abs() < 0.05, "US-SE share {se}");
+        assert!(insureds.iter().all(|i| Some(i.risk.territory) == us_se || Some(i.risk.territory) == us_ne));
+
+        let again = Simulation::from_config(config).unwrap();
+        let sis = |s: &Simulation| s.brokers.iter().flat_map(|b| &b.insureds).map(|i| i.sum_insured()).collect::<Vec<_>>();
+        assert_eq!(sis(&sim), sis(&again), "same seed, same portfolio");
+    }
+
+    /// Multi-asset insureds place each asset separately: one policy per asset, assets spread
+    /// over the territories, and the owner's reservation price and broker shared by its assets.
+    #[test]
+    fn multi_asset_insureds_place_each_asset_separately() {
+        let mut config = minimal_config(1, 4);
+        config.max_rol_sigma = 0.4;
+        config.catastrophe.territories = vec!["US-SE".to_string(), "US-NE".to_string(), "US-Gulf".to_string()];
+        config.portfolio = Some(PortfolioConfig {
+            sum_insured_sigma: 0.0,
+            territory_weights: vec![],
+            attritional_rate_sigma: 0.0,
+            assets_per_insured: 3,
+        });
+        let sim = run_sim(config);
+
+        let mut owners: HashMap<InsuredId, Vec<&Insured>> = HashMap::new();
+        for insured in sim.brokers.iter().flat_map(|b| &b.insureds) {
+            owners.entry(insured.owner().expect("every asset has an owner")).or_default().push(insured);
+        }
+        assert_eq!(owners.len(), 4);
+        for (owner, assets) in &owners {
+            assert_eq!(assets.len(), 3);
+            assert_eq!(assets.iter().map(|a| a.risk.territory).collect::<HashSet<_>>().len(), 3);
+            assert!(assets.iter().all(|a| a.base_max_rol() == assets[0].base_max_rol()));
+            let broker = sim.insured_broker[owner];
+            assert!(assets.iter().all(|a| sim.insured_broker[&a.id] == broker));
+        }
+        let bound: HashSet<InsuredId> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::PolicyBound { insured_id, .. } => Some(insured_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(bound.len(), 12, "one policy per asset");
+        let created = sim.log.iter().filter(|e| matches!(e.event, Event::InsuredCreated { owner: Some(_), .. })).count();
+        assert_eq!(created, 12);
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn inflation_revalues_assets_and_trends_attritional_severity() {
+        let inflation = InflationConfig { asset_growth: 0.10, claims_inflation: 0.05 };
+        let sim = run_sim(SimulationConfig { inflation: Some(inflation.clone()), ..minimal_config(4, 10) });
+        assert!(crate::analysis::verify_mechanics(&sim.log).is_empty());
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+
+        // Every coverage request is valued at its own year's index.
+        for e in &sim.log {
+            if let Event::CoverageRequested { ref risk, .. } = e.event {
+                let expected = (ASSET_VALUE as f64 * inflation.asset_index(e.day.year())).round() as u64;
+                assert_eq!(risk.sum_insured, expected, "day {}", e.day.0);
+                assert_eq!(risk.limit, risk.sum_insured);
+            }
+        }
+        assert!(sim.log.iter().any(|e| e.day.year() == Year(4)
+            && matches!(e.event, Event::PolicyBound { sum_insured, .. } if sum_insured > ASSET_VALUE)));
+
+        // The capital benchmark compounds with asset values.
+        let initial = sim.config.insurers[0].initial_capital as f64;
+        let benchmark = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::YearEndCapital { initial_capital, .. } => Some(initial_capital),
+                _ => None,
+            })
+            .last();
+        assert_eq!(benchmark, Some((initial * 1.1f64.powi(3)).round() as u64));
+
+        // Attritional damage fractions trend at (1 + claims_inflation) on top of asset values.
+        let flat = run_sim(minimal_config(4, 10));
+        let mean_df = |sim: &Simulation, year: Year| {
+            let si = (ASSET_VALUE as f64 * sim.config.inflation.as_ref().map_or(1.0, |i| i.asset_index(year))).round();
+            let dfs: Vec<f64> = sim
+                .log
+                .iter()
+                .filter(|e| e.day.year() == year)
+                .filter_map(|e| match e.event {
+                    Event::AssetDamage { peril: Peril::Attritional, ground_up_loss, .. } => Some(ground_up_loss as f64 / si),
+                    _ => None,
+                })
+                .collect();
+            dfs.iter().sum::<f64>() / dfs.len() as f64
+        };
+        let ratio = mean_df(&sim, Year(4)) / mean_df(&flat, Year(4));
+        assert!(ratio > 1.0, "year-4 attritional severity should trend upward, got ×{ratio:.3}");
+    }
+
+    #[test]
+    fn exposure_grows_in_listed_territories_on_top_of_inflation() {
+        let inflation = InflationConfig { asset_growth: 0.02, claims_inflation: 0.0 };
+        let growth = ExposureGrowthConfig {
+            territories: vec![TerritoryGrowth { territory: "US-Gulf".to_string(), annual_growth: 0.10 }],
+        };
+        let mut config = SimulationConfig {
+            inflation: Some(inflation.clone()),
+            exposure_growth: Some(growth.clone()),
+            ..minimal_config(4, 10)
+        };
+        config.catastrophe.territories = vec!["US-SE".to_string(), "US-Gulf".to_string()];
+        let sim = run_sim(config);
+        assert!(crate::analysis::verify_mechanics(&sim.log).is_empty());
+
+        let logged: Vec<(Year, f64)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match &e.event {
+                Event::ExposureGrowth { year, territory, index, .. } => {
+                    assert_eq!(sim.territories.name(*territory), Some("US-Gulf"));
+                    assert_eq!(e.day, Day::year_start(*year));
+                    Some((*year, *index))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(logged.iter().map(|(y, _)| y.0).collect::<Vec<_>>(), vec![2, 3, 4], "from year 2");
+        assert!(logged.iter().all(|&(y, index)| (index - 1.1f64.powi(y.0 as i32 - 1)).abs() < 1e-12));
+
+        let (mut gulf, mut se) = (0, 0);
+        for e in &sim.log {
+            if let Event::CoverageRequested { ref risk, .. } = e.event {
+                let year = e.day.year();
+                let index = inflation.asset_index(year) * growth.index(sim.territories.name(risk.territory).unwrap(), year);
+                assert_eq!(risk.sum_insured, (ASSET_VALUE as f64 * index).round() as u64, "day {}", e.day.0);
+                if year == Year(4) {
+                    if risk.territory == Territory(1) { gulf = risk.sum_insured } else { se = risk.sum_insured }
+                }
+            }
+        }
+        assert!(gulf > se && se > ASSET_VALUE, "Gulf values outgrow inflation alone");
+    }
+
+    #[test]
+    fn rationing_batches_cat_lead_requests_per_window() {
+        let rationing = RationingConfig { window_days: 30, priority: RationingPriority::RelationshipScore };
+        let sim = run_sim(SimulationConfig { rationing: Some(rationing), ..minimal_config(3, 10) });
+        assert!(crate::analysis::verify_mechanics(&sim.log).is_empty());
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+
+        let mut batches = 0;
+        for (i, e) in sim.log.iter().enumerate() {
+            let Event::LeadQuoteBatchClosed { insurer_id, requests, .. } = e.event else { continue };
+            batches += 1;
+            assert_eq!(e.day.0 % 30, 29, "a batch closes on its window's last day");
+            let responses = sim.log[i + 1..]
+                .iter()
+                .take_while(|r| r.day == e.day)
+                .filter(|r| matches!(r.event,
+                    Event::LeadQuoteIssued { insurer_id: id, .. }
+                    | Event::LeadQuotePartial { insurer_id: id, .. }
+                    | Event::LeadQuoteDeclined { insurer_id: id, .. } if id == insurer_id))
+                .count();
+            assert!(requests > 0 && responses >= requests as usize);
+        }
+        assert!(batches > 0);
+        let placed = sim.log.iter().filter(|e| matches!(e.event, Event::PolicyBound { .. })).count();
+        assert!(placed > 0, "batched requests still place");
+    }
+
+    #[test]
+    fn depleted_insurer_recapitalises_at_year_end() {
+        let config = SimulationConfig {
+            recapitalisation: Some(RecapitalisationConfig {
+                depletion_threshold: 0.5,
+                max_cr: 2.0,
+                recap_probability: 1.0,
+                recap_fraction: 1.0,
+                cost_of_capital: 0.25,
+            }),
+            ..minimal_config(2, 20)
+        };
+        let initial = config.insurers[0].initial_capital;
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run_until(Day(200));
+        sim.insurers[0].capital = initial / 4;
+        sim.run_until(Day::year_end(Year(1)));
+
+        let raised: Vec<_> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::CapitalRaised { amount, cost, remaining_capital, .. } => Some((e.day, amount, cost, remaining_capital)),
+                _ => None,
+            })
+            .collect();
+        let [(day, amount, cost, remaining)] = raised[..] else { panic!("expected one raise, got {raised:?}") };
+        assert_eq!(day, Day::year_end(Year(1)));
+        assert_eq!(cost, (amount as f64 * 0.25).round() as u64, "a quarter of the raise is lost to the issuance discount");
+        assert_eq!(remaining as i64, sim.insurers[0].capital);
+        assert!(sim.insurers[0].capital > initial / 2, "raise restores most of the shortfall");
+    }
+
+    #[test]
+    fn portfolio_transfer_moves_lines_and_renewal_rights_at_a_zero_sum_price() {
+        let mut config = SimulationConfig {
+            portfolio_transfer: Some(PortfolioTransferConfig { renewal_multiple: 1.0 }),
+            ..minimal_config(2, 20)
+        };
+        let second = InsurerConfig { id: InsurerId(2), ..config.insurers[0].clone() };
+        config.insurers.push(second);
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        let day = Day(500);
+        sim.run_until(day);
+
+        let seller_book = sim.market.policies_written_by(InsurerId(1));
+        assert!(!seller_book.is_empty(), "seller must have in-force lines to transfer");
+        let capitals = |sim: &Simulation| sim.insurers.iter().map(|i| i.capital).collect::<Vec<_>>();
+        let before = capitals(&sim);
+        let seller_score = sim.brokers[0].score_of(InsurerId(1)).unwrap();
+        let buyer_score = sim.brokers[0].score_of(InsurerId(2)).unwrap();
+
+        sim.transfer_portfolio(day, InsurerId(1));
+        sim.run_until(day);
+
+        assert!(sim.market.policies_written_by(InsurerId(1)).is_empty());
+        assert_eq!(sim.insurers[0].cat_aggregate, 0, "ceded lines release the seller's aggregate");
+        let price = sim
+            .log
+            .iter()
+            .find_map(|e| match &e.event {
+                Event::PortfolioTransferred(t) if t.seller_id == InsurerId(1) && t.buyer_id == InsurerId(2) => {
+                    assert_eq!(t.policies as usize, seller_book.len());
+                    Some(t.price)
+                }
+                _ => None,
+            })
+            .expect("PortfolioTransferred must be logged");
+        let after = capitals(&sim);
+        assert_eq!(after[0] - before[0], price);
+        assert_eq!(after[1] - before[1], -price);
+        assert_eq!(sim.brokers[0].score_of(InsurerId(1)), Some(0.0));
+        assert_eq!(sim.brokers[0].score_of(InsurerId(2)), Some(seller_score + buyer_score));
+    }
+
+    #[test]
+    fn elastic_demand_declines_quotes_the_reservation_price_would_accept() {
+        let bound = |sim: &Simulation| {
+            sim.log.iter().filter(|e| matches!(e.event, Event::PolicyBound { .. })).count()
+        };
+        let inelastic = run_sim(minimal_config(3, 3));
+        let config = SimulationConfig {
+            demand: Some(DemandConfig { reference_rate: 1e-4, elasticity: 1.0, min_cover_fraction: 1.0 }),
+            ..minimal_config(3, 3)
+        };
+        let sim = run_sim(config);
+
+        let declined = sim.log.iter().any(|e| matches!(
+            e.event,
+            Event::QuoteRejected { reason: RejectionReason::PriceTooHigh, .. }
+        ));
+        assert!(declined, "rates far above the reference must trigger PriceTooHigh");
+        assert!(bound(&sim) < bound(&inelastic), "elastic demand must shrink the bound book");
+        assert!(sim.log.iter().all(|e| !matches!(
+            e.event,
+            Event::QuoteRejected { reason: RejectionReason::ReservationPriceExceeded, .. }
+        )), "minimal config accepts every quote on price alone");
+    }
+
+    #[test]
+    fn brokers_publish_market_intelligence_that_insureds_anchor_to() {
+        let config = SimulationConfig {
+            demand: Some(DemandConfig { reference_rate: 1e-4, elasticity: 1.0, min_cover_fraction: 0.0 }),
+            market_intelligence: Some(MarketIntelligenceConfig {
+                anchor_demand: true,
+                entry_min_decline_rate: 0.0,
+            }),
+            ..minimal_config(3, 3)
+        };
+        let sim = run_sim(config);
+
+        let intel: Vec<_> = sim
+            .log
+            .iter()
+            .filter_map(|e| match &e.event {
+                Event::MarketIntelligence { year, avg_quoted_rate, .. } => Some((e.day, *year, *avg_quoted_rate)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(intel.len(), 3, "one digest per broker per year");
+        for &(day, year, rate) in &intel {
+            assert_eq!(day, Day::year_end(year), "published on the YearEnd day");
+            assert!(rate > 0.0);
+        }
+        let last_rate = intel.last().unwrap().2;
+        let insured = sim.insured(InsuredId(1)).unwrap();
+        assert_eq!(
+            insured.acceptance_probability(last_rate),
+            1.0,
+            "the published market rate replaces the configured reference"
+        );
+    }
+
+    #[test]
+    fn retention_pressure_raises_deductibles_at_renewal() {
+        let config = SimulationConfig {
+            retention: Some(RetentionConfig {
+                deductible_ladder: vec![0.0, 0.01, 0.02],
+                limit_fraction: 1.0,
+                step_up_at: 0.0, // any accepted rate strains the budget → climb every renewal
+                step_down_at: 0.0,
+            }),
+            ..minimal_config(3, 3)
+        };
+        let sim = run_sim(config);
+        let attachments: Vec<(u32, u64)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match &e.event {
+                Event::CoverageRequested { risk, .. } => Some((e.day.year().0, risk.attachment)),
+                _ => None,
+            })
+            .collect();
+        assert!(attachments.iter().filter(|(y, _)| *y == 1).all(|&(_, a)| a == 0));
+        assert!(
+            attachments.iter().filter(|(y, _)| *y == 3).all(|&(_, a)| a == ASSET_VALUE / 50),
+            "two renewals climb two rungs: {attachments:?}"
+        );
+    }
+
+    #[test]
+    fn scheduling_a_claim_behind_year_end_is_refused() {
+        let mut sim = Simulation::from_config(minimal_config(2, 1)).unwrap();
+        let year_end = Day::year_end(Year(1));
+        let claim = Event::ClaimSettled { policy_id: PolicyId(0), insurer_id: InsurerId(1), amount: 1, peril: Peril::Attritional, remaining_capital: 0 };
+        sim.dispatching = Some((year_end, PriorityClass::YearClose));
+        let err = sim.schedule(year_end, claim.clone()).unwrap_err();
+        assert!(err.to_string().contains("out of phase"), "{err}");
+
+        // A capital event dispatched after YearEnd cannot reopen the day either.
+        sim.year_closed = Some(year_end);
+        sim.dispatching = Some((year_end, PriorityClass::Capital));
+        assert!(matches!(sim.schedule(year_end, claim.clone()), Err(crate::Error::Schedule(e)) if e.phase == PriorityClass::YearClose));
+        assert!(sim.schedule(year_end.offset(1), claim).is_ok(), "the next day is open");
+
+        // From a handler the violation halts the run instead.
+        sim.enqueue(Day(0), Event::YearStart { year: Year(1) });
+        assert!(sim.schedule_error().is_some_and(|e| e.day < e.now));
+        assert!(sim.is_finished());
+    }
+
+    #[test]
+    fn logged_seq_orders_same_day_events_within_each_priority_class() {
+        let mut sim = Simulation::from_config(minimal_config(2, 6)).unwrap();
+        sim.start();
+        sim.run();
+        let queued: Vec<&SimEvent> = sim.log.iter().filter(|e| e.seq.is_some()).collect();
+        assert!(queued.len() > sim.log.len() / 2, "dispatched events carry their seq");
+        for w in queued.windows(2) {
+            if w[0].day == w[1].day && w[0].event.priority_class() == w[1].event.priority_class() {
+                assert!(w[0].seq < w[1].seq, "{:?} then {:?}", w[0], w[1]);
+            }
+        }
+        assert!(
+            sim.log.iter().filter(|e| matches!(e.event, Event::InsurerCreated { .. })).all(|e| e.seq.is_none()),
+            "directly logged events have none"
+        );
+    }
+
+    #[test]
+    fn aggregate_only_matches_analysing_the_full_log() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 4;
+        config.warmup_years = 1;
+        config.n_insureds = 30;
+        config.health = Some(crate::config::HealthConfig { interval_days: 90 });
+
+        let mut full = Simulation::from_config(config.clone()).unwrap();
+        full.start();
+        full.run();
+        let expected = crate::analysis::analyse(&full.log, &HashMap::new(), 0.3);
+
+        let mut lean = Simulation::from_config(config).unwrap().aggregate_only();
+        lean.start();
+        lean.run();
+        assert!(lean.log.is_empty(), "nothing is retained");
+        assert_eq!(lean.events_logged(), full.log.len() as u64);
+        let (warmup, stats) = lean.year_stats().expect("aggregating");
+        assert_eq!(warmup, expected.0);
+        crate::testing::assert_year_stats_match(&stats, &expected.1);
+        assert!(full.year_stats().is_none());
+    }
+
+    #[test]
+    fn insurers_hold_the_unearned_premium_the_log_implies() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 3;
+        config.warmup_years = 0;
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        let (_, stats) = crate::analysis::analyse(&sim.log, &HashMap::new(), 0.3);
+        for s in &stats {
+            let held: u64 = sim
+                .log
+                .iter()
+                .filter(|e| e.day == Day::year_end(Year(s.year)))
+                .filter_map(|e| match e.event {
+                    Event::YearEndCapital { unearned_premium, .. } => Some(unearned_premium),
+                    _ => None,
+                })
+                .sum();
+            assert!(s.unearned_premium > 0);
+            let gap = held.abs_diff(s.unearned_premium) as f64 / s.unearned_premium as f64;
+            assert!(gap < 1e-3, "year {}: insurers hold {held}, log implies {}", s.year, s.unearned_premium);
+        }
+    }
+
+    #[test]
+    fn large_fire_losses_are_claimed_and_counted_apart_from_attritional() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 3;
+        config.warmup_years = 0;
+        config.large_loss = Some(crate::config::LargeLossConfig { annual_rate: 0.05, scale: 0.2, shape: 1.2 });
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+        let claimed = sim
+            .log
+            .iter()
+            .any(|e| matches!(e.event, Event::ClaimSettled { peril: Peril::LargeFire, .. } | Event::ClaimReported { peril: Peril::LargeFire, .. }));
+        assert!(claimed, "large fires on insured assets reach insurers");
+        let (_, stats) = crate::analysis::analyse(&sim.log, &HashMap::new(), 0.3);
+        let large: u64 = stats.iter().map(|s| s.large_gul).sum();
+        let logged: u64 = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::AssetDamage { peril: Peril::LargeFire, ground_up_loss, .. } => Some(ground_up_loss),
+                _ => None,
+            })
+            .sum();
+        assert!(large > 0);
+        assert_eq!(large, logged);
+    }
+
+    #[test]
+    fn insurers_without_casualty_appetite_stay_off_casualty_panels() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 2;
+        config.warmup_years = 0;
+        config.claims_development = Some(ClaimsDevelopmentConfig {
+            payment_pattern: vec![1.0],
+            payment_interval_days: 360,
+            initial_reserve_ratio: 1.0,
+        });
+        config.casualty = Some(crate::config::CasualtyConfig {
+            annual_rate: 0.1,
+            scale: 0.05,
+            shape: 1.5,
+            reporting_pattern: vec![1.0],
+            reserve_sigma: 0.0,
+        });
+        let out = config.insurers[0].id;
+        config.insurers[0].appetite =
+            vec![crate::config::LineAppetite { line: Line::Casualty, write: false, target_share: None, loading: 0.0 }];
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+
+        let declined = sim
+            .log
+            .iter()
+            .filter(|e| {
+                matches!(e.event,
+                    Event::LeadQuoteDeclined { insurer_id, reason: DeclineReason::OutsideAppetite, .. }
+                    | Event::FollowerQuoteDeclined { insurer_id, reason: DeclineReason::OutsideAppetite, .. }
+                    if insurer_id == out)
+            })
+            .count();
+        assert!(declined > 0);
+        let mut casualty_insureds = HashSet::new();
+        let (mut casualty_bound, mut property_led) = (0, 0);
+        for e in &sim.log {
+            match &e.event {
+                Event::CoverageRequested { insured_id, risk } if risk.line() == Line::Casualty => {
+                    casualty_insureds.insert(*insured_id);
+                }
+                Event::PolicyBound { insured_id, panel, .. } if casualty_insureds.contains(insured_id) => {
+                    casualty_bound += 1;
+                    assert!(panel.iter().all(|(id, _)| *id != out), "{panel:?}");
+                }
+                Event::PolicyBound { panel, .. } => property_led += usize::from(panel[0].0 == out),
+                _ => {}
+            }
+        }
+        assert!(casualty_bound > 0, "the rest of the market still writes casualty");
+        assert!(property_led > 0, "the insurer still leads property");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn casualty_claims_emerge_in_later_years_reserved_at_the_report_years_factor() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 4;
+        config.warmup_years = 0;
+        config.claims_development = Some(ClaimsDevelopmentConfig {
+            payment_pattern: vec![0.5, 0.5],
+            payment_interval_days: 360,
+            initial_reserve_ratio: 1.0,
+        });
+        config.casualty = Some(crate::config::CasualtyConfig {
+            annual_rate: 0.1,
+            scale: 0.05,
+            shape: 1.5,
+            reporting_pattern: vec![0.5, 0.3, 0.2],
+            reserve_sigma: 0.3,
+        });
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+
+        let (_, stats) = crate::analysis::analyse(&sim.log, &HashMap::new(), 0.3);
+        assert!(stats[0].casualty_gul > 0);
+        assert_eq!(stats[0].casualty_reported, 0, "nothing is reported in its accident year");
+        assert!(stats[1].casualty_reported > 0);
+
+        // Claims reported after the policy behind them expired, and reserved at one factor per
+        // report year.
+        let mut expired = HashMap::new();
+        let mut reported = HashMap::new();
+        let mut late = 0;
+        let mut ratios: BTreeMap<Year, Vec<f64>> = BTreeMap::new();
+        for e in &sim.log {
+            match e.event {
+                Event::PolicyExpired { policy_id } => {
+                    expired.insert(policy_id, e.day);
+                }
+                Event::ClaimReported { claim_id, policy_id, amount, peril: Peril::Casualty, .. } => {
+                    late += usize::from(expired.get(&policy_id).is_some_and(|&d| d <= e.day));
+                    reported.insert(claim_id, amount);
+                }
+                Event::ReserveEstablished { claim_id, reserve, .. } => {
+                    if let Some(&amount) = reported.get(&claim_id).filter(|&&a| a > 1_000_000) {
+                        ratios.entry(e.day.year()).or_default().push(reserve as f64 / amount as f64);
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(late > 0, "long-tail claims outlive the cover that pays them");
+        let factors: Vec<f64> = ratios
+            .values()
+            .map(|r| {
+                assert!(r.iter().all(|x| (x - r[0]).abs() < 1e-4), "one factor per report year: {r:?}");
+                r[0]
+            })
+            .collect();
+        assert!(factors.len() >= 2 && factors.windows(2).any(|w| (w[0] - w[1]).abs() > 1e-3), "{factors:?}");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+        assert!(crate::analysis::verify_mechanics(&sim.log).is_empty());
+    }
+
+    /// Casualty is placed as its own line: one liability risk per owner, bound on its own
+    /// policy at its own premium, never struck by a catastrophe, and reported per line.
+    #[test]
+    fn casualty_is_placed_as_a_separate_line_per_insured() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 2;
+        config.warmup_years = 0;
+        config.claims_development = Some(ClaimsDevelopmentConfig {
+            payment_pattern: vec![1.0],
+            payment_interval_days: 360,
+            initial_reserve_ratio: 1.0,
+        });
+        let casualty = crate::config::CasualtyConfig {
+            annual_rate: 0.1,
+            scale: 0.05,
+            shape: 1.5,
+            reporting_pattern: vec![1.0],
+            reserve_sigma: 0.0,
+        };
+        let elf = casualty.expected_loss_fraction();
+        config.casualty = Some(casualty);
+        let n = config.n_insureds;
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+
+        let mut line_of = HashMap::new();
+        let mut owners = HashSet::new();
+        for e in &sim.log {
+            match &e.event {
+                Event::InsuredCreated { owner, .. } => {
+                    owners.insert(owner.expect("every unit names its owner"));
+                }
+                Event::CoverageRequested { insured_id, risk } => {
+                    assert!(risk.perils_covered.iter().all(|p| p.line() == risk.line()), "{risk:?}");
+                    line_of.insert(*insured_id, risk.line());
+                }
+                Event::AssetDamage { insured_id, peril, .. } => assert_eq!(line_of[insured_id], peril.line()),
+                _ => {}
+            }
+        }
+        assert_eq!(owners.len(), n);
+        assert_eq!(line_of.values().filter(|&&l| l == Line::Casualty).count(), n, "one liability risk per owner");
+        assert_eq!(line_of.values().filter(|&&l| l == Line::Property).count(), n);
+
+        // Liability is priced at the casualty ELF, not the property rate.
+        let risk = |line| sim.brokers[0].insureds.iter().find(|i| i.risk.line() == line).unwrap().risk.clone();
+        let (property, liability) = (risk(Line::Property), risk(Line::Casualty));
+        let insurer = &sim.insurers[0];
+        assert!((insurer.expected_loss(&liability) - elf * liability.sum_insured as f64).abs() < 1.0);
+        assert!(insurer.expected_loss(&property) > insurer.expected_loss(&liability));
+
+        let (_, stats) = crate::analysis::analyse(&sim.log, &HashMap::new(), 0.3);
+        assert!(stats[0].casualty_premium > 0 && stats[0].casualty_premium < stats[0].bound_premium);
+        assert_eq!(stats[0].casualty_loss_ratio(), 0.0, "nothing reported in the accident year");
+        assert!(crate::analysis::verify_integrity(&sim.log).is_empty());
+    }
+
+    #[test]
+    fn growth_targets_set_a_plan_each_year_start_and_undercut_after_a_miss() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 6;
+        config.warmup_years = 0;
+        let targeted = config.insurers[0].id;
+        config.growth_targets = Some(crate::config::GrowthTargetConfig {
+            insurer_ids: vec![targeted],
+            annual_growth: 0.25,
+            undercut_per_shortfall: 1.0,
+            price_floor: 0.8,
+        });
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+
+        let plans: Vec<(Day, InsurerId, Year, f64)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::UnderwritingPlanSet { insurer_id, year, undercut, .. } => Some((e.day, insurer_id, year, undercut)),
+                _ => None,
+            })
+            .collect();
+        assert!(plans.iter().all(|&(day, id, year, _)| id == targeted && day == Day::year_start(year)));
+        let years: Vec<u32> = plans.iter().map(|p| p.2.0).collect();
+        assert_eq!(years, vec![2, 3, 4, 5, 6], "one plan a year once a year of premium is written");
+        assert_eq!(plans[0].3, 0.0, "no target to miss in the first plan");
+        assert!(plans.iter().any(|p| p.3 > 0.0), "25% a year is not met every year");
+
+        let (_, stats) = crate::analysis::analyse(&sim.log, &HashMap::new(), 0.3);
+        let undercutting: u32 = stats.iter().map(|s| s.undercutting_insurers).sum();
+        assert_eq!(undercutting as usize, plans.iter().filter(|p| p.3 > 0.0).count());
+    }
+
+    #[test]
+    fn quote_comparison_records_every_binding_without_changing_the_run() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 4;
+        config.warmup_years = 0;
+        let mut plain = Simulation::from_config(config.clone()).unwrap();
+        plain.start();
+        plain.run();
+        config.quote_comparison = true;
+        let mut sim = Simulation::from_config(config).unwrap();
+        sim.start();
+        sim.run();
+
+        let bound: HashMap<PolicyId, (Day, u64)> = sim
+            .log
+            .iter()
+            .filter_map(|e| match e.event {
+                Event::PolicyBound { policy_id, premium, .. } => Some((policy_id, (e.day, premium))),
+                _ => None,
+            })
+            .collect();
+        let mut compared = 0;
+        for e in &sim.log {
+            if let Event::QuoteComparisonRecorded { policy_id, premium, median_quote, ref quotes } = e.event {
+                compared += 1;
+                assert_eq!(bound.get(&policy_id), Some(&(e.day, premium)), "the bound premium, on the binding day");
+                assert!(!quotes.is_empty(), "the winner itself is a writing insurer");
+                let (lo, hi) = (quotes.iter().map(|q| q.1).min().unwrap(), quotes.iter().map(|q| q.1).max().unwrap());
+                assert!((lo..=hi).contains(&median_quote));
+            }
+        }
+        assert_eq!(compared, bound.len());
+
+        let without: Vec<&Event> = sim
+            .log
+            .iter()
+            .map(|e| &e.event)
+            .filter(|e| !matches!(e, Event::QuoteComparisonRecorded { .. } | Event::RunMetadata(_)))
+            .collect();
+        let plain_events: Vec<&Event> =
+            plain.log.iter().map(|e| &e.event).filter(|e| !matches!(e, Event::RunMetadata(_))).collect();
+        assert!(without == plain_events, "recording quotes must not perturb the run");
+
+        let deciles = crate::analysis::winners_curse(&sim.log);
+        assert_eq!(deciles.len(), 10);
+        assert_eq!(deciles.iter().map(|d| d.policies as usize).sum::<usize>(), compared);
+        assert!(deciles.windows(2).all(|w| w[0].max_spread <= w[1].min_spread));
+    }
+
+    #[test]
+    fn risk_views_give_each_insurer_its_own_cat_elf_logged_at_creation() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 1;
+        config.warmup_years = 0;
+        let configured: Vec<f64> = config.insurers.iter().map(|c| c.cat_elf).collect();
+        let views = |config: SimulationConfig| {
+            let mut sim = Simulation::from_config(config).unwrap();
+            sim.start();
+            let logged: Vec<f64> = sim
+                .log
+                .iter()
+                .filter_map(|e| match e.event {
+                    Event::InsurerCreated { cat_elf, .. } => Some(cat_elf),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(logged, sim.insurers.iter().map(|i| i.cat_elf()).collect::<Vec<_>>(), "the view priced on is the view logged");
+            logged
+        };
+        assert_eq!(views(config.clone()), configured, "no risk views: the configured cat ELF");
+
+        config.risk_views = Some(crate::config::RiskViewConfig { cat_elf_sigma: 0.3 });
+        let perturbed = views(config.clone());
+        assert_eq!(perturbed, views(config), "views are part of the seeded run");
+        let errors: Vec<f64> = perturbed.iter().zip(&configured).map(|(v, c)| v / c).collect();
+        assert!(errors.iter().all(|&e| e > 0.0 && e != 1.0));
+        assert!(errors.windows(2).all(|w| w[0] != w[1]), "insurers disagree with each other");
+    }
+
+    #[test]
+    fn capital_ledger_flows_reconcile_every_reported_capital_without_changing_the_run() {
+        use crate::config::ReinsuranceConfig;
+        let mut config = minimal_config(8, 10);
+        config.catastrophe.event_classes[0].annual_frequency = 2.0;
+        config.ils = Some(IlsConfig {
+            trigger: CatBondTrigger::Indemnity { attachment_capital_fraction: 0.0 },
+            term_years: 2,
+            principal_capital_fraction: 0.5,
+            spread: 0.04,
+            issuance_probability: 0.5,
+            rate_elasticity: 0.0,
+        });
+        config.reinsurance = Some(ReinsuranceConfig {
+            return_period: 200.0,
+            retention_capital_fraction: 0.02,
+            base_rate_on_line: 0.1,
+            target_loss_ratio: 0.5,
+            cycle_weight: 0.5,
+            min_rate_factor: 0.5,
+            max_rate_factor: 3.0,
+            price_elasticity: 1.0,
+        });
+        // Year-end flows that land after the snapshots queued before them.
+        config.recapitalisation = Some(RecapitalisationConfig {
+            depletion_threshold: 0.05,
+            max_cr: 10.0,
+            recap_probability: 1.0,
+            recap_fraction: 0.5,
+            cost_of_capital: 0.25,
+        });
+        config.solvency = Some(SolvencyConfig { premium_risk_factor: 3.0, reserve_risk_factor: 0.2, target_ratio: 1.0, min_ratio: 0.0 });
+        config.investment = Some(InvestmentConfig { mean_return: 0.04, volatility: 0.1, crash_probability: 0.2, crash_return: -0.25 });
+        config.runoff = Some(RunoffConfig {
+            runoff_cr_threshold: 1.2,
+            capital_exit_floor: 0.0,
+            reentry_ap_tp_factor: 1.0,
+            reentry_probability: 0.5,
+            soft_market_exit: None,
+        });
+        config.insurers.push(InsurerConfig { id: InsurerId(2), ..config.insurers[0].clone() });
+        let plain = run_sim(config.clone());
+        config.capital_ledger = true;
+        let sim = run_sim(config);
+
+        let without: Vec<&Event> = sim.log.iter().map(|e| &e.event).filter(|e| !matches!(e, Event::CapitalFlow { .. })).collect();
+        assert!(without == plain.log.iter().map(|e| &e.event).collect::<Vec<_>>(), "the ledger must not perturb the run");
+
+        let mut reasons: HashSet<CapitalFlowReason> = HashSet::new();
+        let mut ledger: HashMap<InsurerId, i64> = HashMap::new();
+        for e in &sim.log {
+            match e.event {
+                Event::InsurerCreated { insurer_id, initial_capital, .. }
+                | Event::InsurerEntered { insurer_id, initial_capital, .. } => {
+                    ledger.entry(insurer_id).or_insert(initial_capital as i64);
+                }
+                Event::CapitalFlow { insurer_id, reason, amount, .. } => {
+                    assert!(e.seq.is_none(), "flows are logged, never queued");
+                    assert_ne!(amount, 0);
+                    reasons.insert(reason);
+                    *ledger.get_mut(&insurer_id).unwrap() += amount;
+                }
+                _ => {}
+            }
+        }
+        for reason in [
+            CapitalFlowReason::Premium,
+            CapitalFlowReason::Claim,
+            CapitalFlowReason::ReinsurancePremium,
+            CapitalFlowReason::ReinsuranceRecovery,
+            CapitalFlowReason::CatBondPremium,
+            CapitalFlowReason::InvestmentIncome,
+            CapitalFlowReason::CapitalRaise,
+        ] {
+            assert!(reasons.contains(&reason), "{reason:?} flows are logged");
+        }
+        for insurer in &sim.insurers {
+            assert_eq!(ledger[&insurer.id], insurer.capital, "insurer {:?}: initial + flows = capital", insurer.id);
+        }
+        assert!(crate::report::verify(&sim.log).passed);
+    }
+
+    #[test]
+    fn quarterly_reporting_closes_four_quarters_a_year_that_sum_to_the_year() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 3;
+        config.warmup_years = 0;
+        let plain = run_sim(config.clone());
+        config.quarterly_reporting = true;
+        let sim = run_sim(config);
+
+        let without: Vec<&Event> = sim.log.iter().map(|e| &e.event).filter(|e| !matches!(e, Event::QuarterEnd { .. })).collect();
+        assert!(without == plain.log.iter().map(|e| &e.event).collect::<Vec<_>>(), "reporting must not perturb the run");
+
+        // Per year: premium and claims from the raw events, and from the quarters.
+        let mut raw: HashMap<u32, (u64, u64)> = HashMap::new();
+        let mut reported: HashMap<u32, (u64, u64)> = HashMap::new();
+        let mut quarters: Vec<(u32, u8)> = vec![];
+        for (i, e) in sim.log.iter().enumerate() {
+            let year = e.day.year().0;
+            match e.event {
+                Event::PolicyBound { premium, .. } | Event::PolicyAnniversary { premium, .. } => raw.entry(year).or_default().0 += premium,
+                Event::ClaimSettled { amount, .. } | Event::ClaimReported { amount, .. } => raw.entry(year).or_default().1 += amount,
+                Event::QuarterEnd { year: Year(y), quarter, written_premium, claims_incurred, capital } => {
+                    assert_eq!(y, year);
+                    assert_eq!(e.day, Day::year_start(Year(y)).offset(quarter as u64 * 90 - 1), "last day of the quarter");
+                    assert!(capital > 0);
+                    let totals = reported.entry(y).or_default();
+                    totals.0 += written_premium;
+                    totals.1 += claims_incurred;
+                    quarters.push((y, quarter));
+                    if quarter == 4 {
+                        assert!(matches!(sim.log[i + 1].event, Event::YearEnd { .. }), "Q4 closes ahead of YearEnd");
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(quarters, (1..=3).flat_map(|y| (1..=4).map(move |q| (y, q))).collect::<Vec<_>>());
+        assert_eq!(reported, raw, "the quarters add up to the year");
+    }
+
+    #[test]
+    fn live_check_sees_every_event_and_agrees_with_post_hoc_verification() {
+        let mut config = SimulationConfig::canonical();
+        config.years = 3;
+        config.warmup_years = 1;
+        config.health = Some(crate::config::HealthConfig { interval_days: 90 });
+
+        let mut sim = Simulation::from_config(config.clone()).unwrap().check_live(LiveCheckMode::Abort);
+        sim.start();
+        sim.run();
+        assert!(crate::report::verify(&sim.log).passed);
+        assert!(sim.live_violations().is_empty(), "{}", sim.live_violations()[0]);
+        assert_eq!(sim.live_check.as_ref().unwrap().events_checked(), sim.log.len() as u64);
+
+        let mut lean = Simulation::from_config(config).unwrap().aggregate_only().check_live(LiveCheckMode::Log);
+        lean.start();
+        lean.run();
+        assert_eq!(lean.live_check.as_ref().unwrap().events_checked(), sim.log.len() as u64);
+        assert!(Simulation::from_config(minimal_config(1, 1)).unwrap().live_violations().is_empty());
+    }
+
+    #[test]
+    fn observers_see_every_dispatched_event_and_each_year_end() {
+        use std::sync::{Arc, Mutex};
+
+        // Quote-to-bind latency and peak queue depth: the kind of one-off measurement an
+        // observer is for.
+        #[derive(Default)]
+        struct Latency {
+            dispatched: u64,
+            max_queue: usize,
+            requested: HashMap<SubmissionId, Day>,
+            latencies: Vec<u64>,
+            year_ends: Vec<(Year, usize)>,
+        }
+        impl Observer for Latency {
+            fn on_event_dispatched(&mut self, event: &SimEvent, view: &MarketView) {
+                self.dispatched += 1;
+                self.max_queue = self.max_queue.max(view.queue_depth);
+                match event.event {
+                    Event::LeadQuoteRequested { submission_id, .. } => {
+                        self.requested.entry(submission_id).or_insert(event.day);
+                    }
+                    Event::PolicyBound { submission_id, .. } => {
+                        self.latencies.push(event.day.0 - self.requested[&submission_id].0);
+                    }
+                    _ => {}
+                }
+            }
+            fn on_year_end(&mut self, year: Year, view: &MarketView) {
+                self.year_ends.push((year, view.insurers.len()));
+            }
+        }
+
+        let latency = Arc::new(Mutex::new(Latency::default()));
+        let mut sim = Simulation::from_config(minimal_config(2, 3)).unwrap().with_observer(latency.clone());
+        sim.start();
+        sim.run();
+        assert!(sim.clone().observers.is_empty(), "a snapshot carries no observers");
+
+        let latency = latency.lock().unwrap();
+        assert_eq!(latency.dispatched, sim.events_dispatched);
+        assert!(latency.max_queue > 0);
+        assert!(!latency.latencies.is_empty() && latency.latencies.iter().all(|&d| d == 2), "Inv 1");
+        let years: Vec<Year> = sim.log.iter().filter_map(|e| match e.event { Event::YearEnd { year } => Some(year), _ => None }).collect();
+        assert_eq!(latency.year_ends.iter().map(|&(y, _)| y).collect::<Vec<_>>(), years);
+        assert_eq!(latency.year_ends.last().map(|&(_, n)| n), Some(sim.insurers.len()), "the view is the live insurer pool");
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_json_and_resumes_to_the_horizon() {
+        use rand::RngCore as _;
+
+        let mut sim = Simulation::from_config(minimal_config(3, 6)).unwrap();
+        sim.start();
+        sim.run_until(Day::year_end(Year(1)));
+        assert_eq!(sim.log.last().unwrap().day, Day::year_end(Year(1)));
+
+        let state = sim.snapshot();
+        assert_eq!(state.day, Day::year_end(Year(1)));
+        let json = serde_json::to_string(&state).unwrap();
+        let mut resumed = Simulation::restore(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(resumed.log, sim.log);
+        assert_eq!(resumed.queue.len(), sim.queue.len());
+        assert_eq!(
+            resumed.streams.get(Stream::Cats).next_u64(),
+            sim.streams.get(Stream::Cats).next_u64(),
+            "RNG resumes mid-stream, not from the seed"
+        );
+
+        resumed.run();
+        assert!(resumed.log.starts_with(&sim.log));
+        let year_ends = resumed.log.iter().filter(|e| matches!(e.event, Event::YearEnd { .. })).count();
+        assert_eq!(year_ends, 3);
+    }
+
+    #[test]
+    fn restore_rejects_other_checkpoint_versions() {
+        let mut state = Simulation::from_config(minimal_config(1, 1)).unwrap().snapshot();
+        state.version = CHECKPOINT_VERSION + 1;
+        assert_eq!(
+            Simulation::restore(state).err(),
+            Some(CheckpointError::VersionMismatch { found: CHECKPOINT_VERSION + 1, expected: CHECKPOINT_VERSION })
+        );
+    }
+
+    #[test]
+    fn from_config_rejects_invalid_configs_listing_every_problem() {
+        let fields = |edit: fn(&mut SimulationConfig)| {
+            let mut config = minimal_config(1, 1);
+            edit(&mut config);
+            match Simulation::from_config(config) {
+                Err(crate::Error::Config(errors)) => errors.into_iter().map(|e| e.field).collect::<Vec<_>>(),
+                Err(e) => panic!("unexpected error {e}"),
+                Ok(_) => vec![],
+            }
+        };
+        assert!(fields(|_| {}).is_empty());
+        assert_eq!(fields(|c| c.max_rol_sigma = -0.1), ["max_rol_sigma"]);
+        assert_eq!(fields(|c| c.attritional.annual_rate = f64::NAN), ["attritional.annual_rate"]);
+        assert_eq!(fields(|c| c.catastrophe.event_classes[0].pareto_shape = 1.0), [
+            "catastrophe.event_classes[0].pareto_shape"
+        ]);
+        assert_eq!(fields(|c| c.quotes_per_submission = Some(2)), ["quotes_per_submission"]);
+        assert_eq!(
+            fields(|c| {
+                let appetite = crate::config::LineAppetite { line: Line::Casualty, write: true, target_share: Some(0.0), loading: -1.0 };
+                c.insurers[0].appetite = vec![appetite.clone(), appetite];
+            }),
+            [
+                "insurers[0].appetite[0].target_share",
+                "insurers[0].appetite[0].loading",
+                "insurers[0].appetite[1].line",
+                "insurers[0].appetite[1].target_share",
+                "insurers[0].appetite[1].loading",
+            ],
+        );
+        assert_eq!(
+            fields(|c| {
+                c.years = 0;
+                c.insurers[0].initial_capital = 0;
+                c.insurers[0].cat_elf = 1.5;
+                c.catastrophe.territories.clear();
+            }),
+            ["years", "insurers[0].initial_capital", "insurers[0].cat_elf", "catastrophe.territories"],
+        );
+        let err = Simulation::from_config(SimulationConfig { insurers: vec![], ..minimal_config(1, 1) }).err().unwrap();
+        assert_eq!(err.to_string(), "invalid config: insurers: at least one insurer is required");
+    }
+
+    #[test]
+    fn attritional_losses_follow_each_years_coverage_request() {
+        let mut config = minimal_config(3, 6);
+        config.attritional.annual_rate = 5.0;
+        let sim = run_sim(config);
+
+        let mut cr_day: HashMap<(InsuredId, Year), Day> = HashMap::new();
+        let mut years_with_losses = HashSet::new();
+        for e in sim.log.iter() {
+            match &e.event {
+                Event::CoverageRequested { insured_id, .. } => {
+                    cr_day.entry((*insured_id, e.day.year())).or_insert(e.day);
+                }
+                Event::AssetDamage { insured_id, peril: Peril::Attritional, .. } => {
+                    let from = cr_day.get(&(*insured_id, e.day.year()));
+                    assert!(from.is_some_and(|&d| e.day > d), "loss on {:?} precedes the year's request", e.day);
+                    years_with_losses.insert(e.day.year());
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(years_with_losses.len(), 3, "every year draws attritional losses");
+    }
+
+    #[test]
+    fn renewals_bind_after_the_expiring_policy_leaves_the_book() {
+        // Both events are queued days ahead, so within a day every expiry precedes every bind.
+        let sim = run_sim(minimal_config(3, 6));
+        let mut shared_days = 0;
+        for (i, e) in sim.log.iter().enumerate() {
+            if !matches!(e.event, Event::PolicyExpired { .. }) {
+                continue;
+            }
+            let binds_before = sim.log[..i]
+                .iter()
+                .rev()
+                .take_while(|x| x.day == e.day)
+                .filter(|x| matches!(x.event, Event::PolicyBound { .. }))
+                .count();
+            assert_eq!(binds_before, 0, "PolicyBound dispatched before PolicyExpired on {:?}", e.day);
+            shared_days += sim.log.iter().any(|x| x.day == e.day && matches!(x.event, Event::PolicyBound { .. })) as usize;
+        }
+        assert!(shared_days > 0, "renewals bind on the expiry day");
+    }
 }
@@ -0,0 +1,176 @@
+//! Stylised-fact hypothesis tests: target bands for statistics the market should reproduce,
+//! and an evaluator that scores a batch of runs against them.
+//!
+//! Each fact reduces one run's post-warmup `YearStats` to a single statistic (or nothing, when
+//! the run has no observation — e.g. no cat shock to measure a rate response after). The
+//! batch estimate is the mean over runs that produced one. A fact passes when that mean lies
+//! in its band; the effect size says how far outside it lies, in per-run standard deviations.
+//!
+//! Bands come from the property-cat literature summarised in `docs/phenomena.md`.
+
+use serde::Serialize;
+
+use crate::analysis::{YearStats, percentile_stats};
+use crate::cycle;
+
+/// A target band for one per-run statistic.
+#[derive(Debug, Clone)]
+pub struct StylisedFact {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub lo: f64,
+    pub hi: f64,
+    /// The statistic for one run, given the expense ratio. `None` if the run has no
+    /// observation of it.
+    pub metric: fn(&[YearStats], f64) -> Option<f64>,
+}
+
+/// One fact scored against a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct FactResult {
+    pub name: &'static str,
+    pub lo: f64,
+    pub hi: f64,
+    /// Runs that produced the statistic.
+    pub runs: usize,
+    /// Mean of the statistic over those runs. `None` if no run produced it.
+    pub value: Option<f64>,
+    /// Fraction of those runs whose own statistic lies in the band.
+    pub in_band: f64,
+    /// Distance of `value` outside the band in per-run standard deviations: zero inside, negative
+    /// below `lo`, positive above `hi`. The band width stands in for the standard deviation when
+    /// the runs show no spread.
+    pub effect_size: f64,
+    pub pass: bool,
+}
+
+/// The default fact set.
+pub fn canonical_facts() -> Vec<StylisedFact> {
+    vec![
+        StylisedFact {
+            name: "cr_sd_points",
+            description: "Year-to-year standard deviation of the market combined ratio (points)",
+            lo: 5.0,
+            hi: 15.0,
+            metric: cr_sd_points,
+        },
+        StylisedFact {
+            name: "cycle_period_years",
+            description: "Underwriting cycle length from zero-crossings of detrended RoL (years)",
+            lo: 6.0,
+            hi: 10.0,
+            metric: cycle_period_years,
+        },
+        StylisedFact {
+            name: "post_cat_rate_change_pct",
+            description: "RoL change the year after a cat year with loss ratio above 100% (%)",
+            lo: 10.0,
+            hi: 40.0,
+            metric: post_cat_rate_change_pct,
+        },
+    ]
+}
+
+/// Score every fact against `runs` (post-warmup `YearStats` per run).
+pub fn evaluate(facts: &[StylisedFact], runs: &[Vec<YearStats>], expense_ratio: f64) -> Vec<FactResult> {
+    facts
+        .iter()
+        .map(|fact| {
+            let mut values: Vec<f64> = runs.iter().filter_map(|run| (fact.metric)(run, expense_ratio)).collect();
+            let in_band = values.iter().filter(|v| (fact.lo..=fact.hi).contains(*v)).count();
+            let runs_with = values.len();
+            let dist = percentile_stats(&mut values);
+            let value = dist.as_ref().map(|d| d.mean);
+            let scale = match dist {
+                Some(ref d) if d.std_dev > 0.0 => d.std_dev,
+                _ => fact.hi - fact.lo,
+            };
+            let effect_size = match value {
+                Some(v) if v < fact.lo => (v - fact.lo) / scale,
+                Some(v) if v > fact.hi => (v - fact.hi) / scale,
+                _ => 0.0,
+            };
+            FactResult {
+                name: fact.name,
+                lo: fact.lo,
+                hi: fact.hi,
+                runs: runs_with,
+                value,
+                in_band: if runs_with > 0 { in_band as f64 / runs_with as f64 } else { 0.0 },
+                effect_size,
+                pass: value.is_some_and(|v| (fact.lo..=fact.hi).contains(&v)),
+            }
+        })
+        .collect()
+}
+
+/// Sample standard deviation of the combined ratio, in points. Needs two years.
+fn cr_sd_points(run: &[YearStats], expense_ratio: f64) -> Option<f64> {
+    let mut crs: Vec<f64> = run.iter().map(|s| s.combined_ratio(expense_ratio) * 100.0).collect();
+    percentile_stats(&mut crs).filter(|d| d.n >= 2).map(|d| d.std_dev)
+}
+
+fn cycle_period_years(run: &[YearStats], _expense_ratio: f64) -> Option<f64> {
+    cycle::cycle(&[run.to_vec()], 1).pooled.period
+}
+
+/// Mean RoL change from each shock year (a cat event and loss ratio above 100%) to the next.
+fn post_cat_rate_change_pct(run: &[YearStats], _expense_ratio: f64) -> Option<f64> {
+    let changes: Vec<f64> = run
+        .windows(2)
+        .filter(|w| w[0].cat_event_count > 0 && w[0].loss_ratio() > 1.0 && w[0].rate_on_line() > 0.0)
+        .map(|w| (w[1].rate_on_line() / w[0].rate_on_line() - 1.0) * 100.0)
+        .collect();
+    (!changes.is_empty()).then(|| changes.iter().sum::<f64>() / changes.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn year(year: u32, rol: f64, loss_ratio: f64, cats: u32) -> YearStats {
+        let mut s = YearStats::zero(year);
+        s.sum_insured = 1_000_000;
+        s.bound_premium = (rol * 1_000_000.0).round() as u64;
+        s.claims = (loss_ratio * s.bound_premium as f64).round() as u64;
+        s.cat_event_count = cats;
+        s
+    }
+
+    #[test]
+    fn post_cat_rate_change_averages_the_years_after_shocks() {
+        // Year 2 is a shock (cat, LR 150%) followed by +20%; year 4 has a cat but a profit.
+        let run = vec![
+            year(1, 0.10, 0.6, 0),
+            year(2, 0.10, 1.5, 1),
+            year(3, 0.12, 0.5, 0),
+            year(4, 0.12, 0.8, 1),
+            year(5, 0.10, 0.6, 0),
+        ];
+        let change = post_cat_rate_change_pct(&run, 0.3).expect("one shock year");
+        assert!((change - 20.0).abs() < 1e-9, "change {change}");
+        assert_eq!(post_cat_rate_change_pct(&run[2..], 0.3), None, "no shock, no observation");
+    }
+
+    #[test]
+    fn evaluate_reports_pass_fail_and_signed_effect_sizes() {
+        let fact = |lo, hi| StylisedFact { name: "lr", description: "", lo, hi, metric: |run, _| Some(run[0].loss_ratio()) };
+        // Per-run loss ratios 0.4 and 0.6: mean 0.5, sample sd √0.02.
+        let runs = vec![vec![year(1, 0.1, 0.4, 0)], vec![year(1, 0.1, 0.6, 0)]];
+        let sd = 0.02_f64.sqrt();
+
+        let results = evaluate(&[fact(0.45, 0.55), fact(0.7, 0.9), fact(0.1, 0.2)], &runs, 0.3);
+        let inside = &results[0];
+        assert!(inside.pass);
+        assert_eq!((inside.runs, inside.in_band, inside.effect_size), (2, 0.0, 0.0));
+        assert!((inside.value.unwrap() - 0.5).abs() < 1e-9);
+        let below = &results[1];
+        assert!(!below.pass);
+        assert!((below.effect_size + 0.2 / sd).abs() < 1e-9, "below the band is negative");
+        let above = &results[2];
+        assert!((above.effect_size - 0.3 / sd).abs() < 1e-9, "above the band is positive");
+
+        let none = evaluate(&[StylisedFact { metric: |_, _| None, ..fact(0.0, 1.0) }], &runs, 0.3);
+        assert_eq!((none[0].runs, none[0].value, none[0].pass), (0, None, false));
+    }
+}
@@ -0,0 +1,423 @@
+//! Parameter sweeps: sample combinations of config values, run each under several seeds, and
+//! summarise every combination in a long-format table.
+//!
+//! A sweep is described by a small TOML file:
+//!
+//! ```toml
+//! samples = 20        # LHS: number of combinations; grid: levels per parameter
+//! seeds = 4           # seeds per combination (run in parallel)
+//! method = "lhs"      # "lhs" (Latin hypercube, default) or "grid"
+//! seed = 42           # first run seed and LHS sampling seed (default: the base config's)
+//! years = 50          # optional override of the base config's analysis years
+//! evaluations = 60    # `--optimise` only: objective evaluation budget
+//!
+//! [params]
+//! "insurers.attritional_elf" = [0.20, 0.30]
+//! max_rol_mu = [0.10, 0.20]
+//! ```
+//!
+//! Each `[params]` key is a dotted path into the serialized `SimulationConfig`; a path through
+//! a list (e.g. `insurers`) sets the field on every element. Integer fields are rounded. Fields
+//! that are unset (`None`) in the base config cannot be swept — enable the feature in a base
+//! config first. Only this subset of TOML is read: `key = value` lines, one `[params]` table,
+//! `#` comments, numbers, quoted strings and two-element number arrays.
+
+use std::io::{self, Write};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde_json::Value;
+
+use crate::analysis::YearStats;
+use crate::batch::{self, BatchOptions};
+use crate::config::SimulationConfig;
+use crate::stylized_facts;
+
+/// How parameter combinations are drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepMethod {
+    /// `samples` combinations, each parameter's range cut into `samples` strata used once.
+    LatinHypercube,
+    /// `samples` evenly spaced levels per parameter (ends included), every combination.
+    Grid,
+}
+
+/// One swept config field and its range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepParam {
+    pub path: String,
+    pub lo: f64,
+    pub hi: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepSpec {
+    pub samples: usize,
+    pub seeds: u64,
+    pub method: SweepMethod,
+    pub seed: Option<u64>,
+    pub years: Option<u32>,
+    /// Objective-evaluation budget for [`crate::optimise`]; unused by sweeps.
+    pub evaluations: Option<usize>,
+    pub params: Vec<SweepParam>,
+}
+
+/// Why a sweep could not be set up or run.
+#[derive(Debug)]
+pub enum SweepError {
+    /// A spec line is not `key = value` or a table header. `line` is 1-based.
+    BadLine { line: usize, text: String },
+    /// A key the spec does not know, or a setting outside `[params]` that belongs in it.
+    UnknownKey { line: usize, key: String },
+    /// A value of the wrong form for its key.
+    BadValue { line: usize, key: String, value: String },
+    /// The spec sweeps no parameters, asks for zero seeds, or sets neither samples nor evaluations.
+    Empty,
+    /// A parameter path does not name a numeric field that is set in the base config.
+    BadPath { path: String, reason: String },
+    Io(io::Error),
+    /// A sampled config was rejected or its batch failed.
+    Run(Box<crate::Error>),
+}
+
+impl std::fmt::Display for SweepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadLine { line, text } => write!(f, "sweep spec line {line}: cannot parse {text:?}"),
+            Self::UnknownKey { line, key } => write!(f, "sweep spec line {line}: unknown key `{key}`"),
+            Self::BadValue { line, key, value } => {
+                write!(f, "sweep spec line {line}: bad `{key}` value {value:?}")
+            }
+            Self::Empty => write!(f, "sweep spec needs samples ≥ 1 (or evaluations), seeds ≥ 1 and at least one [params] entry"),
+            Self::BadPath { path, reason } => write!(f, "sweep parameter `{path}`: {reason}"),
+            Self::Io(e) => write!(f, "sweep I/O error: {e}"),
+            Self::Run(e) => write!(f, "sweep run failed: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for SweepError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<crate::Error> for SweepError {
+    fn from(e: crate::Error) -> Self {
+        Self::Run(Box::new(e))
+    }
+}
+
+/// Parse a sweep spec (see module docs for the format).
+pub fn parse_spec(text: &str) -> Result<SweepSpec, SweepError> {
+    let mut spec = SweepSpec {
+        samples: 0,
+        seeds: 1,
+        method: SweepMethod::LatinHypercube,
+        seed: None,
+        years: None,
+        evaluations: None,
+        params: vec![],
+    };
+    let mut in_params = false;
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let content = raw.split('#').next().unwrap_or("").trim();
+        if content.is_empty() {
+            continue;
+        }
+        if content.starts_with('[') && !content.contains('=') {
+            match content {
+                "[params]" => in_params = true,
+                _ => return Err(SweepError::BadLine { line, text: content.to_string() }),
+            }
+            continue;
+        }
+        let Some((key, value)) = content.split_once('=') else {
+            return Err(SweepError::BadLine { line, text: content.to_string() });
+        };
+        let key = key.trim().trim_matches('"').to_string();
+        let value = value.trim();
+        let bad = || SweepError::BadValue { line, key: key.clone(), value: value.to_string() };
+        if in_params {
+            let bounds: Vec<f64> = value
+                .strip_prefix('[')
+                .and_then(|v| v.strip_suffix(']'))
+                .ok_or_else(bad)?
+                .split(',')
+                .map(|b| b.trim().parse::<f64>().ok().filter(|b| b.is_finite()))
+                .collect::<Option<_>>()
+                .ok_or_else(bad)?;
+            let [lo, hi] = bounds[..] else { return Err(bad()) };
+            if lo > hi {
+                return Err(bad());
+            }
+            spec.params.push(SweepParam { path: key, lo, hi });
+            continue;
+        }
+        match key.as_str() {
+            "samples" => spec.samples = value.parse().map_err(|_| bad())?,
+            "seeds" => spec.seeds = value.parse().map_err(|_| bad())?,
+            "seed" => spec.seed = Some(value.parse().map_err(|_| bad())?),
+            "years" => spec.years = Some(value.parse().map_err(|_| bad())?),
+            "evaluations" => spec.evaluations = Some(value.parse().map_err(|_| bad())?),
+            "method" => {
+                spec.method = match value.trim_matches('"') {
+                    "lhs" => SweepMethod::LatinHypercube,
+                    "grid" => SweepMethod::Grid,
+                    _ => return Err(bad()),
+                }
+            }
+            _ => return Err(SweepError::UnknownKey { line, key }),
+        }
+    }
+    if (spec.samples == 0 && spec.evaluations.is_none()) || spec.seeds == 0 || spec.params.is_empty() {
+        return Err(SweepError::Empty);
+    }
+    Ok(spec)
+}
+
+/// The parameter combinations to run, one value per `spec.params` entry, in run order.
+/// Latin hypercube draws are reproducible from `seed`.
+pub fn sample_points(spec: &SweepSpec, seed: u64) -> Vec<Vec<f64>> {
+    let n = spec.samples;
+    match spec.method {
+        SweepMethod::LatinHypercube => {
+            let mut rng = ChaCha20Rng::seed_from_u64(seed);
+            let columns: Vec<Vec<f64>> = spec
+                .params
+                .iter()
+                .map(|p| {
+                    // Fisher–Yates shuffle of the strata, then a uniform draw inside each.
+                    let mut strata: Vec<usize> = (0..n).collect();
+                    for i in (1..n).rev() {
+                        strata.swap(i, rng.random_range(0..=i));
+                    }
+                    strata
+                        .into_iter()
+                        .map(|k| p.lo + (k as f64 + rng.random::<f64>()) / n as f64 * (p.hi - p.lo))
+                        .collect()
+                })
+                .collect();
+            (0..n).map(|i| columns.iter().map(|c| c[i]).collect()).collect()
+        }
+        SweepMethod::Grid => {
+            let level = |p: &SweepParam, k: usize| {
+                if n == 1 { (p.lo + p.hi) / 2.0 } else { p.lo + k as f64 / (n - 1) as f64 * (p.hi - p.lo) }
+            };
+            let total = n.pow(spec.params.len() as u32);
+            (0..total)
+                .map(|mut idx| {
+                    // Mixed-radix digits, last parameter varying fastest.
+                    let mut point = vec![0.0; spec.params.len()];
+                    for (slot, p) in point.iter_mut().zip(&spec.params).rev() {
+                        *slot = level(p, idx % n);
+                        idx /= n;
+                    }
+                    point
+                })
+                .collect()
+        }
+    }
+}
+
+/// `base` with the spec's `seed` and `years` overrides applied — the config every combination
+/// starts from.
+pub fn base_config(base: &SimulationConfig, spec: &SweepSpec) -> SimulationConfig {
+    let mut config = base.clone();
+    if let Some(seed) = spec.seed {
+        config.seed = seed;
+    }
+    if let Some(years) = spec.years {
+        config.years = years;
+    }
+    config
+}
+
+/// `base` with every `params[i].path` set to `values[i]`.
+pub fn apply(base: &SimulationConfig, params: &[SweepParam], values: &[f64]) -> Result<SimulationConfig, SweepError> {
+    let mut json = serde_json::to_value(base).map_err(|e| SweepError::Io(io::Error::other(e)))?;
+    for (p, &v) in params.iter().zip(values) {
+        let segments: Vec<&str> = p.path.split('.').collect();
+        set_path(&mut json, &segments, v).map_err(|reason| SweepError::BadPath { path: p.path.clone(), reason })?;
+    }
+    serde_json::from_value(json).map_err(|e| SweepError::BadPath {
+        path: params.iter().map(|p| p.path.as_str()).collect::<Vec<_>>().join(", "),
+        reason: e.to_string(),
+    })
+}
+
+fn set_path(node: &mut Value, path: &[&str], v: f64) -> Result<(), String> {
+    match (node, path) {
+        (Value::Array(items), _) => items.iter_mut().try_for_each(|item| set_path(item, path, v)),
+        (Value::Object(map), [key, rest @ ..]) => {
+            let child = map.get_mut(*key).ok_or_else(|| format!("no field `{key}`"))?;
+            set_path(child, rest, v)
+        }
+        (Value::Number(n), []) => {
+            *n = if n.is_f64() {
+                serde_json::Number::from_f64(v).ok_or("not a finite number")?
+            } else if n.is_u64() {
+                serde_json::Number::from(v.round().max(0.0) as u64)
+            } else {
+                serde_json::Number::from(v.round() as i64)
+            };
+            Ok(())
+        }
+        (Value::Null, _) => Err("unset in the base config; enable the feature first".to_string()),
+        (_, []) => Err("not a numeric field".to_string()),
+        (_, [key, ..]) => Err(format!("cannot descend into `{key}`")),
+    }
+}
+
+/// One summary statistic of one combination, averaged over its seeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepRow {
+    pub sample: usize,
+    pub values: Vec<f64>,
+    pub metric: &'static str,
+    pub value: f64,
+}
+
+/// Summary statistics for one combination's runs: market means over all analysis years and
+/// seeds, final-year capital, and every stylised fact a run produced.
+pub fn summarise(runs: &[Vec<YearStats>], expense_ratio: f64) -> Vec<(&'static str, f64)> {
+    let years: Vec<&YearStats> = runs.iter().flatten().collect();
+    let n = years.len().max(1) as f64;
+    let mean = |f: &dyn Fn(&YearStats) -> f64| years.iter().map(|s| f(s)).sum::<f64>() / n;
+    let finals: Vec<f64> = runs.iter().filter_map(|r| r.last()).map(|s| s.total_capital as f64 / 1e11).collect();
+    let mut out = vec![
+        ("loss_ratio_mean", mean(&|s| s.loss_ratio())),
+        ("combined_ratio_mean", mean(&|s| s.combined_ratio(expense_ratio))),
+        ("rate_on_line_mean", mean(&|s| s.rate_on_line())),
+        ("insolvencies_per_year", mean(&|s| s.insolvent_count as f64)),
+        ("final_capital_b", finals.iter().sum::<f64>() / finals.len().max(1) as f64),
+    ];
+    let facts = stylized_facts::canonical_facts();
+    for r in stylized_facts::evaluate(&facts, runs, expense_ratio) {
+        if let Some(v) = r.value {
+            out.push((r.name, v));
+        }
+    }
+    out
+}
+
+/// Run every combination of `spec` on top of `base`, `spec.seeds` seeds each (in parallel),
+/// calling `on_sample(i, total)` as each combination finishes.
+pub fn run_sweep(
+    base: &SimulationConfig,
+    spec: &SweepSpec,
+    on_sample: impl Fn(usize, usize),
+) -> Result<Vec<SweepRow>, SweepError> {
+    let base = base_config(base, spec);
+    let points = sample_points(spec, base.seed);
+    // Reject bad paths before any simulation runs.
+    let configs = points.iter().map(|v| apply(&base, &spec.params, v)).collect::<Result<Vec<_>, _>>()?;
+
+    let mut rows = Vec::new();
+    for (sample, (config, values)) in configs.iter().zip(&points).enumerate() {
+        let opts = BatchOptions { aggregate_only: true, ..BatchOptions::default() };
+        let results = batch::run_batch(config, spec.seeds, &opts, |_| {})?;
+        for (metric, value) in summarise(&results.stats(), results.expense_ratio) {
+            rows.push(SweepRow { sample, values: values.clone(), metric, value });
+        }
+        on_sample(sample + 1, points.len());
+    }
+    Ok(rows)
+}
+
+/// Write sweep rows as long-format CSV: `sample,<param paths…>,metric,value`.
+pub fn write_csv(params: &[SweepParam], rows: &[SweepRow], mut w: impl Write) -> io::Result<()> {
+    let names: Vec<&str> = params.iter().map(|p| p.path.as_str()).collect();
+    writeln!(w, "sample,{},metric,value", names.join(","))?;
+    for r in rows {
+        let values: Vec<String> = r.values.iter().map(|v| format!("{v:.6}")).collect();
+        writeln!(w, "{},{},{},{:.6}", r.sample, values.join(","), r.metric, r.value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+        # Calibrate ELFs and rate ceiling.
+        samples = 5
+        seeds = 2
+        method = "lhs"
+        seed = 7
+
+        [params]
+        "insurers.attritional_elf" = [0.2, 0.3]
+        n_insureds = [10, 20]   # integer field
+    "#;
+
+    #[test]
+    fn parse_spec_reads_settings_and_params() {
+        let spec = parse_spec(SPEC).unwrap();
+        assert_eq!((spec.samples, spec.seeds, spec.method, spec.seed, spec.years), (5, 2, SweepMethod::LatinHypercube, Some(7), None));
+        assert_eq!(spec.params[0], SweepParam { path: "insurers.attritional_elf".into(), lo: 0.2, hi: 0.3 });
+        assert_eq!(spec.params[1].path, "n_insureds");
+
+        assert!(matches!(parse_spec("samples = 5\nbogus = 1\n[params]\nx = [0, 1]"), Err(SweepError::UnknownKey { line: 2, .. })));
+        assert!(matches!(parse_spec("samples = 5\n[params]\nx = [1]"), Err(SweepError::BadValue { line: 3, .. })));
+        assert!(matches!(parse_spec("samples = 5"), Err(SweepError::Empty)));
+    }
+
+    #[test]
+    fn latin_hypercube_uses_each_stratum_once_per_parameter() {
+        let spec = parse_spec(SPEC).unwrap();
+        let points = sample_points(&spec, 7);
+        assert_eq!(points.len(), 5);
+        for (k, p) in spec.params.iter().enumerate() {
+            let mut strata: Vec<usize> =
+                points.iter().map(|v| ((v[k] - p.lo) / (p.hi - p.lo) * 5.0).floor() as usize).collect();
+            strata.sort();
+            assert_eq!(strata, vec![0, 1, 2, 3, 4], "param {}", p.path);
+        }
+        assert_eq!(points, sample_points(&spec, 7), "reproducible from the seed");
+    }
+
+    #[test]
+    fn grid_covers_every_level_combination() {
+        let spec = SweepSpec { method: SweepMethod::Grid, samples: 3, ..parse_spec(SPEC).unwrap() };
+        let points = sample_points(&spec, 0);
+        assert_eq!(points.len(), 9);
+        assert_eq!(points[0], vec![0.2, 10.0]);
+        assert_eq!(points[1], vec![0.2, 15.0]);
+        assert_eq!(points[8], vec![0.3, 20.0]);
+    }
+
+    #[test]
+    fn apply_sets_fields_through_lists_and_rounds_integers() {
+        let base = SimulationConfig::canonical();
+        let spec = parse_spec(SPEC).unwrap();
+        let config = apply(&base, &spec.params, &[0.25, 14.6]).unwrap();
+        assert!(config.insurers.iter().all(|ic| ic.attritional_elf == 0.25));
+        assert_eq!(config.n_insureds, 15);
+
+        let unset = SweepParam { path: "investment.mean_return".into(), lo: 0.0, hi: 1.0 };
+        assert!(matches!(apply(&base, &[unset], &[0.5]), Err(SweepError::BadPath { .. })));
+        let missing = SweepParam { path: "no_such_field".into(), lo: 0.0, hi: 1.0 };
+        assert!(matches!(apply(&base, &[missing], &[0.5]), Err(SweepError::BadPath { .. })));
+    }
+
+    #[test]
+    fn run_sweep_writes_one_row_per_combination_and_metric() {
+        let mut base = SimulationConfig::canonical();
+        base.years = 3;
+        base.warmup_years = 1;
+        base.n_insureds = 20;
+        let spec = SweepSpec { samples: 2, seeds: 2, ..parse_spec(SPEC).unwrap() };
+        let rows = run_sweep(&base, &spec, |_, _| {}).unwrap();
+
+        assert!(rows.iter().any(|r| r.sample == 1 && r.metric == "loss_ratio_mean"));
+        assert!(rows.iter().all(|r| r.values.len() == 2 && r.value.is_finite()));
+        let mut csv = Vec::new();
+        write_csv(&spec.params, &rows, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("sample,insurers.attritional_elf,n_insureds,metric,value\n"));
+        assert_eq!(csv.lines().count(), rows.len() + 1);
+    }
+}
@@ -0,0 +1,262 @@
+//! Performance telemetry for a run: events dispatched per kind, wall time per year, peak queue
+//! depth and, when the binary installs [`CountingAlloc`], allocation counts.
+//!
+//! [`Telemetry`] is an [`Observer`]: register it with `Simulation::with_observer` (through an
+//! `Arc<Mutex<_>>` to read it back) and call [`Telemetry::summary`] once the run is over. The
+//! summary prints with `Display` and serialises as JSON. Nothing is added to the event log.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::mem::Discriminant;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::events::{Event, SimEvent};
+use crate::observer::{MarketView, Observer};
+use crate::types::Year;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting allocations while a [`Telemetry`] is recording. Install it
+/// in a binary with `#[global_allocator] static ALLOC: CountingAlloc = CountingAlloc;`;
+/// counts are process-wide, so concurrent runs share them. Until a `Telemetry` starts it adds
+/// only a relaxed load per call.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() && COUNTING.load(Ordering::Relaxed) {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        if COUNTING.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() && COUNTING.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    // Memory allocated before counting started can be freed after: saturate rather than wrap.
+    let _ = LIVE_BYTES.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| Some(live.saturating_sub(size as u64)));
+}
+
+/// Allocation counts while a [`Telemetry`] was recording.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AllocStats {
+    /// Allocations, reallocations included.
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// Highest heap in use by blocks allocated since recording started.
+    pub peak_live_bytes: u64,
+}
+
+/// Wall time and events dispatched between one `YearEnd` (or the start) and the next.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct YearPerf {
+    pub year: u32,
+    pub wall_seconds: f64,
+    pub events: u64,
+}
+
+/// What [`Telemetry`] recorded, from its creation to the call to `summary`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PerfSummary {
+    pub wall_seconds: f64,
+    pub events_dispatched: u64,
+    /// Dispatched events by `Event` variant name.
+    pub events_by_kind: BTreeMap<String, u64>,
+    pub years: Vec<YearPerf>,
+    /// Most events queued after any dispatch.
+    pub peak_queue_depth: usize,
+    /// None unless [`CountingAlloc`] is the global allocator.
+    pub allocations: Option<AllocStats>,
+}
+
+impl PerfSummary {
+    pub fn events_per_second(&self) -> f64 {
+        if self.wall_seconds > 0.0 { self.events_dispatched as f64 / self.wall_seconds } else { 0.0 }
+    }
+}
+
+/// Records a [`PerfSummary`] as the simulation dispatches. Recording starts at `new`.
+pub struct Telemetry {
+    started: Instant,
+    year_started: Instant,
+    year_events: u64,
+    events: u64,
+    /// Count per variant, with its name taken from the first event of that kind.
+    by_kind: HashMap<Discriminant<Event>, (String, u64)>,
+    years: Vec<YearPerf>,
+    peak_queue_depth: usize,
+    /// Allocation counters when recording started.
+    allocs_at_start: (u64, u64),
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        COUNTING.store(true, Ordering::Relaxed);
+        PEAK_LIVE_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+        let now = Instant::now();
+        Telemetry {
+            started: now,
+            year_started: now,
+            year_events: 0,
+            events: 0,
+            by_kind: HashMap::new(),
+            years: Vec::new(),
+            peak_queue_depth: 0,
+            allocs_at_start: (ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED_BYTES.load(Ordering::Relaxed)),
+        }
+    }
+
+    pub fn summary(&self) -> PerfSummary {
+        let (allocations, allocated_bytes) = self.allocs_at_start;
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        PerfSummary {
+            wall_seconds: self.started.elapsed().as_secs_f64(),
+            events_dispatched: self.events,
+            events_by_kind: self.by_kind.values().map(|(name, n)| (name.clone(), *n)).collect(),
+            years: self.years.clone(),
+            peak_queue_depth: self.peak_queue_depth,
+            allocations: (allocations > 0).then(|| AllocStats {
+                allocations,
+                allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+                peak_live_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed),
+            }),
+        }
+    }
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Observer for Telemetry {
+    fn on_event_dispatched(&mut self, event: &SimEvent, view: &MarketView) {
+        self.events += 1;
+        self.year_events += 1;
+        self.peak_queue_depth = self.peak_queue_depth.max(view.queue_depth);
+        self.by_kind
+            .entry(std::mem::discriminant(&event.event))
+            .or_insert_with(|| (kind_name(&event.event), 0))
+            .1 += 1;
+    }
+
+    fn on_year_end(&mut self, year: Year, _view: &MarketView) {
+        let now = Instant::now();
+        self.years.push(YearPerf {
+            year: year.0,
+            wall_seconds: now.duration_since(self.year_started).as_secs_f64(),
+            events: self.year_events,
+        });
+        self.year_started = now;
+        self.year_events = 0;
+    }
+}
+
+/// The variant name, as in the NDJSON log and `rins filter --type`.
+fn kind_name(event: &Event) -> String {
+    let debug = format!("{event:?}");
+    debug.split(|c: char| !c.is_alphanumeric()).next().unwrap_or_default().to_string()
+}
+
+impl fmt::Display for PerfSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Performance ===")?;
+        writeln!(
+            f,
+            "  {} events in {:.2}s ({:.0}/s), peak queue {}",
+            self.events_dispatched,
+            self.wall_seconds,
+            self.events_per_second(),
+            self.peak_queue_depth
+        )?;
+        if let Some(ref a) = self.allocations {
+            writeln!(
+                f,
+                "  {} allocations, {:.1} MB allocated, peak live {:.1} MB",
+                a.allocations,
+                a.allocated_bytes as f64 / 1e6,
+                a.peak_live_bytes as f64 / 1e6
+            )?;
+        }
+        if !self.years.is_empty() {
+            writeln!(f, "\n  {:>4} | {:>8} | {:>9}", "Year", "Events", "Wall ms")?;
+            for y in &self.years {
+                writeln!(f, "  {:>4} | {:>8} | {:>9.1}", y.year, y.events, y.wall_seconds * 1e3)?;
+            }
+        }
+        let mut kinds: Vec<(&String, &u64)> = self.events_by_kind.iter().collect();
+        kinds.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        writeln!(f, "\n  {:<28} {:>10} {:>6}", "Event", "Dispatched", "%")?;
+        for (kind, &n) in kinds {
+            let share = n as f64 / self.events_dispatched.max(1) as f64 * 100.0;
+            writeln!(f, "  {kind:<28} {n:>10} {share:>5.1}%")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::config::SimulationConfig;
+    use crate::simulation::Simulation;
+
+    #[test]
+    fn telemetry_counts_every_dispatched_event_by_kind_and_year() {
+        let mut config = SimulationConfig::canonical();
+        config.warmup_years = 1;
+        config.years = 2;
+        let telemetry = Arc::new(Mutex::new(Telemetry::new()));
+        let mut sim = Simulation::from_config(config).unwrap().with_observer(telemetry.clone());
+        sim.start();
+        sim.run();
+
+        let summary = telemetry.lock().unwrap().summary();
+        let dispatched = |kind: &str| summary.events_by_kind.get(kind).copied().unwrap_or(0);
+        let logged = |pred: fn(&Event) -> bool| sim.log.iter().filter(|e| pred(&e.event)).count() as u64;
+        assert_eq!(summary.events_dispatched, summary.events_by_kind.values().sum::<u64>());
+        assert_eq!(dispatched("PolicyBound"), logged(|e| matches!(e, Event::PolicyBound { .. })));
+        assert_eq!(dispatched("YearEnd"), 3);
+        assert_eq!(dispatched("SimHealth"), 0, "logged without dispatch");
+        assert_eq!(summary.years.iter().map(|y| y.year).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(summary.years.iter().map(|y| y.events).sum::<u64>() < summary.events_dispatched, "the final YearEnd's followers");
+        assert!(summary.peak_queue_depth > 0);
+        assert!(summary.allocations.is_none(), "the test harness uses the system allocator");
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["events_dispatched"], summary.events_dispatched);
+    }
+}
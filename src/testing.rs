@@ -0,0 +1,194 @@
+//! Scenario assertions over an event log (test builds only).
+//!
+//! `expect_events!` checks that a log contains an ordered sequence of event patterns,
+//! with any number of unrelated events (wildcards) between consecutive steps:
+//!
+//! ```ignore
+//! expect_events!(sim.log, [
+//!     Event::CoverageRequested { insured_id: InsuredId(1), .. },
+//!     Event::LeadQuoteRequested { insured_id: InsuredId(1), .. } => +1,
+//!     Event::PolicyBound { insured_id: InsuredId(1), .. } => within 3,
+//! ]);
+//! ```
+//!
+//! Each step is a pattern over `Event` with an optional `if` guard, optionally followed by a
+//! day constraint relative to the previous step's match (guards see bound `Copy` fields by value;
+//! bind anything else with `ref`):
+//! - `=> +N`       — exactly N days later;
+//! - `=> within N` — at most N days later.
+//!
+//! Each step matches the first qualifying event after the previous match. On failure the macro
+//! panics naming the step; on success it evaluates to the matched `&SimEvent`s in step order.
+
+use crate::events::{Event, SimEvent};
+
+/// Day constraint between consecutive `expect_events!` steps.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Gap {
+    Any,
+    Exactly(u64),
+    Within(u64),
+}
+
+impl Gap {
+    fn admits(self, prev_day: u64, day: u64) -> bool {
+        match self {
+            Gap::Any => true,
+            Gap::Exactly(n) => day == prev_day + n,
+            Gap::Within(n) => day <= prev_day + n,
+        }
+    }
+}
+
+/// Advance `cursor` to the first event after it matching `pred` and `gap`; push it onto `matched`.
+/// Panics with the step description when no such event exists.
+pub(crate) fn expect_step<'a>(
+    log: &'a [SimEvent],
+    matched: &mut Vec<&'a SimEvent>,
+    cursor: &mut usize,
+    step: &str,
+    gap: Gap,
+    pred: impl Fn(&Event) -> bool,
+) {
+    let prev_day = matched.last().map(|e| e.day.0);
+    let found = log[*cursor..].iter().position(|e| {
+        pred(&e.event) && prev_day.is_none_or(|p| gap.admits(p, e.day.0))
+    });
+    match found {
+        Some(offset) => {
+            let idx = *cursor + offset;
+            matched.push(&log[idx]);
+            *cursor = idx + 1;
+        }
+        None => {
+            let nearest = log[*cursor..].iter().find(|e| pred(&e.event)).map(|e| e.day.0);
+            panic!(
+                "expect_events!: step {} `{step}` ({gap:?} after day {prev_day:?}) not found \
+                 after log index {}; nearest pattern match: day {nearest:?}",
+                matched.len() + 1,
+                *cursor,
+            );
+        }
+    }
+}
+
+macro_rules! expect_events {
+    ($log:expr, [ $($steps:tt)* ]) => {{
+        let log: &[$crate::events::SimEvent] = &$log;
+        let mut matched: Vec<&$crate::events::SimEvent> = Vec::new();
+        let mut cursor = 0usize;
+        expect_events!(@step log, matched, cursor, $($steps)*);
+        matched
+    }};
+
+    (@step $log:ident, $m:ident, $c:ident, ) => {};
+
+    (@step $log:ident, $m:ident, $c:ident,
+        $pat:pat $(if $guard:expr)? => within $days:expr $(, $($rest:tt)*)?) => {
+        $crate::testing::expect_step(
+            $log, &mut $m, &mut $c, stringify!($pat), $crate::testing::Gap::Within($days),
+            |e| matches!(*e, $pat $(if $guard)?),
+        );
+        expect_events!(@step $log, $m, $c, $($($rest)*)?);
+    };
+
+    (@step $log:ident, $m:ident, $c:ident,
+        $pat:pat $(if $guard:expr)? => + $days:expr $(, $($rest:tt)*)?) => {
+        $crate::testing::expect_step(
+            $log, &mut $m, &mut $c, stringify!($pat), $crate::testing::Gap::Exactly($days),
+            |e| matches!(*e, $pat $(if $guard)?),
+        );
+        expect_events!(@step $log, $m, $c, $($($rest)*)?);
+    };
+
+    (@step $log:ident, $m:ident, $c:ident,
+        $pat:pat $(if $guard:expr)? $(, $($rest:tt)*)?) => {
+        $crate::testing::expect_step(
+            $log, &mut $m, &mut $c, stringify!($pat), $crate::testing::Gap::Any,
+            |e| matches!(*e, $pat $(if $guard)?),
+        );
+        expect_events!(@step $log, $m, $c, $($($rest)*)?);
+    };
+}
+
+/// Assert two `YearStats` series agree: integers exactly, floats to 1e-9 relative. Means over
+/// hash maps may differ in the last bit between otherwise identical analyses.
+pub(crate) fn assert_year_stats_match(a: &[crate::analysis::YearStats], b: &[crate::analysis::YearStats]) {
+    fn same(a: &serde_json::Value, b: &serde_json::Value, path: &str) {
+        use serde_json::Value;
+        match (a, b) {
+            (Value::Number(x), Value::Number(y)) if x.is_f64() || y.is_f64() => {
+                let (x, y) = (x.as_f64().unwrap(), y.as_f64().unwrap());
+                assert!((x - y).abs() <= 1e-9 * x.abs().max(y.abs()).max(1.0), "{path}: {x} vs {y}");
+            }
+            (Value::Array(x), Value::Array(y)) => {
+                assert_eq!(x.len(), y.len(), "{path}: length");
+                for (i, (x, y)) in x.iter().zip(y).enumerate() {
+                    same(x, y, &format!("{path}[{i}]"));
+                }
+            }
+            (Value::Object(x), Value::Object(y)) => {
+                for (k, v) in x {
+                    same(v, &y[k], &format!("{path}.{k}"));
+                }
+            }
+            _ => assert_eq!(a, b, "{path}"),
+        }
+    }
+    same(&serde_json::to_value(a).unwrap(), &serde_json::to_value(b).unwrap(), "stats");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::events::{Event, SimEvent};
+    use crate::types::{Day, InsuredId, SubmissionId, Year};
+
+    fn log() -> Vec<SimEvent> {
+        let ev = |day, event| SimEvent { day: Day(day), seq: None, event };
+        vec![
+            ev(0, Event::YearStart { year: Year(1) }),
+            ev(5, Event::SubmissionDropped { submission_id: SubmissionId(0), insured_id: InsuredId(2) }),
+            ev(7, Event::SubmissionDropped { submission_id: SubmissionId(1), insured_id: InsuredId(1) }),
+            ev(359, Event::YearEnd { year: Year(1) }),
+        ]
+    }
+
+    #[test]
+    fn matches_ordered_steps_with_wildcards_between() {
+        let log = log();
+        let matched = expect_events!(log, [
+            Event::YearStart { .. },
+            Event::SubmissionDropped { insured_id, .. } if insured_id == InsuredId(1) => within 10,
+            Event::YearEnd { year: Year(1) },
+        ]);
+        assert_eq!(matched.iter().map(|e| e.day.0).collect::<Vec<_>>(), vec![0, 7, 359]);
+    }
+
+    #[test]
+    fn exact_offset_skips_earlier_pattern_matches() {
+        let log = log();
+        let matched = expect_events!(log, [
+            Event::YearStart { .. },
+            Event::SubmissionDropped { .. } => +7,
+        ]);
+        assert_eq!(matched[1].day, Day(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "step 2")]
+    fn panics_when_day_constraint_unmet() {
+        let log = log();
+        expect_events!(log, [
+            Event::SubmissionDropped { .. },
+            Event::YearEnd { .. } => within 30,
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "YearStart")]
+    fn panics_when_step_out_of_order() {
+        let log = log();
+        expect_events!(log, [Event::YearEnd { .. }, Event::YearStart { .. }]);
+    }
+}
+
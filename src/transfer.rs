@@ -0,0 +1,101 @@
+//! Portfolio transfer pricing.
+//!
+//! When an insurer exits into run-off its in-force lines and renewal rights pass to a writing
+//! insurer. The buyer takes the unexpired risk and the broker relationships that drive renewals;
+//! the seller keeps its outstanding claim reserves and runs them off.
+//!
+//! The price is settled net between the two capitals. Gross, the seller hands over the unearned
+//! premium reserve and the buyer pays back its expected margin on that premium plus the value of
+//! the renewal rights. The unearned premium cancels, so the buyer pays the seller
+//! `franchise_value − expected_losses`:
+//!
+//! - `expected_losses`: the buyer's expected loss cost on the unexpired portion of each line.
+//! - `franchise_value`: one year's expected underwriting profit on the book at renewal,
+//!   × `renewal_multiple`, floored at zero.
+//!
+//! A negative price means the seller pays the buyer to take the book.
+
+use crate::config::PortfolioTransferConfig;
+
+/// One transferred line, valued from the buyer's perspective.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferLine {
+    /// The seller's share of the policy's gross premium (cents).
+    pub premium: u64,
+    /// The buyer's expected annual loss cost on the seller's share (cents).
+    pub expected_annual_loss: f64,
+    /// Fraction of the policy period still to run, in [0, 1].
+    pub unexpired_fraction: f64,
+}
+
+/// The valuation inputs and resulting price for one portfolio transfer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortfolioValuation {
+    /// Unearned premium net of expenses on the transferred lines (cents).
+    pub unearned_premium: u64,
+    /// Buyer's expected losses on the unexpired risk (cents).
+    pub expected_losses: u64,
+    /// Annual gross premium of the book at renewal (cents).
+    pub renewal_premium: u64,
+    /// Value of the renewal rights (cents).
+    pub franchise_value: u64,
+    /// Net amount the buyer pays the seller (cents); negative = the seller pays.
+    pub price: i64,
+}
+
+/// Value a book of lines at the buyer's expense ratio.
+pub fn value_portfolio(
+    lines: &[TransferLine],
+    expense_ratio: f64,
+    config: &PortfolioTransferConfig,
+) -> PortfolioValuation {
+    let (mut unearned, mut expected, mut renewal, mut annual_loss) = (0.0, 0.0, 0u64, 0.0);
+    for line in lines {
+        let unexpired = line.unexpired_fraction.clamp(0.0, 1.0);
+        unearned += line.premium as f64 * (1.0 - expense_ratio) * unexpired;
+        expected += line.expected_annual_loss * unexpired;
+        renewal += line.premium;
+        annual_loss += line.expected_annual_loss;
+    }
+    let annual_profit = renewal as f64 * (1.0 - expense_ratio) - annual_loss;
+    let franchise_value = (annual_profit * config.renewal_multiple).max(0.0).round() as u64;
+    let expected_losses = expected.round() as u64;
+    PortfolioValuation {
+        unearned_premium: unearned.round() as u64,
+        expected_losses,
+        renewal_premium: renewal,
+        franchise_value,
+        price: franchise_value as i64 - expected_losses as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: PortfolioTransferConfig = PortfolioTransferConfig { renewal_multiple: 2.0 };
+
+    #[test]
+    fn profitable_book_sells_for_its_franchise_less_unexpired_losses() {
+        // 1000 premium, 30% expenses, 500 expected loss a year, half the term left.
+        let lines = [TransferLine { premium: 1_000, expected_annual_loss: 500.0, unexpired_fraction: 0.5 }];
+        let v = value_portfolio(&lines, 0.3, &CONFIG);
+        assert_eq!(v.unearned_premium, 350);
+        assert_eq!(v.expected_losses, 250);
+        assert_eq!(v.renewal_premium, 1_000);
+        assert_eq!(v.franchise_value, 400, "(700 − 500) × 2");
+        assert_eq!(v.price, 150);
+    }
+
+    #[test]
+    fn loss_making_book_costs_the_seller_its_unexpired_losses() {
+        let lines = [
+            TransferLine { premium: 1_000, expected_annual_loss: 900.0, unexpired_fraction: 1.0 },
+            TransferLine { premium: 500, expected_annual_loss: 450.0, unexpired_fraction: 0.2 },
+        ];
+        let v = value_portfolio(&lines, 0.3, &CONFIG);
+        assert_eq!(v.franchise_value, 0, "no renewal value below break-even");
+        assert_eq!(v.expected_losses, 990);
+        assert_eq!(v.price, -990);
+    }
+}
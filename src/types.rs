@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -12,6 +14,12 @@ pub struct InsurerId(pub u64);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PolicyId(pub u64);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BrokerId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ClaimId(pub u64);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Year(pub u32);
 
@@ -43,18 +51,102 @@ impl Day {
     }
 }
 
+/// A territory, as its index into the run's [`TerritoryTable`]. Two bytes and `Copy`; the
+/// names are logged once, in `RunMetadata.territories`, and every event carries only the id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Territory(pub u16);
+
+impl std::fmt::Display for Territory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// One run's territory names, indexed by [`Territory`]. Built from the run's config
+/// (`SimulationConfig::territory_table`) and recorded in `RunMetadata.territories`, so ids are
+/// stable within a run and mean nothing across runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct TerritoryTable {
+    names: Vec<String>,
+    ids: HashMap<String, Territory>,
+}
+
+impl TerritoryTable {
+    /// Most names a table holds: one per `u16` id.
+    pub const CAPACITY: usize = u16::MAX as usize + 1;
+
+    /// A table interning `names` in order (repeats share the first id; names past
+    /// [`Self::CAPACITY`] are dropped).
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Self {
+        let mut table = Self::default();
+        for name in names {
+            table.intern(name.as_ref());
+        }
+        table
+    }
+
+    /// The id of `name`, assigning the next one on first sight. None once the table is full.
+    pub fn intern(&mut self, name: &str) -> Option<Territory> {
+        if let Some(&id) = self.ids.get(name) {
+            return Some(id);
+        }
+        let id = Territory(u16::try_from(self.names.len()).ok()?);
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        Some(id)
+    }
+
+    /// The id of `name`, if interned.
+    pub fn get(&self, name: &str) -> Option<Territory> {
+        self.ids.get(name).copied()
+    }
+
+    /// The name behind `territory`, if it is one of this table's ids.
+    pub fn name(&self, territory: Territory) -> Option<&str> {
+        self.names.get(territory.0 as usize).map(String::as_str)
+    }
+
+    /// Every name, indexed by id.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl From<Vec<String>> for TerritoryTable {
+    fn from(names: Vec<String>) -> Self {
+        TerritoryTable::new(&names)
+    }
+}
+
+impl From<TerritoryTable> for Vec<String> {
+    fn from(table: TerritoryTable) -> Self {
+        table.names
+    }
+}
+
 /// Mutable per-year accumulator for premium and claims.
 /// Held by agents to track year-to-date financials; reset at each YearEnd.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct YearAccumulator {
     /// Gross premium written (cents).
     pub premium: u64,
     /// Total claims paid, all perils (cents).
     pub total_claims: u64,
-    /// Attritional claims paid (cents).
+    /// Non-cat (attritional and large-loss) claims paid (cents): the experience-rated burning cost.
     pub attritional_claims: u64,
     /// Sum insured written (cents). Used as EWMA denominator.
     pub exposure: u64,
+    /// Investment return credited to capital (cents; negative in a down year).
+    pub investment_income: i64,
 }
 
 impl YearAccumulator {